@@ -0,0 +1,417 @@
+//! Accounts of online services registered on the device, shared between the apps
+//! syncing their data.
+//!
+//! Apps can only insert and modify their own accounts, declared as an account provider in
+//! their manifest. Requires the `http://tizen.org/privilege/account.read` privilege, and
+//! `http://tizen.org/privilege/account.write` to modify them.
+
+use crate::error::{check, Error, Result};
+use crate::rutin::{abort_on_panic, borrow_str, cstring, pin_callback, take_string};
+use rutin_tizen_sys::{
+    account_capability_state_e, account_capability_state_e_ACCOUNT_CAPABILITY_DISABLED,
+    account_capability_state_e_ACCOUNT_CAPABILITY_ENABLED, account_create,
+    account_delete_from_db_by_id, account_destroy, account_foreach_account_from_db,
+    account_get_account_id, account_get_capability_all, account_get_display_name,
+    account_get_email_address, account_get_icon_path, account_get_package_name, account_get_secret,
+    account_get_sync_support, account_get_user_name, account_h, account_insert_to_db,
+    account_query_account_by_account_id, account_query_account_by_capability_type,
+    account_secrecy_state_e, account_secrecy_state_e_ACCOUNT_SECRECY_INVISIBLE,
+    account_secrecy_state_e_ACCOUNT_SECRECY_VISIBLE, account_set_capability,
+    account_set_display_name, account_set_email_address, account_set_icon_path, account_set_secret,
+    account_set_sync_support, account_set_user_name, account_subscribe_create, account_subscribe_h,
+    account_subscribe_notification, account_sync_state_e,
+    account_sync_state_e_ACCOUNT_SYNC_NOT_SUPPORT, account_sync_state_e_ACCOUNT_SYNC_STATUS_IDLE,
+    account_sync_state_e_ACCOUNT_SYNC_STATUS_OFF, account_sync_state_e_ACCOUNT_SYNC_STATUS_RUNNING,
+    account_unsubscribe_notification, account_update_to_db_by_id, ACCOUNT_NOTI_NAME_DELETE,
+    ACCOUNT_NOTI_NAME_INSERT, ACCOUNT_NOTI_NAME_UPDATE,
+};
+use std::collections::BTreeMap;
+use std::ffi::CStr;
+use std::os::raw::{c_char, c_int, c_void};
+use std::pin::Pin;
+use std::ptr::null_mut;
+
+/// The capabilities defined by the platform, apps can define their own.
+pub mod capability {
+    pub const CONTACT: &str = "http://tizen.org/account/capability/contact";
+    pub const CALENDAR: &str = "http://tizen.org/account/capability/calendar";
+    pub const PHOTO: &str = "http://tizen.org/account/capability/photo";
+    pub const VIDEO: &str = "http://tizen.org/account/capability/video";
+    pub const EMAIL: &str = "http://tizen.org/account/capability/email";
+    pub const POST: &str = "http://tizen.org/account/capability/post";
+    pub const VOIP: &str = "http://tizen.org/account/capability/voip";
+    pub const SAMSUNG_APPS: &str = "http://tizen.org/account/capability/samsungapps";
+    pub const TASK: &str = "http://tizen.org/account/capability/task";
+    pub const MOBILE_TRACKER: &str = "http://tizen.org/account/capability/mobiletracker";
+    pub const S_NOTE: &str = "http://tizen.org/account/capability/s-note";
+    pub const GALLERY: &str = "http://tizen.org/account/capability/gallery";
+    pub const MEMO: &str = "http://tizen.org/account/capability/memo";
+    pub const CHAT: &str = "http://tizen.org/account/capability/chat";
+    pub const BOOKMARK: &str = "http://tizen.org/account/capability/bookmark";
+    pub const SYNCHRONISE: &str = "http://tizen.org/account/capability/synchronise";
+    pub const TAG: &str = "http://tizen.org/account/capability/tag";
+    pub const DATA_CLOUD: &str = "http://tizen.org/account/capability/datacloud";
+}
+
+/// Whether an account is shown in the account settings.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Secrecy {
+    Visible,
+    Invisible,
+}
+
+impl From<Secrecy> for account_secrecy_state_e {
+    fn from(secrecy: Secrecy) -> account_secrecy_state_e {
+        match secrecy {
+            Secrecy::Visible => account_secrecy_state_e_ACCOUNT_SECRECY_VISIBLE,
+            Secrecy::Invisible => account_secrecy_state_e_ACCOUNT_SECRECY_INVISIBLE,
+        }
+    }
+}
+
+impl From<account_secrecy_state_e> for Secrecy {
+    fn from(secrecy: account_secrecy_state_e) -> Secrecy {
+        match secrecy {
+            account_secrecy_state_e_ACCOUNT_SECRECY_INVISIBLE => Secrecy::Invisible,
+            _ => Secrecy::Visible,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SyncState {
+    NotSupported,
+    /// Supported but turned off by the user.
+    Off,
+    Idle,
+    Running,
+}
+
+impl From<SyncState> for account_sync_state_e {
+    fn from(state: SyncState) -> account_sync_state_e {
+        match state {
+            SyncState::NotSupported => account_sync_state_e_ACCOUNT_SYNC_NOT_SUPPORT,
+            SyncState::Off => account_sync_state_e_ACCOUNT_SYNC_STATUS_OFF,
+            SyncState::Idle => account_sync_state_e_ACCOUNT_SYNC_STATUS_IDLE,
+            SyncState::Running => account_sync_state_e_ACCOUNT_SYNC_STATUS_RUNNING,
+        }
+    }
+}
+
+impl From<account_sync_state_e> for SyncState {
+    fn from(state: account_sync_state_e) -> SyncState {
+        match state {
+            account_sync_state_e_ACCOUNT_SYNC_STATUS_OFF => SyncState::Off,
+            account_sync_state_e_ACCOUNT_SYNC_STATUS_IDLE => SyncState::Idle,
+            account_sync_state_e_ACCOUNT_SYNC_STATUS_RUNNING => SyncState::Running,
+            _ => SyncState::NotSupported,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Account {
+    /// 0 until inserted.
+    pub id: i32,
+    pub user_name: Option<String>,
+    pub display_name: Option<String>,
+    pub email_address: Option<String>,
+    pub icon_path: Option<String>,
+    /// The app owning the account, set when inserting.
+    pub package_name: Option<String>,
+    pub secrecy: Secrecy,
+    pub sync_state: SyncState,
+    /// Whether each capability, such as [`capability::CONTACT`], is enabled.
+    pub capabilities: BTreeMap<String, bool>,
+}
+
+impl Default for Account {
+    fn default() -> Account {
+        Account {
+            id: 0,
+            user_name: None,
+            display_name: None,
+            email_address: None,
+            icon_path: None,
+            package_name: None,
+            secrecy: Secrecy::Visible,
+            sync_state: SyncState::NotSupported,
+            capabilities: BTreeMap::new(),
+        }
+    }
+}
+
+type StrGetter = unsafe extern "C" fn(account_h, *mut *mut c_char) -> c_int;
+type StrSetter = unsafe extern "C" fn(account_h, *const c_char) -> c_int;
+
+impl Account {
+    unsafe fn read(account: account_h) -> Result<Account> {
+        let mut id = 0;
+        check(account_get_account_id(account, &mut id))?;
+
+        let mut secrecy = account_secrecy_state_e_ACCOUNT_SECRECY_VISIBLE;
+        check(account_get_secret(account, &mut secrecy))?;
+
+        let mut sync_state = account_sync_state_e_ACCOUNT_SYNC_NOT_SUPPORT;
+        check(account_get_sync_support(account, &mut sync_state))?;
+
+        let mut capabilities = BTreeMap::<String, bool>::new();
+        check(account_get_capability_all(
+            account,
+            Some(capability_handler),
+            &mut capabilities as *mut BTreeMap<String, bool> as *mut c_void,
+        ))?;
+
+        Ok(Account {
+            id,
+            user_name: get_str(account, account_get_user_name),
+            display_name: get_str(account, account_get_display_name),
+            email_address: get_str(account, account_get_email_address),
+            icon_path: get_str(account, account_get_icon_path),
+            package_name: get_str(account, account_get_package_name),
+            secrecy: secrecy.into(),
+            sync_state: sync_state.into(),
+            capabilities,
+        })
+    }
+
+    unsafe fn write(&self, account: account_h) -> Result<()> {
+        set_str(account, account_set_user_name, self.user_name.as_deref())?;
+        set_str(
+            account,
+            account_set_display_name,
+            self.display_name.as_deref(),
+        )?;
+        set_str(
+            account,
+            account_set_email_address,
+            self.email_address.as_deref(),
+        )?;
+        set_str(account, account_set_icon_path, self.icon_path.as_deref())?;
+        check(account_set_secret(account, self.secrecy.into()))?;
+        check(account_set_sync_support(account, self.sync_state.into()))?;
+
+        for (capability, &enabled) in &self.capabilities {
+            let capability = cstring(capability.as_str())?;
+            let state = if enabled {
+                account_capability_state_e_ACCOUNT_CAPABILITY_ENABLED
+            } else {
+                account_capability_state_e_ACCOUNT_CAPABILITY_DISABLED
+            };
+
+            check(account_set_capability(account, capability.as_ptr(), state))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Inserts an account owned by this app, returning its id.
+pub fn insert_account(account: &Account) -> Result<i32> {
+    let raw = RawAccount::new()?;
+    unsafe { account.write(raw.handle) }?;
+
+    let mut id = 0;
+    check(unsafe { account_insert_to_db(raw.handle, &mut id) })?;
+
+    Ok(id)
+}
+
+pub fn get_account(id: i32) -> Result<Account> {
+    let mut raw = RawAccount::new()?;
+    check(unsafe { account_query_account_by_account_id(id, &mut raw.handle) })?;
+
+    unsafe { Account::read(raw.handle) }
+}
+
+/// Saves the changes to an account owned by this app.
+pub fn update_account(account: &Account) -> Result<()> {
+    let mut raw = RawAccount::new()?;
+    check(unsafe { account_query_account_by_account_id(account.id, &mut raw.handle) })?;
+    unsafe { account.write(raw.handle) }?;
+
+    check(unsafe { account_update_to_db_by_id(raw.handle, account.id) })
+}
+
+pub fn delete_account(id: i32) -> Result<()> {
+    check(unsafe { account_delete_from_db_by_id(id) })
+}
+
+pub fn all_accounts() -> Result<Vec<Account>> {
+    let mut accounts = Vec::<Account>::new();
+
+    match check(unsafe {
+        account_foreach_account_from_db(
+            Some(account_handler),
+            &mut accounts as *mut Vec<Account> as *mut c_void,
+        )
+    }) {
+        Err(Error::NoData) => {}
+        result => result?,
+    }
+
+    Ok(accounts)
+}
+
+/// The accounts having `capability`, enabled or not.
+pub fn accounts_with_capability(capability: &str) -> Result<Vec<Account>> {
+    let capability = cstring(capability)?;
+    let mut accounts = Vec::<Account>::new();
+
+    match check(unsafe {
+        account_query_account_by_capability_type(
+            Some(account_handler),
+            capability.as_ptr(),
+            &mut accounts as *mut Vec<Account> as *mut c_void,
+        )
+    }) {
+        Err(Error::NoData) => {}
+        result => result?,
+    }
+
+    Ok(accounts)
+}
+
+struct RawAccount {
+    handle: account_h,
+}
+
+impl RawAccount {
+    fn new() -> Result<RawAccount> {
+        let mut handle = null_mut();
+        check(unsafe { account_create(&mut handle) })?;
+
+        Ok(Self { handle })
+    }
+}
+
+impl Drop for RawAccount {
+    fn drop(&mut self) {
+        unsafe {
+            account_destroy(self.handle);
+        }
+    }
+}
+
+unsafe fn get_str(account: account_h, get: StrGetter) -> Option<String> {
+    let mut value = null_mut();
+    check(get(account, &mut value)).ok()?;
+
+    take_string(value).filter(|value| !value.is_empty())
+}
+
+unsafe fn set_str(account: account_h, set: StrSetter, value: Option<&str>) -> Result<()> {
+    match value {
+        Some(value) => {
+            let value = cstring(value)?;
+            check(set(account, value.as_ptr()))
+        }
+        None => Ok(()),
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AccountEvent {
+    Inserted(i32),
+    Updated(i32),
+    Deleted(i32),
+}
+
+type AccountEventFn = Box<dyn FnMut(AccountEvent)>;
+
+/// Calls a callback whenever an account is inserted, updated or deleted, until dropped.
+pub struct AccountListener {
+    handle: account_subscribe_h,
+    _callback_fn: Pin<Box<AccountEventFn>>,
+}
+
+impl AccountListener {
+    pub fn new<F>(callback_fn: F) -> Result<AccountListener>
+    where
+        F: FnMut(AccountEvent),
+        F: 'static,
+    {
+        let mut handle = null_mut();
+        check(unsafe { account_subscribe_create(&mut handle) })?;
+
+        let (callback_fn, data) = pin_callback::<dyn FnMut(AccountEvent)>(Box::new(callback_fn));
+        check(unsafe { account_subscribe_notification(handle, Some(event_handler), data) })?;
+
+        Ok(Self {
+            handle,
+            _callback_fn: callback_fn,
+        })
+    }
+}
+
+impl Drop for AccountListener {
+    fn drop(&mut self) {
+        unsafe {
+            // also destroys the handle
+            account_unsubscribe_notification(self.handle);
+        }
+    }
+}
+
+extern "C" fn account_handler(account: account_h, user_data: *mut c_void) -> bool {
+    abort_on_panic(|| {
+        let accounts = unsafe { &mut *(user_data as *mut Vec<Account>) };
+
+        if let Ok(account) = unsafe { Account::read(account) } {
+            accounts.push(account);
+        }
+
+        true
+    })
+}
+
+extern "C" fn capability_handler(
+    capability_type: *const c_char,
+    capability_value: account_capability_state_e,
+    user_data: *mut c_void,
+) -> bool {
+    abort_on_panic(|| {
+        let capabilities = unsafe { &mut *(user_data as *mut BTreeMap<String, bool>) };
+
+        if let Some(capability) = unsafe { borrow_str(capability_type) } {
+            capabilities.insert(
+                capability.into_owned(),
+                capability_value == account_capability_state_e_ACCOUNT_CAPABILITY_ENABLED,
+            );
+        }
+
+        true
+    })
+}
+
+extern "C" fn event_handler(
+    event_type: *const c_char,
+    account_id: c_int,
+    user_data: *mut c_void,
+) -> bool {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut AccountEventFn) };
+
+        if event_type.is_null() {
+            return true;
+        }
+
+        // the name may carry a suffix, such as the capability that changed
+        let event_type = unsafe { CStr::from_ptr(event_type) }.to_bytes();
+        let is = |name: &[u8]| event_type.starts_with(&name[..name.len() - 1]);
+
+        if is(ACCOUNT_NOTI_NAME_INSERT) {
+            closure(AccountEvent::Inserted(account_id));
+        } else if is(ACCOUNT_NOTI_NAME_UPDATE) {
+            closure(AccountEvent::Updated(account_id));
+        } else if is(ACCOUNT_NOTI_NAME_DELETE) {
+            closure(AccountEvent::Deleted(account_id));
+        }
+
+        true
+    })
+}
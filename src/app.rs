@@ -1,9 +1,13 @@
 use crate::rutin::rutin_debug;
-use rutin_tizen_sys::{ui_app_lifecycle_callback_s, ui_app_main};
+use rutin_tizen_sys::{
+    app_get_data_path, app_get_resource_path, ui_app_lifecycle_callback_s, ui_app_main,
+};
 use std::env::args_os;
+use std::ffi::{CStr, OsStr};
 use std::marker::Sized;
 use std::os::raw::{c_char, c_int, c_void};
 use std::os::unix::ffi::OsStrExt;
+use std::path::PathBuf;
 use std::ptr::null_mut;
 
 pub trait UIApp: Sized {
@@ -64,3 +68,25 @@ extern "C" fn app_resume<T: UIApp>(data: *mut c_void) {
     let app = unsafe { &mut *(data as *mut T) };
     app.resume()
 }
+
+/// Returns the application's read-only resource directory (`res/`), where
+/// compiled EDC layouts and images are installed.
+pub fn get_resource_path() -> Option<PathBuf> {
+    owned_path(unsafe { app_get_resource_path() })
+}
+
+/// Returns the application's private writable data directory.
+pub fn get_data_path() -> Option<PathBuf> {
+    owned_path(unsafe { app_get_data_path() })
+}
+
+fn owned_path(ptr: *mut c_char) -> Option<PathBuf> {
+    if ptr.is_null() {
+        return None;
+    }
+
+    let path = PathBuf::from(OsStr::from_bytes(unsafe { CStr::from_ptr(ptr) }.to_bytes()));
+    unsafe { libc::free(ptr as *mut c_void) };
+
+    Some(path)
+}
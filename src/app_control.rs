@@ -0,0 +1,214 @@
+//! Launch requests to other applications, described by an operation, a URI, a MIME type
+//! and extra data.
+//!
+//! With the `async` feature, the reply of the launched application can be awaited on the
+//! main loop.
+
+use crate::error::{check, last_error, Result};
+use crate::rutin::{abort_on_panic, cstring, take_string};
+#[cfg(feature = "async")]
+use crate::stream::Reply;
+use rutin_tizen_sys::{
+    app_control_add_extra_data, app_control_clone, app_control_create, app_control_destroy,
+    app_control_get_app_id, app_control_get_extra_data, app_control_get_mime,
+    app_control_get_operation, app_control_get_uri, app_control_h, app_control_result_e,
+    app_control_result_e_APP_CONTROL_RESULT_APP_STARTED,
+    app_control_result_e_APP_CONTROL_RESULT_CANCELED,
+    app_control_result_e_APP_CONTROL_RESULT_SUCCEEDED, app_control_send_launch_request,
+    app_control_set_app_id, app_control_set_mime, app_control_set_operation, app_control_set_uri,
+};
+use std::os::raw::{c_char, c_int, c_void};
+use std::ptr::null_mut;
+
+pub const OPERATION_DEFAULT: &str = "http://tizen.org/appcontrol/operation/default";
+pub const OPERATION_VIEW: &str = "http://tizen.org/appcontrol/operation/view";
+pub const OPERATION_PICK: &str = "http://tizen.org/appcontrol/operation/pick";
+pub const OPERATION_SHARE: &str = "http://tizen.org/appcontrol/operation/share";
+pub const OPERATION_SETTING: &str = "http://tizen.org/appcontrol/operation/setting";
+
+/// How the launched application answered.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LaunchResult {
+    Succeeded,
+    Failed,
+    Canceled,
+}
+
+impl From<app_control_result_e> for LaunchResult {
+    fn from(result: app_control_result_e) -> LaunchResult {
+        match result {
+            app_control_result_e_APP_CONTROL_RESULT_SUCCEEDED => LaunchResult::Succeeded,
+            app_control_result_e_APP_CONTROL_RESULT_CANCELED => LaunchResult::Canceled,
+            _ => LaunchResult::Failed,
+        }
+    }
+}
+
+type StringGetter = unsafe extern "C" fn(app_control_h, *mut *mut c_char) -> c_int;
+
+/// A launch request, or the reply of a launched application.
+pub struct AppControl {
+    handle: app_control_h,
+}
+
+impl AppControl {
+    pub fn new() -> Result<AppControl> {
+        let mut handle = null_mut();
+        check(unsafe { app_control_create(&mut handle) })?;
+
+        Ok(Self { handle })
+    }
+
+    /// Copies a handle owned by the platform, such as a reply.
+    fn clone_from_raw(handle: app_control_h) -> Result<AppControl> {
+        let mut clone = null_mut();
+        check(unsafe { app_control_clone(&mut clone, handle) })?;
+
+        if clone.is_null() {
+            Err(last_error())
+        } else {
+            Ok(Self { handle: clone })
+        }
+    }
+
+    fn get_string(&self, get: StringGetter) -> Result<Option<String>> {
+        let mut value = null_mut();
+        check(unsafe { get(self.handle, &mut value) })?;
+
+        Ok(unsafe { take_string(value) })
+    }
+
+    /// One of the `OPERATION_*` constants, or an operation defined by an application.
+    pub fn set_operation(&mut self, operation: &str) -> Result<()> {
+        let operation = cstring(operation)?;
+
+        check(unsafe { app_control_set_operation(self.handle, operation.as_ptr()) })
+    }
+
+    pub fn get_operation(&self) -> Result<Option<String>> {
+        self.get_string(app_control_get_operation)
+    }
+
+    pub fn set_uri(&mut self, uri: &str) -> Result<()> {
+        let uri = cstring(uri)?;
+
+        check(unsafe { app_control_set_uri(self.handle, uri.as_ptr()) })
+    }
+
+    pub fn get_uri(&self) -> Result<Option<String>> {
+        self.get_string(app_control_get_uri)
+    }
+
+    pub fn set_mime(&mut self, mime: &str) -> Result<()> {
+        let mime = cstring(mime)?;
+
+        check(unsafe { app_control_set_mime(self.handle, mime.as_ptr()) })
+    }
+
+    pub fn get_mime(&self) -> Result<Option<String>> {
+        self.get_string(app_control_get_mime)
+    }
+
+    /// Launches this application explicitly, instead of any application handling the
+    /// operation.
+    pub fn set_app_id(&mut self, app_id: &str) -> Result<()> {
+        let app_id = cstring(app_id)?;
+
+        check(unsafe { app_control_set_app_id(self.handle, app_id.as_ptr()) })
+    }
+
+    pub fn get_app_id(&self) -> Result<Option<String>> {
+        self.get_string(app_control_get_app_id)
+    }
+
+    pub fn add_extra_data(&mut self, key: &str, value: &str) -> Result<()> {
+        let key = cstring(key)?;
+        let value = cstring(value)?;
+
+        check(unsafe { app_control_add_extra_data(self.handle, key.as_ptr(), value.as_ptr()) })
+    }
+
+    /// Fails with [`Error::Other`](crate::Error::Other) if `key` is missing.
+    pub fn get_extra_data(&self, key: &str) -> Result<String> {
+        let key = cstring(key)?;
+
+        let mut value = null_mut();
+        check(unsafe { app_control_get_extra_data(self.handle, key.as_ptr(), &mut value) })?;
+
+        Ok(unsafe { take_string(value) }.unwrap_or_default())
+    }
+
+    /// Launches the matching application, without waiting for a reply.
+    pub fn send_launch_request(&self) -> Result<()> {
+        check(unsafe { app_control_send_launch_request(self.handle, None, null_mut()) })
+    }
+
+    /// Launches the matching application, `callback_fn` is called with its reply once it
+    /// answers or terminates.
+    pub fn send_launch_request_with_reply<F>(&self, callback_fn: F) -> Result<()>
+    where
+        F: FnOnce(LaunchResult, Option<AppControl>),
+        F: 'static,
+    {
+        let callback_fn: Box<ReplyFn> = Box::new(callback_fn);
+        let data = Box::into_raw(Box::new(callback_fn));
+
+        let ret = unsafe {
+            app_control_send_launch_request(self.handle, Some(reply_handler), data as *mut c_void)
+        };
+
+        if let Err(e) = check(ret) {
+            drop(unsafe { Box::from_raw(data) });
+            return Err(e);
+        }
+
+        Ok(())
+    }
+
+    /// Launches the matching application, resolving to its reply.
+    #[cfg(feature = "async")]
+    pub fn launch(&self) -> Reply<Result<(LaunchResult, Option<AppControl>)>> {
+        let (sender, reply) = Reply::new();
+
+        match self.send_launch_request_with_reply(move |result, app_control| {
+            sender.send(Ok((result, app_control)))
+        }) {
+            Ok(()) => reply,
+            Err(e) => Reply::ready(Err(e)),
+        }
+    }
+}
+
+impl Drop for AppControl {
+    fn drop(&mut self) {
+        unsafe {
+            app_control_destroy(self.handle);
+        }
+    }
+}
+
+type ReplyFn = dyn FnOnce(LaunchResult, Option<AppControl>);
+
+extern "C" fn reply_handler(
+    _request: app_control_h,
+    reply: app_control_h,
+    result: app_control_result_e,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        // only sent when enabled, the final result follows
+        if result == app_control_result_e_APP_CONTROL_RESULT_APP_STARTED {
+            return;
+        }
+
+        let closure = unsafe { Box::from_raw(user_data as *mut Box<ReplyFn>) };
+        let reply = if reply.is_null() {
+            None
+        } else {
+            AppControl::clone_from_raw(reply).ok()
+        };
+
+        closure(LaunchResult::from(result), reply);
+    })
+}
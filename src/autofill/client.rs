@@ -0,0 +1,251 @@
+//! The client side, for apps and IMEs with forms to fill.
+
+use super::{AuthInfo, ErrorInfo, FillResponse, ViewInfo};
+use crate::error::{check, Result};
+use crate::rutin::{abort_on_panic, pin_callback};
+use rutin_tizen_sys::{
+    autofill_auth_info_h, autofill_auth_info_request, autofill_auth_info_set_received_cb,
+    autofill_auth_info_unset_received_cb, autofill_cancel_fill_request, autofill_commit,
+    autofill_connect, autofill_connection_status_e,
+    autofill_connection_status_e_AUTOFILL_CONNECTION_STATUS_CONNECTED,
+    autofill_connection_status_e_AUTOFILL_CONNECTION_STATUS_REJECTED, autofill_create,
+    autofill_destroy, autofill_error_info_h, autofill_error_info_set_received_cb,
+    autofill_error_info_unset_received_cb, autofill_fill_request, autofill_fill_response_h,
+    autofill_fill_response_set_received_cb, autofill_fill_response_unset_received_cb, autofill_h,
+};
+use std::os::raw::c_void;
+use std::pin::Pin;
+use std::ptr::null_mut;
+
+/// The state of the connection to the autofill daemon.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ConnectionStatus {
+    Connected,
+    Disconnected,
+    Rejected,
+}
+
+impl From<autofill_connection_status_e> for ConnectionStatus {
+    fn from(status: autofill_connection_status_e) -> ConnectionStatus {
+        match status {
+            autofill_connection_status_e_AUTOFILL_CONNECTION_STATUS_CONNECTED => {
+                ConnectionStatus::Connected
+            }
+            autofill_connection_status_e_AUTOFILL_CONNECTION_STATUS_REJECTED => {
+                ConnectionStatus::Rejected
+            }
+            _ => ConnectionStatus::Disconnected,
+        }
+    }
+}
+
+type ConnectionFn = Box<dyn FnMut(ConnectionStatus)>;
+type AuthInfoFn = Box<dyn FnMut(Result<AuthInfo>)>;
+type FillResponseFn = Box<dyn FnMut(Result<FillResponse>)>;
+type ErrorInfoFn = Box<dyn FnMut(Result<ErrorInfo>)>;
+
+/// A connection to the autofill daemon, forwarding the requests to the selected service.
+pub struct Autofill {
+    handle: autofill_h,
+    _connection_fn: Pin<Box<ConnectionFn>>,
+    auth_info_fn: Option<Pin<Box<AuthInfoFn>>>,
+    fill_response_fn: Option<Pin<Box<FillResponseFn>>>,
+    error_info_fn: Option<Pin<Box<ErrorInfoFn>>>,
+}
+
+impl Autofill {
+    /// Connects to the daemon, `callback_fn` is called when the connection is established
+    /// or lost. Requests can only be made once connected.
+    pub fn new<F>(callback_fn: F) -> Result<Autofill>
+    where
+        F: FnMut(ConnectionStatus),
+        F: 'static,
+    {
+        let mut handle = null_mut();
+        check(unsafe { autofill_create(&mut handle) })?;
+
+        let (connection_fn, data) =
+            pin_callback::<dyn FnMut(ConnectionStatus)>(Box::new(callback_fn));
+
+        if let Err(e) = check(unsafe { autofill_connect(handle, Some(connection_handler), data) }) {
+            unsafe { autofill_destroy(handle) };
+            return Err(e);
+        }
+
+        Ok(Self {
+            handle,
+            _connection_fn: connection_fn,
+            auth_info_fn: None,
+            fill_response_fn: None,
+            error_info_fn: None,
+        })
+    }
+
+    /// Asks the service whether it has data for `view`, delivered to the auth info callback.
+    pub fn request_auth_info(&self, view: &ViewInfo) -> Result<()> {
+        let view = view.to_raw()?;
+
+        check(unsafe { autofill_auth_info_request(self.handle, view.handle) })
+    }
+
+    /// Calls `callback_fn` with the answers to [`request_auth_info`](Self::request_auth_info),
+    /// replacing the previous callback.
+    pub fn set_auth_info_received_cb<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(Result<AuthInfo>),
+        F: 'static,
+    {
+        let (callback_fn, data) =
+            pin_callback::<dyn FnMut(Result<AuthInfo>)>(Box::new(callback_fn));
+
+        check(unsafe {
+            autofill_auth_info_set_received_cb(self.handle, Some(auth_info_handler), data)
+        })?;
+        self.auth_info_fn = Some(callback_fn);
+
+        Ok(())
+    }
+
+    pub fn unset_auth_info_received_cb(&mut self) -> Result<()> {
+        check(unsafe { autofill_auth_info_unset_received_cb(self.handle) })?;
+        self.auth_info_fn = None;
+
+        Ok(())
+    }
+
+    /// Asks the service for values for the fields of `view`, delivered to the fill response
+    /// callback.
+    pub fn request_fill(&self, view: &ViewInfo) -> Result<()> {
+        let view = view.to_raw()?;
+
+        check(unsafe { autofill_fill_request(self.handle, view.handle) })
+    }
+
+    pub fn cancel_fill_request(&self, view: &ViewInfo) -> Result<()> {
+        let view = view.to_raw()?;
+
+        check(unsafe { autofill_cancel_fill_request(self.handle, view.handle) })
+    }
+
+    /// Calls `callback_fn` with the answers to [`request_fill`](Self::request_fill),
+    /// replacing the previous callback.
+    pub fn set_fill_response_received_cb<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(Result<FillResponse>),
+        F: 'static,
+    {
+        let (callback_fn, data) =
+            pin_callback::<dyn FnMut(Result<FillResponse>)>(Box::new(callback_fn));
+
+        check(unsafe {
+            autofill_fill_response_set_received_cb(self.handle, Some(fill_response_handler), data)
+        })?;
+        self.fill_response_fn = Some(callback_fn);
+
+        Ok(())
+    }
+
+    pub fn unset_fill_response_received_cb(&mut self) -> Result<()> {
+        check(unsafe { autofill_fill_response_unset_received_cb(self.handle) })?;
+        self.fill_response_fn = None;
+
+        Ok(())
+    }
+
+    /// Sends the values entered by the user in `view`, for the service to save them.
+    pub fn commit(&self, view: &ViewInfo) -> Result<()> {
+        let view = view.to_raw_save()?;
+
+        check(unsafe { autofill_commit(self.handle, view.handle) })
+    }
+
+    /// Calls `callback_fn` when the service fails to handle a request, replacing the previous
+    /// callback.
+    pub fn set_error_info_received_cb<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(Result<ErrorInfo>),
+        F: 'static,
+    {
+        let (callback_fn, data) =
+            pin_callback::<dyn FnMut(Result<ErrorInfo>)>(Box::new(callback_fn));
+
+        check(unsafe {
+            autofill_error_info_set_received_cb(self.handle, Some(error_info_handler), data)
+        })?;
+        self.error_info_fn = Some(callback_fn);
+
+        Ok(())
+    }
+
+    pub fn unset_error_info_received_cb(&mut self) -> Result<()> {
+        check(unsafe { autofill_error_info_unset_received_cb(self.handle) })?;
+        self.error_info_fn = None;
+
+        Ok(())
+    }
+}
+
+impl Drop for Autofill {
+    fn drop(&mut self) {
+        unsafe {
+            if self.auth_info_fn.is_some() {
+                autofill_auth_info_unset_received_cb(self.handle);
+            }
+
+            if self.fill_response_fn.is_some() {
+                autofill_fill_response_unset_received_cb(self.handle);
+            }
+
+            if self.error_info_fn.is_some() {
+                autofill_error_info_unset_received_cb(self.handle);
+            }
+
+            autofill_destroy(self.handle);
+        }
+    }
+}
+
+extern "C" fn connection_handler(
+    _handle: autofill_h,
+    status: autofill_connection_status_e,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut ConnectionFn) };
+        closure(status.into());
+    })
+}
+
+extern "C" fn auth_info_handler(
+    _handle: autofill_h,
+    auth_info: autofill_auth_info_h,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut AuthInfoFn) };
+        closure(unsafe { AuthInfo::from_raw(auth_info) });
+    })
+}
+
+extern "C" fn fill_response_handler(
+    _handle: autofill_h,
+    fill_response: autofill_fill_response_h,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut FillResponseFn) };
+        closure(unsafe { FillResponse::from_raw(fill_response) });
+    })
+}
+
+extern "C" fn error_info_handler(
+    _handle: autofill_h,
+    error_info: autofill_error_info_h,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut ErrorInfoFn) };
+        closure(unsafe { ErrorInfo::from_raw(error_info) });
+    })
+}
@@ -0,0 +1,845 @@
+//! Autofill, the filling of login and address forms with data saved by an autofill service,
+//! since Tizen 5.5.
+//!
+//! Apps and IMEs request fill data with [`Autofill`], services supply it with [`Service`].
+
+pub mod client;
+pub mod service;
+
+pub use client::Autofill;
+pub use service::Service;
+
+use crate::error::{check, Error, Result};
+use crate::rutin::{abort_on_panic, cstring, take_string};
+use rutin_tizen_sys::{
+    autofill_auth_info_create, autofill_auth_info_destroy, autofill_auth_info_get_app_id,
+    autofill_auth_info_get_authentication_needed, autofill_auth_info_get_autofill_data_present,
+    autofill_auth_info_get_service_logo_image_path, autofill_auth_info_get_service_message,
+    autofill_auth_info_get_service_name, autofill_auth_info_get_view_id, autofill_auth_info_h,
+    autofill_auth_info_set_app_id, autofill_auth_info_set_authentication_needed,
+    autofill_auth_info_set_autofill_data_present, autofill_auth_info_set_service_logo_image_path,
+    autofill_auth_info_set_service_message, autofill_auth_info_set_service_name,
+    autofill_auth_info_set_view_id, autofill_error_e, autofill_error_info_create,
+    autofill_error_info_destroy, autofill_error_info_get_app_id,
+    autofill_error_info_get_error_code, autofill_error_info_get_error_message,
+    autofill_error_info_h, autofill_error_info_set_app_id, autofill_error_info_set_error_code,
+    autofill_error_info_set_error_message, autofill_fill_response_add_group,
+    autofill_fill_response_create, autofill_fill_response_destroy,
+    autofill_fill_response_foreach_group, autofill_fill_response_get_app_id,
+    autofill_fill_response_get_view_id, autofill_fill_response_group_add_item,
+    autofill_fill_response_group_create, autofill_fill_response_group_destroy,
+    autofill_fill_response_group_foreach_item, autofill_fill_response_group_h,
+    autofill_fill_response_h, autofill_fill_response_item_create,
+    autofill_fill_response_item_destroy, autofill_fill_response_item_get_autofill_hint,
+    autofill_fill_response_item_get_id, autofill_fill_response_item_get_presentation_text,
+    autofill_fill_response_item_get_value, autofill_fill_response_item_h,
+    autofill_fill_response_item_set_autofill_hint, autofill_fill_response_item_set_id,
+    autofill_fill_response_item_set_presentation_text, autofill_fill_response_item_set_value,
+    autofill_fill_response_set_app_id, autofill_fill_response_set_view_id, autofill_hint_e,
+    autofill_hint_e_AUTOFILL_HINT_CREDIT_CARD_EXPIRATION_DATE,
+    autofill_hint_e_AUTOFILL_HINT_CREDIT_CARD_EXPIRATION_DAY,
+    autofill_hint_e_AUTOFILL_HINT_CREDIT_CARD_EXPIRATION_MONTH,
+    autofill_hint_e_AUTOFILL_HINT_CREDIT_CARD_EXPIRATION_YEAR,
+    autofill_hint_e_AUTOFILL_HINT_CREDIT_CARD_NUMBER,
+    autofill_hint_e_AUTOFILL_HINT_CREDIT_CARD_SECURITY_CODE,
+    autofill_hint_e_AUTOFILL_HINT_EMAIL_ADDRESS, autofill_hint_e_AUTOFILL_HINT_ID,
+    autofill_hint_e_AUTOFILL_HINT_NAME, autofill_hint_e_AUTOFILL_HINT_NONE,
+    autofill_hint_e_AUTOFILL_HINT_PASSWORD, autofill_hint_e_AUTOFILL_HINT_PHONE,
+    autofill_hint_e_AUTOFILL_HINT_POSTAL_ADDRESS, autofill_hint_e_AUTOFILL_HINT_POSTAL_CODE,
+    autofill_item_create, autofill_item_destroy, autofill_item_get_autofill_hint,
+    autofill_item_get_id, autofill_item_get_label, autofill_item_get_sensitive_data,
+    autofill_item_get_value, autofill_item_h, autofill_item_set_autofill_hint,
+    autofill_item_set_id, autofill_item_set_label, autofill_item_set_sensitive_data,
+    autofill_item_set_value, autofill_save_item_create, autofill_save_item_destroy,
+    autofill_save_item_get_autofill_hint, autofill_save_item_get_id, autofill_save_item_get_label,
+    autofill_save_item_get_sensitive_data, autofill_save_item_get_value, autofill_save_item_h,
+    autofill_save_item_set_autofill_hint, autofill_save_item_set_id, autofill_save_item_set_label,
+    autofill_save_item_set_sensitive_data, autofill_save_item_set_value,
+    autofill_save_view_info_add_item, autofill_save_view_info_create,
+    autofill_save_view_info_destroy, autofill_save_view_info_foreach_item,
+    autofill_save_view_info_get_app_id, autofill_save_view_info_get_view_id,
+    autofill_save_view_info_h, autofill_save_view_info_set_app_id,
+    autofill_save_view_info_set_view_id, autofill_view_info_add_item, autofill_view_info_create,
+    autofill_view_info_destroy, autofill_view_info_foreach_item, autofill_view_info_get_app_id,
+    autofill_view_info_get_view_id, autofill_view_info_h, autofill_view_info_set_app_id,
+    autofill_view_info_set_view_id,
+};
+use std::os::raw::{c_char, c_int, c_void};
+use std::path::PathBuf;
+use std::ptr::null_mut;
+
+/// The kind of data expected by a field.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Hint {
+    None,
+    CreditCardExpirationDate,
+    CreditCardExpirationDay,
+    CreditCardExpirationMonth,
+    CreditCardExpirationYear,
+    CreditCardNumber,
+    CreditCardSecurityCode,
+    EmailAddress,
+    Name,
+    Phone,
+    PostalAddress,
+    PostalCode,
+    Id,
+    Password,
+}
+
+impl From<Hint> for autofill_hint_e {
+    fn from(hint: Hint) -> autofill_hint_e {
+        match hint {
+            Hint::None => autofill_hint_e_AUTOFILL_HINT_NONE,
+            Hint::CreditCardExpirationDate => {
+                autofill_hint_e_AUTOFILL_HINT_CREDIT_CARD_EXPIRATION_DATE
+            }
+            Hint::CreditCardExpirationDay => {
+                autofill_hint_e_AUTOFILL_HINT_CREDIT_CARD_EXPIRATION_DAY
+            }
+            Hint::CreditCardExpirationMonth => {
+                autofill_hint_e_AUTOFILL_HINT_CREDIT_CARD_EXPIRATION_MONTH
+            }
+            Hint::CreditCardExpirationYear => {
+                autofill_hint_e_AUTOFILL_HINT_CREDIT_CARD_EXPIRATION_YEAR
+            }
+            Hint::CreditCardNumber => autofill_hint_e_AUTOFILL_HINT_CREDIT_CARD_NUMBER,
+            Hint::CreditCardSecurityCode => autofill_hint_e_AUTOFILL_HINT_CREDIT_CARD_SECURITY_CODE,
+            Hint::EmailAddress => autofill_hint_e_AUTOFILL_HINT_EMAIL_ADDRESS,
+            Hint::Name => autofill_hint_e_AUTOFILL_HINT_NAME,
+            Hint::Phone => autofill_hint_e_AUTOFILL_HINT_PHONE,
+            Hint::PostalAddress => autofill_hint_e_AUTOFILL_HINT_POSTAL_ADDRESS,
+            Hint::PostalCode => autofill_hint_e_AUTOFILL_HINT_POSTAL_CODE,
+            Hint::Id => autofill_hint_e_AUTOFILL_HINT_ID,
+            Hint::Password => autofill_hint_e_AUTOFILL_HINT_PASSWORD,
+        }
+    }
+}
+
+impl From<autofill_hint_e> for Hint {
+    fn from(hint: autofill_hint_e) -> Hint {
+        match hint {
+            autofill_hint_e_AUTOFILL_HINT_CREDIT_CARD_EXPIRATION_DATE => {
+                Hint::CreditCardExpirationDate
+            }
+            autofill_hint_e_AUTOFILL_HINT_CREDIT_CARD_EXPIRATION_DAY => {
+                Hint::CreditCardExpirationDay
+            }
+            autofill_hint_e_AUTOFILL_HINT_CREDIT_CARD_EXPIRATION_MONTH => {
+                Hint::CreditCardExpirationMonth
+            }
+            autofill_hint_e_AUTOFILL_HINT_CREDIT_CARD_EXPIRATION_YEAR => {
+                Hint::CreditCardExpirationYear
+            }
+            autofill_hint_e_AUTOFILL_HINT_CREDIT_CARD_NUMBER => Hint::CreditCardNumber,
+            autofill_hint_e_AUTOFILL_HINT_CREDIT_CARD_SECURITY_CODE => Hint::CreditCardSecurityCode,
+            autofill_hint_e_AUTOFILL_HINT_EMAIL_ADDRESS => Hint::EmailAddress,
+            autofill_hint_e_AUTOFILL_HINT_NAME => Hint::Name,
+            autofill_hint_e_AUTOFILL_HINT_PHONE => Hint::Phone,
+            autofill_hint_e_AUTOFILL_HINT_POSTAL_ADDRESS => Hint::PostalAddress,
+            autofill_hint_e_AUTOFILL_HINT_POSTAL_CODE => Hint::PostalCode,
+            autofill_hint_e_AUTOFILL_HINT_ID => Hint::Id,
+            autofill_hint_e_AUTOFILL_HINT_PASSWORD => Hint::Password,
+            _ => Hint::None,
+        }
+    }
+}
+
+/// A field of a form.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Item {
+    /// Identifies the field in the view, fill responses refer to it.
+    pub id: String,
+    pub label: Option<String>,
+    pub hint: Hint,
+    /// Whether the value is a secret, like a password, which the service should protect.
+    pub sensitive: bool,
+    /// The text entered by the user, sent when committing the form.
+    pub value: Option<String>,
+}
+
+impl Item {
+    pub fn new(id: &str, hint: Hint) -> Item {
+        Self {
+            id: id.to_string(),
+            label: None,
+            hint,
+            sensitive: false,
+            value: None,
+        }
+    }
+}
+
+/// A form of an app, with the fields to fill.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ViewInfo {
+    pub app_id: String,
+    pub view_id: String,
+    pub items: Vec<Item>,
+}
+
+/// A value for a field, suggested by the service.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FillResponseItem {
+    /// The id of the [`Item`] to fill.
+    pub id: String,
+    /// What to show the user when suggesting the value, e.g. the account name.
+    pub presentation_text: Option<String>,
+    pub value: Option<String>,
+    pub hint: Hint,
+}
+
+/// The values suggested for a form.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FillResponse {
+    pub app_id: String,
+    pub view_id: String,
+    /// Each group is filled at once, e.g. the id and the password of one account.
+    pub groups: Vec<Vec<FillResponseItem>>,
+}
+
+/// Whether the service has data for a form, and how to present it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AuthInfo {
+    pub app_id: String,
+    pub view_id: String,
+    pub data_present: bool,
+    /// Whether the user must authenticate to the service before the form is filled.
+    pub authentication_needed: bool,
+    pub service_name: Option<String>,
+    pub service_message: Option<String>,
+    pub service_logo_image_path: Option<PathBuf>,
+}
+
+/// A failure of the service to handle a request of an app.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ErrorInfo {
+    pub app_id: String,
+    pub error: Error,
+    pub message: Option<String>,
+}
+
+/// The functions shared by the items of forms to fill and of forms to save.
+struct ItemApi<H> {
+    create: unsafe extern "C" fn(*mut H) -> c_int,
+    destroy: unsafe extern "C" fn(H) -> c_int,
+    set_hint: unsafe extern "C" fn(H, autofill_hint_e) -> c_int,
+    get_hint: unsafe extern "C" fn(H, *mut autofill_hint_e) -> c_int,
+    set_id: unsafe extern "C" fn(H, *const c_char) -> c_int,
+    get_id: unsafe extern "C" fn(H, *mut *mut c_char) -> c_int,
+    set_label: unsafe extern "C" fn(H, *const c_char) -> c_int,
+    get_label: unsafe extern "C" fn(H, *mut *mut c_char) -> c_int,
+    set_sensitive: unsafe extern "C" fn(H, bool) -> c_int,
+    get_sensitive: unsafe extern "C" fn(H, *mut bool) -> c_int,
+    set_value: unsafe extern "C" fn(H, *const c_char) -> c_int,
+    get_value: unsafe extern "C" fn(H, *mut *mut c_char) -> c_int,
+}
+
+const ITEM: ItemApi<autofill_item_h> = ItemApi {
+    create: autofill_item_create,
+    destroy: autofill_item_destroy,
+    set_hint: autofill_item_set_autofill_hint,
+    get_hint: autofill_item_get_autofill_hint,
+    set_id: autofill_item_set_id,
+    get_id: autofill_item_get_id,
+    set_label: autofill_item_set_label,
+    get_label: autofill_item_get_label,
+    set_sensitive: autofill_item_set_sensitive_data,
+    get_sensitive: autofill_item_get_sensitive_data,
+    set_value: autofill_item_set_value,
+    get_value: autofill_item_get_value,
+};
+
+const SAVE_ITEM: ItemApi<autofill_save_item_h> = ItemApi {
+    create: autofill_save_item_create,
+    destroy: autofill_save_item_destroy,
+    set_hint: autofill_save_item_set_autofill_hint,
+    get_hint: autofill_save_item_get_autofill_hint,
+    set_id: autofill_save_item_set_id,
+    get_id: autofill_save_item_get_id,
+    set_label: autofill_save_item_set_label,
+    get_label: autofill_save_item_get_label,
+    set_sensitive: autofill_save_item_set_sensitive_data,
+    get_sensitive: autofill_save_item_get_sensitive_data,
+    set_value: autofill_save_item_set_value,
+    get_value: autofill_save_item_get_value,
+};
+
+impl<H: Copy> ItemApi<H> {
+    unsafe fn read(&self, item: H) -> Result<Item> {
+        let string = |get: unsafe extern "C" fn(H, *mut *mut c_char) -> c_int| {
+            let mut value = null_mut();
+            check(get(item, &mut value)).ok()?;
+            take_string(value)
+        };
+
+        let mut id = null_mut();
+        check((self.get_id)(item, &mut id))?;
+
+        let mut hint = autofill_hint_e_AUTOFILL_HINT_NONE;
+        check((self.get_hint)(item, &mut hint))?;
+
+        let mut sensitive = false;
+        check((self.get_sensitive)(item, &mut sensitive))?;
+
+        Ok(Item {
+            id: take_string(id).unwrap_or_default(),
+            label: string(self.get_label),
+            hint: hint.into(),
+            sensitive,
+            value: string(self.get_value),
+        })
+    }
+
+    /// Creates a handle with the fields of `item`, to be added to a view info which takes
+    /// ownership of it.
+    unsafe fn write(&self, item: &Item) -> Result<H> {
+        let mut handle = std::mem::MaybeUninit::uninit();
+        check((self.create)(handle.as_mut_ptr()))?;
+        let handle = handle.assume_init();
+
+        let result: Result<()> = (|| {
+            let id = cstring(&item.id)?;
+            check((self.set_id)(handle, id.as_ptr()))?;
+            check((self.set_hint)(handle, item.hint.into()))?;
+            check((self.set_sensitive)(handle, item.sensitive))?;
+
+            if let Some(label) = &item.label {
+                let label = cstring(label)?;
+                check((self.set_label)(handle, label.as_ptr()))?;
+            }
+
+            if let Some(value) = &item.value {
+                let value = cstring(value)?;
+                check((self.set_value)(handle, value.as_ptr()))?;
+            }
+
+            Ok(())
+        })();
+
+        match result {
+            Ok(()) => Ok(handle),
+            Err(e) => {
+                (self.destroy)(handle);
+                Err(e)
+            }
+        }
+    }
+}
+
+type ViewInfoGetter = unsafe extern "C" fn(autofill_view_info_h, *mut *mut c_char) -> c_int;
+type SaveViewInfoGetter =
+    unsafe extern "C" fn(autofill_save_view_info_h, *mut *mut c_char) -> c_int;
+
+impl ViewInfo {
+    pub fn new(app_id: &str, view_id: &str) -> ViewInfo {
+        Self {
+            app_id: app_id.to_string(),
+            view_id: view_id.to_string(),
+            items: Vec::new(),
+        }
+    }
+
+    unsafe fn from_raw(view_info: autofill_view_info_h) -> Result<ViewInfo> {
+        let string = |get: ViewInfoGetter| {
+            let mut value = null_mut();
+            check(get(view_info, &mut value))?;
+            Ok(take_string(value).unwrap_or_default())
+        };
+
+        let mut items: Vec<Result<Item>> = Vec::new();
+        check(autofill_view_info_foreach_item(
+            view_info,
+            Some(item_handler),
+            &mut items as *mut Vec<Result<Item>> as *mut c_void,
+        ))?;
+
+        Ok(Self {
+            app_id: string(autofill_view_info_get_app_id)?,
+            view_id: string(autofill_view_info_get_view_id)?,
+            items: items.into_iter().collect::<Result<_>>()?,
+        })
+    }
+
+    unsafe fn from_raw_save(view_info: autofill_save_view_info_h) -> Result<ViewInfo> {
+        let string = |get: SaveViewInfoGetter| {
+            let mut value = null_mut();
+            check(get(view_info, &mut value))?;
+            Ok(take_string(value).unwrap_or_default())
+        };
+
+        let mut items: Vec<Result<Item>> = Vec::new();
+        check(autofill_save_view_info_foreach_item(
+            view_info,
+            Some(save_item_handler),
+            &mut items as *mut Vec<Result<Item>> as *mut c_void,
+        ))?;
+
+        Ok(Self {
+            app_id: string(autofill_save_view_info_get_app_id)?,
+            view_id: string(autofill_save_view_info_get_view_id)?,
+            items: items.into_iter().collect::<Result<_>>()?,
+        })
+    }
+
+    fn to_raw(&self) -> Result<RawViewInfo> {
+        let mut handle = null_mut();
+        check(unsafe { autofill_view_info_create(&mut handle) })?;
+        let view_info = RawViewInfo { handle };
+
+        let app_id = cstring(&self.app_id)?;
+        check(unsafe { autofill_view_info_set_app_id(view_info.handle, app_id.as_ptr()) })?;
+
+        let view_id = cstring(&self.view_id)?;
+        check(unsafe { autofill_view_info_set_view_id(view_info.handle, view_id.as_ptr()) })?;
+
+        for item in &self.items {
+            unsafe {
+                let item = ITEM.write(item)?;
+
+                if let Err(e) = check(autofill_view_info_add_item(view_info.handle, item)) {
+                    (ITEM.destroy)(item);
+                    return Err(e);
+                }
+            }
+        }
+
+        Ok(view_info)
+    }
+
+    fn to_raw_save(&self) -> Result<RawSaveViewInfo> {
+        let mut handle = null_mut();
+        check(unsafe { autofill_save_view_info_create(&mut handle) })?;
+        let view_info = RawSaveViewInfo { handle };
+
+        let app_id = cstring(&self.app_id)?;
+        check(unsafe { autofill_save_view_info_set_app_id(view_info.handle, app_id.as_ptr()) })?;
+
+        let view_id = cstring(&self.view_id)?;
+        check(unsafe { autofill_save_view_info_set_view_id(view_info.handle, view_id.as_ptr()) })?;
+
+        for item in &self.items {
+            unsafe {
+                let item = SAVE_ITEM.write(item)?;
+
+                if let Err(e) = check(autofill_save_view_info_add_item(view_info.handle, item)) {
+                    (SAVE_ITEM.destroy)(item);
+                    return Err(e);
+                }
+            }
+        }
+
+        Ok(view_info)
+    }
+}
+
+type ResponseItemGetter =
+    unsafe extern "C" fn(autofill_fill_response_item_h, *mut *mut c_char) -> c_int;
+type ResponseGetter = unsafe extern "C" fn(autofill_fill_response_h, *mut *mut c_char) -> c_int;
+
+impl FillResponseItem {
+    pub fn new(id: &str, value: &str, hint: Hint) -> FillResponseItem {
+        Self {
+            id: id.to_string(),
+            presentation_text: None,
+            value: Some(value.to_string()),
+            hint,
+        }
+    }
+
+    unsafe fn from_raw(item: autofill_fill_response_item_h) -> Result<FillResponseItem> {
+        let string = |get: ResponseItemGetter| {
+            let mut value = null_mut();
+            check(get(item, &mut value)).ok()?;
+            take_string(value)
+        };
+
+        let mut id = null_mut();
+        check(autofill_fill_response_item_get_id(item, &mut id))?;
+
+        let mut hint = autofill_hint_e_AUTOFILL_HINT_NONE;
+        check(autofill_fill_response_item_get_autofill_hint(
+            item, &mut hint,
+        ))?;
+
+        Ok(Self {
+            id: take_string(id).unwrap_or_default(),
+            presentation_text: string(autofill_fill_response_item_get_presentation_text),
+            value: string(autofill_fill_response_item_get_value),
+            hint: hint.into(),
+        })
+    }
+
+    /// Creates a handle owned by the group it is added to.
+    unsafe fn write(&self) -> Result<autofill_fill_response_item_h> {
+        let mut handle = null_mut();
+        check(autofill_fill_response_item_create(&mut handle))?;
+        let item = RawFillResponseItem { handle };
+
+        let id = cstring(&self.id)?;
+        check(autofill_fill_response_item_set_id(item.handle, id.as_ptr()))?;
+        check(autofill_fill_response_item_set_autofill_hint(
+            item.handle,
+            self.hint.into(),
+        ))?;
+
+        if let Some(presentation_text) = &self.presentation_text {
+            let presentation_text = cstring(presentation_text)?;
+            check(autofill_fill_response_item_set_presentation_text(
+                item.handle,
+                presentation_text.as_ptr(),
+            ))?;
+        }
+
+        if let Some(value) = &self.value {
+            let value = cstring(value)?;
+            check(autofill_fill_response_item_set_value(
+                item.handle,
+                value.as_ptr(),
+            ))?;
+        }
+
+        Ok(item.into_raw())
+    }
+}
+
+impl FillResponse {
+    pub fn new(app_id: &str, view_id: &str) -> FillResponse {
+        Self {
+            app_id: app_id.to_string(),
+            view_id: view_id.to_string(),
+            groups: Vec::new(),
+        }
+    }
+
+    unsafe fn from_raw(response: autofill_fill_response_h) -> Result<FillResponse> {
+        let string = |get: ResponseGetter| {
+            let mut value = null_mut();
+            check(get(response, &mut value))?;
+            Ok(take_string(value).unwrap_or_default())
+        };
+
+        let mut groups: Vec<Result<Vec<FillResponseItem>>> = Vec::new();
+        check(autofill_fill_response_foreach_group(
+            response,
+            Some(group_handler),
+            &mut groups as *mut Vec<Result<Vec<FillResponseItem>>> as *mut c_void,
+        ))?;
+
+        Ok(Self {
+            app_id: string(autofill_fill_response_get_app_id)?,
+            view_id: string(autofill_fill_response_get_view_id)?,
+            groups: groups.into_iter().collect::<Result<_>>()?,
+        })
+    }
+
+    fn to_raw(&self) -> Result<RawFillResponse> {
+        let mut handle = null_mut();
+        check(unsafe { autofill_fill_response_create(&mut handle) })?;
+        let response = RawFillResponse { handle };
+
+        let app_id = cstring(&self.app_id)?;
+        check(unsafe { autofill_fill_response_set_app_id(response.handle, app_id.as_ptr()) })?;
+
+        let view_id = cstring(&self.view_id)?;
+        check(unsafe { autofill_fill_response_set_view_id(response.handle, view_id.as_ptr()) })?;
+
+        for items in &self.groups {
+            let mut handle = null_mut();
+            check(unsafe { autofill_fill_response_group_create(&mut handle) })?;
+            let group = RawFillResponseGroup { handle };
+
+            for item in items {
+                unsafe {
+                    let item = item.write()?;
+                    check(autofill_fill_response_group_add_item(group.handle, item))?;
+                }
+            }
+
+            check(unsafe { autofill_fill_response_add_group(response.handle, group.into_raw()) })?;
+        }
+
+        Ok(response)
+    }
+}
+
+type AuthInfoGetter = unsafe extern "C" fn(autofill_auth_info_h, *mut *mut c_char) -> c_int;
+type AuthInfoSetter = unsafe extern "C" fn(autofill_auth_info_h, *const c_char) -> c_int;
+
+impl AuthInfo {
+    unsafe fn from_raw(auth_info: autofill_auth_info_h) -> Result<AuthInfo> {
+        let string = |get: AuthInfoGetter| {
+            let mut value = null_mut();
+            check(get(auth_info, &mut value)).ok()?;
+            take_string(value)
+        };
+
+        let mut data_present = false;
+        check(autofill_auth_info_get_autofill_data_present(
+            auth_info,
+            &mut data_present,
+        ))?;
+
+        let mut authentication_needed = false;
+        check(autofill_auth_info_get_authentication_needed(
+            auth_info,
+            &mut authentication_needed,
+        ))?;
+
+        Ok(Self {
+            app_id: string(autofill_auth_info_get_app_id).unwrap_or_default(),
+            view_id: string(autofill_auth_info_get_view_id).unwrap_or_default(),
+            data_present,
+            authentication_needed,
+            service_name: string(autofill_auth_info_get_service_name),
+            service_message: string(autofill_auth_info_get_service_message),
+            service_logo_image_path: string(autofill_auth_info_get_service_logo_image_path)
+                .map(PathBuf::from),
+        })
+    }
+
+    fn to_raw(&self) -> Result<RawAuthInfo> {
+        let mut handle = null_mut();
+        check(unsafe { autofill_auth_info_create(&mut handle) })?;
+        let auth_info = RawAuthInfo { handle };
+
+        let strings: [(AuthInfoSetter, Option<String>); 5] = [
+            (autofill_auth_info_set_app_id, Some(self.app_id.clone())),
+            (autofill_auth_info_set_view_id, Some(self.view_id.clone())),
+            (
+                autofill_auth_info_set_service_name,
+                self.service_name.clone(),
+            ),
+            (
+                autofill_auth_info_set_service_message,
+                self.service_message.clone(),
+            ),
+            (
+                autofill_auth_info_set_service_logo_image_path,
+                self.service_logo_image_path
+                    .as_ref()
+                    .map(|path| path.to_string_lossy().into_owned()),
+            ),
+        ];
+
+        for (set, value) in strings.iter() {
+            if let Some(value) = value {
+                let value = cstring(value)?;
+                check(unsafe { set(auth_info.handle, value.as_ptr()) })?;
+            }
+        }
+
+        check(unsafe {
+            autofill_auth_info_set_autofill_data_present(auth_info.handle, self.data_present)
+        })?;
+        check(unsafe {
+            autofill_auth_info_set_authentication_needed(
+                auth_info.handle,
+                self.authentication_needed,
+            )
+        })?;
+
+        Ok(auth_info)
+    }
+}
+
+impl ErrorInfo {
+    unsafe fn from_raw(error_info: autofill_error_info_h) -> Result<ErrorInfo> {
+        let mut app_id = null_mut();
+        check(autofill_error_info_get_app_id(error_info, &mut app_id))?;
+
+        let mut code: autofill_error_e = 0;
+        check(autofill_error_info_get_error_code(error_info, &mut code))?;
+
+        let mut message = null_mut();
+        check(autofill_error_info_get_error_message(
+            error_info,
+            &mut message,
+        ))?;
+
+        Ok(Self {
+            app_id: take_string(app_id).unwrap_or_default(),
+            error: Error::from(code as c_int),
+            message: take_string(message),
+        })
+    }
+
+    fn to_raw(&self) -> Result<RawErrorInfo> {
+        let mut handle = null_mut();
+        check(unsafe { autofill_error_info_create(&mut handle) })?;
+        let error_info = RawErrorInfo { handle };
+
+        let app_id = cstring(&self.app_id)?;
+        check(unsafe { autofill_error_info_set_app_id(error_info.handle, app_id.as_ptr()) })?;
+        check(unsafe {
+            autofill_error_info_set_error_code(error_info.handle, self.error.code() as _)
+        })?;
+
+        if let Some(message) = &self.message {
+            let message = cstring(message)?;
+            check(unsafe {
+                autofill_error_info_set_error_message(error_info.handle, message.as_ptr())
+            })?;
+        }
+
+        Ok(error_info)
+    }
+}
+
+struct RawViewInfo {
+    handle: autofill_view_info_h,
+}
+
+impl Drop for RawViewInfo {
+    fn drop(&mut self) {
+        unsafe {
+            autofill_view_info_destroy(self.handle);
+        }
+    }
+}
+
+struct RawSaveViewInfo {
+    handle: autofill_save_view_info_h,
+}
+
+impl Drop for RawSaveViewInfo {
+    fn drop(&mut self) {
+        unsafe {
+            autofill_save_view_info_destroy(self.handle);
+        }
+    }
+}
+
+struct RawFillResponseItem {
+    handle: autofill_fill_response_item_h,
+}
+
+impl RawFillResponseItem {
+    fn into_raw(self) -> autofill_fill_response_item_h {
+        let handle = self.handle;
+        std::mem::forget(self);
+
+        handle
+    }
+}
+
+impl Drop for RawFillResponseItem {
+    fn drop(&mut self) {
+        unsafe {
+            autofill_fill_response_item_destroy(self.handle);
+        }
+    }
+}
+
+struct RawFillResponseGroup {
+    handle: autofill_fill_response_group_h,
+}
+
+impl RawFillResponseGroup {
+    fn into_raw(self) -> autofill_fill_response_group_h {
+        let handle = self.handle;
+        std::mem::forget(self);
+
+        handle
+    }
+}
+
+impl Drop for RawFillResponseGroup {
+    fn drop(&mut self) {
+        unsafe {
+            autofill_fill_response_group_destroy(self.handle);
+        }
+    }
+}
+
+struct RawFillResponse {
+    handle: autofill_fill_response_h,
+}
+
+impl Drop for RawFillResponse {
+    fn drop(&mut self) {
+        unsafe {
+            autofill_fill_response_destroy(self.handle);
+        }
+    }
+}
+
+struct RawAuthInfo {
+    handle: autofill_auth_info_h,
+}
+
+impl Drop for RawAuthInfo {
+    fn drop(&mut self) {
+        unsafe {
+            autofill_auth_info_destroy(self.handle);
+        }
+    }
+}
+
+struct RawErrorInfo {
+    handle: autofill_error_info_h,
+}
+
+impl Drop for RawErrorInfo {
+    fn drop(&mut self) {
+        unsafe {
+            autofill_error_info_destroy(self.handle);
+        }
+    }
+}
+
+extern "C" fn item_handler(item: autofill_item_h, user_data: *mut c_void) -> bool {
+    abort_on_panic(|| {
+        let items = unsafe { &mut *(user_data as *mut Vec<Result<Item>>) };
+        items.push(unsafe { ITEM.read(item) });
+
+        true
+    })
+}
+
+extern "C" fn save_item_handler(item: autofill_save_item_h, user_data: *mut c_void) -> bool {
+    abort_on_panic(|| {
+        let items = unsafe { &mut *(user_data as *mut Vec<Result<Item>>) };
+        items.push(unsafe { SAVE_ITEM.read(item) });
+
+        true
+    })
+}
+
+extern "C" fn group_handler(group: autofill_fill_response_group_h, user_data: *mut c_void) -> bool {
+    abort_on_panic(|| {
+        let groups = unsafe { &mut *(user_data as *mut Vec<Result<Vec<FillResponseItem>>>) };
+
+        let mut items: Vec<Result<FillResponseItem>> = Vec::new();
+        let result = check(unsafe {
+            autofill_fill_response_group_foreach_item(
+                group,
+                Some(response_item_handler),
+                &mut items as *mut Vec<Result<FillResponseItem>> as *mut c_void,
+            )
+        });
+
+        groups.push(result.and_then(|_| items.into_iter().collect()));
+
+        true
+    })
+}
+
+extern "C" fn response_item_handler(
+    item: autofill_fill_response_item_h,
+    user_data: *mut c_void,
+) -> bool {
+    abort_on_panic(|| {
+        let items = unsafe { &mut *(user_data as *mut Vec<Result<FillResponseItem>>) };
+        items.push(unsafe { FillResponseItem::from_raw(item) });
+
+        true
+    })
+}
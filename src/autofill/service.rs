@@ -0,0 +1,260 @@
+//! The service side, for apps saving the data of the user and supplying it to the forms.
+//!
+//! Each request comes with a context id, which the replies must be sent with.
+
+use super::{AuthInfo, ErrorInfo, FillResponse, ViewInfo};
+use crate::error::{check, Error, Result};
+use crate::rutin::{abort_on_panic, pin_callback};
+use rutin_tizen_sys::{
+    autofill_save_view_info_h, autofill_service_deinitialize, autofill_service_initialize,
+    autofill_service_send_auth_info, autofill_service_send_error_info,
+    autofill_service_send_fill_response, autofill_service_set_auth_info_requested_cb,
+    autofill_service_set_cancel_fill_requested_cb, autofill_service_set_committed_cb,
+    autofill_service_set_fill_requested_cb, autofill_service_set_terminate_received_cb,
+    autofill_service_unset_auth_info_requested_cb, autofill_service_unset_cancel_fill_requested_cb,
+    autofill_service_unset_committed_cb, autofill_service_unset_fill_requested_cb,
+    autofill_service_unset_terminate_received_cb, autofill_view_info_h,
+};
+use std::cell::Cell;
+use std::os::raw::{c_int, c_void};
+use std::pin::Pin;
+
+/// Called with the context id of a request and the form it is about.
+type RequestFn = Box<dyn FnMut(i32, Result<ViewInfo>)>;
+type TerminateFn = Box<dyn FnMut()>;
+
+type SetRequestedCb = unsafe extern "C" fn(
+    Option<unsafe extern "C" fn(c_int, autofill_view_info_h, *mut c_void)>,
+    *mut c_void,
+) -> c_int;
+
+thread_local! {
+    static INITIALIZED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// The autofill service of the app, only one can exist at a time.
+pub struct Service {
+    auth_info_requested_fn: Option<Pin<Box<RequestFn>>>,
+    fill_requested_fn: Option<Pin<Box<RequestFn>>>,
+    cancel_fill_requested_fn: Option<Pin<Box<RequestFn>>>,
+    committed_fn: Option<Pin<Box<RequestFn>>>,
+    terminate_received_fn: Option<Pin<Box<TerminateFn>>>,
+}
+
+impl Service {
+    pub fn new() -> Result<Service> {
+        if INITIALIZED.with(|initialized| initialized.get()) {
+            return Err(Error::InvalidOperation);
+        }
+
+        check(unsafe { autofill_service_initialize() })?;
+        INITIALIZED.with(|initialized| initialized.set(true));
+
+        Ok(Self {
+            auth_info_requested_fn: None,
+            fill_requested_fn: None,
+            cancel_fill_requested_fn: None,
+            committed_fn: None,
+            terminate_received_fn: None,
+        })
+    }
+
+    /// Calls `callback_fn` when an app asks whether the service has data for a form,
+    /// replacing the previous callback. Answer with [`send_auth_info`](Self::send_auth_info).
+    pub fn set_auth_info_requested_cb<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(i32, Result<ViewInfo>),
+        F: 'static,
+    {
+        self.auth_info_requested_fn = Some(set_requested_cb(
+            autofill_service_set_auth_info_requested_cb,
+            Box::new(callback_fn),
+        )?);
+
+        Ok(())
+    }
+
+    pub fn unset_auth_info_requested_cb(&mut self) -> Result<()> {
+        check(unsafe { autofill_service_unset_auth_info_requested_cb() })?;
+        self.auth_info_requested_fn = None;
+
+        Ok(())
+    }
+
+    /// Calls `callback_fn` when an app asks for the values of a form, replacing the previous
+    /// callback. Answer with [`send_fill_response`](Self::send_fill_response).
+    pub fn set_fill_requested_cb<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(i32, Result<ViewInfo>),
+        F: 'static,
+    {
+        self.fill_requested_fn = Some(set_requested_cb(
+            autofill_service_set_fill_requested_cb,
+            Box::new(callback_fn),
+        )?);
+
+        Ok(())
+    }
+
+    pub fn unset_fill_requested_cb(&mut self) -> Result<()> {
+        check(unsafe { autofill_service_unset_fill_requested_cb() })?;
+        self.fill_requested_fn = None;
+
+        Ok(())
+    }
+
+    /// Calls `callback_fn` when an app no longer needs the values of a form, replacing the
+    /// previous callback.
+    pub fn set_cancel_fill_requested_cb<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(i32, Result<ViewInfo>),
+        F: 'static,
+    {
+        self.cancel_fill_requested_fn = Some(set_requested_cb(
+            autofill_service_set_cancel_fill_requested_cb,
+            Box::new(callback_fn),
+        )?);
+
+        Ok(())
+    }
+
+    pub fn unset_cancel_fill_requested_cb(&mut self) -> Result<()> {
+        check(unsafe { autofill_service_unset_cancel_fill_requested_cb() })?;
+        self.cancel_fill_requested_fn = None;
+
+        Ok(())
+    }
+
+    /// Calls `callback_fn` with the values entered by the user in a form, to be saved,
+    /// replacing the previous callback.
+    pub fn set_committed_cb<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(i32, Result<ViewInfo>),
+        F: 'static,
+    {
+        let callback_fn: RequestFn = Box::new(callback_fn);
+        let (callback_fn, data) = pin_callback::<dyn FnMut(i32, Result<ViewInfo>)>(callback_fn);
+
+        check(unsafe { autofill_service_set_committed_cb(Some(committed_handler), data) })?;
+        self.committed_fn = Some(callback_fn);
+
+        Ok(())
+    }
+
+    pub fn unset_committed_cb(&mut self) -> Result<()> {
+        check(unsafe { autofill_service_unset_committed_cb() })?;
+        self.committed_fn = None;
+
+        Ok(())
+    }
+
+    /// Calls `callback_fn` when the daemon asks the service to terminate, replacing the
+    /// previous callback.
+    pub fn set_terminate_received_cb<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(),
+        F: 'static,
+    {
+        let (callback_fn, data) = pin_callback::<dyn FnMut()>(Box::new(callback_fn));
+
+        check(unsafe {
+            autofill_service_set_terminate_received_cb(Some(terminate_received_handler), data)
+        })?;
+        self.terminate_received_fn = Some(callback_fn);
+
+        Ok(())
+    }
+
+    pub fn unset_terminate_received_cb(&mut self) -> Result<()> {
+        check(unsafe { autofill_service_unset_terminate_received_cb() })?;
+        self.terminate_received_fn = None;
+
+        Ok(())
+    }
+
+    pub fn send_auth_info(&self, context_id: i32, auth_info: &AuthInfo) -> Result<()> {
+        let auth_info = auth_info.to_raw()?;
+
+        check(unsafe { autofill_service_send_auth_info(context_id, auth_info.handle) })
+    }
+
+    pub fn send_fill_response(&self, context_id: i32, fill_response: &FillResponse) -> Result<()> {
+        let fill_response = fill_response.to_raw()?;
+
+        check(unsafe { autofill_service_send_fill_response(context_id, fill_response.handle) })
+    }
+
+    /// Reports that the request with `context_id` could not be handled.
+    pub fn send_error_info(&self, context_id: i32, error_info: &ErrorInfo) -> Result<()> {
+        let error_info = error_info.to_raw()?;
+
+        check(unsafe { autofill_service_send_error_info(context_id, error_info.handle) })
+    }
+}
+
+impl Drop for Service {
+    fn drop(&mut self) {
+        unsafe {
+            if self.auth_info_requested_fn.is_some() {
+                autofill_service_unset_auth_info_requested_cb();
+            }
+
+            if self.fill_requested_fn.is_some() {
+                autofill_service_unset_fill_requested_cb();
+            }
+
+            if self.cancel_fill_requested_fn.is_some() {
+                autofill_service_unset_cancel_fill_requested_cb();
+            }
+
+            if self.committed_fn.is_some() {
+                autofill_service_unset_committed_cb();
+            }
+
+            if self.terminate_received_fn.is_some() {
+                autofill_service_unset_terminate_received_cb();
+            }
+
+            autofill_service_deinitialize();
+        }
+
+        INITIALIZED.with(|initialized| initialized.set(false));
+    }
+}
+
+fn set_requested_cb(set: SetRequestedCb, callback_fn: RequestFn) -> Result<Pin<Box<RequestFn>>> {
+    let (callback_fn, data) = pin_callback::<dyn FnMut(i32, Result<ViewInfo>)>(callback_fn);
+
+    check(unsafe { set(Some(requested_handler), data) })?;
+
+    Ok(callback_fn)
+}
+
+extern "C" fn requested_handler(
+    context_id: c_int,
+    view_info: autofill_view_info_h,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut RequestFn) };
+        closure(context_id, unsafe { ViewInfo::from_raw(view_info) });
+    })
+}
+
+extern "C" fn committed_handler(
+    context_id: c_int,
+    view_info: autofill_save_view_info_h,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut RequestFn) };
+        closure(context_id, unsafe { ViewInfo::from_raw_save(view_info) });
+    })
+}
+
+extern "C" fn terminate_received_handler(user_data: *mut c_void) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut TerminateFn) };
+        closure();
+    })
+}
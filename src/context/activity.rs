@@ -0,0 +1,187 @@
+//! Recognition of the activity of the user (walking, running, in a vehicle), from the
+//! sensors combined by the platform.
+//!
+//! Fails with [`Error::NotSupported`](crate::Error::NotSupported) on devices without the
+//! `http://tizen.org/feature/sensor.activity_recognition` feature.
+
+use crate::error::{check, Result};
+use crate::rutin::{abort_on_panic, pin_callback};
+#[cfg(feature = "async")]
+use crate::stream::EventStream;
+use rutin_tizen_sys::{
+    activity_accuracy_e, activity_accuracy_e_ACTIVITY_ACCURACY_HIGH,
+    activity_accuracy_e_ACTIVITY_ACCURACY_LOW, activity_accuracy_e_ACTIVITY_ACCURACY_MID,
+    activity_create, activity_data_h, activity_error_e, activity_get_accuracy, activity_h,
+    activity_is_supported, activity_release, activity_start_recognition, activity_stop_recognition,
+    activity_type_e, activity_type_e_ACTIVITY_IN_VEHICLE, activity_type_e_ACTIVITY_RUN,
+    activity_type_e_ACTIVITY_STATIONARY, activity_type_e_ACTIVITY_WALK,
+};
+use std::os::raw::c_void;
+use std::pin::Pin;
+use std::ptr::null_mut;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Activity {
+    Stationary,
+    Walk,
+    Run,
+    InVehicle,
+}
+
+impl From<Activity> for activity_type_e {
+    fn from(activity: Activity) -> activity_type_e {
+        match activity {
+            Activity::Stationary => activity_type_e_ACTIVITY_STATIONARY,
+            Activity::Walk => activity_type_e_ACTIVITY_WALK,
+            Activity::Run => activity_type_e_ACTIVITY_RUN,
+            Activity::InVehicle => activity_type_e_ACTIVITY_IN_VEHICLE,
+        }
+    }
+}
+
+impl From<activity_type_e> for Activity {
+    fn from(activity: activity_type_e) -> Activity {
+        match activity {
+            activity_type_e_ACTIVITY_WALK => Activity::Walk,
+            activity_type_e_ACTIVITY_RUN => Activity::Run,
+            activity_type_e_ACTIVITY_IN_VEHICLE => Activity::InVehicle,
+            _ => Activity::Stationary,
+        }
+    }
+}
+
+/// How confident the platform is that the activity is correct.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Accuracy {
+    Low,
+    Mid,
+    High,
+}
+
+impl From<activity_accuracy_e> for Accuracy {
+    fn from(accuracy: activity_accuracy_e) -> Accuracy {
+        match accuracy {
+            activity_accuracy_e_ACTIVITY_ACCURACY_HIGH => Accuracy::High,
+            activity_accuracy_e_ACTIVITY_ACCURACY_MID => Accuracy::Mid,
+            _ => Accuracy::Low,
+        }
+    }
+}
+
+/// An activity recognized.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ActivityEvent {
+    pub activity: Activity,
+    pub accuracy: Accuracy,
+    /// When it was recognized, in milliseconds since the epoch.
+    pub timestamp: f64,
+}
+
+type ActivityFn = Box<dyn FnMut(Result<ActivityEvent>)>;
+
+/// Recognizes one activity at a time, stopped when dropped.
+pub struct ActivityRecognizer {
+    handle: activity_h,
+    callback_fn: Option<Pin<Box<ActivityFn>>>,
+}
+
+impl ActivityRecognizer {
+    pub fn is_supported(activity: Activity) -> Result<bool> {
+        let mut supported = false;
+        check(unsafe { activity_is_supported(activity.into(), &mut supported) })?;
+
+        Ok(supported)
+    }
+
+    pub fn new() -> Result<ActivityRecognizer> {
+        let mut handle = null_mut();
+        check(unsafe { activity_create(&mut handle) })?;
+
+        Ok(Self {
+            handle,
+            callback_fn: None,
+        })
+    }
+
+    /// Starts recognizing `activity`, `callback_fn` is called each time the user is doing it.
+    pub fn start<F>(&mut self, activity: Activity, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(Result<ActivityEvent>),
+        F: 'static,
+    {
+        let (callback_fn, data) =
+            pin_callback::<dyn FnMut(Result<ActivityEvent>)>(Box::new(callback_fn));
+
+        check(unsafe {
+            activity_start_recognition(self.handle, activity.into(), Some(activity_handler), data)
+        })?;
+        self.callback_fn = Some(callback_fn);
+
+        Ok(())
+    }
+
+    pub fn stop(&mut self) -> Result<()> {
+        check(unsafe { activity_stop_recognition(self.handle) })?;
+        self.callback_fn = None;
+
+        Ok(())
+    }
+
+    /// Returns a stream of the recognitions of all of `activities`.
+    #[cfg(feature = "async")]
+    pub fn events(activities: &[Activity]) -> Result<EventStream<Result<ActivityEvent>>> {
+        EventStream::new(|sender| {
+            activities
+                .iter()
+                .map(|&activity| {
+                    let mut recognizer = ActivityRecognizer::new()?;
+
+                    let sender = sender.clone();
+                    recognizer.start(activity, move |event| sender.send(event))?;
+
+                    Ok(recognizer)
+                })
+                .collect::<Result<Vec<_>>>()
+        })
+    }
+}
+
+impl Drop for ActivityRecognizer {
+    fn drop(&mut self) {
+        unsafe {
+            if self.callback_fn.is_some() {
+                activity_stop_recognition(self.handle);
+            }
+
+            activity_release(self.handle);
+        }
+    }
+}
+
+extern "C" fn activity_handler(
+    activity: activity_type_e,
+    data: activity_data_h,
+    timestamp: f64,
+    error: activity_error_e,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut ActivityFn) };
+
+        let event = check(error).map(|_| {
+            let mut accuracy = activity_accuracy_e_ACTIVITY_ACCURACY_LOW;
+            unsafe { activity_get_accuracy(data, &mut accuracy) };
+
+            ActivityEvent {
+                activity: activity.into(),
+                accuracy: accuracy.into(),
+                timestamp,
+            }
+        });
+
+        closure(event);
+    })
+}
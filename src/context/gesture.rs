@@ -0,0 +1,234 @@
+//! Recognition of gestures made with the device, such as shaking it or raising the wrist.
+//!
+//! The gestures are recognized by the platform, even on devices which don't expose the
+//! sensors they rely on.
+
+use crate::error::{check, Result};
+use crate::rutin::{abort_on_panic, pin_callback};
+#[cfg(feature = "async")]
+use crate::stream::EventStream;
+use rutin_tizen_sys::{
+    gesture_create, gesture_data_h, gesture_error_e, gesture_event_e,
+    gesture_event_e_GESTURE_EVENT_DETECTED, gesture_event_e_GESTURE_EVENT_NONE,
+    gesture_event_e_GESTURE_SHAKE_FINISHED, gesture_get_event, gesture_get_tilt, gesture_h,
+    gesture_is_supported, gesture_option_e_GESTURE_OPTION_ALWAYS_ON,
+    gesture_option_e_GESTURE_OPTION_DEFAULT, gesture_release, gesture_start_recognition,
+    gesture_stop_recognition, gesture_type_e, gesture_type_e_GESTURE_DOUBLE_TAP,
+    gesture_type_e_GESTURE_MOVE_TO_EAR, gesture_type_e_GESTURE_NO_MOVE,
+    gesture_type_e_GESTURE_PICK_UP, gesture_type_e_GESTURE_SHAKE, gesture_type_e_GESTURE_SNAP,
+    gesture_type_e_GESTURE_TILT, gesture_type_e_GESTURE_TURN_FACE_DOWN,
+    gesture_type_e_GESTURE_WRIST_UP,
+};
+use std::os::raw::c_void;
+use std::pin::Pin;
+use std::ptr::null_mut;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Gesture {
+    DoubleTap,
+    MoveToEar,
+    /// The device stayed still for a while.
+    NoMove,
+    PickUp,
+    Shake,
+    Snap,
+    Tilt,
+    TurnFaceDown,
+    WristUp,
+}
+
+impl From<Gesture> for gesture_type_e {
+    fn from(gesture: Gesture) -> gesture_type_e {
+        match gesture {
+            Gesture::DoubleTap => gesture_type_e_GESTURE_DOUBLE_TAP,
+            Gesture::MoveToEar => gesture_type_e_GESTURE_MOVE_TO_EAR,
+            Gesture::NoMove => gesture_type_e_GESTURE_NO_MOVE,
+            Gesture::PickUp => gesture_type_e_GESTURE_PICK_UP,
+            Gesture::Shake => gesture_type_e_GESTURE_SHAKE,
+            Gesture::Snap => gesture_type_e_GESTURE_SNAP,
+            Gesture::Tilt => gesture_type_e_GESTURE_TILT,
+            Gesture::TurnFaceDown => gesture_type_e_GESTURE_TURN_FACE_DOWN,
+            Gesture::WristUp => gesture_type_e_GESTURE_WRIST_UP,
+        }
+    }
+}
+
+impl From<gesture_type_e> for Gesture {
+    fn from(gesture: gesture_type_e) -> Gesture {
+        match gesture {
+            gesture_type_e_GESTURE_MOVE_TO_EAR => Gesture::MoveToEar,
+            gesture_type_e_GESTURE_NO_MOVE => Gesture::NoMove,
+            gesture_type_e_GESTURE_PICK_UP => Gesture::PickUp,
+            gesture_type_e_GESTURE_SHAKE => Gesture::Shake,
+            gesture_type_e_GESTURE_SNAP => Gesture::Snap,
+            gesture_type_e_GESTURE_TILT => Gesture::Tilt,
+            gesture_type_e_GESTURE_TURN_FACE_DOWN => Gesture::TurnFaceDown,
+            gesture_type_e_GESTURE_WRIST_UP => Gesture::WristUp,
+            _ => Gesture::DoubleTap,
+        }
+    }
+}
+
+/// What was recognized of a gesture.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Detection {
+    Detected,
+    /// The gesture is over, for [`Gesture::NoMove`] when the device moves again.
+    Finished,
+    ShakeStarted,
+    ShakeFinished,
+    /// The tilt of the device on each axis, for [`Gesture::Tilt`].
+    Tilt {
+        x: i32,
+        y: i32,
+    },
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GestureEvent {
+    pub gesture: Gesture,
+    pub detection: Detection,
+    /// When it was recognized, in milliseconds since the epoch.
+    pub timestamp: f64,
+}
+
+type GestureFn = Box<dyn FnMut(Result<GestureEvent>)>;
+
+/// Recognizes one gesture at a time, stopped when dropped.
+pub struct GestureRecognizer {
+    handle: gesture_h,
+    callback_fn: Option<Pin<Box<GestureFn>>>,
+}
+
+impl GestureRecognizer {
+    pub fn is_supported(gesture: Gesture) -> Result<bool> {
+        let mut supported = false;
+        check(unsafe { gesture_is_supported(gesture.into(), &mut supported) })?;
+
+        Ok(supported)
+    }
+
+    pub fn new() -> Result<GestureRecognizer> {
+        let mut handle = null_mut();
+        check(unsafe { gesture_create(&mut handle) })?;
+
+        Ok(Self {
+            handle,
+            callback_fn: None,
+        })
+    }
+
+    /// Starts recognizing `gesture`, calling `callback_fn` each time it is made. If
+    /// `always_on`, it is also recognized while the display is off, at the cost of battery.
+    pub fn start<F>(&mut self, gesture: Gesture, always_on: bool, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(Result<GestureEvent>),
+        F: 'static,
+    {
+        let (callback_fn, data) =
+            pin_callback::<dyn FnMut(Result<GestureEvent>)>(Box::new(callback_fn));
+        let option = if always_on {
+            gesture_option_e_GESTURE_OPTION_ALWAYS_ON
+        } else {
+            gesture_option_e_GESTURE_OPTION_DEFAULT
+        };
+
+        check(unsafe {
+            gesture_start_recognition(
+                self.handle,
+                gesture.into(),
+                option,
+                Some(gesture_handler),
+                data,
+            )
+        })?;
+        self.callback_fn = Some(callback_fn);
+
+        Ok(())
+    }
+
+    pub fn stop(&mut self) -> Result<()> {
+        check(unsafe { gesture_stop_recognition(self.handle) })?;
+        self.callback_fn = None;
+
+        Ok(())
+    }
+
+    /// Returns a stream of the recognitions of all of `gestures`, while the display is on.
+    #[cfg(feature = "async")]
+    pub fn events(gestures: &[Gesture]) -> Result<EventStream<Result<GestureEvent>>> {
+        EventStream::new(|sender| {
+            gestures
+                .iter()
+                .map(|&gesture| {
+                    let mut recognizer = GestureRecognizer::new()?;
+
+                    let sender = sender.clone();
+                    recognizer.start(gesture, false, move |event| sender.send(event))?;
+
+                    Ok(recognizer)
+                })
+                .collect::<Result<Vec<_>>>()
+        })
+    }
+}
+
+impl Drop for GestureRecognizer {
+    fn drop(&mut self) {
+        unsafe {
+            if self.callback_fn.is_some() {
+                gesture_stop_recognition(self.handle);
+            }
+
+            gesture_release(self.handle);
+        }
+    }
+}
+
+unsafe fn read_detection(gesture: Gesture, data: gesture_data_h) -> Result<Detection> {
+    if gesture == Gesture::Tilt {
+        let (mut x, mut y) = (0, 0);
+        check(gesture_get_tilt(data, &mut x, &mut y))?;
+
+        return Ok(Detection::Tilt { x, y });
+    }
+
+    let mut event: gesture_event_e = gesture_event_e_GESTURE_EVENT_NONE;
+    check(gesture_get_event(data, &mut event))?;
+
+    // GESTURE_SHAKE_DETECTED has the value of GESTURE_EVENT_DETECTED
+    Ok(match event {
+        gesture_event_e_GESTURE_EVENT_DETECTED if gesture == Gesture::Shake => {
+            Detection::ShakeStarted
+        }
+        gesture_event_e_GESTURE_EVENT_DETECTED => Detection::Detected,
+        gesture_event_e_GESTURE_SHAKE_FINISHED => Detection::ShakeFinished,
+        _ => Detection::Finished,
+    })
+}
+
+extern "C" fn gesture_handler(
+    gesture: gesture_type_e,
+    data: gesture_data_h,
+    timestamp: f64,
+    error: gesture_error_e,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut GestureFn) };
+        let gesture = Gesture::from(gesture);
+
+        let event = check(error)
+            .and_then(|_| unsafe { read_detection(gesture, data) })
+            .map(|detection| GestureEvent {
+                gesture,
+                detection,
+                timestamp,
+            });
+
+        closure(event);
+    })
+}
@@ -0,0 +1,421 @@
+//! The history of how the device was used, summarized by the platform: which apps are used
+//! and when, how music and videos are listened to, and what drained the battery.
+//!
+//! App usage requires the `http://tizen.org/privilege/apphistory.read` privilege, music and
+//! video playback `http://tizen.org/privilege/mediahistory.read` and communication
+//! `http://tizen.org/privilege/callhistory.read`. Times are in seconds since the epoch.
+
+use crate::error::{check, Error, Result};
+use crate::rutin::{cstring, take_string};
+use rutin_tizen_sys::{
+    context_history_create, context_history_data_e,
+    context_history_data_e_CONTEXT_HISTORY_BATTERY_USAGE,
+    context_history_data_e_CONTEXT_HISTORY_COMMON_SETTING_FOR_APP,
+    context_history_data_e_CONTEXT_HISTORY_COMMON_SETTING_FOR_MUSIC,
+    context_history_data_e_CONTEXT_HISTORY_COMMON_SETTING_FOR_VIDEO,
+    context_history_data_e_CONTEXT_HISTORY_FREQUENTLY_COMMUNICATED_ADDRESS,
+    context_history_data_e_CONTEXT_HISTORY_FREQUENTLY_USED_APP,
+    context_history_data_e_CONTEXT_HISTORY_PEAK_TIME_FOR_APP,
+    context_history_data_e_CONTEXT_HISTORY_PEAK_TIME_FOR_MUSIC,
+    context_history_data_e_CONTEXT_HISTORY_PEAK_TIME_FOR_VIDEO,
+    context_history_data_e_CONTEXT_HISTORY_RARELY_USED_APP,
+    context_history_data_e_CONTEXT_HISTORY_RECENTLY_USED_APP,
+    context_history_data_e_CONTEXT_HISTORY_RECENT_BATTERY_USAGE, context_history_destroy,
+    context_history_filter_create, context_history_filter_day_of_week_e,
+    context_history_filter_day_of_week_e_CONTEXT_HISTORY_FILTER_DAY_OF_WEEK_ALL,
+    context_history_filter_day_of_week_e_CONTEXT_HISTORY_FILTER_DAY_OF_WEEK_WEEKDAYS,
+    context_history_filter_day_of_week_e_CONTEXT_HISTORY_FILTER_DAY_OF_WEEK_WEEKENDS,
+    context_history_filter_destroy, context_history_filter_e_CONTEXT_HISTORY_FILTER_APP_ID,
+    context_history_filter_e_CONTEXT_HISTORY_FILTER_AUDIO_JACK,
+    context_history_filter_e_CONTEXT_HISTORY_FILTER_DAY_OF_WEEK,
+    context_history_filter_e_CONTEXT_HISTORY_FILTER_END_TIME,
+    context_history_filter_e_CONTEXT_HISTORY_FILTER_RESULT_SIZE,
+    context_history_filter_e_CONTEXT_HISTORY_FILTER_START_TIME,
+    context_history_filter_e_CONTEXT_HISTORY_FILTER_TIME_SPAN, context_history_filter_h,
+    context_history_filter_set_int, context_history_filter_set_string, context_history_get_list,
+    context_history_h, context_history_is_supported, context_history_list_destroy,
+    context_history_list_get_count, context_history_list_get_current, context_history_list_h,
+    context_history_list_move_next, context_history_record_destroy,
+    context_history_record_get_double, context_history_record_get_int,
+    context_history_record_get_string, context_history_record_h, CONTEXT_HISTORY_ADDRESS,
+    CONTEXT_HISTORY_APP_ID, CONTEXT_HISTORY_AUDIO_JACK, CONTEXT_HISTORY_HOUR_OF_DAY,
+    CONTEXT_HISTORY_LAST_TIME, CONTEXT_HISTORY_MEDIA_VOLUME, CONTEXT_HISTORY_SYSTEM_VOLUME,
+    CONTEXT_HISTORY_TOTAL_AMOUNT, CONTEXT_HISTORY_TOTAL_COUNT, CONTEXT_HISTORY_TOTAL_DURATION,
+};
+use std::os::raw::c_char;
+use std::ptr::null_mut;
+
+/// The kinds of history kept by the platform.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HistoryData {
+    RecentlyUsedApp,
+    FrequentlyUsedApp,
+    RarelyUsedApp,
+    PeakTimeForApp,
+    PeakTimeForMusic,
+    PeakTimeForVideo,
+    CommonSettingForApp,
+    CommonSettingForMusic,
+    CommonSettingForVideo,
+    FrequentlyCommunicatedAddress,
+    BatteryUsage,
+    RecentBatteryUsage,
+}
+
+impl From<HistoryData> for context_history_data_e {
+    fn from(data: HistoryData) -> context_history_data_e {
+        match data {
+            HistoryData::RecentlyUsedApp => {
+                context_history_data_e_CONTEXT_HISTORY_RECENTLY_USED_APP
+            }
+            HistoryData::FrequentlyUsedApp => {
+                context_history_data_e_CONTEXT_HISTORY_FREQUENTLY_USED_APP
+            }
+            HistoryData::RarelyUsedApp => context_history_data_e_CONTEXT_HISTORY_RARELY_USED_APP,
+            HistoryData::PeakTimeForApp => context_history_data_e_CONTEXT_HISTORY_PEAK_TIME_FOR_APP,
+            HistoryData::PeakTimeForMusic => {
+                context_history_data_e_CONTEXT_HISTORY_PEAK_TIME_FOR_MUSIC
+            }
+            HistoryData::PeakTimeForVideo => {
+                context_history_data_e_CONTEXT_HISTORY_PEAK_TIME_FOR_VIDEO
+            }
+            HistoryData::CommonSettingForApp => {
+                context_history_data_e_CONTEXT_HISTORY_COMMON_SETTING_FOR_APP
+            }
+            HistoryData::CommonSettingForMusic => {
+                context_history_data_e_CONTEXT_HISTORY_COMMON_SETTING_FOR_MUSIC
+            }
+            HistoryData::CommonSettingForVideo => {
+                context_history_data_e_CONTEXT_HISTORY_COMMON_SETTING_FOR_VIDEO
+            }
+            HistoryData::FrequentlyCommunicatedAddress => {
+                context_history_data_e_CONTEXT_HISTORY_FREQUENTLY_COMMUNICATED_ADDRESS
+            }
+            HistoryData::BatteryUsage => context_history_data_e_CONTEXT_HISTORY_BATTERY_USAGE,
+            HistoryData::RecentBatteryUsage => {
+                context_history_data_e_CONTEXT_HISTORY_RECENT_BATTERY_USAGE
+            }
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DayOfWeek {
+    Weekdays,
+    Weekends,
+    All,
+}
+
+impl From<DayOfWeek> for context_history_filter_day_of_week_e {
+    fn from(day: DayOfWeek) -> context_history_filter_day_of_week_e {
+        match day {
+            DayOfWeek::Weekdays => {
+                context_history_filter_day_of_week_e_CONTEXT_HISTORY_FILTER_DAY_OF_WEEK_WEEKDAYS
+            }
+            DayOfWeek::Weekends => {
+                context_history_filter_day_of_week_e_CONTEXT_HISTORY_FILTER_DAY_OF_WEEK_WEEKENDS
+            }
+            DayOfWeek::All => {
+                context_history_filter_day_of_week_e_CONTEXT_HISTORY_FILTER_DAY_OF_WEEK_ALL
+            }
+        }
+    }
+}
+
+/// Narrows a query, each kind of history only supports some of the fields.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Filter {
+    /// How many days back to look.
+    pub time_span: Option<i32>,
+    pub result_size: Option<i32>,
+    pub app_id: Option<String>,
+    pub day_of_week: Option<DayOfWeek>,
+    pub start_time: Option<i32>,
+    pub end_time: Option<i32>,
+    /// Only the playbacks with headphones plugged in, or without.
+    pub audio_jack: Option<bool>,
+}
+
+impl Filter {
+    fn create(&self) -> Result<RawFilter> {
+        let mut handle = null_mut();
+        check(unsafe { context_history_filter_create(&mut handle) })?;
+        let filter = RawFilter { handle };
+
+        let ints = [
+            (
+                context_history_filter_e_CONTEXT_HISTORY_FILTER_TIME_SPAN,
+                self.time_span,
+            ),
+            (
+                context_history_filter_e_CONTEXT_HISTORY_FILTER_RESULT_SIZE,
+                self.result_size,
+            ),
+            (
+                context_history_filter_e_CONTEXT_HISTORY_FILTER_DAY_OF_WEEK,
+                self.day_of_week
+                    .map(|day| context_history_filter_day_of_week_e::from(day) as i32),
+            ),
+            (
+                context_history_filter_e_CONTEXT_HISTORY_FILTER_START_TIME,
+                self.start_time,
+            ),
+            (
+                context_history_filter_e_CONTEXT_HISTORY_FILTER_END_TIME,
+                self.end_time,
+            ),
+            (
+                context_history_filter_e_CONTEXT_HISTORY_FILTER_AUDIO_JACK,
+                self.audio_jack.map(i32::from),
+            ),
+        ];
+
+        for (item, value) in ints.iter() {
+            if let Some(value) = value {
+                check(unsafe { context_history_filter_set_int(filter.handle, *item, *value) })?;
+            }
+        }
+
+        if let Some(app_id) = &self.app_id {
+            let app_id = cstring(app_id.as_str())?;
+            check(unsafe {
+                context_history_filter_set_string(
+                    filter.handle,
+                    context_history_filter_e_CONTEXT_HISTORY_FILTER_APP_ID,
+                    app_id.as_ptr(),
+                )
+            })?;
+        }
+
+        Ok(filter)
+    }
+}
+
+/// How an app was used, or how often an address was communicated with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Usage {
+    /// The app id, or the address of a communication.
+    pub name: String,
+    pub total_count: i32,
+    /// In seconds.
+    pub total_duration: i32,
+    pub last_time: i32,
+}
+
+/// How often apps, music or videos were used during an hour of the day.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PeakTime {
+    pub hour_of_day: i32,
+    pub total_count: i32,
+}
+
+/// The settings most often used while using apps, listening to music or watching videos.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CommonSetting {
+    pub audio_jack: bool,
+    pub system_volume: i32,
+    pub media_volume: i32,
+}
+
+/// How much an app drained the battery.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BatteryUsage {
+    pub app_id: String,
+    /// In percents of a full charge.
+    pub total_amount: f64,
+}
+
+pub struct History {
+    handle: context_history_h,
+}
+
+impl History {
+    pub fn is_supported(data: HistoryData) -> Result<bool> {
+        let mut supported = false;
+        check(unsafe { context_history_is_supported(data.into(), &mut supported) })?;
+
+        Ok(supported)
+    }
+
+    pub fn new() -> Result<History> {
+        let mut handle = null_mut();
+        check(unsafe { context_history_create(&mut handle) })?;
+
+        Ok(Self { handle })
+    }
+
+    /// How the apps were used, `data` must be one of [`HistoryData::RecentlyUsedApp`],
+    /// [`HistoryData::FrequentlyUsedApp`] or [`HistoryData::RarelyUsedApp`].
+    pub fn app_usage(&self, data: HistoryData, filter: &Filter) -> Result<Vec<Usage>> {
+        self.list(data, filter, |record| {
+            Ok(Usage {
+                name: record_string(record, CONTEXT_HISTORY_APP_ID)?,
+                total_count: record_int(record, CONTEXT_HISTORY_TOTAL_COUNT)?,
+                total_duration: record_int(record, CONTEXT_HISTORY_TOTAL_DURATION)?,
+                last_time: record_int(record, CONTEXT_HISTORY_LAST_TIME)?,
+            })
+        })
+    }
+
+    /// The addresses called or messaged most often first.
+    pub fn communication_frequency(&self, filter: &Filter) -> Result<Vec<Usage>> {
+        self.list(
+            HistoryData::FrequentlyCommunicatedAddress,
+            filter,
+            |record| {
+                Ok(Usage {
+                    name: record_string(record, CONTEXT_HISTORY_ADDRESS)?,
+                    total_count: record_int(record, CONTEXT_HISTORY_TOTAL_COUNT)?,
+                    total_duration: record_int(record, CONTEXT_HISTORY_TOTAL_DURATION)?,
+                    last_time: record_int(record, CONTEXT_HISTORY_LAST_TIME)?,
+                })
+            },
+        )
+    }
+
+    /// The busiest hours first, `data` must be one of [`HistoryData::PeakTimeForApp`],
+    /// [`HistoryData::PeakTimeForMusic`] or [`HistoryData::PeakTimeForVideo`].
+    pub fn peak_times(&self, data: HistoryData, filter: &Filter) -> Result<Vec<PeakTime>> {
+        self.list(data, filter, |record| {
+            Ok(PeakTime {
+                hour_of_day: record_int(record, CONTEXT_HISTORY_HOUR_OF_DAY)?,
+                total_count: record_int(record, CONTEXT_HISTORY_TOTAL_COUNT)?,
+            })
+        })
+    }
+
+    /// The most common settings first, `data` must be one of
+    /// [`HistoryData::CommonSettingForApp`], [`HistoryData::CommonSettingForMusic`] or
+    /// [`HistoryData::CommonSettingForVideo`].
+    pub fn common_settings(
+        &self,
+        data: HistoryData,
+        filter: &Filter,
+    ) -> Result<Vec<CommonSetting>> {
+        self.list(data, filter, |record| {
+            Ok(CommonSetting {
+                audio_jack: record_int(record, CONTEXT_HISTORY_AUDIO_JACK)? != 0,
+                system_volume: record_int(record, CONTEXT_HISTORY_SYSTEM_VOLUME)?,
+                media_volume: record_int(record, CONTEXT_HISTORY_MEDIA_VOLUME)?,
+            })
+        })
+    }
+
+    /// The apps which drained the battery the most first, since the last full charge if
+    /// `recent`, otherwise over the time span of `filter`.
+    pub fn battery_usage(&self, recent: bool, filter: &Filter) -> Result<Vec<BatteryUsage>> {
+        let data = if recent {
+            HistoryData::RecentBatteryUsage
+        } else {
+            HistoryData::BatteryUsage
+        };
+
+        self.list(data, filter, |record| {
+            let mut total_amount = 0.0;
+            check(unsafe {
+                context_history_record_get_double(
+                    record,
+                    CONTEXT_HISTORY_TOTAL_AMOUNT.as_ptr() as *const c_char,
+                    &mut total_amount,
+                )
+            })?;
+
+            Ok(BatteryUsage {
+                app_id: record_string(record, CONTEXT_HISTORY_APP_ID)?,
+                total_amount,
+            })
+        })
+    }
+
+    fn list<T, F>(&self, data: HistoryData, filter: &Filter, read: F) -> Result<Vec<T>>
+    where
+        F: Fn(context_history_record_h) -> Result<T>,
+    {
+        let filter = filter.create()?;
+
+        let mut handle = null_mut();
+        match check(unsafe {
+            context_history_get_list(self.handle, data.into(), filter.handle, &mut handle)
+        }) {
+            Err(Error::NoData) => return Ok(Vec::new()),
+            result => result?,
+        }
+        let list = RawList { handle };
+
+        let mut count = 0;
+        check(unsafe { context_history_list_get_count(list.handle, &mut count) })?;
+
+        let mut records = Vec::with_capacity(count as usize);
+        for i in 0..count {
+            if i > 0 {
+                check(unsafe { context_history_list_move_next(list.handle) })?;
+            }
+
+            let mut record = null_mut();
+            check(unsafe { context_history_list_get_current(list.handle, &mut record) })?;
+
+            let result = read(record);
+            unsafe { context_history_record_destroy(record) };
+            records.push(result?);
+        }
+
+        Ok(records)
+    }
+}
+
+impl Drop for History {
+    fn drop(&mut self) {
+        unsafe {
+            context_history_destroy(self.handle);
+        }
+    }
+}
+
+fn record_int(record: context_history_record_h, key: &[u8]) -> Result<i32> {
+    let mut value = 0;
+    check(unsafe {
+        context_history_record_get_int(record, key.as_ptr() as *const c_char, &mut value)
+    })?;
+
+    Ok(value)
+}
+
+fn record_string(record: context_history_record_h, key: &[u8]) -> Result<String> {
+    let mut value = null_mut();
+    check(unsafe {
+        context_history_record_get_string(record, key.as_ptr() as *const c_char, &mut value)
+    })?;
+
+    Ok(unsafe { take_string(value) }.unwrap_or_default())
+}
+
+struct RawFilter {
+    handle: context_history_filter_h,
+}
+
+impl Drop for RawFilter {
+    fn drop(&mut self) {
+        unsafe {
+            context_history_filter_destroy(self.handle);
+        }
+    }
+}
+
+struct RawList {
+    handle: context_history_list_h,
+}
+
+impl Drop for RawList {
+    fn drop(&mut self) {
+        unsafe {
+            context_history_list_destroy(self.handle);
+        }
+    }
+}
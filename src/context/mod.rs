@@ -0,0 +1,4 @@
+pub mod activity;
+pub mod gesture;
+pub mod history;
+pub mod trigger;
@@ -0,0 +1,644 @@
+//! Rules evaluated by the platform, even when the app isn't running, which launch an app
+//! or post a notification when their events happen under their conditions.
+//!
+//! The rules are kept by the platform until removed, and added disabled. Launching an app
+//! requires the `http://tizen.org/privilege/appmanager.launch` privilege, posting
+//! a notification `http://tizen.org/privilege/notification` and time events
+//! `http://tizen.org/privilege/alarm.set`.
+
+use crate::error::{check, Result};
+use crate::rutin::{cstring, take_string};
+use rutin_tizen_sys::{
+    app_control_create, app_control_destroy, app_control_h, app_control_set_app_id,
+    app_control_set_operation, context_trigger_add_rule, context_trigger_condition_e,
+    context_trigger_condition_e_CONTEXT_TRIGGER_CONDITION_APP_USE_FREQUENCY,
+    context_trigger_condition_e_CONTEXT_TRIGGER_CONDITION_BATTERY,
+    context_trigger_condition_e_CONTEXT_TRIGGER_CONDITION_CALL,
+    context_trigger_condition_e_CONTEXT_TRIGGER_CONDITION_CHARGER,
+    context_trigger_condition_e_CONTEXT_TRIGGER_CONDITION_COMMUNICATION_FREQUENCY,
+    context_trigger_condition_e_CONTEXT_TRIGGER_CONDITION_DAY_OF_WEEK,
+    context_trigger_condition_e_CONTEXT_TRIGGER_CONDITION_GPS,
+    context_trigger_condition_e_CONTEXT_TRIGGER_CONDITION_HEADPHONE,
+    context_trigger_condition_e_CONTEXT_TRIGGER_CONDITION_MUSIC_PLAYBACK_FREQUENCY,
+    context_trigger_condition_e_CONTEXT_TRIGGER_CONDITION_POWER_SAVING_MODE,
+    context_trigger_condition_e_CONTEXT_TRIGGER_CONDITION_TIME,
+    context_trigger_condition_e_CONTEXT_TRIGGER_CONDITION_USB,
+    context_trigger_condition_e_CONTEXT_TRIGGER_CONDITION_VIDEO_PLAYBACK_FREQUENCY,
+    context_trigger_condition_e_CONTEXT_TRIGGER_CONDITION_WIFI, context_trigger_disable_rule,
+    context_trigger_enable_rule, context_trigger_event_e,
+    context_trigger_event_e_CONTEXT_TRIGGER_EVENT_ACTIVITY_IN_VEHICLE,
+    context_trigger_event_e_CONTEXT_TRIGGER_EVENT_ACTIVITY_RUNNING,
+    context_trigger_event_e_CONTEXT_TRIGGER_EVENT_ACTIVITY_STATIONARY,
+    context_trigger_event_e_CONTEXT_TRIGGER_EVENT_ACTIVITY_WALKING,
+    context_trigger_event_e_CONTEXT_TRIGGER_EVENT_BATTERY,
+    context_trigger_event_e_CONTEXT_TRIGGER_EVENT_CALL,
+    context_trigger_event_e_CONTEXT_TRIGGER_EVENT_CHARGER,
+    context_trigger_event_e_CONTEXT_TRIGGER_EVENT_CONTACTS,
+    context_trigger_event_e_CONTEXT_TRIGGER_EVENT_EMAIL,
+    context_trigger_event_e_CONTEXT_TRIGGER_EVENT_GPS,
+    context_trigger_event_e_CONTEXT_TRIGGER_EVENT_HEADPHONE,
+    context_trigger_event_e_CONTEXT_TRIGGER_EVENT_MESSAGE,
+    context_trigger_event_e_CONTEXT_TRIGGER_EVENT_PLACE,
+    context_trigger_event_e_CONTEXT_TRIGGER_EVENT_POWER_SAVING_MODE,
+    context_trigger_event_e_CONTEXT_TRIGGER_EVENT_TIME,
+    context_trigger_event_e_CONTEXT_TRIGGER_EVENT_USB,
+    context_trigger_event_e_CONTEXT_TRIGGER_EVENT_WIFI, context_trigger_get_own_rule_ids,
+    context_trigger_get_rule_by_id, context_trigger_logical_type_e,
+    context_trigger_logical_type_e_CONTEXT_TRIGGER_LOGICAL_CONJUNCTION,
+    context_trigger_logical_type_e_CONTEXT_TRIGGER_LOGICAL_DISJUNCTION,
+    context_trigger_remove_rule, context_trigger_rule_add_entry, context_trigger_rule_create,
+    context_trigger_rule_destroy, context_trigger_rule_entry_add_comparison_int,
+    context_trigger_rule_entry_add_comparison_string, context_trigger_rule_entry_add_key,
+    context_trigger_rule_entry_add_option_string, context_trigger_rule_entry_create_condition,
+    context_trigger_rule_entry_create_event, context_trigger_rule_entry_destroy,
+    context_trigger_rule_entry_h, context_trigger_rule_get_description, context_trigger_rule_h,
+    context_trigger_rule_set_action_app_control, context_trigger_rule_set_action_notification,
+    context_trigger_rule_set_description,
+};
+use std::os::raw::{c_char, c_int, c_void};
+use std::path::PathBuf;
+use std::ptr::{null, null_mut};
+use std::slice;
+
+/// The attributes of the events and conditions, with some of their values.
+pub mod key {
+    pub const LEVEL: &str = "Level";
+    pub const IS_CONNECTED: &str = "IsConnected";
+    pub const IS_ENABLED: &str = "IsEnabled";
+    pub const STATE: &str = "State";
+    pub const TYPE: &str = "Type";
+    pub const EVENT: &str = "Event";
+    pub const ADDRESS: &str = "Address";
+    pub const ACCURACY: &str = "Accuracy";
+    /// Minutes since midnight, for time events and conditions.
+    pub const TIME_OF_DAY: &str = "TimeOfDay";
+    pub const DAY_OF_WEEK: &str = "DayOfWeek";
+    pub const TOTAL_COUNT: &str = "TotalCount";
+    pub const RANK: &str = "Rank";
+    /// The option naming the app of an app use frequency condition.
+    pub const APP_ID: &str = "AppId";
+
+    pub const LEVEL_EMPTY: &str = "Empty";
+    pub const LEVEL_CRITICAL: &str = "Critical";
+    pub const LEVEL_LOW: &str = "Low";
+    pub const LEVEL_NORMAL: &str = "Normal";
+    pub const LEVEL_HIGH: &str = "High";
+    pub const LEVEL_FULL: &str = "Full";
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Logical {
+    And,
+    Or,
+}
+
+impl From<Logical> for context_trigger_logical_type_e {
+    fn from(logical: Logical) -> context_trigger_logical_type_e {
+        match logical {
+            Logical::And => context_trigger_logical_type_e_CONTEXT_TRIGGER_LOGICAL_CONJUNCTION,
+            Logical::Or => context_trigger_logical_type_e_CONTEXT_TRIGGER_LOGICAL_DISJUNCTION,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Operator {
+    Equal,
+    NotEqual,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+}
+
+impl Operator {
+    fn as_cstr(self) -> &'static str {
+        match self {
+            Operator::Equal => "==\0",
+            Operator::NotEqual => "!=\0",
+            Operator::Less => "<\0",
+            Operator::LessOrEqual => "<=\0",
+            Operator::Greater => ">\0",
+            Operator::GreaterOrEqual => ">=\0",
+        }
+    }
+}
+
+/// What can make a rule fire.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TriggerEvent {
+    Time,
+    Battery,
+    Charger,
+    Gps,
+    Headphone,
+    Usb,
+    Wifi,
+    PowerSavingMode,
+    Call,
+    Email,
+    Message,
+    Contacts,
+    ActivityStationary,
+    ActivityWalking,
+    ActivityRunning,
+    ActivityInVehicle,
+    Place,
+}
+
+impl From<TriggerEvent> for context_trigger_event_e {
+    fn from(event: TriggerEvent) -> context_trigger_event_e {
+        match event {
+            TriggerEvent::Time => context_trigger_event_e_CONTEXT_TRIGGER_EVENT_TIME,
+            TriggerEvent::Battery => context_trigger_event_e_CONTEXT_TRIGGER_EVENT_BATTERY,
+            TriggerEvent::Charger => context_trigger_event_e_CONTEXT_TRIGGER_EVENT_CHARGER,
+            TriggerEvent::Gps => context_trigger_event_e_CONTEXT_TRIGGER_EVENT_GPS,
+            TriggerEvent::Headphone => context_trigger_event_e_CONTEXT_TRIGGER_EVENT_HEADPHONE,
+            TriggerEvent::Usb => context_trigger_event_e_CONTEXT_TRIGGER_EVENT_USB,
+            TriggerEvent::Wifi => context_trigger_event_e_CONTEXT_TRIGGER_EVENT_WIFI,
+            TriggerEvent::PowerSavingMode => {
+                context_trigger_event_e_CONTEXT_TRIGGER_EVENT_POWER_SAVING_MODE
+            }
+            TriggerEvent::Call => context_trigger_event_e_CONTEXT_TRIGGER_EVENT_CALL,
+            TriggerEvent::Email => context_trigger_event_e_CONTEXT_TRIGGER_EVENT_EMAIL,
+            TriggerEvent::Message => context_trigger_event_e_CONTEXT_TRIGGER_EVENT_MESSAGE,
+            TriggerEvent::Contacts => context_trigger_event_e_CONTEXT_TRIGGER_EVENT_CONTACTS,
+            TriggerEvent::ActivityStationary => {
+                context_trigger_event_e_CONTEXT_TRIGGER_EVENT_ACTIVITY_STATIONARY
+            }
+            TriggerEvent::ActivityWalking => {
+                context_trigger_event_e_CONTEXT_TRIGGER_EVENT_ACTIVITY_WALKING
+            }
+            TriggerEvent::ActivityRunning => {
+                context_trigger_event_e_CONTEXT_TRIGGER_EVENT_ACTIVITY_RUNNING
+            }
+            TriggerEvent::ActivityInVehicle => {
+                context_trigger_event_e_CONTEXT_TRIGGER_EVENT_ACTIVITY_IN_VEHICLE
+            }
+            TriggerEvent::Place => context_trigger_event_e_CONTEXT_TRIGGER_EVENT_PLACE,
+        }
+    }
+}
+
+/// What must hold when an event happens for a rule to fire.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TriggerCondition {
+    Time,
+    DayOfWeek,
+    Battery,
+    Charger,
+    Gps,
+    Headphone,
+    Usb,
+    Wifi,
+    PowerSavingMode,
+    Call,
+    AppUseFrequency,
+    CommunicationFrequency,
+    MusicPlaybackFrequency,
+    VideoPlaybackFrequency,
+}
+
+impl From<TriggerCondition> for context_trigger_condition_e {
+    fn from(condition: TriggerCondition) -> context_trigger_condition_e {
+        match condition {
+            TriggerCondition::Time => context_trigger_condition_e_CONTEXT_TRIGGER_CONDITION_TIME,
+            TriggerCondition::DayOfWeek => {
+                context_trigger_condition_e_CONTEXT_TRIGGER_CONDITION_DAY_OF_WEEK
+            }
+            TriggerCondition::Battery => {
+                context_trigger_condition_e_CONTEXT_TRIGGER_CONDITION_BATTERY
+            }
+            TriggerCondition::Charger => {
+                context_trigger_condition_e_CONTEXT_TRIGGER_CONDITION_CHARGER
+            }
+            TriggerCondition::Gps => context_trigger_condition_e_CONTEXT_TRIGGER_CONDITION_GPS,
+            TriggerCondition::Headphone => {
+                context_trigger_condition_e_CONTEXT_TRIGGER_CONDITION_HEADPHONE
+            }
+            TriggerCondition::Usb => context_trigger_condition_e_CONTEXT_TRIGGER_CONDITION_USB,
+            TriggerCondition::Wifi => context_trigger_condition_e_CONTEXT_TRIGGER_CONDITION_WIFI,
+            TriggerCondition::PowerSavingMode => {
+                context_trigger_condition_e_CONTEXT_TRIGGER_CONDITION_POWER_SAVING_MODE
+            }
+            TriggerCondition::Call => context_trigger_condition_e_CONTEXT_TRIGGER_CONDITION_CALL,
+            TriggerCondition::AppUseFrequency => {
+                context_trigger_condition_e_CONTEXT_TRIGGER_CONDITION_APP_USE_FREQUENCY
+            }
+            TriggerCondition::CommunicationFrequency => {
+                context_trigger_condition_e_CONTEXT_TRIGGER_CONDITION_COMMUNICATION_FREQUENCY
+            }
+            TriggerCondition::MusicPlaybackFrequency => {
+                context_trigger_condition_e_CONTEXT_TRIGGER_CONDITION_MUSIC_PLAYBACK_FREQUENCY
+            }
+            TriggerCondition::VideoPlaybackFrequency => {
+                context_trigger_condition_e_CONTEXT_TRIGGER_CONDITION_VIDEO_PLAYBACK_FREQUENCY
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+enum Item {
+    Event(TriggerEvent),
+    Condition(TriggerCondition),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+enum Value {
+    Int(i32),
+    Str(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+enum Filter {
+    Key(String, Logical),
+    Comparison(String, Operator, Value),
+    Option(String, String),
+}
+
+/// An event or condition of a rule, with the comparisons of its attributes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Entry {
+    item: Item,
+    logical: Logical,
+    filters: Vec<Filter>,
+}
+
+impl Entry {
+    /// The keys of the entry are combined with `logical`.
+    pub fn event(event: TriggerEvent, logical: Logical) -> Entry {
+        Self {
+            item: Item::Event(event),
+            logical,
+            filters: Vec::new(),
+        }
+    }
+
+    pub fn condition(condition: TriggerCondition, logical: Logical) -> Entry {
+        Self {
+            item: Item::Condition(condition),
+            logical,
+            filters: Vec::new(),
+        }
+    }
+
+    /// Adds an attribute to compare, its comparisons are combined with `logical`.
+    pub fn key(mut self, key: &str, logical: Logical) -> Self {
+        self.filters.push(Filter::Key(key.to_string(), logical));
+        self
+    }
+
+    /// Compares an attribute already added with [`Entry::key`].
+    pub fn compare_int(mut self, key: &str, operator: Operator, value: i32) -> Self {
+        self.filters.push(Filter::Comparison(
+            key.to_string(),
+            operator,
+            Value::Int(value),
+        ));
+        self
+    }
+
+    pub fn compare_str(mut self, key: &str, operator: Operator, value: &str) -> Self {
+        self.filters.push(Filter::Comparison(
+            key.to_string(),
+            operator,
+            Value::Str(value.to_string()),
+        ));
+        self
+    }
+
+    /// Sets an option of a condition, such as the [`key::APP_ID`] of an app use frequency.
+    pub fn option(mut self, key: &str, value: &str) -> Self {
+        self.filters
+            .push(Filter::Option(key.to_string(), value.to_string()));
+        self
+    }
+
+    fn create(&self) -> Result<RawEntry> {
+        let mut handle = null_mut();
+        check(unsafe {
+            match self.item {
+                Item::Event(event) => context_trigger_rule_entry_create_event(
+                    event.into(),
+                    self.logical.into(),
+                    &mut handle,
+                ),
+                Item::Condition(condition) => context_trigger_rule_entry_create_condition(
+                    condition.into(),
+                    self.logical.into(),
+                    &mut handle,
+                ),
+            }
+        })?;
+        let entry = RawEntry { handle };
+
+        for filter in &self.filters {
+            match filter {
+                Filter::Key(key, logical) => {
+                    let key = cstring(key)?;
+                    check(unsafe {
+                        context_trigger_rule_entry_add_key(
+                            entry.handle,
+                            (*logical).into(),
+                            key.as_ptr(),
+                        )
+                    })?;
+                }
+                Filter::Comparison(key, operator, Value::Int(value)) => {
+                    let key = cstring(key)?;
+                    check(unsafe {
+                        context_trigger_rule_entry_add_comparison_int(
+                            entry.handle,
+                            key.as_ptr(),
+                            operator.as_cstr().as_ptr() as *const c_char,
+                            *value,
+                        )
+                    })?;
+                }
+                Filter::Comparison(key, operator, Value::Str(value)) => {
+                    let key = cstring(key)?;
+                    let value = cstring(value)?;
+                    check(unsafe {
+                        context_trigger_rule_entry_add_comparison_string(
+                            entry.handle,
+                            key.as_ptr(),
+                            operator.as_cstr().as_ptr() as *const c_char,
+                            value.as_ptr(),
+                        )
+                    })?;
+                }
+                Filter::Option(key, value) => {
+                    let key = cstring(key)?;
+                    let value = cstring(value)?;
+                    check(unsafe {
+                        context_trigger_rule_entry_add_option_string(
+                            entry.handle,
+                            key.as_ptr(),
+                            value.as_ptr(),
+                        )
+                    })?;
+                }
+            }
+        }
+
+        Ok(entry)
+    }
+}
+
+/// What a rule does when it fires.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Action {
+    /// Launches an app, with an app control operation if set.
+    LaunchApp {
+        app_id: String,
+        operation: Option<String>,
+    },
+    /// Posts a notification, launching `app_id` if set when the user taps it.
+    Notification {
+        title: String,
+        content: String,
+        icon_path: Option<PathBuf>,
+        app_id: Option<String>,
+    },
+}
+
+impl Action {
+    pub fn launch_app(app_id: &str) -> Action {
+        Action::LaunchApp {
+            app_id: app_id.to_string(),
+            operation: None,
+        }
+    }
+
+    pub fn notification(title: &str, content: &str) -> Action {
+        Action::Notification {
+            title: title.to_string(),
+            content: content.to_string(),
+            icon_path: None,
+            app_id: None,
+        }
+    }
+
+    fn set(&self, rule: context_trigger_rule_h) -> Result<()> {
+        match self {
+            Action::LaunchApp { app_id, operation } => {
+                let app_control = RawAppControl::new(app_id, operation.as_deref())?;
+
+                check(unsafe {
+                    context_trigger_rule_set_action_app_control(rule, app_control.handle)
+                })
+            }
+            Action::Notification {
+                title,
+                content,
+                icon_path,
+                app_id,
+            } => {
+                let title = cstring(title)?;
+                let content = cstring(content)?;
+                let icon_path = icon_path
+                    .as_ref()
+                    .map(|path| cstring(&path.to_string_lossy()))
+                    .transpose()?;
+                let app_control = app_id
+                    .as_ref()
+                    .map(|app_id| RawAppControl::new(app_id, None))
+                    .transpose()?;
+
+                check(unsafe {
+                    context_trigger_rule_set_action_notification(
+                        rule,
+                        title.as_ptr(),
+                        content.as_ptr(),
+                        icon_path.as_ref().map_or(null(), |path| path.as_ptr()),
+                        app_control
+                            .as_ref()
+                            .map_or(null_mut(), |app_control| app_control.handle),
+                    )
+                })
+            }
+        }
+    }
+}
+
+/// Entries combined with a logical operator, and the action to take when they match.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Rule {
+    logical: Logical,
+    entries: Vec<Entry>,
+    action: Action,
+    description: Option<String>,
+}
+
+impl Rule {
+    /// A rule needs at least one event entry.
+    pub fn new(logical: Logical, action: Action) -> Rule {
+        Self {
+            logical,
+            entries: Vec::new(),
+            action,
+            description: None,
+        }
+    }
+
+    pub fn entry(mut self, entry: Entry) -> Self {
+        self.entries.push(entry);
+        self
+    }
+
+    /// Describes the rule to the user, in the settings.
+    pub fn description(mut self, description: &str) -> Self {
+        self.description = Some(description.to_string());
+        self
+    }
+}
+
+/// Registers a rule with the platform, returning its id. The rule is disabled until
+/// [`enable_rule`] is called.
+pub fn add_rule(rule: &Rule) -> Result<i32> {
+    let mut handle = null_mut();
+    check(unsafe { context_trigger_rule_create(rule.logical.into(), &mut handle) })?;
+    let raw_rule = RawRule { handle };
+
+    for entry in &rule.entries {
+        // the rule keeps a copy of the entry
+        let raw_entry = entry.create()?;
+        check(unsafe { context_trigger_rule_add_entry(raw_rule.handle, raw_entry.handle) })?;
+    }
+
+    rule.action.set(raw_rule.handle)?;
+
+    if let Some(description) = &rule.description {
+        let description = cstring(description)?;
+        check(unsafe {
+            context_trigger_rule_set_description(raw_rule.handle, description.as_ptr())
+        })?;
+    }
+
+    let mut id = 0;
+    check(unsafe { context_trigger_add_rule(raw_rule.handle, &mut id) })?;
+
+    Ok(id)
+}
+
+/// Removes a disabled rule.
+pub fn remove_rule(id: i32) -> Result<()> {
+    check(unsafe { context_trigger_remove_rule(id) })
+}
+
+pub fn enable_rule(id: i32) -> Result<()> {
+    check(unsafe { context_trigger_enable_rule(id) })
+}
+
+pub fn disable_rule(id: i32) -> Result<()> {
+    check(unsafe { context_trigger_disable_rule(id) })
+}
+
+/// The ids of the enabled rules of the app, then of the disabled ones.
+pub fn own_rules() -> Result<(Vec<i32>, Vec<i32>)> {
+    let (mut enabled, mut enabled_count) = (null_mut(), 0);
+    let (mut disabled, mut disabled_count) = (null_mut(), 0);
+
+    check(unsafe {
+        context_trigger_get_own_rule_ids(
+            &mut enabled,
+            &mut enabled_count,
+            &mut disabled,
+            &mut disabled_count,
+        )
+    })?;
+
+    unsafe {
+        Ok((
+            take_ids(enabled, enabled_count),
+            take_ids(disabled, disabled_count),
+        ))
+    }
+}
+
+pub fn rule_description(id: i32) -> Result<Option<String>> {
+    let mut handle = null_mut();
+    check(unsafe { context_trigger_get_rule_by_id(id, &mut handle) })?;
+    let rule = RawRule { handle };
+
+    let mut description = null_mut();
+    check(unsafe { context_trigger_rule_get_description(rule.handle, &mut description) })?;
+
+    Ok(unsafe { take_string(description) })
+}
+
+unsafe fn take_ids(ids: *mut c_int, count: c_int) -> Vec<i32> {
+    if ids.is_null() {
+        return Vec::new();
+    }
+
+    let values = slice::from_raw_parts(ids, count as usize).to_vec();
+    libc::free(ids as *mut c_void);
+
+    values
+}
+
+struct RawRule {
+    handle: context_trigger_rule_h,
+}
+
+impl Drop for RawRule {
+    fn drop(&mut self) {
+        unsafe {
+            context_trigger_rule_destroy(self.handle);
+        }
+    }
+}
+
+struct RawEntry {
+    handle: context_trigger_rule_entry_h,
+}
+
+impl Drop for RawEntry {
+    fn drop(&mut self) {
+        unsafe {
+            context_trigger_rule_entry_destroy(self.handle);
+        }
+    }
+}
+
+struct RawAppControl {
+    handle: app_control_h,
+}
+
+impl RawAppControl {
+    fn new(app_id: &str, operation: Option<&str>) -> Result<RawAppControl> {
+        let app_id = cstring(app_id)?;
+        let operation = operation.map(cstring).transpose()?;
+
+        let mut handle = null_mut();
+        check(unsafe { app_control_create(&mut handle) })?;
+        let app_control = Self { handle };
+
+        check(unsafe { app_control_set_app_id(app_control.handle, app_id.as_ptr()) })?;
+        if let Some(operation) = &operation {
+            check(unsafe { app_control_set_operation(app_control.handle, operation.as_ptr()) })?;
+        }
+
+        Ok(app_control)
+    }
+}
+
+impl Drop for RawAppControl {
+    fn drop(&mut self) {
+        unsafe {
+            app_control_destroy(self.handle);
+        }
+    }
+}
@@ -0,0 +1,254 @@
+//! Downloads over HTTP run by the platform download service, which keeps going while the app
+//! is in the background.
+//!
+//! Requires the `http://tizen.org/privilege/download` privilege.
+
+use crate::error::{check, Error, Result};
+use crate::rutin::{abort_on_panic, cstring, path_cstring, pin_callback, take_string};
+#[cfg(feature = "async")]
+use crate::stream::EventStream;
+use rutin_tizen_sys::{
+    download_add_http_header_field, download_cancel, download_create, download_destroy,
+    download_get_content_size, download_get_downloaded_file_path, download_get_error,
+    download_get_state, download_pause, download_set_destination, download_set_file_name,
+    download_set_progress_cb, download_set_state_changed_cb, download_set_url, download_start,
+    download_state_e, download_state_e_DOWNLOAD_STATE_CANCELED,
+    download_state_e_DOWNLOAD_STATE_COMPLETED, download_state_e_DOWNLOAD_STATE_DOWNLOADING,
+    download_state_e_DOWNLOAD_STATE_FAILED, download_state_e_DOWNLOAD_STATE_NONE,
+    download_state_e_DOWNLOAD_STATE_PAUSED, download_state_e_DOWNLOAD_STATE_QUEUED,
+    download_state_e_DOWNLOAD_STATE_READY, download_unset_progress_cb,
+    download_unset_state_changed_cb,
+};
+use std::convert::TryFrom;
+use std::os::raw::{c_int, c_ulonglong, c_void};
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::ptr::null_mut;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DownloadState {
+    Ready,
+    /// Waiting for the other downloads of the service.
+    Queued,
+    Downloading,
+    Paused,
+    Completed,
+    /// The reason is read with [`Download::get_error`].
+    Failed,
+    Canceled,
+}
+
+impl TryFrom<download_state_e> for DownloadState {
+    type Error = Error;
+
+    fn try_from(state: download_state_e) -> Result<DownloadState> {
+        match state {
+            download_state_e_DOWNLOAD_STATE_READY => Ok(DownloadState::Ready),
+            download_state_e_DOWNLOAD_STATE_QUEUED => Ok(DownloadState::Queued),
+            download_state_e_DOWNLOAD_STATE_DOWNLOADING => Ok(DownloadState::Downloading),
+            download_state_e_DOWNLOAD_STATE_PAUSED => Ok(DownloadState::Paused),
+            download_state_e_DOWNLOAD_STATE_COMPLETED => Ok(DownloadState::Completed),
+            download_state_e_DOWNLOAD_STATE_FAILED => Ok(DownloadState::Failed),
+            download_state_e_DOWNLOAD_STATE_CANCELED => Ok(DownloadState::Canceled),
+            _ => Err(Error::Unknown),
+        }
+    }
+}
+
+/// A change of a download, as delivered by [`Download::events`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DownloadEvent {
+    StateChanged(DownloadState),
+    /// The number of bytes received so far.
+    Progress(u64),
+}
+
+type StateFn = Box<dyn FnMut(DownloadState)>;
+type ProgressFn = Box<dyn FnMut(u64)>;
+
+/// A download, removed from the service when dropped.
+pub struct Download {
+    id: c_int,
+    state_fn: Option<Pin<Box<StateFn>>>,
+    progress_fn: Option<Pin<Box<ProgressFn>>>,
+}
+
+impl Download {
+    pub fn new(url: &str) -> Result<Download> {
+        let url = cstring(url)?;
+
+        let mut id = 0;
+        check(unsafe { download_create(&mut id) })?;
+
+        let download = Self {
+            id,
+            state_fn: None,
+            progress_fn: None,
+        };
+        check(unsafe { download_set_url(download.id, url.as_ptr()) })?;
+
+        Ok(download)
+    }
+
+    /// The directory to save the file in, the downloads directory by default.
+    pub fn set_destination(&self, path: &Path) -> Result<()> {
+        let path = path_cstring(path)?;
+
+        check(unsafe { download_set_destination(self.id, path.as_ptr()) })
+    }
+
+    /// The name to save the file as, taken from the server by default.
+    pub fn set_file_name(&self, file_name: &str) -> Result<()> {
+        let file_name = cstring(file_name)?;
+
+        check(unsafe { download_set_file_name(self.id, file_name.as_ptr()) })
+    }
+
+    pub fn add_http_header(&self, field: &str, value: &str) -> Result<()> {
+        let field = cstring(field)?;
+        let value = cstring(value)?;
+
+        check(unsafe { download_add_http_header_field(self.id, field.as_ptr(), value.as_ptr()) })
+    }
+
+    /// Starts or resumes the download.
+    pub fn start(&self) -> Result<()> {
+        check(unsafe { download_start(self.id) })
+    }
+
+    pub fn pause(&self) -> Result<()> {
+        check(unsafe { download_pause(self.id) })
+    }
+
+    pub fn cancel(&self) -> Result<()> {
+        check(unsafe { download_cancel(self.id) })
+    }
+
+    /// Starts the download, returning a stream of its state changes and progress.
+    ///
+    /// The stream owns the download, so it is removed when the stream is dropped.
+    #[cfg(feature = "async")]
+    pub fn events(mut self) -> Result<EventStream<DownloadEvent>> {
+        EventStream::new(|sender| {
+            let state_sender = sender.clone();
+            self.set_state_changed_cb(move |state| {
+                state_sender.send(DownloadEvent::StateChanged(state))
+            })?;
+
+            self.set_progress_cb(move |received| sender.send(DownloadEvent::Progress(received)))?;
+            self.start()?;
+
+            Ok(self)
+        })
+    }
+
+    pub fn get_state(&self) -> Result<DownloadState> {
+        let mut state = download_state_e_DOWNLOAD_STATE_NONE;
+        check(unsafe { download_get_state(self.id, &mut state) })?;
+
+        DownloadState::try_from(state)
+    }
+
+    /// The size of the file in bytes, known once downloading.
+    pub fn get_content_size(&self) -> Result<u64> {
+        let mut size = 0;
+        check(unsafe { download_get_content_size(self.id, &mut size) })?;
+
+        Ok(size as u64)
+    }
+
+    /// The path of the saved file, once completed.
+    pub fn get_downloaded_file_path(&self) -> Result<PathBuf> {
+        let mut path = null_mut();
+        check(unsafe { download_get_downloaded_file_path(self.id, &mut path) })?;
+
+        unsafe { take_string(path) }
+            .map(PathBuf::from)
+            .ok_or(Error::NoData)
+    }
+
+    /// Why the download failed, as a `DOWNLOAD_ERROR_*` code.
+    pub fn get_error(&self) -> Result<Error> {
+        let mut error = 0;
+        check(unsafe { download_get_error(self.id, &mut error) })?;
+
+        Ok(Error::from(error))
+    }
+
+    /// Calls `callback_fn` with each state change, replacing the previous callback.
+    pub fn set_state_changed_cb<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(DownloadState),
+        F: 'static,
+    {
+        let (callback_fn, data) = pin_callback::<dyn FnMut(DownloadState)>(Box::new(callback_fn));
+
+        check(unsafe { download_set_state_changed_cb(self.id, Some(state_handler), data) })?;
+        self.state_fn = Some(callback_fn);
+
+        Ok(())
+    }
+
+    pub fn unset_state_changed_cb(&mut self) -> Result<()> {
+        check(unsafe { download_unset_state_changed_cb(self.id) })?;
+        self.state_fn = None;
+
+        Ok(())
+    }
+
+    /// Calls `callback_fn` with the number of bytes received so far, replacing the previous
+    /// callback.
+    pub fn set_progress_cb<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(u64),
+        F: 'static,
+    {
+        let (callback_fn, data) = pin_callback::<dyn FnMut(u64)>(Box::new(callback_fn));
+
+        check(unsafe { download_set_progress_cb(self.id, Some(progress_handler), data) })?;
+        self.progress_fn = Some(callback_fn);
+
+        Ok(())
+    }
+
+    pub fn unset_progress_cb(&mut self) -> Result<()> {
+        check(unsafe { download_unset_progress_cb(self.id) })?;
+        self.progress_fn = None;
+
+        Ok(())
+    }
+}
+
+impl Drop for Download {
+    fn drop(&mut self) {
+        unsafe {
+            if self.state_fn.is_some() {
+                download_unset_state_changed_cb(self.id);
+            }
+            if self.progress_fn.is_some() {
+                download_unset_progress_cb(self.id);
+            }
+
+            download_destroy(self.id);
+        }
+    }
+}
+
+extern "C" fn state_handler(_id: c_int, state: download_state_e, user_data: *mut c_void) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut StateFn) };
+
+        if let Ok(state) = DownloadState::try_from(state) {
+            closure(state);
+        }
+    })
+}
+
+extern "C" fn progress_handler(_id: c_int, received: c_ulonglong, user_data: *mut c_void) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut ProgressFn) };
+        closure(received as u64);
+    })
+}
@@ -0,0 +1,82 @@
+use crate::rutin::abort_on_panic;
+use rutin_tizen_sys::{
+    _Ecore_Fd_Handler_Flags_ECORE_FD_READ, ecore_main_fd_handler_add, ecore_main_fd_handler_del,
+    Ecore_Fd_Handler, Eina_Bool,
+};
+use std::os::raw::c_void;
+use std::os::unix::io::RawFd;
+use std::pin::Pin;
+use std::ptr::null;
+
+struct FdState<'a> {
+    active: bool,
+    callback_fn: Box<dyn FnMut() -> bool + 'a>,
+}
+
+/// Watches a file descriptor from the main loop.
+///
+/// The handler is removed when this is dropped, or when the callback returns `false`.
+pub struct FdHandler<'a> {
+    handler: *mut Ecore_Fd_Handler,
+    state: Pin<Box<FdState<'a>>>,
+}
+
+impl<'a> FdHandler<'a> {
+    /// Calls `callback_fn` each time `fd` becomes readable.
+    pub fn readable<F>(fd: RawFd, callback_fn: F) -> Option<FdHandler<'a>>
+    where
+        F: FnMut() -> bool,
+        F: 'a,
+    {
+        let state = Box::pin(FdState {
+            active: true,
+            callback_fn: Box::new(callback_fn),
+        });
+        let data = &*state as *const FdState<'a> as *const c_void;
+
+        let handler = unsafe {
+            ecore_main_fd_handler_add(
+                fd,
+                _Ecore_Fd_Handler_Flags_ECORE_FD_READ,
+                Some(fd_handler),
+                data,
+                None,
+                null(),
+            )
+        };
+
+        if handler.is_null() {
+            None
+        } else {
+            Some(Self { handler, state })
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.state.active
+    }
+}
+
+impl<'a> Drop for FdHandler<'a> {
+    fn drop(&mut self) {
+        if self.state.active {
+            unsafe {
+                ecore_main_fd_handler_del(self.handler);
+            }
+        }
+    }
+}
+
+extern "C" fn fd_handler(data: *mut c_void, _handler: *mut Ecore_Fd_Handler) -> Eina_Bool {
+    abort_on_panic(|| {
+        let state = unsafe { &mut *(data as *mut FdState) };
+        state.active = (state.callback_fn)();
+
+        // ECORE_CALLBACK_RENEW or ECORE_CALLBACK_CANCEL
+        if state.active {
+            1
+        } else {
+            0
+        }
+    })
+}
@@ -0,0 +1,119 @@
+use super::prelude::*;
+use rutin_tizen_sys::{
+    elm_atspi_accessible_can_highlight_set, elm_atspi_accessible_description_set,
+    elm_atspi_accessible_name_set, elm_atspi_accessible_role_set,
+    elm_atspi_accessible_translation_domain_set, elm_atspi_component_highlight_clear,
+    elm_atspi_component_highlight_grab, Elm_Atspi_Role, Elm_Atspi_Role_ELM_ATSPI_ROLE_CHECK_BOX,
+    Elm_Atspi_Role_ELM_ATSPI_ROLE_DIALOG, Elm_Atspi_Role_ELM_ATSPI_ROLE_ENTRY,
+    Elm_Atspi_Role_ELM_ATSPI_ROLE_HEADING, Elm_Atspi_Role_ELM_ATSPI_ROLE_IMAGE,
+    Elm_Atspi_Role_ELM_ATSPI_ROLE_LABEL, Elm_Atspi_Role_ELM_ATSPI_ROLE_LIST,
+    Elm_Atspi_Role_ELM_ATSPI_ROLE_LIST_ITEM, Elm_Atspi_Role_ELM_ATSPI_ROLE_PROGRESS_BAR,
+    Elm_Atspi_Role_ELM_ATSPI_ROLE_PUSH_BUTTON, Elm_Atspi_Role_ELM_ATSPI_ROLE_SLIDER,
+    Elm_Atspi_Role_ELM_ATSPI_ROLE_TEXT, Elm_Atspi_Role_ELM_ATSPI_ROLE_TOGGLE_BUTTON,
+    Elm_Atspi_Role_ELM_ATSPI_ROLE_UNKNOWN, Elm_Atspi_Role_ELM_ATSPI_ROLE_WINDOW,
+};
+use std::ffi::CString;
+
+/// What an object is, as announced by the screen reader.
+pub enum AccessibleRole {
+    Unknown,
+    Label,
+    Text,
+    Heading,
+    Image,
+    PushButton,
+    ToggleButton,
+    CheckBox,
+    Slider,
+    ProgressBar,
+    Entry,
+    List,
+    ListItem,
+    Dialog,
+    Window,
+}
+
+impl From<AccessibleRole> for Elm_Atspi_Role {
+    fn from(role: AccessibleRole) -> Elm_Atspi_Role {
+        match role {
+            AccessibleRole::Unknown => Elm_Atspi_Role_ELM_ATSPI_ROLE_UNKNOWN,
+            AccessibleRole::Label => Elm_Atspi_Role_ELM_ATSPI_ROLE_LABEL,
+            AccessibleRole::Text => Elm_Atspi_Role_ELM_ATSPI_ROLE_TEXT,
+            AccessibleRole::Heading => Elm_Atspi_Role_ELM_ATSPI_ROLE_HEADING,
+            AccessibleRole::Image => Elm_Atspi_Role_ELM_ATSPI_ROLE_IMAGE,
+            AccessibleRole::PushButton => Elm_Atspi_Role_ELM_ATSPI_ROLE_PUSH_BUTTON,
+            AccessibleRole::ToggleButton => Elm_Atspi_Role_ELM_ATSPI_ROLE_TOGGLE_BUTTON,
+            AccessibleRole::CheckBox => Elm_Atspi_Role_ELM_ATSPI_ROLE_CHECK_BOX,
+            AccessibleRole::Slider => Elm_Atspi_Role_ELM_ATSPI_ROLE_SLIDER,
+            AccessibleRole::ProgressBar => Elm_Atspi_Role_ELM_ATSPI_ROLE_PROGRESS_BAR,
+            AccessibleRole::Entry => Elm_Atspi_Role_ELM_ATSPI_ROLE_ENTRY,
+            AccessibleRole::List => Elm_Atspi_Role_ELM_ATSPI_ROLE_LIST,
+            AccessibleRole::ListItem => Elm_Atspi_Role_ELM_ATSPI_ROLE_LIST_ITEM,
+            AccessibleRole::Dialog => Elm_Atspi_Role_ELM_ATSPI_ROLE_DIALOG,
+            AccessibleRole::Window => Elm_Atspi_Role_ELM_ATSPI_ROLE_WINDOW,
+        }
+    }
+}
+
+/// Screen reader (ATSPI) properties of an Elementary object.
+pub trait Accessible<'a>: Object<'a> {
+    /// Sets the text read when the object is highlighted.
+    fn set_accessible_name(&mut self, name: &str) {
+        let name = CString::new(name).unwrap();
+
+        unsafe { elm_atspi_accessible_name_set(self.eo_ptr(), name.as_ptr()) }
+    }
+
+    /// Sets the hint read after the name, e.g. "Double tap to open".
+    fn set_accessible_description(&mut self, description: &str) {
+        let description = CString::new(description).unwrap();
+
+        unsafe { elm_atspi_accessible_description_set(self.eo_ptr(), description.as_ptr()) }
+    }
+
+    fn set_accessible_role(&mut self, role: AccessibleRole) {
+        unsafe { elm_atspi_accessible_role_set(self.eo_ptr(), role.into()) }
+    }
+
+    /// Makes the name and description gettext message ids, translated in `domain`.
+    fn set_accessible_translation_domain(&mut self, domain: &str) {
+        let domain = CString::new(domain).unwrap();
+
+        unsafe { elm_atspi_accessible_translation_domain_set(self.eo_ptr(), domain.as_ptr()) }
+    }
+
+    /// Whether the screen reader can move its highlight to the object.
+    fn set_accessible_highlightable(&mut self, value: bool) {
+        unsafe { elm_atspi_accessible_can_highlight_set(self.eo_ptr(), if value { 1 } else { 0 }) }
+    }
+
+    /// Moves the screen reader highlight to the object, which reads it.
+    fn grab_accessible_highlight(&mut self) -> bool {
+        unsafe { elm_atspi_component_highlight_grab(self.eo_ptr()) != 0 }
+    }
+
+    fn clear_accessible_highlight(&mut self) -> bool {
+        unsafe { elm_atspi_component_highlight_clear(self.eo_ptr()) != 0 }
+    }
+
+    /// Called when the screen reader highlights the object, just before reading it.
+    fn on_accessible_highlighted<F>(&mut self, callback_fn: F) -> RegisteredSmartCallback<'a>
+    where
+        F: FnMut(),
+        F: 'a,
+        Self: Sized,
+    {
+        self.smart_callback_add("atspi,highlighted", callback_fn)
+    }
+
+    fn on_accessible_unhighlighted<F>(&mut self, callback_fn: F) -> RegisteredSmartCallback<'a>
+    where
+        F: FnMut(),
+        F: 'a,
+        Self: Sized,
+    {
+        self.smart_callback_add("atspi,unhighlighted", callback_fn)
+    }
+}
+
+impl<'a, O: Object<'a>> Accessible<'a> for O {}
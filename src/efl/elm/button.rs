@@ -0,0 +1,36 @@
+use super::prelude::*;
+use rutin_tizen_sys::{elm_button_add, Eo};
+
+pub struct Button<'a> {
+    eo: *mut Eo,
+    _phantom: std::marker::PhantomData<&'a Eo>,
+}
+
+impl<'a> Button<'a> {
+    pub fn new(parent: &mut dyn Object<'a>) -> Option<Button<'a>> {
+        let eo = unsafe { elm_button_add(parent.eo_ptr()) };
+
+        if eo.is_null() {
+            None
+        } else {
+            Some(Self {
+                eo,
+                _phantom: std::marker::PhantomData,
+            })
+        }
+    }
+
+    pub fn on_clicked<F>(&mut self, callback_fn: F) -> RegisteredSmartCallback<'a>
+    where
+        F: FnMut(),
+        F: 'a,
+    {
+        self.smart_callback_add("clicked", callback_fn)
+    }
+}
+
+impl<'a> Object<'a> for Button<'a> {
+    fn eo_ptr(&mut self) -> *mut Eo {
+        self.eo
+    }
+}
@@ -20,6 +20,16 @@ impl<'a> Conformant<'a> {
             })
         }
     }
+
+    /// Lets the content extend below the indicator instead of leaving room for it,
+    /// for full screen apps showing a transparent indicator.
+    pub fn set_indicator_overlap(&mut self, overlap: bool) {
+        if overlap {
+            self.signal_emit("elm,state,indicator,overlap", "elm")
+        } else {
+            self.signal_emit("elm,state,indicator,nooverlap", "elm")
+        }
+    }
 }
 
 impl<'a> Object<'a> for Conformant<'a> {
@@ -0,0 +1,380 @@
+use super::prelude::*;
+use crate::rutin::abort_on_panic;
+use rutin_tizen_sys::{
+    _Elm_Gesture_State_ELM_GESTURE_STATE_ABORT, _Elm_Gesture_State_ELM_GESTURE_STATE_END,
+    _Elm_Gesture_State_ELM_GESTURE_STATE_MOVE, _Elm_Gesture_State_ELM_GESTURE_STATE_START,
+    _Elm_Gesture_Type_ELM_GESTURE_MOMENTUM, _Elm_Gesture_Type_ELM_GESTURE_N_DOUBLE_TAPS,
+    _Elm_Gesture_Type_ELM_GESTURE_N_FLICKS, _Elm_Gesture_Type_ELM_GESTURE_N_LINES,
+    _Elm_Gesture_Type_ELM_GESTURE_N_LONG_TAPS, _Elm_Gesture_Type_ELM_GESTURE_N_TAPS,
+    _Elm_Gesture_Type_ELM_GESTURE_N_TRIPLE_TAPS, _Elm_Gesture_Type_ELM_GESTURE_ROTATE,
+    _Elm_Gesture_Type_ELM_GESTURE_ZOOM, _Evas_Event_Flags_EVAS_EVENT_FLAG_NONE,
+    elm_gesture_layer_add, elm_gesture_layer_attach, elm_gesture_layer_cb_add,
+    elm_gesture_layer_cb_del, elm_gesture_layer_hold_events_set, Elm_Gesture_Line_Info,
+    Elm_Gesture_Momentum_Info, Elm_Gesture_Rotate_Info, Elm_Gesture_State, Elm_Gesture_Taps_Info,
+    Elm_Gesture_Type, Elm_Gesture_Zoom_Info, Eo, Evas_Event_Flags,
+};
+use std::os::raw::c_void;
+use std::pin::Pin;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GestureState {
+    Start,
+    Move,
+    End,
+    Abort,
+}
+
+impl From<GestureState> for Elm_Gesture_State {
+    fn from(state: GestureState) -> Elm_Gesture_State {
+        match state {
+            GestureState::Start => _Elm_Gesture_State_ELM_GESTURE_STATE_START,
+            GestureState::Move => _Elm_Gesture_State_ELM_GESTURE_STATE_MOVE,
+            GestureState::End => _Elm_Gesture_State_ELM_GESTURE_STATE_END,
+            GestureState::Abort => _Elm_Gesture_State_ELM_GESTURE_STATE_ABORT,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TapGesture {
+    Tap,
+    LongTap,
+    DoubleTap,
+    TripleTap,
+}
+
+impl From<TapGesture> for Elm_Gesture_Type {
+    fn from(gesture: TapGesture) -> Elm_Gesture_Type {
+        match gesture {
+            TapGesture::Tap => _Elm_Gesture_Type_ELM_GESTURE_N_TAPS,
+            TapGesture::LongTap => _Elm_Gesture_Type_ELM_GESTURE_N_LONG_TAPS,
+            TapGesture::DoubleTap => _Elm_Gesture_Type_ELM_GESTURE_N_DOUBLE_TAPS,
+            TapGesture::TripleTap => _Elm_Gesture_Type_ELM_GESTURE_N_TRIPLE_TAPS,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LineGesture {
+    Line,
+    Flick,
+}
+
+impl From<LineGesture> for Elm_Gesture_Type {
+    fn from(gesture: LineGesture) -> Elm_Gesture_Type {
+        match gesture {
+            LineGesture::Line => _Elm_Gesture_Type_ELM_GESTURE_N_LINES,
+            LineGesture::Flick => _Elm_Gesture_Type_ELM_GESTURE_N_FLICKS,
+        }
+    }
+}
+
+/// Information passed to the callbacks of a gesture.
+pub trait GestureInfo: Sized {
+    unsafe fn from_event_info(event_info: *mut c_void) -> Self;
+}
+
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TapsInfo {
+    pub x: i32,
+    pub y: i32,
+    /// Number of fingers tapping.
+    pub n: u32,
+    pub timestamp: u32,
+}
+
+impl GestureInfo for TapsInfo {
+    unsafe fn from_event_info(event_info: *mut c_void) -> Self {
+        let info = &*(event_info as *const Elm_Gesture_Taps_Info);
+
+        Self {
+            x: info.x,
+            y: info.y,
+            n: info.n,
+            timestamp: info.timestamp,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MomentumInfo {
+    pub x1: i32,
+    pub y1: i32,
+    pub x2: i32,
+    pub y2: i32,
+    /// Momentum on the x and y axis, in pixels per second.
+    pub mx: i32,
+    pub my: i32,
+    pub n: u32,
+}
+
+impl MomentumInfo {
+    fn from_raw(info: &Elm_Gesture_Momentum_Info) -> Self {
+        Self {
+            x1: info.x1,
+            y1: info.y1,
+            x2: info.x2,
+            y2: info.y2,
+            mx: info.mx,
+            my: info.my,
+            n: info.n,
+        }
+    }
+}
+
+impl GestureInfo for MomentumInfo {
+    unsafe fn from_event_info(event_info: *mut c_void) -> Self {
+        Self::from_raw(&*(event_info as *const Elm_Gesture_Momentum_Info))
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LineInfo {
+    pub momentum: MomentumInfo,
+    /// Angle of the line in degrees.
+    pub angle: f64,
+}
+
+impl GestureInfo for LineInfo {
+    unsafe fn from_event_info(event_info: *mut c_void) -> Self {
+        let info = &*(event_info as *const Elm_Gesture_Line_Info);
+
+        Self {
+            momentum: MomentumInfo::from_raw(&info.momentum),
+            angle: info.angle,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ZoomInfo {
+    pub x: i32,
+    pub y: i32,
+    pub radius: i32,
+    /// Zoom factor, `1.0` being the original size.
+    pub zoom: f64,
+    pub momentum: f64,
+}
+
+impl GestureInfo for ZoomInfo {
+    unsafe fn from_event_info(event_info: *mut c_void) -> Self {
+        let info = &*(event_info as *const Elm_Gesture_Zoom_Info);
+
+        Self {
+            x: info.x,
+            y: info.y,
+            radius: info.radius,
+            zoom: info.zoom,
+            momentum: info.momentum,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RotateInfo {
+    pub x: i32,
+    pub y: i32,
+    pub radius: i32,
+    pub base_angle: f64,
+    pub angle: f64,
+    pub momentum: f64,
+}
+
+impl GestureInfo for RotateInfo {
+    unsafe fn from_event_info(event_info: *mut c_void) -> Self {
+        let info = &*(event_info as *const Elm_Gesture_Rotate_Info);
+
+        Self {
+            x: info.x,
+            y: info.y,
+            radius: info.radius,
+            base_angle: info.base_angle,
+            angle: info.angle,
+            momentum: info.momentum,
+        }
+    }
+}
+
+/// A gesture callback, removed from the layer when this is dropped.
+pub struct RegisteredGestureCallback<'a, I: GestureInfo> {
+    eo: *mut Eo,
+    gesture_type: Elm_Gesture_Type,
+    state: Elm_Gesture_State,
+    callback_fn: Pin<Box<Box<dyn FnMut(I) + 'a>>>,
+}
+
+impl<'a, I: GestureInfo> RegisteredGestureCallback<'a, I> {
+    fn data_ptr(&mut self) -> *mut c_void {
+        let callback_fn_ref = Pin::as_mut(&mut self.callback_fn);
+        let box_ref = unsafe { Pin::get_unchecked_mut(callback_fn_ref) };
+        box_ref as *mut Box<dyn FnMut(I) + 'a> as *mut c_void
+    }
+}
+
+impl<'a, I: GestureInfo> Drop for RegisteredGestureCallback<'a, I> {
+    fn drop(&mut self) {
+        let data = self.data_ptr();
+
+        unsafe {
+            elm_gesture_layer_cb_del(
+                self.eo,
+                self.gesture_type,
+                self.state,
+                Some(gesture_callback_handler::<I>),
+                data,
+            );
+        }
+    }
+}
+
+/// Recognizes gestures on the object it is attached to.
+pub struct GestureLayer<'a> {
+    eo: *mut Eo,
+    _phantom: std::marker::PhantomData<&'a Eo>,
+}
+
+impl<'a> GestureLayer<'a> {
+    pub fn new(parent: &mut dyn Object<'a>) -> Option<GestureLayer<'a>> {
+        let eo = unsafe { elm_gesture_layer_add(parent.eo_ptr()) };
+
+        if eo.is_null() {
+            None
+        } else {
+            Some(Self {
+                eo,
+                _phantom: std::marker::PhantomData,
+            })
+        }
+    }
+
+    pub fn attach(&mut self, target: &mut dyn Object<'a>) -> bool {
+        unsafe { elm_gesture_layer_attach(self.eo_ptr(), target.eo_ptr()) != 0 }
+    }
+
+    /// Stops the target from receiving the input events while a gesture is in progress.
+    pub fn set_hold_events(&mut self, hold_events: bool) {
+        unsafe { elm_gesture_layer_hold_events_set(self.eo_ptr(), if hold_events { 1 } else { 0 }) }
+    }
+
+    pub fn on_tap<F>(
+        &mut self,
+        gesture: TapGesture,
+        state: GestureState,
+        callback_fn: F,
+    ) -> RegisteredGestureCallback<'a, TapsInfo>
+    where
+        F: FnMut(TapsInfo),
+        F: 'a,
+    {
+        self.gesture_callback_add(gesture.into(), state, callback_fn)
+    }
+
+    pub fn on_momentum<F>(
+        &mut self,
+        state: GestureState,
+        callback_fn: F,
+    ) -> RegisteredGestureCallback<'a, MomentumInfo>
+    where
+        F: FnMut(MomentumInfo),
+        F: 'a,
+    {
+        self.gesture_callback_add(_Elm_Gesture_Type_ELM_GESTURE_MOMENTUM, state, callback_fn)
+    }
+
+    pub fn on_line<F>(
+        &mut self,
+        gesture: LineGesture,
+        state: GestureState,
+        callback_fn: F,
+    ) -> RegisteredGestureCallback<'a, LineInfo>
+    where
+        F: FnMut(LineInfo),
+        F: 'a,
+    {
+        self.gesture_callback_add(gesture.into(), state, callback_fn)
+    }
+
+    pub fn on_zoom<F>(
+        &mut self,
+        state: GestureState,
+        callback_fn: F,
+    ) -> RegisteredGestureCallback<'a, ZoomInfo>
+    where
+        F: FnMut(ZoomInfo),
+        F: 'a,
+    {
+        self.gesture_callback_add(_Elm_Gesture_Type_ELM_GESTURE_ZOOM, state, callback_fn)
+    }
+
+    pub fn on_rotate<F>(
+        &mut self,
+        state: GestureState,
+        callback_fn: F,
+    ) -> RegisteredGestureCallback<'a, RotateInfo>
+    where
+        F: FnMut(RotateInfo),
+        F: 'a,
+    {
+        self.gesture_callback_add(_Elm_Gesture_Type_ELM_GESTURE_ROTATE, state, callback_fn)
+    }
+
+    fn gesture_callback_add<I, F>(
+        &mut self,
+        gesture_type: Elm_Gesture_Type,
+        state: GestureState,
+        callback_fn: F,
+    ) -> RegisteredGestureCallback<'a, I>
+    where
+        I: GestureInfo,
+        F: FnMut(I),
+        F: 'a,
+    {
+        let mut rcb = RegisteredGestureCallback {
+            eo: self.eo_ptr(),
+            gesture_type,
+            state: state.into(),
+            callback_fn: Box::pin(Box::new(callback_fn)),
+        };
+
+        let data = rcb.data_ptr();
+
+        unsafe {
+            elm_gesture_layer_cb_add(
+                rcb.eo,
+                rcb.gesture_type,
+                rcb.state,
+                Some(gesture_callback_handler::<I>),
+                data,
+            );
+        }
+
+        rcb
+    }
+}
+
+impl<'a> Object<'a> for GestureLayer<'a> {
+    fn eo_ptr(&mut self) -> *mut Eo {
+        self.eo
+    }
+}
+
+extern "C" fn gesture_callback_handler<I: GestureInfo>(
+    data: *mut c_void,
+    event_info: *mut c_void,
+) -> Evas_Event_Flags {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(data as *mut Box<dyn FnMut(I)>) };
+        let info = unsafe { I::from_event_info(event_info) };
+        closure(info);
+    });
+
+    _Evas_Event_Flags_EVAS_EVENT_FLAG_NONE
+}
@@ -0,0 +1,172 @@
+use super::prelude::*;
+use crate::rutin::{abort_on_panic, cstring, path_cstring};
+use rutin_tizen_sys::{
+    edje_object_signal_callback_add, edje_object_signal_callback_del_full, elm_layout_add,
+    elm_layout_edje_get, elm_layout_file_set, elm_layout_theme_set, elm_object_part_text_get,
+    elm_object_signal_emit, Eo, Evas_Object,
+};
+use std::ffi::{CStr, CString};
+use std::os::raw::{c_char, c_void};
+use std::path::Path;
+use std::pin::Pin;
+
+/// A layout loaded from a compiled EDC file (`.edj`) or from the theme.
+pub struct Layout<'a> {
+    eo: *mut Eo,
+    _phantom: std::marker::PhantomData<&'a Eo>,
+}
+
+impl<'a> Layout<'a> {
+    pub fn new(parent: &mut dyn Object<'a>) -> Option<Layout<'a>> {
+        let eo = unsafe { elm_layout_add(parent.eo_ptr()) };
+
+        if eo.is_null() {
+            None
+        } else {
+            Some(Self {
+                eo,
+                _phantom: std::marker::PhantomData,
+            })
+        }
+    }
+
+    /// Loads `group` from the `.edj` file, usually below `app::get_resource_path`.
+    pub fn set_file(&mut self, file: &Path, group: &str) -> bool {
+        let (file, group) = match (path_cstring(file), cstring(group)) {
+            (Ok(file), Ok(group)) => (file, group),
+            _ => return false,
+        };
+
+        unsafe { elm_layout_file_set(self.eo_ptr(), file.as_ptr(), group.as_ptr()) != 0 }
+    }
+
+    /// Loads a layout from the Elementary theme, e.g. `("layout", "application", "default")`.
+    pub fn set_theme(&mut self, klass: &str, group: &str, style: &str) -> bool {
+        let klass = CString::new(klass).unwrap();
+        let group = CString::new(group).unwrap();
+        let style = CString::new(style).unwrap();
+
+        unsafe {
+            elm_layout_theme_set(
+                self.eo_ptr(),
+                klass.as_ptr(),
+                group.as_ptr(),
+                style.as_ptr(),
+            ) != 0
+        }
+    }
+
+    pub fn get_part_text(&mut self, part: &str) -> Option<String> {
+        let part = CString::new(part).unwrap();
+        let text = unsafe { elm_object_part_text_get(self.eo_ptr(), part.as_ptr()) };
+
+        if text.is_null() {
+            None
+        } else {
+            Some(
+                unsafe { CStr::from_ptr(text) }
+                    .to_string_lossy()
+                    .into_owned(),
+            )
+        }
+    }
+}
+
+impl<'a> Object<'a> for Layout<'a> {
+    fn eo_ptr(&mut self) -> *mut Eo {
+        self.eo
+    }
+}
+
+/// An Edje signal callback, removed when this is dropped.
+///
+/// The callback is registered on the Edje object of the layout, so it is removed by its data
+/// as well and dropping one of two identical registrations leaves the other in place.
+pub struct RegisteredSignalCallback<'a> {
+    edje: *mut Evas_Object,
+    emission: CString,
+    source: CString,
+    data: *mut c_void,
+    _callback_fn: Pin<Box<Box<dyn FnMut(&str, &str) + 'a>>>,
+}
+
+impl<'a> Drop for RegisteredSignalCallback<'a> {
+    fn drop(&mut self) {
+        unsafe {
+            edje_object_signal_callback_del_full(
+                self.edje,
+                self.emission.as_ptr(),
+                self.source.as_ptr(),
+                Some(signal_callback_handler),
+                self.data,
+            );
+        }
+    }
+}
+
+pub trait ObjectWithSignals<'a>: Object<'a> {
+    fn signal_emit(&mut self, emission: &str, source: &str) {
+        let emission = CString::new(emission).unwrap();
+        let source = CString::new(source).unwrap();
+
+        unsafe { elm_object_signal_emit(self.eo_ptr(), emission.as_ptr(), source.as_ptr()) }
+    }
+
+    /// Calls `callback_fn` with the emission and source of every matching signal,
+    /// both accept `*` and `?` globs.
+    ///
+    /// The object must be layout based, other objects never emit signals.
+    fn signal_callback_add<F>(
+        &mut self,
+        emission: &str,
+        source: &str,
+        callback_fn: F,
+    ) -> RegisteredSignalCallback<'a>
+    where
+        F: FnMut(&str, &str),
+        F: 'a,
+    {
+        let mut callback_fn: Pin<Box<Box<dyn FnMut(&str, &str) + 'a>>> =
+            Box::pin(Box::new(callback_fn));
+
+        let callback_fn_ref = Pin::as_mut(&mut callback_fn);
+        let box_ref = unsafe { Pin::get_unchecked_mut(callback_fn_ref) };
+        let data = box_ref as *mut Box<dyn FnMut(&str, &str) + 'a> as *mut c_void;
+
+        let rcb = RegisteredSignalCallback {
+            edje: unsafe { elm_layout_edje_get(self.eo_ptr()) },
+            emission: CString::new(emission).unwrap(),
+            source: CString::new(source).unwrap(),
+            data,
+            _callback_fn: callback_fn,
+        };
+
+        unsafe {
+            edje_object_signal_callback_add(
+                rcb.edje,
+                rcb.emission.as_ptr(),
+                rcb.source.as_ptr(),
+                Some(signal_callback_handler),
+                data,
+            );
+        }
+
+        rcb
+    }
+}
+
+impl<'a, O: Object<'a>> ObjectWithSignals<'a> for O {}
+
+extern "C" fn signal_callback_handler(
+    data: *mut c_void,
+    _obj: *mut Evas_Object,
+    emission: *const c_char,
+    source: *const c_char,
+) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(data as *mut Box<dyn FnMut(&str, &str)>) };
+        let emission = unsafe { CStr::from_ptr(emission) }.to_string_lossy();
+        let source = unsafe { CStr::from_ptr(source) }.to_string_lossy();
+        closure(&emission, &source);
+    })
+}
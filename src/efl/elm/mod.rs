@@ -1,8 +1,22 @@
+mod atspi;
+mod button;
 mod conformant;
+mod gesture_layer;
 mod label;
+mod layout;
+mod popup;
 pub mod prelude;
+mod progressbar;
+mod transit;
 mod win;
 
+pub use atspi::*;
+pub use button::*;
 pub use conformant::*;
+pub use gesture_layer::*;
 pub use label::*;
+pub use layout::*;
+pub use popup::*;
+pub use progressbar::*;
+pub use transit::*;
 pub use win::*;
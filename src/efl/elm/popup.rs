@@ -0,0 +1,220 @@
+use super::prelude::*;
+use super::{Button, ProgressBar};
+use rutin_tizen_sys::{
+    elm_popup_add, elm_popup_dismiss, elm_popup_orient_set, elm_popup_timeout_get,
+    elm_popup_timeout_set, evas_object_del, evas_object_smart_callback_add, Elm_Popup_Orient,
+    Elm_Popup_Orient_ELM_POPUP_ORIENT_BOTTOM, Elm_Popup_Orient_ELM_POPUP_ORIENT_BOTTOM_LEFT,
+    Elm_Popup_Orient_ELM_POPUP_ORIENT_BOTTOM_RIGHT, Elm_Popup_Orient_ELM_POPUP_ORIENT_CENTER,
+    Elm_Popup_Orient_ELM_POPUP_ORIENT_LEFT, Elm_Popup_Orient_ELM_POPUP_ORIENT_RIGHT,
+    Elm_Popup_Orient_ELM_POPUP_ORIENT_TOP, Elm_Popup_Orient_ELM_POPUP_ORIENT_TOP_LEFT,
+    Elm_Popup_Orient_ELM_POPUP_ORIENT_TOP_RIGHT, Eo, Evas_Object,
+};
+use std::os::raw::c_void;
+
+/// How long a toast stays on screen, in seconds.
+pub const TOAST_TIMEOUT: f64 = 2.0;
+
+pub enum PopupOrient {
+    Top,
+    Center,
+    Bottom,
+    Left,
+    Right,
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+impl From<PopupOrient> for Elm_Popup_Orient {
+    fn from(orient: PopupOrient) -> Elm_Popup_Orient {
+        match orient {
+            PopupOrient::Top => Elm_Popup_Orient_ELM_POPUP_ORIENT_TOP,
+            PopupOrient::Center => Elm_Popup_Orient_ELM_POPUP_ORIENT_CENTER,
+            PopupOrient::Bottom => Elm_Popup_Orient_ELM_POPUP_ORIENT_BOTTOM,
+            PopupOrient::Left => Elm_Popup_Orient_ELM_POPUP_ORIENT_LEFT,
+            PopupOrient::Right => Elm_Popup_Orient_ELM_POPUP_ORIENT_RIGHT,
+            PopupOrient::TopLeft => Elm_Popup_Orient_ELM_POPUP_ORIENT_TOP_LEFT,
+            PopupOrient::TopRight => Elm_Popup_Orient_ELM_POPUP_ORIENT_TOP_RIGHT,
+            PopupOrient::BottomLeft => Elm_Popup_Orient_ELM_POPUP_ORIENT_BOTTOM_LEFT,
+            PopupOrient::BottomRight => Elm_Popup_Orient_ELM_POPUP_ORIENT_BOTTOM_RIGHT,
+        }
+    }
+}
+
+/// The button slots of a popup, from left to right.
+pub enum PopupButton {
+    First,
+    Second,
+    Third,
+}
+
+impl PopupButton {
+    fn part(&self) -> &'static str {
+        match self {
+            PopupButton::First => "button1",
+            PopupButton::Second => "button2",
+            PopupButton::Third => "button3",
+        }
+    }
+}
+
+pub struct Popup<'a> {
+    eo: *mut Eo,
+    _phantom: std::marker::PhantomData<&'a Eo>,
+}
+
+impl<'a> Popup<'a> {
+    pub fn new(parent: &mut dyn Object<'a>) -> Option<Popup<'a>> {
+        let eo = unsafe { elm_popup_add(parent.eo_ptr()) };
+
+        if eo.is_null() {
+            None
+        } else {
+            Some(Self {
+                eo,
+                _phantom: std::marker::PhantomData,
+            })
+        }
+    }
+
+    pub fn set_title(&mut self, title: &str) {
+        self.set_part_text("title,text", title)
+    }
+
+    pub fn set_orient(&mut self, orient: PopupOrient) {
+        unsafe { elm_popup_orient_set(self.eo_ptr(), orient.into()) }
+    }
+
+    /// Sets the time in seconds after which the popup emits "timeout", `0.0` disables it.
+    pub fn set_timeout(&mut self, timeout: f64) {
+        unsafe { elm_popup_timeout_set(self.eo_ptr(), timeout) }
+    }
+
+    pub fn get_timeout(&mut self) -> f64 {
+        unsafe { elm_popup_timeout_get(self.eo_ptr()) }
+    }
+
+    /// Adds a button with the given label in the slot, calling `callback_fn` when clicked.
+    pub fn set_button<F>(
+        &mut self,
+        slot: PopupButton,
+        text: &str,
+        callback_fn: F,
+    ) -> Option<RegisteredSmartCallback<'a>>
+    where
+        F: FnMut(),
+        F: 'a,
+    {
+        let mut button = Button::new(self)?;
+        button.set_text(text);
+        let rcb = button.on_clicked(callback_fn);
+        self.set_part_content(slot.part(), &mut button);
+
+        Some(rcb)
+    }
+
+    pub fn on_timeout<F>(&mut self, callback_fn: F) -> RegisteredSmartCallback<'a>
+    where
+        F: FnMut(),
+        F: 'a,
+    {
+        self.smart_callback_add("timeout", callback_fn)
+    }
+
+    /// Called when the user taps outside of the popup.
+    pub fn on_block_clicked<F>(&mut self, callback_fn: F) -> RegisteredSmartCallback<'a>
+    where
+        F: FnMut(),
+        F: 'a,
+    {
+        self.smart_callback_add("block,clicked", callback_fn)
+    }
+
+    /// Hides the popup with its hide animation, emitting "dismissed" when done.
+    pub fn dismiss(&mut self) {
+        unsafe { elm_popup_dismiss(self.eo_ptr()) }
+    }
+
+    /// Deletes the popup on its own once it is dismissed or times out.
+    ///
+    /// Only for popups without callbacks, whose guards would outlive the deleted object.
+    fn set_autodel(&mut self) {
+        unsafe {
+            for event in &["timeout\0", "block,clicked\0", "dismissed\0"] {
+                evas_object_smart_callback_add(
+                    self.eo,
+                    event.as_ptr() as *const _,
+                    Some(delete_object_handler),
+                    std::ptr::null(),
+                );
+            }
+        }
+    }
+}
+
+impl<'a> Object<'a> for Popup<'a> {
+    fn eo_ptr(&mut self) -> *mut Eo {
+        self.eo
+    }
+}
+
+/// A popup showing a progress indicator under a title, starting in pulsing mode.
+pub struct ProgressPopup<'a> {
+    pub popup: Popup<'a>,
+    pub progressbar: ProgressBar<'a>,
+}
+
+impl<'a> ProgressPopup<'a> {
+    pub fn new(parent: &mut dyn Object<'a>, text: &str) -> Option<ProgressPopup<'a>> {
+        let mut popup = Popup::new(parent)?;
+        popup.set_title(text);
+
+        let mut progressbar = ProgressBar::new(&mut popup)?;
+        progressbar.set_style("process");
+        progressbar.set_pulse(true);
+        progressbar.pulse(true);
+        progressbar.show();
+        popup.set_content(&mut progressbar);
+
+        Some(Self { popup, progressbar })
+    }
+
+    /// Leaves the pulsing mode to show a determinate `value`, from `0.0` to `1.0`.
+    pub fn set_value(&mut self, value: f64) {
+        if self.progressbar.get_pulse() {
+            self.progressbar.pulse(false);
+            self.progressbar.set_pulse(false);
+        }
+
+        self.progressbar.set_value(value)
+    }
+
+    pub fn show(&mut self) {
+        self.popup.show()
+    }
+
+    pub fn dismiss(&mut self) {
+        self.popup.dismiss()
+    }
+}
+
+/// Shows `text` in a toast popup over `parent`, deleted automatically after [`TOAST_TIMEOUT`].
+pub fn show_toast<'a>(parent: &mut dyn Object<'a>, text: &str) {
+    if let Some(mut popup) = Popup::new(parent) {
+        popup.set_style("toast");
+        popup.set_size_hint_weight(SizeHint::Expand, SizeHint::Expand);
+        popup.set_part_text("elm.text", text);
+        popup.set_timeout(TOAST_TIMEOUT);
+        popup.set_autodel();
+        popup.show();
+    }
+}
+
+extern "C" fn delete_object_handler(
+    _data: *mut c_void,
+    obj: *mut Evas_Object,
+    _event_info: *mut c_void,
+) {
+    unsafe { evas_object_del(obj) }
+}
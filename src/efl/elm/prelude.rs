@@ -1 +1,3 @@
-pub use crate::efl::evas::Object;
+pub use crate::efl::elm::ObjectWithSignals;
+pub use crate::efl::evas::{Object, SizeHint};
+pub use crate::efl::smart::{ObjectWithSmartCallbacks, RegisteredSmartCallback};
@@ -0,0 +1,54 @@
+use super::prelude::*;
+use rutin_tizen_sys::{
+    elm_progressbar_add, elm_progressbar_pulse, elm_progressbar_pulse_get,
+    elm_progressbar_pulse_set, elm_progressbar_value_get, elm_progressbar_value_set, Eo,
+};
+
+pub struct ProgressBar<'a> {
+    eo: *mut Eo,
+    _phantom: std::marker::PhantomData<&'a Eo>,
+}
+
+impl<'a> ProgressBar<'a> {
+    pub fn new(parent: &mut dyn Object<'a>) -> Option<ProgressBar<'a>> {
+        let eo = unsafe { elm_progressbar_add(parent.eo_ptr()) };
+
+        if eo.is_null() {
+            None
+        } else {
+            Some(Self {
+                eo,
+                _phantom: std::marker::PhantomData,
+            })
+        }
+    }
+
+    /// Sets the progress, from `0.0` to `1.0`.
+    pub fn set_value(&mut self, value: f64) {
+        unsafe { elm_progressbar_value_set(self.eo_ptr(), value) }
+    }
+
+    pub fn get_value(&mut self) -> f64 {
+        unsafe { elm_progressbar_value_get(self.eo_ptr()) }
+    }
+
+    /// Switches to the pulsing (indeterminate) mode, see [`ProgressBar::pulse`].
+    pub fn set_pulse(&mut self, value: bool) {
+        unsafe { elm_progressbar_pulse_set(self.eo_ptr(), if value { 1 } else { 0 }) }
+    }
+
+    pub fn get_pulse(&mut self) -> bool {
+        unsafe { elm_progressbar_pulse_get(self.eo_ptr()) != 0 }
+    }
+
+    /// Starts or stops the pulse animation.
+    pub fn pulse(&mut self, state: bool) {
+        unsafe { elm_progressbar_pulse(self.eo_ptr(), if state { 1 } else { 0 }) }
+    }
+}
+
+impl<'a> Object<'a> for ProgressBar<'a> {
+    fn eo_ptr(&mut self) -> *mut Eo {
+        self.eo
+    }
+}
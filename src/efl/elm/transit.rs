@@ -0,0 +1,174 @@
+use super::prelude::*;
+use crate::rutin::abort_on_panic;
+use rutin_tizen_sys::{
+    elm_transit_add, elm_transit_auto_reverse_set, elm_transit_chain_transit_add, elm_transit_del,
+    elm_transit_del_cb_set, elm_transit_duration_set, elm_transit_effect_color_add,
+    elm_transit_effect_fade_add, elm_transit_effect_rotation_add,
+    elm_transit_effect_translation_add, elm_transit_effect_zoom_add, elm_transit_go,
+    elm_transit_object_add, elm_transit_objects_final_state_keep_set, elm_transit_repeat_times_set,
+    elm_transit_tween_mode_set, Elm_Transit, Elm_Transit_Tween_Mode,
+    Elm_Transit_Tween_Mode_ELM_TRANSIT_TWEEN_MODE_ACCELERATE,
+    Elm_Transit_Tween_Mode_ELM_TRANSIT_TWEEN_MODE_DECELERATE,
+    Elm_Transit_Tween_Mode_ELM_TRANSIT_TWEEN_MODE_LINEAR,
+    Elm_Transit_Tween_Mode_ELM_TRANSIT_TWEEN_MODE_SINUSOIDAL,
+};
+use std::marker::PhantomData;
+use std::os::raw::c_void;
+
+pub enum TweenMode {
+    Linear,
+    Sinusoidal,
+    Decelerate,
+    Accelerate,
+}
+
+impl From<TweenMode> for Elm_Transit_Tween_Mode {
+    fn from(mode: TweenMode) -> Elm_Transit_Tween_Mode {
+        match mode {
+            TweenMode::Linear => Elm_Transit_Tween_Mode_ELM_TRANSIT_TWEEN_MODE_LINEAR,
+            TweenMode::Sinusoidal => Elm_Transit_Tween_Mode_ELM_TRANSIT_TWEEN_MODE_SINUSOIDAL,
+            TweenMode::Decelerate => Elm_Transit_Tween_Mode_ELM_TRANSIT_TWEEN_MODE_DECELERATE,
+            TweenMode::Accelerate => Elm_Transit_Tween_Mode_ELM_TRANSIT_TWEEN_MODE_ACCELERATE,
+        }
+    }
+}
+
+struct TransitData {
+    on_done: Option<Box<dyn FnOnce()>>,
+}
+
+/// An animation applied to one or more objects.
+///
+/// Elementary deletes the transit once it finishes, so starting it with
+/// [`Transit::go`] consumes the wrapper. Dropping a transit that was never
+/// started deletes it.
+pub struct Transit<'a> {
+    transit: *mut Elm_Transit,
+    data: *mut TransitData,
+    _objects: PhantomData<&'a ()>,
+}
+
+impl<'a> Transit<'a> {
+    pub fn new() -> Option<Transit<'a>> {
+        let transit = unsafe { elm_transit_add() };
+
+        if transit.is_null() {
+            return None;
+        }
+
+        let data = Box::into_raw(Box::new(TransitData { on_done: None }));
+
+        unsafe {
+            elm_transit_del_cb_set(transit, Some(transit_del_handler), data as *mut c_void);
+        }
+
+        Some(Self {
+            transit,
+            data,
+            _objects: PhantomData,
+        })
+    }
+
+    pub fn add_object(&mut self, obj: &mut dyn Object<'a>) {
+        unsafe { elm_transit_object_add(self.transit, obj.eo_ptr()) }
+    }
+
+    /// Sets the duration of a single run, in seconds.
+    pub fn set_duration(&mut self, duration: f64) {
+        unsafe { elm_transit_duration_set(self.transit, duration) }
+    }
+
+    /// Sets how many times the animation is repeated after the first run, `-1` repeats forever.
+    pub fn set_repeat_times(&mut self, repeat: i32) {
+        unsafe { elm_transit_repeat_times_set(self.transit, repeat) }
+    }
+
+    /// Plays the animation backwards after each forward run.
+    pub fn set_auto_reverse(&mut self, reverse: bool) {
+        unsafe { elm_transit_auto_reverse_set(self.transit, if reverse { 1 } else { 0 }) }
+    }
+
+    pub fn set_tween_mode(&mut self, mode: TweenMode) {
+        unsafe { elm_transit_tween_mode_set(self.transit, mode.into()) }
+    }
+
+    /// Keeps the objects in their final state instead of restoring them when done.
+    pub fn set_keep_final_state(&mut self, keep: bool) {
+        unsafe { elm_transit_objects_final_state_keep_set(self.transit, if keep { 1 } else { 0 }) }
+    }
+
+    pub fn add_translation(&mut self, from_dx: i32, from_dy: i32, to_dx: i32, to_dy: i32) {
+        unsafe {
+            elm_transit_effect_translation_add(self.transit, from_dx, from_dy, to_dx, to_dy);
+        }
+    }
+
+    /// Scales the objects, `1.0` being their current size.
+    pub fn add_zoom(&mut self, from_rate: f32, to_rate: f32) {
+        unsafe {
+            elm_transit_effect_zoom_add(self.transit, from_rate, to_rate);
+        }
+    }
+
+    /// Fades the objects between two opacities, from `0` (transparent) to `255` (opaque).
+    pub fn add_fade(&mut self, from_alpha: u8, to_alpha: u8) {
+        let (f, t) = (from_alpha as u32, to_alpha as u32);
+
+        // evas colors are premultiplied
+        unsafe {
+            elm_transit_effect_color_add(self.transit, f, f, f, f, t, t, t, t);
+        }
+    }
+
+    /// Cross-fades from the first object added to the second one.
+    pub fn add_crossfade(&mut self) {
+        unsafe {
+            elm_transit_effect_fade_add(self.transit);
+        }
+    }
+
+    /// Rotates the objects around their center, in degrees.
+    pub fn add_rotation(&mut self, from_degree: f32, to_degree: f32) {
+        unsafe {
+            elm_transit_effect_rotation_add(self.transit, from_degree, to_degree);
+        }
+    }
+
+    /// Starts `next` automatically once this transit is done.
+    pub fn chain(&mut self, next: Transit<'a>) {
+        unsafe { elm_transit_chain_transit_add(self.transit, next.transit) }
+        std::mem::forget(next);
+    }
+
+    /// Calls `callback_fn` once the transit is done and deleted.
+    ///
+    /// The callback must be `'static`, since the transit outlives the wrapper once started.
+    pub fn on_done<F>(&mut self, callback_fn: F)
+    where
+        F: FnOnce(),
+        F: 'static,
+    {
+        unsafe { (*self.data).on_done = Some(Box::new(callback_fn)) }
+    }
+
+    pub fn go(self) {
+        unsafe { elm_transit_go(self.transit) }
+        std::mem::forget(self);
+    }
+}
+
+impl<'a> Drop for Transit<'a> {
+    fn drop(&mut self) {
+        unsafe { elm_transit_del(self.transit) }
+    }
+}
+
+extern "C" fn transit_del_handler(data: *mut c_void, _transit: *mut Elm_Transit) {
+    abort_on_panic(|| {
+        let data = unsafe { Box::from_raw(data as *mut TransitData) };
+
+        if let Some(on_done) = data.on_done {
+            on_done();
+        }
+    })
+}
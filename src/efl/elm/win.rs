@@ -1,9 +1,12 @@
 use super::prelude::*;
 use crate::rutin::rutin_debug;
 use rutin_tizen_sys::{
-    elm_win_autodel_get, elm_win_autodel_set, elm_win_indicator_mode_set, elm_win_lower,
-    elm_win_resize_object_add, elm_win_util_standard_add, elm_win_wm_rotation_supported_get,
-    evas_free, Elm_Win_Indicator_Mode, Elm_Win_Indicator_Mode_ELM_WIN_INDICATOR_HIDE,
+    elm_win_autodel_get, elm_win_autodel_set, elm_win_indicator_mode_get,
+    elm_win_indicator_mode_set, elm_win_indicator_opacity_get, elm_win_indicator_opacity_set,
+    elm_win_lower, elm_win_resize_object_add, elm_win_rotation_get, elm_win_util_standard_add,
+    elm_win_wm_rotation_available_rotations_set, elm_win_wm_rotation_preferred_rotation_get,
+    elm_win_wm_rotation_preferred_rotation_set, elm_win_wm_rotation_supported_get, evas_free,
+    Elm_Win_Indicator_Mode, Elm_Win_Indicator_Mode_ELM_WIN_INDICATOR_HIDE,
     Elm_Win_Indicator_Mode_ELM_WIN_INDICATOR_SHOW,
     Elm_Win_Indicator_Mode_ELM_WIN_INDICATOR_UNKNOWN, Elm_Win_Indicator_Opacity_Mode,
     Elm_Win_Indicator_Opacity_Mode_ELM_WIN_INDICATOR_BG_TRANSPARENT,
@@ -13,7 +16,10 @@ use rutin_tizen_sys::{
     Elm_Win_Indicator_Opacity_Mode_ELM_WIN_INDICATOR_TRANSPARENT, Eo,
 };
 use std::ffi::CString;
+use std::os::raw::c_int;
 
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum IndicatorMode {
     Unknown,
     Hide,
@@ -30,6 +36,18 @@ impl From<IndicatorMode> for Elm_Win_Indicator_Mode {
     }
 }
 
+impl From<Elm_Win_Indicator_Mode> for IndicatorMode {
+    fn from(indicator_mode: Elm_Win_Indicator_Mode) -> IndicatorMode {
+        match indicator_mode {
+            Elm_Win_Indicator_Mode_ELM_WIN_INDICATOR_HIDE => IndicatorMode::Hide,
+            Elm_Win_Indicator_Mode_ELM_WIN_INDICATOR_SHOW => IndicatorMode::Show,
+            _ => IndicatorMode::Unknown,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum IndicatorOpacityMode {
     Unknown,
     Opaque,
@@ -58,6 +76,57 @@ impl From<IndicatorOpacityMode> for Elm_Win_Indicator_Opacity_Mode {
     }
 }
 
+impl From<Elm_Win_Indicator_Opacity_Mode> for IndicatorOpacityMode {
+    fn from(indicator: Elm_Win_Indicator_Opacity_Mode) -> IndicatorOpacityMode {
+        match indicator {
+            Elm_Win_Indicator_Opacity_Mode_ELM_WIN_INDICATOR_OPAQUE => IndicatorOpacityMode::Opaque,
+            Elm_Win_Indicator_Opacity_Mode_ELM_WIN_INDICATOR_TRANSLUCENT => {
+                IndicatorOpacityMode::Translucent
+            }
+            Elm_Win_Indicator_Opacity_Mode_ELM_WIN_INDICATOR_TRANSPARENT => {
+                IndicatorOpacityMode::Transparent
+            }
+            Elm_Win_Indicator_Opacity_Mode_ELM_WIN_INDICATOR_BG_TRANSPARENT => {
+                IndicatorOpacityMode::BgTransparent
+            }
+            _ => IndicatorOpacityMode::Unknown,
+        }
+    }
+}
+
+/// Orientation of a window, clockwise from the natural orientation of the device.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Rotation {
+    Deg0,
+    Deg90,
+    Deg180,
+    Deg270,
+}
+
+impl From<Rotation> for c_int {
+    fn from(rotation: Rotation) -> c_int {
+        match rotation {
+            Rotation::Deg0 => 0,
+            Rotation::Deg90 => 90,
+            Rotation::Deg180 => 180,
+            Rotation::Deg270 => 270,
+        }
+    }
+}
+
+impl Rotation {
+    fn from_degrees(degrees: c_int) -> Option<Rotation> {
+        match degrees.rem_euclid(360) {
+            0 => Some(Rotation::Deg0),
+            90 => Some(Rotation::Deg90),
+            180 => Some(Rotation::Deg180),
+            270 => Some(Rotation::Deg270),
+            _ => None,
+        }
+    }
+}
+
 pub struct Win<'a> {
     eo: *mut Eo,
     _phantom: std::marker::PhantomData<&'a Eo>,
@@ -72,11 +141,16 @@ impl<'a> Win<'a> {
 
         rutin_debug(&format!("win created pointer: {:p}", win));
 
-        if win.is_null() {
+        Self::from_eo(win)
+    }
+
+    /// Wraps a window created by another native API.
+    pub(crate) fn from_eo(eo: *mut Eo) -> Option<Self> {
+        if eo.is_null() {
             None
         } else {
             Some(Self {
-                eo: win,
+                eo,
                 _phantom: std::marker::PhantomData,
             })
         }
@@ -90,8 +164,16 @@ impl<'a> Win<'a> {
         unsafe { elm_win_indicator_mode_set(self.eo_ptr(), indicator.into()) }
     }
 
+    pub fn get_indicator_mode(&mut self) -> IndicatorMode {
+        unsafe { elm_win_indicator_mode_get(self.eo_ptr()) }.into()
+    }
+
     pub fn set_indicator_opacity(&mut self, indicator: IndicatorOpacityMode) {
-        unsafe { elm_win_indicator_mode_set(self.eo_ptr(), indicator.into()) }
+        unsafe { elm_win_indicator_opacity_set(self.eo_ptr(), indicator.into()) }
+    }
+
+    pub fn get_indicator_opacity(&mut self) -> IndicatorOpacityMode {
+        unsafe { elm_win_indicator_opacity_get(self.eo_ptr()) }.into()
     }
 
     pub fn set_autodel(&mut self, value: bool) {
@@ -111,6 +193,54 @@ impl<'a> Win<'a> {
     pub fn get_rotation_supported(&mut self) -> bool {
         unsafe { elm_win_wm_rotation_supported_get(self.eo_ptr()) != 0 }
     }
+
+    /// Declares the orientations the window can be rotated to by the window manager.
+    pub fn set_available_rotations(&mut self, rotations: &[Rotation]) {
+        let rotations = rotations
+            .iter()
+            .map(|r| c_int::from(*r))
+            .collect::<Vec<_>>();
+
+        unsafe {
+            elm_win_wm_rotation_available_rotations_set(
+                self.eo_ptr(),
+                rotations.as_ptr(),
+                rotations.len() as u32,
+            )
+        }
+    }
+
+    /// Asks the window manager to keep the window in a single orientation,
+    /// `None` lets it follow the device again.
+    pub fn set_preferred_rotation(&mut self, rotation: Option<Rotation>) {
+        let rotation = rotation.map(c_int::from).unwrap_or(-1);
+
+        unsafe { elm_win_wm_rotation_preferred_rotation_set(self.eo_ptr(), rotation) }
+    }
+
+    pub fn get_preferred_rotation(&mut self) -> Option<Rotation> {
+        Rotation::from_degrees(unsafe { elm_win_wm_rotation_preferred_rotation_get(self.eo_ptr()) })
+    }
+
+    pub fn get_rotation(&mut self) -> Rotation {
+        Rotation::from_degrees(unsafe { elm_win_rotation_get(self.eo_ptr()) })
+            .unwrap_or(Rotation::Deg0)
+    }
+
+    /// Calls `callback_fn` with the new orientation after the window manager rotated the window.
+    pub fn on_rotation_changed<F>(&mut self, mut callback_fn: F) -> RegisteredSmartCallback<'a>
+    where
+        F: FnMut(Rotation),
+        F: 'a,
+    {
+        let eo = self.eo;
+
+        self.smart_callback_add("wm,rotation,changed", move || {
+            let rotation = Rotation::from_degrees(unsafe { elm_win_rotation_get(eo) })
+                .unwrap_or(Rotation::Deg0);
+            callback_fn(rotation)
+        })
+    }
 }
 
 impl<'a> Object<'a> for Win<'a> {
@@ -118,3 +248,53 @@ impl<'a> Object<'a> for Win<'a> {
         self.eo
     }
 }
+
+/// Exposes the Wayland surface and display of the window, to create EGL or
+/// Vulkan contexts with crates such as `wgpu` or `glow`.
+#[cfg(feature = "raw-window-handle")]
+mod wayland_handle {
+    use super::Win;
+    use raw_window_handle::{
+        DisplayHandle, HandleError, HasDisplayHandle, HasWindowHandle, RawDisplayHandle,
+        RawWindowHandle, WaylandDisplayHandle, WaylandWindowHandle, WindowHandle,
+    };
+    use rutin_tizen_sys::{
+        ecore_wl2_display_get, ecore_wl2_window_display_get, ecore_wl2_window_surface_get,
+        elm_win_wl_window_get, Ecore_Wl2_Window,
+    };
+    use std::os::raw::c_void;
+    use std::ptr::NonNull;
+
+    impl<'a> Win<'a> {
+        fn wl2_window(&self) -> Result<*mut Ecore_Wl2_Window, HandleError> {
+            let window = unsafe { elm_win_wl_window_get(self.eo) } as *mut Ecore_Wl2_Window;
+
+            if window.is_null() {
+                Err(HandleError::Unavailable)
+            } else {
+                Ok(window)
+            }
+        }
+    }
+
+    impl<'a> HasWindowHandle for Win<'a> {
+        fn window_handle(&self) -> Result<WindowHandle<'_>, HandleError> {
+            let surface = unsafe { ecore_wl2_window_surface_get(self.wl2_window()?) };
+            let surface = NonNull::new(surface as *mut c_void).ok_or(HandleError::Unavailable)?;
+            let raw = RawWindowHandle::Wayland(WaylandWindowHandle::new(surface));
+
+            Ok(unsafe { WindowHandle::borrow_raw(raw) })
+        }
+    }
+
+    impl<'a> HasDisplayHandle for Win<'a> {
+        fn display_handle(&self) -> Result<DisplayHandle<'_>, HandleError> {
+            let display = unsafe { ecore_wl2_window_display_get(self.wl2_window()?) };
+            let display = unsafe { ecore_wl2_display_get(display) };
+            let display = NonNull::new(display as *mut c_void).ok_or(HandleError::Unavailable)?;
+            let raw = RawDisplayHandle::Wayland(WaylandDisplayHandle::new(display));
+
+            Ok(unsafe { DisplayHandle::borrow_raw(raw) })
+        }
+    }
+}
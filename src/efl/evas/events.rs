@@ -0,0 +1,261 @@
+use crate::efl::evas::Object;
+use crate::rutin::abort_on_panic;
+use rutin_tizen_sys::{
+    _Evas_Callback_Type_EVAS_CALLBACK_MOUSE_DOWN, _Evas_Callback_Type_EVAS_CALLBACK_MOUSE_MOVE,
+    _Evas_Callback_Type_EVAS_CALLBACK_MOUSE_UP, _Evas_Callback_Type_EVAS_CALLBACK_MULTI_DOWN,
+    _Evas_Callback_Type_EVAS_CALLBACK_MULTI_MOVE, _Evas_Callback_Type_EVAS_CALLBACK_MULTI_UP,
+    evas_object_event_callback_add, evas_object_event_callback_del_full, Eo, Evas,
+    Evas_Callback_Type, Evas_Event_Mouse_Down, Evas_Event_Mouse_Move, Evas_Event_Mouse_Up,
+    Evas_Event_Multi_Down, Evas_Event_Multi_Move, Evas_Event_Multi_Up, Evas_Object,
+};
+use std::os::raw::c_void;
+use std::pin::Pin;
+
+/// An input event that can be received from an Evas object.
+pub trait InputEvent: Sized {
+    const CALLBACK_TYPE: Evas_Callback_Type;
+
+    /// Builds the event from the `event_info` pointer passed to the callback.
+    unsafe fn from_event_info(event_info: *mut c_void) -> Self;
+}
+
+/// The first finger touching the object (or a mouse button press).
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MouseDown {
+    pub button: i32,
+    pub x: i32,
+    pub y: i32,
+    pub timestamp: u32,
+}
+
+impl InputEvent for MouseDown {
+    const CALLBACK_TYPE: Evas_Callback_Type = _Evas_Callback_Type_EVAS_CALLBACK_MOUSE_DOWN;
+
+    unsafe fn from_event_info(event_info: *mut c_void) -> Self {
+        let ev = &*(event_info as *const Evas_Event_Mouse_Down);
+
+        Self {
+            button: ev.button,
+            x: ev.canvas.x,
+            y: ev.canvas.y,
+            timestamp: ev.timestamp,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MouseUp {
+    pub button: i32,
+    pub x: i32,
+    pub y: i32,
+    pub timestamp: u32,
+}
+
+impl InputEvent for MouseUp {
+    const CALLBACK_TYPE: Evas_Callback_Type = _Evas_Callback_Type_EVAS_CALLBACK_MOUSE_UP;
+
+    unsafe fn from_event_info(event_info: *mut c_void) -> Self {
+        let ev = &*(event_info as *const Evas_Event_Mouse_Up);
+
+        Self {
+            button: ev.button,
+            x: ev.canvas.x,
+            y: ev.canvas.y,
+            timestamp: ev.timestamp,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MouseMove {
+    pub buttons: i32,
+    pub x: i32,
+    pub y: i32,
+    pub prev_x: i32,
+    pub prev_y: i32,
+    pub timestamp: u32,
+}
+
+impl InputEvent for MouseMove {
+    const CALLBACK_TYPE: Evas_Callback_Type = _Evas_Callback_Type_EVAS_CALLBACK_MOUSE_MOVE;
+
+    unsafe fn from_event_info(event_info: *mut c_void) -> Self {
+        let ev = &*(event_info as *const Evas_Event_Mouse_Move);
+
+        Self {
+            buttons: ev.buttons,
+            x: ev.cur.canvas.x,
+            y: ev.cur.canvas.y,
+            prev_x: ev.prev.canvas.x,
+            prev_y: ev.prev.canvas.y,
+            timestamp: ev.timestamp,
+        }
+    }
+}
+
+/// Any additional finger touching the object, `device` identifies the finger.
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MultiDown {
+    pub device: i32,
+    pub x: i32,
+    pub y: i32,
+    pub radius: f64,
+    pub pressure: f64,
+    pub angle: f64,
+    pub timestamp: u32,
+}
+
+impl InputEvent for MultiDown {
+    const CALLBACK_TYPE: Evas_Callback_Type = _Evas_Callback_Type_EVAS_CALLBACK_MULTI_DOWN;
+
+    unsafe fn from_event_info(event_info: *mut c_void) -> Self {
+        let ev = &*(event_info as *const Evas_Event_Multi_Down);
+
+        Self {
+            device: ev.device,
+            x: ev.canvas.x,
+            y: ev.canvas.y,
+            radius: ev.radius,
+            pressure: ev.pressure,
+            angle: ev.angle,
+            timestamp: ev.timestamp,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MultiUp {
+    pub device: i32,
+    pub x: i32,
+    pub y: i32,
+    pub radius: f64,
+    pub pressure: f64,
+    pub angle: f64,
+    pub timestamp: u32,
+}
+
+impl InputEvent for MultiUp {
+    const CALLBACK_TYPE: Evas_Callback_Type = _Evas_Callback_Type_EVAS_CALLBACK_MULTI_UP;
+
+    unsafe fn from_event_info(event_info: *mut c_void) -> Self {
+        let ev = &*(event_info as *const Evas_Event_Multi_Up);
+
+        Self {
+            device: ev.device,
+            x: ev.canvas.x,
+            y: ev.canvas.y,
+            radius: ev.radius,
+            pressure: ev.pressure,
+            angle: ev.angle,
+            timestamp: ev.timestamp,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MultiMove {
+    pub device: i32,
+    pub x: i32,
+    pub y: i32,
+    pub radius: f64,
+    pub pressure: f64,
+    pub angle: f64,
+    pub timestamp: u32,
+}
+
+impl InputEvent for MultiMove {
+    const CALLBACK_TYPE: Evas_Callback_Type = _Evas_Callback_Type_EVAS_CALLBACK_MULTI_MOVE;
+
+    unsafe fn from_event_info(event_info: *mut c_void) -> Self {
+        let ev = &*(event_info as *const Evas_Event_Multi_Move);
+
+        Self {
+            device: ev.device,
+            x: ev.cur.canvas.x,
+            y: ev.cur.canvas.y,
+            radius: ev.radius,
+            pressure: ev.pressure,
+            angle: ev.angle,
+            timestamp: ev.timestamp,
+        }
+    }
+}
+
+/// An input callback registered on an object, removed when this is dropped.
+pub struct RegisteredInputCallback<'a, E: InputEvent> {
+    eo: *mut Eo,
+    callback_fn: Pin<Box<Box<dyn FnMut(E) + 'a>>>,
+}
+
+impl<'a, E: InputEvent> RegisteredInputCallback<'a, E> {
+    fn data_ptr(&mut self) -> *mut c_void {
+        let callback_fn_ref = Pin::as_mut(&mut self.callback_fn);
+        let box_ref = unsafe { Pin::get_unchecked_mut(callback_fn_ref) };
+        box_ref as *mut Box<dyn FnMut(E) + 'a> as *mut c_void
+    }
+}
+
+impl<'a, E: InputEvent> Drop for RegisteredInputCallback<'a, E> {
+    fn drop(&mut self) {
+        let data = self.data_ptr();
+
+        unsafe {
+            evas_object_event_callback_del_full(
+                self.eo,
+                E::CALLBACK_TYPE,
+                Some(input_callback_handler::<E>),
+                data,
+            );
+        }
+    }
+}
+
+pub trait ObjectWithInputEvents<'a>: Object<'a> {
+    /// Calls `callback_fn` for every event of type `E` on the object, e.g.
+    /// `obj.input_callback_add(|ev: MouseDown| ...)`.
+    fn input_callback_add<E, F>(&mut self, callback_fn: F) -> RegisteredInputCallback<'a, E>
+    where
+        E: InputEvent,
+        F: FnMut(E),
+        F: 'a,
+    {
+        let mut rcb = RegisteredInputCallback {
+            eo: self.eo_ptr(),
+            callback_fn: Box::pin(Box::new(callback_fn)),
+        };
+
+        let data = rcb.data_ptr();
+
+        unsafe {
+            evas_object_event_callback_add(
+                rcb.eo,
+                E::CALLBACK_TYPE,
+                Some(input_callback_handler::<E>),
+                data,
+            );
+        }
+
+        rcb
+    }
+}
+
+impl<'a, O: Object<'a>> ObjectWithInputEvents<'a> for O {}
+
+extern "C" fn input_callback_handler<E: InputEvent>(
+    data: *mut c_void,
+    _evas: *mut Evas,
+    _obj: *mut Evas_Object,
+    event_info: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(data as *mut Box<dyn FnMut(E)>) };
+        let event = unsafe { E::from_event_info(event_info) };
+        closure(event);
+    })
+}
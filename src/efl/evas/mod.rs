@@ -0,0 +1,80 @@
+pub mod events;
+pub mod surface;
+
+use crate::rutin::rutin_debug;
+use rutin_tizen_sys::{
+    elm_object_part_content_set, elm_object_part_text_set, elm_object_style_set, evas_free,
+    evas_object_del, evas_object_hide, evas_object_show, evas_object_size_hint_weight_set, Eo,
+    EVAS_HINT_EXPAND, EVAS_HINT_FILL,
+};
+use std::ffi::CString;
+
+pub enum SizeHint {
+    Expand,
+    Fill,
+    Other(f64),
+}
+
+impl From<SizeHint> for f64 {
+    fn from(hint: SizeHint) -> f64 {
+        match hint {
+            SizeHint::Expand => EVAS_HINT_EXPAND,
+            SizeHint::Fill => EVAS_HINT_FILL,
+            SizeHint::Other(v) => v,
+        }
+    }
+}
+
+pub trait Object<'a> {
+    fn eo_ptr(&mut self) -> *mut Eo;
+
+    fn show(&mut self) {
+        rutin_debug(&format!("show evas object {:p}", self.eo_ptr()));
+        unsafe { evas_object_show(self.eo_ptr()) }
+    }
+
+    fn hide(&mut self) {
+        unsafe { evas_object_hide(self.eo_ptr()) }
+    }
+
+    /// Deletes the underlying object, the wrapper must not be used afterwards.
+    fn del(&mut self) {
+        rutin_debug(&format!("del evas object {:p}", self.eo_ptr()));
+        unsafe { evas_object_del(self.eo_ptr()) }
+    }
+
+    fn set_size_hint_weight(&mut self, x: SizeHint, y: SizeHint) {
+        unsafe { evas_object_size_hint_weight_set(self.eo_ptr(), x.into(), y.into()) }
+    }
+
+    fn set_style(&mut self, style: &str) {
+        let style = CString::new(style).unwrap();
+
+        unsafe {
+            elm_object_style_set(self.eo_ptr(), style.as_ptr());
+        }
+    }
+
+    fn set_text(&mut self, text: &str) {
+        let text = CString::new(text).unwrap();
+
+        unsafe { elm_object_part_text_set(self.eo_ptr(), std::ptr::null(), text.as_ptr()) }
+    }
+
+    fn set_part_text(&mut self, part: &str, text: &str) {
+        let part = CString::new(part).unwrap();
+        let text = CString::new(text).unwrap();
+
+        unsafe { elm_object_part_text_set(self.eo_ptr(), part.as_ptr(), text.as_ptr()) }
+    }
+
+    fn set_content(&mut self, content: &mut dyn Object<'a>) {
+        unsafe { elm_object_part_content_set(self.eo_ptr(), std::ptr::null(), content.eo_ptr()) }
+    }
+
+    fn set_part_content(&mut self, part: &str, content: &mut dyn Object<'a>) {
+        let part = CString::new(part).unwrap();
+
+        unsafe { elm_object_part_content_set(self.eo_ptr(), part.as_ptr(), content.eo_ptr()) }
+    }
+}
@@ -0,0 +1,176 @@
+use crate::efl::evas::Object;
+use crate::error::{Error, Result};
+use crate::rutin::abort_on_panic;
+use rutin_tizen_sys::{
+    evas_object_del, evas_object_evas_get, evas_object_image_alpha_set, evas_object_image_data_set,
+    evas_object_image_data_update_add, evas_object_image_filled_add,
+    evas_object_image_pixels_dirty_set, evas_object_image_pixels_get_callback_set,
+    evas_object_image_size_set, Eo, Evas_Object,
+};
+use std::os::raw::{c_int, c_void};
+
+struct SurfaceState<'a> {
+    eo: *mut Eo,
+    width: u32,
+    height: u32,
+    /// Premultiplied RGBA, as handed out by `map()`.
+    pixels: Vec<u8>,
+    /// Native endian premultiplied ARGB words, as read by Evas.
+    backing: Vec<u32>,
+    redraw_fn: Option<Box<dyn FnMut(&mut [u8], u32, u32) + 'a>>,
+}
+
+impl<'a> SurfaceState<'a> {
+    fn flush(&mut self) {
+        for (dst, src) in self.backing.iter_mut().zip(self.pixels.chunks_exact(4)) {
+            *dst = (src[3] as u32) << 24
+                | (src[0] as u32) << 16
+                | (src[1] as u32) << 8
+                | src[2] as u32;
+        }
+
+        unsafe {
+            evas_object_image_data_set(self.eo, self.backing.as_mut_ptr() as *mut c_void);
+            evas_object_image_data_update_add(
+                self.eo,
+                0,
+                0,
+                self.width as c_int,
+                self.height as c_int,
+            );
+        }
+    }
+}
+
+/// An image object drawing a Rust owned pixel buffer, for software renderers.
+///
+/// The buffer is premultiplied RGBA, 4 bytes per pixel with no row padding.
+/// Draw into [`PixelSurface::map`] and call [`PixelSurface::flush`] to show it,
+/// or register [`PixelSurface::on_redraw`] to draw when the canvas renders
+/// the next frame. The image object is deleted when the surface is dropped.
+pub struct PixelSurface<'a> {
+    state: Box<SurfaceState<'a>>,
+}
+
+impl<'a> PixelSurface<'a> {
+    pub fn new(parent: &mut dyn Object<'a>, width: u32, height: u32) -> Option<PixelSurface<'a>> {
+        let eo = unsafe { evas_object_image_filled_add(evas_object_evas_get(parent.eo_ptr())) };
+
+        if eo.is_null() {
+            return None;
+        }
+
+        unsafe { evas_object_image_alpha_set(eo, 1) };
+
+        let mut surface = Self {
+            state: Box::new(SurfaceState {
+                eo,
+                width: 0,
+                height: 0,
+                pixels: Vec::new(),
+                backing: Vec::new(),
+                redraw_fn: None,
+            }),
+        };
+        surface.resize(width, height).ok()?;
+
+        Some(surface)
+    }
+
+    pub fn width(&self) -> u32 {
+        self.state.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.state.height
+    }
+
+    /// Changes the buffer size, clearing its contents.
+    ///
+    /// Fails with [`Error::InvalidParameter`] if the buffer of that size cannot be addressed.
+    pub fn resize(&mut self, width: u32, height: u32) -> Result<()> {
+        if width > c_int::MAX as u32 || height > c_int::MAX as u32 {
+            return Err(Error::InvalidParameter);
+        }
+
+        let len = (width as usize)
+            .checked_mul(height as usize)
+            .filter(|len| len.checked_mul(4).is_some())
+            .ok_or(Error::InvalidParameter)?;
+        let state = &mut self.state;
+
+        state.width = width;
+        state.height = height;
+        state.pixels = vec![0; len * 4];
+        state.backing = vec![0; len];
+
+        unsafe {
+            evas_object_image_size_set(state.eo, width as c_int, height as c_int);
+        }
+        state.flush();
+
+        Ok(())
+    }
+
+    /// Gives access to the pixels, changes are shown after [`PixelSurface::flush`].
+    pub fn map(&mut self) -> &mut [u8] {
+        &mut self.state.pixels
+    }
+
+    pub fn flush(&mut self) {
+        self.state.flush()
+    }
+
+    /// Calls `redraw_fn` with the pixels, width and height each time a redraw
+    /// was requested and the canvas renders a frame, which keeps drawing in
+    /// step with the display refresh. The pixels are flushed afterwards.
+    pub fn on_redraw<F>(&mut self, redraw_fn: F)
+    where
+        F: FnMut(&mut [u8], u32, u32),
+        F: 'a,
+    {
+        let state = &mut *self.state;
+        state.redraw_fn = Some(Box::new(redraw_fn));
+
+        unsafe {
+            evas_object_image_pixels_get_callback_set(
+                state.eo,
+                Some(pixels_get_handler),
+                state as *mut SurfaceState<'a> as *mut c_void,
+            );
+        }
+    }
+
+    /// Schedules a call to the redraw callback for the next frame.
+    pub fn request_redraw(&mut self) {
+        unsafe { evas_object_image_pixels_dirty_set(self.state.eo, 1) }
+    }
+}
+
+impl<'a> Object<'a> for PixelSurface<'a> {
+    fn eo_ptr(&mut self) -> *mut Eo {
+        self.state.eo
+    }
+}
+
+impl<'a> Drop for PixelSurface<'a> {
+    fn drop(&mut self) {
+        unsafe {
+            evas_object_image_pixels_get_callback_set(self.state.eo, None, std::ptr::null_mut());
+            evas_object_image_data_set(self.state.eo, std::ptr::null_mut());
+            evas_object_del(self.state.eo);
+        }
+    }
+}
+
+extern "C" fn pixels_get_handler(data: *mut c_void, _obj: *mut Evas_Object) {
+    abort_on_panic(|| {
+        let state = unsafe { &mut *(data as *mut SurfaceState) };
+
+        if let Some(redraw_fn) = state.redraw_fn.as_mut() {
+            redraw_fn(&mut state.pixels, state.width, state.height);
+        }
+
+        state.flush();
+    })
+}
@@ -1,19 +1,18 @@
 use crate::efl::evas::Object;
-use crate::rutin::rutin_debug;
+use crate::rutin::{abort_on_panic, rutin_debug};
 use rutin_tizen_sys::Evas_Object;
 use std::cell::{RefCell, RefMut};
 use std::mem;
 use std::os::raw::c_void;
-use std::panic::catch_unwind;
 use std::pin::Pin;
 
 use rutin_tizen_sys::{
-    Eext_Callback_Type, _Eext_Callback_Type_EEXT_CALLBACK_BACK,
-    _Eext_Callback_Type_EEXT_CALLBACK_LAST, _Eext_Callback_Type_EEXT_CALLBACK_MORE,
-    eext_object_event_callback_add,
+    _Eext_Callback_Type_EEXT_CALLBACK_BACK, _Eext_Callback_Type_EEXT_CALLBACK_LAST,
+    _Eext_Callback_Type_EEXT_CALLBACK_MORE, eext_object_event_callback_add, Eext_Callback_Type,
 };
 
 #[derive(Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CallbackType {
     Back,
     More,
@@ -86,14 +85,8 @@ extern "C" fn event_callback_handler(
     obj: *mut Evas_Object,
     event_info: *mut c_void,
 ) {
-    if let Err(e) = catch_unwind(|| {
+    abort_on_panic(|| {
         let closure: &mut Box<dyn FnMut()> = unsafe { mem::transmute(data) };
         closure();
-    }) {
-        match e.downcast_ref::<&'static str>() {
-            Some(s) => rutin_debug(&format!("panic: {:?}", s)),
-            None => rutin_debug(&format!("panic: unknown {:?}", e)),
-        };
-        std::process::abort();
-    }
+    })
 }
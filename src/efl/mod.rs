@@ -1,3 +1,8 @@
+pub mod ecore;
 pub mod elm;
 pub mod evas;
 pub mod ext;
+pub mod smart;
+pub mod util;
+#[cfg(feature = "webview")]
+pub mod webview;
@@ -0,0 +1,98 @@
+use crate::efl::evas::Object;
+use crate::rutin::abort_on_panic;
+use rutin_tizen_sys::{
+    evas_object_smart_callback_add, evas_object_smart_callback_del_full, Eo, Evas_Object,
+};
+use std::ffi::CString;
+use std::mem;
+use std::os::raw::c_void;
+use std::pin::Pin;
+
+/// A smart callback ("clicked", "timeout", ...) registered on an object.
+///
+/// The callback is removed from the object when this is dropped.
+pub struct RegisteredSmartCallback<'a> {
+    eo: *mut Eo,
+    event: CString,
+    callback_fn: Pin<Box<Box<dyn FnMut(*mut c_void) + 'a>>>,
+}
+
+impl<'a> RegisteredSmartCallback<'a> {
+    fn data_ptr(&mut self) -> *mut c_void {
+        let callback_fn_ref = Pin::as_mut(&mut self.callback_fn);
+        let box_ref = unsafe { Pin::get_unchecked_mut(callback_fn_ref) };
+        box_ref as *mut Box<dyn FnMut(*mut c_void) + 'a> as *mut c_void
+    }
+}
+
+impl<'a> Drop for RegisteredSmartCallback<'a> {
+    fn drop(&mut self) {
+        let data = self.data_ptr();
+
+        unsafe {
+            evas_object_smart_callback_del_full(
+                self.eo,
+                self.event.as_ptr(),
+                Some(smart_callback_handler),
+                data,
+            );
+        }
+    }
+}
+
+pub trait ObjectWithSmartCallbacks<'a>: Object<'a> {
+    fn smart_callback_add<F>(&mut self, event: &str, callback_fn: F) -> RegisteredSmartCallback<'a>
+    where
+        F: FnMut(),
+        F: 'a,
+    {
+        let mut callback_fn = callback_fn;
+
+        smart_callback_add_with_info(self, event, move |_| callback_fn())
+    }
+}
+
+impl<'a, O: Object<'a>> ObjectWithSmartCallbacks<'a> for O {}
+
+/// Registers a callback receiving the `event_info` of the event, whose type depends on
+/// the event.
+pub(crate) fn smart_callback_add_with_info<'a, O, F>(
+    object: &mut O,
+    event: &str,
+    callback_fn: F,
+) -> RegisteredSmartCallback<'a>
+where
+    O: Object<'a> + ?Sized,
+    F: FnMut(*mut c_void),
+    F: 'a,
+{
+    let mut rcb = RegisteredSmartCallback {
+        eo: object.eo_ptr(),
+        event: CString::new(event).unwrap(),
+        callback_fn: Box::pin(Box::new(callback_fn)),
+    };
+
+    let data = rcb.data_ptr();
+
+    unsafe {
+        evas_object_smart_callback_add(
+            rcb.eo,
+            rcb.event.as_ptr(),
+            Some(smart_callback_handler),
+            data,
+        );
+    }
+
+    rcb
+}
+
+extern "C" fn smart_callback_handler(
+    data: *mut c_void,
+    _obj: *mut Evas_Object,
+    event_info: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let closure: &mut Box<dyn FnMut(*mut c_void)> = unsafe { mem::transmute(data) };
+        closure(event_info);
+    })
+}
@@ -0,0 +1,263 @@
+use crate::efl::elm::Win;
+use crate::efl::evas::Object;
+use crate::error::{check, last_error, Error, Result};
+use crate::system::info::get_platform_int;
+use rutin_tizen_sys::{
+    efl_util_get_notification_window_level, efl_util_get_window_screen_mode,
+    efl_util_notification_level_e,
+    efl_util_notification_level_e_EFL_UTIL_NOTIFICATION_LEVEL_DEFAULT,
+    efl_util_notification_level_e_EFL_UTIL_NOTIFICATION_LEVEL_HIGH,
+    efl_util_notification_level_e_EFL_UTIL_NOTIFICATION_LEVEL_MEDIUM,
+    efl_util_notification_level_e_EFL_UTIL_NOTIFICATION_LEVEL_NONE,
+    efl_util_notification_level_e_EFL_UTIL_NOTIFICATION_LEVEL_TOP, efl_util_screen_mode_e,
+    efl_util_screen_mode_e_EFL_UTIL_SCREEN_MODE_ALWAYS_ON,
+    efl_util_screen_mode_e_EFL_UTIL_SCREEN_MODE_DEFAULT, efl_util_screenshot_deinitialize,
+    efl_util_screenshot_h, efl_util_screenshot_initialize, efl_util_screenshot_take_tbm_surface,
+    efl_util_set_notification_window_level, efl_util_set_window_screen_mode, tbm_surface_destroy,
+    tbm_surface_info_s, tbm_surface_map, tbm_surface_unmap, TBM_SURF_OPTION_READ,
+};
+use std::convert::TryFrom;
+use std::os::raw::c_int;
+
+/// Stacking level of a notification window, windows with a higher level are
+/// shown above the lock screen and lower level windows.
+///
+/// The window must be created with the notification type for this to take effect.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NotificationLevel {
+    None,
+    Default,
+    Medium,
+    High,
+    Top,
+}
+
+impl From<NotificationLevel> for efl_util_notification_level_e {
+    fn from(level: NotificationLevel) -> efl_util_notification_level_e {
+        match level {
+            NotificationLevel::None => {
+                efl_util_notification_level_e_EFL_UTIL_NOTIFICATION_LEVEL_NONE
+            }
+            NotificationLevel::Default => {
+                efl_util_notification_level_e_EFL_UTIL_NOTIFICATION_LEVEL_DEFAULT
+            }
+            NotificationLevel::Medium => {
+                efl_util_notification_level_e_EFL_UTIL_NOTIFICATION_LEVEL_MEDIUM
+            }
+            NotificationLevel::High => {
+                efl_util_notification_level_e_EFL_UTIL_NOTIFICATION_LEVEL_HIGH
+            }
+            NotificationLevel::Top => efl_util_notification_level_e_EFL_UTIL_NOTIFICATION_LEVEL_TOP,
+        }
+    }
+}
+
+impl TryFrom<efl_util_notification_level_e> for NotificationLevel {
+    type Error = Error;
+
+    fn try_from(level: efl_util_notification_level_e) -> Result<NotificationLevel> {
+        match level {
+            efl_util_notification_level_e_EFL_UTIL_NOTIFICATION_LEVEL_NONE => {
+                Ok(NotificationLevel::None)
+            }
+            efl_util_notification_level_e_EFL_UTIL_NOTIFICATION_LEVEL_DEFAULT => {
+                Ok(NotificationLevel::Default)
+            }
+            efl_util_notification_level_e_EFL_UTIL_NOTIFICATION_LEVEL_MEDIUM => {
+                Ok(NotificationLevel::Medium)
+            }
+            efl_util_notification_level_e_EFL_UTIL_NOTIFICATION_LEVEL_HIGH => {
+                Ok(NotificationLevel::High)
+            }
+            efl_util_notification_level_e_EFL_UTIL_NOTIFICATION_LEVEL_TOP => {
+                Ok(NotificationLevel::Top)
+            }
+            _ => Err(Error::Unknown),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ScreenMode {
+    Default,
+    /// Keeps the screen on while the window is visible.
+    AlwaysOn,
+}
+
+impl From<ScreenMode> for efl_util_screen_mode_e {
+    fn from(mode: ScreenMode) -> efl_util_screen_mode_e {
+        match mode {
+            ScreenMode::Default => efl_util_screen_mode_e_EFL_UTIL_SCREEN_MODE_DEFAULT,
+            ScreenMode::AlwaysOn => efl_util_screen_mode_e_EFL_UTIL_SCREEN_MODE_ALWAYS_ON,
+        }
+    }
+}
+
+impl TryFrom<efl_util_screen_mode_e> for ScreenMode {
+    type Error = Error;
+
+    fn try_from(mode: efl_util_screen_mode_e) -> Result<ScreenMode> {
+        match mode {
+            efl_util_screen_mode_e_EFL_UTIL_SCREEN_MODE_DEFAULT => Ok(ScreenMode::Default),
+            efl_util_screen_mode_e_EFL_UTIL_SCREEN_MODE_ALWAYS_ON => Ok(ScreenMode::AlwaysOn),
+            _ => Err(Error::Unknown),
+        }
+    }
+}
+
+/// Requires the `http://tizen.org/privilege/window.priority.set` privilege.
+pub fn set_notification_window_level(win: &mut Win, level: NotificationLevel) -> Result<()> {
+    check(unsafe { efl_util_set_notification_window_level(win.eo_ptr(), level.into()) })
+}
+
+pub fn get_notification_window_level(win: &mut Win) -> Result<NotificationLevel> {
+    let mut level = efl_util_notification_level_e_EFL_UTIL_NOTIFICATION_LEVEL_NONE;
+
+    check(unsafe { efl_util_get_notification_window_level(win.eo_ptr(), &mut level) })?;
+
+    NotificationLevel::try_from(level)
+}
+
+/// Requires the `http://tizen.org/privilege/display` privilege.
+pub fn set_window_screen_mode(win: &mut Win, mode: ScreenMode) -> Result<()> {
+    check(unsafe { efl_util_set_window_screen_mode(win.eo_ptr(), mode.into()) })
+}
+
+pub fn get_window_screen_mode(win: &mut Win) -> Result<ScreenMode> {
+    let mut mode = efl_util_screen_mode_e_EFL_UTIL_SCREEN_MODE_DEFAULT;
+
+    check(unsafe { efl_util_get_window_screen_mode(win.eo_ptr(), &mut mode) })?;
+
+    ScreenMode::try_from(mode)
+}
+
+const fn fourcc(a: u8, b: u8, c: u8, d: u8) -> u32 {
+    (a as u32) | (b as u32) << 8 | (c as u32) << 16 | (d as u32) << 24
+}
+
+const TBM_FORMAT_ARGB8888: u32 = fourcc(b'A', b'R', b'2', b'4');
+const TBM_FORMAT_XRGB8888: u32 = fourcc(b'X', b'R', b'2', b'4');
+
+/// An image in tightly packed 8-bit RGBA.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ImageBuffer {
+    pub width: u32,
+    pub height: u32,
+    pub data: Vec<u8>,
+}
+
+impl ImageBuffer {
+    /// Returns the `[r, g, b, a]` pixel at the given coordinates.
+    pub fn pixel(&self, x: u32, y: u32) -> Option<&[u8]> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+
+        let offset = (y as usize)
+            .checked_mul(self.width as usize)?
+            .checked_add(x as usize)?
+            .checked_mul(4)?;
+
+        self.data.get(offset..offset.checked_add(4)?)
+    }
+}
+
+/// A screenshot session capturing the screen at a fixed size.
+///
+/// Requires the `http://tizen.org/privilege/screenshot` privilege.
+pub struct Screenshot {
+    handle: efl_util_screenshot_h,
+}
+
+impl Screenshot {
+    pub fn new(width: u32, height: u32) -> Result<Screenshot> {
+        let handle = unsafe { efl_util_screenshot_initialize(width as c_int, height as c_int) };
+
+        if handle.is_null() {
+            Err(last_error())
+        } else {
+            Ok(Self { handle })
+        }
+    }
+
+    /// Captures the current screen contents.
+    pub fn capture(&mut self) -> Result<ImageBuffer> {
+        let surface = unsafe { efl_util_screenshot_take_tbm_surface(self.handle) };
+
+        if surface.is_null() {
+            return Err(last_error());
+        }
+
+        let mut info: tbm_surface_info_s = unsafe { std::mem::zeroed() };
+        let mapped = unsafe { tbm_surface_map(surface, TBM_SURF_OPTION_READ as c_int, &mut info) };
+
+        let result = if mapped != 0 {
+            Err(Error::IoError)
+        } else {
+            let buffer = surface_to_rgba(&info);
+            unsafe { tbm_surface_unmap(surface) };
+            buffer
+        };
+
+        unsafe { tbm_surface_destroy(surface) };
+
+        result
+    }
+}
+
+impl Drop for Screenshot {
+    fn drop(&mut self) {
+        unsafe {
+            efl_util_screenshot_deinitialize(self.handle);
+        }
+    }
+}
+
+fn surface_to_rgba(info: &tbm_surface_info_s) -> Result<ImageBuffer> {
+    let has_alpha = match info.format {
+        TBM_FORMAT_ARGB8888 => true,
+        TBM_FORMAT_XRGB8888 => false,
+        _ => return Err(Error::NotSupported),
+    };
+
+    let plane = &info.planes[0];
+    let width = info.width as usize;
+    let height = info.height as usize;
+    let mut data = Vec::with_capacity(width * height * 4);
+
+    for y in 0..height {
+        let row = unsafe {
+            std::slice::from_raw_parts(
+                plane
+                    .ptr
+                    .add(plane.offset as usize + y * plane.stride as usize),
+                width * 4,
+            )
+        };
+
+        // little endian ARGB words are stored as B, G, R, A
+        for bgra in row.chunks_exact(4) {
+            data.push(bgra[2]);
+            data.push(bgra[1]);
+            data.push(bgra[0]);
+            data.push(if has_alpha { bgra[3] } else { 0xff });
+        }
+    }
+
+    Ok(ImageBuffer {
+        width: info.width,
+        height: info.height,
+        data,
+    })
+}
+
+/// Captures the whole screen at its native resolution.
+pub fn capture_screen() -> Result<ImageBuffer> {
+    let width = get_platform_int("http://tizen.org/feature/screen.width")?;
+    let height = get_platform_int("http://tizen.org/feature/screen.height")?;
+
+    Screenshot::new(width as u32, height as u32)?.capture()
+}
@@ -0,0 +1,275 @@
+//! Web views, the Chromium based EWK engine embedded as an Evas object.
+//!
+//! Requires the `http://tizen.org/privilege/internet` privilege to load remote pages.
+
+use crate::efl::elm::prelude::*;
+use crate::efl::smart::smart_callback_add_with_info;
+use crate::rutin::{abort_on_panic, borrow_str, cstring};
+use rutin_tizen_sys::{
+    evas_object_evas_get, ewk_error_cancellation_get, ewk_error_code_get,
+    ewk_error_description_get, ewk_error_url_get, ewk_init, ewk_policy_decision_ignore,
+    ewk_policy_decision_url_get, ewk_policy_decision_use, ewk_settings_javascript_enabled_set,
+    ewk_view_add, ewk_view_back, ewk_view_back_possible, ewk_view_forward,
+    ewk_view_forward_possible, ewk_view_html_string_load, ewk_view_load_progress_get,
+    ewk_view_reload, ewk_view_script_execute, ewk_view_settings_get, ewk_view_stop,
+    ewk_view_title_get, ewk_view_url_get, ewk_view_url_set, Eo, Evas_Object, Ewk_Error,
+    Ewk_Policy_Decision,
+};
+use std::cell::Cell;
+use std::os::raw::{c_char, c_void};
+use std::ptr::null;
+
+thread_local! {
+    static INITIALIZED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Why a page failed to load.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LoadError {
+    pub code: i32,
+    pub url: Option<String>,
+    pub description: Option<String>,
+    /// Whether the load was stopped rather than failed.
+    pub canceled: bool,
+}
+
+type ScriptFn = Box<dyn FnOnce(Option<String>)>;
+
+/// A view showing web content, EWK is initialized when the first one is created.
+pub struct WebView<'a> {
+    eo: *mut Eo,
+    _phantom: std::marker::PhantomData<&'a Eo>,
+}
+
+impl<'a> WebView<'a> {
+    pub fn new(parent: &mut dyn Object<'a>) -> Option<WebView<'a>> {
+        if !INITIALIZED.with(|initialized| initialized.replace(true)) {
+            unsafe { ewk_init() };
+        }
+
+        let eo = unsafe { ewk_view_add(evas_object_evas_get(parent.eo_ptr())) };
+
+        if eo.is_null() {
+            None
+        } else {
+            Some(Self {
+                eo,
+                _phantom: std::marker::PhantomData,
+            })
+        }
+    }
+
+    /// Returns `false` if the load couldn't start, or `url` contains a NUL byte.
+    pub fn load_url(&mut self, url: &str) -> bool {
+        let url = match cstring(url) {
+            Ok(url) => url,
+            Err(_) => return false,
+        };
+
+        unsafe { ewk_view_url_set(self.eo_ptr(), url.as_ptr()) != 0 }
+    }
+
+    pub fn get_url(&mut self) -> Option<String> {
+        unsafe { borrow_str(ewk_view_url_get(self.eo_ptr())) }.map(|url| url.into_owned())
+    }
+
+    /// Shows `html`, resolving its relative URLs against `base_url`.
+    ///
+    /// Returns `false` if the load couldn't start, or an argument contains a NUL byte.
+    pub fn load_html(&mut self, html: &str, base_url: Option<&str>) -> bool {
+        let (html, base_url) = match (cstring(html), base_url.map(cstring).transpose()) {
+            (Ok(html), Ok(base_url)) => (html, base_url),
+            _ => return false,
+        };
+
+        unsafe {
+            ewk_view_html_string_load(
+                self.eo_ptr(),
+                html.as_ptr(),
+                base_url
+                    .as_ref()
+                    .map_or(null(), |base_url| base_url.as_ptr()),
+                null(),
+            ) != 0
+        }
+    }
+
+    pub fn reload(&mut self) -> bool {
+        unsafe { ewk_view_reload(self.eo_ptr()) != 0 }
+    }
+
+    pub fn stop(&mut self) -> bool {
+        unsafe { ewk_view_stop(self.eo_ptr()) != 0 }
+    }
+
+    pub fn back(&mut self) -> bool {
+        unsafe { ewk_view_back(self.eo_ptr()) != 0 }
+    }
+
+    pub fn forward(&mut self) -> bool {
+        unsafe { ewk_view_forward(self.eo_ptr()) != 0 }
+    }
+
+    pub fn can_go_back(&mut self) -> bool {
+        unsafe { ewk_view_back_possible(self.eo_ptr()) != 0 }
+    }
+
+    pub fn can_go_forward(&mut self) -> bool {
+        unsafe { ewk_view_forward_possible(self.eo_ptr()) != 0 }
+    }
+
+    pub fn get_title(&mut self) -> Option<String> {
+        unsafe { borrow_str(ewk_view_title_get(self.eo_ptr())) }.map(|title| title.into_owned())
+    }
+
+    /// The progress of the current load, from 0.0 to 1.0.
+    pub fn get_load_progress(&mut self) -> f64 {
+        unsafe { ewk_view_load_progress_get(self.eo_ptr()) }
+    }
+
+    pub fn set_javascript_enabled(&mut self, enabled: bool) -> bool {
+        unsafe {
+            let settings = ewk_view_settings_get(self.eo_ptr());
+            ewk_settings_javascript_enabled_set(settings, enabled as _) != 0
+        }
+    }
+
+    /// Runs `script` in the page, `callback_fn` is called with its result converted to a
+    /// string, or `None` if it has none.
+    ///
+    /// Returns `false`, without calling `callback_fn`, if the script couldn't start or
+    /// contains a NUL byte.
+    pub fn execute_script<F>(&mut self, script: &str, callback_fn: F) -> bool
+    where
+        F: FnOnce(Option<String>),
+        F: 'static,
+    {
+        let script = match cstring(script) {
+            Ok(script) => script,
+            Err(_) => return false,
+        };
+        let callback_fn: ScriptFn = Box::new(callback_fn);
+        let data = Box::into_raw(Box::new(callback_fn));
+
+        let started = unsafe {
+            ewk_view_script_execute(
+                self.eo_ptr(),
+                script.as_ptr(),
+                Some(script_handler),
+                data as *mut c_void,
+            ) != 0
+        };
+
+        if !started {
+            drop(unsafe { Box::from_raw(data) });
+        }
+
+        started
+    }
+
+    pub fn on_load_started<F>(&mut self, callback_fn: F) -> RegisteredSmartCallback<'a>
+    where
+        F: FnMut(),
+        F: 'a,
+    {
+        self.smart_callback_add("load,started", callback_fn)
+    }
+
+    pub fn on_load_finished<F>(&mut self, callback_fn: F) -> RegisteredSmartCallback<'a>
+    where
+        F: FnMut(),
+        F: 'a,
+    {
+        self.smart_callback_add("load,finished", callback_fn)
+    }
+
+    pub fn on_load_error<F>(&mut self, mut callback_fn: F) -> RegisteredSmartCallback<'a>
+    where
+        F: FnMut(LoadError),
+        F: 'a,
+    {
+        smart_callback_add_with_info(self, "load,error", move |event_info| {
+            let error = event_info as *const Ewk_Error;
+
+            callback_fn(unsafe {
+                LoadError {
+                    code: ewk_error_code_get(error),
+                    url: borrow_str(ewk_error_url_get(error)).map(|url| url.into_owned()),
+                    description: borrow_str(ewk_error_description_get(error))
+                        .map(|description| description.into_owned()),
+                    canceled: ewk_error_cancellation_get(error) != 0,
+                }
+            })
+        })
+    }
+
+    /// Called with the progress of the current load, from 0.0 to 1.0.
+    pub fn on_load_progress<F>(&mut self, mut callback_fn: F) -> RegisteredSmartCallback<'a>
+    where
+        F: FnMut(f64),
+        F: 'a,
+    {
+        smart_callback_add_with_info(self, "load,progress", move |event_info| {
+            callback_fn(unsafe { *(event_info as *const f64) })
+        })
+    }
+
+    pub fn on_title_changed<F>(&mut self, mut callback_fn: F) -> RegisteredSmartCallback<'a>
+    where
+        F: FnMut(&str),
+        F: 'a,
+    {
+        smart_callback_add_with_info(self, "title,changed", move |event_info| {
+            let title = unsafe { borrow_str(event_info as *const c_char) }.unwrap_or_default();
+            callback_fn(&title)
+        })
+    }
+
+    pub fn on_url_changed<F>(&mut self, mut callback_fn: F) -> RegisteredSmartCallback<'a>
+    where
+        F: FnMut(&str),
+        F: 'a,
+    {
+        smart_callback_add_with_info(self, "url,changed", move |event_info| {
+            let url = unsafe { borrow_str(event_info as *const c_char) }.unwrap_or_default();
+            callback_fn(&url)
+        })
+    }
+
+    /// Called with the URL of each navigation before it happens, returning whether to
+    /// allow it.
+    pub fn on_navigation<F>(&mut self, mut callback_fn: F) -> RegisteredSmartCallback<'a>
+    where
+        F: FnMut(&str) -> bool,
+        F: 'a,
+    {
+        smart_callback_add_with_info(self, "policy,navigation,decide", move |event_info| {
+            let decision = event_info as *mut Ewk_Policy_Decision;
+            let url = unsafe { borrow_str(ewk_policy_decision_url_get(decision)) };
+
+            if callback_fn(&url.unwrap_or_default()) {
+                unsafe { ewk_policy_decision_use(decision) };
+            } else {
+                unsafe { ewk_policy_decision_ignore(decision) };
+            }
+        })
+    }
+}
+
+impl<'a> Object<'a> for WebView<'a> {
+    fn eo_ptr(&mut self) -> *mut Eo {
+        self.eo
+    }
+}
+
+extern "C" fn script_handler(
+    _obj: *mut Evas_Object,
+    result_value: *const c_char,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let callback_fn = unsafe { Box::from_raw(user_data as *mut ScriptFn) };
+        callback_fn(unsafe { borrow_str(result_value) }.map(|result| result.into_owned()));
+    })
+}
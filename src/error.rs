@@ -0,0 +1,157 @@
+// the simulator only builds some of the wrappers using these
+#![cfg_attr(not(feature = "native"), allow(dead_code))]
+
+#[cfg(feature = "native")]
+use crate::sys::get_last_result;
+use crate::sys::{
+    get_error_message, tizen_error_e_TIZEN_ERROR_ALREADY_IN_PROGRESS,
+    tizen_error_e_TIZEN_ERROR_CANCELED, tizen_error_e_TIZEN_ERROR_INVALID_OPERATION,
+    tizen_error_e_TIZEN_ERROR_INVALID_PARAMETER, tizen_error_e_TIZEN_ERROR_IO_ERROR,
+    tizen_error_e_TIZEN_ERROR_NONE, tizen_error_e_TIZEN_ERROR_NOT_PERMITTED,
+    tizen_error_e_TIZEN_ERROR_NOT_SUPPORTED, tizen_error_e_TIZEN_ERROR_NOW_IN_PROGRESS,
+    tizen_error_e_TIZEN_ERROR_NO_DATA, tizen_error_e_TIZEN_ERROR_NO_SUCH_FILE,
+    tizen_error_e_TIZEN_ERROR_OUT_OF_MEMORY, tizen_error_e_TIZEN_ERROR_PERMISSION_DENIED,
+    tizen_error_e_TIZEN_ERROR_RESOURCE_BUSY, tizen_error_e_TIZEN_ERROR_TIMED_OUT,
+    tizen_error_e_TIZEN_ERROR_UNKNOWN,
+};
+use std::ffi::CStr;
+use std::fmt;
+use std::os::raw::c_int;
+
+/// Errors returned by the Tizen native APIs.
+///
+/// The common `TIZEN_ERROR_*` codes have their own variant, module specific
+/// codes (e.g. `CONNECTION_ERROR_NO_CONNECTION`) are kept in `Other`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Error {
+    NotPermitted,
+    NoSuchFile,
+    IoError,
+    OutOfMemory,
+    PermissionDenied,
+    ResourceBusy,
+    InvalidParameter,
+    NoData,
+    TimedOut,
+    NotSupported,
+    AlreadyInProgress,
+    NowInProgress,
+    InvalidOperation,
+    Canceled,
+    Unknown,
+    Other(c_int),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+    pub fn code(&self) -> c_int {
+        match *self {
+            Error::NotPermitted => tizen_error_e_TIZEN_ERROR_NOT_PERMITTED,
+            Error::NoSuchFile => tizen_error_e_TIZEN_ERROR_NO_SUCH_FILE,
+            Error::IoError => tizen_error_e_TIZEN_ERROR_IO_ERROR,
+            Error::OutOfMemory => tizen_error_e_TIZEN_ERROR_OUT_OF_MEMORY,
+            Error::PermissionDenied => tizen_error_e_TIZEN_ERROR_PERMISSION_DENIED,
+            Error::ResourceBusy => tizen_error_e_TIZEN_ERROR_RESOURCE_BUSY,
+            Error::InvalidParameter => tizen_error_e_TIZEN_ERROR_INVALID_PARAMETER,
+            Error::NoData => tizen_error_e_TIZEN_ERROR_NO_DATA,
+            Error::TimedOut => tizen_error_e_TIZEN_ERROR_TIMED_OUT,
+            Error::NotSupported => tizen_error_e_TIZEN_ERROR_NOT_SUPPORTED,
+            Error::AlreadyInProgress => tizen_error_e_TIZEN_ERROR_ALREADY_IN_PROGRESS,
+            Error::NowInProgress => tizen_error_e_TIZEN_ERROR_NOW_IN_PROGRESS,
+            Error::InvalidOperation => tizen_error_e_TIZEN_ERROR_INVALID_OPERATION,
+            Error::Canceled => tizen_error_e_TIZEN_ERROR_CANCELED,
+            Error::Unknown => tizen_error_e_TIZEN_ERROR_UNKNOWN,
+            Error::Other(code) => code,
+        }
+    }
+}
+
+impl From<c_int> for Error {
+    fn from(code: c_int) -> Error {
+        match code {
+            tizen_error_e_TIZEN_ERROR_NOT_PERMITTED => Error::NotPermitted,
+            tizen_error_e_TIZEN_ERROR_NO_SUCH_FILE => Error::NoSuchFile,
+            tizen_error_e_TIZEN_ERROR_IO_ERROR => Error::IoError,
+            tizen_error_e_TIZEN_ERROR_OUT_OF_MEMORY => Error::OutOfMemory,
+            tizen_error_e_TIZEN_ERROR_PERMISSION_DENIED => Error::PermissionDenied,
+            tizen_error_e_TIZEN_ERROR_RESOURCE_BUSY => Error::ResourceBusy,
+            tizen_error_e_TIZEN_ERROR_INVALID_PARAMETER => Error::InvalidParameter,
+            tizen_error_e_TIZEN_ERROR_NO_DATA => Error::NoData,
+            tizen_error_e_TIZEN_ERROR_TIMED_OUT => Error::TimedOut,
+            tizen_error_e_TIZEN_ERROR_NOT_SUPPORTED => Error::NotSupported,
+            tizen_error_e_TIZEN_ERROR_ALREADY_IN_PROGRESS => Error::AlreadyInProgress,
+            tizen_error_e_TIZEN_ERROR_NOW_IN_PROGRESS => Error::NowInProgress,
+            tizen_error_e_TIZEN_ERROR_INVALID_OPERATION => Error::InvalidOperation,
+            tizen_error_e_TIZEN_ERROR_CANCELED => Error::Canceled,
+            tizen_error_e_TIZEN_ERROR_UNKNOWN => Error::Unknown,
+            code => Error::Other(code),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let message = unsafe { get_error_message(self.code()) };
+
+        if message.is_null() {
+            write!(f, "tizen error {}", self.code())
+        } else {
+            let message = unsafe { CStr::from_ptr(message) };
+            write!(f, "{} ({})", message.to_string_lossy(), self.code())
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Converts the return code of a native call into a `Result`.
+///
+/// With the `tracing` feature, failures are reported as events with the code, the message
+/// and the location of the wrapper which made the call. Successful calls are not traced,
+/// and neither are the arguments, since they can hold user data.
+#[cfg_attr(feature = "tracing", track_caller)]
+pub(crate) fn check(code: c_int) -> Result<()> {
+    if code == tizen_error_e_TIZEN_ERROR_NONE {
+        Ok(())
+    } else {
+        Err(traced(Error::from(code)))
+    }
+}
+
+/// Converts the byte count or negative error code returned by native reads and writes,
+/// traced like [`check`].
+#[cfg_attr(feature = "tracing", track_caller)]
+pub(crate) fn check_count(ret: c_int) -> Result<usize> {
+    if ret >= 0 {
+        Ok(ret as usize)
+    } else {
+        Err(traced(Error::from(ret)))
+    }
+}
+
+/// Reads the error set by the last native call that returned a handle.
+#[cfg(feature = "native")]
+#[cfg_attr(feature = "tracing", track_caller)]
+pub(crate) fn last_error() -> Error {
+    traced(Error::from(unsafe { get_last_result() }))
+}
+
+#[cfg(feature = "tracing")]
+#[track_caller]
+fn traced(error: Error) -> Error {
+    tracing::debug!(
+        code = error.code(),
+        location = %std::panic::Location::caller(),
+        "native call failed: {}",
+        error,
+    );
+
+    error
+}
+
+#[cfg(not(feature = "tracing"))]
+fn traced(error: Error) -> Error {
+    error
+}
@@ -0,0 +1,106 @@
+//! Runs futures on the Ecore main loop, where the platform delivers the events of the
+//! [`EventStream`](crate::stream::EventStream)s and [`Reply`]s of the crate.
+//!
+//! Futures are only polled when woken, the main loop sleeps the rest of the time.
+
+use crate::rutin::abort_on_panic;
+use crate::stream::Reply;
+#[cfg(feature = "native")]
+use crate::sys::ecore_main_loop_thread_safe_call_async;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::future::Future;
+use std::os::raw::c_void;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Wake, Waker};
+#[cfg(feature = "simulator")]
+use std::thread::{self, ThreadId};
+
+type Task = Pin<Box<dyn Future<Output = ()>>>;
+
+thread_local! {
+    static TASKS: RefCell<HashMap<usize, Task>> = RefCell::new(HashMap::new());
+    static NEXT_ID: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Wakes a task by scheduling its next poll on the main loop, from any thread.
+struct TaskWaker {
+    id: usize,
+    /// The thread holding the task, the simulator runs a main loop on each thread.
+    #[cfg(feature = "simulator")]
+    thread: ThreadId,
+    scheduled: AtomicBool,
+}
+
+impl Wake for TaskWaker {
+    fn wake(self: Arc<Self>) {
+        if !self.scheduled.swap(true, Ordering::AcqRel) {
+            #[cfg(feature = "simulator")]
+            let thread = self.thread;
+            let data = Arc::into_raw(self) as *mut c_void;
+
+            #[cfg(feature = "native")]
+            unsafe {
+                ecore_main_loop_thread_safe_call_async(Some(poll_handler), data)
+            };
+            #[cfg(feature = "simulator")]
+            crate::sys::call_async_on(thread, poll_handler, data);
+        }
+    }
+}
+
+/// Runs `future` on the main loop, resolving to its output.
+///
+/// Must be called from the main loop thread. The future keeps running when the returned
+/// [`Reply`] is dropped, the first poll happens once the main loop runs.
+pub fn spawn<F>(future: F) -> Reply<F::Output>
+where
+    F: Future,
+    F: 'static,
+{
+    let (sender, reply) = Reply::new();
+    let id = NEXT_ID.with(|next_id| {
+        let id = next_id.get();
+        next_id.set(id.wrapping_add(1));
+
+        id
+    });
+
+    let task: Task = Box::pin(async move { sender.send(future.await) });
+    TASKS.with(|tasks| tasks.borrow_mut().insert(id, task));
+
+    Arc::new(TaskWaker {
+        id,
+        #[cfg(feature = "simulator")]
+        thread: thread::current().id(),
+        scheduled: AtomicBool::new(false),
+    })
+    .wake();
+
+    reply
+}
+
+extern "C" fn poll_handler(data: *mut c_void) {
+    abort_on_panic(|| {
+        let waker = unsafe { Arc::from_raw(data as *const TaskWaker) };
+        waker.scheduled.store(false, Ordering::Release);
+
+        // taken out while polled, so it can spawn other tasks
+        let task = TASKS.with(|tasks| tasks.borrow_mut().remove(&waker.id));
+
+        if let Some(mut task) = task {
+            let id = waker.id;
+            let waker = Waker::from(waker);
+
+            if task
+                .as_mut()
+                .poll(&mut Context::from_waker(&waker))
+                .is_pending()
+            {
+                TASKS.with(|tasks| tasks.borrow_mut().insert(id, task));
+            }
+        }
+    })
+}
@@ -0,0 +1,179 @@
+use super::{locale_cstring, locale_ptr, uchars, uchars_ptr};
+use crate::error::{check, Result};
+use rutin_tizen_sys::{
+    i18n_ucalendar_add, i18n_ucalendar_create, i18n_ucalendar_date_fields_e,
+    i18n_ucalendar_date_fields_e_I18N_UCALENDAR_AM_PM,
+    i18n_ucalendar_date_fields_e_I18N_UCALENDAR_DATE,
+    i18n_ucalendar_date_fields_e_I18N_UCALENDAR_DAY_OF_WEEK,
+    i18n_ucalendar_date_fields_e_I18N_UCALENDAR_DAY_OF_WEEK_IN_MONTH,
+    i18n_ucalendar_date_fields_e_I18N_UCALENDAR_DAY_OF_YEAR,
+    i18n_ucalendar_date_fields_e_I18N_UCALENDAR_DST_OFFSET,
+    i18n_ucalendar_date_fields_e_I18N_UCALENDAR_ERA,
+    i18n_ucalendar_date_fields_e_I18N_UCALENDAR_HOUR,
+    i18n_ucalendar_date_fields_e_I18N_UCALENDAR_HOUR_OF_DAY,
+    i18n_ucalendar_date_fields_e_I18N_UCALENDAR_MILLISECOND,
+    i18n_ucalendar_date_fields_e_I18N_UCALENDAR_MINUTE,
+    i18n_ucalendar_date_fields_e_I18N_UCALENDAR_MONTH,
+    i18n_ucalendar_date_fields_e_I18N_UCALENDAR_SECOND,
+    i18n_ucalendar_date_fields_e_I18N_UCALENDAR_WEEK_OF_MONTH,
+    i18n_ucalendar_date_fields_e_I18N_UCALENDAR_WEEK_OF_YEAR,
+    i18n_ucalendar_date_fields_e_I18N_UCALENDAR_YEAR,
+    i18n_ucalendar_date_fields_e_I18N_UCALENDAR_ZONE_OFFSET, i18n_ucalendar_destroy,
+    i18n_ucalendar_get, i18n_ucalendar_get_milliseconds, i18n_ucalendar_get_now, i18n_ucalendar_h,
+    i18n_ucalendar_is_in_daylight_time, i18n_ucalendar_set, i18n_ucalendar_set_milliseconds,
+    i18n_ucalendar_type_e, i18n_ucalendar_type_e_I18N_UCALENDAR_GREGORIAN,
+    i18n_ucalendar_type_e_I18N_UCALENDAR_TRADITIONAL,
+};
+use std::ptr::null_mut;
+
+/// The current time, in milliseconds since the epoch.
+pub fn now() -> Result<f64> {
+    let mut date = 0.0;
+    check(unsafe { i18n_ucalendar_get_now(&mut date) })?;
+
+    Ok(date)
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CalendarType {
+    /// The calendar of the locale, e.g. the Buddhist calendar in `th_TH`.
+    Traditional,
+    Gregorian,
+}
+
+impl From<CalendarType> for i18n_ucalendar_type_e {
+    fn from(calendar_type: CalendarType) -> i18n_ucalendar_type_e {
+        match calendar_type {
+            CalendarType::Traditional => i18n_ucalendar_type_e_I18N_UCALENDAR_TRADITIONAL,
+            CalendarType::Gregorian => i18n_ucalendar_type_e_I18N_UCALENDAR_GREGORIAN,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DateField {
+    Era,
+    Year,
+    /// From 0 for January.
+    Month,
+    WeekOfYear,
+    WeekOfMonth,
+    /// The day of the month, from 1.
+    Date,
+    DayOfYear,
+    /// From 1 for Sunday.
+    DayOfWeek,
+    DayOfWeekInMonth,
+    AmPm,
+    Hour,
+    HourOfDay,
+    Minute,
+    Second,
+    Millisecond,
+    /// The raw offset from UTC of the time zone, in milliseconds.
+    ZoneOffset,
+    /// The daylight saving offset, in milliseconds.
+    DstOffset,
+}
+
+impl From<DateField> for i18n_ucalendar_date_fields_e {
+    fn from(field: DateField) -> i18n_ucalendar_date_fields_e {
+        match field {
+            DateField::Era => i18n_ucalendar_date_fields_e_I18N_UCALENDAR_ERA,
+            DateField::Year => i18n_ucalendar_date_fields_e_I18N_UCALENDAR_YEAR,
+            DateField::Month => i18n_ucalendar_date_fields_e_I18N_UCALENDAR_MONTH,
+            DateField::WeekOfYear => i18n_ucalendar_date_fields_e_I18N_UCALENDAR_WEEK_OF_YEAR,
+            DateField::WeekOfMonth => i18n_ucalendar_date_fields_e_I18N_UCALENDAR_WEEK_OF_MONTH,
+            DateField::Date => i18n_ucalendar_date_fields_e_I18N_UCALENDAR_DATE,
+            DateField::DayOfYear => i18n_ucalendar_date_fields_e_I18N_UCALENDAR_DAY_OF_YEAR,
+            DateField::DayOfWeek => i18n_ucalendar_date_fields_e_I18N_UCALENDAR_DAY_OF_WEEK,
+            DateField::DayOfWeekInMonth => {
+                i18n_ucalendar_date_fields_e_I18N_UCALENDAR_DAY_OF_WEEK_IN_MONTH
+            }
+            DateField::AmPm => i18n_ucalendar_date_fields_e_I18N_UCALENDAR_AM_PM,
+            DateField::Hour => i18n_ucalendar_date_fields_e_I18N_UCALENDAR_HOUR,
+            DateField::HourOfDay => i18n_ucalendar_date_fields_e_I18N_UCALENDAR_HOUR_OF_DAY,
+            DateField::Minute => i18n_ucalendar_date_fields_e_I18N_UCALENDAR_MINUTE,
+            DateField::Second => i18n_ucalendar_date_fields_e_I18N_UCALENDAR_SECOND,
+            DateField::Millisecond => i18n_ucalendar_date_fields_e_I18N_UCALENDAR_MILLISECOND,
+            DateField::ZoneOffset => i18n_ucalendar_date_fields_e_I18N_UCALENDAR_ZONE_OFFSET,
+            DateField::DstOffset => i18n_ucalendar_date_fields_e_I18N_UCALENDAR_DST_OFFSET,
+        }
+    }
+}
+
+/// Splits dates into fields (year, month, hour...) in a time zone, initially set to the
+/// current time.
+pub struct Calendar {
+    handle: i18n_ucalendar_h,
+}
+
+impl Calendar {
+    /// `time_zone` is an id such as `Europe/Paris`, `None` for the time zone of the device.
+    pub fn new(
+        time_zone: Option<&str>,
+        locale: Option<&str>,
+        calendar_type: CalendarType,
+    ) -> Result<Calendar> {
+        let time_zone = time_zone.map(uchars);
+        let (zone_ptr, zone_len) = uchars_ptr(&time_zone);
+        let locale = locale_cstring(locale)?;
+
+        let mut handle = null_mut();
+        check(unsafe {
+            i18n_ucalendar_create(
+                zone_ptr,
+                zone_len,
+                locale_ptr(&locale),
+                calendar_type.into(),
+                &mut handle,
+            )
+        })?;
+
+        Ok(Self { handle })
+    }
+
+    pub fn get(&self, field: DateField) -> Result<i32> {
+        let mut value = 0;
+        check(unsafe { i18n_ucalendar_get(self.handle, field.into(), &mut value) })?;
+
+        Ok(value)
+    }
+
+    pub fn set(&mut self, field: DateField, value: i32) -> Result<()> {
+        check(unsafe { i18n_ucalendar_set(self.handle, field.into(), value) })
+    }
+
+    /// Adds `amount` to `field`, carrying over to the larger fields.
+    pub fn add(&mut self, field: DateField, amount: i32) -> Result<()> {
+        check(unsafe { i18n_ucalendar_add(self.handle, field.into(), amount) })
+    }
+
+    pub fn get_milliseconds(&self) -> Result<f64> {
+        let mut date = 0.0;
+        check(unsafe { i18n_ucalendar_get_milliseconds(self.handle, &mut date) })?;
+
+        Ok(date)
+    }
+
+    pub fn set_milliseconds(&mut self, date: f64) -> Result<()> {
+        check(unsafe { i18n_ucalendar_set_milliseconds(self.handle, date) })
+    }
+
+    pub fn is_in_daylight_time(&self) -> Result<bool> {
+        let mut daylight = false;
+        check(unsafe { i18n_ucalendar_is_in_daylight_time(self.handle, &mut daylight) })?;
+
+        Ok(daylight)
+    }
+}
+
+impl Drop for Calendar {
+    fn drop(&mut self) {
+        unsafe {
+            i18n_ucalendar_destroy(self.handle);
+        }
+    }
+}
@@ -0,0 +1,108 @@
+use super::{locale_cstring, locale_ptr, uchars};
+use crate::error::{check, Result};
+use rutin_tizen_sys::{
+    i18n_ucollator_create, i18n_ucollator_destroy, i18n_ucollator_h,
+    i18n_ucollator_result_e_I18N_UCOLLATOR_EQUAL, i18n_ucollator_result_e_I18N_UCOLLATOR_LESS,
+    i18n_ucollator_set_strength, i18n_ucollator_str_collator, i18n_ucollator_strength_e,
+    i18n_ucollator_strength_e_I18N_UCOLLATOR_IDENTICAL,
+    i18n_ucollator_strength_e_I18N_UCOLLATOR_PRIMARY,
+    i18n_ucollator_strength_e_I18N_UCOLLATOR_QUATERNARY,
+    i18n_ucollator_strength_e_I18N_UCOLLATOR_SECONDARY,
+    i18n_ucollator_strength_e_I18N_UCOLLATOR_TERTIARY,
+};
+use std::cmp::Ordering;
+use std::ptr::null_mut;
+
+/// Which differences between strings make them different.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Strength {
+    /// Only the base letters, `a` equals `á` and `A`.
+    Primary,
+    /// Also the accents, `a` equals `A` only.
+    Secondary,
+    /// Also the case, the default.
+    Tertiary,
+    /// Also the punctuation, when ignored otherwise.
+    Quaternary,
+    Identical,
+}
+
+impl From<Strength> for i18n_ucollator_strength_e {
+    fn from(strength: Strength) -> i18n_ucollator_strength_e {
+        match strength {
+            Strength::Primary => i18n_ucollator_strength_e_I18N_UCOLLATOR_PRIMARY,
+            Strength::Secondary => i18n_ucollator_strength_e_I18N_UCOLLATOR_SECONDARY,
+            Strength::Tertiary => i18n_ucollator_strength_e_I18N_UCOLLATOR_TERTIARY,
+            Strength::Quaternary => i18n_ucollator_strength_e_I18N_UCOLLATOR_QUATERNARY,
+            Strength::Identical => i18n_ucollator_strength_e_I18N_UCOLLATOR_IDENTICAL,
+        }
+    }
+}
+
+/// Compares strings in the alphabetical order of a locale, e.g. to sort contact names.
+pub struct Collator {
+    handle: i18n_ucollator_h,
+}
+
+impl Collator {
+    pub fn new(locale: Option<&str>) -> Result<Collator> {
+        let locale = locale_cstring(locale)?;
+
+        let mut handle = null_mut();
+        check(unsafe { i18n_ucollator_create(locale_ptr(&locale), &mut handle) })?;
+
+        Ok(Self { handle })
+    }
+
+    pub fn set_strength(&mut self, strength: Strength) -> Result<()> {
+        check(unsafe { i18n_ucollator_set_strength(self.handle, strength.into()) })
+    }
+
+    pub fn compare(&self, a: &str, b: &str) -> Result<Ordering> {
+        let (a, b) = (uchars(a), uchars(b));
+
+        let mut result = i18n_ucollator_result_e_I18N_UCOLLATOR_EQUAL;
+        check(unsafe {
+            i18n_ucollator_str_collator(
+                self.handle,
+                a.as_ptr(),
+                a.len() as i32,
+                b.as_ptr(),
+                b.len() as i32,
+                &mut result,
+            )
+        })?;
+
+        Ok(match result {
+            i18n_ucollator_result_e_I18N_UCOLLATOR_EQUAL => Ordering::Equal,
+            i18n_ucollator_result_e_I18N_UCOLLATOR_LESS => Ordering::Less,
+            _ => Ordering::Greater,
+        })
+    }
+
+    /// Sorts `strings` in the order of the collator.
+    pub fn sort<S: AsRef<str>>(&self, strings: &mut [S]) -> Result<()> {
+        let mut error = None;
+
+        strings.sort_by(|a, b| {
+            self.compare(a.as_ref(), b.as_ref()).unwrap_or_else(|e| {
+                error = Some(e);
+                Ordering::Equal
+            })
+        });
+
+        match error {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for Collator {
+    fn drop(&mut self) {
+        unsafe {
+            i18n_ucollator_destroy(self.handle);
+        }
+    }
+}
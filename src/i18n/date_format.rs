@@ -0,0 +1,163 @@
+use super::{locale_cstring, locale_ptr, read_uchars, uchars, uchars_ptr};
+use crate::error::{check, Result};
+use rutin_tizen_sys::{
+    i18n_uchar, i18n_udate_create, i18n_udate_destroy, i18n_udate_format_date, i18n_udate_format_h,
+    i18n_udate_format_style_e, i18n_udate_format_style_e_I18N_UDATE_FULL,
+    i18n_udate_format_style_e_I18N_UDATE_LONG, i18n_udate_format_style_e_I18N_UDATE_MEDIUM,
+    i18n_udate_format_style_e_I18N_UDATE_NONE, i18n_udate_format_style_e_I18N_UDATE_PATTERN,
+    i18n_udate_format_style_e_I18N_UDATE_SHORT, i18n_udatepg_create, i18n_udatepg_destroy,
+    i18n_udatepg_get_best_pattern, i18n_udatepg_h,
+};
+use std::ptr::null_mut;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DateStyle {
+    /// e.g. `Tuesday, April 12, 1952 AD` or `3:30:42pm PST`.
+    Full,
+    Long,
+    Medium,
+    /// e.g. `12/13/52` or `3:30pm`.
+    Short,
+    /// Leaves the date, or the time, out.
+    None,
+}
+
+impl From<DateStyle> for i18n_udate_format_style_e {
+    fn from(style: DateStyle) -> i18n_udate_format_style_e {
+        match style {
+            DateStyle::Full => i18n_udate_format_style_e_I18N_UDATE_FULL,
+            DateStyle::Long => i18n_udate_format_style_e_I18N_UDATE_LONG,
+            DateStyle::Medium => i18n_udate_format_style_e_I18N_UDATE_MEDIUM,
+            DateStyle::Short => i18n_udate_format_style_e_I18N_UDATE_SHORT,
+            DateStyle::None => i18n_udate_format_style_e_I18N_UDATE_NONE,
+        }
+    }
+}
+
+/// The pattern the locale uses to show the fields of `skeleton`, such as `h:mm a` in
+/// `en_US` and `HH:mm` in `fr_FR` for the skeleton `jmm`.
+pub fn best_pattern(skeleton: &str, locale: Option<&str>) -> Result<String> {
+    let locale = locale_cstring(locale)?;
+    let skeleton = uchars(skeleton);
+
+    let mut handle: i18n_udatepg_h = null_mut();
+    check(unsafe { i18n_udatepg_create(locale_ptr(&locale), &mut handle) })?;
+
+    let pattern = read_uchars(|buffer, capacity| {
+        let mut len = 0;
+        let code = unsafe {
+            i18n_udatepg_get_best_pattern(
+                handle,
+                skeleton.as_ptr(),
+                skeleton.len() as i32,
+                buffer,
+                capacity,
+                &mut len,
+            )
+        };
+
+        (code, len)
+    });
+
+    unsafe { i18n_udatepg_destroy(handle) };
+
+    pattern
+}
+
+/// Formats dates per the conventions of a locale, in a time zone.
+pub struct DateFormat {
+    handle: i18n_udate_format_h,
+}
+
+impl DateFormat {
+    /// `time_zone` is an id such as `Europe/Paris`, `None` for the time zone of the device.
+    pub fn new(
+        time_style: DateStyle,
+        date_style: DateStyle,
+        locale: Option<&str>,
+        time_zone: Option<&str>,
+    ) -> Result<DateFormat> {
+        Self::create(
+            time_style.into(),
+            date_style.into(),
+            locale,
+            time_zone,
+            None,
+        )
+    }
+
+    /// Formats with an ICU pattern such as `EEE d MMM`, see [`best_pattern`] for the
+    /// patterns of the locale.
+    pub fn with_pattern(
+        pattern: &str,
+        locale: Option<&str>,
+        time_zone: Option<&str>,
+    ) -> Result<DateFormat> {
+        Self::create(
+            i18n_udate_format_style_e_I18N_UDATE_PATTERN,
+            i18n_udate_format_style_e_I18N_UDATE_PATTERN,
+            locale,
+            time_zone,
+            Some(uchars(pattern)),
+        )
+    }
+
+    /// Formats with the pattern the locale uses for the fields of `skeleton`.
+    pub fn with_skeleton(
+        skeleton: &str,
+        locale: Option<&str>,
+        time_zone: Option<&str>,
+    ) -> Result<DateFormat> {
+        Self::with_pattern(&best_pattern(skeleton, locale)?, locale, time_zone)
+    }
+
+    fn create(
+        time_style: i18n_udate_format_style_e,
+        date_style: i18n_udate_format_style_e,
+        locale: Option<&str>,
+        time_zone: Option<&str>,
+        pattern: Option<Vec<i18n_uchar>>,
+    ) -> Result<DateFormat> {
+        let locale = locale_cstring(locale)?;
+        let time_zone = time_zone.map(uchars);
+        let (zone_ptr, zone_len) = uchars_ptr(&time_zone);
+        let (pattern_ptr, pattern_len) = uchars_ptr(&pattern);
+
+        let mut handle = null_mut();
+        check(unsafe {
+            i18n_udate_create(
+                time_style,
+                date_style,
+                locale_ptr(&locale),
+                zone_ptr,
+                zone_len,
+                pattern_ptr,
+                pattern_len,
+                &mut handle,
+            )
+        })?;
+
+        Ok(Self { handle })
+    }
+
+    /// Formats `date`, in milliseconds since the epoch.
+    pub fn format(&self, date: f64) -> Result<String> {
+        read_uchars(|buffer, capacity| {
+            let mut len = 0;
+            let code = unsafe {
+                i18n_udate_format_date(self.handle, date, buffer, capacity, null_mut(), &mut len)
+            };
+
+            (code, len)
+        })
+    }
+}
+
+impl Drop for DateFormat {
+    fn drop(&mut self) {
+        unsafe {
+            i18n_udate_destroy(self.handle);
+        }
+    }
+}
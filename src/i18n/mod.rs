@@ -0,0 +1,75 @@
+//! Locale aware formatting and comparison, from the ICU library of the platform.
+//!
+//! Locales are ICU ids such as `en_US`, `None` stands for the locale of the device.
+//! Dates are milliseconds since the epoch, as `f64`.
+
+pub mod calendar;
+pub mod collator;
+pub mod date_format;
+pub mod number_format;
+pub mod timezone;
+
+pub use calendar::Calendar;
+pub use collator::Collator;
+pub use date_format::DateFormat;
+pub use number_format::NumberFormat;
+pub use timezone::TimeZone;
+
+use crate::error::{check, Result};
+use crate::rutin::{borrow_str, cstring};
+use rutin_tizen_sys::{i18n_uchar, i18n_ulocale_get_default};
+use std::ffi::CString;
+use std::os::raw::{c_char, c_int};
+use std::ptr::null;
+
+/// The locale of the device, such as `en_US`.
+pub fn get_default_locale() -> Result<String> {
+    let mut locale = null();
+    check(unsafe { i18n_ulocale_get_default(&mut locale) })?;
+
+    Ok(unsafe { borrow_str(locale) }
+        .map(|locale| locale.into_owned())
+        .unwrap_or_default())
+}
+
+fn locale_cstring(locale: Option<&str>) -> Result<Option<CString>> {
+    locale.map(cstring).transpose()
+}
+
+fn locale_ptr(locale: &Option<CString>) -> *const c_char {
+    locale.as_ref().map_or(null(), |locale| locale.as_ptr())
+}
+
+fn uchars(s: &str) -> Vec<i18n_uchar> {
+    s.encode_utf16().collect()
+}
+
+/// The pointer and length of an optional UTF-16 string, null with -1 when unset.
+fn uchars_ptr(s: &Option<Vec<i18n_uchar>>) -> (*const i18n_uchar, i32) {
+    s.as_ref()
+        .map_or((null(), -1), |s| (s.as_ptr(), s.len() as i32))
+}
+
+/// Reads the UTF-16 string written by `fill` into a buffer of the given capacity, which
+/// returns its error code and the length of the whole string. The call is retried with a
+/// larger buffer when the first one was too small.
+fn read_uchars<F>(mut fill: F) -> Result<String>
+where
+    F: FnMut(*mut i18n_uchar, i32) -> (c_int, i32),
+{
+    let mut buffer: Vec<i18n_uchar> = vec![0; 64];
+
+    loop {
+        let (code, len) = fill(buffer.as_mut_ptr(), buffer.len() as i32);
+
+        // no room left for the NUL either
+        if len >= buffer.len() as i32 {
+            buffer.resize(len as usize + 1, 0);
+            continue;
+        }
+
+        check(code)?;
+
+        return Ok(String::from_utf16_lossy(&buffer[..len.max(0) as usize]));
+    }
+}
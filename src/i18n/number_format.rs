@@ -0,0 +1,95 @@
+use super::{locale_cstring, locale_ptr, read_uchars};
+use crate::error::{check, Result};
+use rutin_tizen_sys::{
+    get_last_result, i18n_unumber_create, i18n_unumber_destroy, i18n_unumber_format_double,
+    i18n_unumber_format_h, i18n_unumber_format_int64, i18n_unumber_format_style_e,
+    i18n_unumber_format_style_e_I18N_UNUMBER_CURRENCY,
+    i18n_unumber_format_style_e_I18N_UNUMBER_DECIMAL,
+    i18n_unumber_format_style_e_I18N_UNUMBER_DURATION,
+    i18n_unumber_format_style_e_I18N_UNUMBER_ORDINAL,
+    i18n_unumber_format_style_e_I18N_UNUMBER_PERCENT,
+    i18n_unumber_format_style_e_I18N_UNUMBER_SCIENTIFIC,
+    i18n_unumber_format_style_e_I18N_UNUMBER_SPELLOUT,
+};
+use std::ptr::{null, null_mut};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NumberStyle {
+    /// e.g. `1,234.5`.
+    Decimal,
+    /// In the currency of the locale.
+    Currency,
+    /// e.g. `42%` for `0.42`.
+    Percent,
+    Scientific,
+    /// e.g. `forty-two`.
+    Spellout,
+    /// e.g. `42nd`.
+    Ordinal,
+    /// Seconds as hours, minutes and seconds, e.g. `1:02:03`.
+    Duration,
+}
+
+impl From<NumberStyle> for i18n_unumber_format_style_e {
+    fn from(style: NumberStyle) -> i18n_unumber_format_style_e {
+        match style {
+            NumberStyle::Decimal => i18n_unumber_format_style_e_I18N_UNUMBER_DECIMAL,
+            NumberStyle::Currency => i18n_unumber_format_style_e_I18N_UNUMBER_CURRENCY,
+            NumberStyle::Percent => i18n_unumber_format_style_e_I18N_UNUMBER_PERCENT,
+            NumberStyle::Scientific => i18n_unumber_format_style_e_I18N_UNUMBER_SCIENTIFIC,
+            NumberStyle::Spellout => i18n_unumber_format_style_e_I18N_UNUMBER_SPELLOUT,
+            NumberStyle::Ordinal => i18n_unumber_format_style_e_I18N_UNUMBER_ORDINAL,
+            NumberStyle::Duration => i18n_unumber_format_style_e_I18N_UNUMBER_DURATION,
+        }
+    }
+}
+
+/// Formats numbers per the conventions of a locale.
+pub struct NumberFormat {
+    handle: i18n_unumber_format_h,
+}
+
+impl NumberFormat {
+    pub fn new(style: NumberStyle, locale: Option<&str>) -> Result<NumberFormat> {
+        let locale = locale_cstring(locale)?;
+
+        let mut handle = null_mut();
+        check(unsafe {
+            i18n_unumber_create(
+                style.into(),
+                null(),
+                -1,
+                locale_ptr(&locale),
+                null_mut(),
+                &mut handle,
+            )
+        })?;
+
+        Ok(Self { handle })
+    }
+
+    pub fn format_f64(&self, number: f64) -> Result<String> {
+        read_uchars(|buffer, capacity| unsafe {
+            let len = i18n_unumber_format_double(self.handle, number, buffer, capacity, null_mut());
+
+            (get_last_result(), len)
+        })
+    }
+
+    pub fn format_i64(&self, number: i64) -> Result<String> {
+        read_uchars(|buffer, capacity| unsafe {
+            let len = i18n_unumber_format_int64(self.handle, number, buffer, capacity, null_mut());
+
+            (get_last_result(), len)
+        })
+    }
+}
+
+impl Drop for NumberFormat {
+    fn drop(&mut self) {
+        unsafe {
+            i18n_unumber_destroy(self.handle);
+        }
+    }
+}
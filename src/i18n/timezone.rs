@@ -0,0 +1,106 @@
+use crate::error::{check, Result};
+use crate::rutin::{abort_on_panic, borrow_str, cstring, take_string};
+use rutin_tizen_sys::{
+    i18n_timezone_create, i18n_timezone_create_default, i18n_timezone_destroy,
+    i18n_timezone_foreach_timezone_id, i18n_timezone_get_display_name,
+    i18n_timezone_get_dst_savings, i18n_timezone_get_id, i18n_timezone_get_raw_offset,
+    i18n_timezone_h, i18n_timezone_use_daylight_time,
+};
+use std::os::raw::{c_char, c_void};
+use std::ptr::null_mut;
+
+/// The ids of all the time zones known to the platform.
+pub fn timezone_ids() -> Result<Vec<String>> {
+    let mut ids: Vec<String> = Vec::new();
+    check(unsafe {
+        i18n_timezone_foreach_timezone_id(
+            Some(id_handler),
+            &mut ids as *mut Vec<String> as *mut c_void,
+        )
+    })?;
+
+    Ok(ids)
+}
+
+pub struct TimeZone {
+    handle: i18n_timezone_h,
+}
+
+impl TimeZone {
+    /// The time zone of the device.
+    pub fn current() -> Result<TimeZone> {
+        let mut handle = null_mut();
+        check(unsafe { i18n_timezone_create_default(&mut handle) })?;
+
+        Ok(Self { handle })
+    }
+
+    /// `id` is such as `Europe/Paris` or `GMT+2`, ICU falls back to `GMT` when unknown.
+    pub fn new(id: &str) -> Result<TimeZone> {
+        let id = cstring(id)?;
+
+        let mut handle = null_mut();
+        check(unsafe { i18n_timezone_create(&mut handle, id.as_ptr()) })?;
+
+        Ok(Self { handle })
+    }
+
+    pub fn get_id(&self) -> Result<String> {
+        let mut id = null_mut();
+        check(unsafe { i18n_timezone_get_id(self.handle, &mut id) })?;
+
+        Ok(unsafe { take_string(id) }.unwrap_or_default())
+    }
+
+    /// The name of the time zone in the locale of the device, e.g. `Central European
+    /// Standard Time`.
+    pub fn get_display_name(&self) -> Result<String> {
+        let mut name = null_mut();
+        check(unsafe { i18n_timezone_get_display_name(self.handle, &mut name) })?;
+
+        Ok(unsafe { take_string(name) }.unwrap_or_default())
+    }
+
+    /// The offset from UTC outside of daylight saving time, in milliseconds.
+    pub fn get_raw_offset(&self) -> Result<i32> {
+        let mut offset = 0;
+        check(unsafe { i18n_timezone_get_raw_offset(self.handle, &mut offset) })?;
+
+        Ok(offset)
+    }
+
+    /// How much the clock moves forward during daylight saving time, in milliseconds.
+    pub fn get_dst_savings(&self) -> Result<i32> {
+        let mut savings = 0;
+        check(unsafe { i18n_timezone_get_dst_savings(self.handle, &mut savings) })?;
+
+        Ok(savings)
+    }
+
+    pub fn uses_daylight_time(&self) -> Result<bool> {
+        let mut daylight = false;
+        check(unsafe { i18n_timezone_use_daylight_time(self.handle, &mut daylight) })?;
+
+        Ok(daylight)
+    }
+}
+
+impl Drop for TimeZone {
+    fn drop(&mut self) {
+        unsafe {
+            i18n_timezone_destroy(self.handle);
+        }
+    }
+}
+
+extern "C" fn id_handler(id: *const c_char, user_data: *mut c_void) -> bool {
+    abort_on_panic(|| {
+        let ids = unsafe { &mut *(user_data as *mut Vec<String>) };
+
+        if let Some(id) = unsafe { borrow_str(id) } {
+            ids.push(id.into_owned());
+        }
+
+        true
+    })
+}
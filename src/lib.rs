@@ -1,4 +1,74 @@
+// the bindings keep the C names of the enum constants, which are matched on throughout
+#![allow(non_upper_case_globals)]
+
+#[cfg(all(feature = "native", feature = "simulator"))]
+compile_error!("`simulator` replaces `native`, enable it with `default-features = false`");
+#[cfg(not(any(feature = "native", feature = "simulator")))]
+compile_error!("either the `native` or the `simulator` feature is required");
+
+#[cfg(feature = "native")]
+use rutin_tizen_sys as sys;
+#[cfg(all(feature = "simulator", not(feature = "native")))]
+use simulator::sys;
+
+#[cfg(feature = "account")]
+pub mod account;
+#[cfg(feature = "app")]
 pub mod app;
+#[cfg(feature = "app")]
+pub mod app_control;
+#[cfg(feature = "autofill")]
+pub mod autofill;
+#[cfg(feature = "context")]
+pub mod context;
+#[cfg(feature = "download")]
+pub mod download;
+#[cfg(feature = "efl")]
 pub mod efl;
+pub mod error;
+#[cfg(feature = "async")]
+pub mod executor;
+#[cfg(feature = "i18n")]
+pub mod i18n;
+#[cfg(feature = "location")]
+pub mod location;
+#[cfg(feature = "media")]
+pub mod media;
+#[cfg(feature = "messaging")]
+pub mod messaging;
+#[cfg(feature = "minicontrol")]
+pub mod minicontrol;
+#[cfg(feature = "network")]
+pub mod network;
+#[cfg(feature = "notification")]
+pub mod notification;
+#[cfg(feature = "oauth2")]
+pub mod oauth2;
+#[cfg(feature = "package-manager")]
+pub mod package_manager;
+#[cfg(feature = "pim")]
+pub mod pim;
 pub mod rutin;
+#[cfg(feature = "security")]
+pub mod security;
+#[cfg(feature = "sensor")]
+pub mod sensor;
+#[cfg(feature = "simulator")]
+pub mod simulator;
+#[cfg(feature = "sticker")]
+pub mod sticker;
+#[cfg(feature = "async")]
+pub mod stream;
+#[cfg(feature = "sync-manager")]
+pub mod sync_manager;
 pub mod system;
+#[cfg(feature = "telephony")]
+pub mod telephony;
+#[cfg(feature = "uix")]
+pub mod uix;
+#[cfg(feature = "watchface")]
+pub mod watchface;
+#[cfg(feature = "widget-service")]
+pub mod widget_service;
+
+pub use error::{Error, Result};
@@ -0,0 +1,338 @@
+//! Monitoring of the device entering and leaving places.
+//!
+//! A place groups fences, each being an area around a point, a Wi-Fi access point or a Bluetooth
+//! device. Requires the `http://tizen.org/privilege/location` privilege.
+
+use super::Coordinates;
+use crate::error::{check, Error, Result};
+use crate::rutin::{abort_on_panic, cstring, pin_callback};
+use rutin_tizen_sys::{
+    geofence_create_bluetooth, geofence_create_geopoint, geofence_create_wifi, geofence_destroy,
+    geofence_h, geofence_manage_e, geofence_manage_e_GEOFENCE_MANAGE_FENCE_ADDED,
+    geofence_manage_e_GEOFENCE_MANAGE_FENCE_REMOVED,
+    geofence_manage_e_GEOFENCE_MANAGE_FENCE_STARTED,
+    geofence_manage_e_GEOFENCE_MANAGE_FENCE_STOPPED, geofence_manage_e_GEOFENCE_MANAGE_PLACE_ADDED,
+    geofence_manage_e_GEOFENCE_MANAGE_PLACE_REMOVED,
+    geofence_manage_e_GEOFENCE_MANAGE_PLACE_UPDATED,
+    geofence_manage_e_GEOFENCE_MANAGE_SETTING_DISABLED,
+    geofence_manage_e_GEOFENCE_MANAGE_SETTING_ENABLED, geofence_manager_add_fence,
+    geofence_manager_add_place, geofence_manager_create, geofence_manager_destroy,
+    geofence_manager_h, geofence_manager_is_supported, geofence_manager_remove_fence,
+    geofence_manager_remove_place, geofence_manager_set_geofence_event_cb,
+    geofence_manager_set_geofence_state_changed_cb, geofence_manager_start, geofence_manager_stop,
+    geofence_manager_unset_geofence_event_cb, geofence_manager_unset_geofence_state_changed_cb,
+    geofence_manager_update_place, geofence_state_e, geofence_state_e_GEOFENCE_STATE_IN,
+    geofence_state_e_GEOFENCE_STATE_OUT, geofence_state_e_GEOFENCE_STATE_UNCERTAIN,
+    geofence_status_create, geofence_status_destroy, geofence_status_get_duration,
+    geofence_status_get_state,
+};
+use std::convert::TryFrom;
+use std::os::raw::{c_char, c_int, c_void};
+use std::pin::Pin;
+use std::ptr::null_mut;
+
+pub fn is_supported() -> Result<bool> {
+    let mut supported = false;
+    check(unsafe { geofence_manager_is_supported(&mut supported) })?;
+
+    Ok(supported)
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FenceState {
+    Uncertain,
+    In,
+    Out,
+}
+
+impl From<geofence_state_e> for FenceState {
+    fn from(state: geofence_state_e) -> FenceState {
+        match state {
+            geofence_state_e_GEOFENCE_STATE_IN => FenceState::In,
+            geofence_state_e_GEOFENCE_STATE_OUT => FenceState::Out,
+            _ => FenceState::Uncertain,
+        }
+    }
+}
+
+/// What a [`GeofenceEvent`] reports the completion of.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Action {
+    FenceAdded,
+    FenceRemoved,
+    FenceStarted,
+    FenceStopped,
+    PlaceAdded,
+    PlaceRemoved,
+    PlaceUpdated,
+    /// The user enabled the geofence service in the settings.
+    SettingEnabled,
+    SettingDisabled,
+}
+
+impl TryFrom<geofence_manage_e> for Action {
+    type Error = Error;
+
+    fn try_from(action: geofence_manage_e) -> Result<Action> {
+        match action {
+            geofence_manage_e_GEOFENCE_MANAGE_FENCE_ADDED => Ok(Action::FenceAdded),
+            geofence_manage_e_GEOFENCE_MANAGE_FENCE_REMOVED => Ok(Action::FenceRemoved),
+            geofence_manage_e_GEOFENCE_MANAGE_FENCE_STARTED => Ok(Action::FenceStarted),
+            geofence_manage_e_GEOFENCE_MANAGE_FENCE_STOPPED => Ok(Action::FenceStopped),
+            geofence_manage_e_GEOFENCE_MANAGE_PLACE_ADDED => Ok(Action::PlaceAdded),
+            geofence_manage_e_GEOFENCE_MANAGE_PLACE_REMOVED => Ok(Action::PlaceRemoved),
+            geofence_manage_e_GEOFENCE_MANAGE_PLACE_UPDATED => Ok(Action::PlaceUpdated),
+            geofence_manage_e_GEOFENCE_MANAGE_SETTING_ENABLED => Ok(Action::SettingEnabled),
+            geofence_manage_e_GEOFENCE_MANAGE_SETTING_DISABLED => Ok(Action::SettingDisabled),
+            _ => Err(Error::Unknown),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GeofenceEvent {
+    pub place_id: i32,
+    pub geofence_id: i32,
+    pub result: Result<()>,
+    pub action: Action,
+}
+
+/// The parameters of a fence, to be added with [`GeofenceManager::add_fence`].
+pub struct Fence {
+    handle: geofence_h,
+}
+
+impl Fence {
+    /// A circle of `radius` meters (100, 200, 300, 500, 1000 or 2000) around `center`.
+    pub fn geopoint(
+        place_id: i32,
+        center: Coordinates,
+        radius: i32,
+        address: &str,
+    ) -> Result<Fence> {
+        let address = cstring(address)?;
+        let mut handle = null_mut();
+
+        check(unsafe {
+            geofence_create_geopoint(
+                place_id,
+                center.latitude,
+                center.longitude,
+                radius,
+                address.as_ptr(),
+                &mut handle,
+            )
+        })?;
+
+        Ok(Self { handle })
+    }
+
+    /// The range of the Wi-Fi access point `bssid`.
+    pub fn wifi(place_id: i32, bssid: &str, ssid: &str) -> Result<Fence> {
+        Self::create(geofence_create_wifi, place_id, bssid, ssid)
+    }
+
+    /// The range of the Bluetooth device with address `bssid`.
+    pub fn bluetooth(place_id: i32, bssid: &str, ssid: &str) -> Result<Fence> {
+        Self::create(geofence_create_bluetooth, place_id, bssid, ssid)
+    }
+
+    fn create(
+        create: unsafe extern "C" fn(c_int, *const c_char, *const c_char, *mut geofence_h) -> c_int,
+        place_id: i32,
+        bssid: &str,
+        ssid: &str,
+    ) -> Result<Fence> {
+        let bssid = cstring(bssid)?;
+        let ssid = cstring(ssid)?;
+        let mut handle = null_mut();
+
+        check(unsafe { create(place_id, bssid.as_ptr(), ssid.as_ptr(), &mut handle) })?;
+
+        Ok(Self { handle })
+    }
+}
+
+impl Drop for Fence {
+    fn drop(&mut self) {
+        unsafe {
+            geofence_destroy(self.handle);
+        }
+    }
+}
+
+type StateFn = Box<dyn FnMut(i32, FenceState)>;
+type EventFn = Box<dyn FnMut(GeofenceEvent)>;
+
+pub struct GeofenceManager {
+    handle: geofence_manager_h,
+    state_fn: Option<Pin<Box<StateFn>>>,
+    event_fn: Option<Pin<Box<EventFn>>>,
+}
+
+impl GeofenceManager {
+    pub fn new() -> Result<GeofenceManager> {
+        let mut handle = null_mut();
+        check(unsafe { geofence_manager_create(&mut handle) })?;
+
+        Ok(Self {
+            handle,
+            state_fn: None,
+            event_fn: None,
+        })
+    }
+
+    /// Creates a place, returning its id.
+    pub fn add_place(&self, name: &str) -> Result<i32> {
+        let name = cstring(name)?;
+        let mut place_id = 0;
+
+        check(unsafe { geofence_manager_add_place(self.handle, name.as_ptr(), &mut place_id) })?;
+
+        Ok(place_id)
+    }
+
+    pub fn update_place(&self, place_id: i32, name: &str) -> Result<()> {
+        let name = cstring(name)?;
+
+        check(unsafe { geofence_manager_update_place(self.handle, place_id, name.as_ptr()) })
+    }
+
+    /// Removes a place along with its fences.
+    pub fn remove_place(&self, place_id: i32) -> Result<()> {
+        check(unsafe { geofence_manager_remove_place(self.handle, place_id) })
+    }
+
+    /// Adds a fence to its place, returning its id.
+    pub fn add_fence(&self, fence: &Fence) -> Result<i32> {
+        let mut geofence_id = 0;
+        check(unsafe { geofence_manager_add_fence(self.handle, fence.handle, &mut geofence_id) })?;
+
+        Ok(geofence_id)
+    }
+
+    pub fn remove_fence(&self, geofence_id: i32) -> Result<()> {
+        check(unsafe { geofence_manager_remove_fence(self.handle, geofence_id) })
+    }
+
+    /// Starts monitoring a fence, state changes are reported to the state changed callback.
+    pub fn start(&self, geofence_id: i32) -> Result<()> {
+        check(unsafe { geofence_manager_start(self.handle, geofence_id) })
+    }
+
+    pub fn stop(&self, geofence_id: i32) -> Result<()> {
+        check(unsafe { geofence_manager_stop(self.handle, geofence_id) })
+    }
+
+    /// The current state of a fence, along with how many seconds it has been in it.
+    pub fn get_state(&self, geofence_id: i32) -> Result<(FenceState, i32)> {
+        let mut status = null_mut();
+        check(unsafe { geofence_status_create(geofence_id, &mut status) })?;
+
+        let mut state = geofence_state_e_GEOFENCE_STATE_UNCERTAIN;
+        let mut duration = 0;
+        let ret = check(unsafe { geofence_status_get_state(status, &mut state) })
+            .and_then(|_| check(unsafe { geofence_status_get_duration(status, &mut duration) }));
+
+        unsafe {
+            geofence_status_destroy(status);
+        }
+
+        ret.map(|_| (state.into(), duration))
+    }
+
+    /// Calls `callback_fn` with the fence id when a monitored fence is entered or left,
+    /// replacing the previous callback.
+    pub fn set_state_changed_cb<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(i32, FenceState),
+        F: 'static,
+    {
+        let (callback_fn, data) = pin_callback::<dyn FnMut(i32, FenceState)>(Box::new(callback_fn));
+
+        check(unsafe {
+            geofence_manager_set_geofence_state_changed_cb(self.handle, Some(state_handler), data)
+        })?;
+        self.state_fn = Some(callback_fn);
+
+        Ok(())
+    }
+
+    pub fn unset_state_changed_cb(&mut self) -> Result<()> {
+        check(unsafe { geofence_manager_unset_geofence_state_changed_cb(self.handle) })?;
+        self.state_fn = None;
+
+        Ok(())
+    }
+
+    /// Calls `callback_fn` when places and fences are added, removed, started or stopped,
+    /// replacing the previous callback.
+    pub fn set_event_cb<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(GeofenceEvent),
+        F: 'static,
+    {
+        let (callback_fn, data) = pin_callback::<dyn FnMut(GeofenceEvent)>(Box::new(callback_fn));
+
+        check(unsafe {
+            geofence_manager_set_geofence_event_cb(self.handle, Some(event_handler), data)
+        })?;
+        self.event_fn = Some(callback_fn);
+
+        Ok(())
+    }
+
+    pub fn unset_event_cb(&mut self) -> Result<()> {
+        check(unsafe { geofence_manager_unset_geofence_event_cb(self.handle) })?;
+        self.event_fn = None;
+
+        Ok(())
+    }
+}
+
+impl Drop for GeofenceManager {
+    fn drop(&mut self) {
+        unsafe {
+            if self.state_fn.is_some() {
+                geofence_manager_unset_geofence_state_changed_cb(self.handle);
+            }
+
+            if self.event_fn.is_some() {
+                geofence_manager_unset_geofence_event_cb(self.handle);
+            }
+
+            geofence_manager_destroy(self.handle);
+        }
+    }
+}
+
+extern "C" fn state_handler(geofence_id: c_int, state: geofence_state_e, user_data: *mut c_void) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut StateFn) };
+        closure(geofence_id, state.into());
+    })
+}
+
+extern "C" fn event_handler(
+    place_id: c_int,
+    geofence_id: c_int,
+    error: c_int,
+    manage: geofence_manage_e,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut EventFn) };
+
+        if let Ok(action) = Action::try_from(manage) {
+            closure(GeofenceEvent {
+                place_id,
+                geofence_id,
+                result: check(error),
+                action,
+            });
+        }
+    })
+}
@@ -0,0 +1,667 @@
+use crate::error::{check, Result};
+use crate::rutin::{abort_on_panic, pin_callback};
+#[cfg(feature = "async")]
+use crate::stream::EventStream;
+use rutin_tizen_sys::{
+    location_accuracy_level_e, location_accuracy_level_e_LOCATIONS_ACCURACY_COUNTRY,
+    location_accuracy_level_e_LOCATIONS_ACCURACY_DETAILED,
+    location_accuracy_level_e_LOCATIONS_ACCURACY_NONE,
+    location_accuracy_level_e_LOCATIONS_ACCURACY_POSTALCODE,
+    location_accuracy_level_e_LOCATIONS_ACCURACY_REGION,
+    location_accuracy_level_e_LOCATIONS_ACCURACY_STREET, location_manager_create,
+    location_manager_destroy, location_manager_foreach_location_batch,
+    location_manager_get_accuracy, location_manager_get_distance,
+    location_manager_get_last_location, location_manager_get_last_position,
+    location_manager_get_position, location_manager_get_velocity, location_manager_h,
+    location_manager_set_location_batch_cb, location_manager_set_location_changed_cb,
+    location_manager_set_position_updated_cb, location_manager_set_service_state_changed_cb,
+    location_manager_set_velocity_updated_cb, location_manager_start, location_manager_start_batch,
+    location_manager_stop, location_manager_stop_batch, location_manager_unset_location_batch_cb,
+    location_manager_unset_location_changed_cb, location_manager_unset_position_updated_cb,
+    location_manager_unset_service_state_changed_cb, location_manager_unset_velocity_updated_cb,
+    location_method_e, location_method_e_LOCATIONS_METHOD_FUSED,
+    location_method_e_LOCATIONS_METHOD_GPS, location_method_e_LOCATIONS_METHOD_HYBRID,
+    location_method_e_LOCATIONS_METHOD_PASSIVE, location_method_e_LOCATIONS_METHOD_WPS,
+    location_service_state_e, location_service_state_e_LOCATIONS_SERVICE_ENABLED,
+};
+use std::os::raw::{c_int, c_void};
+use std::pin::Pin;
+use std::ptr::null_mut;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LocationMethod {
+    /// The best of GPS and WPS at any given time.
+    Hybrid,
+    Gps,
+    /// Wi-Fi positioning.
+    Wps,
+    /// Only the fixes requested by other applications.
+    Passive,
+    /// GPS, WPS and the sensors combined.
+    Fused,
+}
+
+impl From<LocationMethod> for location_method_e {
+    fn from(method: LocationMethod) -> location_method_e {
+        match method {
+            LocationMethod::Hybrid => location_method_e_LOCATIONS_METHOD_HYBRID,
+            LocationMethod::Gps => location_method_e_LOCATIONS_METHOD_GPS,
+            LocationMethod::Wps => location_method_e_LOCATIONS_METHOD_WPS,
+            LocationMethod::Passive => location_method_e_LOCATIONS_METHOD_PASSIVE,
+            LocationMethod::Fused => location_method_e_LOCATIONS_METHOD_FUSED,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AccuracyLevel {
+    None,
+    Country,
+    Region,
+    PostalCode,
+    Street,
+    Detailed,
+}
+
+impl From<location_accuracy_level_e> for AccuracyLevel {
+    fn from(level: location_accuracy_level_e) -> AccuracyLevel {
+        match level {
+            location_accuracy_level_e_LOCATIONS_ACCURACY_COUNTRY => AccuracyLevel::Country,
+            location_accuracy_level_e_LOCATIONS_ACCURACY_REGION => AccuracyLevel::Region,
+            location_accuracy_level_e_LOCATIONS_ACCURACY_POSTALCODE => AccuracyLevel::PostalCode,
+            location_accuracy_level_e_LOCATIONS_ACCURACY_STREET => AccuracyLevel::Street,
+            location_accuracy_level_e_LOCATIONS_ACCURACY_DETAILED => AccuracyLevel::Detailed,
+            _ => AccuracyLevel::None,
+        }
+    }
+}
+
+/// A point on the WGS84 ellipsoid, in degrees.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Coordinates {
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+impl Coordinates {
+    /// The great-circle distance to `other`, in meters.
+    pub fn distance_to(&self, other: Coordinates) -> Result<f64> {
+        let mut distance = 0.0;
+
+        check(unsafe {
+            location_manager_get_distance(
+                self.latitude,
+                self.longitude,
+                other.latitude,
+                other.longitude,
+                &mut distance,
+            )
+        })?;
+
+        Ok(distance)
+    }
+}
+
+/// Timestamps are in seconds since the epoch.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Position {
+    pub coordinates: Coordinates,
+    /// Meters above sea level.
+    pub altitude: f64,
+    pub timestamp: i64,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Velocity {
+    /// Speed in km/h.
+    pub speed: f64,
+    /// Heading in degrees, clockwise from the north.
+    pub direction: f64,
+    /// Vertical speed in km/h.
+    pub climb: f64,
+    pub timestamp: i64,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Accuracy {
+    pub level: AccuracyLevel,
+    /// Radius of uncertainty in meters.
+    pub horizontal: f64,
+    pub vertical: f64,
+}
+
+/// A fix combining position, velocity and accuracy.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Location {
+    pub coordinates: Coordinates,
+    pub altitude: f64,
+    pub speed: f64,
+    pub direction: f64,
+    pub horizontal_accuracy: f64,
+    pub timestamp: i64,
+}
+
+type PositionFn = Box<dyn FnMut(Position)>;
+type VelocityFn = Box<dyn FnMut(Velocity)>;
+type LocationFn = Box<dyn FnMut(Location)>;
+type StateFn = Box<dyn FnMut(bool)>;
+
+struct BatchState {
+    handle: location_manager_h,
+    callback_fn: Box<dyn FnMut(Vec<Location>)>,
+}
+
+pub struct LocationManager {
+    handle: location_manager_h,
+    position_fn: Option<Pin<Box<PositionFn>>>,
+    velocity_fn: Option<Pin<Box<VelocityFn>>>,
+    location_fn: Option<Pin<Box<LocationFn>>>,
+    state_fn: Option<Pin<Box<StateFn>>>,
+    batch_state: Option<Pin<Box<BatchState>>>,
+}
+
+impl LocationManager {
+    pub fn new(method: LocationMethod) -> Result<LocationManager> {
+        let mut handle = null_mut();
+        check(unsafe { location_manager_create(method.into(), &mut handle) })?;
+
+        Ok(Self {
+            handle,
+            position_fn: None,
+            velocity_fn: None,
+            location_fn: None,
+            state_fn: None,
+            batch_state: None,
+        })
+    }
+
+    /// Starts the location service, the callbacks are called until the listener is dropped.
+    pub fn start(&self) -> Result<LocationListener<'_>> {
+        check(unsafe { location_manager_start(self.handle) })?;
+
+        Ok(LocationListener { manager: self })
+    }
+
+    /// Starts collecting fixes in batches, the batch callback is called until the listener is
+    /// dropped. Only supported with [`LocationMethod::Gps`].
+    pub fn start_batch(&self) -> Result<BatchListener<'_>> {
+        check(unsafe { location_manager_start_batch(self.handle) })?;
+
+        Ok(BatchListener { manager: self })
+    }
+
+    /// Returns a stream of the position every `interval` seconds (1 to 120).
+    ///
+    /// The stream starts the location service on its own manager, so it does not replace
+    /// the callbacks set on this one.
+    #[cfg(feature = "async")]
+    pub fn positions(method: LocationMethod, interval: i32) -> Result<EventStream<Position>> {
+        EventStream::new(|sender| {
+            let mut manager = LocationManager::new(method)?;
+            manager.set_position_updated_cb(interval, move |position| sender.send(position))?;
+            check(unsafe { location_manager_start(manager.handle) })?;
+
+            Ok(StartedManager(manager))
+        })
+    }
+
+    /// Calls `callback_fn` with the position every `interval` seconds (1 to 120),
+    /// replacing the previous callback.
+    pub fn set_position_updated_cb<F>(&mut self, interval: i32, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(Position),
+        F: 'static,
+    {
+        let (callback_fn, data) = pin_callback::<dyn FnMut(Position)>(Box::new(callback_fn));
+
+        check(unsafe {
+            location_manager_set_position_updated_cb(
+                self.handle,
+                Some(position_handler),
+                interval,
+                data,
+            )
+        })?;
+        self.position_fn = Some(callback_fn);
+
+        Ok(())
+    }
+
+    pub fn unset_position_updated_cb(&mut self) -> Result<()> {
+        check(unsafe { location_manager_unset_position_updated_cb(self.handle) })?;
+        self.position_fn = None;
+
+        Ok(())
+    }
+
+    /// Calls `callback_fn` with the velocity every `interval` seconds (1 to 120),
+    /// replacing the previous callback.
+    pub fn set_velocity_updated_cb<F>(&mut self, interval: i32, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(Velocity),
+        F: 'static,
+    {
+        let (callback_fn, data) = pin_callback::<dyn FnMut(Velocity)>(Box::new(callback_fn));
+
+        check(unsafe {
+            location_manager_set_velocity_updated_cb(
+                self.handle,
+                Some(velocity_handler),
+                interval,
+                data,
+            )
+        })?;
+        self.velocity_fn = Some(callback_fn);
+
+        Ok(())
+    }
+
+    pub fn unset_velocity_updated_cb(&mut self) -> Result<()> {
+        check(unsafe { location_manager_unset_velocity_updated_cb(self.handle) })?;
+        self.velocity_fn = None;
+
+        Ok(())
+    }
+
+    /// Calls `callback_fn` with a full fix every `interval` seconds (1 to 120),
+    /// replacing the previous callback.
+    pub fn set_location_changed_cb<F>(&mut self, interval: i32, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(Location),
+        F: 'static,
+    {
+        let (callback_fn, data) = pin_callback::<dyn FnMut(Location)>(Box::new(callback_fn));
+
+        check(unsafe {
+            location_manager_set_location_changed_cb(
+                self.handle,
+                Some(location_handler),
+                interval,
+                data,
+            )
+        })?;
+        self.location_fn = Some(callback_fn);
+
+        Ok(())
+    }
+
+    pub fn unset_location_changed_cb(&mut self) -> Result<()> {
+        check(unsafe { location_manager_unset_location_changed_cb(self.handle) })?;
+        self.location_fn = None;
+
+        Ok(())
+    }
+
+    /// Calls `callback_fn` with the fixes collected every `interval` seconds (1 to 255), once
+    /// every `period` seconds (1 to 60000), replacing the previous callback.
+    ///
+    /// The device can stay asleep in between, which uses far less power than single fixes.
+    pub fn set_location_batch_cb<F>(
+        &mut self,
+        interval: i32,
+        period: i32,
+        callback_fn: F,
+    ) -> Result<()>
+    where
+        F: FnMut(Vec<Location>),
+        F: 'static,
+    {
+        let state = Box::pin(BatchState {
+            handle: self.handle,
+            callback_fn: Box::new(callback_fn),
+        });
+        let data = &*state as *const BatchState as *mut c_void;
+
+        check(unsafe {
+            location_manager_set_location_batch_cb(
+                self.handle,
+                Some(batch_handler),
+                interval,
+                period,
+                data,
+            )
+        })?;
+        self.batch_state = Some(state);
+
+        Ok(())
+    }
+
+    pub fn unset_location_batch_cb(&mut self) -> Result<()> {
+        check(unsafe { location_manager_unset_location_batch_cb(self.handle) })?;
+        self.batch_state = None;
+
+        Ok(())
+    }
+
+    /// Calls `callback_fn` when the service becomes usable (`true`) or stops being so,
+    /// replacing the previous callback.
+    pub fn set_service_state_changed_cb<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(bool),
+        F: 'static,
+    {
+        let (callback_fn, data) = pin_callback::<dyn FnMut(bool)>(Box::new(callback_fn));
+
+        check(unsafe {
+            location_manager_set_service_state_changed_cb(self.handle, Some(state_handler), data)
+        })?;
+        self.state_fn = Some(callback_fn);
+
+        Ok(())
+    }
+
+    pub fn unset_service_state_changed_cb(&mut self) -> Result<()> {
+        check(unsafe { location_manager_unset_service_state_changed_cb(self.handle) })?;
+        self.state_fn = None;
+
+        Ok(())
+    }
+
+    /// The current position, the service must be started and enabled.
+    pub fn get_position(&self) -> Result<Position> {
+        self.read_position(location_manager_get_position)
+    }
+
+    /// The last position recorded by any application, even if the service is stopped.
+    pub fn get_last_position(&self) -> Result<Position> {
+        self.read_position(location_manager_get_last_position)
+    }
+
+    /// The last position, velocity and accuracy recorded by any application, even if the service
+    /// is stopped.
+    pub fn get_last_location(&self) -> Result<(Position, Velocity, Accuracy)> {
+        let (mut altitude, mut latitude, mut longitude) = (0.0, 0.0, 0.0);
+        let (mut climb, mut direction, mut speed) = (0.0, 0.0, 0.0);
+        let mut level = location_accuracy_level_e_LOCATIONS_ACCURACY_NONE;
+        let (mut horizontal, mut vertical) = (0.0, 0.0);
+        let mut timestamp: libc::time_t = 0;
+
+        check(unsafe {
+            location_manager_get_last_location(
+                self.handle,
+                &mut altitude,
+                &mut latitude,
+                &mut longitude,
+                &mut climb,
+                &mut direction,
+                &mut speed,
+                &mut level,
+                &mut horizontal,
+                &mut vertical,
+                &mut timestamp,
+            )
+        })?;
+
+        let timestamp = i64::from(timestamp);
+
+        Ok((
+            Position {
+                coordinates: Coordinates {
+                    latitude,
+                    longitude,
+                },
+                altitude,
+                timestamp,
+            },
+            Velocity {
+                speed,
+                direction,
+                climb,
+                timestamp,
+            },
+            Accuracy {
+                level: level.into(),
+                horizontal,
+                vertical,
+            },
+        ))
+    }
+
+    fn read_position(
+        &self,
+        get: unsafe extern "C" fn(
+            location_manager_h,
+            *mut f64,
+            *mut f64,
+            *mut f64,
+            *mut libc::time_t,
+        ) -> c_int,
+    ) -> Result<Position> {
+        let (mut latitude, mut longitude, mut altitude) = (0.0, 0.0, 0.0);
+        let mut timestamp: libc::time_t = 0;
+
+        check(unsafe {
+            get(
+                self.handle,
+                &mut latitude,
+                &mut longitude,
+                &mut altitude,
+                &mut timestamp,
+            )
+        })?;
+
+        Ok(Position {
+            coordinates: Coordinates {
+                latitude,
+                longitude,
+            },
+            altitude,
+            timestamp: i64::from(timestamp),
+        })
+    }
+
+    pub fn get_velocity(&self) -> Result<Velocity> {
+        let (mut climb, mut direction, mut speed) = (0.0, 0.0, 0.0);
+        let mut timestamp: libc::time_t = 0;
+
+        check(unsafe {
+            location_manager_get_velocity(
+                self.handle,
+                &mut climb,
+                &mut direction,
+                &mut speed,
+                &mut timestamp,
+            )
+        })?;
+
+        Ok(Velocity {
+            speed,
+            direction,
+            climb,
+            timestamp: i64::from(timestamp),
+        })
+    }
+
+    pub fn get_accuracy(&self) -> Result<Accuracy> {
+        let mut level = location_accuracy_level_e_LOCATIONS_ACCURACY_NONE;
+        let (mut horizontal, mut vertical) = (0.0, 0.0);
+
+        check(unsafe {
+            location_manager_get_accuracy(self.handle, &mut level, &mut horizontal, &mut vertical)
+        })?;
+
+        Ok(Accuracy {
+            level: level.into(),
+            horizontal,
+            vertical,
+        })
+    }
+}
+
+impl Drop for LocationManager {
+    fn drop(&mut self) {
+        unsafe {
+            // also unsets the callbacks
+            location_manager_destroy(self.handle);
+        }
+    }
+}
+
+/// A started location service, stopped when dropped.
+pub struct LocationListener<'m> {
+    manager: &'m LocationManager,
+}
+
+impl<'m> Drop for LocationListener<'m> {
+    fn drop(&mut self) {
+        unsafe {
+            location_manager_stop(self.manager.handle);
+        }
+    }
+}
+
+/// The location service of a stream, stopped when dropped.
+#[cfg(feature = "async")]
+struct StartedManager(LocationManager);
+
+#[cfg(feature = "async")]
+impl Drop for StartedManager {
+    fn drop(&mut self) {
+        unsafe {
+            location_manager_stop(self.0.handle);
+        }
+    }
+}
+
+/// A started batch collection, stopped when dropped.
+pub struct BatchListener<'m> {
+    manager: &'m LocationManager,
+}
+
+impl<'m> Drop for BatchListener<'m> {
+    fn drop(&mut self) {
+        unsafe {
+            location_manager_stop_batch(self.manager.handle);
+        }
+    }
+}
+
+extern "C" fn position_handler(
+    latitude: f64,
+    longitude: f64,
+    altitude: f64,
+    timestamp: libc::time_t,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut PositionFn) };
+
+        closure(Position {
+            coordinates: Coordinates {
+                latitude,
+                longitude,
+            },
+            altitude,
+            timestamp: i64::from(timestamp),
+        });
+    })
+}
+
+extern "C" fn velocity_handler(
+    speed: f64,
+    direction: f64,
+    climb: f64,
+    timestamp: libc::time_t,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut VelocityFn) };
+
+        closure(Velocity {
+            speed,
+            direction,
+            climb,
+            timestamp: i64::from(timestamp),
+        });
+    })
+}
+
+#[allow(clippy::too_many_arguments)]
+extern "C" fn location_handler(
+    latitude: f64,
+    longitude: f64,
+    altitude: f64,
+    speed: f64,
+    direction: f64,
+    horizontal_accuracy: f64,
+    timestamp: libc::time_t,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut LocationFn) };
+
+        closure(Location {
+            coordinates: Coordinates {
+                latitude,
+                longitude,
+            },
+            altitude,
+            speed,
+            direction,
+            horizontal_accuracy,
+            timestamp: i64::from(timestamp),
+        });
+    })
+}
+
+extern "C" fn state_handler(state: location_service_state_e, user_data: *mut c_void) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut StateFn) };
+        closure(state == location_service_state_e_LOCATIONS_SERVICE_ENABLED);
+    })
+}
+
+extern "C" fn batch_handler(_num_of_location: c_int, user_data: *mut c_void) {
+    abort_on_panic(|| {
+        let state = unsafe { &mut *(user_data as *mut BatchState) };
+        let mut locations = Vec::<Location>::new();
+
+        unsafe {
+            location_manager_foreach_location_batch(
+                state.handle,
+                Some(batch_location_handler),
+                &mut locations as *mut Vec<Location> as *mut c_void,
+            );
+        }
+
+        (state.callback_fn)(locations);
+    })
+}
+
+#[allow(clippy::too_many_arguments)]
+extern "C" fn batch_location_handler(
+    latitude: f64,
+    longitude: f64,
+    altitude: f64,
+    speed: f64,
+    direction: f64,
+    horizontal: f64,
+    _vertical: f64,
+    timestamp: libc::time_t,
+    user_data: *mut c_void,
+) -> bool {
+    abort_on_panic(|| {
+        let locations = unsafe { &mut *(user_data as *mut Vec<Location>) };
+
+        locations.push(Location {
+            coordinates: Coordinates {
+                latitude,
+                longitude,
+            },
+            altitude,
+            speed,
+            direction,
+            horizontal_accuracy: horizontal,
+            timestamp: i64::from(timestamp),
+        });
+
+        true
+    })
+}
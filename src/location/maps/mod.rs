@@ -0,0 +1,286 @@
+//! Geocoding, place search and routing through an online maps provider.
+//!
+//! Requires the `http://tizen.org/privilege/mapservice`, `http://tizen.org/privilege/internet`
+//! and `http://tizen.org/privilege/network.get` privileges.
+
+mod place;
+mod route;
+
+pub use place::Place;
+pub use route::{Maneuver, Route, Segment, TransportMode};
+
+use super::Coordinates;
+use crate::error::{check, Result};
+use crate::rutin::{abort_on_panic, cstring, take_string};
+use rutin_tizen_sys::{
+    maps_address_destroy, maps_address_get_building_number, maps_address_get_city,
+    maps_address_get_country, maps_address_get_country_code, maps_address_get_county,
+    maps_address_get_district, maps_address_get_freetext, maps_address_get_postal_code,
+    maps_address_get_state, maps_address_get_street, maps_address_h, maps_coordinates_create,
+    maps_coordinates_destroy, maps_coordinates_get_latitude_longitude, maps_coordinates_h,
+    maps_service_create, maps_service_destroy, maps_service_geocode, maps_service_h,
+    maps_service_reverse_geocode, maps_service_set_provider_key,
+};
+use std::os::raw::{c_char, c_int, c_void};
+use std::ptr::null_mut;
+
+/// A postal address, any part of which may be unknown.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Address {
+    pub building_number: Option<String>,
+    pub street: Option<String>,
+    pub district: Option<String>,
+    pub city: Option<String>,
+    pub county: Option<String>,
+    pub state: Option<String>,
+    pub country: Option<String>,
+    pub country_code: Option<String>,
+    pub postal_code: Option<String>,
+    /// The whole address as a single line.
+    pub freetext: Option<String>,
+}
+
+impl Address {
+    pub(crate) unsafe fn from_raw(handle: maps_address_h) -> Address {
+        let field = |get: unsafe extern "C" fn(maps_address_h, *mut *mut c_char) -> c_int| {
+            let mut value = null_mut();
+
+            if check(get(handle, &mut value)).is_ok() {
+                take_string(value)
+            } else {
+                None
+            }
+        };
+
+        Address {
+            building_number: field(maps_address_get_building_number),
+            street: field(maps_address_get_street),
+            district: field(maps_address_get_district),
+            city: field(maps_address_get_city),
+            county: field(maps_address_get_county),
+            state: field(maps_address_get_state),
+            country: field(maps_address_get_country),
+            country_code: field(maps_address_get_country_code),
+            postal_code: field(maps_address_get_postal_code),
+            freetext: field(maps_address_get_freetext),
+        }
+    }
+}
+
+pub(crate) struct RawCoordinates {
+    pub(crate) handle: maps_coordinates_h,
+}
+
+impl RawCoordinates {
+    pub(crate) fn new(coordinates: Coordinates) -> Result<RawCoordinates> {
+        let mut handle = null_mut();
+        check(unsafe {
+            maps_coordinates_create(coordinates.latitude, coordinates.longitude, &mut handle)
+        })?;
+
+        Ok(Self { handle })
+    }
+}
+
+impl Drop for RawCoordinates {
+    fn drop(&mut self) {
+        unsafe {
+            maps_coordinates_destroy(self.handle);
+        }
+    }
+}
+
+pub(crate) unsafe fn read_coordinates(handle: maps_coordinates_h) -> Result<Coordinates> {
+    let (mut latitude, mut longitude) = (0.0, 0.0);
+    check(maps_coordinates_get_latitude_longitude(
+        handle,
+        &mut latitude,
+        &mut longitude,
+    ))?;
+
+    Ok(Coordinates {
+        latitude,
+        longitude,
+    })
+}
+
+/// Gathers the results of a request, which the service delivers one per call.
+pub(crate) struct Collector<T> {
+    results: Result<Vec<T>>,
+    callback_fn: Box<dyn FnOnce(Result<Vec<T>>)>,
+}
+
+impl<T> Collector<T> {
+    pub(crate) fn into_raw(callback_fn: Box<dyn FnOnce(Result<Vec<T>>)>) -> *mut Collector<T> {
+        Box::into_raw(Box::new(Collector {
+            results: Ok(Vec::new()),
+            callback_fn,
+        }))
+    }
+
+    /// Adds the result at `index` out of `total`, calling back and freeing the collector after
+    /// the last one. Returns whether more results are expected.
+    pub(crate) unsafe fn feed(
+        raw: *mut Collector<T>,
+        result: Result<T>,
+        index: c_int,
+        total: c_int,
+    ) -> bool {
+        let collector = &mut *raw;
+
+        match result {
+            Ok(item) => {
+                if let Ok(results) = &mut collector.results {
+                    results.push(item);
+                }
+            }
+            Err(e) => {
+                if collector.results.is_ok() {
+                    collector.results = Err(e);
+                }
+            }
+        }
+
+        if index + 1 < total {
+            return true;
+        }
+
+        let collector = Box::from_raw(raw);
+        (collector.callback_fn)(collector.results);
+
+        false
+    }
+
+    /// Frees a collector whose request failed to start.
+    pub(crate) unsafe fn discard(raw: *mut Collector<T>) {
+        drop(Box::from_raw(raw));
+    }
+}
+
+pub struct MapsService {
+    handle: maps_service_h,
+}
+
+impl MapsService {
+    /// Connects to `provider`, such as `"HERE"`.
+    pub fn new(provider: &str) -> Result<MapsService> {
+        let provider = cstring(provider)?;
+        let mut handle = null_mut();
+
+        check(unsafe { maps_service_create(provider.as_ptr(), &mut handle) })?;
+
+        Ok(Self { handle })
+    }
+
+    /// Sets the key identifying the application to the provider, needed before any request.
+    pub fn set_provider_key(&self, key: &str) -> Result<()> {
+        let key = cstring(key)?;
+
+        check(unsafe { maps_service_set_provider_key(self.handle, key.as_ptr()) })
+    }
+
+    /// Looks up the coordinates matching `address`, `callback_fn` is called with all of them.
+    pub fn geocode<F>(&self, address: &str, callback_fn: F) -> Result<()>
+    where
+        F: FnOnce(Result<Vec<Coordinates>>),
+        F: 'static,
+    {
+        let address = cstring(address)?;
+        let data = Collector::into_raw(Box::new(callback_fn));
+        let mut request_id = 0;
+
+        let ret = unsafe {
+            maps_service_geocode(
+                self.handle,
+                address.as_ptr(),
+                null_mut(),
+                Some(geocode_handler),
+                data as *mut c_void,
+                &mut request_id,
+            )
+        };
+
+        if let Err(e) = check(ret) {
+            unsafe { Collector::discard(data) };
+            return Err(e);
+        }
+
+        Ok(())
+    }
+
+    /// Looks up the addresses at `coordinates`, `callback_fn` is called with all of them.
+    pub fn reverse_geocode<F>(&self, coordinates: Coordinates, callback_fn: F) -> Result<()>
+    where
+        F: FnOnce(Result<Vec<Address>>),
+        F: 'static,
+    {
+        let data = Collector::into_raw(Box::new(callback_fn));
+        let mut request_id = 0;
+
+        let ret = unsafe {
+            maps_service_reverse_geocode(
+                self.handle,
+                coordinates.latitude,
+                coordinates.longitude,
+                null_mut(),
+                Some(reverse_geocode_handler),
+                data as *mut c_void,
+                &mut request_id,
+            )
+        };
+
+        if let Err(e) = check(ret) {
+            unsafe { Collector::discard(data) };
+            return Err(e);
+        }
+
+        Ok(())
+    }
+}
+
+impl Drop for MapsService {
+    fn drop(&mut self) {
+        unsafe {
+            maps_service_destroy(self.handle);
+        }
+    }
+}
+
+extern "C" fn geocode_handler(
+    result: c_int,
+    _request_id: c_int,
+    index: c_int,
+    total: c_int,
+    coordinates: maps_coordinates_h,
+    user_data: *mut c_void,
+) -> bool {
+    abort_on_panic(|| unsafe {
+        let item = check(result).and_then(|_| {
+            let item = read_coordinates(coordinates);
+            maps_coordinates_destroy(coordinates);
+            item
+        });
+
+        Collector::feed(user_data as *mut Collector<Coordinates>, item, index, total)
+    })
+}
+
+extern "C" fn reverse_geocode_handler(
+    result: c_int,
+    _request_id: c_int,
+    index: c_int,
+    total: c_int,
+    address: maps_address_h,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| unsafe {
+        let item = check(result).map(|_| {
+            let item = Address::from_raw(address);
+            maps_address_destroy(address);
+            item
+        });
+
+        Collector::feed(user_data as *mut Collector<Address>, item, index, total);
+    })
+}
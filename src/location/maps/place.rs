@@ -0,0 +1,134 @@
+use super::{read_coordinates, Address, Collector, MapsService, RawCoordinates};
+use crate::error::{check, Result};
+use crate::location::Coordinates;
+use crate::rutin::{abort_on_panic, cstring, take_string};
+use rutin_tizen_sys::{
+    maps_address_destroy, maps_coordinates_destroy, maps_place_destroy, maps_place_filter_create,
+    maps_place_filter_destroy, maps_place_filter_h, maps_place_filter_set_keyword,
+    maps_place_get_address, maps_place_get_distance, maps_place_get_id, maps_place_get_location,
+    maps_place_get_name, maps_place_h, maps_service_search_place,
+};
+use std::os::raw::{c_int, c_void};
+use std::ptr::null_mut;
+
+/// A point of interest found by [`MapsService::search_place`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Place {
+    pub id: Option<String>,
+    pub name: Option<String>,
+    pub location: Coordinates,
+    /// Distance from the center of the search, in meters.
+    pub distance: i32,
+    pub address: Option<Address>,
+}
+
+impl Place {
+    unsafe fn from_raw(handle: maps_place_h) -> Result<Place> {
+        let mut coordinates = null_mut();
+        check(maps_place_get_location(handle, &mut coordinates))?;
+        let location = read_coordinates(coordinates);
+        maps_coordinates_destroy(coordinates);
+
+        let mut id = null_mut();
+        let mut name = null_mut();
+        let mut distance = 0;
+        let mut address = null_mut();
+        maps_place_get_id(handle, &mut id);
+        maps_place_get_name(handle, &mut name);
+        maps_place_get_distance(handle, &mut distance);
+
+        let address = if check(maps_place_get_address(handle, &mut address)).is_ok() {
+            let value = Address::from_raw(address);
+            maps_address_destroy(address);
+            Some(value)
+        } else {
+            None
+        };
+
+        Ok(Place {
+            id: take_string(id),
+            name: take_string(name),
+            location: location?,
+            distance,
+            address,
+        })
+    }
+}
+
+struct RawFilter {
+    handle: maps_place_filter_h,
+}
+
+impl Drop for RawFilter {
+    fn drop(&mut self) {
+        unsafe {
+            maps_place_filter_destroy(self.handle);
+        }
+    }
+}
+
+impl MapsService {
+    /// Searches for places matching `keyword` within `radius` meters of `center`,
+    /// `callback_fn` is called with all of them.
+    pub fn search_place<F>(
+        &self,
+        center: Coordinates,
+        radius: i32,
+        keyword: &str,
+        callback_fn: F,
+    ) -> Result<()>
+    where
+        F: FnOnce(Result<Vec<Place>>),
+        F: 'static,
+    {
+        let keyword = cstring(keyword)?;
+        let center = RawCoordinates::new(center)?;
+
+        let mut filter = RawFilter { handle: null_mut() };
+        check(unsafe { maps_place_filter_create(&mut filter.handle) })?;
+        check(unsafe { maps_place_filter_set_keyword(filter.handle, keyword.as_ptr()) })?;
+
+        let data = Collector::into_raw(Box::new(callback_fn));
+        let mut request_id = 0;
+
+        let ret = unsafe {
+            maps_service_search_place(
+                self.handle,
+                center.handle,
+                radius,
+                filter.handle,
+                null_mut(),
+                Some(search_place_handler),
+                data as *mut c_void,
+                &mut request_id,
+            )
+        };
+
+        if let Err(e) = check(ret) {
+            unsafe { Collector::discard(data) };
+            return Err(e);
+        }
+
+        Ok(())
+    }
+}
+
+extern "C" fn search_place_handler(
+    error: c_int,
+    _request_id: c_int,
+    index: c_int,
+    total: c_int,
+    place: maps_place_h,
+    user_data: *mut c_void,
+) -> bool {
+    abort_on_panic(|| unsafe {
+        let item = check(error).and_then(|_| {
+            let item = Place::from_raw(place);
+            maps_place_destroy(place);
+            item
+        });
+
+        Collector::feed(user_data as *mut Collector<Place>, item, index, total)
+    })
+}
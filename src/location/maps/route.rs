@@ -0,0 +1,281 @@
+use super::{read_coordinates, Collector, MapsService, RawCoordinates};
+use crate::error::{check, Result};
+use crate::location::Coordinates;
+use crate::rutin::{abort_on_panic, take_string};
+use rutin_tizen_sys::{
+    maps_coordinates_destroy, maps_coordinates_h, maps_distance_unit_e_MAPS_DISTANCE_UNIT_M,
+    maps_preference_create, maps_preference_destroy, maps_preference_h,
+    maps_preference_set_distance_unit, maps_preference_set_route_transport_mode,
+    maps_route_destroy, maps_route_foreach_segment, maps_route_get_total_distance,
+    maps_route_get_total_duration, maps_route_h, maps_route_maneuver_destroy,
+    maps_route_maneuver_get_distance_to_next_instruction, maps_route_maneuver_get_instruction_text,
+    maps_route_maneuver_get_position, maps_route_maneuver_get_road_name,
+    maps_route_maneuver_get_time_to_next_instruction, maps_route_maneuver_h,
+    maps_route_segment_destroy, maps_route_segment_foreach_maneuver,
+    maps_route_segment_get_destination, maps_route_segment_get_distance,
+    maps_route_segment_get_duration, maps_route_segment_get_origin, maps_route_segment_h,
+    maps_route_transport_mode_e, maps_route_transport_mode_e_MAPS_ROUTE_TRANSPORT_MODE_BICYCLE,
+    maps_route_transport_mode_e_MAPS_ROUTE_TRANSPORT_MODE_CAR,
+    maps_route_transport_mode_e_MAPS_ROUTE_TRANSPORT_MODE_PEDESTRIAN,
+    maps_route_transport_mode_e_MAPS_ROUTE_TRANSPORT_MODE_PUBLICTRANSIT,
+    maps_route_transport_mode_e_MAPS_ROUTE_TRANSPORT_MODE_TRUCK, maps_service_search_route,
+};
+use std::os::raw::{c_int, c_long, c_void};
+use std::ptr::null_mut;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TransportMode {
+    Car,
+    Pedestrian,
+    Bicycle,
+    PublicTransit,
+    Truck,
+}
+
+impl From<TransportMode> for maps_route_transport_mode_e {
+    fn from(mode: TransportMode) -> maps_route_transport_mode_e {
+        match mode {
+            TransportMode::Car => maps_route_transport_mode_e_MAPS_ROUTE_TRANSPORT_MODE_CAR,
+            TransportMode::Pedestrian => {
+                maps_route_transport_mode_e_MAPS_ROUTE_TRANSPORT_MODE_PEDESTRIAN
+            }
+            TransportMode::Bicycle => maps_route_transport_mode_e_MAPS_ROUTE_TRANSPORT_MODE_BICYCLE,
+            TransportMode::PublicTransit => {
+                maps_route_transport_mode_e_MAPS_ROUTE_TRANSPORT_MODE_PUBLICTRANSIT
+            }
+            TransportMode::Truck => maps_route_transport_mode_e_MAPS_ROUTE_TRANSPORT_MODE_TRUCK,
+        }
+    }
+}
+
+/// Distances are in meters and durations in seconds.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Route {
+    pub distance: f64,
+    pub duration: i64,
+    /// Only filled in if the provider supports route segments.
+    pub segments: Vec<Segment>,
+}
+
+impl Route {
+    unsafe fn from_raw(handle: maps_route_h) -> Result<Route> {
+        let mut distance = 0.0;
+        let mut duration: c_long = 0;
+        check(maps_route_get_total_distance(handle, &mut distance))?;
+        check(maps_route_get_total_duration(handle, &mut duration))?;
+
+        let mut segments = Vec::<Segment>::new();
+        maps_route_foreach_segment(
+            handle,
+            Some(segment_handler),
+            &mut segments as *mut Vec<Segment> as *mut c_void,
+        );
+
+        Ok(Route {
+            distance,
+            duration: i64::from(duration),
+            segments,
+        })
+    }
+}
+
+/// A leg of a [`Route`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Segment {
+    pub origin: Coordinates,
+    pub destination: Coordinates,
+    pub distance: f64,
+    pub duration: i64,
+    pub maneuvers: Vec<Maneuver>,
+}
+
+impl Segment {
+    unsafe fn from_raw(handle: maps_route_segment_h) -> Result<Segment> {
+        let origin = take_coordinates(handle, maps_route_segment_get_origin)?;
+        let destination = take_coordinates(handle, maps_route_segment_get_destination)?;
+
+        let mut distance = 0.0;
+        let mut duration: c_long = 0;
+        check(maps_route_segment_get_distance(handle, &mut distance))?;
+        check(maps_route_segment_get_duration(handle, &mut duration))?;
+
+        let mut maneuvers = Vec::<Maneuver>::new();
+        maps_route_segment_foreach_maneuver(
+            handle,
+            Some(maneuver_handler),
+            &mut maneuvers as *mut Vec<Maneuver> as *mut c_void,
+        );
+
+        Ok(Segment {
+            origin,
+            destination,
+            distance,
+            duration: i64::from(duration),
+            maneuvers,
+        })
+    }
+}
+
+/// A turn-by-turn instruction.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Maneuver {
+    pub position: Option<Coordinates>,
+    pub road_name: Option<String>,
+    pub instruction: Option<String>,
+    pub distance_to_next: f64,
+    pub time_to_next: i32,
+}
+
+impl Maneuver {
+    unsafe fn from_raw(handle: maps_route_maneuver_h) -> Maneuver {
+        let mut road_name = null_mut();
+        let mut instruction = null_mut();
+        let mut distance_to_next = 0.0;
+        let mut time_to_next = 0;
+        maps_route_maneuver_get_road_name(handle, &mut road_name);
+        maps_route_maneuver_get_instruction_text(handle, &mut instruction);
+        maps_route_maneuver_get_distance_to_next_instruction(handle, &mut distance_to_next);
+        maps_route_maneuver_get_time_to_next_instruction(handle, &mut time_to_next);
+
+        Maneuver {
+            position: take_coordinates(handle, maps_route_maneuver_get_position).ok(),
+            road_name: take_string(road_name),
+            instruction: take_string(instruction),
+            distance_to_next,
+            time_to_next,
+        }
+    }
+}
+
+/// Reads coordinates copied out of `handle` by `get`, freeing the copy.
+unsafe fn take_coordinates<H>(
+    handle: H,
+    get: unsafe extern "C" fn(H, *mut maps_coordinates_h) -> c_int,
+) -> Result<Coordinates> {
+    let mut coordinates = null_mut();
+    check(get(handle, &mut coordinates))?;
+
+    let value = read_coordinates(coordinates);
+    maps_coordinates_destroy(coordinates);
+
+    value
+}
+
+struct RawPreference {
+    handle: maps_preference_h,
+}
+
+impl Drop for RawPreference {
+    fn drop(&mut self) {
+        unsafe {
+            maps_preference_destroy(self.handle);
+        }
+    }
+}
+
+impl MapsService {
+    /// Calculates the routes from `origin` to `destination`, `callback_fn` is called with all
+    /// of them.
+    pub fn search_route<F>(
+        &self,
+        origin: Coordinates,
+        destination: Coordinates,
+        mode: TransportMode,
+        callback_fn: F,
+    ) -> Result<()>
+    where
+        F: FnOnce(Result<Vec<Route>>),
+        F: 'static,
+    {
+        let origin = RawCoordinates::new(origin)?;
+        let destination = RawCoordinates::new(destination)?;
+
+        let mut preference = RawPreference { handle: null_mut() };
+        check(unsafe { maps_preference_create(&mut preference.handle) })?;
+        check(unsafe {
+            maps_preference_set_distance_unit(
+                preference.handle,
+                maps_distance_unit_e_MAPS_DISTANCE_UNIT_M,
+            )
+        })?;
+        check(unsafe { maps_preference_set_route_transport_mode(preference.handle, mode.into()) })?;
+
+        let data = Collector::into_raw(Box::new(callback_fn));
+        let mut request_id = 0;
+
+        let ret = unsafe {
+            maps_service_search_route(
+                self.handle,
+                origin.handle,
+                destination.handle,
+                preference.handle,
+                Some(search_route_handler),
+                data as *mut c_void,
+                &mut request_id,
+            )
+        };
+
+        if let Err(e) = check(ret) {
+            unsafe { Collector::discard(data) };
+            return Err(e);
+        }
+
+        Ok(())
+    }
+}
+
+extern "C" fn search_route_handler(
+    error: c_int,
+    _request_id: c_int,
+    index: c_int,
+    total: c_int,
+    route: maps_route_h,
+    user_data: *mut c_void,
+) -> bool {
+    abort_on_panic(|| unsafe {
+        let item = check(error).and_then(|_| {
+            let item = Route::from_raw(route);
+            maps_route_destroy(route);
+            item
+        });
+
+        Collector::feed(user_data as *mut Collector<Route>, item, index, total)
+    })
+}
+
+extern "C" fn segment_handler(
+    _index: c_int,
+    _total: c_int,
+    segment: maps_route_segment_h,
+    user_data: *mut c_void,
+) -> bool {
+    abort_on_panic(|| unsafe {
+        let segments = &mut *(user_data as *mut Vec<Segment>);
+
+        if let Ok(value) = Segment::from_raw(segment) {
+            segments.push(value);
+        }
+        maps_route_segment_destroy(segment);
+
+        true
+    })
+}
+
+extern "C" fn maneuver_handler(
+    _index: c_int,
+    _total: c_int,
+    maneuver: maps_route_maneuver_h,
+    user_data: *mut c_void,
+) -> bool {
+    abort_on_panic(|| unsafe {
+        let maneuvers = &mut *(user_data as *mut Vec<Maneuver>);
+        maneuvers.push(Maneuver::from_raw(maneuver));
+        maps_route_maneuver_destroy(maneuver);
+
+        true
+    })
+}
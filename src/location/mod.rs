@@ -0,0 +1,14 @@
+//! Position of the device and location based services.
+//!
+//! Requires the `http://tizen.org/privilege/location` privilege.
+
+pub mod geofence;
+mod manager;
+pub mod maps;
+mod settings;
+
+pub use manager::{
+    Accuracy, AccuracyLevel, BatchListener, Coordinates, Location, LocationListener,
+    LocationManager, LocationMethod, Position, Velocity,
+};
+pub use settings::{is_enabled_method, launch_settings, SettingListener};
@@ -0,0 +1,83 @@
+use super::LocationMethod;
+use crate::error::{check, Result};
+use crate::rutin::{abort_on_panic, pin_callback};
+use rutin_tizen_sys::{
+    app_control_create, app_control_destroy, app_control_send_launch_request,
+    app_control_set_operation, location_manager_is_enabled_method,
+    location_manager_set_setting_changed_cb, location_manager_unset_setting_changed_cb,
+    location_method_e,
+};
+use std::os::raw::c_void;
+use std::pin::Pin;
+use std::ptr::null_mut;
+
+const OPERATION_SETTING_LOCATION: &str = "http://tizen.org/appcontrol/operation/setting/location\0";
+
+/// Whether the user allows `method` in the location settings.
+pub fn is_enabled_method(method: LocationMethod) -> Result<bool> {
+    let mut enabled = false;
+    check(unsafe { location_manager_is_enabled_method(method.into(), &mut enabled) })?;
+
+    Ok(enabled)
+}
+
+/// Opens the location settings screen, so that the user can enable a method.
+pub fn launch_settings() -> Result<()> {
+    let mut handle = null_mut();
+    check(unsafe { app_control_create(&mut handle) })?;
+
+    let ret = check(unsafe {
+        app_control_set_operation(handle, OPERATION_SETTING_LOCATION.as_ptr() as *const _)
+    })
+    .and_then(|_| check(unsafe { app_control_send_launch_request(handle, None, null_mut()) }));
+
+    unsafe {
+        app_control_destroy(handle);
+    }
+
+    ret
+}
+
+type SettingFn = Box<dyn FnMut(bool)>;
+
+/// Calls a callback when `method` is enabled (`true`) or disabled in the settings, until dropped.
+///
+/// Only one listener can exist per method.
+pub struct SettingListener {
+    method: LocationMethod,
+    _callback_fn: Pin<Box<SettingFn>>,
+}
+
+impl SettingListener {
+    pub fn new<F>(method: LocationMethod, callback_fn: F) -> Result<SettingListener>
+    where
+        F: FnMut(bool),
+        F: 'static,
+    {
+        let (callback_fn, data) = pin_callback::<dyn FnMut(bool)>(Box::new(callback_fn));
+
+        check(unsafe {
+            location_manager_set_setting_changed_cb(method.into(), Some(setting_handler), data)
+        })?;
+
+        Ok(Self {
+            method,
+            _callback_fn: callback_fn,
+        })
+    }
+}
+
+impl Drop for SettingListener {
+    fn drop(&mut self) {
+        unsafe {
+            location_manager_unset_setting_changed_cb(self.method.into());
+        }
+    }
+}
+
+extern "C" fn setting_handler(_method: location_method_e, enable: bool, user_data: *mut c_void) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut SettingFn) };
+        closure(enable);
+    })
+}
@@ -0,0 +1,350 @@
+//! Raw PCM capture and playback.
+//!
+//! Capturing requires the `http://tizen.org/privilege/recorder` privilege.
+
+use super::{RawStreamInfo, StreamType};
+use crate::error::{check, check_count, Error, Result};
+use crate::rutin::{abort_on_panic, cstring, pin_callback};
+use rutin_tizen_sys::{
+    audio_channel_e, audio_channel_e_AUDIO_CHANNEL_MONO, audio_channel_e_AUDIO_CHANNEL_STEREO,
+    audio_in_create, audio_in_destroy, audio_in_drop, audio_in_flush, audio_in_get_buffer_size,
+    audio_in_h, audio_in_pause, audio_in_peek, audio_in_prepare, audio_in_read, audio_in_resume,
+    audio_in_set_sound_stream_info, audio_in_set_stream_cb, audio_in_unprepare,
+    audio_in_unset_stream_cb, audio_out_create_new, audio_out_destroy, audio_out_drain,
+    audio_out_flush, audio_out_get_buffer_size, audio_out_h, audio_out_pause, audio_out_prepare,
+    audio_out_resume, audio_out_set_sound_stream_info, audio_out_set_stream_cb,
+    audio_out_unprepare, audio_out_unset_stream_cb, audio_out_write, audio_sample_type_e,
+    audio_sample_type_e_AUDIO_SAMPLE_TYPE_S16_LE, audio_sample_type_e_AUDIO_SAMPLE_TYPE_S24_32_LE,
+    audio_sample_type_e_AUDIO_SAMPLE_TYPE_S24_LE, audio_sample_type_e_AUDIO_SAMPLE_TYPE_S32_LE,
+    audio_sample_type_e_AUDIO_SAMPLE_TYPE_U8,
+};
+use std::io;
+use std::os::raw::{c_uint, c_void};
+use std::pin::Pin;
+use std::ptr::{null, null_mut};
+use std::slice;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Channel {
+    Mono,
+    Stereo,
+}
+
+impl From<Channel> for audio_channel_e {
+    fn from(channel: Channel) -> audio_channel_e {
+        match channel {
+            Channel::Mono => audio_channel_e_AUDIO_CHANNEL_MONO,
+            Channel::Stereo => audio_channel_e_AUDIO_CHANNEL_STEREO,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SampleType {
+    U8,
+    S16Le,
+    /// 24 bits packed in 3 bytes.
+    S24Le,
+    /// 24 bits in the low bytes of 4.
+    S24In32Le,
+    S32Le,
+}
+
+impl From<SampleType> for audio_sample_type_e {
+    fn from(sample_type: SampleType) -> audio_sample_type_e {
+        match sample_type {
+            SampleType::U8 => audio_sample_type_e_AUDIO_SAMPLE_TYPE_U8,
+            SampleType::S16Le => audio_sample_type_e_AUDIO_SAMPLE_TYPE_S16_LE,
+            SampleType::S24Le => audio_sample_type_e_AUDIO_SAMPLE_TYPE_S24_LE,
+            SampleType::S24In32Le => audio_sample_type_e_AUDIO_SAMPLE_TYPE_S24_32_LE,
+            SampleType::S32Le => audio_sample_type_e_AUDIO_SAMPLE_TYPE_S32_LE,
+        }
+    }
+}
+
+type InputFn = Box<dyn FnMut(&[u8]) + Send>;
+
+/// Audio captured from the microphone.
+///
+/// Reading is either synchronous with [`AudioIn::read`], or done through the stream callback,
+/// which runs on the audio thread of the platform.
+pub struct AudioIn {
+    handle: audio_in_h,
+    stream_fn: Option<Pin<Box<InputFn>>>,
+}
+
+impl AudioIn {
+    /// `sample_rate` is between 8000 and 48000 Hz.
+    pub fn new(sample_rate: i32, channel: Channel, sample_type: SampleType) -> Result<AudioIn> {
+        let mut handle = null_mut();
+        check(unsafe {
+            audio_in_create(sample_rate, channel.into(), sample_type.into(), &mut handle)
+        })?;
+
+        Ok(Self {
+            handle,
+            stream_fn: None,
+        })
+    }
+
+    /// Sets what the audio is recorded for, before preparing.
+    pub fn set_stream_type(&self, stream_type: StreamType) -> Result<()> {
+        let stream_info = RawStreamInfo::new(stream_type)?;
+
+        check(unsafe { audio_in_set_sound_stream_info(self.handle, stream_info.handle) })
+    }
+
+    /// Starts capturing.
+    pub fn prepare(&self) -> Result<()> {
+        check(unsafe { audio_in_prepare(self.handle) })
+    }
+
+    pub fn unprepare(&self) -> Result<()> {
+        check(unsafe { audio_in_unprepare(self.handle) })
+    }
+
+    pub fn pause(&self) -> Result<()> {
+        check(unsafe { audio_in_pause(self.handle) })
+    }
+
+    pub fn resume(&self) -> Result<()> {
+        check(unsafe { audio_in_resume(self.handle) })
+    }
+
+    /// Discards the captured audio which has not been read yet.
+    pub fn flush(&self) -> Result<()> {
+        check(unsafe { audio_in_flush(self.handle) })
+    }
+
+    /// The size in bytes of the buffers to read.
+    pub fn buffer_size(&self) -> Result<usize> {
+        let mut size = 0;
+        check(unsafe { audio_in_get_buffer_size(self.handle, &mut size) })?;
+
+        Ok(size as usize)
+    }
+
+    /// Blocks until `buffer` is filled, returning the number of bytes read.
+    pub fn read(&mut self, buffer: &mut [u8]) -> Result<usize> {
+        check_count(unsafe {
+            audio_in_read(
+                self.handle,
+                buffer.as_mut_ptr() as *mut c_void,
+                buffer.len() as c_uint,
+            )
+        })
+    }
+
+    /// Calls `callback_fn` on the audio thread with each captured fragment once prepared,
+    /// replacing the previous callback. [`AudioIn::read`] can't be used while set.
+    pub fn set_stream_cb<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(&[u8]),
+        F: Send,
+        F: 'static,
+    {
+        let (callback_fn, data) = pin_callback::<dyn FnMut(&[u8]) + Send>(Box::new(callback_fn));
+
+        check(unsafe { audio_in_set_stream_cb(self.handle, Some(input_handler), data) })?;
+        self.stream_fn = Some(callback_fn);
+
+        Ok(())
+    }
+
+    pub fn unset_stream_cb(&mut self) -> Result<()> {
+        check(unsafe { audio_in_unset_stream_cb(self.handle) })?;
+        self.stream_fn = None;
+
+        Ok(())
+    }
+}
+
+impl io::Read for AudioIn {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        AudioIn::read(self, buf).map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+    }
+}
+
+impl Drop for AudioIn {
+    fn drop(&mut self) {
+        unsafe {
+            if self.stream_fn.is_some() {
+                audio_in_unset_stream_cb(self.handle);
+            }
+
+            audio_in_unprepare(self.handle);
+            audio_in_destroy(self.handle);
+        }
+    }
+}
+
+struct OutputState {
+    callback_fn: Box<dyn FnMut(&mut [u8]) + Send>,
+    // reused by each fragment, only grown when the requested size is larger
+    buffer: Vec<u8>,
+}
+
+/// Audio played on the speaker.
+///
+/// Writing is either synchronous with [`AudioOut::write`], or done through the stream callback,
+/// which runs on the audio thread of the platform.
+pub struct AudioOut {
+    handle: audio_out_h,
+    stream_fn: Option<Pin<Box<OutputState>>>,
+}
+
+impl AudioOut {
+    /// `sample_rate` is between 8000 and 48000 Hz.
+    pub fn new(sample_rate: i32, channel: Channel, sample_type: SampleType) -> Result<AudioOut> {
+        let mut handle = null_mut();
+        check(unsafe {
+            audio_out_create_new(sample_rate, channel.into(), sample_type.into(), &mut handle)
+        })?;
+
+        Ok(Self {
+            handle,
+            stream_fn: None,
+        })
+    }
+
+    /// Sets what the audio is played for, before preparing.
+    pub fn set_stream_type(&self, stream_type: StreamType) -> Result<()> {
+        let stream_info = RawStreamInfo::new(stream_type)?;
+
+        check(unsafe { audio_out_set_sound_stream_info(self.handle, stream_info.handle) })
+    }
+
+    /// Starts playing.
+    pub fn prepare(&self) -> Result<()> {
+        check(unsafe { audio_out_prepare(self.handle) })
+    }
+
+    pub fn unprepare(&self) -> Result<()> {
+        check(unsafe { audio_out_unprepare(self.handle) })
+    }
+
+    pub fn pause(&self) -> Result<()> {
+        check(unsafe { audio_out_pause(self.handle) })
+    }
+
+    pub fn resume(&self) -> Result<()> {
+        check(unsafe { audio_out_resume(self.handle) })
+    }
+
+    /// Blocks until all the written audio has been played.
+    pub fn drain(&self) -> Result<()> {
+        check(unsafe { audio_out_drain(self.handle) })
+    }
+
+    /// Discards the written audio which has not been played yet.
+    pub fn flush(&self) -> Result<()> {
+        check(unsafe { audio_out_flush(self.handle) })
+    }
+
+    /// The size in bytes of the buffers to write.
+    pub fn buffer_size(&self) -> Result<usize> {
+        let mut size = 0;
+        check(unsafe { audio_out_get_buffer_size(self.handle, &mut size) })?;
+
+        Ok(size as usize)
+    }
+
+    /// Blocks until `buffer` is queued, returning the number of bytes written.
+    pub fn write(&mut self, buffer: &[u8]) -> Result<usize> {
+        check_count(unsafe {
+            audio_out_write(
+                self.handle,
+                buffer.as_ptr() as *mut c_void,
+                buffer.len() as c_uint,
+            )
+        })
+    }
+
+    /// Calls `callback_fn` on the audio thread to fill each fragment to play once prepared,
+    /// replacing the previous callback. [`AudioOut::write`] can't be used while set.
+    pub fn set_stream_cb<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(&mut [u8]),
+        F: Send,
+        F: 'static,
+    {
+        let state = Box::pin(OutputState {
+            callback_fn: Box::new(callback_fn),
+            buffer: Vec::new(),
+        });
+        let data = &*state as *const OutputState as *mut c_void;
+
+        check(unsafe { audio_out_set_stream_cb(self.handle, Some(output_handler), data) })?;
+        self.stream_fn = Some(state);
+
+        Ok(())
+    }
+
+    pub fn unset_stream_cb(&mut self) -> Result<()> {
+        check(unsafe { audio_out_unset_stream_cb(self.handle) })?;
+        self.stream_fn = None;
+
+        Ok(())
+    }
+}
+
+impl io::Write for AudioOut {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        AudioOut::write(self, buf).map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.drain()
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+    }
+}
+
+impl Drop for AudioOut {
+    fn drop(&mut self) {
+        unsafe {
+            if self.stream_fn.is_some() {
+                audio_out_unset_stream_cb(self.handle);
+            }
+
+            audio_out_unprepare(self.handle);
+            audio_out_destroy(self.handle);
+        }
+    }
+}
+
+extern "C" fn input_handler(handle: audio_in_h, _nbytes: usize, user_data: *mut c_void) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut InputFn) };
+        let mut buffer = null();
+        let mut length = 0;
+
+        if check(unsafe { audio_in_peek(handle, &mut buffer, &mut length) }).is_ok() {
+            closure(unsafe { slice::from_raw_parts(buffer as *const u8, length as usize) });
+
+            unsafe {
+                audio_in_drop(handle);
+            }
+        }
+    })
+}
+
+extern "C" fn output_handler(handle: audio_out_h, nbytes: usize, user_data: *mut c_void) {
+    abort_on_panic(|| {
+        let state = unsafe { &mut *(user_data as *mut OutputState) };
+
+        state.buffer.clear();
+        state.buffer.resize(nbytes, 0);
+
+        (state.callback_fn)(&mut state.buffer);
+
+        unsafe {
+            audio_out_write(
+                handle,
+                state.buffer.as_mut_ptr() as *mut c_void,
+                nbytes as c_uint,
+            );
+        }
+    })
+}
@@ -0,0 +1,486 @@
+//! The device cameras.
+//!
+//! Requires the `http://tizen.org/privilege/camera` privilege.
+
+use super::DisplayType;
+use crate::efl::evas::Object;
+use crate::error::{check, Result};
+use crate::rutin::{abort_on_panic, pin_callback};
+use rutin_tizen_sys::{
+    camera_attr_af_mode_e, camera_attr_af_mode_e_CAMERA_ATTR_AF_FULL,
+    camera_attr_af_mode_e_CAMERA_ATTR_AF_MACRO, camera_attr_af_mode_e_CAMERA_ATTR_AF_NONE,
+    camera_attr_af_mode_e_CAMERA_ATTR_AF_NORMAL, camera_attr_flash_mode_e,
+    camera_attr_flash_mode_e_CAMERA_ATTR_FLASH_MODE_AUTO,
+    camera_attr_flash_mode_e_CAMERA_ATTR_FLASH_MODE_OFF,
+    camera_attr_flash_mode_e_CAMERA_ATTR_FLASH_MODE_ON,
+    camera_attr_flash_mode_e_CAMERA_ATTR_FLASH_MODE_PERMANENT,
+    camera_attr_flash_mode_e_CAMERA_ATTR_FLASH_MODE_REDEYE_REDUCTION, camera_attr_get_flash_mode,
+    camera_attr_set_af_mode, camera_attr_set_flash_mode, camera_cancel_focusing, camera_create,
+    camera_destroy, camera_device_e, camera_device_e_CAMERA_DEVICE_CAMERA0,
+    camera_device_e_CAMERA_DEVICE_CAMERA1, camera_display_type_e_CAMERA_DISPLAY_TYPE_EVAS,
+    camera_display_type_e_CAMERA_DISPLAY_TYPE_OVERLAY, camera_focus_state_e,
+    camera_focus_state_e_CAMERA_FOCUS_STATE_FAILED,
+    camera_focus_state_e_CAMERA_FOCUS_STATE_FOCUSED,
+    camera_focus_state_e_CAMERA_FOCUS_STATE_ONGOING, camera_foreach_supported_capture_resolution,
+    camera_foreach_supported_preview_resolution, camera_get_preview_resolution, camera_get_state,
+    camera_h, camera_image_data_s, camera_pixel_format_e,
+    camera_pixel_format_e_CAMERA_PIXEL_FORMAT_I420,
+    camera_pixel_format_e_CAMERA_PIXEL_FORMAT_INVALID,
+    camera_pixel_format_e_CAMERA_PIXEL_FORMAT_JPEG, camera_pixel_format_e_CAMERA_PIXEL_FORMAT_NV12,
+    camera_pixel_format_e_CAMERA_PIXEL_FORMAT_NV21,
+    camera_pixel_format_e_CAMERA_PIXEL_FORMAT_RGB565,
+    camera_pixel_format_e_CAMERA_PIXEL_FORMAT_RGB888,
+    camera_pixel_format_e_CAMERA_PIXEL_FORMAT_RGBA, camera_pixel_format_e_CAMERA_PIXEL_FORMAT_UYVY,
+    camera_pixel_format_e_CAMERA_PIXEL_FORMAT_YUYV, camera_pixel_format_e_CAMERA_PIXEL_FORMAT_YV12,
+    camera_set_capture_format, camera_set_capture_resolution, camera_set_display,
+    camera_set_focus_changed_cb, camera_set_preview_format, camera_set_preview_resolution,
+    camera_start_capture, camera_start_focusing, camera_start_preview, camera_state_e,
+    camera_state_e_CAMERA_STATE_CAPTURED, camera_state_e_CAMERA_STATE_CAPTURING,
+    camera_state_e_CAMERA_STATE_CREATED, camera_state_e_CAMERA_STATE_NONE,
+    camera_state_e_CAMERA_STATE_PREVIEW, camera_stop_preview, camera_unset_focus_changed_cb,
+};
+use std::os::raw::{c_int, c_void};
+use std::pin::Pin;
+use std::ptr::null_mut;
+use std::slice;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CameraDevice {
+    /// Usually the rear camera.
+    Primary,
+    /// Usually the front camera.
+    Secondary,
+}
+
+impl From<CameraDevice> for camera_device_e {
+    fn from(device: CameraDevice) -> camera_device_e {
+        match device {
+            CameraDevice::Primary => camera_device_e_CAMERA_DEVICE_CAMERA0,
+            CameraDevice::Secondary => camera_device_e_CAMERA_DEVICE_CAMERA1,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CameraState {
+    None,
+    Created,
+    Preview,
+    Capturing,
+    /// A still has been captured, the preview must be started again.
+    Captured,
+}
+
+impl From<camera_state_e> for CameraState {
+    fn from(state: camera_state_e) -> CameraState {
+        match state {
+            camera_state_e_CAMERA_STATE_CREATED => CameraState::Created,
+            camera_state_e_CAMERA_STATE_PREVIEW => CameraState::Preview,
+            camera_state_e_CAMERA_STATE_CAPTURING => CameraState::Capturing,
+            camera_state_e_CAMERA_STATE_CAPTURED => CameraState::Captured,
+            _ => CameraState::None,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PixelFormat {
+    Nv12,
+    Nv21,
+    Yuyv,
+    Uyvy,
+    I420,
+    Yv12,
+    Rgb565,
+    Rgb888,
+    Rgba,
+    Jpeg,
+    Invalid,
+}
+
+impl From<PixelFormat> for camera_pixel_format_e {
+    fn from(format: PixelFormat) -> camera_pixel_format_e {
+        match format {
+            PixelFormat::Nv12 => camera_pixel_format_e_CAMERA_PIXEL_FORMAT_NV12,
+            PixelFormat::Nv21 => camera_pixel_format_e_CAMERA_PIXEL_FORMAT_NV21,
+            PixelFormat::Yuyv => camera_pixel_format_e_CAMERA_PIXEL_FORMAT_YUYV,
+            PixelFormat::Uyvy => camera_pixel_format_e_CAMERA_PIXEL_FORMAT_UYVY,
+            PixelFormat::I420 => camera_pixel_format_e_CAMERA_PIXEL_FORMAT_I420,
+            PixelFormat::Yv12 => camera_pixel_format_e_CAMERA_PIXEL_FORMAT_YV12,
+            PixelFormat::Rgb565 => camera_pixel_format_e_CAMERA_PIXEL_FORMAT_RGB565,
+            PixelFormat::Rgb888 => camera_pixel_format_e_CAMERA_PIXEL_FORMAT_RGB888,
+            PixelFormat::Rgba => camera_pixel_format_e_CAMERA_PIXEL_FORMAT_RGBA,
+            PixelFormat::Jpeg => camera_pixel_format_e_CAMERA_PIXEL_FORMAT_JPEG,
+            PixelFormat::Invalid => camera_pixel_format_e_CAMERA_PIXEL_FORMAT_INVALID,
+        }
+    }
+}
+
+impl From<camera_pixel_format_e> for PixelFormat {
+    fn from(format: camera_pixel_format_e) -> PixelFormat {
+        match format {
+            camera_pixel_format_e_CAMERA_PIXEL_FORMAT_NV12 => PixelFormat::Nv12,
+            camera_pixel_format_e_CAMERA_PIXEL_FORMAT_NV21 => PixelFormat::Nv21,
+            camera_pixel_format_e_CAMERA_PIXEL_FORMAT_YUYV => PixelFormat::Yuyv,
+            camera_pixel_format_e_CAMERA_PIXEL_FORMAT_UYVY => PixelFormat::Uyvy,
+            camera_pixel_format_e_CAMERA_PIXEL_FORMAT_I420 => PixelFormat::I420,
+            camera_pixel_format_e_CAMERA_PIXEL_FORMAT_YV12 => PixelFormat::Yv12,
+            camera_pixel_format_e_CAMERA_PIXEL_FORMAT_RGB565 => PixelFormat::Rgb565,
+            camera_pixel_format_e_CAMERA_PIXEL_FORMAT_RGB888 => PixelFormat::Rgb888,
+            camera_pixel_format_e_CAMERA_PIXEL_FORMAT_RGBA => PixelFormat::Rgba,
+            camera_pixel_format_e_CAMERA_PIXEL_FORMAT_JPEG => PixelFormat::Jpeg,
+            _ => PixelFormat::Invalid,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AfMode {
+    None,
+    Normal,
+    Macro,
+    Full,
+}
+
+impl From<AfMode> for camera_attr_af_mode_e {
+    fn from(mode: AfMode) -> camera_attr_af_mode_e {
+        match mode {
+            AfMode::None => camera_attr_af_mode_e_CAMERA_ATTR_AF_NONE,
+            AfMode::Normal => camera_attr_af_mode_e_CAMERA_ATTR_AF_NORMAL,
+            AfMode::Macro => camera_attr_af_mode_e_CAMERA_ATTR_AF_MACRO,
+            AfMode::Full => camera_attr_af_mode_e_CAMERA_ATTR_AF_FULL,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FocusState {
+    Released,
+    Ongoing,
+    Focused,
+    Failed,
+}
+
+impl From<camera_focus_state_e> for FocusState {
+    fn from(state: camera_focus_state_e) -> FocusState {
+        match state {
+            camera_focus_state_e_CAMERA_FOCUS_STATE_ONGOING => FocusState::Ongoing,
+            camera_focus_state_e_CAMERA_FOCUS_STATE_FOCUSED => FocusState::Focused,
+            camera_focus_state_e_CAMERA_FOCUS_STATE_FAILED => FocusState::Failed,
+            _ => FocusState::Released,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FlashMode {
+    Off,
+    On,
+    Auto,
+    RedEyeReduction,
+    /// Always on, as a torch.
+    Permanent,
+}
+
+impl From<FlashMode> for camera_attr_flash_mode_e {
+    fn from(mode: FlashMode) -> camera_attr_flash_mode_e {
+        match mode {
+            FlashMode::Off => camera_attr_flash_mode_e_CAMERA_ATTR_FLASH_MODE_OFF,
+            FlashMode::On => camera_attr_flash_mode_e_CAMERA_ATTR_FLASH_MODE_ON,
+            FlashMode::Auto => camera_attr_flash_mode_e_CAMERA_ATTR_FLASH_MODE_AUTO,
+            FlashMode::RedEyeReduction => {
+                camera_attr_flash_mode_e_CAMERA_ATTR_FLASH_MODE_REDEYE_REDUCTION
+            }
+            FlashMode::Permanent => camera_attr_flash_mode_e_CAMERA_ATTR_FLASH_MODE_PERMANENT,
+        }
+    }
+}
+
+impl From<camera_attr_flash_mode_e> for FlashMode {
+    fn from(mode: camera_attr_flash_mode_e) -> FlashMode {
+        match mode {
+            camera_attr_flash_mode_e_CAMERA_ATTR_FLASH_MODE_ON => FlashMode::On,
+            camera_attr_flash_mode_e_CAMERA_ATTR_FLASH_MODE_AUTO => FlashMode::Auto,
+            camera_attr_flash_mode_e_CAMERA_ATTR_FLASH_MODE_REDEYE_REDUCTION => {
+                FlashMode::RedEyeReduction
+            }
+            camera_attr_flash_mode_e_CAMERA_ATTR_FLASH_MODE_PERMANENT => FlashMode::Permanent,
+            _ => FlashMode::Off,
+        }
+    }
+}
+
+/// A captured still, only valid during the capture callback.
+#[derive(Debug)]
+pub struct CapturedImage<'a> {
+    pub data: &'a [u8],
+    pub width: i32,
+    pub height: i32,
+    pub format: PixelFormat,
+    /// The EXIF metadata of JPEG images, possibly empty.
+    pub exif: &'a [u8],
+}
+
+impl<'a> CapturedImage<'a> {
+    unsafe fn from_raw(image: *const camera_image_data_s) -> Option<CapturedImage<'a>> {
+        let image = image.as_ref()?;
+
+        let exif = if image.exif.is_null() {
+            &[]
+        } else {
+            slice::from_raw_parts(image.exif as *const u8, image.exif_size as usize)
+        };
+
+        Some(CapturedImage {
+            data: slice::from_raw_parts(image.data as *const u8, image.data_len as usize),
+            width: image.width,
+            height: image.height,
+            format: image.format.into(),
+            exif,
+        })
+    }
+}
+
+struct CaptureRequest {
+    capturing_fn: Box<dyn FnMut(&CapturedImage<'_>, Option<&CapturedImage<'_>>)>,
+    completed_fn: Box<dyn FnOnce()>,
+}
+
+type FocusFn = Box<dyn FnMut(FocusState)>;
+
+pub struct Camera {
+    pub(crate) handle: camera_h,
+    focus_fn: Option<Pin<Box<FocusFn>>>,
+}
+
+impl Camera {
+    pub fn new(device: CameraDevice) -> Result<Camera> {
+        let mut handle = null_mut();
+        check(unsafe { camera_create(device.into(), &mut handle) })?;
+
+        Ok(Self {
+            handle,
+            focus_fn: None,
+        })
+    }
+
+    pub fn get_state(&self) -> Result<CameraState> {
+        let mut state = camera_state_e_CAMERA_STATE_NONE;
+        check(unsafe { camera_get_state(self.handle, &mut state) })?;
+
+        Ok(state.into())
+    }
+
+    /// Shows the preview on `object`, a window for [`DisplayType::Overlay`] or an image for
+    /// [`DisplayType::Evas`], which must outlive the preview.
+    pub fn set_display<'a, O: Object<'a>>(
+        &self,
+        display_type: DisplayType,
+        object: &mut O,
+    ) -> Result<()> {
+        let display_type = match display_type {
+            DisplayType::Overlay => camera_display_type_e_CAMERA_DISPLAY_TYPE_OVERLAY,
+            DisplayType::Evas => camera_display_type_e_CAMERA_DISPLAY_TYPE_EVAS,
+        };
+
+        check(unsafe {
+            camera_set_display(self.handle, display_type, object.eo_ptr() as *mut c_void)
+        })
+    }
+
+    pub fn supported_preview_resolutions(&self) -> Result<Vec<(i32, i32)>> {
+        let mut resolutions = Vec::<(i32, i32)>::new();
+
+        check(unsafe {
+            camera_foreach_supported_preview_resolution(
+                self.handle,
+                Some(resolution_handler),
+                &mut resolutions as *mut Vec<(i32, i32)> as *mut c_void,
+            )
+        })?;
+
+        Ok(resolutions)
+    }
+
+    pub fn supported_capture_resolutions(&self) -> Result<Vec<(i32, i32)>> {
+        let mut resolutions = Vec::<(i32, i32)>::new();
+
+        check(unsafe {
+            camera_foreach_supported_capture_resolution(
+                self.handle,
+                Some(resolution_handler),
+                &mut resolutions as *mut Vec<(i32, i32)> as *mut c_void,
+            )
+        })?;
+
+        Ok(resolutions)
+    }
+
+    pub fn get_preview_resolution(&self) -> Result<(i32, i32)> {
+        let (mut width, mut height) = (0, 0);
+        check(unsafe { camera_get_preview_resolution(self.handle, &mut width, &mut height) })?;
+
+        Ok((width, height))
+    }
+
+    pub fn set_preview_resolution(&self, width: i32, height: i32) -> Result<()> {
+        check(unsafe { camera_set_preview_resolution(self.handle, width, height) })
+    }
+
+    pub fn set_capture_resolution(&self, width: i32, height: i32) -> Result<()> {
+        check(unsafe { camera_set_capture_resolution(self.handle, width, height) })
+    }
+
+    pub fn set_preview_format(&self, format: PixelFormat) -> Result<()> {
+        check(unsafe { camera_set_preview_format(self.handle, format.into()) })
+    }
+
+    pub fn set_capture_format(&self, format: PixelFormat) -> Result<()> {
+        check(unsafe { camera_set_capture_format(self.handle, format.into()) })
+    }
+
+    pub fn start_preview(&self) -> Result<()> {
+        check(unsafe { camera_start_preview(self.handle) })
+    }
+
+    pub fn stop_preview(&self) -> Result<()> {
+        check(unsafe { camera_stop_preview(self.handle) })
+    }
+
+    /// Captures a still while previewing. `capturing_fn` is called with the image and its
+    /// thumbnail if any, then `completed_fn` once the camera can preview again.
+    pub fn start_capture<F, G>(&self, capturing_fn: F, completed_fn: G) -> Result<()>
+    where
+        F: FnMut(&CapturedImage<'_>, Option<&CapturedImage<'_>>),
+        F: 'static,
+        G: FnOnce(),
+        G: 'static,
+    {
+        let data = Box::into_raw(Box::new(CaptureRequest {
+            capturing_fn: Box::new(capturing_fn),
+            completed_fn: Box::new(completed_fn),
+        }));
+
+        let ret = unsafe {
+            camera_start_capture(
+                self.handle,
+                Some(capturing_handler),
+                Some(capture_completed_handler),
+                data as *mut c_void,
+            )
+        };
+
+        if let Err(e) = check(ret) {
+            drop(unsafe { Box::from_raw(data) });
+            return Err(e);
+        }
+
+        Ok(())
+    }
+
+    pub fn set_af_mode(&self, mode: AfMode) -> Result<()> {
+        check(unsafe { camera_attr_set_af_mode(self.handle, mode.into()) })
+    }
+
+    /// Starts auto focusing, once or until canceled if `continuous`.
+    pub fn start_focusing(&self, continuous: bool) -> Result<()> {
+        check(unsafe { camera_start_focusing(self.handle, continuous) })
+    }
+
+    pub fn cancel_focusing(&self) -> Result<()> {
+        check(unsafe { camera_cancel_focusing(self.handle) })
+    }
+
+    /// Calls `callback_fn` when auto focusing progresses, replacing the previous callback.
+    pub fn set_focus_changed_cb<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(FocusState),
+        F: 'static,
+    {
+        let (callback_fn, data) = pin_callback::<dyn FnMut(FocusState)>(Box::new(callback_fn));
+
+        check(unsafe { camera_set_focus_changed_cb(self.handle, Some(focus_handler), data) })?;
+        self.focus_fn = Some(callback_fn);
+
+        Ok(())
+    }
+
+    pub fn unset_focus_changed_cb(&mut self) -> Result<()> {
+        check(unsafe { camera_unset_focus_changed_cb(self.handle) })?;
+        self.focus_fn = None;
+
+        Ok(())
+    }
+
+    pub fn get_flash_mode(&self) -> Result<FlashMode> {
+        let mut mode = camera_attr_flash_mode_e_CAMERA_ATTR_FLASH_MODE_OFF;
+        check(unsafe { camera_attr_get_flash_mode(self.handle, &mut mode) })?;
+
+        Ok(mode.into())
+    }
+
+    pub fn set_flash_mode(&self, mode: FlashMode) -> Result<()> {
+        check(unsafe { camera_attr_set_flash_mode(self.handle, mode.into()) })
+    }
+}
+
+impl Drop for Camera {
+    fn drop(&mut self) {
+        unsafe {
+            if let Ok(CameraState::Preview) = self.get_state() {
+                camera_stop_preview(self.handle);
+            }
+
+            if self.focus_fn.is_some() {
+                camera_unset_focus_changed_cb(self.handle);
+            }
+
+            camera_destroy(self.handle);
+        }
+    }
+}
+
+extern "C" fn resolution_handler(width: c_int, height: c_int, user_data: *mut c_void) -> bool {
+    abort_on_panic(|| {
+        let resolutions = unsafe { &mut *(user_data as *mut Vec<(i32, i32)>) };
+        resolutions.push((width, height));
+
+        true
+    })
+}
+
+extern "C" fn capturing_handler(
+    image: *mut camera_image_data_s,
+    _postview: *mut camera_image_data_s,
+    thumbnail: *mut camera_image_data_s,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let request = unsafe { &mut *(user_data as *mut CaptureRequest) };
+
+        if let Some(image) = unsafe { CapturedImage::from_raw(image) } {
+            let thumbnail = unsafe { CapturedImage::from_raw(thumbnail) };
+            (request.capturing_fn)(&image, thumbnail.as_ref());
+        }
+    })
+}
+
+extern "C" fn capture_completed_handler(user_data: *mut c_void) {
+    abort_on_panic(|| {
+        let request = unsafe { Box::from_raw(user_data as *mut CaptureRequest) };
+        (request.completed_fn)();
+    })
+}
+
+extern "C" fn focus_handler(state: camera_focus_state_e, user_data: *mut c_void) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut FocusFn) };
+        closure(state.into());
+    })
+}
@@ -0,0 +1,536 @@
+//! Low level audio and video encoding and decoding.
+//!
+//! Input is fed packet by packet with [`MediaCodec::process_input`], the output is delivered
+//! asynchronously to the output callback.
+//!
+//! The output, end of stream and error callbacks run on the worker thread of the codec, not on
+//! the main loop, so they must be `Send`.
+
+use crate::error::{check, Error, Result};
+use crate::rutin::{abort_on_panic, pin_callback};
+use rutin_tizen_sys::{
+    media_buffer_flags_e_MEDIA_PACKET_END_OF_STREAM, media_format_create, media_format_h,
+    media_format_mimetype_e, media_format_mimetype_e_MEDIA_FORMAT_AAC,
+    media_format_mimetype_e_MEDIA_FORMAT_AMR_NB, media_format_mimetype_e_MEDIA_FORMAT_H263,
+    media_format_mimetype_e_MEDIA_FORMAT_H264_SP, media_format_mimetype_e_MEDIA_FORMAT_HEVC,
+    media_format_mimetype_e_MEDIA_FORMAT_I420, media_format_mimetype_e_MEDIA_FORMAT_MP3,
+    media_format_mimetype_e_MEDIA_FORMAT_MPEG4_SP, media_format_mimetype_e_MEDIA_FORMAT_OPUS,
+    media_format_mimetype_e_MEDIA_FORMAT_PCM, media_format_mimetype_e_MEDIA_FORMAT_VORBIS,
+    media_format_mimetype_e_MEDIA_FORMAT_VP8, media_format_set_audio_bit,
+    media_format_set_audio_channel, media_format_set_audio_mime, media_format_set_audio_samplerate,
+    media_format_set_video_height, media_format_set_video_mime, media_format_set_video_width,
+    media_format_unref, media_packet_create_alloc, media_packet_destroy,
+    media_packet_get_buffer_data_ptr, media_packet_get_buffer_size,
+    media_packet_get_number_of_video_planes, media_packet_get_pts,
+    media_packet_get_video_plane_data_ptr, media_packet_get_video_stride_height,
+    media_packet_get_video_stride_width, media_packet_h, media_packet_is_raw,
+    media_packet_is_video, media_packet_set_buffer_size, media_packet_set_flags,
+    media_packet_set_pts, mediacodec_codec_type_e, mediacodec_codec_type_e_MEDIACODEC_AAC,
+    mediacodec_codec_type_e_MEDIACODEC_AMR_NB, mediacodec_codec_type_e_MEDIACODEC_H263,
+    mediacodec_codec_type_e_MEDIACODEC_H264, mediacodec_codec_type_e_MEDIACODEC_HEVC,
+    mediacodec_codec_type_e_MEDIACODEC_MP3, mediacodec_codec_type_e_MEDIACODEC_MPEG4,
+    mediacodec_codec_type_e_MEDIACODEC_OPUS, mediacodec_codec_type_e_MEDIACODEC_VORBIS,
+    mediacodec_codec_type_e_MEDIACODEC_VP8, mediacodec_create, mediacodec_destroy,
+    mediacodec_flush_buffers, mediacodec_get_output, mediacodec_h, mediacodec_prepare,
+    mediacodec_process_input, mediacodec_set_adec_info, mediacodec_set_aenc_info,
+    mediacodec_set_codec, mediacodec_set_eos_cb, mediacodec_set_error_cb,
+    mediacodec_set_input_buffer_used_cb, mediacodec_set_output_buffer_available_cb,
+    mediacodec_set_vdec_info, mediacodec_set_venc_info,
+    mediacodec_support_type_e_MEDIACODEC_DECODER, mediacodec_support_type_e_MEDIACODEC_ENCODER,
+    mediacodec_support_type_e_MEDIACODEC_SUPPORT_TYPE_HW,
+    mediacodec_support_type_e_MEDIACODEC_SUPPORT_TYPE_SW, mediacodec_unprepare,
+    mediacodec_unset_eos_cb, mediacodec_unset_error_cb, mediacodec_unset_input_buffer_used_cb,
+    mediacodec_unset_output_buffer_available_cb,
+};
+use std::os::raw::{c_int, c_void};
+use std::pin::Pin;
+use std::ptr::{copy_nonoverlapping, null_mut};
+use std::slice;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Codec {
+    Aac,
+    Mp3,
+    AmrNb,
+    Vorbis,
+    Opus,
+    H263,
+    H264,
+    Mpeg4,
+    Vp8,
+    Hevc,
+}
+
+impl Codec {
+    fn mime(self) -> media_format_mimetype_e {
+        match self {
+            Codec::Aac => media_format_mimetype_e_MEDIA_FORMAT_AAC,
+            Codec::Mp3 => media_format_mimetype_e_MEDIA_FORMAT_MP3,
+            Codec::AmrNb => media_format_mimetype_e_MEDIA_FORMAT_AMR_NB,
+            Codec::Vorbis => media_format_mimetype_e_MEDIA_FORMAT_VORBIS,
+            Codec::Opus => media_format_mimetype_e_MEDIA_FORMAT_OPUS,
+            Codec::H263 => media_format_mimetype_e_MEDIA_FORMAT_H263,
+            Codec::H264 => media_format_mimetype_e_MEDIA_FORMAT_H264_SP,
+            Codec::Mpeg4 => media_format_mimetype_e_MEDIA_FORMAT_MPEG4_SP,
+            Codec::Vp8 => media_format_mimetype_e_MEDIA_FORMAT_VP8,
+            Codec::Hevc => media_format_mimetype_e_MEDIA_FORMAT_HEVC,
+        }
+    }
+}
+
+impl From<Codec> for mediacodec_codec_type_e {
+    fn from(codec: Codec) -> mediacodec_codec_type_e {
+        match codec {
+            Codec::Aac => mediacodec_codec_type_e_MEDIACODEC_AAC,
+            Codec::Mp3 => mediacodec_codec_type_e_MEDIACODEC_MP3,
+            Codec::AmrNb => mediacodec_codec_type_e_MEDIACODEC_AMR_NB,
+            Codec::Vorbis => mediacodec_codec_type_e_MEDIACODEC_VORBIS,
+            Codec::Opus => mediacodec_codec_type_e_MEDIACODEC_OPUS,
+            Codec::H263 => mediacodec_codec_type_e_MEDIACODEC_H263,
+            Codec::H264 => mediacodec_codec_type_e_MEDIACODEC_H264,
+            Codec::Mpeg4 => mediacodec_codec_type_e_MEDIACODEC_MPEG4,
+            Codec::Vp8 => mediacodec_codec_type_e_MEDIACODEC_VP8,
+            Codec::Hevc => mediacodec_codec_type_e_MEDIACODEC_HEVC,
+        }
+    }
+}
+
+/// Whether to encode or decode, with the parameters of the raw side.
+///
+/// Raw audio is interleaved PCM of `bits` per sample, raw video is I420.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Config {
+    AudioDecoder {
+        sample_rate: i32,
+        channels: i32,
+        bits: i32,
+    },
+    AudioEncoder {
+        sample_rate: i32,
+        channels: i32,
+        bits: i32,
+        /// In bits per second.
+        bitrate: i32,
+    },
+    VideoDecoder {
+        width: i32,
+        height: i32,
+    },
+    VideoEncoder {
+        width: i32,
+        height: i32,
+        fps: i32,
+        /// In kbits per second.
+        bitrate: i32,
+    },
+}
+
+/// An output buffer, with its presentation timestamp in nanoseconds.
+///
+/// Decoded video frames have their planes concatenated, stride padding included.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Packet {
+    pub data: Vec<u8>,
+    pub pts: u64,
+}
+
+impl Packet {
+    unsafe fn from_raw(handle: media_packet_h) -> Result<Packet> {
+        let mut pts = 0;
+        check(media_packet_get_pts(handle, &mut pts))?;
+
+        let (mut is_video, mut is_raw) = (false, false);
+        media_packet_is_video(handle, &mut is_video);
+        media_packet_is_raw(handle, &mut is_raw);
+
+        let mut data = Vec::new();
+
+        if is_video && is_raw {
+            let mut planes = 0;
+            check(media_packet_get_number_of_video_planes(handle, &mut planes))?;
+
+            for plane in 0..planes as c_int {
+                let mut ptr = null_mut();
+                let (mut width, mut height) = (0, 0);
+                check(media_packet_get_video_plane_data_ptr(
+                    handle, plane, &mut ptr,
+                ))?;
+                check(media_packet_get_video_stride_width(
+                    handle, plane, &mut width,
+                ))?;
+                check(media_packet_get_video_stride_height(
+                    handle,
+                    plane,
+                    &mut height,
+                ))?;
+
+                let len = width as usize * height as usize;
+                data.extend_from_slice(slice::from_raw_parts(ptr as *const u8, len));
+            }
+        } else {
+            let mut ptr = null_mut();
+            let mut size = 0;
+            check(media_packet_get_buffer_data_ptr(handle, &mut ptr))?;
+            check(media_packet_get_buffer_size(handle, &mut size))?;
+
+            data.extend_from_slice(slice::from_raw_parts(ptr as *const u8, size as usize));
+        }
+
+        Ok(Packet { data, pts })
+    }
+}
+
+struct OutputState {
+    handle: mediacodec_h,
+    callback_fn: Box<dyn FnMut(Packet) + Send>,
+}
+
+type EosFn = Box<dyn FnMut() + Send>;
+type ErrorFn = Box<dyn FnMut(Error) + Send>;
+
+pub struct MediaCodec {
+    handle: mediacodec_h,
+    /// The format of the input packets.
+    format: media_format_h,
+    output_state: Option<Pin<Box<OutputState>>>,
+    eos_fn: Option<Pin<Box<EosFn>>>,
+    error_fn: Option<Pin<Box<ErrorFn>>>,
+}
+
+impl MediaCodec {
+    /// Creates a codec, in hardware if `hardware` and in software otherwise.
+    pub fn new(codec: Codec, config: Config, hardware: bool) -> Result<MediaCodec> {
+        let mut handle = null_mut();
+        check(unsafe { mediacodec_create(&mut handle) })?;
+
+        let mut format = null_mut();
+        if let Err(e) = check(unsafe { media_format_create(&mut format) }) {
+            unsafe { mediacodec_destroy(handle) };
+            return Err(e);
+        }
+
+        // from here the codec cleans up after itself when dropped
+        let media_codec = MediaCodec {
+            handle,
+            format,
+            output_state: None,
+            eos_fn: None,
+            error_fn: None,
+        };
+        media_codec.configure(codec, config, hardware)?;
+
+        Ok(media_codec)
+    }
+
+    fn configure(&self, codec: Codec, config: Config, hardware: bool) -> Result<()> {
+        let support = if hardware {
+            mediacodec_support_type_e_MEDIACODEC_SUPPORT_TYPE_HW
+        } else {
+            mediacodec_support_type_e_MEDIACODEC_SUPPORT_TYPE_SW
+        };
+
+        let (direction, input_mime) = match config {
+            Config::AudioDecoder { .. } | Config::VideoDecoder { .. } => {
+                (mediacodec_support_type_e_MEDIACODEC_DECODER, codec.mime())
+            }
+            Config::AudioEncoder { .. } => (
+                mediacodec_support_type_e_MEDIACODEC_ENCODER,
+                media_format_mimetype_e_MEDIA_FORMAT_PCM,
+            ),
+            Config::VideoEncoder { .. } => (
+                mediacodec_support_type_e_MEDIACODEC_ENCODER,
+                media_format_mimetype_e_MEDIA_FORMAT_I420,
+            ),
+        };
+
+        check(unsafe {
+            mediacodec_set_codec(self.handle, codec.into(), (direction | support) as c_int)
+        })?;
+
+        unsafe {
+            match config {
+                Config::AudioDecoder {
+                    sample_rate,
+                    channels,
+                    bits,
+                } => {
+                    check(mediacodec_set_adec_info(
+                        self.handle,
+                        sample_rate,
+                        channels,
+                        bits,
+                    ))?;
+                    self.set_audio_format(input_mime, sample_rate, channels, bits)?;
+                }
+                Config::AudioEncoder {
+                    sample_rate,
+                    channels,
+                    bits,
+                    bitrate,
+                } => {
+                    check(mediacodec_set_aenc_info(
+                        self.handle,
+                        sample_rate,
+                        channels,
+                        bits,
+                        bitrate,
+                    ))?;
+                    self.set_audio_format(input_mime, sample_rate, channels, bits)?;
+                }
+                Config::VideoDecoder { width, height } => {
+                    check(mediacodec_set_vdec_info(self.handle, width, height))?;
+                    self.set_video_format(input_mime, width, height)?;
+                }
+                Config::VideoEncoder {
+                    width,
+                    height,
+                    fps,
+                    bitrate,
+                } => {
+                    check(mediacodec_set_venc_info(
+                        self.handle,
+                        width,
+                        height,
+                        fps,
+                        bitrate,
+                    ))?;
+                    self.set_video_format(input_mime, width, height)?;
+                }
+            }
+
+            check(mediacodec_set_input_buffer_used_cb(
+                self.handle,
+                Some(input_used_handler),
+                null_mut(),
+            ))
+        }
+    }
+
+    unsafe fn set_audio_format(
+        &self,
+        mime: media_format_mimetype_e,
+        sample_rate: i32,
+        channels: i32,
+        bits: i32,
+    ) -> Result<()> {
+        check(media_format_set_audio_mime(self.format, mime))?;
+        check(media_format_set_audio_samplerate(self.format, sample_rate))?;
+        check(media_format_set_audio_channel(self.format, channels))?;
+        check(media_format_set_audio_bit(self.format, bits))
+    }
+
+    unsafe fn set_video_format(
+        &self,
+        mime: media_format_mimetype_e,
+        width: i32,
+        height: i32,
+    ) -> Result<()> {
+        check(media_format_set_video_mime(self.format, mime))?;
+        check(media_format_set_video_width(self.format, width))?;
+        check(media_format_set_video_height(self.format, height))
+    }
+
+    /// Starts the codec, after setting the callbacks.
+    pub fn prepare(&self) -> Result<()> {
+        check(unsafe { mediacodec_prepare(self.handle) })
+    }
+
+    pub fn unprepare(&self) -> Result<()> {
+        check(unsafe { mediacodec_unprepare(self.handle) })
+    }
+
+    /// Discards the queued input and output buffers.
+    pub fn flush(&self) -> Result<()> {
+        check(unsafe { mediacodec_flush_buffers(self.handle) })
+    }
+
+    /// Queues an input buffer with its presentation timestamp in nanoseconds.
+    ///
+    /// Fails with [`Error::InvalidParameter`] if `data` is larger than a packet of the input
+    /// format can hold.
+    pub fn process_input(&self, data: &[u8], pts: u64) -> Result<()> {
+        self.queue(data, pts, false)
+    }
+
+    /// Signals the end of the input, the end of stream callback is called once the remaining
+    /// output has been delivered.
+    pub fn end_of_stream(&self) -> Result<()> {
+        self.queue(&[], 0, true)
+    }
+
+    fn queue(&self, data: &[u8], pts: u64, eos: bool) -> Result<()> {
+        let mut packet = null_mut();
+        check(unsafe { media_packet_create_alloc(self.format, None, null_mut(), &mut packet) })?;
+
+        let ret = unsafe { fill_packet(packet, data, pts, eos) }
+            .and_then(|_| check(unsafe { mediacodec_process_input(self.handle, packet, 0) }));
+
+        if ret.is_err() {
+            unsafe { media_packet_destroy(packet) };
+        }
+
+        ret
+    }
+
+    /// Calls `callback_fn` on the codec thread with each output buffer, replacing the
+    /// previous callback.
+    pub fn set_output_cb<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(Packet),
+        F: Send,
+        F: 'static,
+    {
+        let state = Box::pin(OutputState {
+            handle: self.handle,
+            callback_fn: Box::new(callback_fn),
+        });
+        let data = &*state as *const OutputState as *mut c_void;
+
+        check(unsafe {
+            mediacodec_set_output_buffer_available_cb(self.handle, Some(output_handler), data)
+        })?;
+        self.output_state = Some(state);
+
+        Ok(())
+    }
+
+    pub fn unset_output_cb(&mut self) -> Result<()> {
+        check(unsafe { mediacodec_unset_output_buffer_available_cb(self.handle) })?;
+        self.output_state = None;
+
+        Ok(())
+    }
+
+    /// Calls `callback_fn` on the codec thread once all the output has been delivered after
+    /// [`MediaCodec::end_of_stream`], replacing the previous callback.
+    pub fn set_eos_cb<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(),
+        F: Send,
+        F: 'static,
+    {
+        let (callback_fn, data) = pin_callback::<dyn FnMut() + Send>(Box::new(callback_fn));
+
+        check(unsafe { mediacodec_set_eos_cb(self.handle, Some(eos_handler), data) })?;
+        self.eos_fn = Some(callback_fn);
+
+        Ok(())
+    }
+
+    pub fn unset_eos_cb(&mut self) -> Result<()> {
+        check(unsafe { mediacodec_unset_eos_cb(self.handle) })?;
+        self.eos_fn = None;
+
+        Ok(())
+    }
+
+    /// Calls `callback_fn` on the codec thread with processing errors, replacing the previous
+    /// callback.
+    pub fn set_error_cb<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(Error),
+        F: Send,
+        F: 'static,
+    {
+        let (callback_fn, data) = pin_callback::<dyn FnMut(Error) + Send>(Box::new(callback_fn));
+
+        check(unsafe { mediacodec_set_error_cb(self.handle, Some(error_handler), data) })?;
+        self.error_fn = Some(callback_fn);
+
+        Ok(())
+    }
+
+    pub fn unset_error_cb(&mut self) -> Result<()> {
+        check(unsafe { mediacodec_unset_error_cb(self.handle) })?;
+        self.error_fn = None;
+
+        Ok(())
+    }
+}
+
+impl Drop for MediaCodec {
+    fn drop(&mut self) {
+        unsafe {
+            mediacodec_unprepare(self.handle);
+
+            if self.output_state.is_some() {
+                mediacodec_unset_output_buffer_available_cb(self.handle);
+            }
+
+            if self.eos_fn.is_some() {
+                mediacodec_unset_eos_cb(self.handle);
+            }
+
+            if self.error_fn.is_some() {
+                mediacodec_unset_error_cb(self.handle);
+            }
+
+            mediacodec_unset_input_buffer_used_cb(self.handle);
+            mediacodec_destroy(self.handle);
+            media_format_unref(self.format);
+        }
+    }
+}
+
+unsafe fn fill_packet(packet: media_packet_h, data: &[u8], pts: u64, eos: bool) -> Result<()> {
+    let mut ptr = null_mut();
+    let mut capacity = 0;
+    check(media_packet_get_buffer_data_ptr(packet, &mut ptr))?;
+    check(media_packet_get_buffer_size(packet, &mut capacity))?;
+
+    if data.len() as u64 > capacity {
+        return Err(Error::InvalidParameter);
+    }
+    copy_nonoverlapping(data.as_ptr(), ptr as *mut u8, data.len());
+
+    check(media_packet_set_buffer_size(packet, data.len() as u64))?;
+    check(media_packet_set_pts(packet, pts))?;
+
+    if eos {
+        check(media_packet_set_flags(
+            packet,
+            media_buffer_flags_e_MEDIA_PACKET_END_OF_STREAM,
+        ))?;
+    }
+
+    Ok(())
+}
+
+extern "C" fn input_used_handler(packet: media_packet_h, _user_data: *mut c_void) {
+    unsafe {
+        media_packet_destroy(packet);
+    }
+}
+
+extern "C" fn output_handler(_packet: media_packet_h, user_data: *mut c_void) {
+    abort_on_panic(|| unsafe {
+        let state = &mut *(user_data as *mut OutputState);
+        let mut packet = null_mut();
+
+        if check(mediacodec_get_output(state.handle, &mut packet, 0)).is_ok() {
+            let output = Packet::from_raw(packet);
+            media_packet_destroy(packet);
+
+            if let Ok(output) = output {
+                (state.callback_fn)(output);
+            }
+        }
+    })
+}
+
+extern "C" fn eos_handler(user_data: *mut c_void) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut EosFn) };
+        closure();
+    })
+}
+
+extern "C" fn error_handler(error: c_int, user_data: *mut c_void) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut ErrorFn) };
+        closure(Error::from(error));
+    })
+}
@@ -0,0 +1,426 @@
+//! Decoding, encoding and transformation of images, hardware accelerated where supported.
+
+use crate::error::{check, Error, Result};
+use crate::rutin::path_cstring;
+use rutin_tizen_sys::{
+    image_util_colorspace_e, image_util_colorspace_e_IMAGE_UTIL_COLORSPACE_ARGB8888,
+    image_util_colorspace_e_IMAGE_UTIL_COLORSPACE_BGRA8888,
+    image_util_colorspace_e_IMAGE_UTIL_COLORSPACE_BGRX8888,
+    image_util_colorspace_e_IMAGE_UTIL_COLORSPACE_I420,
+    image_util_colorspace_e_IMAGE_UTIL_COLORSPACE_NV12,
+    image_util_colorspace_e_IMAGE_UTIL_COLORSPACE_NV16,
+    image_util_colorspace_e_IMAGE_UTIL_COLORSPACE_NV21,
+    image_util_colorspace_e_IMAGE_UTIL_COLORSPACE_NV61,
+    image_util_colorspace_e_IMAGE_UTIL_COLORSPACE_RGB565,
+    image_util_colorspace_e_IMAGE_UTIL_COLORSPACE_RGB888,
+    image_util_colorspace_e_IMAGE_UTIL_COLORSPACE_RGBA8888,
+    image_util_colorspace_e_IMAGE_UTIL_COLORSPACE_UYVY,
+    image_util_colorspace_e_IMAGE_UTIL_COLORSPACE_YUV422,
+    image_util_colorspace_e_IMAGE_UTIL_COLORSPACE_YUYV,
+    image_util_colorspace_e_IMAGE_UTIL_COLORSPACE_YV12, image_util_create_image,
+    image_util_decode_create, image_util_decode_destroy, image_util_decode_h,
+    image_util_decode_run2, image_util_decode_set_colorspace, image_util_decode_set_input_buffer,
+    image_util_decode_set_input_path, image_util_destroy_image, image_util_encode_create,
+    image_util_encode_destroy, image_util_encode_h, image_util_encode_run_to_buffer,
+    image_util_encode_run_to_file, image_util_encode_set_quality, image_util_get_image,
+    image_util_image_h, image_util_rotation_e, image_util_rotation_e_IMAGE_UTIL_ROTATION_180,
+    image_util_rotation_e_IMAGE_UTIL_ROTATION_270, image_util_rotation_e_IMAGE_UTIL_ROTATION_90,
+    image_util_rotation_e_IMAGE_UTIL_ROTATION_FLIP_HORZ,
+    image_util_rotation_e_IMAGE_UTIL_ROTATION_FLIP_VERT,
+    image_util_rotation_e_IMAGE_UTIL_ROTATION_NONE, image_util_transform_create,
+    image_util_transform_destroy, image_util_transform_h, image_util_transform_run2,
+    image_util_transform_set_colorspace, image_util_transform_set_crop_area,
+    image_util_transform_set_resolution, image_util_transform_set_rotation, image_util_type_e,
+    image_util_type_e_IMAGE_UTIL_BMP, image_util_type_e_IMAGE_UTIL_GIF,
+    image_util_type_e_IMAGE_UTIL_JPEG, image_util_type_e_IMAGE_UTIL_PNG,
+    image_util_type_e_IMAGE_UTIL_WEBP,
+};
+use std::convert::TryFrom;
+use std::os::raw::c_void;
+use std::path::Path;
+use std::ptr::null_mut;
+use std::slice;
+
+/// Encoded image formats.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ImageType {
+    Jpeg,
+    Png,
+    Gif,
+    Bmp,
+    Webp,
+}
+
+impl From<ImageType> for image_util_type_e {
+    fn from(image_type: ImageType) -> image_util_type_e {
+        match image_type {
+            ImageType::Jpeg => image_util_type_e_IMAGE_UTIL_JPEG,
+            ImageType::Png => image_util_type_e_IMAGE_UTIL_PNG,
+            ImageType::Gif => image_util_type_e_IMAGE_UTIL_GIF,
+            ImageType::Bmp => image_util_type_e_IMAGE_UTIL_BMP,
+            ImageType::Webp => image_util_type_e_IMAGE_UTIL_WEBP,
+        }
+    }
+}
+
+/// Raw pixel layouts.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Colorspace {
+    Yv12,
+    Yuv422,
+    I420,
+    Nv12,
+    Uyvy,
+    Yuyv,
+    Rgb565,
+    Rgb888,
+    Argb8888,
+    Bgra8888,
+    Rgba8888,
+    Bgrx8888,
+    Nv21,
+    Nv16,
+    Nv61,
+}
+
+impl From<Colorspace> for image_util_colorspace_e {
+    fn from(colorspace: Colorspace) -> image_util_colorspace_e {
+        match colorspace {
+            Colorspace::Yv12 => image_util_colorspace_e_IMAGE_UTIL_COLORSPACE_YV12,
+            Colorspace::Yuv422 => image_util_colorspace_e_IMAGE_UTIL_COLORSPACE_YUV422,
+            Colorspace::I420 => image_util_colorspace_e_IMAGE_UTIL_COLORSPACE_I420,
+            Colorspace::Nv12 => image_util_colorspace_e_IMAGE_UTIL_COLORSPACE_NV12,
+            Colorspace::Uyvy => image_util_colorspace_e_IMAGE_UTIL_COLORSPACE_UYVY,
+            Colorspace::Yuyv => image_util_colorspace_e_IMAGE_UTIL_COLORSPACE_YUYV,
+            Colorspace::Rgb565 => image_util_colorspace_e_IMAGE_UTIL_COLORSPACE_RGB565,
+            Colorspace::Rgb888 => image_util_colorspace_e_IMAGE_UTIL_COLORSPACE_RGB888,
+            Colorspace::Argb8888 => image_util_colorspace_e_IMAGE_UTIL_COLORSPACE_ARGB8888,
+            Colorspace::Bgra8888 => image_util_colorspace_e_IMAGE_UTIL_COLORSPACE_BGRA8888,
+            Colorspace::Rgba8888 => image_util_colorspace_e_IMAGE_UTIL_COLORSPACE_RGBA8888,
+            Colorspace::Bgrx8888 => image_util_colorspace_e_IMAGE_UTIL_COLORSPACE_BGRX8888,
+            Colorspace::Nv21 => image_util_colorspace_e_IMAGE_UTIL_COLORSPACE_NV21,
+            Colorspace::Nv16 => image_util_colorspace_e_IMAGE_UTIL_COLORSPACE_NV16,
+            Colorspace::Nv61 => image_util_colorspace_e_IMAGE_UTIL_COLORSPACE_NV61,
+        }
+    }
+}
+
+impl TryFrom<image_util_colorspace_e> for Colorspace {
+    type Error = Error;
+
+    fn try_from(colorspace: image_util_colorspace_e) -> Result<Colorspace> {
+        match colorspace {
+            image_util_colorspace_e_IMAGE_UTIL_COLORSPACE_YV12 => Ok(Colorspace::Yv12),
+            image_util_colorspace_e_IMAGE_UTIL_COLORSPACE_YUV422 => Ok(Colorspace::Yuv422),
+            image_util_colorspace_e_IMAGE_UTIL_COLORSPACE_I420 => Ok(Colorspace::I420),
+            image_util_colorspace_e_IMAGE_UTIL_COLORSPACE_NV12 => Ok(Colorspace::Nv12),
+            image_util_colorspace_e_IMAGE_UTIL_COLORSPACE_UYVY => Ok(Colorspace::Uyvy),
+            image_util_colorspace_e_IMAGE_UTIL_COLORSPACE_YUYV => Ok(Colorspace::Yuyv),
+            image_util_colorspace_e_IMAGE_UTIL_COLORSPACE_RGB565 => Ok(Colorspace::Rgb565),
+            image_util_colorspace_e_IMAGE_UTIL_COLORSPACE_RGB888 => Ok(Colorspace::Rgb888),
+            image_util_colorspace_e_IMAGE_UTIL_COLORSPACE_ARGB8888 => Ok(Colorspace::Argb8888),
+            image_util_colorspace_e_IMAGE_UTIL_COLORSPACE_BGRA8888 => Ok(Colorspace::Bgra8888),
+            image_util_colorspace_e_IMAGE_UTIL_COLORSPACE_RGBA8888 => Ok(Colorspace::Rgba8888),
+            image_util_colorspace_e_IMAGE_UTIL_COLORSPACE_BGRX8888 => Ok(Colorspace::Bgrx8888),
+            image_util_colorspace_e_IMAGE_UTIL_COLORSPACE_NV21 => Ok(Colorspace::Nv21),
+            image_util_colorspace_e_IMAGE_UTIL_COLORSPACE_NV16 => Ok(Colorspace::Nv16),
+            image_util_colorspace_e_IMAGE_UTIL_COLORSPACE_NV61 => Ok(Colorspace::Nv61),
+            _ => Err(Error::Unknown),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Rotation {
+    None,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+    FlipHorizontal,
+    FlipVertical,
+}
+
+impl From<Rotation> for image_util_rotation_e {
+    fn from(rotation: Rotation) -> image_util_rotation_e {
+        match rotation {
+            Rotation::None => image_util_rotation_e_IMAGE_UTIL_ROTATION_NONE,
+            Rotation::Rotate90 => image_util_rotation_e_IMAGE_UTIL_ROTATION_90,
+            Rotation::Rotate180 => image_util_rotation_e_IMAGE_UTIL_ROTATION_180,
+            Rotation::Rotate270 => image_util_rotation_e_IMAGE_UTIL_ROTATION_270,
+            Rotation::FlipHorizontal => image_util_rotation_e_IMAGE_UTIL_ROTATION_FLIP_HORZ,
+            Rotation::FlipVertical => image_util_rotation_e_IMAGE_UTIL_ROTATION_FLIP_VERT,
+        }
+    }
+}
+
+/// Decoded pixels.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Image {
+    pub width: u32,
+    pub height: u32,
+    pub colorspace: Colorspace,
+    pub data: Vec<u8>,
+}
+
+impl Image {
+    fn to_raw(&self) -> Result<RawImage> {
+        let mut handle = null_mut();
+
+        check(unsafe {
+            image_util_create_image(
+                self.width,
+                self.height,
+                self.colorspace.into(),
+                self.data.as_ptr(),
+                self.data.len(),
+                &mut handle,
+            )
+        })?;
+
+        Ok(RawImage { handle })
+    }
+
+    fn from_raw(image: &RawImage) -> Result<Image> {
+        let (mut width, mut height) = (0, 0);
+        let mut colorspace = image_util_colorspace_e_IMAGE_UTIL_COLORSPACE_RGBA8888;
+        let mut data = null_mut();
+        let mut size = 0;
+
+        check(unsafe {
+            image_util_get_image(
+                image.handle,
+                &mut width,
+                &mut height,
+                &mut colorspace,
+                &mut data,
+                &mut size,
+            )
+        })?;
+
+        let pixels = unsafe { slice::from_raw_parts(data, size) }.to_vec();
+        unsafe { libc::free(data as *mut c_void) };
+
+        Ok(Image {
+            width,
+            height,
+            colorspace: Colorspace::try_from(colorspace)?,
+            data: pixels,
+        })
+    }
+}
+
+struct RawImage {
+    handle: image_util_image_h,
+}
+
+impl RawImage {
+    fn null() -> RawImage {
+        RawImage { handle: null_mut() }
+    }
+}
+
+impl Drop for RawImage {
+    fn drop(&mut self) {
+        if !self.handle.is_null() {
+            unsafe {
+                image_util_destroy_image(self.handle);
+            }
+        }
+    }
+}
+
+struct Decoder {
+    handle: image_util_decode_h,
+}
+
+impl Decoder {
+    fn new(colorspace: Colorspace) -> Result<Decoder> {
+        let mut decoder = Decoder { handle: null_mut() };
+        check(unsafe { image_util_decode_create(&mut decoder.handle) })?;
+        check(unsafe { image_util_decode_set_colorspace(decoder.handle, colorspace.into()) })?;
+
+        Ok(decoder)
+    }
+
+    fn run(&self) -> Result<Image> {
+        let mut image = RawImage::null();
+        check(unsafe { image_util_decode_run2(self.handle, &mut image.handle) })?;
+
+        Image::from_raw(&image)
+    }
+}
+
+impl Drop for Decoder {
+    fn drop(&mut self) {
+        unsafe {
+            image_util_decode_destroy(self.handle);
+        }
+    }
+}
+
+/// Decodes a JPEG, PNG, GIF, BMP or WebP image into `colorspace`.
+pub fn decode(data: &[u8], colorspace: Colorspace) -> Result<Image> {
+    let decoder = Decoder::new(colorspace)?;
+    check(unsafe {
+        image_util_decode_set_input_buffer(decoder.handle, data.as_ptr(), data.len())
+    })?;
+
+    decoder.run()
+}
+
+pub fn decode_file(path: &Path, colorspace: Colorspace) -> Result<Image> {
+    let path = path_cstring(path)?;
+
+    let decoder = Decoder::new(colorspace)?;
+    check(unsafe { image_util_decode_set_input_path(decoder.handle, path.as_ptr()) })?;
+
+    decoder.run()
+}
+
+struct Encoder {
+    handle: image_util_encode_h,
+}
+
+impl Encoder {
+    fn new(image_type: ImageType, quality: i32) -> Result<Encoder> {
+        let mut encoder = Encoder { handle: null_mut() };
+        check(unsafe { image_util_encode_create(image_type.into(), &mut encoder.handle) })?;
+
+        if image_type == ImageType::Jpeg {
+            check(unsafe { image_util_encode_set_quality(encoder.handle, quality) })?;
+        }
+
+        Ok(encoder)
+    }
+}
+
+impl Drop for Encoder {
+    fn drop(&mut self) {
+        unsafe {
+            image_util_encode_destroy(self.handle);
+        }
+    }
+}
+
+/// Encodes `image`, `quality` being between 1 and 100 and only used for JPEG.
+pub fn encode(image: &Image, image_type: ImageType, quality: i32) -> Result<Vec<u8>> {
+    let encoder = Encoder::new(image_type, quality)?;
+    let image = image.to_raw()?;
+    let mut buffer = null_mut();
+    let mut size = 0;
+
+    check(unsafe {
+        image_util_encode_run_to_buffer(encoder.handle, image.handle, &mut buffer, &mut size)
+    })?;
+
+    let encoded = unsafe { slice::from_raw_parts(buffer, size) }.to_vec();
+    unsafe { libc::free(buffer as *mut c_void) };
+
+    Ok(encoded)
+}
+
+pub fn encode_file(image: &Image, image_type: ImageType, quality: i32, path: &Path) -> Result<()> {
+    let path = path_cstring(path)?;
+
+    let encoder = Encoder::new(image_type, quality)?;
+    let image = image.to_raw()?;
+
+    check(unsafe { image_util_encode_run_to_file(encoder.handle, image.handle, path.as_ptr()) })
+}
+
+/// A set of operations applied to images by [`Transform::run`].
+///
+/// Only one of resizing, rotating or cropping can be done at once, though any can be combined
+/// with a colorspace conversion.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Transform {
+    colorspace: Option<Colorspace>,
+    resolution: Option<(u32, u32)>,
+    rotation: Option<Rotation>,
+    crop_area: Option<(u32, u32, u32, u32)>,
+}
+
+impl Transform {
+    pub fn new() -> Transform {
+        Transform::default()
+    }
+
+    pub fn colorspace(mut self, colorspace: Colorspace) -> Transform {
+        self.colorspace = Some(colorspace);
+        self
+    }
+
+    pub fn resize(mut self, width: u32, height: u32) -> Transform {
+        self.resolution = Some((width, height));
+        self
+    }
+
+    pub fn rotate(mut self, rotation: Rotation) -> Transform {
+        self.rotation = Some(rotation);
+        self
+    }
+
+    /// Keeps the area from `(start_x, start_y)` to `(end_x, end_y)`, excluded.
+    pub fn crop(mut self, start_x: u32, start_y: u32, end_x: u32, end_y: u32) -> Transform {
+        self.crop_area = Some((start_x, start_y, end_x, end_y));
+        self
+    }
+
+    pub fn run(&self, image: &Image) -> Result<Image> {
+        let transform = RawTransform::new()?;
+
+        if let Some(colorspace) = self.colorspace {
+            check(unsafe {
+                image_util_transform_set_colorspace(transform.handle, colorspace.into())
+            })?;
+        }
+
+        if let Some((width, height)) = self.resolution {
+            check(unsafe { image_util_transform_set_resolution(transform.handle, width, height) })?;
+        }
+
+        if let Some(rotation) = self.rotation {
+            check(unsafe { image_util_transform_set_rotation(transform.handle, rotation.into()) })?;
+        }
+
+        if let Some((start_x, start_y, end_x, end_y)) = self.crop_area {
+            check(unsafe {
+                image_util_transform_set_crop_area(transform.handle, start_x, start_y, end_x, end_y)
+            })?;
+        }
+
+        let source = image.to_raw()?;
+        let mut result = RawImage::null();
+        check(unsafe {
+            image_util_transform_run2(transform.handle, source.handle, &mut result.handle)
+        })?;
+
+        Image::from_raw(&result)
+    }
+}
+
+struct RawTransform {
+    handle: image_util_transform_h,
+}
+
+impl RawTransform {
+    fn new() -> Result<RawTransform> {
+        let mut handle = null_mut();
+        check(unsafe { image_util_transform_create(&mut handle) })?;
+
+        Ok(RawTransform { handle })
+    }
+}
+
+impl Drop for RawTransform {
+    fn drop(&mut self) {
+        unsafe {
+            image_util_transform_destroy(self.handle);
+        }
+    }
+}
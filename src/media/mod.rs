@@ -0,0 +1,97 @@
+pub mod audio_io;
+pub mod camera;
+pub mod codec;
+pub mod image_util;
+pub mod player;
+pub mod radio;
+pub mod recorder;
+pub mod screen_mirroring;
+pub mod sound_manager;
+pub mod sound_pool;
+pub mod streamer;
+pub mod tone_player;
+pub mod vision;
+
+use crate::error::{check, Result};
+use rutin_tizen_sys::{
+    sound_manager_create_stream_information, sound_manager_destroy_stream_information,
+    sound_stream_info_h, sound_stream_type_e, sound_stream_type_e_SOUND_STREAM_TYPE_ALARM,
+    sound_stream_type_e_SOUND_STREAM_TYPE_EMERGENCY, sound_stream_type_e_SOUND_STREAM_TYPE_MEDIA,
+    sound_stream_type_e_SOUND_STREAM_TYPE_NOTIFICATION,
+    sound_stream_type_e_SOUND_STREAM_TYPE_RINGTONE_VOIP,
+    sound_stream_type_e_SOUND_STREAM_TYPE_SYSTEM,
+    sound_stream_type_e_SOUND_STREAM_TYPE_VOICE_INFORMATION,
+    sound_stream_type_e_SOUND_STREAM_TYPE_VOICE_RECOGNITION,
+    sound_stream_type_e_SOUND_STREAM_TYPE_VOIP,
+};
+use std::ptr::null_mut;
+
+/// What a sound is played or recorded for, deciding its volume and routing.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum StreamType {
+    Media,
+    System,
+    Alarm,
+    Notification,
+    Emergency,
+    VoiceInformation,
+    VoiceRecognition,
+    RingtoneVoip,
+    Voip,
+}
+
+impl From<StreamType> for sound_stream_type_e {
+    fn from(stream_type: StreamType) -> sound_stream_type_e {
+        match stream_type {
+            StreamType::Media => sound_stream_type_e_SOUND_STREAM_TYPE_MEDIA,
+            StreamType::System => sound_stream_type_e_SOUND_STREAM_TYPE_SYSTEM,
+            StreamType::Alarm => sound_stream_type_e_SOUND_STREAM_TYPE_ALARM,
+            StreamType::Notification => sound_stream_type_e_SOUND_STREAM_TYPE_NOTIFICATION,
+            StreamType::Emergency => sound_stream_type_e_SOUND_STREAM_TYPE_EMERGENCY,
+            StreamType::VoiceInformation => sound_stream_type_e_SOUND_STREAM_TYPE_VOICE_INFORMATION,
+            StreamType::VoiceRecognition => sound_stream_type_e_SOUND_STREAM_TYPE_VOICE_RECOGNITION,
+            StreamType::RingtoneVoip => sound_stream_type_e_SOUND_STREAM_TYPE_RINGTONE_VOIP,
+            StreamType::Voip => sound_stream_type_e_SOUND_STREAM_TYPE_VOIP,
+        }
+    }
+}
+
+/// Where video is shown.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DisplayType {
+    /// Directly on a window, through the hardware overlay.
+    Overlay,
+    /// On an Evas image object.
+    Evas,
+}
+
+/// Stream information without a focus callback.
+pub(crate) struct RawStreamInfo {
+    pub(crate) handle: sound_stream_info_h,
+}
+
+impl RawStreamInfo {
+    pub(crate) fn new(stream_type: StreamType) -> Result<RawStreamInfo> {
+        let mut handle = null_mut();
+        check(unsafe {
+            sound_manager_create_stream_information(
+                stream_type.into(),
+                None,
+                null_mut(),
+                &mut handle,
+            )
+        })?;
+
+        Ok(Self { handle })
+    }
+}
+
+impl Drop for RawStreamInfo {
+    fn drop(&mut self) {
+        unsafe {
+            sound_manager_destroy_stream_information(self.handle);
+        }
+    }
+}
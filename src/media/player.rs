@@ -0,0 +1,358 @@
+//! Playback of audio and video files or streams.
+//!
+//! Streaming requires the `http://tizen.org/privilege/internet` privilege.
+
+use super::{DisplayType, RawStreamInfo, StreamType};
+use crate::efl::evas::Object;
+use crate::error::{check, Error, Result};
+use crate::rutin::{abort_on_panic, cstring, pin_callback};
+use rutin_tizen_sys::{
+    player_audio_effect_equalizer_clear, player_audio_effect_equalizer_is_available,
+    player_audio_effect_get_equalizer_band_frequency,
+    player_audio_effect_get_equalizer_band_frequency_range,
+    player_audio_effect_get_equalizer_band_level, player_audio_effect_get_equalizer_bands_count,
+    player_audio_effect_get_equalizer_level_range, player_audio_effect_set_equalizer_all_bands,
+    player_audio_effect_set_equalizer_band_level, player_create, player_destroy,
+    player_display_mode_e, player_display_mode_e_PLAYER_DISPLAY_MODE_CROPPED_FULL,
+    player_display_mode_e_PLAYER_DISPLAY_MODE_FULL_SCREEN,
+    player_display_mode_e_PLAYER_DISPLAY_MODE_LETTER_BOX,
+    player_display_mode_e_PLAYER_DISPLAY_MODE_ORIGIN_OR_LETTER,
+    player_display_mode_e_PLAYER_DISPLAY_MODE_ORIGIN_SIZE, player_display_rotation_e,
+    player_display_rotation_e_PLAYER_DISPLAY_ROTATION_180,
+    player_display_rotation_e_PLAYER_DISPLAY_ROTATION_270,
+    player_display_rotation_e_PLAYER_DISPLAY_ROTATION_90,
+    player_display_rotation_e_PLAYER_DISPLAY_ROTATION_NONE,
+    player_display_type_e_PLAYER_DISPLAY_TYPE_EVAS,
+    player_display_type_e_PLAYER_DISPLAY_TYPE_OVERLAY, player_get_play_position, player_get_state,
+    player_h, player_is_display_visible, player_pause, player_prepare, player_set_completed_cb,
+    player_set_display, player_set_display_mode, player_set_display_rotation,
+    player_set_display_visible, player_set_looping, player_set_sound_stream_info, player_set_uri,
+    player_start, player_state_e, player_state_e_PLAYER_STATE_IDLE,
+    player_state_e_PLAYER_STATE_NONE, player_state_e_PLAYER_STATE_PAUSED,
+    player_state_e_PLAYER_STATE_PLAYING, player_state_e_PLAYER_STATE_READY, player_stop,
+    player_unprepare, player_unset_completed_cb,
+};
+use std::convert::TryFrom;
+use std::marker::PhantomData;
+use std::os::raw::{c_int, c_void};
+use std::pin::Pin;
+use std::ptr::null_mut;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PlayerState {
+    Idle,
+    Ready,
+    Playing,
+    Paused,
+}
+
+impl TryFrom<player_state_e> for PlayerState {
+    type Error = Error;
+
+    fn try_from(state: player_state_e) -> Result<PlayerState> {
+        match state {
+            player_state_e_PLAYER_STATE_IDLE => Ok(PlayerState::Idle),
+            player_state_e_PLAYER_STATE_READY => Ok(PlayerState::Ready),
+            player_state_e_PLAYER_STATE_PLAYING => Ok(PlayerState::Playing),
+            player_state_e_PLAYER_STATE_PAUSED => Ok(PlayerState::Paused),
+            _ => Err(Error::Unknown),
+        }
+    }
+}
+
+/// How the video is fitted in the display.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DisplayMode {
+    /// Scaled to fit, keeping the aspect ratio.
+    LetterBox,
+    /// Unscaled.
+    OriginSize,
+    /// Stretched to fill.
+    FullScreen,
+    /// Scaled to fill keeping the aspect ratio, cutting the edges.
+    CroppedFull,
+    /// Unscaled if it fits, letter boxed otherwise.
+    OriginOrLetter,
+}
+
+impl From<DisplayMode> for player_display_mode_e {
+    fn from(mode: DisplayMode) -> player_display_mode_e {
+        match mode {
+            DisplayMode::LetterBox => player_display_mode_e_PLAYER_DISPLAY_MODE_LETTER_BOX,
+            DisplayMode::OriginSize => player_display_mode_e_PLAYER_DISPLAY_MODE_ORIGIN_SIZE,
+            DisplayMode::FullScreen => player_display_mode_e_PLAYER_DISPLAY_MODE_FULL_SCREEN,
+            DisplayMode::CroppedFull => player_display_mode_e_PLAYER_DISPLAY_MODE_CROPPED_FULL,
+            DisplayMode::OriginOrLetter => {
+                player_display_mode_e_PLAYER_DISPLAY_MODE_ORIGIN_OR_LETTER
+            }
+        }
+    }
+}
+
+/// Clockwise rotation of the video.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DisplayRotation {
+    None,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+}
+
+impl From<DisplayRotation> for player_display_rotation_e {
+    fn from(rotation: DisplayRotation) -> player_display_rotation_e {
+        match rotation {
+            DisplayRotation::None => player_display_rotation_e_PLAYER_DISPLAY_ROTATION_NONE,
+            DisplayRotation::Rotate90 => player_display_rotation_e_PLAYER_DISPLAY_ROTATION_90,
+            DisplayRotation::Rotate180 => player_display_rotation_e_PLAYER_DISPLAY_ROTATION_180,
+            DisplayRotation::Rotate270 => player_display_rotation_e_PLAYER_DISPLAY_ROTATION_270,
+        }
+    }
+}
+
+/// A band of the equalizer, frequencies being in Hz.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EqualizerBand {
+    /// The center frequency.
+    pub frequency: i32,
+    /// The width of the band around its center.
+    pub range: i32,
+}
+
+type CompletedFn = Box<dyn FnMut()>;
+
+/// A player, borrowing the object it displays the video on.
+pub struct Player<'d> {
+    handle: player_h,
+    completed_fn: Option<Pin<Box<CompletedFn>>>,
+    _display: PhantomData<&'d mut ()>,
+}
+
+impl<'d> Player<'d> {
+    pub fn new() -> Result<Player<'d>> {
+        let mut handle = null_mut();
+        check(unsafe { player_create(&mut handle) })?;
+
+        Ok(Self {
+            handle,
+            completed_fn: None,
+            _display: PhantomData,
+        })
+    }
+
+    pub fn get_state(&self) -> Result<PlayerState> {
+        let mut state = player_state_e_PLAYER_STATE_NONE;
+        check(unsafe { player_get_state(self.handle, &mut state) })?;
+
+        PlayerState::try_from(state)
+    }
+
+    /// A local path or a streaming URL, set while idle.
+    pub fn set_uri(&self, uri: &str) -> Result<()> {
+        let uri = cstring(uri)?;
+
+        check(unsafe { player_set_uri(self.handle, uri.as_ptr()) })
+    }
+
+    pub fn set_stream_type(&self, stream_type: StreamType) -> Result<()> {
+        let stream_info = RawStreamInfo::new(stream_type)?;
+
+        check(unsafe { player_set_sound_stream_info(self.handle, stream_info.handle) })
+    }
+
+    pub fn set_looping(&self, looping: bool) -> Result<()> {
+        check(unsafe { player_set_looping(self.handle, looping) })
+    }
+
+    /// Shows the video on `object`, a window for [`DisplayType::Overlay`] or an image for
+    /// [`DisplayType::Evas`], which stays borrowed until the player is dropped. Set while idle.
+    pub fn set_display<'a, O: Object<'a>>(
+        &self,
+        display_type: DisplayType,
+        object: &'d mut O,
+    ) -> Result<()> {
+        let display_type = match display_type {
+            DisplayType::Overlay => player_display_type_e_PLAYER_DISPLAY_TYPE_OVERLAY,
+            DisplayType::Evas => player_display_type_e_PLAYER_DISPLAY_TYPE_EVAS,
+        };
+
+        check(unsafe {
+            player_set_display(self.handle, display_type, object.eo_ptr() as *mut c_void)
+        })
+    }
+
+    pub fn set_display_mode(&self, mode: DisplayMode) -> Result<()> {
+        check(unsafe { player_set_display_mode(self.handle, mode.into()) })
+    }
+
+    pub fn set_display_rotation(&self, rotation: DisplayRotation) -> Result<()> {
+        check(unsafe { player_set_display_rotation(self.handle, rotation.into()) })
+    }
+
+    pub fn is_display_visible(&self) -> Result<bool> {
+        let mut visible = false;
+        check(unsafe { player_is_display_visible(self.handle, &mut visible) })?;
+
+        Ok(visible)
+    }
+
+    /// Hides or shows the video, the audio keeps playing.
+    pub fn set_display_visible(&self, visible: bool) -> Result<()> {
+        check(unsafe { player_set_display_visible(self.handle, visible) })
+    }
+
+    /// Opens the media, going from idle to ready.
+    pub fn prepare(&self) -> Result<()> {
+        check(unsafe { player_prepare(self.handle) })
+    }
+
+    pub fn unprepare(&self) -> Result<()> {
+        check(unsafe { player_unprepare(self.handle) })
+    }
+
+    /// Starts playing, or resumes if paused.
+    pub fn start(&self) -> Result<()> {
+        check(unsafe { player_start(self.handle) })
+    }
+
+    pub fn pause(&self) -> Result<()> {
+        check(unsafe { player_pause(self.handle) })
+    }
+
+    pub fn stop(&self) -> Result<()> {
+        check(unsafe { player_stop(self.handle) })
+    }
+
+    /// The current position, in milliseconds.
+    pub fn get_play_position(&self) -> Result<i32> {
+        let mut position = 0;
+        check(unsafe { player_get_play_position(self.handle, &mut position) })?;
+
+        Ok(position)
+    }
+
+    /// Calls `callback_fn` when the end of the media is reached, replacing the previous callback.
+    pub fn set_completed_cb<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(),
+        F: 'static,
+    {
+        let (callback_fn, data) = pin_callback::<dyn FnMut()>(Box::new(callback_fn));
+
+        check(unsafe { player_set_completed_cb(self.handle, Some(completed_handler), data) })?;
+        self.completed_fn = Some(callback_fn);
+
+        Ok(())
+    }
+
+    pub fn unset_completed_cb(&mut self) -> Result<()> {
+        check(unsafe { player_unset_completed_cb(self.handle) })?;
+        self.completed_fn = None;
+
+        Ok(())
+    }
+
+    /// Whether the equalizer can be used with the current audio output.
+    pub fn is_equalizer_available(&self) -> Result<bool> {
+        let mut available = false;
+        check(unsafe { player_audio_effect_equalizer_is_available(self.handle, &mut available) })?;
+
+        Ok(available)
+    }
+
+    pub fn equalizer_bands(&self) -> Result<Vec<EqualizerBand>> {
+        let mut count = 0;
+        check(unsafe { player_audio_effect_get_equalizer_bands_count(self.handle, &mut count) })?;
+
+        (0..count)
+            .map(|index| {
+                let mut band = EqualizerBand {
+                    frequency: 0,
+                    range: 0,
+                };
+
+                check(unsafe {
+                    player_audio_effect_get_equalizer_band_frequency(
+                        self.handle,
+                        index,
+                        &mut band.frequency,
+                    )
+                })?;
+                check(unsafe {
+                    player_audio_effect_get_equalizer_band_frequency_range(
+                        self.handle,
+                        index,
+                        &mut band.range,
+                    )
+                })?;
+
+                Ok(band)
+            })
+            .collect()
+    }
+
+    /// The minimum and maximum level of the bands, in dB.
+    pub fn equalizer_level_range(&self) -> Result<(i32, i32)> {
+        let mut min = 0;
+        let mut max = 0;
+        check(unsafe {
+            player_audio_effect_get_equalizer_level_range(self.handle, &mut min, &mut max)
+        })?;
+
+        Ok((min, max))
+    }
+
+    pub fn get_equalizer_band_level(&self, index: i32) -> Result<i32> {
+        let mut level = 0;
+        check(unsafe {
+            player_audio_effect_get_equalizer_band_level(self.handle, index, &mut level)
+        })?;
+
+        Ok(level)
+    }
+
+    pub fn set_equalizer_band_level(&self, index: i32, level: i32) -> Result<()> {
+        check(unsafe { player_audio_effect_set_equalizer_band_level(self.handle, index, level) })
+    }
+
+    /// Sets the level of every band at once, such as from a preset.
+    pub fn set_equalizer_levels(&self, levels: &[i32]) -> Result<()> {
+        let mut levels = levels.to_vec();
+
+        check(unsafe {
+            player_audio_effect_set_equalizer_all_bands(
+                self.handle,
+                levels.as_mut_ptr(),
+                levels.len() as c_int,
+            )
+        })
+    }
+
+    /// Resets every band to 0 dB.
+    pub fn clear_equalizer(&self) -> Result<()> {
+        check(unsafe { player_audio_effect_equalizer_clear(self.handle) })
+    }
+}
+
+impl Drop for Player<'_> {
+    fn drop(&mut self) {
+        unsafe {
+            if self.completed_fn.is_some() {
+                player_unset_completed_cb(self.handle);
+            }
+
+            // also stops and unprepares
+            player_destroy(self.handle);
+        }
+    }
+}
+
+extern "C" fn completed_handler(user_data: *mut c_void) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut CompletedFn) };
+        closure();
+    })
+}
@@ -0,0 +1,300 @@
+//! The FM radio tuner, on devices which have one.
+//!
+//! Frequencies are in kHz, between 87500 and 108000.
+
+use crate::error::{check, Result};
+use crate::rutin::{abort_on_panic, pin_callback};
+use rutin_tizen_sys::{
+    radio_create, radio_destroy, radio_get_frequency, radio_get_signal_strength, radio_get_state,
+    radio_h, radio_interrupted_code_e, radio_interrupted_code_e_RADIO_INTERRUPTED_BY_ALARM,
+    radio_interrupted_code_e_RADIO_INTERRUPTED_BY_CALL,
+    radio_interrupted_code_e_RADIO_INTERRUPTED_BY_EARJACK_UNPLUG,
+    radio_interrupted_code_e_RADIO_INTERRUPTED_BY_EMERGENCY,
+    radio_interrupted_code_e_RADIO_INTERRUPTED_BY_MEDIA,
+    radio_interrupted_code_e_RADIO_INTERRUPTED_BY_NOTIFICATION,
+    radio_interrupted_code_e_RADIO_INTERRUPTED_COMPLETED, radio_is_muted, radio_scan_start,
+    radio_scan_stop, radio_seek_completed_cb, radio_seek_down, radio_seek_up, radio_set_frequency,
+    radio_set_interrupted_cb, radio_set_mute, radio_set_scan_completed_cb, radio_start,
+    radio_state_e, radio_state_e_RADIO_STATE_PLAYING, radio_state_e_RADIO_STATE_READY,
+    radio_state_e_RADIO_STATE_SCANNING, radio_stop, radio_unset_interrupted_cb,
+    radio_unset_scan_completed_cb,
+};
+use std::os::raw::{c_int, c_void};
+use std::pin::Pin;
+use std::ptr::null_mut;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RadioState {
+    Ready,
+    Playing,
+    Scanning,
+}
+
+impl From<radio_state_e> for RadioState {
+    fn from(state: radio_state_e) -> RadioState {
+        match state {
+            radio_state_e_RADIO_STATE_PLAYING => RadioState::Playing,
+            radio_state_e_RADIO_STATE_SCANNING => RadioState::Scanning,
+            _ => RadioState::Ready,
+        }
+    }
+}
+
+/// Why the radio was stopped by the system.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Interruption {
+    /// The interruption is over.
+    Completed,
+    Media,
+    Call,
+    EarjackUnplug,
+    ResourceConflict,
+    Alarm,
+    Emergency,
+    Notification,
+}
+
+impl From<radio_interrupted_code_e> for Interruption {
+    fn from(code: radio_interrupted_code_e) -> Interruption {
+        match code {
+            radio_interrupted_code_e_RADIO_INTERRUPTED_COMPLETED => Interruption::Completed,
+            radio_interrupted_code_e_RADIO_INTERRUPTED_BY_MEDIA => Interruption::Media,
+            radio_interrupted_code_e_RADIO_INTERRUPTED_BY_CALL => Interruption::Call,
+            radio_interrupted_code_e_RADIO_INTERRUPTED_BY_EARJACK_UNPLUG => {
+                Interruption::EarjackUnplug
+            }
+            radio_interrupted_code_e_RADIO_INTERRUPTED_BY_ALARM => Interruption::Alarm,
+            radio_interrupted_code_e_RADIO_INTERRUPTED_BY_EMERGENCY => Interruption::Emergency,
+            radio_interrupted_code_e_RADIO_INTERRUPTED_BY_NOTIFICATION => {
+                Interruption::Notification
+            }
+            _ => Interruption::ResourceConflict,
+        }
+    }
+}
+
+struct ScanState {
+    updated_fn: Box<dyn FnMut(i32)>,
+    completed_fn: Option<Box<dyn FnOnce()>>,
+}
+
+type InterruptedFn = Box<dyn FnMut(Interruption)>;
+
+pub struct Radio {
+    handle: radio_h,
+    scan_state: Option<Pin<Box<ScanState>>>,
+    interrupted_fn: Option<Pin<Box<InterruptedFn>>>,
+}
+
+impl Radio {
+    pub fn new() -> Result<Radio> {
+        let mut handle = null_mut();
+        check(unsafe { radio_create(&mut handle) })?;
+
+        Ok(Self {
+            handle,
+            scan_state: None,
+            interrupted_fn: None,
+        })
+    }
+
+    pub fn get_state(&self) -> Result<RadioState> {
+        let mut state = radio_state_e_RADIO_STATE_READY;
+        check(unsafe { radio_get_state(self.handle, &mut state) })?;
+
+        Ok(state.into())
+    }
+
+    /// Starts playing the current frequency.
+    pub fn start(&self) -> Result<()> {
+        check(unsafe { radio_start(self.handle) })
+    }
+
+    pub fn stop(&self) -> Result<()> {
+        check(unsafe { radio_stop(self.handle) })
+    }
+
+    pub fn get_frequency(&self) -> Result<i32> {
+        let mut frequency = 0;
+        check(unsafe { radio_get_frequency(self.handle, &mut frequency) })?;
+
+        Ok(frequency)
+    }
+
+    pub fn set_frequency(&self, frequency: i32) -> Result<()> {
+        check(unsafe { radio_set_frequency(self.handle, frequency) })
+    }
+
+    /// The signal strength of the current frequency, in dBm.
+    pub fn get_signal_strength(&self) -> Result<i32> {
+        let mut strength = 0;
+        check(unsafe { radio_get_signal_strength(self.handle, &mut strength) })?;
+
+        Ok(strength)
+    }
+
+    pub fn is_muted(&self) -> Result<bool> {
+        let mut muted = false;
+        check(unsafe { radio_is_muted(self.handle, &mut muted) })?;
+
+        Ok(muted)
+    }
+
+    pub fn set_mute(&self, muted: bool) -> Result<()> {
+        check(unsafe { radio_set_mute(self.handle, muted) })
+    }
+
+    /// Tunes to the next station above the current frequency while playing,
+    /// `callback_fn` is called with its frequency.
+    pub fn seek_up<F>(&self, callback_fn: F) -> Result<()>
+    where
+        F: FnOnce(i32),
+        F: 'static,
+    {
+        self.seek(radio_seek_up, Box::new(callback_fn))
+    }
+
+    /// Tunes to the next station below the current frequency while playing,
+    /// `callback_fn` is called with its frequency.
+    pub fn seek_down<F>(&self, callback_fn: F) -> Result<()>
+    where
+        F: FnOnce(i32),
+        F: 'static,
+    {
+        self.seek(radio_seek_down, Box::new(callback_fn))
+    }
+
+    fn seek(
+        &self,
+        seek: unsafe extern "C" fn(radio_h, radio_seek_completed_cb, *mut c_void) -> c_int,
+        callback_fn: Box<dyn FnOnce(i32)>,
+    ) -> Result<()> {
+        let data = Box::into_raw(Box::new(callback_fn));
+        let ret = unsafe { seek(self.handle, Some(seek_handler), data as *mut c_void) };
+
+        if let Err(e) = check(ret) {
+            drop(unsafe { Box::from_raw(data) });
+            return Err(e);
+        }
+
+        Ok(())
+    }
+
+    /// Scans the whole band while stopped, calling `updated_fn` with the frequency of each
+    /// station found, then `completed_fn` once done.
+    pub fn start_scan<F, G>(&mut self, updated_fn: F, completed_fn: G) -> Result<()>
+    where
+        F: FnMut(i32),
+        F: 'static,
+        G: FnOnce(),
+        G: 'static,
+    {
+        let state = Box::pin(ScanState {
+            updated_fn: Box::new(updated_fn),
+            completed_fn: Some(Box::new(completed_fn)),
+        });
+        let data = &*state as *const ScanState as *mut c_void;
+
+        check(unsafe {
+            radio_set_scan_completed_cb(self.handle, Some(scan_completed_handler), data)
+        })?;
+
+        if let Err(e) = check(unsafe { radio_scan_start(self.handle, Some(scan_handler), data) }) {
+            unsafe { radio_unset_scan_completed_cb(self.handle) };
+            return Err(e);
+        }
+        self.scan_state = Some(state);
+
+        Ok(())
+    }
+
+    /// Stops scanning, without calling the completion callback.
+    pub fn stop_scan(&mut self) -> Result<()> {
+        check(unsafe { radio_scan_stop(self.handle, None, null_mut()) })?;
+        unsafe { radio_unset_scan_completed_cb(self.handle) };
+        self.scan_state = None;
+
+        Ok(())
+    }
+
+    /// Calls `callback_fn` when the system stops the radio, and when the interruption is over,
+    /// replacing the previous callback.
+    pub fn set_interrupted_cb<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(Interruption),
+        F: 'static,
+    {
+        let (callback_fn, data) = pin_callback::<dyn FnMut(Interruption)>(Box::new(callback_fn));
+
+        check(unsafe { radio_set_interrupted_cb(self.handle, Some(interrupted_handler), data) })?;
+        self.interrupted_fn = Some(callback_fn);
+
+        Ok(())
+    }
+
+    pub fn unset_interrupted_cb(&mut self) -> Result<()> {
+        check(unsafe { radio_unset_interrupted_cb(self.handle) })?;
+        self.interrupted_fn = None;
+
+        Ok(())
+    }
+}
+
+impl Drop for Radio {
+    fn drop(&mut self) {
+        unsafe {
+            match self.get_state() {
+                Ok(RadioState::Playing) => {
+                    radio_stop(self.handle);
+                }
+                Ok(RadioState::Scanning) => {
+                    radio_scan_stop(self.handle, None, null_mut());
+                }
+                _ => {}
+            }
+
+            if self.scan_state.is_some() {
+                radio_unset_scan_completed_cb(self.handle);
+            }
+
+            if self.interrupted_fn.is_some() {
+                radio_unset_interrupted_cb(self.handle);
+            }
+
+            radio_destroy(self.handle);
+        }
+    }
+}
+
+extern "C" fn seek_handler(frequency: c_int, user_data: *mut c_void) {
+    abort_on_panic(|| {
+        let callback_fn = unsafe { Box::from_raw(user_data as *mut Box<dyn FnOnce(i32)>) };
+        callback_fn(frequency);
+    })
+}
+
+extern "C" fn scan_handler(frequency: c_int, user_data: *mut c_void) {
+    abort_on_panic(|| {
+        let state = unsafe { &mut *(user_data as *mut ScanState) };
+        (state.updated_fn)(frequency);
+    })
+}
+
+extern "C" fn scan_completed_handler(user_data: *mut c_void) {
+    abort_on_panic(|| {
+        let state = unsafe { &mut *(user_data as *mut ScanState) };
+
+        if let Some(completed_fn) = state.completed_fn.take() {
+            completed_fn();
+        }
+    })
+}
+
+extern "C" fn interrupted_handler(code: radio_interrupted_code_e, user_data: *mut c_void) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut InterruptedFn) };
+        closure(code.into());
+    })
+}
@@ -0,0 +1,462 @@
+//! Audio and video recording to a file.
+//!
+//! Requires the `http://tizen.org/privilege/recorder` privilege, and the
+//! `http://tizen.org/privilege/camera` privilege for video.
+//!
+//! A recorder goes from [`RecorderState::Created`] to [`RecorderState::Ready`] once prepared,
+//! then to [`RecorderState::Recording`] and back once the recording is committed or canceled.
+//! Each method checks the current state, returning [`Error::InvalidOperation`] when called in
+//! the wrong one.
+
+use super::camera::Camera;
+use crate::error::{check, Error, Result};
+use crate::rutin::{abort_on_panic, path_cstring, pin_callback};
+use rutin_tizen_sys::{
+    recorder_attr_set_audio_channel, recorder_attr_set_audio_encoder_bitrate,
+    recorder_attr_set_audio_samplerate, recorder_attr_set_size_limit, recorder_attr_set_time_limit,
+    recorder_attr_set_video_encoder_bitrate, recorder_audio_codec_e,
+    recorder_audio_codec_e_RECORDER_AUDIO_CODEC_AAC,
+    recorder_audio_codec_e_RECORDER_AUDIO_CODEC_AMR,
+    recorder_audio_codec_e_RECORDER_AUDIO_CODEC_DISABLE,
+    recorder_audio_codec_e_RECORDER_AUDIO_CODEC_PCM,
+    recorder_audio_codec_e_RECORDER_AUDIO_CODEC_VORBIS, recorder_cancel, recorder_commit,
+    recorder_create_audiorecorder, recorder_create_videorecorder, recorder_destroy,
+    recorder_file_format_e, recorder_file_format_e_RECORDER_FILE_FORMAT_3GP,
+    recorder_file_format_e_RECORDER_FILE_FORMAT_ADTS,
+    recorder_file_format_e_RECORDER_FILE_FORMAT_AMR,
+    recorder_file_format_e_RECORDER_FILE_FORMAT_M2TS,
+    recorder_file_format_e_RECORDER_FILE_FORMAT_MP4,
+    recorder_file_format_e_RECORDER_FILE_FORMAT_OGG,
+    recorder_file_format_e_RECORDER_FILE_FORMAT_WAV, recorder_get_state, recorder_h,
+    recorder_pause, recorder_prepare, recorder_recording_limit_type_e,
+    recorder_recording_limit_type_e_RECORDER_RECORDING_LIMIT_SIZE,
+    recorder_recording_limit_type_e_RECORDER_RECORDING_LIMIT_TIME, recorder_set_audio_encoder,
+    recorder_set_error_cb, recorder_set_file_format, recorder_set_filename,
+    recorder_set_recording_limit_reached_cb, recorder_set_recording_status_cb,
+    recorder_set_state_changed_cb, recorder_set_video_encoder, recorder_start, recorder_state_e,
+    recorder_state_e_RECORDER_STATE_CREATED, recorder_state_e_RECORDER_STATE_NONE,
+    recorder_state_e_RECORDER_STATE_PAUSED, recorder_state_e_RECORDER_STATE_READY,
+    recorder_state_e_RECORDER_STATE_RECORDING, recorder_unprepare, recorder_unset_error_cb,
+    recorder_unset_recording_limit_reached_cb, recorder_unset_recording_status_cb,
+    recorder_unset_state_changed_cb, recorder_video_codec_e,
+    recorder_video_codec_e_RECORDER_VIDEO_CODEC_H263,
+    recorder_video_codec_e_RECORDER_VIDEO_CODEC_H264,
+    recorder_video_codec_e_RECORDER_VIDEO_CODEC_MPEG4,
+    recorder_video_codec_e_RECORDER_VIDEO_CODEC_THEORA,
+};
+use std::marker::PhantomData;
+use std::os::raw::{c_int, c_ulonglong, c_void};
+use std::path::Path;
+use std::pin::Pin;
+use std::ptr::null_mut;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RecorderState {
+    None,
+    Created,
+    Ready,
+    Recording,
+    Paused,
+}
+
+impl From<recorder_state_e> for RecorderState {
+    fn from(state: recorder_state_e) -> RecorderState {
+        match state {
+            recorder_state_e_RECORDER_STATE_CREATED => RecorderState::Created,
+            recorder_state_e_RECORDER_STATE_READY => RecorderState::Ready,
+            recorder_state_e_RECORDER_STATE_RECORDING => RecorderState::Recording,
+            recorder_state_e_RECORDER_STATE_PAUSED => RecorderState::Paused,
+            _ => RecorderState::None,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FileFormat {
+    ThreeGp,
+    Mp4,
+    Amr,
+    Adts,
+    Wav,
+    Ogg,
+    M2ts,
+}
+
+impl From<FileFormat> for recorder_file_format_e {
+    fn from(format: FileFormat) -> recorder_file_format_e {
+        match format {
+            FileFormat::ThreeGp => recorder_file_format_e_RECORDER_FILE_FORMAT_3GP,
+            FileFormat::Mp4 => recorder_file_format_e_RECORDER_FILE_FORMAT_MP4,
+            FileFormat::Amr => recorder_file_format_e_RECORDER_FILE_FORMAT_AMR,
+            FileFormat::Adts => recorder_file_format_e_RECORDER_FILE_FORMAT_ADTS,
+            FileFormat::Wav => recorder_file_format_e_RECORDER_FILE_FORMAT_WAV,
+            FileFormat::Ogg => recorder_file_format_e_RECORDER_FILE_FORMAT_OGG,
+            FileFormat::M2ts => recorder_file_format_e_RECORDER_FILE_FORMAT_M2TS,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AudioCodec {
+    /// Records video without a sound track.
+    Disabled,
+    Amr,
+    Aac,
+    Vorbis,
+    Pcm,
+}
+
+impl From<AudioCodec> for recorder_audio_codec_e {
+    fn from(codec: AudioCodec) -> recorder_audio_codec_e {
+        match codec {
+            AudioCodec::Disabled => recorder_audio_codec_e_RECORDER_AUDIO_CODEC_DISABLE,
+            AudioCodec::Amr => recorder_audio_codec_e_RECORDER_AUDIO_CODEC_AMR,
+            AudioCodec::Aac => recorder_audio_codec_e_RECORDER_AUDIO_CODEC_AAC,
+            AudioCodec::Vorbis => recorder_audio_codec_e_RECORDER_AUDIO_CODEC_VORBIS,
+            AudioCodec::Pcm => recorder_audio_codec_e_RECORDER_AUDIO_CODEC_PCM,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum VideoCodec {
+    H263,
+    H264,
+    Mpeg4,
+    Theora,
+}
+
+impl From<VideoCodec> for recorder_video_codec_e {
+    fn from(codec: VideoCodec) -> recorder_video_codec_e {
+        match codec {
+            VideoCodec::H263 => recorder_video_codec_e_RECORDER_VIDEO_CODEC_H263,
+            VideoCodec::H264 => recorder_video_codec_e_RECORDER_VIDEO_CODEC_H264,
+            VideoCodec::Mpeg4 => recorder_video_codec_e_RECORDER_VIDEO_CODEC_MPEG4,
+            VideoCodec::Theora => recorder_video_codec_e_RECORDER_VIDEO_CODEC_THEORA,
+        }
+    }
+}
+
+/// Why a recording was stopped, it must then be committed or canceled.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Limit {
+    Time,
+    Size,
+    FreeSpace,
+}
+
+impl From<recorder_recording_limit_type_e> for Limit {
+    fn from(limit: recorder_recording_limit_type_e) -> Limit {
+        match limit {
+            recorder_recording_limit_type_e_RECORDER_RECORDING_LIMIT_TIME => Limit::Time,
+            recorder_recording_limit_type_e_RECORDER_RECORDING_LIMIT_SIZE => Limit::Size,
+            _ => Limit::FreeSpace,
+        }
+    }
+}
+
+type StateFn = Box<dyn FnMut(RecorderState, RecorderState, bool)>;
+type LimitFn = Box<dyn FnMut(Limit)>;
+type StatusFn = Box<dyn FnMut(u64, u64)>;
+type ErrorFn = Box<dyn FnMut(Error, RecorderState)>;
+
+/// A recorder, borrowing its camera when recording video.
+pub struct Recorder<'c> {
+    handle: recorder_h,
+    state_fn: Option<Pin<Box<StateFn>>>,
+    limit_fn: Option<Pin<Box<LimitFn>>>,
+    status_fn: Option<Pin<Box<StatusFn>>>,
+    error_fn: Option<Pin<Box<ErrorFn>>>,
+    _camera: PhantomData<&'c Camera>,
+}
+
+impl Recorder<'static> {
+    pub fn audio() -> Result<Recorder<'static>> {
+        let mut handle = null_mut();
+        check(unsafe { recorder_create_audiorecorder(&mut handle) })?;
+
+        Ok(Recorder::from_handle(handle))
+    }
+}
+
+impl<'c> Recorder<'c> {
+    /// Records video from `camera`, which must not be previewing yet.
+    pub fn video(camera: &'c Camera) -> Result<Recorder<'c>> {
+        let mut handle = null_mut();
+        check(unsafe { recorder_create_videorecorder(camera.handle, &mut handle) })?;
+
+        Ok(Recorder::from_handle(handle))
+    }
+
+    fn from_handle(handle: recorder_h) -> Recorder<'c> {
+        Self {
+            handle,
+            state_fn: None,
+            limit_fn: None,
+            status_fn: None,
+            error_fn: None,
+            _camera: PhantomData,
+        }
+    }
+
+    pub fn get_state(&self) -> Result<RecorderState> {
+        let mut state = recorder_state_e_RECORDER_STATE_NONE;
+        check(unsafe { recorder_get_state(self.handle, &mut state) })?;
+
+        Ok(state.into())
+    }
+
+    fn expect_state(&self, states: &[RecorderState]) -> Result<()> {
+        if states.contains(&self.get_state()?) {
+            Ok(())
+        } else {
+            Err(Error::InvalidOperation)
+        }
+    }
+
+    /// Acquires the audio and video sources, once the recorder is configured.
+    pub fn prepare(&self) -> Result<()> {
+        self.expect_state(&[RecorderState::Created])?;
+        check(unsafe { recorder_prepare(self.handle) })
+    }
+
+    /// Releases the sources, allowing the recorder to be configured again.
+    pub fn unprepare(&self) -> Result<()> {
+        self.expect_state(&[RecorderState::Ready])?;
+        check(unsafe { recorder_unprepare(self.handle) })
+    }
+
+    /// Starts or resumes recording.
+    pub fn start(&self) -> Result<()> {
+        self.expect_state(&[RecorderState::Ready, RecorderState::Paused])?;
+        check(unsafe { recorder_start(self.handle) })
+    }
+
+    pub fn pause(&self) -> Result<()> {
+        self.expect_state(&[RecorderState::Recording])?;
+        check(unsafe { recorder_pause(self.handle) })
+    }
+
+    /// Stops recording and saves the file.
+    pub fn commit(&self) -> Result<()> {
+        self.expect_state(&[RecorderState::Recording, RecorderState::Paused])?;
+        check(unsafe { recorder_commit(self.handle) })
+    }
+
+    /// Stops recording and discards the file.
+    pub fn cancel(&self) -> Result<()> {
+        self.expect_state(&[RecorderState::Recording, RecorderState::Paused])?;
+        check(unsafe { recorder_cancel(self.handle) })
+    }
+
+    /// The file to record to, set before starting.
+    pub fn set_filename(&self, path: &Path) -> Result<()> {
+        let path = path_cstring(path)?;
+
+        self.expect_state(&[RecorderState::Created, RecorderState::Ready])?;
+        check(unsafe { recorder_set_filename(self.handle, path.as_ptr()) })
+    }
+
+    pub fn set_file_format(&self, format: FileFormat) -> Result<()> {
+        self.expect_state(&[RecorderState::Created, RecorderState::Ready])?;
+        check(unsafe { recorder_set_file_format(self.handle, format.into()) })
+    }
+
+    pub fn set_audio_encoder(&self, codec: AudioCodec) -> Result<()> {
+        self.expect_state(&[RecorderState::Created, RecorderState::Ready])?;
+        check(unsafe { recorder_set_audio_encoder(self.handle, codec.into()) })
+    }
+
+    pub fn set_video_encoder(&self, codec: VideoCodec) -> Result<()> {
+        self.expect_state(&[RecorderState::Created, RecorderState::Ready])?;
+        check(unsafe { recorder_set_video_encoder(self.handle, codec.into()) })
+    }
+
+    /// In bits per second.
+    pub fn set_audio_encoder_bitrate(&self, bitrate: i32) -> Result<()> {
+        self.expect_state(&[RecorderState::Created, RecorderState::Ready])?;
+        check(unsafe { recorder_attr_set_audio_encoder_bitrate(self.handle, bitrate) })
+    }
+
+    /// In bits per second.
+    pub fn set_video_encoder_bitrate(&self, bitrate: i32) -> Result<()> {
+        self.expect_state(&[RecorderState::Created, RecorderState::Ready])?;
+        check(unsafe { recorder_attr_set_video_encoder_bitrate(self.handle, bitrate) })
+    }
+
+    pub fn set_audio_samplerate(&self, sample_rate: i32) -> Result<()> {
+        self.expect_state(&[RecorderState::Created, RecorderState::Ready])?;
+        check(unsafe { recorder_attr_set_audio_samplerate(self.handle, sample_rate) })
+    }
+
+    /// 1 for mono, 2 for stereo.
+    pub fn set_audio_channel(&self, channels: i32) -> Result<()> {
+        self.expect_state(&[RecorderState::Created, RecorderState::Ready])?;
+        check(unsafe { recorder_attr_set_audio_channel(self.handle, channels) })
+    }
+
+    /// Stops recording after `seconds`, 0 for no limit.
+    pub fn set_time_limit(&self, seconds: i32) -> Result<()> {
+        check(unsafe { recorder_attr_set_time_limit(self.handle, seconds) })
+    }
+
+    /// Stops recording once the file reaches `kilobytes`, 0 for no limit.
+    pub fn set_size_limit(&self, kilobytes: i32) -> Result<()> {
+        check(unsafe { recorder_attr_set_size_limit(self.handle, kilobytes) })
+    }
+
+    /// Calls `callback_fn` with the previous and current states, and whether the change was
+    /// forced by the sound policy, replacing the previous callback.
+    pub fn set_state_changed_cb<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(RecorderState, RecorderState, bool),
+        F: 'static,
+    {
+        let (callback_fn, data) =
+            pin_callback::<dyn FnMut(RecorderState, RecorderState, bool)>(Box::new(callback_fn));
+
+        check(unsafe { recorder_set_state_changed_cb(self.handle, Some(state_handler), data) })?;
+        self.state_fn = Some(callback_fn);
+
+        Ok(())
+    }
+
+    pub fn unset_state_changed_cb(&mut self) -> Result<()> {
+        check(unsafe { recorder_unset_state_changed_cb(self.handle) })?;
+        self.state_fn = None;
+
+        Ok(())
+    }
+
+    /// Calls `callback_fn` when a limit stops the recording, replacing the previous callback.
+    pub fn set_recording_limit_reached_cb<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(Limit),
+        F: 'static,
+    {
+        let (callback_fn, data) = pin_callback::<dyn FnMut(Limit)>(Box::new(callback_fn));
+
+        check(unsafe {
+            recorder_set_recording_limit_reached_cb(self.handle, Some(limit_handler), data)
+        })?;
+        self.limit_fn = Some(callback_fn);
+
+        Ok(())
+    }
+
+    pub fn unset_recording_limit_reached_cb(&mut self) -> Result<()> {
+        check(unsafe { recorder_unset_recording_limit_reached_cb(self.handle) })?;
+        self.limit_fn = None;
+
+        Ok(())
+    }
+
+    /// Calls `callback_fn` periodically with the elapsed milliseconds and the file size in
+    /// kilobytes, replacing the previous callback.
+    pub fn set_recording_status_cb<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(u64, u64),
+        F: 'static,
+    {
+        let (callback_fn, data) = pin_callback::<dyn FnMut(u64, u64)>(Box::new(callback_fn));
+
+        check(unsafe {
+            recorder_set_recording_status_cb(self.handle, Some(status_handler), data)
+        })?;
+        self.status_fn = Some(callback_fn);
+
+        Ok(())
+    }
+
+    pub fn unset_recording_status_cb(&mut self) -> Result<()> {
+        check(unsafe { recorder_unset_recording_status_cb(self.handle) })?;
+        self.status_fn = None;
+
+        Ok(())
+    }
+
+    /// Calls `callback_fn` with asynchronous errors and the state they happened in,
+    /// replacing the previous callback.
+    pub fn set_error_cb<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(Error, RecorderState),
+        F: 'static,
+    {
+        let (callback_fn, data) =
+            pin_callback::<dyn FnMut(Error, RecorderState)>(Box::new(callback_fn));
+
+        check(unsafe { recorder_set_error_cb(self.handle, Some(error_handler), data) })?;
+        self.error_fn = Some(callback_fn);
+
+        Ok(())
+    }
+
+    pub fn unset_error_cb(&mut self) -> Result<()> {
+        check(unsafe { recorder_unset_error_cb(self.handle) })?;
+        self.error_fn = None;
+
+        Ok(())
+    }
+}
+
+impl<'c> Drop for Recorder<'c> {
+    fn drop(&mut self) {
+        unsafe {
+            match self.get_state() {
+                Ok(RecorderState::Recording) | Ok(RecorderState::Paused) => {
+                    recorder_cancel(self.handle);
+                    recorder_unprepare(self.handle);
+                }
+                Ok(RecorderState::Ready) => {
+                    recorder_unprepare(self.handle);
+                }
+                _ => {}
+            }
+
+            // also unsets the callbacks
+            recorder_destroy(self.handle);
+        }
+    }
+}
+
+extern "C" fn state_handler(
+    previous: recorder_state_e,
+    current: recorder_state_e,
+    by_policy: bool,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut StateFn) };
+        closure(previous.into(), current.into(), by_policy);
+    })
+}
+
+extern "C" fn limit_handler(limit: recorder_recording_limit_type_e, user_data: *mut c_void) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut LimitFn) };
+        closure(limit.into());
+    })
+}
+
+extern "C" fn status_handler(
+    elapsed_time: c_ulonglong,
+    file_size: c_ulonglong,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut StatusFn) };
+        closure(elapsed_time, file_size);
+    })
+}
+
+extern "C" fn error_handler(error: c_int, current_state: recorder_state_e, user_data: *mut c_void) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut ErrorFn) };
+        closure(Error::from(error), current_state.into());
+    })
+}
@@ -0,0 +1,217 @@
+//! Screen mirroring sink, showing the screen of a Miracast source.
+//!
+//! Requires the `http://tizen.org/privilege/internet` privilege.
+
+use super::DisplayType;
+use crate::efl::evas::Object;
+use crate::error::{check, Result};
+use crate::rutin::{abort_on_panic, cstring, pin_callback};
+use rutin_tizen_sys::{
+    scmirroring_display_type_e_SCMIRRORING_DISPLAY_TYPE_EVAS,
+    scmirroring_display_type_e_SCMIRRORING_DISPLAY_TYPE_OVERLAY, scmirroring_sink_connect,
+    scmirroring_sink_create, scmirroring_sink_destroy, scmirroring_sink_disconnect,
+    scmirroring_sink_get_current_state, scmirroring_sink_get_negotiated_video_resolution,
+    scmirroring_sink_h, scmirroring_sink_pause, scmirroring_sink_prepare, scmirroring_sink_resume,
+    scmirroring_sink_set_display, scmirroring_sink_set_ip_and_port,
+    scmirroring_sink_set_resolution, scmirroring_sink_set_state_changed_cb, scmirroring_sink_start,
+    scmirroring_sink_state_e, scmirroring_sink_state_e_SCMIRRORING_SINK_STATE_CONNECTED,
+    scmirroring_sink_state_e_SCMIRRORING_SINK_STATE_DISCONNECTED,
+    scmirroring_sink_state_e_SCMIRRORING_SINK_STATE_NONE,
+    scmirroring_sink_state_e_SCMIRRORING_SINK_STATE_NULL,
+    scmirroring_sink_state_e_SCMIRRORING_SINK_STATE_PAUSED,
+    scmirroring_sink_state_e_SCMIRRORING_SINK_STATE_PLAYING,
+    scmirroring_sink_state_e_SCMIRRORING_SINK_STATE_PREPARED, scmirroring_sink_unprepare,
+    scmirroring_sink_unset_state_changed_cb,
+};
+use std::os::raw::{c_int, c_void};
+use std::pin::Pin;
+use std::ptr::null_mut;
+
+/// Flags of the resolutions to offer to the source.
+pub mod resolution {
+    pub const R1920X1080_P30: i32 = 1 << 0;
+    pub const R1280X720_P30: i32 = 1 << 1;
+    pub const R960X540_P30: i32 = 1 << 2;
+    pub const R864X480_P30: i32 = 1 << 3;
+    pub const R720X480_P60: i32 = 1 << 4;
+    pub const R640X480_P60: i32 = 1 << 5;
+    pub const R640X360_P30: i32 = 1 << 6;
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SinkState {
+    None,
+    Null,
+    Prepared,
+    Connected,
+    Playing,
+    Paused,
+    Disconnected,
+}
+
+impl From<scmirroring_sink_state_e> for SinkState {
+    fn from(state: scmirroring_sink_state_e) -> SinkState {
+        match state {
+            scmirroring_sink_state_e_SCMIRRORING_SINK_STATE_NULL => SinkState::Null,
+            scmirroring_sink_state_e_SCMIRRORING_SINK_STATE_PREPARED => SinkState::Prepared,
+            scmirroring_sink_state_e_SCMIRRORING_SINK_STATE_CONNECTED => SinkState::Connected,
+            scmirroring_sink_state_e_SCMIRRORING_SINK_STATE_PLAYING => SinkState::Playing,
+            scmirroring_sink_state_e_SCMIRRORING_SINK_STATE_PAUSED => SinkState::Paused,
+            scmirroring_sink_state_e_SCMIRRORING_SINK_STATE_DISCONNECTED => SinkState::Disconnected,
+            _ => SinkState::None,
+        }
+    }
+}
+
+type StateFn = Box<dyn FnMut(Result<()>, SinkState)>;
+
+pub struct ScreenMirroringSink {
+    handle: scmirroring_sink_h,
+    state_fn: Option<Pin<Box<StateFn>>>,
+}
+
+impl ScreenMirroringSink {
+    pub fn new() -> Result<ScreenMirroringSink> {
+        let mut handle = null_mut();
+        check(unsafe { scmirroring_sink_create(&mut handle) })?;
+
+        Ok(Self {
+            handle,
+            state_fn: None,
+        })
+    }
+
+    /// Shows the mirrored screen on `object`, a window for [`DisplayType::Overlay`] or an image
+    /// for [`DisplayType::Evas`], which must outlive the sink.
+    pub fn set_display<'a, O: Object<'a>>(
+        &self,
+        display_type: DisplayType,
+        object: &mut O,
+    ) -> Result<()> {
+        let display_type = match display_type {
+            DisplayType::Overlay => scmirroring_display_type_e_SCMIRRORING_DISPLAY_TYPE_OVERLAY,
+            DisplayType::Evas => scmirroring_display_type_e_SCMIRRORING_DISPLAY_TYPE_EVAS,
+        };
+
+        check(unsafe {
+            scmirroring_sink_set_display(self.handle, display_type, object.eo_ptr() as *mut c_void)
+        })
+    }
+
+    /// The resolutions offered to the source, a combination of [`resolution`] flags.
+    pub fn set_resolution(&self, resolutions: i32) -> Result<()> {
+        check(unsafe { scmirroring_sink_set_resolution(self.handle, resolutions) })
+    }
+
+    /// The address of the source, usually learned through Wi-Fi Direct.
+    pub fn set_ip_and_port(&self, ip: &str, port: u16) -> Result<()> {
+        let ip = cstring(ip)?;
+        let port = cstring(&port.to_string())?;
+
+        check(unsafe { scmirroring_sink_set_ip_and_port(self.handle, ip.as_ptr(), port.as_ptr()) })
+    }
+
+    pub fn get_state(&self) -> Result<SinkState> {
+        let mut state = scmirroring_sink_state_e_SCMIRRORING_SINK_STATE_NONE;
+        check(unsafe { scmirroring_sink_get_current_state(self.handle, &mut state) })?;
+
+        Ok(state.into())
+    }
+
+    /// The resolution agreed on with the source, once connected.
+    pub fn get_negotiated_resolution(&self) -> Result<(i32, i32)> {
+        let (mut width, mut height) = (0, 0);
+        check(unsafe {
+            scmirroring_sink_get_negotiated_video_resolution(self.handle, &mut width, &mut height)
+        })?;
+
+        Ok((width, height))
+    }
+
+    pub fn prepare(&self) -> Result<()> {
+        check(unsafe { scmirroring_sink_prepare(self.handle) })
+    }
+
+    /// Connects to the source asynchronously, the state changes to
+    /// [`SinkState::Connected`] on success.
+    pub fn connect(&self) -> Result<()> {
+        check(unsafe { scmirroring_sink_connect(self.handle) })
+    }
+
+    /// Starts showing the source once connected.
+    pub fn start(&self) -> Result<()> {
+        check(unsafe { scmirroring_sink_start(self.handle) })
+    }
+
+    pub fn pause(&self) -> Result<()> {
+        check(unsafe { scmirroring_sink_pause(self.handle) })
+    }
+
+    pub fn resume(&self) -> Result<()> {
+        check(unsafe { scmirroring_sink_resume(self.handle) })
+    }
+
+    pub fn disconnect(&self) -> Result<()> {
+        check(unsafe { scmirroring_sink_disconnect(self.handle) })
+    }
+
+    pub fn unprepare(&self) -> Result<()> {
+        check(unsafe { scmirroring_sink_unprepare(self.handle) })
+    }
+
+    /// Calls `callback_fn` with the new state and any error which caused the change,
+    /// replacing the previous callback.
+    pub fn set_state_changed_cb<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(Result<()>, SinkState),
+        F: 'static,
+    {
+        let (callback_fn, data) =
+            pin_callback::<dyn FnMut(Result<()>, SinkState)>(Box::new(callback_fn));
+
+        check(unsafe {
+            scmirroring_sink_set_state_changed_cb(self.handle, Some(state_handler), data)
+        })?;
+        self.state_fn = Some(callback_fn);
+
+        Ok(())
+    }
+
+    pub fn unset_state_changed_cb(&mut self) -> Result<()> {
+        check(unsafe { scmirroring_sink_unset_state_changed_cb(self.handle) })?;
+        self.state_fn = None;
+
+        Ok(())
+    }
+}
+
+impl Drop for ScreenMirroringSink {
+    fn drop(&mut self) {
+        unsafe {
+            match self.get_state() {
+                Ok(SinkState::Connected) | Ok(SinkState::Playing) | Ok(SinkState::Paused) => {
+                    scmirroring_sink_disconnect(self.handle);
+                    scmirroring_sink_unprepare(self.handle);
+                }
+                Ok(SinkState::Prepared) | Ok(SinkState::Disconnected) => {
+                    scmirroring_sink_unprepare(self.handle);
+                }
+                _ => {}
+            }
+
+            if self.state_fn.is_some() {
+                scmirroring_sink_unset_state_changed_cb(self.handle);
+            }
+
+            scmirroring_sink_destroy(self.handle);
+        }
+    }
+}
+
+extern "C" fn state_handler(error: c_int, state: scmirroring_sink_state_e, user_data: *mut c_void) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut StateFn) };
+        closure(check(error), state.into());
+    })
+}
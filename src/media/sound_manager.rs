@@ -0,0 +1,595 @@
+//! System volumes, audio devices and stream focus.
+//!
+//! Audio apps acquire the focus before playing, and pause or duck when another stream takes it.
+//! Setting volumes requires the `http://tizen.org/privilege/volume.set` privilege.
+
+use super::StreamType;
+use crate::error::{check, Error, Result};
+use crate::rutin::{abort_on_panic, borrow_str, cstring, pin_callback};
+#[cfg(feature = "async")]
+use crate::stream::EventStream;
+use rutin_tizen_sys::{
+    sound_device_h, sound_device_io_direction_e,
+    sound_device_io_direction_e_SOUND_DEVICE_IO_DIRECTION_BOTH,
+    sound_device_io_direction_e_SOUND_DEVICE_IO_DIRECTION_IN,
+    sound_device_io_direction_e_SOUND_DEVICE_IO_DIRECTION_OUT,
+    sound_device_mask_e_SOUND_DEVICE_ALL_MASK, sound_device_type_e,
+    sound_device_type_e_SOUND_DEVICE_AUDIO_JACK, sound_device_type_e_SOUND_DEVICE_BLUETOOTH_MEDIA,
+    sound_device_type_e_SOUND_DEVICE_BLUETOOTH_VOICE, sound_device_type_e_SOUND_DEVICE_BUILTIN_MIC,
+    sound_device_type_e_SOUND_DEVICE_BUILTIN_RECEIVER,
+    sound_device_type_e_SOUND_DEVICE_BUILTIN_SPEAKER, sound_device_type_e_SOUND_DEVICE_FORWARDING,
+    sound_device_type_e_SOUND_DEVICE_HDMI, sound_device_type_e_SOUND_DEVICE_USB_AUDIO,
+    sound_manager_acquire_focus, sound_manager_add_device_connection_changed_cb,
+    sound_manager_add_volume_changed_cb, sound_manager_create_stream_information,
+    sound_manager_destroy_stream_information, sound_manager_free_device_list,
+    sound_manager_get_current_sound_type, sound_manager_get_device_id,
+    sound_manager_get_device_io_direction, sound_manager_get_device_list,
+    sound_manager_get_device_name, sound_manager_get_device_type, sound_manager_get_focus_state,
+    sound_manager_get_max_volume, sound_manager_get_next_device, sound_manager_get_volume,
+    sound_manager_release_focus, sound_manager_remove_device_connection_changed_cb,
+    sound_manager_remove_volume_changed_cb, sound_manager_set_volume,
+    sound_stream_focus_change_reason_e,
+    sound_stream_focus_change_reason_e_SOUND_STREAM_FOCUS_CHANGED_BY_ALARM,
+    sound_stream_focus_change_reason_e_SOUND_STREAM_FOCUS_CHANGED_BY_CALL,
+    sound_stream_focus_change_reason_e_SOUND_STREAM_FOCUS_CHANGED_BY_EMERGENCY,
+    sound_stream_focus_change_reason_e_SOUND_STREAM_FOCUS_CHANGED_BY_MEDIA,
+    sound_stream_focus_change_reason_e_SOUND_STREAM_FOCUS_CHANGED_BY_NOTIFICATION,
+    sound_stream_focus_change_reason_e_SOUND_STREAM_FOCUS_CHANGED_BY_RINGTONE,
+    sound_stream_focus_change_reason_e_SOUND_STREAM_FOCUS_CHANGED_BY_SYSTEM,
+    sound_stream_focus_change_reason_e_SOUND_STREAM_FOCUS_CHANGED_BY_VOICE_INFORMATION,
+    sound_stream_focus_change_reason_e_SOUND_STREAM_FOCUS_CHANGED_BY_VOICE_RECOGNITION,
+    sound_stream_focus_change_reason_e_SOUND_STREAM_FOCUS_CHANGED_BY_VOIP,
+    sound_stream_focus_mask_e, sound_stream_focus_mask_e_SOUND_STREAM_FOCUS_FOR_BOTH,
+    sound_stream_focus_mask_e_SOUND_STREAM_FOCUS_FOR_PLAYBACK,
+    sound_stream_focus_mask_e_SOUND_STREAM_FOCUS_FOR_RECORDING, sound_stream_focus_state_e,
+    sound_stream_focus_state_e_SOUND_STREAM_FOCUS_STATE_ACQUIRED,
+    sound_stream_focus_state_e_SOUND_STREAM_FOCUS_STATE_RELEASED, sound_stream_info_h,
+    sound_type_e, sound_type_e_SOUND_TYPE_ALARM, sound_type_e_SOUND_TYPE_CALL,
+    sound_type_e_SOUND_TYPE_MEDIA, sound_type_e_SOUND_TYPE_NOTIFICATION,
+    sound_type_e_SOUND_TYPE_RINGTONE, sound_type_e_SOUND_TYPE_SYSTEM,
+    sound_type_e_SOUND_TYPE_VOICE, sound_type_e_SOUND_TYPE_VOIP, SOUND_BEHAVIOR_NONE,
+};
+use std::convert::TryFrom;
+use std::os::raw::{c_char, c_int, c_uint, c_void};
+use std::pin::Pin;
+use std::ptr::{null, null_mut};
+
+/// The volume categories.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SoundType {
+    System,
+    Notification,
+    Alarm,
+    Ringtone,
+    Media,
+    Call,
+    Voip,
+    Voice,
+}
+
+impl From<SoundType> for sound_type_e {
+    fn from(sound_type: SoundType) -> sound_type_e {
+        match sound_type {
+            SoundType::System => sound_type_e_SOUND_TYPE_SYSTEM,
+            SoundType::Notification => sound_type_e_SOUND_TYPE_NOTIFICATION,
+            SoundType::Alarm => sound_type_e_SOUND_TYPE_ALARM,
+            SoundType::Ringtone => sound_type_e_SOUND_TYPE_RINGTONE,
+            SoundType::Media => sound_type_e_SOUND_TYPE_MEDIA,
+            SoundType::Call => sound_type_e_SOUND_TYPE_CALL,
+            SoundType::Voip => sound_type_e_SOUND_TYPE_VOIP,
+            SoundType::Voice => sound_type_e_SOUND_TYPE_VOICE,
+        }
+    }
+}
+
+impl TryFrom<sound_type_e> for SoundType {
+    type Error = Error;
+
+    fn try_from(sound_type: sound_type_e) -> Result<SoundType> {
+        match sound_type {
+            sound_type_e_SOUND_TYPE_SYSTEM => Ok(SoundType::System),
+            sound_type_e_SOUND_TYPE_NOTIFICATION => Ok(SoundType::Notification),
+            sound_type_e_SOUND_TYPE_ALARM => Ok(SoundType::Alarm),
+            sound_type_e_SOUND_TYPE_RINGTONE => Ok(SoundType::Ringtone),
+            sound_type_e_SOUND_TYPE_MEDIA => Ok(SoundType::Media),
+            sound_type_e_SOUND_TYPE_CALL => Ok(SoundType::Call),
+            sound_type_e_SOUND_TYPE_VOIP => Ok(SoundType::Voip),
+            sound_type_e_SOUND_TYPE_VOICE => Ok(SoundType::Voice),
+            _ => Err(Error::Unknown),
+        }
+    }
+}
+
+pub fn get_max_volume(sound_type: SoundType) -> Result<i32> {
+    let mut max = 0;
+    check(unsafe { sound_manager_get_max_volume(sound_type.into(), &mut max) })?;
+
+    Ok(max)
+}
+
+pub fn get_volume(sound_type: SoundType) -> Result<i32> {
+    let mut volume = 0;
+    check(unsafe { sound_manager_get_volume(sound_type.into(), &mut volume) })?;
+
+    Ok(volume)
+}
+
+/// `volume` is between 0 and [`get_max_volume`].
+pub fn set_volume(sound_type: SoundType, volume: i32) -> Result<()> {
+    check(unsafe { sound_manager_set_volume(sound_type.into(), volume) })
+}
+
+/// The type of the sound currently playing, whose volume the hardware keys change.
+pub fn get_current_sound_type() -> Result<SoundType> {
+    let mut sound_type = sound_type_e_SOUND_TYPE_MEDIA;
+    check(unsafe { sound_manager_get_current_sound_type(&mut sound_type) })?;
+
+    SoundType::try_from(sound_type)
+}
+
+type VolumeFn = Box<dyn FnMut(SoundType, u32)>;
+
+/// Calls a callback with the new volume when any is changed, until dropped.
+pub struct VolumeListener {
+    id: c_int,
+    _callback_fn: Pin<Box<VolumeFn>>,
+}
+
+impl VolumeListener {
+    pub fn new<F>(callback_fn: F) -> Result<VolumeListener>
+    where
+        F: FnMut(SoundType, u32),
+        F: 'static,
+    {
+        let (callback_fn, data) = pin_callback::<dyn FnMut(SoundType, u32)>(Box::new(callback_fn));
+        let mut id = 0;
+
+        check(unsafe { sound_manager_add_volume_changed_cb(Some(volume_handler), data, &mut id) })?;
+
+        Ok(Self {
+            id,
+            _callback_fn: callback_fn,
+        })
+    }
+}
+
+impl Drop for VolumeListener {
+    fn drop(&mut self) {
+        unsafe {
+            sound_manager_remove_volume_changed_cb(self.id);
+        }
+    }
+}
+
+/// The kinds of audio devices.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DeviceType {
+    BuiltinSpeaker,
+    /// The earpiece used for calls.
+    BuiltinReceiver,
+    BuiltinMic,
+    AudioJack,
+    BluetoothMedia,
+    BluetoothVoice,
+    Hdmi,
+    Forwarding,
+    UsbAudio,
+}
+
+impl TryFrom<sound_device_type_e> for DeviceType {
+    type Error = Error;
+
+    fn try_from(device_type: sound_device_type_e) -> Result<DeviceType> {
+        match device_type {
+            sound_device_type_e_SOUND_DEVICE_BUILTIN_SPEAKER => Ok(DeviceType::BuiltinSpeaker),
+            sound_device_type_e_SOUND_DEVICE_BUILTIN_RECEIVER => Ok(DeviceType::BuiltinReceiver),
+            sound_device_type_e_SOUND_DEVICE_BUILTIN_MIC => Ok(DeviceType::BuiltinMic),
+            sound_device_type_e_SOUND_DEVICE_AUDIO_JACK => Ok(DeviceType::AudioJack),
+            sound_device_type_e_SOUND_DEVICE_BLUETOOTH_MEDIA => Ok(DeviceType::BluetoothMedia),
+            sound_device_type_e_SOUND_DEVICE_BLUETOOTH_VOICE => Ok(DeviceType::BluetoothVoice),
+            sound_device_type_e_SOUND_DEVICE_HDMI => Ok(DeviceType::Hdmi),
+            sound_device_type_e_SOUND_DEVICE_FORWARDING => Ok(DeviceType::Forwarding),
+            sound_device_type_e_SOUND_DEVICE_USB_AUDIO => Ok(DeviceType::UsbAudio),
+            _ => Err(Error::Unknown),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum IoDirection {
+    In,
+    Out,
+    Both,
+}
+
+impl From<sound_device_io_direction_e> for IoDirection {
+    fn from(direction: sound_device_io_direction_e) -> IoDirection {
+        match direction {
+            sound_device_io_direction_e_SOUND_DEVICE_IO_DIRECTION_IN => IoDirection::In,
+            sound_device_io_direction_e_SOUND_DEVICE_IO_DIRECTION_OUT => IoDirection::Out,
+            _ => IoDirection::Both,
+        }
+    }
+}
+
+/// An audio input or output, built in or connected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Device {
+    pub id: i32,
+    pub name: String,
+    pub device_type: DeviceType,
+    pub direction: IoDirection,
+}
+
+impl Device {
+    unsafe fn from_raw(device: sound_device_h) -> Result<Device> {
+        let mut id = 0;
+        check(sound_manager_get_device_id(device, &mut id))?;
+
+        // points inside the device, it must not be freed
+        let mut name = null_mut();
+        check(sound_manager_get_device_name(device, &mut name))?;
+
+        let mut device_type = sound_device_type_e_SOUND_DEVICE_BUILTIN_SPEAKER;
+        check(sound_manager_get_device_type(device, &mut device_type))?;
+
+        let mut direction = sound_device_io_direction_e_SOUND_DEVICE_IO_DIRECTION_BOTH;
+        check(sound_manager_get_device_io_direction(
+            device,
+            &mut direction,
+        ))?;
+
+        Ok(Device {
+            id,
+            name: borrow_str(name).unwrap_or_default().into_owned(),
+            device_type: DeviceType::try_from(device_type)?,
+            direction: direction.into(),
+        })
+    }
+}
+
+/// The audio devices currently available.
+pub fn connected_devices() -> Result<Vec<Device>> {
+    let mut list = null_mut();
+
+    match check(unsafe {
+        sound_manager_get_device_list(
+            sound_device_mask_e_SOUND_DEVICE_ALL_MASK as c_int,
+            &mut list,
+        )
+    }) {
+        Err(Error::NoData) => return Ok(Vec::new()),
+        result => result?,
+    }
+
+    let mut devices = Vec::new();
+    let mut device = null_mut();
+    let mut result = Ok(());
+
+    while check(unsafe { sound_manager_get_next_device(list, &mut device) }).is_ok() {
+        match unsafe { Device::from_raw(device) } {
+            Ok(device) => devices.push(device),
+            Err(e) => {
+                result = Err(e);
+                break;
+            }
+        }
+    }
+
+    unsafe { sound_manager_free_device_list(list) };
+
+    result.map(|_| devices)
+}
+
+type DeviceFn = Box<dyn FnMut(Device, bool)>;
+
+/// Calls a callback with each device connected or disconnected, and whether it is now
+/// connected, until dropped.
+pub struct DeviceListener {
+    id: c_int,
+    _callback_fn: Pin<Box<DeviceFn>>,
+}
+
+impl DeviceListener {
+    pub fn new<F>(callback_fn: F) -> Result<DeviceListener>
+    where
+        F: FnMut(Device, bool),
+        F: 'static,
+    {
+        let (callback_fn, data) = pin_callback::<dyn FnMut(Device, bool)>(Box::new(callback_fn));
+        let mut id = 0;
+
+        check(unsafe {
+            sound_manager_add_device_connection_changed_cb(
+                sound_device_mask_e_SOUND_DEVICE_ALL_MASK as c_int,
+                Some(device_handler),
+                data,
+                &mut id,
+            )
+        })?;
+
+        Ok(Self {
+            id,
+            _callback_fn: callback_fn,
+        })
+    }
+}
+
+impl Drop for DeviceListener {
+    fn drop(&mut self) {
+        unsafe {
+            sound_manager_remove_device_connection_changed_cb(self.id);
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SoundEvent {
+    VolumeChanged(SoundType, u32),
+    DeviceConnected(Device),
+    DeviceDisconnected(Device),
+}
+
+/// Returns a stream of volume changes and device connections, such as a Bluetooth
+/// headset being attached or detached.
+#[cfg(feature = "async")]
+pub fn events() -> Result<EventStream<SoundEvent>> {
+    EventStream::new(|sender| {
+        let volume_sender = sender.clone();
+        let volume_listener = VolumeListener::new(move |sound_type, volume| {
+            volume_sender.send(SoundEvent::VolumeChanged(sound_type, volume))
+        })?;
+
+        let device_listener = DeviceListener::new(move |device, connected| {
+            sender.send(if connected {
+                SoundEvent::DeviceConnected(device)
+            } else {
+                SoundEvent::DeviceDisconnected(device)
+            })
+        })?;
+
+        Ok((volume_listener, device_listener))
+    })
+}
+
+/// Whether a focus is for playing, recording or both.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Focus {
+    Playback,
+    Recording,
+    Both,
+}
+
+impl From<Focus> for sound_stream_focus_mask_e {
+    fn from(focus: Focus) -> sound_stream_focus_mask_e {
+        match focus {
+            Focus::Playback => sound_stream_focus_mask_e_SOUND_STREAM_FOCUS_FOR_PLAYBACK,
+            Focus::Recording => sound_stream_focus_mask_e_SOUND_STREAM_FOCUS_FOR_RECORDING,
+            Focus::Both => sound_stream_focus_mask_e_SOUND_STREAM_FOCUS_FOR_BOTH,
+        }
+    }
+}
+
+impl From<sound_stream_focus_mask_e> for Focus {
+    fn from(mask: sound_stream_focus_mask_e) -> Focus {
+        match mask {
+            sound_stream_focus_mask_e_SOUND_STREAM_FOCUS_FOR_PLAYBACK => Focus::Playback,
+            sound_stream_focus_mask_e_SOUND_STREAM_FOCUS_FOR_RECORDING => Focus::Recording,
+            _ => Focus::Both,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FocusState {
+    Released,
+    Acquired,
+}
+
+impl From<sound_stream_focus_state_e> for FocusState {
+    fn from(state: sound_stream_focus_state_e) -> FocusState {
+        match state {
+            sound_stream_focus_state_e_SOUND_STREAM_FOCUS_STATE_ACQUIRED => FocusState::Acquired,
+            _ => FocusState::Released,
+        }
+    }
+}
+
+/// The kind of stream which took or gave back the focus.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FocusReason {
+    Media,
+    System,
+    Alarm,
+    Notification,
+    Emergency,
+    VoiceInformation,
+    VoiceRecognition,
+    Ringtone,
+    Voip,
+    Call,
+    Other,
+}
+
+impl From<sound_stream_focus_change_reason_e> for FocusReason {
+    fn from(reason: sound_stream_focus_change_reason_e) -> FocusReason {
+        match reason {
+            sound_stream_focus_change_reason_e_SOUND_STREAM_FOCUS_CHANGED_BY_MEDIA => {
+                FocusReason::Media
+            }
+            sound_stream_focus_change_reason_e_SOUND_STREAM_FOCUS_CHANGED_BY_SYSTEM => {
+                FocusReason::System
+            }
+            sound_stream_focus_change_reason_e_SOUND_STREAM_FOCUS_CHANGED_BY_ALARM => {
+                FocusReason::Alarm
+            }
+            sound_stream_focus_change_reason_e_SOUND_STREAM_FOCUS_CHANGED_BY_NOTIFICATION => {
+                FocusReason::Notification
+            }
+            sound_stream_focus_change_reason_e_SOUND_STREAM_FOCUS_CHANGED_BY_EMERGENCY => {
+                FocusReason::Emergency
+            }
+            sound_stream_focus_change_reason_e_SOUND_STREAM_FOCUS_CHANGED_BY_VOICE_INFORMATION => {
+                FocusReason::VoiceInformation
+            }
+            sound_stream_focus_change_reason_e_SOUND_STREAM_FOCUS_CHANGED_BY_VOICE_RECOGNITION => {
+                FocusReason::VoiceRecognition
+            }
+            sound_stream_focus_change_reason_e_SOUND_STREAM_FOCUS_CHANGED_BY_RINGTONE => {
+                FocusReason::Ringtone
+            }
+            sound_stream_focus_change_reason_e_SOUND_STREAM_FOCUS_CHANGED_BY_VOIP => {
+                FocusReason::Voip
+            }
+            sound_stream_focus_change_reason_e_SOUND_STREAM_FOCUS_CHANGED_BY_CALL => {
+                FocusReason::Call
+            }
+            _ => FocusReason::Other,
+        }
+    }
+}
+
+/// The focus of a [`StreamFocus`] was taken or given back by another stream.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FocusChange {
+    pub focus: Focus,
+    pub state: FocusState,
+    pub reason: FocusReason,
+    pub extra_info: Option<String>,
+}
+
+type FocusFn = Box<dyn FnMut(FocusChange)>;
+
+/// A stream of audio competing for the focus with the other applications.
+pub struct StreamFocus {
+    handle: sound_stream_info_h,
+    _callback_fn: Pin<Box<FocusFn>>,
+}
+
+impl StreamFocus {
+    /// `callback_fn` is called when another stream takes or gives back the focus.
+    pub fn new<F>(stream_type: StreamType, callback_fn: F) -> Result<StreamFocus>
+    where
+        F: FnMut(FocusChange),
+        F: 'static,
+    {
+        let (callback_fn, data) = pin_callback::<dyn FnMut(FocusChange)>(Box::new(callback_fn));
+        let mut handle = null_mut();
+
+        check(unsafe {
+            sound_manager_create_stream_information(
+                stream_type.into(),
+                Some(focus_handler),
+                data,
+                &mut handle,
+            )
+        })?;
+
+        Ok(Self {
+            handle,
+            _callback_fn: callback_fn,
+        })
+    }
+
+    /// Takes the focus from the other streams, with `extra_info` passed to them.
+    pub fn acquire(&self, focus: Focus, extra_info: Option<&str>) -> Result<()> {
+        self.change(sound_manager_acquire_focus, focus, extra_info)
+    }
+
+    pub fn release(&self, focus: Focus, extra_info: Option<&str>) -> Result<()> {
+        self.change(sound_manager_release_focus, focus, extra_info)
+    }
+
+    fn change(
+        &self,
+        change: unsafe extern "C" fn(
+            sound_stream_info_h,
+            sound_stream_focus_mask_e,
+            c_int,
+            *const c_char,
+        ) -> c_int,
+        focus: Focus,
+        extra_info: Option<&str>,
+    ) -> Result<()> {
+        let extra_info = extra_info.map(cstring).transpose()?;
+
+        check(unsafe {
+            change(
+                self.handle,
+                focus.into(),
+                SOUND_BEHAVIOR_NONE as c_int,
+                extra_info.as_ref().map_or(null(), |s| s.as_ptr()),
+            )
+        })
+    }
+
+    /// The state of the focus for playback and for recording.
+    pub fn get_focus_state(&self) -> Result<(FocusState, FocusState)> {
+        let mut playback = sound_stream_focus_state_e_SOUND_STREAM_FOCUS_STATE_RELEASED;
+        let mut recording = sound_stream_focus_state_e_SOUND_STREAM_FOCUS_STATE_RELEASED;
+
+        check(unsafe {
+            sound_manager_get_focus_state(self.handle, &mut playback, &mut recording)
+        })?;
+
+        Ok((playback.into(), recording.into()))
+    }
+}
+
+impl Drop for StreamFocus {
+    fn drop(&mut self) {
+        unsafe {
+            sound_manager_destroy_stream_information(self.handle);
+        }
+    }
+}
+
+extern "C" fn volume_handler(sound_type: sound_type_e, volume: c_uint, user_data: *mut c_void) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut VolumeFn) };
+
+        if let Ok(sound_type) = SoundType::try_from(sound_type) {
+            closure(sound_type, volume);
+        }
+    })
+}
+
+extern "C" fn device_handler(device: sound_device_h, is_connected: bool, user_data: *mut c_void) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut DeviceFn) };
+
+        if let Ok(device) = unsafe { Device::from_raw(device) } {
+            closure(device, is_connected);
+        }
+    })
+}
+
+extern "C" fn focus_handler(
+    _stream_info: sound_stream_info_h,
+    focus_mask: sound_stream_focus_mask_e,
+    focus_state: sound_stream_focus_state_e,
+    reason: sound_stream_focus_change_reason_e,
+    _sound_behavior: c_int,
+    extra_info: *const c_char,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut FocusFn) };
+
+        closure(FocusChange {
+            focus: focus_mask.into(),
+            state: focus_state.into(),
+            reason: reason.into(),
+            extra_info: unsafe { borrow_str(extra_info) }.map(|s| s.into_owned()),
+        });
+    })
+}
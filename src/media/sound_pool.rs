@@ -0,0 +1,216 @@
+//! Low latency playback of short preloaded clips, such as game sound effects.
+//!
+//! Clips are decoded once when loaded, then any number of them can play at the same time,
+//! each with its own volume. When too many play at once, the ones of lower priority are
+//! muted or suspended.
+
+use crate::error::{check, Error, Result};
+use crate::rutin::{cstring, path_cstring};
+use rutin_tizen_sys::{
+    sound_pool_activate, sound_pool_create, sound_pool_deactivate, sound_pool_destroy,
+    sound_pool_get_state, sound_pool_get_volume, sound_pool_h, sound_pool_load_source_from_file,
+    sound_pool_priority_policy_e, sound_pool_priority_policy_e_SOUND_POOL_PRIORITY_POLICY_MUTE,
+    sound_pool_priority_policy_e_SOUND_POOL_PRIORITY_POLICY_SUSPENDED, sound_pool_set_volume,
+    sound_pool_state_e_SOUND_POOL_STATE_ACTIVE, sound_pool_state_e_SOUND_POOL_STATE_INACTIVE,
+    sound_pool_stream_get_state, sound_pool_stream_get_volume, sound_pool_stream_pause,
+    sound_pool_stream_play, sound_pool_stream_resume, sound_pool_stream_set_priority,
+    sound_pool_stream_set_volume, sound_pool_stream_state_e,
+    sound_pool_stream_state_e_SOUND_POOL_STREAM_STATE_FINISHED,
+    sound_pool_stream_state_e_SOUND_POOL_STREAM_STATE_NONE,
+    sound_pool_stream_state_e_SOUND_POOL_STREAM_STATE_PAUSED,
+    sound_pool_stream_state_e_SOUND_POOL_STREAM_STATE_PLAYING,
+    sound_pool_stream_state_e_SOUND_POOL_STREAM_STATE_STOPPED,
+    sound_pool_stream_state_e_SOUND_POOL_STREAM_STATE_SUSPENDED, sound_pool_stream_stop,
+    sound_pool_unload_source,
+};
+use std::convert::TryFrom;
+use std::path::Path;
+use std::ptr::null_mut;
+
+/// What happens to a voice while voices of higher priority play.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PriorityPolicy {
+    /// Keeps playing silently.
+    Mute,
+    /// Pauses until the higher priority voices are done.
+    Suspend,
+}
+
+impl From<PriorityPolicy> for sound_pool_priority_policy_e {
+    fn from(policy: PriorityPolicy) -> sound_pool_priority_policy_e {
+        match policy {
+            PriorityPolicy::Mute => sound_pool_priority_policy_e_SOUND_POOL_PRIORITY_POLICY_MUTE,
+            PriorityPolicy::Suspend => {
+                sound_pool_priority_policy_e_SOUND_POOL_PRIORITY_POLICY_SUSPENDED
+            }
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum VoiceState {
+    Playing,
+    Paused,
+    /// Paused for a voice of higher priority.
+    Suspended,
+    Stopped,
+    Finished,
+}
+
+impl TryFrom<sound_pool_stream_state_e> for VoiceState {
+    type Error = Error;
+
+    fn try_from(state: sound_pool_stream_state_e) -> Result<VoiceState> {
+        match state {
+            sound_pool_stream_state_e_SOUND_POOL_STREAM_STATE_PLAYING => Ok(VoiceState::Playing),
+            sound_pool_stream_state_e_SOUND_POOL_STREAM_STATE_PAUSED => Ok(VoiceState::Paused),
+            sound_pool_stream_state_e_SOUND_POOL_STREAM_STATE_SUSPENDED => {
+                Ok(VoiceState::Suspended)
+            }
+            sound_pool_stream_state_e_SOUND_POOL_STREAM_STATE_STOPPED => Ok(VoiceState::Stopped),
+            sound_pool_stream_state_e_SOUND_POOL_STREAM_STATE_FINISHED => Ok(VoiceState::Finished),
+            _ => Err(Error::Unknown),
+        }
+    }
+}
+
+pub struct SoundPool {
+    handle: sound_pool_h,
+}
+
+impl SoundPool {
+    /// Creates an inactive pool, which must be activated before playing.
+    pub fn new() -> Result<SoundPool> {
+        let mut handle = null_mut();
+        check(unsafe { sound_pool_create(&mut handle) })?;
+
+        Ok(Self { handle })
+    }
+
+    /// Whether voices can play, they are suspended while the pool is inactive.
+    pub fn is_active(&self) -> Result<bool> {
+        let mut state = sound_pool_state_e_SOUND_POOL_STATE_INACTIVE;
+        check(unsafe { sound_pool_get_state(self.handle, &mut state) })?;
+
+        Ok(state == sound_pool_state_e_SOUND_POOL_STATE_ACTIVE)
+    }
+
+    pub fn activate(&self) -> Result<()> {
+        check(unsafe { sound_pool_activate(self.handle) })
+    }
+
+    /// Suspends every voice, such as when the game is paused.
+    pub fn deactivate(&self) -> Result<()> {
+        check(unsafe { sound_pool_deactivate(self.handle) })
+    }
+
+    /// The volume of the whole pool, between 0 and 1.
+    pub fn get_volume(&self) -> Result<f32> {
+        let mut volume = 0.0;
+        check(unsafe { sound_pool_get_volume(self.handle, &mut volume) })?;
+
+        Ok(volume)
+    }
+
+    pub fn set_volume(&self, volume: f32) -> Result<()> {
+        check(unsafe { sound_pool_set_volume(self.handle, volume) })
+    }
+
+    /// Decodes a WAV, OGG or other audio file, to be played as `tag`.
+    pub fn load(&self, tag: &str, path: &Path) -> Result<()> {
+        let tag = cstring(tag)?;
+        let path = path_cstring(path)?;
+
+        check(unsafe { sound_pool_load_source_from_file(self.handle, path.as_ptr(), tag.as_ptr()) })
+    }
+
+    /// Frees a clip, stopping its voices.
+    pub fn unload(&self, tag: &str) -> Result<()> {
+        let tag = cstring(tag)?;
+
+        check(unsafe { sound_pool_unload_source(self.handle, tag.as_ptr()) })
+    }
+
+    /// Starts a new voice playing the clip `tag` `times` times, or forever if 0,
+    /// at `volume` between 0 and 1.
+    pub fn play(
+        &self,
+        tag: &str,
+        times: u32,
+        volume: f32,
+        priority: u32,
+        policy: PriorityPolicy,
+    ) -> Result<Voice<'_>> {
+        let tag = cstring(tag)?;
+        let mut id = 0;
+
+        check(unsafe {
+            sound_pool_stream_play(
+                self.handle,
+                tag.as_ptr(),
+                times,
+                volume,
+                priority,
+                policy.into(),
+                None,
+                null_mut(),
+                &mut id,
+            )
+        })?;
+
+        Ok(Voice { pool: self, id })
+    }
+}
+
+impl Drop for SoundPool {
+    fn drop(&mut self) {
+        unsafe {
+            // also stops the voices and unloads the clips
+            sound_pool_destroy(self.handle);
+        }
+    }
+}
+
+/// A clip being played, which keeps playing when dropped.
+pub struct Voice<'p> {
+    pool: &'p SoundPool,
+    id: u32,
+}
+
+impl Voice<'_> {
+    pub fn get_state(&self) -> Result<VoiceState> {
+        let mut state = sound_pool_stream_state_e_SOUND_POOL_STREAM_STATE_NONE;
+        check(unsafe { sound_pool_stream_get_state(self.pool.handle, self.id, &mut state) })?;
+
+        VoiceState::try_from(state)
+    }
+
+    pub fn pause(&self) -> Result<()> {
+        check(unsafe { sound_pool_stream_pause(self.pool.handle, self.id) })
+    }
+
+    pub fn resume(&self) -> Result<()> {
+        check(unsafe { sound_pool_stream_resume(self.pool.handle, self.id) })
+    }
+
+    pub fn stop(self) -> Result<()> {
+        check(unsafe { sound_pool_stream_stop(self.pool.handle, self.id) })
+    }
+
+    pub fn get_volume(&self) -> Result<f32> {
+        let mut volume = 0.0;
+        check(unsafe { sound_pool_stream_get_volume(self.pool.handle, self.id, &mut volume) })?;
+
+        Ok(volume)
+    }
+
+    pub fn set_volume(&self, volume: f32) -> Result<()> {
+        check(unsafe { sound_pool_stream_set_volume(self.pool.handle, self.id, volume) })
+    }
+
+    pub fn set_priority(&self, priority: u32) -> Result<()> {
+        check(unsafe { sound_pool_stream_set_priority(self.pool.handle, self.id, priority) })
+    }
+}
@@ -0,0 +1,463 @@
+//! Media pipelines built from source, processing and sink nodes.
+//!
+//! Nodes are added to a [`MediaStreamer`], which then owns them, and linked from their `"src"`
+//! pads to the `"sink"` pads of the next ones.
+
+use crate::error::{check, Error, Result};
+use crate::rutin::{abort_on_panic, cstring, pin_callback};
+use rutin_tizen_sys::{
+    media_streamer_create, media_streamer_destroy, media_streamer_get_state, media_streamer_h,
+    media_streamer_node_add, media_streamer_node_create, media_streamer_node_create_sink,
+    media_streamer_node_create_src, media_streamer_node_destroy, media_streamer_node_h,
+    media_streamer_node_link, media_streamer_node_set_param, media_streamer_node_sink_type_e,
+    media_streamer_node_sink_type_e_MEDIA_STREAMER_NODE_SINK_TYPE_AUDIO,
+    media_streamer_node_sink_type_e_MEDIA_STREAMER_NODE_SINK_TYPE_FAKE,
+    media_streamer_node_sink_type_e_MEDIA_STREAMER_NODE_SINK_TYPE_FILE,
+    media_streamer_node_sink_type_e_MEDIA_STREAMER_NODE_SINK_TYPE_OVERLAY,
+    media_streamer_node_sink_type_e_MEDIA_STREAMER_NODE_SINK_TYPE_RTSP,
+    media_streamer_node_src_type_e,
+    media_streamer_node_src_type_e_MEDIA_STREAMER_NODE_SRC_TYPE_AUDIO_CAPTURE,
+    media_streamer_node_src_type_e_MEDIA_STREAMER_NODE_SRC_TYPE_AUDIO_TEST,
+    media_streamer_node_src_type_e_MEDIA_STREAMER_NODE_SRC_TYPE_CAMERA,
+    media_streamer_node_src_type_e_MEDIA_STREAMER_NODE_SRC_TYPE_FILE,
+    media_streamer_node_src_type_e_MEDIA_STREAMER_NODE_SRC_TYPE_HTTP,
+    media_streamer_node_src_type_e_MEDIA_STREAMER_NODE_SRC_TYPE_RTSP,
+    media_streamer_node_src_type_e_MEDIA_STREAMER_NODE_SRC_TYPE_VIDEO_TEST,
+    media_streamer_node_type_e,
+    media_streamer_node_type_e_MEDIA_STREAMER_NODE_TYPE_AUDIO_CONVERTER,
+    media_streamer_node_type_e_MEDIA_STREAMER_NODE_TYPE_AUDIO_DECODER,
+    media_streamer_node_type_e_MEDIA_STREAMER_NODE_TYPE_AUDIO_DEPAY,
+    media_streamer_node_type_e_MEDIA_STREAMER_NODE_TYPE_AUDIO_ENCODER,
+    media_streamer_node_type_e_MEDIA_STREAMER_NODE_TYPE_AUDIO_PAY,
+    media_streamer_node_type_e_MEDIA_STREAMER_NODE_TYPE_AUDIO_RESAMPLE,
+    media_streamer_node_type_e_MEDIA_STREAMER_NODE_TYPE_DEMUXER,
+    media_streamer_node_type_e_MEDIA_STREAMER_NODE_TYPE_MUXER,
+    media_streamer_node_type_e_MEDIA_STREAMER_NODE_TYPE_QUEUE,
+    media_streamer_node_type_e_MEDIA_STREAMER_NODE_TYPE_RTP,
+    media_streamer_node_type_e_MEDIA_STREAMER_NODE_TYPE_VIDEO_CONVERTER,
+    media_streamer_node_type_e_MEDIA_STREAMER_NODE_TYPE_VIDEO_DECODER,
+    media_streamer_node_type_e_MEDIA_STREAMER_NODE_TYPE_VIDEO_DEPAY,
+    media_streamer_node_type_e_MEDIA_STREAMER_NODE_TYPE_VIDEO_ENCODER,
+    media_streamer_node_type_e_MEDIA_STREAMER_NODE_TYPE_VIDEO_PAY, media_streamer_pause,
+    media_streamer_play, media_streamer_prepare, media_streamer_set_error_cb,
+    media_streamer_set_state_changed_cb, media_streamer_state_e,
+    media_streamer_state_e_MEDIA_STREAMER_STATE_IDLE,
+    media_streamer_state_e_MEDIA_STREAMER_STATE_NONE,
+    media_streamer_state_e_MEDIA_STREAMER_STATE_PAUSED,
+    media_streamer_state_e_MEDIA_STREAMER_STATE_PLAYING,
+    media_streamer_state_e_MEDIA_STREAMER_STATE_READY,
+    media_streamer_state_e_MEDIA_STREAMER_STATE_SEEKING, media_streamer_stop,
+    media_streamer_unprepare, media_streamer_unset_error_cb, media_streamer_unset_state_changed_cb,
+    MEDIA_STREAMER_PARAM_AUDIO_IN_PORT, MEDIA_STREAMER_PARAM_AUDIO_OUT_PORT,
+    MEDIA_STREAMER_PARAM_CAMERA_ID, MEDIA_STREAMER_PARAM_HOST, MEDIA_STREAMER_PARAM_IP_ADDRESS,
+    MEDIA_STREAMER_PARAM_PORT, MEDIA_STREAMER_PARAM_URI, MEDIA_STREAMER_PARAM_VIDEO_IN_PORT,
+    MEDIA_STREAMER_PARAM_VIDEO_OUT_PORT,
+};
+use std::ffi::CString;
+use std::marker::PhantomData;
+use std::os::raw::{c_char, c_int, c_void};
+use std::pin::Pin;
+use std::ptr::null_mut;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum StreamerState {
+    None,
+    Idle,
+    Ready,
+    Playing,
+    Paused,
+    Seeking,
+}
+
+impl From<media_streamer_state_e> for StreamerState {
+    fn from(state: media_streamer_state_e) -> StreamerState {
+        match state {
+            media_streamer_state_e_MEDIA_STREAMER_STATE_IDLE => StreamerState::Idle,
+            media_streamer_state_e_MEDIA_STREAMER_STATE_READY => StreamerState::Ready,
+            media_streamer_state_e_MEDIA_STREAMER_STATE_PLAYING => StreamerState::Playing,
+            media_streamer_state_e_MEDIA_STREAMER_STATE_PAUSED => StreamerState::Paused,
+            media_streamer_state_e_MEDIA_STREAMER_STATE_SEEKING => StreamerState::Seeking,
+            _ => StreamerState::None,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SourceType {
+    File,
+    Http,
+    Rtsp,
+    Camera,
+    AudioCapture,
+    VideoTest,
+    AudioTest,
+}
+
+impl From<SourceType> for media_streamer_node_src_type_e {
+    fn from(source_type: SourceType) -> media_streamer_node_src_type_e {
+        match source_type {
+            SourceType::File => media_streamer_node_src_type_e_MEDIA_STREAMER_NODE_SRC_TYPE_FILE,
+            SourceType::Http => media_streamer_node_src_type_e_MEDIA_STREAMER_NODE_SRC_TYPE_HTTP,
+            SourceType::Rtsp => media_streamer_node_src_type_e_MEDIA_STREAMER_NODE_SRC_TYPE_RTSP,
+            SourceType::Camera => {
+                media_streamer_node_src_type_e_MEDIA_STREAMER_NODE_SRC_TYPE_CAMERA
+            }
+            SourceType::AudioCapture => {
+                media_streamer_node_src_type_e_MEDIA_STREAMER_NODE_SRC_TYPE_AUDIO_CAPTURE
+            }
+            SourceType::VideoTest => {
+                media_streamer_node_src_type_e_MEDIA_STREAMER_NODE_SRC_TYPE_VIDEO_TEST
+            }
+            SourceType::AudioTest => {
+                media_streamer_node_src_type_e_MEDIA_STREAMER_NODE_SRC_TYPE_AUDIO_TEST
+            }
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SinkType {
+    File,
+    Rtsp,
+    Audio,
+    /// Video shown on the display.
+    Overlay,
+    /// Discards everything.
+    Fake,
+}
+
+impl From<SinkType> for media_streamer_node_sink_type_e {
+    fn from(sink_type: SinkType) -> media_streamer_node_sink_type_e {
+        match sink_type {
+            SinkType::File => media_streamer_node_sink_type_e_MEDIA_STREAMER_NODE_SINK_TYPE_FILE,
+            SinkType::Rtsp => media_streamer_node_sink_type_e_MEDIA_STREAMER_NODE_SINK_TYPE_RTSP,
+            SinkType::Audio => media_streamer_node_sink_type_e_MEDIA_STREAMER_NODE_SINK_TYPE_AUDIO,
+            SinkType::Overlay => {
+                media_streamer_node_sink_type_e_MEDIA_STREAMER_NODE_SINK_TYPE_OVERLAY
+            }
+            SinkType::Fake => media_streamer_node_sink_type_e_MEDIA_STREAMER_NODE_SINK_TYPE_FAKE,
+        }
+    }
+}
+
+/// Nodes processing the stream between sources and sinks.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NodeType {
+    VideoEncoder,
+    VideoDecoder,
+    AudioEncoder,
+    AudioDecoder,
+    VideoConverter,
+    AudioConverter,
+    AudioResample,
+    /// Packs video into RTP payloads.
+    VideoPay,
+    AudioPay,
+    /// Unpacks video from RTP payloads.
+    VideoDepay,
+    AudioDepay,
+    /// Sends or receives RTP over UDP, depending on the ports set.
+    Rtp,
+    Queue,
+    Muxer,
+    Demuxer,
+}
+
+impl From<NodeType> for media_streamer_node_type_e {
+    fn from(node_type: NodeType) -> media_streamer_node_type_e {
+        match node_type {
+            NodeType::VideoEncoder => {
+                media_streamer_node_type_e_MEDIA_STREAMER_NODE_TYPE_VIDEO_ENCODER
+            }
+            NodeType::VideoDecoder => {
+                media_streamer_node_type_e_MEDIA_STREAMER_NODE_TYPE_VIDEO_DECODER
+            }
+            NodeType::AudioEncoder => {
+                media_streamer_node_type_e_MEDIA_STREAMER_NODE_TYPE_AUDIO_ENCODER
+            }
+            NodeType::AudioDecoder => {
+                media_streamer_node_type_e_MEDIA_STREAMER_NODE_TYPE_AUDIO_DECODER
+            }
+            NodeType::VideoConverter => {
+                media_streamer_node_type_e_MEDIA_STREAMER_NODE_TYPE_VIDEO_CONVERTER
+            }
+            NodeType::AudioConverter => {
+                media_streamer_node_type_e_MEDIA_STREAMER_NODE_TYPE_AUDIO_CONVERTER
+            }
+            NodeType::AudioResample => {
+                media_streamer_node_type_e_MEDIA_STREAMER_NODE_TYPE_AUDIO_RESAMPLE
+            }
+            NodeType::VideoPay => media_streamer_node_type_e_MEDIA_STREAMER_NODE_TYPE_VIDEO_PAY,
+            NodeType::AudioPay => media_streamer_node_type_e_MEDIA_STREAMER_NODE_TYPE_AUDIO_PAY,
+            NodeType::VideoDepay => media_streamer_node_type_e_MEDIA_STREAMER_NODE_TYPE_VIDEO_DEPAY,
+            NodeType::AudioDepay => media_streamer_node_type_e_MEDIA_STREAMER_NODE_TYPE_AUDIO_DEPAY,
+            NodeType::Rtp => media_streamer_node_type_e_MEDIA_STREAMER_NODE_TYPE_RTP,
+            NodeType::Queue => media_streamer_node_type_e_MEDIA_STREAMER_NODE_TYPE_QUEUE,
+            NodeType::Muxer => media_streamer_node_type_e_MEDIA_STREAMER_NODE_TYPE_MUXER,
+            NodeType::Demuxer => media_streamer_node_type_e_MEDIA_STREAMER_NODE_TYPE_DEMUXER,
+        }
+    }
+}
+
+/// Node parameters, each only meaningful for some node types.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Param<'a> {
+    /// The file path or URL of file, HTTP and RTSP nodes.
+    Uri(&'a str),
+    /// The remote host of RTP nodes.
+    Host(&'a str),
+    VideoInPort(u16),
+    VideoOutPort(u16),
+    AudioInPort(u16),
+    AudioOutPort(u16),
+    /// The address and port of RTSP sinks.
+    IpAddress(&'a str),
+    Port(u16),
+    CameraId(i32),
+}
+
+impl<'a> Param<'a> {
+    fn to_raw(self) -> Result<(&'static [u8], CString)> {
+        let (name, value): (&[u8], String) = match self {
+            Param::Uri(uri) => (MEDIA_STREAMER_PARAM_URI, uri.to_owned()),
+            Param::Host(host) => (MEDIA_STREAMER_PARAM_HOST, host.to_owned()),
+            Param::VideoInPort(port) => (MEDIA_STREAMER_PARAM_VIDEO_IN_PORT, port.to_string()),
+            Param::VideoOutPort(port) => (MEDIA_STREAMER_PARAM_VIDEO_OUT_PORT, port.to_string()),
+            Param::AudioInPort(port) => (MEDIA_STREAMER_PARAM_AUDIO_IN_PORT, port.to_string()),
+            Param::AudioOutPort(port) => (MEDIA_STREAMER_PARAM_AUDIO_OUT_PORT, port.to_string()),
+            Param::IpAddress(address) => (MEDIA_STREAMER_PARAM_IP_ADDRESS, address.to_owned()),
+            Param::Port(port) => (MEDIA_STREAMER_PARAM_PORT, port.to_string()),
+            Param::CameraId(id) => (MEDIA_STREAMER_PARAM_CAMERA_ID, id.to_string()),
+        };
+
+        Ok((name, cstring(&value)?))
+    }
+}
+
+fn set_param(handle: media_streamer_node_h, param: Param<'_>) -> Result<()> {
+    let (name, value) = param.to_raw()?;
+
+    check(unsafe {
+        media_streamer_node_set_param(handle, name.as_ptr() as *const c_char, value.as_ptr())
+    })
+}
+
+/// A node not added to a streamer yet.
+pub struct Node {
+    handle: media_streamer_node_h,
+}
+
+impl Node {
+    pub fn source(source_type: SourceType) -> Result<Node> {
+        let mut handle = null_mut();
+        check(unsafe { media_streamer_node_create_src(source_type.into(), &mut handle) })?;
+
+        Ok(Self { handle })
+    }
+
+    pub fn sink(sink_type: SinkType) -> Result<Node> {
+        let mut handle = null_mut();
+        check(unsafe { media_streamer_node_create_sink(sink_type.into(), &mut handle) })?;
+
+        Ok(Self { handle })
+    }
+
+    /// A processing node, negotiating its formats with the nodes it is linked to.
+    pub fn new(node_type: NodeType) -> Result<Node> {
+        let mut handle = null_mut();
+        check(unsafe {
+            media_streamer_node_create(node_type.into(), null_mut(), null_mut(), &mut handle)
+        })?;
+
+        Ok(Self { handle })
+    }
+
+    pub fn set_param(&self, param: Param<'_>) -> Result<()> {
+        set_param(self.handle, param)
+    }
+}
+
+impl Drop for Node {
+    fn drop(&mut self) {
+        unsafe {
+            media_streamer_node_destroy(self.handle);
+        }
+    }
+}
+
+/// A node owned by a streamer, valid as long as it.
+#[derive(Debug, Copy, Clone)]
+pub struct AddedNode<'s> {
+    handle: media_streamer_node_h,
+    _streamer: PhantomData<&'s MediaStreamer>,
+}
+
+impl<'s> AddedNode<'s> {
+    pub fn set_param(&self, param: Param<'_>) -> Result<()> {
+        set_param(self.handle, param)
+    }
+
+    /// Links the `src_pad` of this node to the `sink_pad` of `dest`.
+    pub fn link(&self, src_pad: &str, dest: &AddedNode<'s>, sink_pad: &str) -> Result<()> {
+        let src_pad = cstring(src_pad)?;
+        let sink_pad = cstring(sink_pad)?;
+
+        check(unsafe {
+            media_streamer_node_link(
+                self.handle,
+                src_pad.as_ptr(),
+                dest.handle,
+                sink_pad.as_ptr(),
+            )
+        })
+    }
+}
+
+type StateFn = Box<dyn FnMut(StreamerState, StreamerState)>;
+type ErrorFn = Box<dyn FnMut(Error)>;
+
+pub struct MediaStreamer {
+    handle: media_streamer_h,
+    state_fn: Option<Pin<Box<StateFn>>>,
+    error_fn: Option<Pin<Box<ErrorFn>>>,
+}
+
+impl MediaStreamer {
+    pub fn new() -> Result<MediaStreamer> {
+        let mut handle = null_mut();
+        check(unsafe { media_streamer_create(&mut handle) })?;
+
+        Ok(Self {
+            handle,
+            state_fn: None,
+            error_fn: None,
+        })
+    }
+
+    /// Adds `node` to the pipeline, the streamer then taking care of destroying it.
+    pub fn add(&self, node: Node) -> Result<AddedNode<'_>> {
+        check(unsafe { media_streamer_node_add(self.handle, node.handle) })?;
+
+        let handle = node.handle;
+        std::mem::forget(node);
+
+        Ok(AddedNode {
+            handle,
+            _streamer: PhantomData,
+        })
+    }
+
+    pub fn get_state(&self) -> Result<StreamerState> {
+        let mut state = media_streamer_state_e_MEDIA_STREAMER_STATE_NONE;
+        check(unsafe { media_streamer_get_state(self.handle, &mut state) })?;
+
+        Ok(state.into())
+    }
+
+    /// Builds the pipeline once the nodes are linked.
+    pub fn prepare(&self) -> Result<()> {
+        check(unsafe { media_streamer_prepare(self.handle) })
+    }
+
+    pub fn unprepare(&self) -> Result<()> {
+        check(unsafe { media_streamer_unprepare(self.handle) })
+    }
+
+    pub fn play(&self) -> Result<()> {
+        check(unsafe { media_streamer_play(self.handle) })
+    }
+
+    pub fn pause(&self) -> Result<()> {
+        check(unsafe { media_streamer_pause(self.handle) })
+    }
+
+    pub fn stop(&self) -> Result<()> {
+        check(unsafe { media_streamer_stop(self.handle) })
+    }
+
+    /// Calls `callback_fn` with the previous and current states, replacing the previous
+    /// callback.
+    pub fn set_state_changed_cb<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(StreamerState, StreamerState),
+        F: 'static,
+    {
+        let (callback_fn, data) =
+            pin_callback::<dyn FnMut(StreamerState, StreamerState)>(Box::new(callback_fn));
+
+        check(unsafe {
+            media_streamer_set_state_changed_cb(self.handle, Some(state_handler), data)
+        })?;
+        self.state_fn = Some(callback_fn);
+
+        Ok(())
+    }
+
+    pub fn unset_state_changed_cb(&mut self) -> Result<()> {
+        check(unsafe { media_streamer_unset_state_changed_cb(self.handle) })?;
+        self.state_fn = None;
+
+        Ok(())
+    }
+
+    /// Calls `callback_fn` with asynchronous errors, replacing the previous callback.
+    pub fn set_error_cb<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(Error),
+        F: 'static,
+    {
+        let (callback_fn, data) = pin_callback::<dyn FnMut(Error)>(Box::new(callback_fn));
+
+        check(unsafe { media_streamer_set_error_cb(self.handle, Some(error_handler), data) })?;
+        self.error_fn = Some(callback_fn);
+
+        Ok(())
+    }
+
+    pub fn unset_error_cb(&mut self) -> Result<()> {
+        check(unsafe { media_streamer_unset_error_cb(self.handle) })?;
+        self.error_fn = None;
+
+        Ok(())
+    }
+}
+
+impl Drop for MediaStreamer {
+    fn drop(&mut self) {
+        unsafe {
+            if self.state_fn.is_some() {
+                media_streamer_unset_state_changed_cb(self.handle);
+            }
+
+            if self.error_fn.is_some() {
+                media_streamer_unset_error_cb(self.handle);
+            }
+
+            // also destroys the nodes
+            media_streamer_destroy(self.handle);
+        }
+    }
+}
+
+extern "C" fn state_handler(
+    _streamer: media_streamer_h,
+    previous_state: media_streamer_state_e,
+    current_state: media_streamer_state_e,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut StateFn) };
+        closure(previous_state.into(), current_state.into());
+    })
+}
+
+extern "C" fn error_handler(_streamer: media_streamer_h, error: c_int, user_data: *mut c_void) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut ErrorFn) };
+        closure(Error::from(error));
+    })
+}
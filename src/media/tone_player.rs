@@ -0,0 +1,98 @@
+//! Playback of DTMF and other standard telephony tones.
+
+use super::{RawStreamInfo, StreamType};
+use crate::error::{check, Result};
+use rutin_tizen_sys::{
+    tone_player_start_new, tone_player_stop, tone_type_e, tone_type_e_TONE_TYPE_DEFAULT,
+    tone_type_e_TONE_TYPE_DTMF_0, tone_type_e_TONE_TYPE_DTMF_1, tone_type_e_TONE_TYPE_DTMF_2,
+    tone_type_e_TONE_TYPE_DTMF_3, tone_type_e_TONE_TYPE_DTMF_4, tone_type_e_TONE_TYPE_DTMF_5,
+    tone_type_e_TONE_TYPE_DTMF_6, tone_type_e_TONE_TYPE_DTMF_7, tone_type_e_TONE_TYPE_DTMF_8,
+    tone_type_e_TONE_TYPE_DTMF_9, tone_type_e_TONE_TYPE_DTMF_A, tone_type_e_TONE_TYPE_DTMF_B,
+    tone_type_e_TONE_TYPE_DTMF_C, tone_type_e_TONE_TYPE_DTMF_D, tone_type_e_TONE_TYPE_DTMF_P,
+    tone_type_e_TONE_TYPE_DTMF_S, tone_type_e_TONE_TYPE_PROP_ACK, tone_type_e_TONE_TYPE_PROP_BEEP,
+    tone_type_e_TONE_TYPE_PROP_BEEP2, tone_type_e_TONE_TYPE_PROP_NACK,
+    tone_type_e_TONE_TYPE_PROP_PROMPT, tone_type_e_TONE_TYPE_SUP_BUSY,
+    tone_type_e_TONE_TYPE_SUP_CALL_WAITING, tone_type_e_TONE_TYPE_SUP_CONGESTION,
+    tone_type_e_TONE_TYPE_SUP_DIAL, tone_type_e_TONE_TYPE_SUP_ERROR,
+    tone_type_e_TONE_TYPE_SUP_RINGTONE,
+};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Tone {
+    Default,
+    /// A dial-pad key, `0` to `9`, `*`, `#` or `A` to `D`.
+    Dtmf(char),
+    Dial,
+    Busy,
+    Congestion,
+    Error,
+    CallWaiting,
+    Ringtone,
+    Beep,
+    Beep2,
+    Ack,
+    Nack,
+    Prompt,
+}
+
+impl From<Tone> for tone_type_e {
+    fn from(tone: Tone) -> tone_type_e {
+        match tone {
+            Tone::Default => tone_type_e_TONE_TYPE_DEFAULT,
+            Tone::Dtmf(key) => match key {
+                '0' => tone_type_e_TONE_TYPE_DTMF_0,
+                '1' => tone_type_e_TONE_TYPE_DTMF_1,
+                '2' => tone_type_e_TONE_TYPE_DTMF_2,
+                '3' => tone_type_e_TONE_TYPE_DTMF_3,
+                '4' => tone_type_e_TONE_TYPE_DTMF_4,
+                '5' => tone_type_e_TONE_TYPE_DTMF_5,
+                '6' => tone_type_e_TONE_TYPE_DTMF_6,
+                '7' => tone_type_e_TONE_TYPE_DTMF_7,
+                '8' => tone_type_e_TONE_TYPE_DTMF_8,
+                '9' => tone_type_e_TONE_TYPE_DTMF_9,
+                '*' => tone_type_e_TONE_TYPE_DTMF_S,
+                '#' => tone_type_e_TONE_TYPE_DTMF_P,
+                'A' | 'a' => tone_type_e_TONE_TYPE_DTMF_A,
+                'B' | 'b' => tone_type_e_TONE_TYPE_DTMF_B,
+                'C' | 'c' => tone_type_e_TONE_TYPE_DTMF_C,
+                'D' | 'd' => tone_type_e_TONE_TYPE_DTMF_D,
+                _ => tone_type_e_TONE_TYPE_DEFAULT,
+            },
+            Tone::Dial => tone_type_e_TONE_TYPE_SUP_DIAL,
+            Tone::Busy => tone_type_e_TONE_TYPE_SUP_BUSY,
+            Tone::Congestion => tone_type_e_TONE_TYPE_SUP_CONGESTION,
+            Tone::Error => tone_type_e_TONE_TYPE_SUP_ERROR,
+            Tone::CallWaiting => tone_type_e_TONE_TYPE_SUP_CALL_WAITING,
+            Tone::Ringtone => tone_type_e_TONE_TYPE_SUP_RINGTONE,
+            Tone::Beep => tone_type_e_TONE_TYPE_PROP_BEEP,
+            Tone::Beep2 => tone_type_e_TONE_TYPE_PROP_BEEP2,
+            Tone::Ack => tone_type_e_TONE_TYPE_PROP_ACK,
+            Tone::Nack => tone_type_e_TONE_TYPE_PROP_NACK,
+            Tone::Prompt => tone_type_e_TONE_TYPE_PROP_PROMPT,
+        }
+    }
+}
+
+/// A tone being played, which keeps playing when dropped.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TonePlayback {
+    id: i32,
+}
+
+impl TonePlayback {
+    pub fn stop(self) -> Result<()> {
+        check(unsafe { tone_player_stop(self.id) })
+    }
+}
+
+/// Plays `tone` for `duration_ms`, or until stopped if `-1`.
+pub fn start(tone: Tone, stream_type: StreamType, duration_ms: i32) -> Result<TonePlayback> {
+    let stream_info = RawStreamInfo::new(stream_type)?;
+    let mut id = 0;
+
+    check(unsafe { tone_player_start_new(tone.into(), stream_info.handle, duration_ms, &mut id) })?;
+
+    Ok(TonePlayback { id })
+}
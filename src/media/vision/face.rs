@@ -0,0 +1,164 @@
+//! Face detection and recognition.
+
+use super::{Rectangle, Source};
+use crate::error::{check, Result};
+use crate::rutin::{abort_on_panic, path_cstring};
+use rutin_tizen_sys::{
+    mv_engine_config_h, mv_face_detect, mv_face_recognition_model_add,
+    mv_face_recognition_model_create, mv_face_recognition_model_destroy,
+    mv_face_recognition_model_h, mv_face_recognition_model_learn, mv_face_recognition_model_load,
+    mv_face_recognition_model_save, mv_face_recognize, mv_rectangle_s, mv_source_h,
+};
+use std::os::raw::{c_int, c_void};
+use std::path::Path;
+use std::ptr::{null, null_mut};
+use std::slice;
+
+/// The location of every face in `source`.
+pub fn detect_faces(source: &Source) -> Result<Vec<Rectangle>> {
+    let mut faces = Vec::<Rectangle>::new();
+
+    check(unsafe {
+        mv_face_detect(
+            source.handle,
+            null_mut(),
+            Some(detected_handler),
+            &mut faces as *mut Vec<Rectangle> as *mut c_void,
+        )
+    })?;
+
+    Ok(faces)
+}
+
+/// A face recognized by [`RecognitionModel::recognize`].
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Recognition {
+    pub label: i32,
+    /// Between 0 and 1.
+    pub confidence: f64,
+    pub location: Rectangle,
+}
+
+/// A model telling apart the faces it learned, each with an integer label.
+pub struct RecognitionModel {
+    handle: mv_face_recognition_model_h,
+}
+
+impl RecognitionModel {
+    pub fn new() -> Result<RecognitionModel> {
+        let mut handle = null_mut();
+        check(unsafe { mv_face_recognition_model_create(&mut handle) })?;
+
+        Ok(Self { handle })
+    }
+
+    pub fn load(path: &Path) -> Result<RecognitionModel> {
+        let path = path_cstring(path)?;
+        let mut handle = null_mut();
+
+        check(unsafe { mv_face_recognition_model_load(path.as_ptr(), &mut handle) })?;
+
+        Ok(Self { handle })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let path = path_cstring(path)?;
+
+        check(unsafe { mv_face_recognition_model_save(path.as_ptr(), self.handle) })
+    }
+
+    /// Adds an example of the face `label`, in `location` or the whole `source` if `None`.
+    /// The examples only count after [`RecognitionModel::learn`].
+    pub fn add(&self, source: &Source, location: Option<Rectangle>, label: i32) -> Result<()> {
+        let location = location.map(mv_rectangle_s::from);
+
+        check(unsafe {
+            mv_face_recognition_model_add(
+                source.handle,
+                self.handle,
+                location.as_ref().map_or(null(), |l| l as *const _),
+                label,
+            )
+        })
+    }
+
+    /// Trains the model with the examples added so far, which can take a while.
+    pub fn learn(&self) -> Result<()> {
+        check(unsafe { mv_face_recognition_model_learn(null_mut(), self.handle) })
+    }
+
+    /// Recognizes the face in `location`, or the whole `source` if `None`.
+    /// Returns `None` if the face is unknown.
+    pub fn recognize(
+        &self,
+        source: &Source,
+        location: Option<Rectangle>,
+    ) -> Result<Option<Recognition>> {
+        let mut location = location.map(mv_rectangle_s::from);
+        let mut recognition: Option<Recognition> = None;
+
+        check(unsafe {
+            mv_face_recognize(
+                source.handle,
+                self.handle,
+                null_mut(),
+                location.as_mut().map_or(null_mut(), |l| l as *mut _),
+                Some(recognized_handler),
+                &mut recognition as *mut Option<Recognition> as *mut c_void,
+            )
+        })?;
+
+        Ok(recognition)
+    }
+}
+
+impl Drop for RecognitionModel {
+    fn drop(&mut self) {
+        unsafe {
+            mv_face_recognition_model_destroy(self.handle);
+        }
+    }
+}
+
+extern "C" fn detected_handler(
+    _source: mv_source_h,
+    _engine_cfg: mv_engine_config_h,
+    faces_locations: *mut mv_rectangle_s,
+    number_of_faces: c_int,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let faces = unsafe { &mut *(user_data as *mut Vec<Rectangle>) };
+
+        if !faces_locations.is_null() && number_of_faces > 0 {
+            let locations =
+                unsafe { slice::from_raw_parts(faces_locations, number_of_faces as usize) };
+            faces.extend(locations.iter().map(|&l| Rectangle::from(l)));
+        }
+    })
+}
+
+extern "C" fn recognized_handler(
+    _source: mv_source_h,
+    _recognition_model: mv_face_recognition_model_h,
+    _engine_cfg: mv_engine_config_h,
+    face_location: *mut mv_rectangle_s,
+    face_label: *const c_int,
+    confidence: f64,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let recognition = unsafe { &mut *(user_data as *mut Option<Recognition>) };
+
+        unsafe {
+            if let (Some(&label), Some(&location)) = (face_label.as_ref(), face_location.as_ref()) {
+                *recognition = Some(Recognition {
+                    label,
+                    confidence,
+                    location: location.into(),
+                });
+            }
+        }
+    })
+}
@@ -0,0 +1,173 @@
+//! Recognition and tracking of known pictures, such as posters or book covers.
+
+use super::{Quadrangle, Rectangle, Source};
+use crate::error::{check, Result};
+use crate::rutin::{abort_on_panic, path_cstring};
+use rutin_tizen_sys::{
+    mv_engine_config_h, mv_image_object_create, mv_image_object_destroy, mv_image_object_fill,
+    mv_image_object_h, mv_image_object_load, mv_image_object_save, mv_image_recognize,
+    mv_image_track, mv_image_tracking_model_create, mv_image_tracking_model_destroy,
+    mv_image_tracking_model_h, mv_image_tracking_model_load, mv_image_tracking_model_save,
+    mv_image_tracking_model_set_target, mv_quadrangle_s, mv_rectangle_s, mv_source_h,
+};
+use std::os::raw::{c_uint, c_void};
+use std::path::Path;
+use std::ptr::null_mut;
+use std::slice;
+
+/// A picture to look for.
+pub struct ImageObject {
+    handle: mv_image_object_h,
+}
+
+impl ImageObject {
+    /// Extracts the picture in `location`, or the whole `source` if `None`.
+    pub fn new(source: &Source, location: Option<Rectangle>) -> Result<ImageObject> {
+        let mut object = ImageObject { handle: null_mut() };
+        check(unsafe { mv_image_object_create(&mut object.handle) })?;
+
+        let mut location = location.map(mv_rectangle_s::from);
+        check(unsafe {
+            mv_image_object_fill(
+                object.handle,
+                null_mut(),
+                source.handle,
+                location.as_mut().map_or(null_mut(), |l| l as *mut _),
+            )
+        })?;
+
+        Ok(object)
+    }
+
+    pub fn load(path: &Path) -> Result<ImageObject> {
+        let path = path_cstring(path)?;
+        let mut handle = null_mut();
+
+        check(unsafe { mv_image_object_load(path.as_ptr(), &mut handle) })?;
+
+        Ok(Self { handle })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let path = path_cstring(path)?;
+
+        check(unsafe { mv_image_object_save(path.as_ptr(), self.handle) })
+    }
+}
+
+impl Drop for ImageObject {
+    fn drop(&mut self) {
+        unsafe {
+            mv_image_object_destroy(self.handle);
+        }
+    }
+}
+
+/// Looks for each of `objects` in `source`, returning where they were found, in the same order.
+pub fn recognize(source: &Source, objects: &[&ImageObject]) -> Result<Vec<Option<Quadrangle>>> {
+    let handles = objects.iter().map(|o| o.handle).collect::<Vec<_>>();
+    let mut locations = vec![None; handles.len()];
+
+    check(unsafe {
+        mv_image_recognize(
+            source.handle,
+            handles.as_ptr(),
+            handles.len() as i32,
+            null_mut(),
+            Some(recognized_handler),
+            &mut locations as *mut Vec<Option<Quadrangle>> as *mut c_void,
+        )
+    })?;
+
+    Ok(locations)
+}
+
+/// Follows a picture across consecutive frames.
+pub struct TrackingModel {
+    handle: mv_image_tracking_model_h,
+}
+
+impl TrackingModel {
+    pub fn new(target: &ImageObject) -> Result<TrackingModel> {
+        let mut model = TrackingModel { handle: null_mut() };
+        check(unsafe { mv_image_tracking_model_create(&mut model.handle) })?;
+        check(unsafe { mv_image_tracking_model_set_target(target.handle, model.handle) })?;
+
+        Ok(model)
+    }
+
+    pub fn load(path: &Path) -> Result<TrackingModel> {
+        let path = path_cstring(path)?;
+        let mut handle = null_mut();
+
+        check(unsafe { mv_image_tracking_model_load(path.as_ptr(), &mut handle) })?;
+
+        Ok(Self { handle })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let path = path_cstring(path)?;
+
+        check(unsafe { mv_image_tracking_model_save(path.as_ptr(), self.handle) })
+    }
+
+    /// Finds the target in the next frame, `None` if it was lost.
+    pub fn track(&mut self, source: &Source) -> Result<Option<Quadrangle>> {
+        let mut location: Option<Quadrangle> = None;
+
+        check(unsafe {
+            mv_image_track(
+                source.handle,
+                self.handle,
+                null_mut(),
+                Some(tracked_handler),
+                &mut location as *mut Option<Quadrangle> as *mut c_void,
+            )
+        })?;
+
+        Ok(location)
+    }
+}
+
+impl Drop for TrackingModel {
+    fn drop(&mut self) {
+        unsafe {
+            mv_image_tracking_model_destroy(self.handle);
+        }
+    }
+}
+
+extern "C" fn recognized_handler(
+    _source: mv_source_h,
+    _engine_cfg: mv_engine_config_h,
+    _image_objects: *const mv_image_object_h,
+    locations: *mut *mut mv_quadrangle_s,
+    number_of_objects: c_uint,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let found = unsafe { &mut *(user_data as *mut Vec<Option<Quadrangle>>) };
+
+        if locations.is_null() {
+            return;
+        }
+
+        let locations = unsafe { slice::from_raw_parts(locations, number_of_objects as usize) };
+        for (found, &location) in found.iter_mut().zip(locations) {
+            *found = unsafe { location.as_ref() }.map(|&l| Quadrangle::from(l));
+        }
+    })
+}
+
+extern "C" fn tracked_handler(
+    _source: mv_source_h,
+    _image_tracking_model: mv_image_tracking_model_h,
+    _engine_cfg: mv_engine_config_h,
+    location: *mut mv_quadrangle_s,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let tracked = unsafe { &mut *(user_data as *mut Option<Quadrangle>) };
+        *tracked = unsafe { location.as_ref() }.map(|&l| Quadrangle::from(l));
+    })
+}
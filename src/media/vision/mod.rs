@@ -0,0 +1,152 @@
+//! On-device computer vision: face detection and recognition, image recognition and tracking.
+//!
+//! Frames are wrapped in a [`Source`], and the engines use their default configuration.
+
+pub mod face;
+pub mod image;
+
+use crate::error::{check, Result};
+use rutin_tizen_sys::{
+    mv_colorspace_e, mv_colorspace_e_MEDIA_VISION_COLORSPACE_422P,
+    mv_colorspace_e_MEDIA_VISION_COLORSPACE_I420, mv_colorspace_e_MEDIA_VISION_COLORSPACE_NV12,
+    mv_colorspace_e_MEDIA_VISION_COLORSPACE_NV21, mv_colorspace_e_MEDIA_VISION_COLORSPACE_RGB565,
+    mv_colorspace_e_MEDIA_VISION_COLORSPACE_RGB888, mv_colorspace_e_MEDIA_VISION_COLORSPACE_RGBA,
+    mv_colorspace_e_MEDIA_VISION_COLORSPACE_UYVY, mv_colorspace_e_MEDIA_VISION_COLORSPACE_Y800,
+    mv_colorspace_e_MEDIA_VISION_COLORSPACE_YUYV, mv_colorspace_e_MEDIA_VISION_COLORSPACE_YV12,
+    mv_create_source, mv_destroy_source, mv_point_s, mv_quadrangle_s, mv_rectangle_s,
+    mv_source_fill_by_buffer, mv_source_h,
+};
+use std::ptr::null_mut;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Colorspace {
+    /// 8 bit grayscale.
+    Y800,
+    I420,
+    Nv12,
+    Yv12,
+    Nv21,
+    Yuyv,
+    Uyvy,
+    Yuv422P,
+    Rgb565,
+    Rgb888,
+    Rgba,
+}
+
+impl From<Colorspace> for mv_colorspace_e {
+    fn from(colorspace: Colorspace) -> mv_colorspace_e {
+        match colorspace {
+            Colorspace::Y800 => mv_colorspace_e_MEDIA_VISION_COLORSPACE_Y800,
+            Colorspace::I420 => mv_colorspace_e_MEDIA_VISION_COLORSPACE_I420,
+            Colorspace::Nv12 => mv_colorspace_e_MEDIA_VISION_COLORSPACE_NV12,
+            Colorspace::Yv12 => mv_colorspace_e_MEDIA_VISION_COLORSPACE_YV12,
+            Colorspace::Nv21 => mv_colorspace_e_MEDIA_VISION_COLORSPACE_NV21,
+            Colorspace::Yuyv => mv_colorspace_e_MEDIA_VISION_COLORSPACE_YUYV,
+            Colorspace::Uyvy => mv_colorspace_e_MEDIA_VISION_COLORSPACE_UYVY,
+            Colorspace::Yuv422P => mv_colorspace_e_MEDIA_VISION_COLORSPACE_422P,
+            Colorspace::Rgb565 => mv_colorspace_e_MEDIA_VISION_COLORSPACE_RGB565,
+            Colorspace::Rgb888 => mv_colorspace_e_MEDIA_VISION_COLORSPACE_RGB888,
+            Colorspace::Rgba => mv_colorspace_e_MEDIA_VISION_COLORSPACE_RGBA,
+        }
+    }
+}
+
+/// A point in pixels, from the top left corner.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl From<mv_point_s> for Point {
+    fn from(point: mv_point_s) -> Point {
+        Point {
+            x: point.x,
+            y: point.y,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Rectangle {
+    pub origin: Point,
+    pub width: i32,
+    pub height: i32,
+}
+
+impl From<mv_rectangle_s> for Rectangle {
+    fn from(rectangle: mv_rectangle_s) -> Rectangle {
+        Rectangle {
+            origin: rectangle.point.into(),
+            width: rectangle.width,
+            height: rectangle.height,
+        }
+    }
+}
+
+impl From<Rectangle> for mv_rectangle_s {
+    fn from(rectangle: Rectangle) -> mv_rectangle_s {
+        mv_rectangle_s {
+            point: mv_point_s {
+                x: rectangle.origin.x,
+                y: rectangle.origin.y,
+            },
+            width: rectangle.width,
+            height: rectangle.height,
+        }
+    }
+}
+
+/// The corners of an object seen in perspective, clockwise from the top left one.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Quadrangle {
+    pub points: [Point; 4],
+}
+
+impl From<mv_quadrangle_s> for Quadrangle {
+    fn from(quadrangle: mv_quadrangle_s) -> Quadrangle {
+        let p = quadrangle.points;
+
+        Quadrangle {
+            points: [p[0].into(), p[1].into(), p[2].into(), p[3].into()],
+        }
+    }
+}
+
+/// A frame to analyze, copied from a pixel buffer.
+pub struct Source {
+    handle: mv_source_h,
+}
+
+impl Source {
+    pub fn new(data: &[u8], width: u32, height: u32, colorspace: Colorspace) -> Result<Source> {
+        let mut source = Source { handle: null_mut() };
+        check(unsafe { mv_create_source(&mut source.handle) })?;
+
+        check(unsafe {
+            mv_source_fill_by_buffer(
+                source.handle,
+                data.as_ptr() as *mut u8,
+                data.len() as u32,
+                width,
+                height,
+                colorspace.into(),
+            )
+        })?;
+
+        Ok(source)
+    }
+}
+
+impl Drop for Source {
+    fn drop(&mut self) {
+        unsafe {
+            mv_destroy_source(self.handle);
+        }
+    }
+}
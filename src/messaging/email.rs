@@ -0,0 +1,139 @@
+//! Composing and sending emails with the accounts set up on the device.
+//!
+//! The platform only exposes sending, mailboxes cannot be read. Requires the
+//! `http://tizen.org/privilege/email` privilege.
+
+use crate::error::{check, Result};
+use crate::rutin::{abort_on_panic, cstring, path_cstring, pin_callback};
+use rutin_tizen_sys::{
+    email_add_attach, email_add_recipient, email_create_message, email_destroy_message, email_h,
+    email_recipient_type_e, email_recipient_type_e_EMAIL_RECIPIENT_TYPE_BCC,
+    email_recipient_type_e_EMAIL_RECIPIENT_TYPE_CC, email_recipient_type_e_EMAIL_RECIPIENT_TYPE_TO,
+    email_remove_all_attachments, email_remove_all_recipients, email_save_message,
+    email_send_message, email_sending_e, email_sending_e_EMAIL_SENDING_SUCCEEDED, email_set_body,
+    email_set_message_sent_cb, email_set_subject, email_unset_message_sent_cb,
+};
+use std::os::raw::c_void;
+use std::path::Path;
+use std::pin::Pin;
+use std::ptr::null_mut;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RecipientType {
+    To,
+    Cc,
+    Bcc,
+}
+
+impl From<RecipientType> for email_recipient_type_e {
+    fn from(recipient_type: RecipientType) -> email_recipient_type_e {
+        match recipient_type {
+            RecipientType::To => email_recipient_type_e_EMAIL_RECIPIENT_TYPE_TO,
+            RecipientType::Cc => email_recipient_type_e_EMAIL_RECIPIENT_TYPE_CC,
+            RecipientType::Bcc => email_recipient_type_e_EMAIL_RECIPIENT_TYPE_BCC,
+        }
+    }
+}
+
+type SentFn = Box<dyn FnMut(bool)>;
+
+/// An email being composed, sent from the default account.
+pub struct Email {
+    handle: email_h,
+    sent_fn: Option<Pin<Box<SentFn>>>,
+}
+
+impl Email {
+    pub fn new() -> Result<Email> {
+        let mut handle = null_mut();
+        check(unsafe { email_create_message(&mut handle) })?;
+
+        Ok(Self {
+            handle,
+            sent_fn: None,
+        })
+    }
+
+    pub fn set_subject(&self, subject: &str) -> Result<()> {
+        let subject = cstring(subject)?;
+
+        check(unsafe { email_set_subject(self.handle, subject.as_ptr()) })
+    }
+
+    /// The plain text body.
+    pub fn set_body(&self, body: &str) -> Result<()> {
+        let body = cstring(body)?;
+
+        check(unsafe { email_set_body(self.handle, body.as_ptr()) })
+    }
+
+    pub fn add_recipient(&self, recipient_type: RecipientType, address: &str) -> Result<()> {
+        let address = cstring(address)?;
+
+        check(unsafe { email_add_recipient(self.handle, recipient_type.into(), address.as_ptr()) })
+    }
+
+    pub fn remove_all_recipients(&self) -> Result<()> {
+        check(unsafe { email_remove_all_recipients(self.handle) })
+    }
+
+    /// Attaches a file, which must exist until the email is sent.
+    pub fn add_attachment(&self, path: &Path) -> Result<()> {
+        let path = path_cstring(path)?;
+
+        check(unsafe { email_add_attach(self.handle, path.as_ptr()) })
+    }
+
+    pub fn remove_all_attachments(&self) -> Result<()> {
+        check(unsafe { email_remove_all_attachments(self.handle) })
+    }
+
+    /// Sends the email in the background, the sent callback is then called with the result.
+    /// If `save`, it is kept in the sent box.
+    pub fn send(&self, save: bool) -> Result<()> {
+        check(unsafe { email_save_message(self.handle) })?;
+
+        check(unsafe { email_send_message(self.handle, save) })
+    }
+
+    /// Calls `callback_fn` with whether the email was sent, replacing the previous callback.
+    pub fn set_sent_cb<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(bool),
+        F: 'static,
+    {
+        let (callback_fn, data) = pin_callback::<dyn FnMut(bool)>(Box::new(callback_fn));
+
+        check(unsafe { email_set_message_sent_cb(self.handle, Some(sent_handler), data) })?;
+        self.sent_fn = Some(callback_fn);
+
+        Ok(())
+    }
+
+    pub fn unset_sent_cb(&mut self) -> Result<()> {
+        check(unsafe { email_unset_message_sent_cb(self.handle) })?;
+        self.sent_fn = None;
+
+        Ok(())
+    }
+}
+
+impl Drop for Email {
+    fn drop(&mut self) {
+        unsafe {
+            if self.sent_fn.is_some() {
+                email_unset_message_sent_cb(self.handle);
+            }
+
+            email_destroy_message(self.handle);
+        }
+    }
+}
+
+extern "C" fn sent_handler(_email: email_h, result: email_sending_e, user_data: *mut c_void) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut SentFn) };
+        closure(result == email_sending_e_EMAIL_SENDING_SUCCEEDED);
+    })
+}
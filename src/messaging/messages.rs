@@ -0,0 +1,464 @@
+//! SMS and MMS, on devices with telephony.
+//!
+//! Requires the `http://tizen.org/privilege/message.read` privilege, and
+//! `http://tizen.org/privilege/message.write` to send messages.
+
+use crate::error::{check, Result};
+use crate::rutin::{abort_on_panic, cstring, path_cstring, pin_callback, take_string};
+use rutin_tizen_sys::{
+    messages_add_address, messages_close_service, messages_create_message,
+    messages_destroy_message, messages_foreach_message, messages_get_address,
+    messages_get_address_count, messages_get_mbox_type, messages_get_message_id,
+    messages_get_message_type, messages_get_text, messages_get_time, messages_media_type_e,
+    messages_media_type_e_MESSAGES_MEDIA_AUDIO, messages_media_type_e_MESSAGES_MEDIA_IMAGE,
+    messages_media_type_e_MESSAGES_MEDIA_VIDEO, messages_message_box_e,
+    messages_message_box_e_MESSAGES_MBOX_ALL, messages_message_box_e_MESSAGES_MBOX_DRAFT,
+    messages_message_box_e_MESSAGES_MBOX_INBOX, messages_message_box_e_MESSAGES_MBOX_OUTBOX,
+    messages_message_box_e_MESSAGES_MBOX_SENTBOX, messages_message_h, messages_message_type_e,
+    messages_message_type_e_MESSAGES_TYPE_MMS, messages_message_type_e_MESSAGES_TYPE_SMS,
+    messages_message_type_e_MESSAGES_TYPE_UNKNOWN, messages_mms_add_attachment,
+    messages_mms_get_subject, messages_mms_set_subject, messages_open_service,
+    messages_recipient_type_e, messages_recipient_type_e_MESSAGES_RECIPIENT_BCC,
+    messages_recipient_type_e_MESSAGES_RECIPIENT_CC,
+    messages_recipient_type_e_MESSAGES_RECIPIENT_TO, messages_send_message,
+    messages_sending_result_e, messages_sending_result_e_MESSAGES_SENDING_SUCCEEDED,
+    messages_service_h, messages_set_message_incoming_cb, messages_set_text,
+    messages_unset_message_incoming_cb,
+};
+use std::os::raw::{c_int, c_void};
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::ptr::{null, null_mut};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MessageType {
+    Sms,
+    Mms,
+}
+
+impl From<MessageType> for messages_message_type_e {
+    fn from(message_type: MessageType) -> messages_message_type_e {
+        match message_type {
+            MessageType::Sms => messages_message_type_e_MESSAGES_TYPE_SMS,
+            MessageType::Mms => messages_message_type_e_MESSAGES_TYPE_MMS,
+        }
+    }
+}
+
+impl From<messages_message_type_e> for MessageType {
+    /// Cell broadcasts and other SMS variants are reported as [`MessageType::Sms`].
+    fn from(message_type: messages_message_type_e) -> MessageType {
+        match message_type {
+            messages_message_type_e_MESSAGES_TYPE_MMS => MessageType::Mms,
+            _ => MessageType::Sms,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MessageBox {
+    /// Every box, only used for searching.
+    All,
+    Inbox,
+    /// Waiting to be sent.
+    Outbox,
+    Sent,
+    Draft,
+}
+
+impl From<MessageBox> for messages_message_box_e {
+    fn from(mbox: MessageBox) -> messages_message_box_e {
+        match mbox {
+            MessageBox::All => messages_message_box_e_MESSAGES_MBOX_ALL,
+            MessageBox::Inbox => messages_message_box_e_MESSAGES_MBOX_INBOX,
+            MessageBox::Outbox => messages_message_box_e_MESSAGES_MBOX_OUTBOX,
+            MessageBox::Sent => messages_message_box_e_MESSAGES_MBOX_SENTBOX,
+            MessageBox::Draft => messages_message_box_e_MESSAGES_MBOX_DRAFT,
+        }
+    }
+}
+
+impl From<messages_message_box_e> for MessageBox {
+    fn from(mbox: messages_message_box_e) -> MessageBox {
+        match mbox {
+            messages_message_box_e_MESSAGES_MBOX_INBOX => MessageBox::Inbox,
+            messages_message_box_e_MESSAGES_MBOX_OUTBOX => MessageBox::Outbox,
+            messages_message_box_e_MESSAGES_MBOX_SENTBOX => MessageBox::Sent,
+            messages_message_box_e_MESSAGES_MBOX_DRAFT => MessageBox::Draft,
+            _ => MessageBox::All,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RecipientType {
+    To,
+    Cc,
+    Bcc,
+}
+
+impl From<RecipientType> for messages_recipient_type_e {
+    fn from(recipient_type: RecipientType) -> messages_recipient_type_e {
+        match recipient_type {
+            RecipientType::To => messages_recipient_type_e_MESSAGES_RECIPIENT_TO,
+            RecipientType::Cc => messages_recipient_type_e_MESSAGES_RECIPIENT_CC,
+            RecipientType::Bcc => messages_recipient_type_e_MESSAGES_RECIPIENT_BCC,
+        }
+    }
+}
+
+impl From<messages_recipient_type_e> for RecipientType {
+    fn from(recipient_type: messages_recipient_type_e) -> RecipientType {
+        match recipient_type {
+            messages_recipient_type_e_MESSAGES_RECIPIENT_CC => RecipientType::Cc,
+            messages_recipient_type_e_MESSAGES_RECIPIENT_BCC => RecipientType::Bcc,
+            _ => RecipientType::To,
+        }
+    }
+}
+
+/// The kinds of MMS attachments.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MediaType {
+    Image,
+    Audio,
+    Video,
+}
+
+impl From<MediaType> for messages_media_type_e {
+    fn from(media_type: MediaType) -> messages_media_type_e {
+        match media_type {
+            MediaType::Image => messages_media_type_e_MESSAGES_MEDIA_IMAGE,
+            MediaType::Audio => messages_media_type_e_MESSAGES_MEDIA_AUDIO,
+            MediaType::Video => messages_media_type_e_MESSAGES_MEDIA_VIDEO,
+        }
+    }
+}
+
+/// A message of the message store.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Message {
+    pub id: i32,
+    pub message_type: MessageType,
+    pub mbox: MessageBox,
+    /// The sender of received messages, or the recipients of sent ones.
+    pub addresses: Vec<(String, RecipientType)>,
+    pub text: Option<String>,
+    /// Only for MMS.
+    pub subject: Option<String>,
+    /// Seconds since the epoch.
+    pub time: i64,
+}
+
+impl Message {
+    unsafe fn read(message: messages_message_h) -> Result<Message> {
+        let mut id = 0;
+        check(messages_get_message_id(message, &mut id))?;
+
+        let mut message_type = messages_message_type_e_MESSAGES_TYPE_UNKNOWN;
+        check(messages_get_message_type(message, &mut message_type))?;
+        let message_type = MessageType::from(message_type);
+
+        let mut mbox = messages_message_box_e_MESSAGES_MBOX_ALL;
+        check(messages_get_mbox_type(message, &mut mbox))?;
+
+        let mut count = 0;
+        check(messages_get_address_count(message, &mut count))?;
+
+        let mut addresses = Vec::new();
+        for index in 0..count {
+            let mut address = null_mut();
+            let mut recipient_type = messages_recipient_type_e_MESSAGES_RECIPIENT_TO;
+            check(messages_get_address(
+                message,
+                index,
+                &mut address,
+                &mut recipient_type,
+            ))?;
+
+            if let Some(address) = take_string(address) {
+                addresses.push((address, recipient_type.into()));
+            }
+        }
+
+        let mut text = null_mut();
+        check(messages_get_text(message, &mut text))?;
+
+        let mut subject = null_mut();
+        if message_type == MessageType::Mms {
+            check(messages_mms_get_subject(message, &mut subject))?;
+        }
+
+        let mut time = 0;
+        check(messages_get_time(message, &mut time))?;
+
+        Ok(Message {
+            id,
+            message_type,
+            mbox: mbox.into(),
+            addresses,
+            text: take_string(text),
+            subject: take_string(subject),
+            time: i64::from(time),
+        })
+    }
+}
+
+/// A message to send, built with its recipients and content.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NewMessage {
+    message_type: MessageType,
+    recipients: Vec<(String, RecipientType)>,
+    text: Option<String>,
+    subject: Option<String>,
+    attachments: Vec<(MediaType, PathBuf)>,
+}
+
+impl NewMessage {
+    pub fn sms() -> NewMessage {
+        Self::new(MessageType::Sms)
+    }
+
+    pub fn mms() -> NewMessage {
+        Self::new(MessageType::Mms)
+    }
+
+    fn new(message_type: MessageType) -> NewMessage {
+        NewMessage {
+            message_type,
+            recipients: Vec::new(),
+            text: None,
+            subject: None,
+            attachments: Vec::new(),
+        }
+    }
+
+    /// Adds a recipient phone number, or for MMS an email address.
+    pub fn recipient(mut self, address: &str, recipient_type: RecipientType) -> Self {
+        self.recipients.push((address.to_owned(), recipient_type));
+        self
+    }
+
+    pub fn text(mut self, text: &str) -> Self {
+        self.text = Some(text.to_owned());
+        self
+    }
+
+    /// Only for MMS.
+    pub fn subject(mut self, subject: &str) -> Self {
+        self.subject = Some(subject.to_owned());
+        self
+    }
+
+    /// Only for MMS.
+    pub fn attachment(mut self, media_type: MediaType, path: PathBuf) -> Self {
+        self.attachments.push((media_type, path));
+        self
+    }
+
+    fn to_raw(&self) -> Result<RawMessage> {
+        let mut handle = null_mut();
+        check(unsafe { messages_create_message(self.message_type.into(), &mut handle) })?;
+        let message = RawMessage { handle };
+
+        for (address, recipient_type) in &self.recipients {
+            let address = cstring(address.as_str())?;
+            check(unsafe {
+                messages_add_address(handle, address.as_ptr(), (*recipient_type).into())
+            })?;
+        }
+
+        if let Some(text) = &self.text {
+            let text = cstring(text.as_str())?;
+            check(unsafe { messages_set_text(handle, text.as_ptr()) })?;
+        }
+
+        if let Some(subject) = &self.subject {
+            let subject = cstring(subject.as_str())?;
+            check(unsafe { messages_mms_set_subject(handle, subject.as_ptr()) })?;
+        }
+
+        for (media_type, path) in &self.attachments {
+            let path = path_cstring(path)?;
+            check(unsafe {
+                messages_mms_add_attachment(handle, (*media_type).into(), path.as_ptr())
+            })?;
+        }
+
+        Ok(message)
+    }
+}
+
+struct RawMessage {
+    handle: messages_message_h,
+}
+
+impl Drop for RawMessage {
+    fn drop(&mut self) {
+        unsafe {
+            messages_destroy_message(self.handle);
+        }
+    }
+}
+
+type IncomingFn = Box<dyn FnMut(Message)>;
+
+pub struct MessageService {
+    handle: messages_service_h,
+    incoming_fn: Option<Pin<Box<IncomingFn>>>,
+}
+
+impl MessageService {
+    pub fn new() -> Result<MessageService> {
+        let mut handle = null_mut();
+        check(unsafe { messages_open_service(&mut handle) })?;
+
+        Ok(Self {
+            handle,
+            incoming_fn: None,
+        })
+    }
+
+    /// Sends `message`, calling `callback_fn` with whether it was sent. If `save`, it is
+    /// kept in the sent box.
+    pub fn send<F>(&self, message: &NewMessage, save: bool, callback_fn: F) -> Result<()>
+    where
+        F: FnOnce(bool),
+        F: 'static,
+    {
+        let raw = message.to_raw()?;
+        let data = Box::into_raw(Box::new(Box::new(callback_fn) as Box<dyn FnOnce(bool)>));
+
+        let ret = unsafe {
+            messages_send_message(
+                self.handle,
+                raw.handle,
+                save,
+                Some(sent_handler),
+                data as *mut c_void,
+            )
+        };
+
+        if let Err(e) = check(ret) {
+            drop(unsafe { Box::from_raw(data) });
+            return Err(e);
+        }
+
+        Ok(())
+    }
+
+    /// Up to `limit` messages from `offset` in `mbox`, of `message_type` or all if `None`,
+    /// containing `keyword` and exchanged with `address` if given. `limit` 0 means all of them.
+    pub fn search(
+        &self,
+        mbox: MessageBox,
+        message_type: Option<MessageType>,
+        keyword: Option<&str>,
+        address: Option<&str>,
+        offset: i32,
+        limit: i32,
+    ) -> Result<Vec<Message>> {
+        let keyword = keyword.map(cstring).transpose()?;
+        let address = address.map(cstring).transpose()?;
+        let message_type = message_type.map_or(
+            messages_message_type_e_MESSAGES_TYPE_UNKNOWN,
+            messages_message_type_e::from,
+        );
+        let mut messages = Vec::<Message>::new();
+
+        check(unsafe {
+            messages_foreach_message(
+                self.handle,
+                mbox.into(),
+                message_type,
+                keyword.as_ref().map_or(null(), |k| k.as_ptr()),
+                address.as_ref().map_or(null(), |a| a.as_ptr()),
+                offset,
+                limit,
+                Some(search_handler),
+                &mut messages as *mut Vec<Message> as *mut c_void,
+            )
+        })?;
+
+        Ok(messages)
+    }
+
+    /// Calls `callback_fn` with each received message, replacing the previous callback.
+    pub fn set_incoming_cb<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(Message),
+        F: 'static,
+    {
+        let (callback_fn, data) = pin_callback::<dyn FnMut(Message)>(Box::new(callback_fn));
+        check(unsafe {
+            messages_set_message_incoming_cb(self.handle, Some(incoming_handler), data)
+        })?;
+        self.incoming_fn = Some(callback_fn);
+
+        Ok(())
+    }
+
+    pub fn unset_incoming_cb(&mut self) -> Result<()> {
+        check(unsafe { messages_unset_message_incoming_cb(self.handle) })?;
+        self.incoming_fn = None;
+
+        Ok(())
+    }
+}
+
+impl Drop for MessageService {
+    fn drop(&mut self) {
+        unsafe {
+            if self.incoming_fn.is_some() {
+                messages_unset_message_incoming_cb(self.handle);
+            }
+
+            messages_close_service(self.handle);
+        }
+    }
+}
+
+extern "C" fn sent_handler(result: messages_sending_result_e, user_data: *mut c_void) {
+    abort_on_panic(|| {
+        let callback_fn = unsafe { Box::from_raw(user_data as *mut Box<dyn FnOnce(bool)>) };
+        callback_fn(result == messages_sending_result_e_MESSAGES_SENDING_SUCCEEDED);
+    })
+}
+
+extern "C" fn search_handler(
+    message: messages_message_h,
+    _index: c_int,
+    _result_count: c_int,
+    _total_count: c_int,
+    user_data: *mut c_void,
+) -> bool {
+    abort_on_panic(|| {
+        // also called once with no message when nothing matches
+        if message.is_null() {
+            return false;
+        }
+
+        let messages = unsafe { &mut *(user_data as *mut Vec<Message>) };
+        if let Ok(message) = unsafe { Message::read(message) } {
+            messages.push(message);
+        }
+
+        true
+    })
+}
+
+extern "C" fn incoming_handler(message: messages_message_h, user_data: *mut c_void) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut IncomingFn) };
+
+        if let Ok(message) = unsafe { Message::read(message) } {
+            closure(message);
+        }
+    })
+}
@@ -0,0 +1,3 @@
+pub mod email;
+pub mod messages;
+pub mod push;
@@ -0,0 +1,247 @@
+//! The push service, delivering notifications sent by an app server.
+//!
+//! Requires the `http://tizen.org/privilege/push` privilege.
+
+use crate::error::{check, Error, Result};
+use crate::rutin::{abort_on_panic, borrow_str, cstring, take_string};
+use rutin_tizen_sys::{
+    push_service_connect, push_service_connection_h, push_service_deregister,
+    push_service_disconnect, push_service_get_notification_data,
+    push_service_get_notification_message, push_service_get_notification_sender,
+    push_service_get_notification_time, push_service_get_registration_id,
+    push_service_notification_h, push_service_register, push_service_request_unread_notification,
+    push_service_result_e, push_service_result_e_PUSH_SERVICE_RESULT_SERVER_ERROR,
+    push_service_result_e_PUSH_SERVICE_RESULT_SUCCESS,
+    push_service_result_e_PUSH_SERVICE_RESULT_TIMEOUT, push_service_state_e,
+    push_service_state_e_PUSH_SERVICE_STATE_PING_CHANGE,
+    push_service_state_e_PUSH_SERVICE_STATE_PROVISIONING_IPCHANGE,
+    push_service_state_e_PUSH_SERVICE_STATE_REGISTERED,
+    push_service_state_e_PUSH_SERVICE_STATE_UNREGISTERED,
+};
+use std::os::raw::{c_char, c_int, c_void};
+use std::pin::Pin;
+use std::ptr::null_mut;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PushState {
+    /// Registered with the push server, notifications can be received.
+    Registered,
+    /// Connected but not registered yet, [`PushService::register`] should be called.
+    Unregistered,
+    ProvisioningIpChange,
+    PingChange,
+    Error(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PushError {
+    Timeout,
+    ServerError(String),
+    SystemError(String),
+}
+
+fn push_result(
+    result: push_service_result_e,
+    msg: *const c_char,
+) -> std::result::Result<(), PushError> {
+    let msg = || unsafe { borrow_str(msg) }.unwrap_or_default().into_owned();
+
+    match result {
+        push_service_result_e_PUSH_SERVICE_RESULT_SUCCESS => Ok(()),
+        push_service_result_e_PUSH_SERVICE_RESULT_TIMEOUT => Err(PushError::Timeout),
+        push_service_result_e_PUSH_SERVICE_RESULT_SERVER_ERROR => {
+            Err(PushError::ServerError(msg()))
+        }
+        _ => Err(PushError::SystemError(msg())),
+    }
+}
+
+/// A notification received from the push server.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PushNotification {
+    /// The payload set by the app server.
+    pub data: Option<String>,
+    /// The message shown in the notification tray, if any.
+    pub message: Option<String>,
+    pub sender: Option<String>,
+    /// When the notification was received, in seconds since the epoch.
+    pub time: i64,
+}
+
+impl PushNotification {
+    unsafe fn from_raw(notification: push_service_notification_h) -> PushNotification {
+        let string =
+            |get: unsafe extern "C" fn(push_service_notification_h, *mut *mut c_char) -> c_int| {
+                let mut value = null_mut();
+                if get(notification, &mut value) == 0 {
+                    take_string(value)
+                } else {
+                    None
+                }
+            };
+
+        let mut time = 0;
+        push_service_get_notification_time(notification, &mut time);
+
+        Self {
+            data: string(push_service_get_notification_data),
+            message: string(push_service_get_notification_message),
+            sender: string(push_service_get_notification_sender),
+            time,
+        }
+    }
+}
+
+struct Callbacks {
+    state_fn: Box<dyn FnMut(PushState)>,
+    notify_fn: Box<dyn FnMut(PushNotification)>,
+}
+
+type ResultFn = dyn FnOnce(std::result::Result<(), PushError>);
+
+/// A connection to the push service, disconnected when dropped.
+pub struct PushService {
+    handle: push_service_connection_h,
+    _callbacks: Pin<Box<Callbacks>>,
+}
+
+impl PushService {
+    /// Connects with the push app id assigned to the application, `state_fn` is
+    /// called on each state change and `notify_fn` with each notification.
+    pub fn connect<S, N>(push_app_id: &str, state_fn: S, notify_fn: N) -> Result<PushService>
+    where
+        S: FnMut(PushState),
+        S: 'static,
+        N: FnMut(PushNotification),
+        N: 'static,
+    {
+        let push_app_id = cstring(push_app_id)?;
+        let callbacks = Box::pin(Callbacks {
+            state_fn: Box::new(state_fn),
+            notify_fn: Box::new(notify_fn),
+        });
+        let data = &*callbacks as *const Callbacks as *mut c_void;
+
+        let mut handle = null_mut();
+        check(unsafe {
+            push_service_connect(
+                push_app_id.as_ptr(),
+                Some(state_handler),
+                Some(notify_handler),
+                data,
+                &mut handle,
+            )
+        })?;
+
+        Ok(Self {
+            handle,
+            _callbacks: callbacks,
+        })
+    }
+
+    /// Registers with the push server, `callback_fn` is called with the result.
+    pub fn register<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnOnce(std::result::Result<(), PushError>),
+        F: 'static,
+    {
+        let callback_fn: Box<ResultFn> = Box::new(callback_fn);
+        let data = Box::into_raw(Box::new(callback_fn));
+
+        let ret = unsafe {
+            push_service_register(self.handle, Some(result_handler), data as *mut c_void)
+        };
+
+        if let Err(e) = check(ret) {
+            drop(unsafe { Box::from_raw(data) });
+            return Err(e);
+        }
+
+        Ok(())
+    }
+
+    pub fn deregister<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnOnce(std::result::Result<(), PushError>),
+        F: 'static,
+    {
+        let callback_fn: Box<ResultFn> = Box::new(callback_fn);
+        let data = Box::into_raw(Box::new(callback_fn));
+
+        let ret = unsafe {
+            push_service_deregister(self.handle, Some(result_handler), data as *mut c_void)
+        };
+
+        if let Err(e) = check(ret) {
+            drop(unsafe { Box::from_raw(data) });
+            return Err(e);
+        }
+
+        Ok(())
+    }
+
+    /// The id the app server uses to address this device, once registered.
+    pub fn registration_id(&self) -> Result<String> {
+        let mut id = null_mut();
+        check(unsafe { push_service_get_registration_id(self.handle, &mut id) })?;
+
+        unsafe { take_string(id) }.ok_or(Error::NoData)
+    }
+
+    /// Asks for the notifications received while the application wasn't running,
+    /// delivered to the notify callback.
+    pub fn request_unread_notifications(&mut self) -> Result<()> {
+        check(unsafe { push_service_request_unread_notification(self.handle) })
+    }
+}
+
+impl Drop for PushService {
+    fn drop(&mut self) {
+        unsafe {
+            push_service_disconnect(self.handle);
+        }
+    }
+}
+
+extern "C" fn state_handler(
+    state: push_service_state_e,
+    err: *const c_char,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let callbacks = unsafe { &mut *(user_data as *mut Callbacks) };
+
+        let state = match state {
+            push_service_state_e_PUSH_SERVICE_STATE_REGISTERED => PushState::Registered,
+            push_service_state_e_PUSH_SERVICE_STATE_UNREGISTERED => PushState::Unregistered,
+            push_service_state_e_PUSH_SERVICE_STATE_PROVISIONING_IPCHANGE => {
+                PushState::ProvisioningIpChange
+            }
+            push_service_state_e_PUSH_SERVICE_STATE_PING_CHANGE => PushState::PingChange,
+            _ => PushState::Error(unsafe { borrow_str(err) }.unwrap_or_default().into_owned()),
+        };
+
+        (callbacks.state_fn)(state);
+    })
+}
+
+extern "C" fn notify_handler(notification: push_service_notification_h, user_data: *mut c_void) {
+    abort_on_panic(|| {
+        let callbacks = unsafe { &mut *(user_data as *mut Callbacks) };
+        (callbacks.notify_fn)(unsafe { PushNotification::from_raw(notification) });
+    })
+}
+
+extern "C" fn result_handler(
+    result: push_service_result_e,
+    msg: *const c_char,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let closure = unsafe { Box::from_raw(user_data as *mut Box<ResultFn>) };
+        closure(push_result(result, msg));
+    })
+}
@@ -0,0 +1,250 @@
+//! Mini controls, small windows of an app shown inside the quick panel or the lock screen,
+//! such as music playback controls.
+//!
+//! The app owning the control creates it with [`Provider`] and fills its window like any
+//! other, the apps showing it embed it with [`Viewer`]. Providers require the
+//! `http://tizen.org/privilege/minicontrol.provider` privilege.
+
+use crate::efl::elm::Win;
+use crate::efl::evas::Object;
+use crate::error::{check, last_error, Error, Result};
+use crate::rutin::{abort_on_panic, borrow_str, cstring, pin_callback};
+use rutin_tizen_sys::{
+    bundle, minicontrol_create_window, minicontrol_event_e,
+    minicontrol_event_e_MINICONTROL_EVENT_REPORT_ANGLE,
+    minicontrol_event_e_MINICONTROL_EVENT_REQUEST_ANGLE,
+    minicontrol_event_e_MINICONTROL_EVENT_REQUEST_HIDE,
+    minicontrol_event_e_MINICONTROL_EVENT_RESIZE, minicontrol_event_e_MINICONTROL_EVENT_START,
+    minicontrol_provider_event_e,
+    minicontrol_provider_event_e_MINICONTROL_PROVIDER_EVENT_REQUEST_ANGLE,
+    minicontrol_provider_event_e_MINICONTROL_PROVIDER_EVENT_REQUEST_HIDE, minicontrol_send_event,
+    minicontrol_viewer_add, minicontrol_viewer_event_e,
+    minicontrol_viewer_event_e_MINICONTROL_VIEWER_EVENT_HIDE,
+    minicontrol_viewer_event_e_MINICONTROL_VIEWER_EVENT_REPORT_ANGLE,
+    minicontrol_viewer_event_e_MINICONTROL_VIEWER_EVENT_SHOW, minicontrol_viewer_send_event,
+    minicontrol_viewer_set_event_cb, minicontrol_viewer_unset_event_cb, Eo,
+};
+use std::cell::RefCell;
+use std::os::raw::{c_char, c_void};
+use std::pin::Pin;
+use std::ptr::null_mut;
+
+/// Where a mini control can be shown, combined with `|`.
+pub mod target {
+    pub const QUICK_PANEL: i32 = 1 << 0;
+    pub const STOCK_LOCK_SCREEN: i32 = 1 << 1;
+    pub const CUSTOM_LOCK_SCREEN: i32 = 1 << 2;
+}
+
+/// Sent by the viewers to the provider, or by the provider to the viewers.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Event {
+    Start,
+    Stop,
+    Resize,
+    RequestHide,
+    RequestAngle,
+    ReportAngle,
+}
+
+impl From<minicontrol_event_e> for Event {
+    fn from(event: minicontrol_event_e) -> Event {
+        match event {
+            minicontrol_event_e_MINICONTROL_EVENT_START => Event::Start,
+            minicontrol_event_e_MINICONTROL_EVENT_RESIZE => Event::Resize,
+            minicontrol_event_e_MINICONTROL_EVENT_REQUEST_HIDE => Event::RequestHide,
+            minicontrol_event_e_MINICONTROL_EVENT_REQUEST_ANGLE => Event::RequestAngle,
+            minicontrol_event_e_MINICONTROL_EVENT_REPORT_ANGLE => Event::ReportAngle,
+            _ => Event::Stop,
+        }
+    }
+}
+
+/// What a provider asks its viewers.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ProviderEvent {
+    RequestHide,
+    RequestAngle,
+}
+
+impl From<ProviderEvent> for minicontrol_provider_event_e {
+    fn from(event: ProviderEvent) -> minicontrol_provider_event_e {
+        match event {
+            ProviderEvent::RequestHide => {
+                minicontrol_provider_event_e_MINICONTROL_PROVIDER_EVENT_REQUEST_HIDE
+            }
+            ProviderEvent::RequestAngle => {
+                minicontrol_provider_event_e_MINICONTROL_PROVIDER_EVENT_REQUEST_ANGLE
+            }
+        }
+    }
+}
+
+/// What a viewer tells a provider.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ViewerEvent {
+    Show,
+    Hide,
+    ReportAngle,
+}
+
+impl From<ViewerEvent> for minicontrol_viewer_event_e {
+    fn from(event: ViewerEvent) -> minicontrol_viewer_event_e {
+        match event {
+            ViewerEvent::Show => minicontrol_viewer_event_e_MINICONTROL_VIEWER_EVENT_SHOW,
+            ViewerEvent::Hide => minicontrol_viewer_event_e_MINICONTROL_VIEWER_EVENT_HIDE,
+            ViewerEvent::ReportAngle => {
+                minicontrol_viewer_event_e_MINICONTROL_VIEWER_EVENT_REPORT_ANGLE
+            }
+        }
+    }
+}
+
+thread_local! {
+    // the provider callback has no user data, so only one provider can exist at a time
+    static PROVIDER_FN: RefCell<Option<Box<dyn FnMut(Event)>>> = const { RefCell::new(None) };
+}
+
+/// The window of a mini control owned by this app, deleted when dropped.
+pub struct Provider<'a> {
+    win: Win<'a>,
+}
+
+impl<'a> Provider<'a> {
+    /// Creates the window of the mini control `name`, shown in the `targets` viewers.
+    /// `callback_fn` is called with the events sent by the viewers.
+    ///
+    /// Fails with [`Error::InvalidOperation`] if a provider already exists.
+    pub fn new<F>(name: &str, targets: i32, callback_fn: F) -> Result<Provider<'a>>
+    where
+        F: FnMut(Event),
+        F: 'static,
+    {
+        if PROVIDER_FN.with(|provider_fn| provider_fn.borrow().is_some()) {
+            return Err(Error::InvalidOperation);
+        }
+
+        let name = cstring(name)?;
+        let eo = unsafe {
+            minicontrol_create_window(name.as_ptr(), targets as _, Some(provider_handler))
+        };
+        let win = Win::from_eo(eo).ok_or_else(last_error)?;
+
+        PROVIDER_FN.with(|provider_fn| *provider_fn.borrow_mut() = Some(Box::new(callback_fn)));
+
+        Ok(Self { win })
+    }
+
+    /// The window to fill with the content of the mini control.
+    pub fn win(&mut self) -> &mut Win<'a> {
+        &mut self.win
+    }
+
+    pub fn send_event(&mut self, event: ProviderEvent) -> Result<()> {
+        check(unsafe { minicontrol_send_event(self.win.eo_ptr(), event.into(), null_mut()) })
+    }
+}
+
+impl<'a> Drop for Provider<'a> {
+    fn drop(&mut self) {
+        self.win.del();
+        PROVIDER_FN.with(|provider_fn| provider_fn.borrow_mut().take());
+    }
+}
+
+/// A mini control of another app, embedded in a window of this one.
+pub struct Viewer<'a> {
+    eo: *mut Eo,
+    _phantom: std::marker::PhantomData<&'a Eo>,
+}
+
+impl<'a> Viewer<'a> {
+    pub fn new(parent: &mut dyn Object<'a>, name: &str) -> Result<Viewer<'a>> {
+        let name = cstring(name)?;
+        let eo = unsafe { minicontrol_viewer_add(parent.eo_ptr(), name.as_ptr()) };
+
+        if eo.is_null() {
+            Err(last_error())
+        } else {
+            Ok(Self {
+                eo,
+                _phantom: std::marker::PhantomData,
+            })
+        }
+    }
+}
+
+impl<'a> Object<'a> for Viewer<'a> {
+    fn eo_ptr(&mut self) -> *mut Eo {
+        self.eo
+    }
+}
+
+/// Tells the provider of the mini control `name` how it is shown.
+pub fn send_viewer_event(name: &str, event: ViewerEvent) -> Result<()> {
+    let name = cstring(name)?;
+
+    check(unsafe { minicontrol_viewer_send_event(name.as_ptr(), event.into(), null_mut()) })
+}
+
+type ViewerFn = Box<dyn FnMut(Event, &str)>;
+
+/// Receives the events of the providers of all the viewed mini controls, until dropped.
+pub struct ViewerListener {
+    _callback_fn: Pin<Box<ViewerFn>>,
+}
+
+impl ViewerListener {
+    /// Calls `callback_fn` with each event and the name of the mini control which sent it,
+    /// replacing the previous listener.
+    pub fn new<F>(callback_fn: F) -> Result<ViewerListener>
+    where
+        F: FnMut(Event, &str),
+        F: 'static,
+    {
+        let (callback_fn, data) = pin_callback::<dyn FnMut(Event, &str)>(Box::new(callback_fn));
+
+        check(unsafe { minicontrol_viewer_set_event_cb(Some(viewer_handler), data) })?;
+
+        Ok(Self {
+            _callback_fn: callback_fn,
+        })
+    }
+}
+
+impl Drop for ViewerListener {
+    fn drop(&mut self) {
+        unsafe {
+            minicontrol_viewer_unset_event_cb();
+        }
+    }
+}
+
+extern "C" fn provider_handler(event: minicontrol_event_e, _event_arg: *mut bundle) {
+    abort_on_panic(|| {
+        PROVIDER_FN.with(|provider_fn| {
+            if let Ok(mut provider_fn) = provider_fn.try_borrow_mut() {
+                if let Some(provider_fn) = provider_fn.as_mut() {
+                    provider_fn(event.into());
+                }
+            }
+        })
+    })
+}
+
+extern "C" fn viewer_handler(
+    event: minicontrol_event_e,
+    name: *const c_char,
+    _event_arg: *mut bundle,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut ViewerFn) };
+        let name = unsafe { borrow_str(name) }.unwrap_or_default();
+
+        closure(event.into(), &name);
+    })
+}
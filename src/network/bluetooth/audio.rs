@@ -0,0 +1,156 @@
+use crate::error::{check, Error, Result};
+use crate::rutin::{abort_on_panic, borrow_str, cstring, pin_callback};
+use rutin_tizen_sys::{
+    bt_audio_connect, bt_audio_deinitialize, bt_audio_disconnect, bt_audio_initialize,
+    bt_audio_profile_type_e, bt_audio_profile_type_e_BT_AUDIO_PROFILE_TYPE_A2DP,
+    bt_audio_profile_type_e_BT_AUDIO_PROFILE_TYPE_A2DP_SINK,
+    bt_audio_profile_type_e_BT_AUDIO_PROFILE_TYPE_AG,
+    bt_audio_profile_type_e_BT_AUDIO_PROFILE_TYPE_ALL,
+    bt_audio_profile_type_e_BT_AUDIO_PROFILE_TYPE_HSP_HFP,
+    bt_audio_set_connection_state_changed_cb, bt_audio_unset_connection_state_changed_cb,
+};
+use std::convert::TryFrom;
+use std::os::raw::{c_char, c_int, c_void};
+use std::pin::Pin;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AudioProfile {
+    /// Every profile supported by the remote device.
+    All,
+    /// Headset and hands-free, for calls.
+    HspHfp,
+    /// Stereo audio to headphones or speakers.
+    A2dp,
+    /// Audio gateway, the phone side of HFP.
+    Ag,
+    /// Stereo audio received from a remote source.
+    A2dpSink,
+}
+
+impl From<AudioProfile> for bt_audio_profile_type_e {
+    fn from(profile: AudioProfile) -> bt_audio_profile_type_e {
+        match profile {
+            AudioProfile::All => bt_audio_profile_type_e_BT_AUDIO_PROFILE_TYPE_ALL,
+            AudioProfile::HspHfp => bt_audio_profile_type_e_BT_AUDIO_PROFILE_TYPE_HSP_HFP,
+            AudioProfile::A2dp => bt_audio_profile_type_e_BT_AUDIO_PROFILE_TYPE_A2DP,
+            AudioProfile::Ag => bt_audio_profile_type_e_BT_AUDIO_PROFILE_TYPE_AG,
+            AudioProfile::A2dpSink => bt_audio_profile_type_e_BT_AUDIO_PROFILE_TYPE_A2DP_SINK,
+        }
+    }
+}
+
+impl TryFrom<bt_audio_profile_type_e> for AudioProfile {
+    type Error = Error;
+
+    fn try_from(profile: bt_audio_profile_type_e) -> Result<AudioProfile> {
+        match profile {
+            bt_audio_profile_type_e_BT_AUDIO_PROFILE_TYPE_ALL => Ok(AudioProfile::All),
+            bt_audio_profile_type_e_BT_AUDIO_PROFILE_TYPE_HSP_HFP => Ok(AudioProfile::HspHfp),
+            bt_audio_profile_type_e_BT_AUDIO_PROFILE_TYPE_A2DP => Ok(AudioProfile::A2dp),
+            bt_audio_profile_type_e_BT_AUDIO_PROFILE_TYPE_AG => Ok(AudioProfile::Ag),
+            bt_audio_profile_type_e_BT_AUDIO_PROFILE_TYPE_A2DP_SINK => Ok(AudioProfile::A2dpSink),
+            _ => Err(Error::Unknown),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AudioConnectionState {
+    pub result: Result<()>,
+    pub connected: bool,
+    pub remote_address: String,
+    pub profile: AudioProfile,
+}
+
+type ConnectionStateFn = Box<dyn FnMut(AudioConnectionState)>;
+
+/// The Bluetooth audio profiles, deinitialized when this is dropped.
+///
+/// [`super::initialize`] must have been called first.
+pub struct BluetoothAudio {
+    connection_state_fn: Option<Pin<Box<ConnectionStateFn>>>,
+}
+
+impl BluetoothAudio {
+    pub fn new() -> Result<BluetoothAudio> {
+        check(unsafe { bt_audio_initialize() })?;
+
+        Ok(Self {
+            connection_state_fn: None,
+        })
+    }
+
+    /// Connects to a paired device, the result is reported to the connection state callback.
+    pub fn connect(&mut self, remote_address: &str, profile: AudioProfile) -> Result<()> {
+        let remote_address = cstring(remote_address)?;
+
+        check(unsafe { bt_audio_connect(remote_address.as_ptr(), profile.into()) })
+    }
+
+    pub fn disconnect(&mut self, remote_address: &str, profile: AudioProfile) -> Result<()> {
+        let remote_address = cstring(remote_address)?;
+
+        check(unsafe { bt_audio_disconnect(remote_address.as_ptr(), profile.into()) })
+    }
+
+    /// Calls `callback_fn` each time an audio profile connects or disconnects,
+    /// replacing the previous callback.
+    pub fn set_connection_state_changed_cb<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(AudioConnectionState),
+        F: 'static,
+    {
+        let (callback_fn, data) =
+            pin_callback::<dyn FnMut(AudioConnectionState)>(Box::new(callback_fn));
+
+        check(unsafe {
+            bt_audio_set_connection_state_changed_cb(Some(connection_state_handler), data)
+        })?;
+        self.connection_state_fn = Some(callback_fn);
+
+        Ok(())
+    }
+
+    pub fn unset_connection_state_changed_cb(&mut self) -> Result<()> {
+        check(unsafe { bt_audio_unset_connection_state_changed_cb() })?;
+        self.connection_state_fn = None;
+
+        Ok(())
+    }
+}
+
+impl Drop for BluetoothAudio {
+    fn drop(&mut self) {
+        unsafe {
+            if self.connection_state_fn.is_some() {
+                bt_audio_unset_connection_state_changed_cb();
+            }
+            bt_audio_deinitialize();
+        }
+    }
+}
+
+extern "C" fn connection_state_handler(
+    result: c_int,
+    connected: bool,
+    remote_address: *const c_char,
+    profile: bt_audio_profile_type_e,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut ConnectionStateFn) };
+
+        if let Ok(profile) = AudioProfile::try_from(profile) {
+            closure(AudioConnectionState {
+                result: check(result),
+                connected,
+                remote_address: unsafe { borrow_str(remote_address) }
+                    .unwrap_or_default()
+                    .into_owned(),
+                profile,
+            });
+        }
+    })
+}
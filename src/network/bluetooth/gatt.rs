@@ -0,0 +1,472 @@
+use crate::error::{check, Error, Result};
+use crate::rutin::{abort_on_panic, borrow_str, cstring, pin_callback, take_string};
+use rutin_tizen_sys::{
+    bt_gatt_characteristic_foreach_descriptors, bt_gatt_characteristic_get_properties,
+    bt_gatt_characteristic_set_write_type, bt_gatt_client_create, bt_gatt_client_destroy,
+    bt_gatt_client_foreach_services, bt_gatt_client_get_service, bt_gatt_client_h,
+    bt_gatt_client_read_value, bt_gatt_client_set_characteristic_value_changed_cb,
+    bt_gatt_client_unset_characteristic_value_changed_cb, bt_gatt_client_write_value,
+    bt_gatt_connect, bt_gatt_disconnect, bt_gatt_foreach_cb, bt_gatt_get_uuid, bt_gatt_get_value,
+    bt_gatt_h, bt_gatt_service_foreach_characteristics, bt_gatt_service_get_characteristic,
+    bt_gatt_set_connection_state_changed_cb, bt_gatt_set_value,
+    bt_gatt_unset_connection_state_changed_cb, bt_gatt_write_type_e,
+    bt_gatt_write_type_e_BT_GATT_WRITE_TYPE_WRITE,
+    bt_gatt_write_type_e_BT_GATT_WRITE_TYPE_WRITE_NO_RESPONSE,
+};
+use std::marker::PhantomData;
+use std::os::raw::{c_char, c_int, c_void};
+use std::pin::Pin;
+use std::ptr::null_mut;
+
+/// Connects to a remote LE device, `auto_connect` reconnects whenever it
+/// comes back in range. The result is reported to the connection state callback.
+pub fn connect(address: &str, auto_connect: bool) -> Result<()> {
+    let address = cstring(address)?;
+
+    check(unsafe { bt_gatt_connect(address.as_ptr(), auto_connect) })
+}
+
+pub fn disconnect(address: &str) -> Result<()> {
+    let address = cstring(address)?;
+
+    check(unsafe { bt_gatt_disconnect(address.as_ptr()) })
+}
+
+type ConnectionStateFn = Box<dyn FnMut(Result<()>, bool, &str)>;
+
+/// The GATT connection state callback, unset when this is dropped.
+pub struct ConnectionStateCallback {
+    _callback_fn: Pin<Box<ConnectionStateFn>>,
+}
+
+impl Drop for ConnectionStateCallback {
+    fn drop(&mut self) {
+        unsafe {
+            bt_gatt_unset_connection_state_changed_cb();
+        }
+    }
+}
+
+/// Calls `callback_fn` with the result, the new connection state and the
+/// remote address each time a GATT connection changes. Only one callback can
+/// be set at a time.
+pub fn set_connection_state_changed_cb<F>(callback_fn: F) -> Result<ConnectionStateCallback>
+where
+    F: FnMut(Result<()>, bool, &str),
+    F: 'static,
+{
+    let (callback_fn, data) =
+        pin_callback::<dyn FnMut(Result<()>, bool, &str)>(Box::new(callback_fn));
+
+    check(unsafe {
+        bt_gatt_set_connection_state_changed_cb(Some(connection_state_handler), data)
+    })?;
+
+    Ok(ConnectionStateCallback {
+        _callback_fn: callback_fn,
+    })
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum WriteType {
+    WithResponse,
+    WithoutResponse,
+}
+
+impl From<WriteType> for bt_gatt_write_type_e {
+    fn from(write_type: WriteType) -> bt_gatt_write_type_e {
+        match write_type {
+            WriteType::WithResponse => bt_gatt_write_type_e_BT_GATT_WRITE_TYPE_WRITE,
+            WriteType::WithoutResponse => bt_gatt_write_type_e_BT_GATT_WRITE_TYPE_WRITE_NO_RESPONSE,
+        }
+    }
+}
+
+/// `bt_gatt_property_e` flags of a characteristic.
+pub mod property {
+    pub const BROADCAST: i32 = 0x01;
+    pub const READ: i32 = 0x02;
+    pub const WRITE_WITHOUT_RESPONSE: i32 = 0x04;
+    pub const WRITE: i32 = 0x08;
+    pub const NOTIFY: i32 = 0x10;
+    pub const INDICATE: i32 = 0x20;
+}
+
+/// A GATT client bound to a remote device, which must be connected first.
+pub struct GattClient {
+    handle: bt_gatt_client_h,
+}
+
+impl GattClient {
+    pub fn new(remote_address: &str) -> Result<GattClient> {
+        let remote_address = cstring(remote_address)?;
+        let mut handle = null_mut();
+
+        check(unsafe { bt_gatt_client_create(remote_address.as_ptr(), &mut handle) })?;
+
+        Ok(Self { handle })
+    }
+
+    pub fn services(&self) -> Result<Vec<GattService<'_>>> {
+        let handles = collect_handles(|cb, data| unsafe {
+            bt_gatt_client_foreach_services(self.handle, cb, data)
+        })?;
+
+        Ok(handles.into_iter().map(GattService::from_handle).collect())
+    }
+
+    pub fn get_service(&self, uuid: &str) -> Result<GattService<'_>> {
+        let uuid = cstring(uuid)?;
+        let mut handle = null_mut();
+
+        check(unsafe { bt_gatt_client_get_service(self.handle, uuid.as_ptr(), &mut handle) })?;
+
+        Ok(GattService::from_handle(handle))
+    }
+}
+
+impl Drop for GattClient {
+    fn drop(&mut self) {
+        unsafe {
+            bt_gatt_client_destroy(self.handle);
+        }
+    }
+}
+
+fn get_uuid(handle: bt_gatt_h) -> Result<String> {
+    let mut uuid = null_mut();
+
+    check(unsafe { bt_gatt_get_uuid(handle, &mut uuid) })?;
+
+    unsafe { take_string(uuid) }.ok_or(Error::NoData)
+}
+
+fn get_value(handle: bt_gatt_h) -> Result<Vec<u8>> {
+    let mut value: *mut c_char = null_mut();
+    let mut len: c_int = 0;
+
+    check(unsafe { bt_gatt_get_value(handle, &mut value, &mut len) })?;
+
+    if value.is_null() {
+        return Ok(Vec::new());
+    }
+
+    let bytes = unsafe { std::slice::from_raw_parts(value as *const u8, len as usize) }.to_vec();
+    unsafe { libc::free(value as *mut c_void) };
+
+    Ok(bytes)
+}
+
+fn read_value<F>(handle: bt_gatt_h, callback_fn: F) -> Result<()>
+where
+    F: FnOnce(Result<Vec<u8>>),
+    F: 'static,
+{
+    let callback_fn: Box<RequestFn> = Box::new(callback_fn);
+    let data = Box::into_raw(Box::new(callback_fn));
+
+    let ret = unsafe {
+        bt_gatt_client_read_value(handle, Some(read_completed_handler), data as *mut c_void)
+    };
+
+    if let Err(e) = check(ret) {
+        drop(unsafe { Box::from_raw(data) });
+        return Err(e);
+    }
+
+    Ok(())
+}
+
+fn write_value<F>(handle: bt_gatt_h, value: &[u8], callback_fn: F) -> Result<()>
+where
+    F: FnOnce(Result<()>),
+    F: 'static,
+{
+    check(unsafe {
+        bt_gatt_set_value(
+            handle,
+            value.as_ptr() as *const c_char,
+            value.len() as c_int,
+        )
+    })?;
+
+    let callback_fn: Box<WriteFn> = Box::new(callback_fn);
+    let data = Box::into_raw(Box::new(callback_fn));
+
+    let ret = unsafe {
+        bt_gatt_client_write_value(handle, Some(write_completed_handler), data as *mut c_void)
+    };
+
+    if let Err(e) = check(ret) {
+        drop(unsafe { Box::from_raw(data) });
+        return Err(e);
+    }
+
+    Ok(())
+}
+
+pub struct GattService<'c> {
+    handle: bt_gatt_h,
+    _client: PhantomData<&'c GattClient>,
+}
+
+impl<'c> GattService<'c> {
+    fn from_handle(handle: bt_gatt_h) -> GattService<'c> {
+        Self {
+            handle,
+            _client: PhantomData,
+        }
+    }
+
+    pub fn uuid(&self) -> Result<String> {
+        get_uuid(self.handle)
+    }
+
+    pub fn characteristics(&self) -> Result<Vec<GattCharacteristic<'c>>> {
+        let handles = collect_handles(|cb, data| unsafe {
+            bt_gatt_service_foreach_characteristics(self.handle, cb, data)
+        })?;
+
+        Ok(handles
+            .into_iter()
+            .map(GattCharacteristic::from_handle)
+            .collect())
+    }
+
+    pub fn get_characteristic(&self, uuid: &str) -> Result<GattCharacteristic<'c>> {
+        let uuid = cstring(uuid)?;
+        let mut handle = null_mut();
+
+        check(unsafe {
+            bt_gatt_service_get_characteristic(self.handle, uuid.as_ptr(), &mut handle)
+        })?;
+
+        Ok(GattCharacteristic::from_handle(handle))
+    }
+}
+
+pub struct GattCharacteristic<'c> {
+    handle: bt_gatt_h,
+    _client: PhantomData<&'c GattClient>,
+}
+
+impl<'c> GattCharacteristic<'c> {
+    fn from_handle(handle: bt_gatt_h) -> GattCharacteristic<'c> {
+        Self {
+            handle,
+            _client: PhantomData,
+        }
+    }
+
+    pub fn uuid(&self) -> Result<String> {
+        get_uuid(self.handle)
+    }
+
+    /// Returns the [`property`] flags of the characteristic.
+    pub fn properties(&self) -> Result<i32> {
+        let mut properties = 0;
+
+        check(unsafe { bt_gatt_characteristic_get_properties(self.handle, &mut properties) })?;
+
+        Ok(properties)
+    }
+
+    pub fn descriptors(&self) -> Result<Vec<GattDescriptor<'c>>> {
+        let handles = collect_handles(|cb, data| unsafe {
+            bt_gatt_characteristic_foreach_descriptors(self.handle, cb, data)
+        })?;
+
+        Ok(handles
+            .into_iter()
+            .map(GattDescriptor::from_handle)
+            .collect())
+    }
+
+    /// Returns the value cached by the last read or notification.
+    pub fn value(&self) -> Result<Vec<u8>> {
+        get_value(self.handle)
+    }
+
+    /// Reads the value from the remote device, `callback_fn` is called with the result.
+    pub fn read<F>(&self, callback_fn: F) -> Result<()>
+    where
+        F: FnOnce(Result<Vec<u8>>),
+        F: 'static,
+    {
+        read_value(self.handle, callback_fn)
+    }
+
+    pub fn set_write_type(&mut self, write_type: WriteType) -> Result<()> {
+        check(unsafe { bt_gatt_characteristic_set_write_type(self.handle, write_type.into()) })
+    }
+
+    /// Writes the value to the remote device, `callback_fn` is called with the result.
+    pub fn write<F>(&mut self, value: &[u8], callback_fn: F) -> Result<()>
+    where
+        F: FnOnce(Result<()>),
+        F: 'static,
+    {
+        write_value(self.handle, value, callback_fn)
+    }
+
+    /// Enables notifications or indications, calling `callback_fn` with each new
+    /// value until the returned subscription is dropped.
+    pub fn subscribe<F>(&mut self, callback_fn: F) -> Result<GattSubscription<'c>>
+    where
+        F: FnMut(&[u8]),
+        F: 'static,
+    {
+        let (callback_fn, data) = pin_callback::<dyn FnMut(&[u8])>(Box::new(callback_fn));
+
+        check(unsafe {
+            bt_gatt_client_set_characteristic_value_changed_cb(
+                self.handle,
+                Some(value_changed_handler),
+                data,
+            )
+        })?;
+
+        Ok(GattSubscription {
+            handle: self.handle,
+            _callback_fn: callback_fn,
+            _client: PhantomData,
+        })
+    }
+}
+
+/// Notifications of a characteristic, disabled when this is dropped.
+pub struct GattSubscription<'c> {
+    handle: bt_gatt_h,
+    _callback_fn: Pin<Box<Box<dyn FnMut(&[u8])>>>,
+    _client: PhantomData<&'c GattClient>,
+}
+
+impl<'c> Drop for GattSubscription<'c> {
+    fn drop(&mut self) {
+        unsafe {
+            bt_gatt_client_unset_characteristic_value_changed_cb(self.handle);
+        }
+    }
+}
+
+pub struct GattDescriptor<'c> {
+    handle: bt_gatt_h,
+    _client: PhantomData<&'c GattClient>,
+}
+
+impl<'c> GattDescriptor<'c> {
+    fn from_handle(handle: bt_gatt_h) -> GattDescriptor<'c> {
+        Self {
+            handle,
+            _client: PhantomData,
+        }
+    }
+
+    pub fn uuid(&self) -> Result<String> {
+        get_uuid(self.handle)
+    }
+
+    pub fn value(&self) -> Result<Vec<u8>> {
+        get_value(self.handle)
+    }
+
+    pub fn read<F>(&self, callback_fn: F) -> Result<()>
+    where
+        F: FnOnce(Result<Vec<u8>>),
+        F: 'static,
+    {
+        read_value(self.handle, callback_fn)
+    }
+
+    pub fn write<F>(&mut self, value: &[u8], callback_fn: F) -> Result<()>
+    where
+        F: FnOnce(Result<()>),
+        F: 'static,
+    {
+        write_value(self.handle, value, callback_fn)
+    }
+}
+
+fn collect_handles<F>(foreach: F) -> Result<Vec<bt_gatt_h>>
+where
+    F: FnOnce(bt_gatt_foreach_cb, *mut c_void) -> c_int,
+{
+    let mut handles: Vec<bt_gatt_h> = Vec::new();
+
+    check(foreach(
+        Some(foreach_handler),
+        &mut handles as *mut Vec<bt_gatt_h> as *mut c_void,
+    ))?;
+
+    Ok(handles)
+}
+
+unsafe extern "C" fn foreach_handler(
+    _total: c_int,
+    _index: c_int,
+    gatt_handle: bt_gatt_h,
+    user_data: *mut c_void,
+) -> bool {
+    let handles = &mut *(user_data as *mut Vec<bt_gatt_h>);
+    handles.push(gatt_handle);
+
+    true
+}
+
+type RequestFn = dyn FnOnce(Result<Vec<u8>>);
+type WriteFn = dyn FnOnce(Result<()>);
+
+extern "C" fn read_completed_handler(
+    result: c_int,
+    request_handle: bt_gatt_h,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let closure = unsafe { Box::from_raw(user_data as *mut Box<RequestFn>) };
+        closure(check(result).and_then(|_| get_value(request_handle)));
+    })
+}
+
+extern "C" fn write_completed_handler(
+    result: c_int,
+    _request_handle: bt_gatt_h,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let closure = unsafe { Box::from_raw(user_data as *mut Box<WriteFn>) };
+        closure(check(result));
+    })
+}
+
+extern "C" fn value_changed_handler(
+    _characteristic: bt_gatt_h,
+    value: *mut c_char,
+    len: c_int,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut Box<dyn FnMut(&[u8])>) };
+        let value = if value.is_null() {
+            &[][..]
+        } else {
+            unsafe { std::slice::from_raw_parts(value as *const u8, len as usize) }
+        };
+
+        closure(value);
+    })
+}
+
+extern "C" fn connection_state_handler(
+    result: c_int,
+    connected: bool,
+    remote_address: *const c_char,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut ConnectionStateFn) };
+        let remote_address = unsafe { borrow_str(remote_address) }.unwrap_or_default();
+
+        closure(check(result), connected, &remote_address);
+    })
+}
@@ -0,0 +1,162 @@
+use crate::error::{check, Result};
+use crate::rutin::{abort_on_panic, borrow_str, cstring, pin_callback};
+use rutin_tizen_sys::{
+    bt_hid_device_activate, bt_hid_device_connect, bt_hid_device_deactivate,
+    bt_hid_device_disconnect, bt_hid_device_send_key_event, bt_hid_device_send_mouse_event,
+    bt_hid_host_connect, bt_hid_host_deinitialize, bt_hid_host_disconnect, bt_hid_host_initialize,
+    bt_hid_key_data_s, bt_hid_mouse_data_s,
+};
+use std::os::raw::{c_char, c_int, c_void};
+use std::pin::Pin;
+
+type ConnectionStateFn = Box<dyn FnMut(Result<()>, bool, &str)>;
+
+/// HID host, to use remote keyboards and mice.
+///
+/// Deinitialized when dropped, [`super::initialize`] must have been called first.
+pub struct HidHost {
+    _connection_state_fn: Pin<Box<ConnectionStateFn>>,
+}
+
+impl HidHost {
+    /// `callback_fn` is called with the result, the connection state and the
+    /// remote address each time a device connects or disconnects.
+    pub fn new<F>(callback_fn: F) -> Result<HidHost>
+    where
+        F: FnMut(Result<()>, bool, &str),
+        F: 'static,
+    {
+        let (callback_fn, data) =
+            pin_callback::<dyn FnMut(Result<()>, bool, &str)>(Box::new(callback_fn));
+
+        check(unsafe { bt_hid_host_initialize(Some(connection_state_handler), data) })?;
+
+        Ok(Self {
+            _connection_state_fn: callback_fn,
+        })
+    }
+
+    pub fn connect(&mut self, remote_address: &str) -> Result<()> {
+        let remote_address = cstring(remote_address)?;
+
+        check(unsafe { bt_hid_host_connect(remote_address.as_ptr()) })
+    }
+
+    pub fn disconnect(&mut self, remote_address: &str) -> Result<()> {
+        let remote_address = cstring(remote_address)?;
+
+        check(unsafe { bt_hid_host_disconnect(remote_address.as_ptr()) })
+    }
+}
+
+impl Drop for HidHost {
+    fn drop(&mut self) {
+        unsafe {
+            bt_hid_host_deinitialize();
+        }
+    }
+}
+
+/// Relative pointer movement and pressed buttons (bit 0 left, 1 right, 2 middle).
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MouseEvent {
+    pub buttons: u8,
+    pub dx: i8,
+    pub dy: i8,
+}
+
+/// A boot protocol keyboard report: modifier bits and up to 8 pressed usage ids.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KeyEvent {
+    pub modifier: u8,
+    pub keys: [u8; 8],
+}
+
+/// HID device role, letting the watch act as a remote keyboard or mouse.
+///
+/// Deactivated when dropped, [`super::initialize`] must have been called first.
+pub struct HidDevice {
+    _connection_state_fn: Pin<Box<ConnectionStateFn>>,
+}
+
+impl HidDevice {
+    /// `callback_fn` is called with the result, the connection state and the
+    /// remote address each time a host connects or disconnects.
+    pub fn activate<F>(callback_fn: F) -> Result<HidDevice>
+    where
+        F: FnMut(Result<()>, bool, &str),
+        F: 'static,
+    {
+        let (callback_fn, data) =
+            pin_callback::<dyn FnMut(Result<()>, bool, &str)>(Box::new(callback_fn));
+
+        check(unsafe { bt_hid_device_activate(Some(connection_state_handler), data) })?;
+
+        Ok(Self {
+            _connection_state_fn: callback_fn,
+        })
+    }
+
+    pub fn connect(&mut self, remote_address: &str) -> Result<()> {
+        let remote_address = cstring(remote_address)?;
+
+        check(unsafe { bt_hid_device_connect(remote_address.as_ptr()) })
+    }
+
+    pub fn disconnect(&mut self, remote_address: &str) -> Result<()> {
+        let remote_address = cstring(remote_address)?;
+
+        check(unsafe { bt_hid_device_disconnect(remote_address.as_ptr()) })
+    }
+
+    pub fn send_mouse_event(&mut self, remote_address: &str, event: MouseEvent) -> Result<()> {
+        let remote_address = cstring(remote_address)?;
+        let mouse_data = bt_hid_mouse_data_s {
+            buttons: event.buttons,
+            axis_x: event.dx,
+            axis_y: event.dy,
+            padding: 0,
+        };
+
+        let sent = unsafe { bt_hid_device_send_mouse_event(remote_address.as_ptr(), &mouse_data) };
+
+        // returns the number of bytes sent on success
+        check(sent.min(0))
+    }
+
+    pub fn send_key_event(&mut self, remote_address: &str, event: KeyEvent) -> Result<()> {
+        let remote_address = cstring(remote_address)?;
+        let key_data = bt_hid_key_data_s {
+            modifier: event.modifier,
+            key: event.keys,
+        };
+
+        let sent = unsafe { bt_hid_device_send_key_event(remote_address.as_ptr(), &key_data) };
+
+        check(sent.min(0))
+    }
+}
+
+impl Drop for HidDevice {
+    fn drop(&mut self) {
+        unsafe {
+            bt_hid_device_deactivate();
+        }
+    }
+}
+
+extern "C" fn connection_state_handler(
+    result: c_int,
+    connected: bool,
+    remote_address: *const c_char,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut ConnectionStateFn) };
+        let remote_address = unsafe { borrow_str(remote_address) }.unwrap_or_default();
+
+        closure(check(result), connected, &remote_address);
+    })
+}
@@ -0,0 +1,556 @@
+use crate::error::{check, Result};
+use crate::rutin::{abort_on_panic, borrow_str, cstring, pin_callback};
+use rutin_tizen_sys::{
+    bt_adapter_le_add_advertising_manufacturer_data, bt_adapter_le_add_advertising_service_data,
+    bt_adapter_le_add_advertising_service_uuid, bt_adapter_le_advertising_mode_e,
+    bt_adapter_le_advertising_mode_e_BT_ADAPTER_LE_ADVERTISING_MODE_BALANCED,
+    bt_adapter_le_advertising_mode_e_BT_ADAPTER_LE_ADVERTISING_MODE_LOW_ENERGY,
+    bt_adapter_le_advertising_mode_e_BT_ADAPTER_LE_ADVERTISING_MODE_LOW_LATENCY,
+    bt_adapter_le_advertising_state_e,
+    bt_adapter_le_advertising_state_e_BT_ADAPTER_LE_ADVERTISING_STARTED,
+    bt_adapter_le_clear_advertising_data, bt_adapter_le_create_advertiser,
+    bt_adapter_le_destroy_advertiser, bt_adapter_le_device_scan_result_info_s,
+    bt_adapter_le_packet_type_e, bt_adapter_le_packet_type_e_BT_ADAPTER_LE_PACKET_ADVERTISING,
+    bt_adapter_le_packet_type_e_BT_ADAPTER_LE_PACKET_SCAN_RESPONSE,
+    bt_adapter_le_set_advertising_connectable, bt_adapter_le_set_advertising_device_name,
+    bt_adapter_le_set_advertising_mode, bt_adapter_le_set_advertising_tx_power_level,
+    bt_adapter_le_start_advertising_new, bt_adapter_le_start_scan, bt_adapter_le_stop_advertising,
+    bt_adapter_le_stop_scan, bt_advertiser_h,
+};
+use std::os::raw::{c_char, c_int, c_void};
+use std::pin::Pin;
+use std::ptr::null_mut;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PacketType {
+    Advertising,
+    ScanResponse,
+}
+
+impl From<PacketType> for bt_adapter_le_packet_type_e {
+    fn from(packet_type: PacketType) -> bt_adapter_le_packet_type_e {
+        match packet_type {
+            PacketType::Advertising => bt_adapter_le_packet_type_e_BT_ADAPTER_LE_PACKET_ADVERTISING,
+            PacketType::ScanResponse => {
+                bt_adapter_le_packet_type_e_BT_ADAPTER_LE_PACKET_SCAN_RESPONSE
+            }
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AdvertisingMode {
+    Balanced,
+    LowLatency,
+    LowEnergy,
+}
+
+impl From<AdvertisingMode> for bt_adapter_le_advertising_mode_e {
+    fn from(mode: AdvertisingMode) -> bt_adapter_le_advertising_mode_e {
+        match mode {
+            AdvertisingMode::Balanced => {
+                bt_adapter_le_advertising_mode_e_BT_ADAPTER_LE_ADVERTISING_MODE_BALANCED
+            }
+            AdvertisingMode::LowLatency => {
+                bt_adapter_le_advertising_mode_e_BT_ADAPTER_LE_ADVERTISING_MODE_LOW_LATENCY
+            }
+            AdvertisingMode::LowEnergy => {
+                bt_adapter_le_advertising_mode_e_BT_ADAPTER_LE_ADVERTISING_MODE_LOW_ENERGY
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ServiceData {
+    pub uuid: String,
+    pub data: Vec<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ManufacturerData {
+    /// Bluetooth SIG company identifier.
+    pub company_id: u16,
+    pub data: Vec<u8>,
+}
+
+/// The content of an advertising or scan response packet to send.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AdvertiseData {
+    service_uuids: Vec<String>,
+    service_data: Vec<ServiceData>,
+    manufacturer_data: Vec<ManufacturerData>,
+    include_device_name: bool,
+    include_tx_power_level: bool,
+}
+
+impl AdvertiseData {
+    pub fn new() -> AdvertiseData {
+        Self::default()
+    }
+
+    /// Adds a 16-bit ("180D") or 128-bit service UUID.
+    pub fn service_uuid(mut self, uuid: &str) -> AdvertiseData {
+        self.service_uuids.push(uuid.to_string());
+        self
+    }
+
+    pub fn service_data(mut self, uuid: &str, data: &[u8]) -> AdvertiseData {
+        self.service_data.push(ServiceData {
+            uuid: uuid.to_string(),
+            data: data.to_vec(),
+        });
+        self
+    }
+
+    pub fn manufacturer_data(mut self, company_id: u16, data: &[u8]) -> AdvertiseData {
+        self.manufacturer_data.push(ManufacturerData {
+            company_id,
+            data: data.to_vec(),
+        });
+        self
+    }
+
+    pub fn include_device_name(mut self, include: bool) -> AdvertiseData {
+        self.include_device_name = include;
+        self
+    }
+
+    pub fn include_tx_power_level(mut self, include: bool) -> AdvertiseData {
+        self.include_tx_power_level = include;
+        self
+    }
+}
+
+type AdvertisingStateFn = Box<dyn FnMut(Result<bool>)>;
+
+/// An LE advertising set, stopped and destroyed when this is dropped.
+pub struct Advertiser {
+    handle: bt_advertiser_h,
+    state_fn: Option<Pin<Box<AdvertisingStateFn>>>,
+}
+
+impl Advertiser {
+    pub fn new() -> Result<Advertiser> {
+        let mut handle = null_mut();
+
+        check(unsafe { bt_adapter_le_create_advertiser(&mut handle) })?;
+
+        Ok(Self {
+            handle,
+            state_fn: None,
+        })
+    }
+
+    /// Replaces the content of a packet.
+    pub fn set_data(&mut self, packet_type: PacketType, data: &AdvertiseData) -> Result<()> {
+        let pkt = packet_type.into();
+
+        check(unsafe { bt_adapter_le_clear_advertising_data(self.handle, pkt) })?;
+
+        for uuid in &data.service_uuids {
+            let uuid = cstring(uuid)?;
+            check(unsafe {
+                bt_adapter_le_add_advertising_service_uuid(self.handle, pkt, uuid.as_ptr())
+            })?;
+        }
+
+        for service_data in &data.service_data {
+            let uuid = cstring(&service_data.uuid)?;
+            check(unsafe {
+                bt_adapter_le_add_advertising_service_data(
+                    self.handle,
+                    pkt,
+                    uuid.as_ptr(),
+                    service_data.data.as_ptr() as *const c_char,
+                    service_data.data.len() as c_int,
+                )
+            })?;
+        }
+
+        for manufacturer_data in &data.manufacturer_data {
+            check(unsafe {
+                bt_adapter_le_add_advertising_manufacturer_data(
+                    self.handle,
+                    pkt,
+                    manufacturer_data.company_id as c_int,
+                    manufacturer_data.data.as_ptr() as *const c_char,
+                    manufacturer_data.data.len() as c_int,
+                )
+            })?;
+        }
+
+        check(unsafe {
+            bt_adapter_le_set_advertising_device_name(self.handle, pkt, data.include_device_name)
+        })?;
+        check(unsafe {
+            bt_adapter_le_set_advertising_tx_power_level(
+                self.handle,
+                pkt,
+                data.include_tx_power_level,
+            )
+        })
+    }
+
+    pub fn set_mode(&mut self, mode: AdvertisingMode) -> Result<()> {
+        check(unsafe { bt_adapter_le_set_advertising_mode(self.handle, mode.into()) })
+    }
+
+    pub fn set_connectable(&mut self, connectable: bool) -> Result<()> {
+        check(unsafe { bt_adapter_le_set_advertising_connectable(self.handle, connectable) })
+    }
+
+    /// Starts advertising, `callback_fn` is called with whether advertising
+    /// is active each time its state changes.
+    pub fn start<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(Result<bool>),
+        F: 'static,
+    {
+        let (callback_fn, data) = pin_callback::<dyn FnMut(Result<bool>)>(Box::new(callback_fn));
+
+        check(unsafe {
+            bt_adapter_le_start_advertising_new(self.handle, Some(advertising_state_handler), data)
+        })?;
+        self.state_fn = Some(callback_fn);
+
+        Ok(())
+    }
+
+    pub fn stop(&mut self) -> Result<()> {
+        check(unsafe { bt_adapter_le_stop_advertising(self.handle) })
+    }
+}
+
+impl Drop for Advertiser {
+    fn drop(&mut self) {
+        unsafe {
+            if self.state_fn.is_some() {
+                bt_adapter_le_stop_advertising(self.handle);
+            }
+            bt_adapter_le_destroy_advertiser(self.handle);
+        }
+    }
+}
+
+/// A parsed advertising or scan response packet.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Advertisement {
+    pub flags: Option<u8>,
+    pub local_name: Option<String>,
+    pub tx_power_level: Option<i8>,
+    pub service_uuids: Vec<String>,
+    pub service_data: Vec<ServiceData>,
+    pub manufacturer_data: Vec<ManufacturerData>,
+}
+
+fn format_uuid(bytes: &[u8]) -> String {
+    // AD fields are little endian
+    let hex = bytes
+        .iter()
+        .rev()
+        .map(|b| format!("{:02X}", b))
+        .collect::<String>();
+
+    if hex.len() == 32 {
+        format!(
+            "{}-{}-{}-{}-{}",
+            &hex[0..8],
+            &hex[8..12],
+            &hex[12..16],
+            &hex[16..20],
+            &hex[20..32]
+        )
+    } else {
+        hex
+    }
+}
+
+impl Advertisement {
+    /// Parses the AD structures of a raw packet, skipping unknown and malformed ones.
+    pub fn parse(data: &[u8]) -> Advertisement {
+        let mut advertisement = Advertisement::default();
+        let mut rest = data;
+
+        while let Some((&len, tail)) = rest.split_first() {
+            let len = len as usize;
+
+            if len == 0 || len > tail.len() {
+                break;
+            }
+
+            let (field, tail) = tail.split_at(len);
+            rest = tail;
+            let (ad_type, value) = (field[0], &field[1..]);
+
+            match ad_type {
+                0x01 if !value.is_empty() => advertisement.flags = Some(value[0]),
+                0x02 | 0x03 => advertisement
+                    .service_uuids
+                    .extend(value.chunks_exact(2).map(format_uuid)),
+                0x04 | 0x05 => advertisement
+                    .service_uuids
+                    .extend(value.chunks_exact(4).map(format_uuid)),
+                0x06 | 0x07 => advertisement
+                    .service_uuids
+                    .extend(value.chunks_exact(16).map(format_uuid)),
+                0x08 | 0x09 => {
+                    advertisement.local_name = Some(String::from_utf8_lossy(value).into_owned())
+                }
+                0x0a if !value.is_empty() => advertisement.tx_power_level = Some(value[0] as i8),
+                0x16 | 0x20 | 0x21 => {
+                    let uuid_len = match ad_type {
+                        0x16 => 2,
+                        0x20 => 4,
+                        _ => 16,
+                    };
+
+                    if value.len() >= uuid_len {
+                        advertisement.service_data.push(ServiceData {
+                            uuid: format_uuid(&value[..uuid_len]),
+                            data: value[uuid_len..].to_vec(),
+                        });
+                    }
+                }
+                0xff if value.len() >= 2 => {
+                    advertisement.manufacturer_data.push(ManufacturerData {
+                        company_id: u16::from_le_bytes([value[0], value[1]]),
+                        data: value[2..].to_vec(),
+                    })
+                }
+                _ => {}
+            }
+        }
+
+        advertisement
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScanResult {
+    pub remote_address: String,
+    pub rssi: i32,
+    pub advertisement: Advertisement,
+    pub scan_response: Advertisement,
+}
+
+type ScanResultFn = Box<dyn FnMut(Result<ScanResult>)>;
+
+/// A running LE scan, stopped when this is dropped.
+pub struct Scan {
+    _callback_fn: Pin<Box<ScanResultFn>>,
+}
+
+impl Drop for Scan {
+    fn drop(&mut self) {
+        unsafe {
+            bt_adapter_le_stop_scan();
+        }
+    }
+}
+
+/// Starts scanning for advertising devices, calling `callback_fn` with each report.
+pub fn start_scan<F>(callback_fn: F) -> Result<Scan>
+where
+    F: FnMut(Result<ScanResult>),
+    F: 'static,
+{
+    let (callback_fn, data) = pin_callback::<dyn FnMut(Result<ScanResult>)>(Box::new(callback_fn));
+
+    check(unsafe { bt_adapter_le_start_scan(Some(scan_result_handler), data) })?;
+
+    Ok(Scan {
+        _callback_fn: callback_fn,
+    })
+}
+
+unsafe fn packet<'p>(data: *const c_char, len: c_int) -> &'p [u8] {
+    if data.is_null() || len <= 0 {
+        &[]
+    } else {
+        std::slice::from_raw_parts(data as *const u8, len as usize)
+    }
+}
+
+extern "C" fn scan_result_handler(
+    result: c_int,
+    info: *mut bt_adapter_le_device_scan_result_info_s,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut ScanResultFn) };
+
+        if let Err(e) = check(result) {
+            return closure(Err(e));
+        }
+
+        // a successful result without a device carries nothing to report
+        let info = match unsafe { info.as_ref() } {
+            Some(info) => info,
+            None => return,
+        };
+
+        closure(Ok(ScanResult {
+            remote_address: unsafe { borrow_str(info.remote_address) }
+                .unwrap_or_default()
+                .into_owned(),
+            rssi: info.rssi,
+            advertisement: Advertisement::parse(unsafe {
+                packet(info.adv_data, info.adv_data_len)
+            }),
+            scan_response: Advertisement::parse(unsafe {
+                packet(info.scan_data, info.scan_data_len)
+            }),
+        }));
+    })
+}
+
+extern "C" fn advertising_state_handler(
+    result: c_int,
+    _advertiser: bt_advertiser_h,
+    adv_state: bt_adapter_le_advertising_state_e,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut AdvertisingStateFn) };
+
+        closure(check(result).map(|_| {
+            adv_state == bt_adapter_le_advertising_state_e_BT_ADAPTER_LE_ADVERTISING_STARTED
+        }));
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Encodes AD structures, each one as its length, type and value.
+    fn packet(fields: &[(u8, &[u8])]) -> Vec<u8> {
+        let mut data = Vec::new();
+
+        for (ad_type, value) in fields {
+            data.push(value.len() as u8 + 1);
+            data.push(*ad_type);
+            data.extend_from_slice(value);
+        }
+
+        data
+    }
+
+    #[test]
+    fn parse_fields() {
+        let uuid_128 = [
+            0xfb, 0x34, 0x9b, 0x5f, 0x80, 0x00, 0x00, 0x80, 0x00, 0x10, 0x00, 0x00, 0x0d, 0x18,
+            0x00, 0x00,
+        ];
+        let data = packet(&[
+            (0x01, &[0x06]),
+            (0x09, b"Watch"),
+            (0x0a, &[0xf4]),
+            (0x03, &[0x0d, 0x18, 0x0f, 0x18]),
+            (0x05, &[0x78, 0x56, 0x34, 0x12]),
+            (0x07, &uuid_128),
+            (0x16, &[0x0f, 0x18, 0x64]),
+            (0xff, &[0x75, 0x00, 0x01, 0x02]),
+        ]);
+
+        assert_eq!(
+            Advertisement::parse(&data),
+            Advertisement {
+                flags: Some(0x06),
+                local_name: Some("Watch".to_string()),
+                tx_power_level: Some(-12),
+                service_uuids: vec![
+                    "180D".to_string(),
+                    "180F".to_string(),
+                    "12345678".to_string(),
+                    "0000180D-0000-1000-8000-00805F9B34FB".to_string(),
+                ],
+                service_data: vec![ServiceData {
+                    uuid: "180F".to_string(),
+                    data: vec![0x64],
+                }],
+                manufacturer_data: vec![ManufacturerData {
+                    company_id: 0x0075,
+                    data: vec![0x01, 0x02],
+                }],
+            }
+        );
+    }
+
+    #[test]
+    fn parse_empty() {
+        assert_eq!(Advertisement::parse(&[]), Advertisement::default());
+    }
+
+    #[test]
+    fn parse_stops_at_zero_length() {
+        // the rest of a packet is padded with zeros
+        let mut data = packet(&[(0x01, &[0x06])]);
+        data.extend_from_slice(&[0, 0, 0x02, 0x0a, 0x04]);
+
+        assert_eq!(
+            Advertisement::parse(&data),
+            Advertisement {
+                flags: Some(0x06),
+                ..Advertisement::default()
+            }
+        );
+    }
+
+    #[test]
+    fn parse_truncated() {
+        // the last structure announces more bytes than the packet has
+        let mut data = packet(&[(0x09, b"Watch")]);
+        data.extend_from_slice(&[0x05, 0xff, 0x75, 0x00]);
+
+        let advertisement = Advertisement::parse(&data);
+
+        assert_eq!(advertisement.local_name.as_deref(), Some("Watch"));
+        assert!(advertisement.manufacturer_data.is_empty());
+
+        // a lone length byte
+        assert_eq!(Advertisement::parse(&[0x03]), Advertisement::default());
+    }
+
+    #[test]
+    fn parse_short_values() {
+        let data = packet(&[
+            (0x01, &[]),
+            (0x0a, &[]),
+            (0x09, &[]),
+            // the odd byte isn't a whole UUID
+            (0x03, &[0x0d, 0x18, 0x0f]),
+            (0x16, &[0x0f]),
+            (0xff, &[0x75]),
+            // unknown type
+            (0x42, &[1, 2, 3]),
+        ]);
+
+        assert_eq!(
+            Advertisement::parse(&data),
+            Advertisement {
+                local_name: Some(String::new()),
+                service_uuids: vec!["180D".to_string()],
+                ..Advertisement::default()
+            }
+        );
+    }
+
+    #[test]
+    fn parse_invalid_name() {
+        let data = packet(&[(0x08, &[b'W', 0xff, b'!'])]);
+
+        assert_eq!(
+            Advertisement::parse(&data).local_name.as_deref(),
+            Some("W\u{fffd}!")
+        );
+    }
+}
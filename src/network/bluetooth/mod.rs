@@ -0,0 +1,21 @@
+pub mod audio;
+pub mod gatt;
+pub mod hid;
+pub mod le;
+pub mod opp;
+pub mod socket;
+
+use crate::error::{check, Result};
+use rutin_tizen_sys::{bt_deinitialize, bt_initialize};
+
+/// Initializes the Bluetooth API, required before using any of its modules.
+///
+/// Requires the `http://tizen.org/privilege/bluetooth` privilege.
+pub fn initialize() -> Result<()> {
+    check(unsafe { bt_initialize() })
+}
+
+/// Releases the resources taken by [`initialize`].
+pub fn deinitialize() -> Result<()> {
+    check(unsafe { bt_deinitialize() })
+}
@@ -0,0 +1,126 @@
+use crate::error::{check, Result};
+use crate::rutin::{abort_on_panic, borrow_str, cstring, path_cstring, pin_callback};
+use rutin_tizen_sys::{
+    bt_opp_client_add_file, bt_opp_client_cancel_push, bt_opp_client_clear_files,
+    bt_opp_client_deinitialize, bt_opp_client_initialize, bt_opp_client_push_files,
+};
+use std::os::raw::{c_char, c_int, c_longlong, c_void};
+use std::path::Path;
+use std::pin::Pin;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PushEvent {
+    /// The remote device accepted or rejected the push.
+    Responded(Result<()>),
+    Progress {
+        file: String,
+        size: i64,
+        percent: i32,
+    },
+    Finished(Result<()>),
+}
+
+type PushEventFn = Box<dyn FnMut(PushEvent)>;
+
+/// Object push profile client, to send files to a remote device.
+///
+/// Deinitialized when dropped, [`super::initialize`] must have been called first.
+pub struct OppClient {
+    push_fn: Option<Pin<Box<PushEventFn>>>,
+}
+
+impl OppClient {
+    pub fn new() -> Result<OppClient> {
+        check(unsafe { bt_opp_client_initialize() })?;
+
+        Ok(Self { push_fn: None })
+    }
+
+    /// Adds a file to send with the next push.
+    pub fn add_file(&mut self, file: &Path) -> Result<()> {
+        let file = path_cstring(file)?;
+
+        check(unsafe { bt_opp_client_add_file(file.as_ptr()) })
+    }
+
+    pub fn clear_files(&mut self) -> Result<()> {
+        check(unsafe { bt_opp_client_clear_files() })
+    }
+
+    /// Sends the added files, reporting the progress to `callback_fn`.
+    pub fn push_files<F>(&mut self, remote_address: &str, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(PushEvent),
+        F: 'static,
+    {
+        let remote_address = cstring(remote_address)?;
+        let (callback_fn, data) = pin_callback::<dyn FnMut(PushEvent)>(Box::new(callback_fn));
+
+        check(unsafe {
+            bt_opp_client_push_files(
+                remote_address.as_ptr(),
+                Some(push_responded_handler),
+                Some(push_progress_handler),
+                Some(push_finished_handler),
+                data,
+            )
+        })?;
+        self.push_fn = Some(callback_fn);
+
+        Ok(())
+    }
+
+    pub fn cancel_push(&mut self) -> Result<()> {
+        check(unsafe { bt_opp_client_cancel_push() })
+    }
+}
+
+impl Drop for OppClient {
+    fn drop(&mut self) {
+        unsafe {
+            bt_opp_client_deinitialize();
+        }
+    }
+}
+
+fn dispatch(user_data: *mut c_void, event: PushEvent) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut PushEventFn) };
+        closure(event);
+    })
+}
+
+extern "C" fn push_responded_handler(
+    result: c_int,
+    _remote_address: *const c_char,
+    user_data: *mut c_void,
+) {
+    dispatch(user_data, PushEvent::Responded(check(result)))
+}
+
+extern "C" fn push_progress_handler(
+    file: *const c_char,
+    size: c_longlong,
+    percent: c_int,
+    user_data: *mut c_void,
+) {
+    let file = unsafe { borrow_str(file) }.unwrap_or_default().into_owned();
+
+    dispatch(
+        user_data,
+        PushEvent::Progress {
+            file,
+            size,
+            percent,
+        },
+    )
+}
+
+extern "C" fn push_finished_handler(
+    result: c_int,
+    _remote_address: *const c_char,
+    user_data: *mut c_void,
+) {
+    dispatch(user_data, PushEvent::Finished(check(result)))
+}
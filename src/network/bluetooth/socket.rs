@@ -0,0 +1,285 @@
+use crate::error::{check, check_count, Error, Result};
+use crate::rutin::{abort_on_panic, borrow_str, cstring};
+use rutin_tizen_sys::{
+    bt_socket_connect_rfcomm, bt_socket_connection_s, bt_socket_connection_state_e,
+    bt_socket_connection_state_e_BT_SOCKET_CONNECTED, bt_socket_create_rfcomm,
+    bt_socket_destroy_rfcomm, bt_socket_disconnect_rfcomm, bt_socket_listen_and_accept_rfcomm,
+    bt_socket_received_data_s, bt_socket_role_e_BT_SOCKET_SERVER, bt_socket_send_data,
+    bt_socket_set_connection_state_changed_cb, bt_socket_set_data_received_cb,
+    bt_socket_unset_connection_state_changed_cb, bt_socket_unset_data_received_cb,
+};
+use std::cell::RefCell;
+use std::collections::{HashMap, VecDeque};
+use std::io;
+use std::os::raw::{c_char, c_int, c_void};
+use std::rc::Rc;
+
+pub enum SocketEvent {
+    /// A client connected to one of our listeners, or our connection to a server succeeded.
+    Connected(SppConnection),
+    Disconnected {
+        socket_fd: i32,
+    },
+    /// A connection attempt to a server failed.
+    ConnectFailed(Error),
+}
+
+#[derive(Default)]
+struct Shared {
+    buffers: HashMap<c_int, VecDeque<u8>>,
+    event_fn: Option<Box<dyn FnMut(SocketEvent)>>,
+    data_fn: Option<Box<dyn FnMut(i32, &[u8])>>,
+}
+
+/// Serial port profile (RFCOMM) sockets.
+///
+/// The platform reports connections and received data through process wide
+/// callbacks, so only one `Spp` should exist at a time.
+pub struct Spp {
+    shared: Rc<RefCell<Shared>>,
+}
+
+impl Spp {
+    pub fn new() -> Result<Spp> {
+        let shared = Rc::new(RefCell::new(Shared::default()));
+        let data = Rc::as_ptr(&shared) as *mut c_void;
+
+        check(unsafe { bt_socket_set_data_received_cb(Some(data_received_handler), data) })?;
+
+        if let Err(e) = check(unsafe {
+            bt_socket_set_connection_state_changed_cb(Some(connection_state_handler), data)
+        }) {
+            unsafe { bt_socket_unset_data_received_cb() };
+            return Err(e);
+        }
+
+        Ok(Self { shared })
+    }
+
+    /// Calls `callback_fn` on connections and disconnections of any socket.
+    ///
+    /// Connections are closed as soon as their [`SppConnection`] is dropped,
+    /// so without this callback every incoming connection is refused.
+    pub fn set_event_cb<F>(&mut self, callback_fn: F)
+    where
+        F: FnMut(SocketEvent),
+        F: 'static,
+    {
+        self.shared.borrow_mut().event_fn = Some(Box::new(callback_fn));
+    }
+
+    /// Calls `callback_fn` with the socket and data whenever data is received,
+    /// the data can also be read from the [`SppConnection`].
+    pub fn set_data_received_cb<F>(&mut self, callback_fn: F)
+    where
+        F: FnMut(i32, &[u8]),
+        F: 'static,
+    {
+        self.shared.borrow_mut().data_fn = Some(Box::new(callback_fn));
+    }
+
+    /// Registers a service and accepts incoming connections,
+    /// which are reported as [`SocketEvent::Connected`].
+    pub fn listen(&self, service_uuid: &str, max_pending_connections: i32) -> Result<SppListener> {
+        let service_uuid = cstring(service_uuid)?;
+        let mut socket_fd = -1;
+
+        check(unsafe { bt_socket_create_rfcomm(service_uuid.as_ptr(), &mut socket_fd) })?;
+
+        let listener = SppListener { socket_fd };
+
+        check(unsafe { bt_socket_listen_and_accept_rfcomm(socket_fd, max_pending_connections) })?;
+
+        Ok(listener)
+    }
+
+    /// Connects to the service of a remote device, the result is reported
+    /// as a [`SocketEvent`].
+    pub fn connect(&self, remote_address: &str, service_uuid: &str) -> Result<()> {
+        let remote_address = cstring(remote_address)?;
+        let service_uuid = cstring(service_uuid)?;
+
+        check(unsafe { bt_socket_connect_rfcomm(remote_address.as_ptr(), service_uuid.as_ptr()) })
+    }
+}
+
+impl Drop for Spp {
+    fn drop(&mut self) {
+        unsafe {
+            bt_socket_unset_data_received_cb();
+            bt_socket_unset_connection_state_changed_cb();
+        }
+    }
+}
+
+/// A registered RFCOMM service, unregistered when this is dropped.
+pub struct SppListener {
+    socket_fd: c_int,
+}
+
+impl SppListener {
+    pub fn socket_fd(&self) -> i32 {
+        self.socket_fd
+    }
+}
+
+impl Drop for SppListener {
+    fn drop(&mut self) {
+        unsafe {
+            bt_socket_destroy_rfcomm(self.socket_fd);
+        }
+    }
+}
+
+/// A connected RFCOMM socket, disconnected when this is dropped.
+///
+/// Data is received on the main loop, so reads never block and fail with
+/// `WouldBlock` when nothing was received yet.
+pub struct SppConnection {
+    socket_fd: c_int,
+    remote_address: String,
+    is_server: bool,
+    shared: Rc<RefCell<Shared>>,
+}
+
+impl SppConnection {
+    pub fn socket_fd(&self) -> i32 {
+        self.socket_fd
+    }
+
+    pub fn remote_address(&self) -> &str {
+        &self.remote_address
+    }
+
+    /// Whether we accepted this connection on a listener.
+    pub fn is_server(&self) -> bool {
+        self.is_server
+    }
+
+    /// Number of received bytes waiting to be read.
+    pub fn available(&self) -> usize {
+        self.shared
+            .borrow()
+            .buffers
+            .get(&self.socket_fd)
+            .map_or(0, VecDeque::len)
+    }
+}
+
+impl io::Read for SppConnection {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let mut shared = self.shared.borrow_mut();
+        let buffer = shared.buffers.entry(self.socket_fd).or_default();
+
+        if buffer.is_empty() {
+            return Err(io::ErrorKind::WouldBlock.into());
+        }
+
+        let len = buf.len().min(buffer.len());
+        for (dst, src) in buf.iter_mut().zip(buffer.drain(..len)) {
+            *dst = src;
+        }
+
+        Ok(len)
+    }
+}
+
+impl io::Write for SppConnection {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let sent = unsafe {
+            bt_socket_send_data(
+                self.socket_fd,
+                buf.as_ptr() as *const c_char,
+                buf.len() as c_int,
+            )
+        };
+
+        check_count(sent).map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Drop for SppConnection {
+    fn drop(&mut self) {
+        self.shared.borrow_mut().buffers.remove(&self.socket_fd);
+
+        unsafe {
+            bt_socket_disconnect_rfcomm(self.socket_fd);
+        }
+    }
+}
+
+fn shared_from(user_data: *mut c_void) -> Rc<RefCell<Shared>> {
+    let ptr = user_data as *const RefCell<Shared>;
+
+    unsafe {
+        Rc::increment_strong_count(ptr);
+        Rc::from_raw(ptr)
+    }
+}
+
+extern "C" fn data_received_handler(data: *mut bt_socket_received_data_s, user_data: *mut c_void) {
+    abort_on_panic(|| {
+        let shared = shared_from(user_data);
+        let data = unsafe { &*data };
+        let bytes = if data.data.is_null() {
+            &[][..]
+        } else {
+            unsafe { std::slice::from_raw_parts(data.data as *const u8, data.data_size as usize) }
+        };
+
+        let data_fn = {
+            let mut shared = shared.borrow_mut();
+            shared
+                .buffers
+                .entry(data.socket_fd)
+                .or_default()
+                .extend(bytes);
+            shared.data_fn.take()
+        };
+
+        if let Some(mut data_fn) = data_fn {
+            data_fn(data.socket_fd, bytes);
+            shared.borrow_mut().data_fn.get_or_insert(data_fn);
+        }
+    })
+}
+
+extern "C" fn connection_state_handler(
+    result: c_int,
+    connection_state: bt_socket_connection_state_e,
+    connection: *mut bt_socket_connection_s,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let shared = shared_from(user_data);
+        let connection = unsafe { &*connection };
+
+        let event = if let Err(e) = check(result) {
+            SocketEvent::ConnectFailed(e)
+        } else if connection_state == bt_socket_connection_state_e_BT_SOCKET_CONNECTED {
+            SocketEvent::Connected(SppConnection {
+                socket_fd: connection.socket_fd,
+                remote_address: unsafe { borrow_str(connection.remote_address) }
+                    .unwrap_or_default()
+                    .into_owned(),
+                is_server: connection.local_role == bt_socket_role_e_BT_SOCKET_SERVER,
+                shared: shared.clone(),
+            })
+        } else {
+            SocketEvent::Disconnected {
+                socket_fd: connection.socket_fd,
+            }
+        };
+
+        let event_fn = shared.borrow_mut().event_fn.take();
+
+        if let Some(mut event_fn) = event_fn {
+            event_fn(event);
+            shared.borrow_mut().event_fn.get_or_insert(event_fn);
+        }
+    })
+}
@@ -0,0 +1,222 @@
+use super::{
+    connection_result_handler, cstring, payload_handler, peer_handler, sent_callback, sent_handler,
+    ConnectionResult, ConnectionResultFn, Payload, PayloadFn, Peer, PeerFn, RawPayload,
+    ReceivedPayload, TransferStatus,
+};
+use crate::error::{check, Result};
+use crate::rutin::pin_callback;
+use rutin_tizen_sys::{
+    cion_client_add_connection_result_cb, cion_client_add_disconnected_cb,
+    cion_client_add_payload_received_cb, cion_client_connect, cion_client_create,
+    cion_client_destroy, cion_client_disconnect, cion_client_h,
+    cion_client_remove_connection_result_cb, cion_client_remove_disconnected_cb,
+    cion_client_remove_payload_received_cb, cion_client_send_data, cion_client_send_payload_async,
+    cion_client_stop_discovery, cion_client_try_discovery,
+};
+use std::os::raw::{c_int, c_uchar, c_void};
+use std::pin::Pin;
+use std::ptr::null_mut;
+use std::slice;
+use std::time::Duration;
+
+/// Uses the service of a server on another device.
+pub struct Client {
+    handle: cion_client_h,
+    discovered_fn: Option<Pin<Box<PeerFn>>>,
+    connection_result_fn: Option<Pin<Box<ConnectionResultFn>>>,
+    payload_fn: Option<Pin<Box<PayloadFn>>>,
+    disconnected_fn: Option<Pin<Box<PeerFn>>>,
+}
+
+impl Client {
+    pub fn new(service_name: &str) -> Result<Client> {
+        let service_name = cstring(service_name)?;
+
+        let mut handle = null_mut();
+        check(unsafe { cion_client_create(&mut handle, service_name.as_ptr(), null_mut()) })?;
+
+        Ok(Self {
+            handle,
+            discovered_fn: None,
+            connection_result_fn: None,
+            payload_fn: None,
+            disconnected_fn: None,
+        })
+    }
+
+    /// Looks for the servers of the service, `callback_fn` is called with each one found
+    /// until [`Client::stop_discovery`].
+    pub fn try_discovery<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(Peer),
+        F: 'static,
+    {
+        let (callback_fn, data) = pin_callback::<dyn FnMut(Peer)>(Box::new(callback_fn));
+
+        check(unsafe { cion_client_try_discovery(self.handle, Some(peer_handler), data) })?;
+        self.discovered_fn = Some(callback_fn);
+
+        Ok(())
+    }
+
+    pub fn stop_discovery(&mut self) -> Result<()> {
+        check(unsafe { cion_client_stop_discovery(self.handle) })?;
+        self.discovered_fn = None;
+
+        Ok(())
+    }
+
+    /// Asks a discovered server for a connection, the answer being given to the
+    /// connection result callback.
+    pub fn connect(&mut self, server: &Peer) -> Result<()> {
+        check(unsafe { cion_client_connect(self.handle, server.handle) })
+    }
+
+    pub fn disconnect(&mut self) -> Result<()> {
+        check(unsafe { cion_client_disconnect(self.handle) })
+    }
+
+    /// Sends `data` to the connected server, waiting at most `timeout` for its reply.
+    pub fn send_data(&mut self, data: &[u8], timeout: Duration) -> Result<Vec<u8>> {
+        let mut reply: *mut c_uchar = null_mut();
+        let mut reply_size: c_int = 0;
+
+        check(unsafe {
+            cion_client_send_data(
+                self.handle,
+                data.as_ptr() as *mut c_uchar,
+                data.len() as c_int,
+                timeout.as_millis() as c_int,
+                &mut reply,
+                &mut reply_size,
+            )
+        })?;
+
+        if reply.is_null() {
+            return Ok(Vec::new());
+        }
+
+        let content = unsafe { slice::from_raw_parts(reply, reply_size as usize) }.to_vec();
+        unsafe { libc::free(reply as *mut c_void) };
+
+        Ok(content)
+    }
+
+    /// Sends `payload` to the connected server, `callback_fn` is called once it is sent.
+    pub fn send_payload<F>(&mut self, payload: &Payload, callback_fn: F) -> Result<()>
+    where
+        F: FnOnce(Result<()>),
+        F: 'static,
+    {
+        let payload = RawPayload::new(payload)?;
+        let data = sent_callback(callback_fn);
+
+        let ret = unsafe {
+            cion_client_send_payload_async(
+                self.handle,
+                payload.handle,
+                Some(sent_handler),
+                data as *mut c_void,
+            )
+        };
+
+        if let Err(e) = check(ret) {
+            drop(unsafe { Box::from_raw(data) });
+            return Err(e);
+        }
+
+        Ok(())
+    }
+
+    /// Calls `callback_fn` with the answer of the server to [`Client::connect`], replacing
+    /// the previous callback.
+    pub fn set_connection_result_cb<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(Peer, ConnectionResult),
+        F: 'static,
+    {
+        let (callback_fn, data) =
+            pin_callback::<dyn FnMut(Peer, ConnectionResult)>(Box::new(callback_fn));
+
+        if self.connection_result_fn.take().is_some() {
+            unsafe {
+                cion_client_remove_connection_result_cb(
+                    self.handle,
+                    Some(connection_result_handler),
+                )
+            };
+        }
+
+        check(unsafe {
+            cion_client_add_connection_result_cb(self.handle, Some(connection_result_handler), data)
+        })?;
+        self.connection_result_fn = Some(callback_fn);
+
+        Ok(())
+    }
+
+    /// Calls `callback_fn` with each payload sent by the server, replacing the previous
+    /// callback. File payloads are reported as in progress until completely received.
+    pub fn set_payload_received_cb<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(Peer, &ReceivedPayload, TransferStatus),
+        F: 'static,
+    {
+        let (callback_fn, data) = pin_callback::<dyn FnMut(Peer, &ReceivedPayload, TransferStatus)>(
+            Box::new(callback_fn),
+        );
+
+        if self.payload_fn.take().is_some() {
+            unsafe { cion_client_remove_payload_received_cb(self.handle, Some(payload_handler)) };
+        }
+
+        check(unsafe {
+            cion_client_add_payload_received_cb(self.handle, Some(payload_handler), data)
+        })?;
+        self.payload_fn = Some(callback_fn);
+
+        Ok(())
+    }
+
+    /// Calls `callback_fn` when the server disconnects, replacing the previous callback.
+    pub fn set_disconnected_cb<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(Peer),
+        F: 'static,
+    {
+        let (callback_fn, data) = pin_callback::<dyn FnMut(Peer)>(Box::new(callback_fn));
+
+        if self.disconnected_fn.take().is_some() {
+            unsafe { cion_client_remove_disconnected_cb(self.handle, Some(peer_handler)) };
+        }
+
+        check(unsafe { cion_client_add_disconnected_cb(self.handle, Some(peer_handler), data) })?;
+        self.disconnected_fn = Some(callback_fn);
+
+        Ok(())
+    }
+}
+
+impl Drop for Client {
+    fn drop(&mut self) {
+        unsafe {
+            if self.discovered_fn.is_some() {
+                cion_client_stop_discovery(self.handle);
+            }
+            if self.connection_result_fn.is_some() {
+                cion_client_remove_connection_result_cb(
+                    self.handle,
+                    Some(connection_result_handler),
+                );
+            }
+            if self.payload_fn.is_some() {
+                cion_client_remove_payload_received_cb(self.handle, Some(payload_handler));
+            }
+            if self.disconnected_fn.is_some() {
+                cion_client_remove_disconnected_cb(self.handle, Some(peer_handler));
+            }
+
+            cion_client_destroy(self.handle);
+        }
+    }
+}
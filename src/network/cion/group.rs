@@ -0,0 +1,146 @@
+use super::{peer_handler, Payload, Peer, PeerFn, RawPayload, ReceivedPayload};
+use crate::error::{check, Result};
+use crate::rutin::{abort_on_panic, cstring, pin_callback};
+use rutin_tizen_sys::{
+    cion_group_add_joined_cb, cion_group_add_left_cb, cion_group_add_payload_received_cb,
+    cion_group_create, cion_group_destroy, cion_group_h, cion_group_publish,
+    cion_group_remove_joined_cb, cion_group_remove_left_cb, cion_group_remove_payload_received_cb,
+    cion_group_subscribe, cion_group_unsubscribe, cion_payload_h, cion_peer_info_h,
+};
+use std::os::raw::{c_char, c_void};
+use std::pin::Pin;
+use std::ptr::null_mut;
+
+type PayloadFn = Box<dyn FnMut(Peer, &ReceivedPayload)>;
+
+/// Publishes payloads to every subscriber of a topic, without connections.
+pub struct Group {
+    handle: cion_group_h,
+    payload_fn: Option<Pin<Box<PayloadFn>>>,
+    joined_fn: Option<Pin<Box<PeerFn>>>,
+    left_fn: Option<Pin<Box<PeerFn>>>,
+}
+
+impl Group {
+    pub fn new(topic_name: &str) -> Result<Group> {
+        let topic_name = cstring(topic_name)?;
+
+        let mut handle = null_mut();
+        check(unsafe { cion_group_create(&mut handle, topic_name.as_ptr(), null_mut()) })?;
+
+        Ok(Self {
+            handle,
+            payload_fn: None,
+            joined_fn: None,
+            left_fn: None,
+        })
+    }
+
+    pub fn subscribe(&mut self) -> Result<()> {
+        check(unsafe { cion_group_subscribe(self.handle) })
+    }
+
+    pub fn unsubscribe(&mut self) -> Result<()> {
+        check(unsafe { cion_group_unsubscribe(self.handle) })
+    }
+
+    /// Sends `payload` to the other subscribers.
+    pub fn publish(&mut self, payload: &Payload) -> Result<()> {
+        let payload = RawPayload::new(payload)?;
+
+        check(unsafe { cion_group_publish(self.handle, payload.handle) })
+    }
+
+    /// Calls `callback_fn` with each payload published by the other subscribers,
+    /// replacing the previous callback.
+    pub fn set_payload_received_cb<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(Peer, &ReceivedPayload),
+        F: 'static,
+    {
+        let (callback_fn, data) =
+            pin_callback::<dyn FnMut(Peer, &ReceivedPayload)>(Box::new(callback_fn));
+
+        if self.payload_fn.take().is_some() {
+            unsafe { cion_group_remove_payload_received_cb(self.handle, Some(payload_handler)) };
+        }
+
+        check(unsafe {
+            cion_group_add_payload_received_cb(self.handle, Some(payload_handler), data)
+        })?;
+        self.payload_fn = Some(callback_fn);
+
+        Ok(())
+    }
+
+    /// Calls `callback_fn` with each peer subscribing to the topic, replacing the previous
+    /// callback.
+    pub fn set_joined_cb<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(Peer),
+        F: 'static,
+    {
+        let (callback_fn, data) = pin_callback::<dyn FnMut(Peer)>(Box::new(callback_fn));
+
+        if self.joined_fn.take().is_some() {
+            unsafe { cion_group_remove_joined_cb(self.handle, Some(peer_handler)) };
+        }
+
+        check(unsafe { cion_group_add_joined_cb(self.handle, Some(peer_handler), data) })?;
+        self.joined_fn = Some(callback_fn);
+
+        Ok(())
+    }
+
+    /// Calls `callback_fn` with each peer unsubscribing from the topic, replacing the
+    /// previous callback.
+    pub fn set_left_cb<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(Peer),
+        F: 'static,
+    {
+        let (callback_fn, data) = pin_callback::<dyn FnMut(Peer)>(Box::new(callback_fn));
+
+        if self.left_fn.take().is_some() {
+            unsafe { cion_group_remove_left_cb(self.handle, Some(peer_handler)) };
+        }
+
+        check(unsafe { cion_group_add_left_cb(self.handle, Some(peer_handler), data) })?;
+        self.left_fn = Some(callback_fn);
+
+        Ok(())
+    }
+}
+
+impl Drop for Group {
+    fn drop(&mut self) {
+        unsafe {
+            if self.payload_fn.is_some() {
+                cion_group_remove_payload_received_cb(self.handle, Some(payload_handler));
+            }
+            if self.joined_fn.is_some() {
+                cion_group_remove_joined_cb(self.handle, Some(peer_handler));
+            }
+            if self.left_fn.is_some() {
+                cion_group_remove_left_cb(self.handle, Some(peer_handler));
+            }
+
+            cion_group_destroy(self.handle);
+        }
+    }
+}
+
+extern "C" fn payload_handler(
+    _topic_name: *const c_char,
+    peer: cion_peer_info_h,
+    payload: cion_payload_h,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut PayloadFn) };
+
+        if let Some(peer) = unsafe { Peer::clone_raw(peer) } {
+            closure(peer, &ReceivedPayload { handle: payload });
+        }
+    })
+}
@@ -0,0 +1,360 @@
+//! Cion, communicating with the apps of the other devices of the same user on the local
+//! network, since Tizen 6.5.
+//!
+//! Requires the `http://tizen.org/privilege/d2d.datasharing` and
+//! `http://tizen.org/privilege/internet` privileges.
+
+mod client;
+mod group;
+mod server;
+
+pub use client::Client;
+pub use group::Group;
+pub use server::{ConnectionRequest, Server};
+
+use crate::error::{check, Error, Result};
+use crate::rutin::{abort_on_panic, cstring, take_string};
+use rutin_tizen_sys::{
+    cion_connection_result_get_reason, cion_connection_result_get_status, cion_connection_result_h,
+    cion_connection_status_e, cion_connection_status_e_CION_CONNECTION_STATUS_OK,
+    cion_connection_status_e_CION_CONNECTION_STATUS_REJECTED, cion_payload_async_result_e,
+    cion_payload_async_result_e_CION_PAYLOAD_ASYNC_RESULT_SUCCESS,
+    cion_payload_async_result_get_result, cion_payload_async_result_h, cion_payload_create,
+    cion_payload_destroy, cion_payload_get_data, cion_payload_get_payload_id,
+    cion_payload_get_received_bytes, cion_payload_get_received_file_name,
+    cion_payload_get_total_bytes, cion_payload_get_type, cion_payload_h, cion_payload_save_as_file,
+    cion_payload_set_data, cion_payload_set_file_path, cion_payload_transfer_status_e,
+    cion_payload_transfer_status_e_CION_PAYLOAD_TRANSFER_STATUS_IN_PROGRESS,
+    cion_payload_transfer_status_e_CION_PAYLOAD_TRANSFER_STATUS_SUCCESS,
+    cion_payload_type_e_CION_PAYLOAD_TYPE_DATA, cion_payload_type_e_CION_PAYLOAD_TYPE_FILE,
+    cion_peer_info_clone, cion_peer_info_destroy, cion_peer_info_get_app_id,
+    cion_peer_info_get_app_version, cion_peer_info_get_device_id, cion_peer_info_get_device_name,
+    cion_peer_info_get_device_platform, cion_peer_info_get_device_platform_version,
+    cion_peer_info_get_device_type, cion_peer_info_get_uuid, cion_peer_info_h,
+};
+use std::os::raw::{c_char, c_int, c_uchar, c_uint, c_void};
+use std::path::{Path, PathBuf};
+use std::ptr::null_mut;
+use std::slice;
+
+/// An app of another device, as discovered or connected.
+pub struct Peer {
+    handle: cion_peer_info_h,
+}
+
+type PeerGetter = unsafe extern "C" fn(cion_peer_info_h, *mut *mut c_char) -> c_int;
+
+impl Peer {
+    /// Copies a peer borrowed from a callback, `None` if out of memory.
+    unsafe fn clone_raw(peer: cion_peer_info_h) -> Option<Peer> {
+        let mut handle = null_mut();
+        check(cion_peer_info_clone(peer, &mut handle)).ok()?;
+
+        Some(Self { handle })
+    }
+
+    pub fn get_device_id(&self) -> Result<String> {
+        self.get_string(cion_peer_info_get_device_id)
+    }
+
+    pub fn get_device_name(&self) -> Result<String> {
+        self.get_string(cion_peer_info_get_device_name)
+    }
+
+    pub fn get_device_platform(&self) -> Result<String> {
+        self.get_string(cion_peer_info_get_device_platform)
+    }
+
+    pub fn get_device_platform_version(&self) -> Result<String> {
+        self.get_string(cion_peer_info_get_device_platform_version)
+    }
+
+    /// Such as `mobile`, `wearable` or `tv`.
+    pub fn get_device_type(&self) -> Result<String> {
+        self.get_string(cion_peer_info_get_device_type)
+    }
+
+    pub fn get_app_id(&self) -> Result<String> {
+        self.get_string(cion_peer_info_get_app_id)
+    }
+
+    pub fn get_app_version(&self) -> Result<String> {
+        self.get_string(cion_peer_info_get_app_version)
+    }
+
+    /// Identifies the peer among the connections of a server.
+    pub fn get_uuid(&self) -> Result<String> {
+        self.get_string(cion_peer_info_get_uuid)
+    }
+
+    fn get_string(&self, get: PeerGetter) -> Result<String> {
+        let mut value = null_mut();
+        check(unsafe { get(self.handle, &mut value) })?;
+
+        Ok(unsafe { take_string(value) }.unwrap_or_default())
+    }
+}
+
+impl Drop for Peer {
+    fn drop(&mut self) {
+        unsafe {
+            cion_peer_info_destroy(self.handle);
+        }
+    }
+}
+
+/// What to send to a peer or a group.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Payload {
+    Data(Vec<u8>),
+    /// The content of a file, readable by the app.
+    File(PathBuf),
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TransferStatus {
+    Success,
+    Failure,
+    /// Part of a file was received, the callback is called again with the rest.
+    InProgress,
+}
+
+impl From<cion_payload_transfer_status_e> for TransferStatus {
+    fn from(status: cion_payload_transfer_status_e) -> TransferStatus {
+        match status {
+            cion_payload_transfer_status_e_CION_PAYLOAD_TRANSFER_STATUS_SUCCESS => {
+                TransferStatus::Success
+            }
+            cion_payload_transfer_status_e_CION_PAYLOAD_TRANSFER_STATUS_IN_PROGRESS => {
+                TransferStatus::InProgress
+            }
+            _ => TransferStatus::Failure,
+        }
+    }
+}
+
+/// A payload received from a peer, only valid during its callback.
+pub struct ReceivedPayload {
+    handle: cion_payload_h,
+}
+
+impl ReceivedPayload {
+    pub fn get_id(&self) -> Result<String> {
+        let mut id = null_mut();
+        check(unsafe { cion_payload_get_payload_id(self.handle, &mut id) })?;
+
+        Ok(unsafe { take_string(id) }.unwrap_or_default())
+    }
+
+    pub fn is_file(&self) -> Result<bool> {
+        let mut payload_type = cion_payload_type_e_CION_PAYLOAD_TYPE_DATA;
+        check(unsafe { cion_payload_get_type(self.handle, &mut payload_type) })?;
+
+        Ok(payload_type == cion_payload_type_e_CION_PAYLOAD_TYPE_FILE)
+    }
+
+    /// The content of a data payload.
+    pub fn get_data(&self) -> Result<Vec<u8>> {
+        let mut data: *mut c_uchar = null_mut();
+        let mut size: c_uint = 0;
+        check(unsafe { cion_payload_get_data(self.handle, &mut data, &mut size) })?;
+
+        if data.is_null() {
+            return Ok(Vec::new());
+        }
+
+        let content = unsafe { slice::from_raw_parts(data, size as usize) }.to_vec();
+        unsafe { libc::free(data as *mut c_void) };
+
+        Ok(content)
+    }
+
+    /// The name of a file payload, as sent.
+    pub fn get_file_name(&self) -> Result<String> {
+        let mut file_name = null_mut();
+        check(unsafe { cion_payload_get_received_file_name(self.handle, &mut file_name) })?;
+
+        Ok(unsafe { take_string(file_name) }.unwrap_or_default())
+    }
+
+    /// The bytes of a file payload received so far.
+    pub fn get_received_bytes(&self) -> Result<u64> {
+        let mut bytes = 0;
+        check(unsafe { cion_payload_get_received_bytes(self.handle, &mut bytes) })?;
+
+        Ok(bytes)
+    }
+
+    pub fn get_total_bytes(&self) -> Result<u64> {
+        let mut bytes = 0;
+        check(unsafe { cion_payload_get_total_bytes(self.handle, &mut bytes) })?;
+
+        Ok(bytes)
+    }
+
+    /// Stores a completely received file payload at `path`.
+    pub fn save_as_file(&self, path: &Path) -> Result<()> {
+        let path = cstring(&path.to_string_lossy())?;
+
+        check(unsafe { cion_payload_save_as_file(self.handle, path.as_ptr()) })
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ConnectionStatus {
+    Ok,
+    Rejected,
+    Error,
+}
+
+impl From<cion_connection_status_e> for ConnectionStatus {
+    fn from(status: cion_connection_status_e) -> ConnectionStatus {
+        match status {
+            cion_connection_status_e_CION_CONNECTION_STATUS_OK => ConnectionStatus::Ok,
+            cion_connection_status_e_CION_CONNECTION_STATUS_REJECTED => ConnectionStatus::Rejected,
+            _ => ConnectionStatus::Error,
+        }
+    }
+}
+
+/// The outcome of a connection handshake.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConnectionResult {
+    pub status: ConnectionStatus,
+    /// Given by the server when rejecting.
+    pub reason: Option<String>,
+}
+
+impl ConnectionResult {
+    unsafe fn from_raw(result: cion_connection_result_h) -> ConnectionResult {
+        let mut status = cion_connection_status_e_CION_CONNECTION_STATUS_OK;
+        let status = match check(cion_connection_result_get_status(result, &mut status)) {
+            Ok(()) => status.into(),
+            Err(_) => ConnectionStatus::Error,
+        };
+
+        let mut reason = null_mut();
+        let reason = check(cion_connection_result_get_reason(result, &mut reason))
+            .ok()
+            .and_then(|_| take_string(reason));
+
+        Self { status, reason }
+    }
+}
+
+type SentFn = dyn FnOnce(Result<()>);
+
+// the callback is boxed twice to pass it as a thin pointer
+fn sent_callback<F>(callback_fn: F) -> *mut Box<SentFn>
+where
+    F: FnOnce(Result<()>),
+    F: 'static,
+{
+    let callback_fn: Box<SentFn> = Box::new(callback_fn);
+    Box::into_raw(Box::new(callback_fn))
+}
+
+/// A payload handle, destroyed with this value.
+struct RawPayload {
+    handle: cion_payload_h,
+}
+
+impl RawPayload {
+    fn new(payload: &Payload) -> Result<RawPayload> {
+        let payload_type = match payload {
+            Payload::Data(_) => cion_payload_type_e_CION_PAYLOAD_TYPE_DATA,
+            Payload::File(_) => cion_payload_type_e_CION_PAYLOAD_TYPE_FILE,
+        };
+
+        let mut handle = null_mut();
+        check(unsafe { cion_payload_create(&mut handle, payload_type) })?;
+        let raw = Self { handle };
+
+        match payload {
+            Payload::Data(data) => check(unsafe {
+                cion_payload_set_data(handle, data.as_ptr(), data.len() as c_uint)
+            })?,
+            Payload::File(path) => {
+                let path = cstring(&path.to_string_lossy())?;
+                check(unsafe { cion_payload_set_file_path(handle, path.as_ptr()) })?
+            }
+        }
+
+        Ok(raw)
+    }
+}
+
+impl Drop for RawPayload {
+    fn drop(&mut self) {
+        unsafe {
+            cion_payload_destroy(self.handle);
+        }
+    }
+}
+
+type PeerFn = Box<dyn FnMut(Peer)>;
+type ConnectionResultFn = Box<dyn FnMut(Peer, ConnectionResult)>;
+type PayloadFn = Box<dyn FnMut(Peer, &ReceivedPayload, TransferStatus)>;
+
+extern "C" fn peer_handler(_name: *const c_char, peer: cion_peer_info_h, user_data: *mut c_void) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut PeerFn) };
+
+        if let Some(peer) = unsafe { Peer::clone_raw(peer) } {
+            closure(peer);
+        }
+    })
+}
+
+extern "C" fn connection_result_handler(
+    _service_name: *const c_char,
+    peer: cion_peer_info_h,
+    result: cion_connection_result_h,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut ConnectionResultFn) };
+
+        if let Some(peer) = unsafe { Peer::clone_raw(peer) } {
+            closure(peer, unsafe { ConnectionResult::from_raw(result) });
+        }
+    })
+}
+
+extern "C" fn payload_handler(
+    _service_name: *const c_char,
+    peer: cion_peer_info_h,
+    payload: cion_payload_h,
+    status: cion_payload_transfer_status_e,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut PayloadFn) };
+
+        if let Some(peer) = unsafe { Peer::clone_raw(peer) } {
+            closure(peer, &ReceivedPayload { handle: payload }, status.into());
+        }
+    })
+}
+
+extern "C" fn sent_handler(result: cion_payload_async_result_h, user_data: *mut c_void) {
+    abort_on_panic(|| {
+        let closure = unsafe { Box::from_raw(user_data as *mut Box<SentFn>) };
+
+        let mut code: cion_payload_async_result_e = 0;
+        let sent = check(unsafe { cion_payload_async_result_get_result(result, &mut code) })
+            .and_then(|_| {
+                if code == cion_payload_async_result_e_CION_PAYLOAD_ASYNC_RESULT_SUCCESS {
+                    Ok(())
+                } else {
+                    Err(Error::IoError)
+                }
+            });
+
+        closure(sent);
+    })
+}
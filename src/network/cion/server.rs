@@ -0,0 +1,343 @@
+use super::{
+    connection_result_handler, cstring, payload_handler, peer_handler, sent_callback, sent_handler,
+    ConnectionResult, ConnectionResultFn, Payload, PayloadFn, Peer, PeerFn, RawPayload,
+    ReceivedPayload, TransferStatus,
+};
+use crate::error::{check, Result};
+use crate::rutin::{abort_on_panic, pin_callback};
+use rutin_tizen_sys::{
+    cion_peer_info_h, cion_server_accept, cion_server_add_connection_result_cb,
+    cion_server_add_disconnected_cb, cion_server_add_payload_received_cb, cion_server_create,
+    cion_server_destroy, cion_server_disconnect, cion_server_foreach_connected_peer_info,
+    cion_server_h, cion_server_listen, cion_server_reject, cion_server_remove_connection_result_cb,
+    cion_server_remove_disconnected_cb, cion_server_remove_payload_received_cb,
+    cion_server_send_payload_async, cion_server_set_data_received_cb, cion_server_stop,
+    cion_server_unset_data_received_cb,
+};
+use std::os::raw::{c_char, c_uchar, c_uint, c_void};
+use std::pin::Pin;
+use std::ptr::{copy_nonoverlapping, null_mut};
+use std::slice;
+
+/// A connection asked by a client, to be accepted or rejected.
+pub struct ConnectionRequest {
+    server: cion_server_h,
+    pub peer: Peer,
+}
+
+impl ConnectionRequest {
+    pub fn accept(self) -> Result<()> {
+        check(unsafe { cion_server_accept(self.server, self.peer.handle) })
+    }
+
+    /// Refuses the connection, the client receiving `reason`.
+    pub fn reject(self, reason: &str) -> Result<()> {
+        let reason = cstring(reason)?;
+
+        check(unsafe { cion_server_reject(self.server, self.peer.handle, reason.as_ptr()) })
+    }
+}
+
+type RequestFn = Box<dyn FnMut(ConnectionRequest)>;
+type DataFn = Box<dyn FnMut(Peer, &[u8]) -> Vec<u8>>;
+
+struct RequestState {
+    server: cion_server_h,
+    callback_fn: RequestFn,
+}
+
+/// Offers a service to the clients of the other devices.
+pub struct Server {
+    handle: cion_server_h,
+    request_state: Option<Pin<Box<RequestState>>>,
+    connection_result_fn: Option<Pin<Box<ConnectionResultFn>>>,
+    payload_fn: Option<Pin<Box<PayloadFn>>>,
+    data_fn: Option<Pin<Box<DataFn>>>,
+    disconnected_fn: Option<Pin<Box<PeerFn>>>,
+}
+
+impl Server {
+    /// Creates the server of `service_name`, shown as `display_name` to the clients.
+    pub fn new(service_name: &str, display_name: &str) -> Result<Server> {
+        let service_name = cstring(service_name)?;
+        let display_name = cstring(display_name)?;
+
+        let mut handle = null_mut();
+        check(unsafe {
+            cion_server_create(
+                &mut handle,
+                service_name.as_ptr(),
+                display_name.as_ptr(),
+                null_mut(),
+            )
+        })?;
+
+        Ok(Self {
+            handle,
+            request_state: None,
+            connection_result_fn: None,
+            payload_fn: None,
+            data_fn: None,
+            disconnected_fn: None,
+        })
+    }
+
+    /// Makes the server discoverable, `callback_fn` being called with each connection
+    /// request until [`Server::stop`].
+    pub fn listen<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(ConnectionRequest),
+        F: 'static,
+    {
+        let state = Box::pin(RequestState {
+            server: self.handle,
+            callback_fn: Box::new(callback_fn),
+        });
+        let data = &*state as *const RequestState as *mut c_void;
+
+        check(unsafe { cion_server_listen(self.handle, Some(request_handler), data) })?;
+        self.request_state = Some(state);
+
+        Ok(())
+    }
+
+    pub fn stop(&mut self) -> Result<()> {
+        check(unsafe { cion_server_stop(self.handle) })?;
+        self.request_state = None;
+
+        Ok(())
+    }
+
+    pub fn disconnect(&mut self, peer: &Peer) -> Result<()> {
+        check(unsafe { cion_server_disconnect(self.handle, peer.handle) })
+    }
+
+    pub fn get_connected_peers(&self) -> Result<Vec<Peer>> {
+        let mut peers: Vec<Peer> = Vec::new();
+
+        check(unsafe {
+            cion_server_foreach_connected_peer_info(
+                self.handle,
+                Some(connected_peer_handler),
+                &mut peers as *mut Vec<Peer> as *mut c_void,
+            )
+        })?;
+
+        Ok(peers)
+    }
+
+    /// Sends `payload` to a connected client, `callback_fn` is called once it is sent.
+    pub fn send_payload<F>(&mut self, peer: &Peer, payload: &Payload, callback_fn: F) -> Result<()>
+    where
+        F: FnOnce(Result<()>),
+        F: 'static,
+    {
+        let payload = RawPayload::new(payload)?;
+        let data = sent_callback(callback_fn);
+
+        let ret = unsafe {
+            cion_server_send_payload_async(
+                self.handle,
+                peer.handle,
+                payload.handle,
+                Some(sent_handler),
+                data as *mut c_void,
+            )
+        };
+
+        if let Err(e) = check(ret) {
+            drop(unsafe { Box::from_raw(data) });
+            return Err(e);
+        }
+
+        Ok(())
+    }
+
+    /// Calls `callback_fn` with the outcome of each accepted or rejected connection,
+    /// replacing the previous callback.
+    pub fn set_connection_result_cb<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(Peer, ConnectionResult),
+        F: 'static,
+    {
+        let (callback_fn, data) =
+            pin_callback::<dyn FnMut(Peer, ConnectionResult)>(Box::new(callback_fn));
+
+        if self.connection_result_fn.take().is_some() {
+            unsafe {
+                cion_server_remove_connection_result_cb(
+                    self.handle,
+                    Some(connection_result_handler),
+                )
+            };
+        }
+
+        check(unsafe {
+            cion_server_add_connection_result_cb(self.handle, Some(connection_result_handler), data)
+        })?;
+        self.connection_result_fn = Some(callback_fn);
+
+        Ok(())
+    }
+
+    /// Calls `callback_fn` with each payload sent by the clients, replacing the previous
+    /// callback. File payloads are reported as in progress until completely received.
+    pub fn set_payload_received_cb<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(Peer, &ReceivedPayload, TransferStatus),
+        F: 'static,
+    {
+        let (callback_fn, data) = pin_callback::<dyn FnMut(Peer, &ReceivedPayload, TransferStatus)>(
+            Box::new(callback_fn),
+        );
+
+        if self.payload_fn.take().is_some() {
+            unsafe { cion_server_remove_payload_received_cb(self.handle, Some(payload_handler)) };
+        }
+
+        check(unsafe {
+            cion_server_add_payload_received_cb(self.handle, Some(payload_handler), data)
+        })?;
+        self.payload_fn = Some(callback_fn);
+
+        Ok(())
+    }
+
+    /// Answers the data sent with [`Client::send_data`](super::Client::send_data) with
+    /// the result of `callback_fn`, replacing the previous callback.
+    pub fn set_data_received_cb<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(Peer, &[u8]) -> Vec<u8>,
+        F: 'static,
+    {
+        let (callback_fn, data) =
+            pin_callback::<dyn FnMut(Peer, &[u8]) -> Vec<u8>>(Box::new(callback_fn));
+
+        check(unsafe { cion_server_set_data_received_cb(self.handle, Some(data_handler), data) })?;
+        self.data_fn = Some(callback_fn);
+
+        Ok(())
+    }
+
+    pub fn unset_data_received_cb(&mut self) -> Result<()> {
+        check(unsafe { cion_server_unset_data_received_cb(self.handle) })?;
+        self.data_fn = None;
+
+        Ok(())
+    }
+
+    /// Calls `callback_fn` with each client which disconnected, replacing the previous
+    /// callback.
+    pub fn set_disconnected_cb<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(Peer),
+        F: 'static,
+    {
+        let (callback_fn, data) = pin_callback::<dyn FnMut(Peer)>(Box::new(callback_fn));
+
+        if self.disconnected_fn.take().is_some() {
+            unsafe { cion_server_remove_disconnected_cb(self.handle, Some(peer_handler)) };
+        }
+
+        check(unsafe { cion_server_add_disconnected_cb(self.handle, Some(peer_handler), data) })?;
+        self.disconnected_fn = Some(callback_fn);
+
+        Ok(())
+    }
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        unsafe {
+            if self.request_state.is_some() {
+                cion_server_stop(self.handle);
+            }
+            if self.connection_result_fn.is_some() {
+                cion_server_remove_connection_result_cb(
+                    self.handle,
+                    Some(connection_result_handler),
+                );
+            }
+            if self.payload_fn.is_some() {
+                cion_server_remove_payload_received_cb(self.handle, Some(payload_handler));
+            }
+            if self.data_fn.is_some() {
+                cion_server_unset_data_received_cb(self.handle);
+            }
+            if self.disconnected_fn.is_some() {
+                cion_server_remove_disconnected_cb(self.handle, Some(peer_handler));
+            }
+
+            cion_server_destroy(self.handle);
+        }
+    }
+}
+
+extern "C" fn request_handler(
+    _service_name: *const c_char,
+    peer: cion_peer_info_h,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let state = unsafe { &mut *(user_data as *mut RequestState) };
+
+        if let Some(peer) = unsafe { Peer::clone_raw(peer) } {
+            (state.callback_fn)(ConnectionRequest {
+                server: state.server,
+                peer,
+            });
+        }
+    })
+}
+
+extern "C" fn connected_peer_handler(peer: cion_peer_info_h, user_data: *mut c_void) -> bool {
+    abort_on_panic(|| {
+        let peers = unsafe { &mut *(user_data as *mut Vec<Peer>) };
+
+        if let Some(peer) = unsafe { Peer::clone_raw(peer) } {
+            peers.push(peer);
+        }
+
+        true
+    })
+}
+
+#[allow(clippy::too_many_arguments)]
+extern "C" fn data_handler(
+    _service_name: *const c_char,
+    peer: cion_peer_info_h,
+    data: *const c_uchar,
+    data_size: c_uint,
+    return_data: *mut *mut c_uchar,
+    return_data_size: *mut c_uint,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut DataFn) };
+
+        let peer = match unsafe { Peer::clone_raw(peer) } {
+            Some(peer) => peer,
+            None => return,
+        };
+        let data = if data.is_null() {
+            &[][..]
+        } else {
+            unsafe { slice::from_raw_parts(data, data_size as usize) }
+        };
+
+        let reply = closure(peer, data);
+
+        // the reply is freed by Cion
+        unsafe {
+            let buffer = libc::malloc(reply.len().max(1)) as *mut c_uchar;
+
+            if buffer.is_null() {
+                return;
+            }
+
+            copy_nonoverlapping(reply.as_ptr(), buffer, reply.len());
+            *return_data = buffer;
+            *return_data_size = reply.len() as c_uint;
+        }
+    })
+}
@@ -0,0 +1,523 @@
+use crate::error::{check, Error, Result};
+use crate::rutin::{abort_on_panic, borrow_str, pin_callback, take_string};
+#[cfg(feature = "async")]
+use crate::stream::EventStream;
+use crate::sys::{
+    connection_address_family_e, connection_address_family_e_CONNECTION_ADDRESS_FAMILY_IPV4,
+    connection_address_family_e_CONNECTION_ADDRESS_FAMILY_IPV6, connection_cellular_state_e,
+    connection_cellular_state_e_CONNECTION_CELLULAR_STATE_AVAILABLE,
+    connection_cellular_state_e_CONNECTION_CELLULAR_STATE_CALL_ONLY_AVAILABLE,
+    connection_cellular_state_e_CONNECTION_CELLULAR_STATE_CONNECTED,
+    connection_cellular_state_e_CONNECTION_CELLULAR_STATE_FLIGHT_MODE,
+    connection_cellular_state_e_CONNECTION_CELLULAR_STATE_OUT_OF_SERVICE,
+    connection_cellular_state_e_CONNECTION_CELLULAR_STATE_ROAMING_OFF, connection_create,
+    connection_destroy, connection_get_cellular_state, connection_get_current_profile,
+    connection_get_ip_address, connection_get_mac_address, connection_get_proxy,
+    connection_get_type, connection_get_wifi_state, connection_h, connection_profile_destroy,
+    connection_profile_get_dns_address, connection_profile_get_gateway_address,
+    connection_profile_get_ip_address, connection_profile_get_network_interface_name,
+    connection_profile_get_proxy_address, connection_profile_get_proxy_type,
+    connection_profile_get_subnet_mask, connection_profile_h, connection_proxy_type_e,
+    connection_proxy_type_e_CONNECTION_PROXY_TYPE_AUTO,
+    connection_proxy_type_e_CONNECTION_PROXY_TYPE_DIRECT,
+    connection_proxy_type_e_CONNECTION_PROXY_TYPE_MANUAL, connection_set_ip_address_changed_cb,
+    connection_set_type_changed_cb, connection_type_e, connection_type_e_CONNECTION_TYPE_BT,
+    connection_type_e_CONNECTION_TYPE_CELLULAR, connection_type_e_CONNECTION_TYPE_DISCONNECTED,
+    connection_type_e_CONNECTION_TYPE_ETHERNET, connection_type_e_CONNECTION_TYPE_NET_PROXY,
+    connection_type_e_CONNECTION_TYPE_WIFI, connection_unset_ip_address_changed_cb,
+    connection_unset_type_changed_cb, connection_wifi_state_e,
+    connection_wifi_state_e_CONNECTION_WIFI_STATE_CONNECTED,
+    connection_wifi_state_e_CONNECTION_WIFI_STATE_DEACTIVATED,
+    connection_wifi_state_e_CONNECTION_WIFI_STATE_DISCONNECTED,
+};
+use std::convert::TryFrom;
+use std::marker::PhantomData;
+use std::os::raw::{c_char, c_int, c_void};
+use std::pin::Pin;
+use std::ptr::null_mut;
+
+/// The network currently used for data.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ConnectionType {
+    Disconnected,
+    Wifi,
+    Cellular,
+    Ethernet,
+    Bluetooth,
+    /// Tethered through the paired phone.
+    NetProxy,
+}
+
+impl TryFrom<connection_type_e> for ConnectionType {
+    type Error = Error;
+
+    fn try_from(connection_type: connection_type_e) -> Result<ConnectionType> {
+        match connection_type {
+            connection_type_e_CONNECTION_TYPE_DISCONNECTED => Ok(ConnectionType::Disconnected),
+            connection_type_e_CONNECTION_TYPE_WIFI => Ok(ConnectionType::Wifi),
+            connection_type_e_CONNECTION_TYPE_CELLULAR => Ok(ConnectionType::Cellular),
+            connection_type_e_CONNECTION_TYPE_ETHERNET => Ok(ConnectionType::Ethernet),
+            connection_type_e_CONNECTION_TYPE_BT => Ok(ConnectionType::Bluetooth),
+            connection_type_e_CONNECTION_TYPE_NET_PROXY => Ok(ConnectionType::NetProxy),
+            _ => Err(Error::Unknown),
+        }
+    }
+}
+
+impl From<ConnectionType> for connection_type_e {
+    fn from(connection_type: ConnectionType) -> connection_type_e {
+        match connection_type {
+            ConnectionType::Disconnected => connection_type_e_CONNECTION_TYPE_DISCONNECTED,
+            ConnectionType::Wifi => connection_type_e_CONNECTION_TYPE_WIFI,
+            ConnectionType::Cellular => connection_type_e_CONNECTION_TYPE_CELLULAR,
+            ConnectionType::Ethernet => connection_type_e_CONNECTION_TYPE_ETHERNET,
+            ConnectionType::Bluetooth => connection_type_e_CONNECTION_TYPE_BT,
+            ConnectionType::NetProxy => connection_type_e_CONNECTION_TYPE_NET_PROXY,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CellularState {
+    OutOfService,
+    FlightMode,
+    RoamingOff,
+    CallOnlyAvailable,
+    Available,
+    Connected,
+}
+
+impl TryFrom<connection_cellular_state_e> for CellularState {
+    type Error = Error;
+
+    fn try_from(state: connection_cellular_state_e) -> Result<CellularState> {
+        match state {
+            connection_cellular_state_e_CONNECTION_CELLULAR_STATE_OUT_OF_SERVICE => {
+                Ok(CellularState::OutOfService)
+            }
+            connection_cellular_state_e_CONNECTION_CELLULAR_STATE_FLIGHT_MODE => {
+                Ok(CellularState::FlightMode)
+            }
+            connection_cellular_state_e_CONNECTION_CELLULAR_STATE_ROAMING_OFF => {
+                Ok(CellularState::RoamingOff)
+            }
+            connection_cellular_state_e_CONNECTION_CELLULAR_STATE_CALL_ONLY_AVAILABLE => {
+                Ok(CellularState::CallOnlyAvailable)
+            }
+            connection_cellular_state_e_CONNECTION_CELLULAR_STATE_AVAILABLE => {
+                Ok(CellularState::Available)
+            }
+            connection_cellular_state_e_CONNECTION_CELLULAR_STATE_CONNECTED => {
+                Ok(CellularState::Connected)
+            }
+            _ => Err(Error::Unknown),
+        }
+    }
+}
+
+impl From<CellularState> for connection_cellular_state_e {
+    fn from(state: CellularState) -> connection_cellular_state_e {
+        match state {
+            CellularState::OutOfService => {
+                connection_cellular_state_e_CONNECTION_CELLULAR_STATE_OUT_OF_SERVICE
+            }
+            CellularState::FlightMode => {
+                connection_cellular_state_e_CONNECTION_CELLULAR_STATE_FLIGHT_MODE
+            }
+            CellularState::RoamingOff => {
+                connection_cellular_state_e_CONNECTION_CELLULAR_STATE_ROAMING_OFF
+            }
+            CellularState::CallOnlyAvailable => {
+                connection_cellular_state_e_CONNECTION_CELLULAR_STATE_CALL_ONLY_AVAILABLE
+            }
+            CellularState::Available => {
+                connection_cellular_state_e_CONNECTION_CELLULAR_STATE_AVAILABLE
+            }
+            CellularState::Connected => {
+                connection_cellular_state_e_CONNECTION_CELLULAR_STATE_CONNECTED
+            }
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum WifiState {
+    Deactivated,
+    Disconnected,
+    Connected,
+}
+
+impl TryFrom<connection_wifi_state_e> for WifiState {
+    type Error = Error;
+
+    fn try_from(state: connection_wifi_state_e) -> Result<WifiState> {
+        match state {
+            connection_wifi_state_e_CONNECTION_WIFI_STATE_DEACTIVATED => Ok(WifiState::Deactivated),
+            connection_wifi_state_e_CONNECTION_WIFI_STATE_DISCONNECTED => {
+                Ok(WifiState::Disconnected)
+            }
+            connection_wifi_state_e_CONNECTION_WIFI_STATE_CONNECTED => Ok(WifiState::Connected),
+            _ => Err(Error::Unknown),
+        }
+    }
+}
+
+impl From<WifiState> for connection_wifi_state_e {
+    fn from(state: WifiState) -> connection_wifi_state_e {
+        match state {
+            WifiState::Deactivated => connection_wifi_state_e_CONNECTION_WIFI_STATE_DEACTIVATED,
+            WifiState::Disconnected => connection_wifi_state_e_CONNECTION_WIFI_STATE_DISCONNECTED,
+            WifiState::Connected => connection_wifi_state_e_CONNECTION_WIFI_STATE_CONNECTED,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AddressFamily {
+    Ipv4,
+    Ipv6,
+}
+
+impl From<AddressFamily> for connection_address_family_e {
+    fn from(family: AddressFamily) -> connection_address_family_e {
+        match family {
+            AddressFamily::Ipv4 => connection_address_family_e_CONNECTION_ADDRESS_FAMILY_IPV4,
+            AddressFamily::Ipv6 => connection_address_family_e_CONNECTION_ADDRESS_FAMILY_IPV6,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ProxyType {
+    /// No proxy.
+    Direct,
+    /// Configured by the PAC script at the proxy address.
+    Auto,
+    Manual,
+}
+
+impl TryFrom<connection_proxy_type_e> for ProxyType {
+    type Error = Error;
+
+    fn try_from(proxy_type: connection_proxy_type_e) -> Result<ProxyType> {
+        match proxy_type {
+            connection_proxy_type_e_CONNECTION_PROXY_TYPE_DIRECT => Ok(ProxyType::Direct),
+            connection_proxy_type_e_CONNECTION_PROXY_TYPE_AUTO => Ok(ProxyType::Auto),
+            connection_proxy_type_e_CONNECTION_PROXY_TYPE_MANUAL => Ok(ProxyType::Manual),
+            _ => Err(Error::Unknown),
+        }
+    }
+}
+
+impl From<ProxyType> for connection_proxy_type_e {
+    fn from(proxy_type: ProxyType) -> connection_proxy_type_e {
+        match proxy_type {
+            ProxyType::Direct => connection_proxy_type_e_CONNECTION_PROXY_TYPE_DIRECT,
+            ProxyType::Auto => connection_proxy_type_e_CONNECTION_PROXY_TYPE_AUTO,
+            ProxyType::Manual => connection_proxy_type_e_CONNECTION_PROXY_TYPE_MANUAL,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ConnectionEvent {
+    TypeChanged(ConnectionType),
+    IpAddressChanged {
+        ipv4: Option<String>,
+        ipv6: Option<String>,
+    },
+}
+
+type TypeChangedFn = Box<dyn FnMut(ConnectionType)>;
+type AddressChangedFn = Box<dyn FnMut(Option<&str>, Option<&str>)>;
+
+/// A handle to the connection manager.
+///
+/// Requires the `http://tizen.org/privilege/network.get` privilege.
+pub struct Connection {
+    handle: connection_h,
+    type_changed_fn: Option<Pin<Box<TypeChangedFn>>>,
+    ip_address_changed_fn: Option<Pin<Box<AddressChangedFn>>>,
+}
+
+impl Connection {
+    pub fn new() -> Result<Connection> {
+        let mut handle = null_mut();
+
+        check(unsafe { connection_create(&mut handle) })?;
+
+        Ok(Self {
+            handle,
+            type_changed_fn: None,
+            ip_address_changed_fn: None,
+        })
+    }
+
+    /// Calls `callback_fn` whenever the network used for data changes,
+    /// replacing the previous callback.
+    pub fn set_type_changed_cb<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(ConnectionType),
+        F: 'static,
+    {
+        let (callback_fn, data) = pin_callback::<dyn FnMut(ConnectionType)>(Box::new(callback_fn));
+
+        check(unsafe {
+            connection_set_type_changed_cb(self.handle, Some(type_changed_handler), data)
+        })?;
+        self.type_changed_fn = Some(callback_fn);
+
+        Ok(())
+    }
+
+    pub fn unset_type_changed_cb(&mut self) -> Result<()> {
+        check(unsafe { connection_unset_type_changed_cb(self.handle) })?;
+        self.type_changed_fn = None;
+
+        Ok(())
+    }
+
+    /// Calls `callback_fn` with the new IPv4 and IPv6 addresses whenever they
+    /// change, replacing the previous callback.
+    pub fn set_ip_address_changed_cb<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(Option<&str>, Option<&str>),
+        F: 'static,
+    {
+        let (callback_fn, data) =
+            pin_callback::<dyn FnMut(Option<&str>, Option<&str>)>(Box::new(callback_fn));
+
+        check(unsafe {
+            connection_set_ip_address_changed_cb(self.handle, Some(address_changed_handler), data)
+        })?;
+        self.ip_address_changed_fn = Some(callback_fn);
+
+        Ok(())
+    }
+
+    pub fn unset_ip_address_changed_cb(&mut self) -> Result<()> {
+        check(unsafe { connection_unset_ip_address_changed_cb(self.handle) })?;
+        self.ip_address_changed_fn = None;
+
+        Ok(())
+    }
+
+    /// Returns a stream of connection type and IP address changes.
+    ///
+    /// The stream uses its own connection handle, so it does not replace the
+    /// callbacks set on this one.
+    #[cfg(feature = "async")]
+    pub fn events() -> Result<EventStream<ConnectionEvent>> {
+        EventStream::new(|sender| {
+            let mut connection = Connection::new()?;
+
+            let type_sender = sender.clone();
+            connection.set_type_changed_cb(move |connection_type| {
+                type_sender.send(ConnectionEvent::TypeChanged(connection_type))
+            })?;
+
+            connection.set_ip_address_changed_cb(move |ipv4, ipv6| {
+                sender.send(ConnectionEvent::IpAddressChanged {
+                    ipv4: ipv4.map(String::from),
+                    ipv6: ipv6.map(String::from),
+                })
+            })?;
+
+            Ok(connection)
+        })
+    }
+
+    pub fn get_type(&self) -> Result<ConnectionType> {
+        let mut connection_type = connection_type_e_CONNECTION_TYPE_DISCONNECTED;
+
+        check(unsafe { connection_get_type(self.handle, &mut connection_type) })?;
+
+        ConnectionType::try_from(connection_type)
+    }
+
+    /// Whether any network is currently usable for data.
+    pub fn is_online(&self) -> Result<bool> {
+        Ok(self.get_type()? != ConnectionType::Disconnected)
+    }
+
+    pub fn get_cellular_state(&self) -> Result<CellularState> {
+        let mut state = connection_cellular_state_e_CONNECTION_CELLULAR_STATE_OUT_OF_SERVICE;
+
+        check(unsafe { connection_get_cellular_state(self.handle, &mut state) })?;
+
+        CellularState::try_from(state)
+    }
+
+    pub fn get_wifi_state(&self) -> Result<WifiState> {
+        let mut state = connection_wifi_state_e_CONNECTION_WIFI_STATE_DEACTIVATED;
+
+        check(unsafe { connection_get_wifi_state(self.handle, &mut state) })?;
+
+        WifiState::try_from(state)
+    }
+
+    /// Returns the IP address of the current connection.
+    pub fn get_ip_address(&self, family: AddressFamily) -> Result<String> {
+        let mut address = null_mut();
+
+        check(unsafe { connection_get_ip_address(self.handle, family.into(), &mut address) })?;
+
+        unsafe { take_string(address) }.ok_or(Error::NoData)
+    }
+
+    /// Returns the proxy of the current connection, `None` if no proxy is set.
+    pub fn get_proxy(&self, family: AddressFamily) -> Result<Option<String>> {
+        let mut proxy = null_mut();
+
+        check(unsafe { connection_get_proxy(self.handle, family.into(), &mut proxy) })?;
+
+        Ok(unsafe { take_string(proxy) }.filter(|proxy| !proxy.is_empty()))
+    }
+
+    /// Returns the MAC address of the Wi-Fi or Ethernet interface.
+    pub fn get_mac_address(&self, connection_type: ConnectionType) -> Result<String> {
+        let mut address = null_mut();
+
+        check(unsafe {
+            connection_get_mac_address(self.handle, connection_type.into(), &mut address)
+        })?;
+
+        unsafe { take_string(address) }.ok_or(Error::NoData)
+    }
+
+    /// Returns the profile of the network currently used for data.
+    pub fn get_current_profile(&self) -> Result<ConnectionProfile<'_>> {
+        let mut handle = null_mut();
+
+        check(unsafe { connection_get_current_profile(self.handle, &mut handle) })?;
+
+        Ok(ConnectionProfile {
+            handle,
+            _connection: PhantomData,
+        })
+    }
+}
+
+/// The configuration of a network interface.
+pub struct ConnectionProfile<'c> {
+    handle: connection_profile_h,
+    _connection: PhantomData<&'c Connection>,
+}
+
+impl<'c> ConnectionProfile<'c> {
+    fn get_address(
+        &self,
+        get: unsafe extern "C" fn(
+            connection_profile_h,
+            connection_address_family_e,
+            *mut *mut c_char,
+        ) -> c_int,
+        family: AddressFamily,
+    ) -> Result<Option<String>> {
+        let mut address = null_mut();
+
+        check(unsafe { get(self.handle, family.into(), &mut address) })?;
+
+        Ok(unsafe { take_string(address) }.filter(|address| !address.is_empty()))
+    }
+
+    /// The name of the interface, e.g. "wlan0".
+    pub fn get_interface_name(&self) -> Result<String> {
+        let mut name = null_mut();
+
+        check(unsafe { connection_profile_get_network_interface_name(self.handle, &mut name) })?;
+
+        unsafe { take_string(name) }.ok_or(Error::NoData)
+    }
+
+    pub fn get_ip_address(&self, family: AddressFamily) -> Result<Option<String>> {
+        self.get_address(connection_profile_get_ip_address, family)
+    }
+
+    pub fn get_subnet_mask(&self, family: AddressFamily) -> Result<Option<String>> {
+        self.get_address(connection_profile_get_subnet_mask, family)
+    }
+
+    pub fn get_gateway_address(&self, family: AddressFamily) -> Result<Option<String>> {
+        self.get_address(connection_profile_get_gateway_address, family)
+    }
+
+    /// Returns the configured DNS servers, the primary one first.
+    pub fn get_dns_servers(&self, family: AddressFamily) -> Result<Vec<String>> {
+        let mut servers = Vec::new();
+
+        // the platform keeps up to two servers, numbered from 1
+        for order in 1..=2 {
+            let mut address = null_mut();
+
+            check(unsafe {
+                connection_profile_get_dns_address(self.handle, order, family.into(), &mut address)
+            })?;
+
+            if let Some(address) = unsafe { take_string(address) }.filter(|a| !a.is_empty()) {
+                servers.push(address);
+            }
+        }
+
+        Ok(servers)
+    }
+
+    pub fn get_proxy_type(&self) -> Result<ProxyType> {
+        let mut proxy_type = connection_proxy_type_e_CONNECTION_PROXY_TYPE_DIRECT;
+
+        check(unsafe { connection_profile_get_proxy_type(self.handle, &mut proxy_type) })?;
+
+        ProxyType::try_from(proxy_type)
+    }
+
+    /// The proxy as `host:port`, or the PAC script URL for [`ProxyType::Auto`].
+    pub fn get_proxy_address(&self, family: AddressFamily) -> Result<Option<String>> {
+        self.get_address(connection_profile_get_proxy_address, family)
+    }
+}
+
+impl<'c> Drop for ConnectionProfile<'c> {
+    fn drop(&mut self) {
+        unsafe {
+            connection_profile_destroy(self.handle);
+        }
+    }
+}
+
+impl Drop for Connection {
+    fn drop(&mut self) {
+        unsafe {
+            connection_destroy(self.handle);
+        }
+    }
+}
+
+extern "C" fn type_changed_handler(connection_type: connection_type_e, user_data: *mut c_void) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut TypeChangedFn) };
+
+        if let Ok(connection_type) = ConnectionType::try_from(connection_type) {
+            closure(connection_type);
+        }
+    })
+}
+
+extern "C" fn address_changed_handler(
+    ipv4_address: *const c_char,
+    ipv6_address: *const c_char,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut AddressChangedFn) };
+        let ipv4 = unsafe { borrow_str(ipv4_address) };
+        let ipv6 = unsafe { borrow_str(ipv6_address) };
+
+        closure(ipv4.as_deref(), ipv6.as_deref());
+    })
+}
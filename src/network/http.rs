@@ -0,0 +1,432 @@
+//! A small HTTP client using the platform's curl based stack, TLS included.
+//!
+//! Requires the `http://tizen.org/privilege/internet` privilege.
+
+use crate::error::{check, Error, Result};
+use crate::rutin::{abort_on_panic, cstring, take_string};
+use rutin_tizen_sys::{
+    http_deinit, http_init, http_method_e, http_method_e_HTTP_METHOD_CONNECT,
+    http_method_e_HTTP_METHOD_DELETE, http_method_e_HTTP_METHOD_GET,
+    http_method_e_HTTP_METHOD_HEAD, http_method_e_HTTP_METHOD_OPTIONS,
+    http_method_e_HTTP_METHOD_POST, http_method_e_HTTP_METHOD_PUT, http_method_e_HTTP_METHOD_TRACE,
+    http_session_create, http_session_destroy, http_session_h,
+    http_session_mode_e_HTTP_SESSION_MODE_NORMAL, http_session_open_transaction,
+    http_transaction_cancel, http_transaction_destroy, http_transaction_get_status_code,
+    http_transaction_h, http_transaction_header_add_field, http_transaction_header_get_field_value,
+    http_transaction_request_set_uri, http_transaction_request_write_body,
+    http_transaction_set_aborted_cb, http_transaction_set_completed_cb,
+    http_transaction_set_interface_name, http_transaction_set_received_body_cb,
+    http_transaction_set_received_header_cb, http_transaction_set_server_certificate_verification,
+    http_transaction_set_timeout, http_transaction_submit,
+};
+use std::cell::RefCell;
+use std::ffi::CString;
+use std::marker::PhantomData;
+use std::os::raw::{c_char, c_int, c_void};
+use std::pin::Pin;
+use std::ptr::null_mut;
+use std::rc::Rc;
+
+/// Initializes the HTTP API, required before creating a [`Session`].
+pub fn initialize() -> Result<()> {
+    check(unsafe { http_init() })
+}
+
+/// Releases the resources taken by [`initialize`].
+pub fn deinitialize() -> Result<()> {
+    check(unsafe { http_deinit() })
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Method {
+    Get,
+    Options,
+    Head,
+    Delete,
+    Trace,
+    Post,
+    Put,
+    Connect,
+}
+
+impl From<Method> for http_method_e {
+    fn from(method: Method) -> http_method_e {
+        match method {
+            Method::Get => http_method_e_HTTP_METHOD_GET,
+            Method::Options => http_method_e_HTTP_METHOD_OPTIONS,
+            Method::Head => http_method_e_HTTP_METHOD_HEAD,
+            Method::Delete => http_method_e_HTTP_METHOD_DELETE,
+            Method::Trace => http_method_e_HTTP_METHOD_TRACE,
+            Method::Post => http_method_e_HTTP_METHOD_POST,
+            Method::Put => http_method_e_HTTP_METHOD_PUT,
+            Method::Connect => http_method_e_HTTP_METHOD_CONNECT,
+        }
+    }
+}
+
+/// A request to be sent with [`Session::send`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Request {
+    method: Method,
+    uri: String,
+    headers: Vec<(String, String)>,
+    body: Option<Vec<u8>>,
+    timeout: Option<u32>,
+    verify_certificate: bool,
+    interface_name: Option<String>,
+}
+
+impl Request {
+    pub fn new(method: Method, uri: &str) -> Request {
+        Self {
+            method,
+            uri: uri.to_owned(),
+            headers: Vec::new(),
+            body: None,
+            timeout: None,
+            verify_certificate: true,
+            interface_name: None,
+        }
+    }
+
+    pub fn get(uri: &str) -> Request {
+        Self::new(Method::Get, uri)
+    }
+
+    pub fn post(uri: &str) -> Request {
+        Self::new(Method::Post, uri)
+    }
+
+    pub fn header(mut self, field: &str, value: &str) -> Request {
+        self.headers.push((field.to_owned(), value.to_owned()));
+        self
+    }
+
+    /// The request body, sent with its length as `Content-Length` unless that header is set.
+    ///
+    /// The platform takes the body as a C string, so [`Session::send`] fails with
+    /// [`Error::InvalidParameter`] if it contains NUL bytes.
+    pub fn body<B>(mut self, body: B) -> Request
+    where
+        B: Into<Vec<u8>>,
+    {
+        self.body = Some(body.into());
+        self
+    }
+
+    /// Timeout of the whole transaction, in seconds.
+    pub fn timeout(mut self, seconds: u32) -> Request {
+        self.timeout = Some(seconds);
+        self
+    }
+
+    /// Whether to check the server certificate, on by default.
+    pub fn verify_certificate(mut self, verify: bool) -> Request {
+        self.verify_certificate = verify;
+        self
+    }
+
+    /// Sends the request through a specific network interface, e.g. the one
+    /// returned by [`crate::network::connection::Connection`].
+    pub fn interface_name(mut self, name: &str) -> Request {
+        self.interface_name = Some(name.to_owned());
+        self
+    }
+}
+
+/// The status and headers of a completed transaction, those of the last response when
+/// redirected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Response {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+}
+
+impl Response {
+    /// Returns the first header named `field`, ignoring case.
+    pub fn header(&self, field: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(field))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+pub struct Session {
+    handle: http_session_h,
+}
+
+impl Session {
+    pub fn new() -> Result<Session> {
+        let mut handle = null_mut();
+        check(unsafe {
+            http_session_create(http_session_mode_e_HTTP_SESSION_MODE_NORMAL, &mut handle)
+        })?;
+
+        Ok(Self { handle })
+    }
+
+    /// Submits `request`, calling `body_fn` with each chunk of the response body
+    /// as it arrives, then `done_fn` once the transaction completes or fails.
+    ///
+    /// Dropping the returned transaction cancels it.
+    pub fn send<B, D>(&self, request: &Request, body_fn: B, done_fn: D) -> Result<Transaction<'_>>
+    where
+        B: FnMut(&[u8]),
+        B: 'static,
+        D: FnOnce(Result<Response>),
+        D: 'static,
+    {
+        let mut handle = null_mut();
+        check(unsafe {
+            http_session_open_transaction(self.handle, request.method.into(), &mut handle)
+        })?;
+
+        let mut transaction = Transaction {
+            handle,
+            state: Box::pin(TransactionState {
+                headers: Vec::new(),
+                body_fn: Box::new(body_fn),
+                done_fn: Some(Box::new(done_fn)),
+            }),
+            _session: PhantomData,
+        };
+
+        transaction.prepare(request)?;
+        check(unsafe { http_transaction_submit(transaction.handle) })?;
+
+        Ok(transaction)
+    }
+
+    /// Submits `request` and collects the whole response body.
+    pub fn fetch<F>(&self, request: &Request, done_fn: F) -> Result<Transaction<'_>>
+    where
+        F: FnOnce(Result<(Response, Vec<u8>)>),
+        F: 'static,
+    {
+        let body = Rc::new(RefCell::new(Vec::new()));
+        let received = body.clone();
+
+        self.send(
+            request,
+            move |chunk| received.borrow_mut().extend_from_slice(chunk),
+            move |result| done_fn(result.map(|response| (response, body.take()))),
+        )
+    }
+}
+
+impl Drop for Session {
+    fn drop(&mut self) {
+        unsafe {
+            http_session_destroy(self.handle);
+        }
+    }
+}
+
+struct TransactionState {
+    headers: Vec<(String, String)>,
+    body_fn: Box<dyn FnMut(&[u8])>,
+    done_fn: Option<Box<dyn FnOnce(Result<Response>)>>,
+}
+
+/// A submitted request, cancelled when dropped.
+pub struct Transaction<'s> {
+    handle: http_transaction_h,
+    state: Pin<Box<TransactionState>>,
+    _session: PhantomData<&'s Session>,
+}
+
+impl<'s> Transaction<'s> {
+    fn prepare(&mut self, request: &Request) -> Result<()> {
+        let uri = cstring(request.uri.as_str())?;
+        check(unsafe { http_transaction_request_set_uri(self.handle, uri.as_ptr()) })?;
+
+        for (field, value) in &request.headers {
+            let field = cstring(field.as_str())?;
+            let value = cstring(value.as_str())?;
+
+            check(unsafe {
+                http_transaction_header_add_field(self.handle, field.as_ptr(), value.as_ptr())
+            })?;
+        }
+
+        if let Some(body) = &request.body {
+            // the native call finds the end of the body with strlen, the length is only
+            // explicit in the header
+            let has_length = request
+                .headers
+                .iter()
+                .any(|(field, _)| field.eq_ignore_ascii_case("content-length"));
+
+            if !has_length {
+                let field = cstring("Content-Length")?;
+                let value = cstring(&body.len().to_string())?;
+
+                check(unsafe {
+                    http_transaction_header_add_field(self.handle, field.as_ptr(), value.as_ptr())
+                })?;
+            }
+
+            let body = CString::new(body.as_slice()).map_err(|_| Error::InvalidParameter)?;
+            check(unsafe { http_transaction_request_write_body(self.handle, body.as_ptr()) })?;
+        }
+
+        if let Some(timeout) = request.timeout {
+            check(unsafe { http_transaction_set_timeout(self.handle, timeout as c_int) })?;
+        }
+
+        if let Some(name) = &request.interface_name {
+            let name = cstring(name.as_str())?;
+            check(unsafe { http_transaction_set_interface_name(self.handle, name.as_ptr()) })?;
+        }
+
+        check(unsafe {
+            http_transaction_set_server_certificate_verification(
+                self.handle,
+                request.verify_certificate,
+            )
+        })?;
+
+        let data = &*self.state as *const TransactionState as *mut c_void;
+
+        unsafe {
+            check(http_transaction_set_received_header_cb(
+                self.handle,
+                Some(header_handler),
+                data,
+            ))?;
+            check(http_transaction_set_received_body_cb(
+                self.handle,
+                Some(body_handler),
+                data,
+            ))?;
+            check(http_transaction_set_completed_cb(
+                self.handle,
+                Some(completed_handler),
+                data,
+            ))?;
+            check(http_transaction_set_aborted_cb(
+                self.handle,
+                Some(aborted_handler),
+                data,
+            ))?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns a header of the response, once it has been received.
+    pub fn header(&self, field: &str) -> Option<String> {
+        let field = CString::new(field).ok()?;
+        let mut value = null_mut();
+
+        check(unsafe {
+            http_transaction_header_get_field_value(self.handle, field.as_ptr(), &mut value)
+        })
+        .ok()?;
+
+        unsafe { take_string(value) }
+    }
+
+    pub fn cancel(&mut self) -> Result<()> {
+        check(unsafe { http_transaction_cancel(self.handle) })
+    }
+}
+
+impl<'s> Drop for Transaction<'s> {
+    fn drop(&mut self) {
+        unsafe {
+            if self.state.done_fn.is_some() {
+                http_transaction_cancel(self.handle);
+            }
+            http_transaction_destroy(self.handle);
+        }
+    }
+}
+
+fn state<'a>(user_data: *mut c_void) -> &'a mut TransactionState {
+    unsafe { &mut *(user_data as *mut TransactionState) }
+}
+
+extern "C" fn header_handler(
+    _transaction: http_transaction_h,
+    header: *mut c_char,
+    header_len: usize,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let state = state(user_data);
+        let header = unsafe { std::slice::from_raw_parts(header as *const u8, header_len) };
+
+        // the status line and the blank line ending the headers have no colon
+        for line in String::from_utf8_lossy(header).lines() {
+            // a redirect is followed by the headers of the next response, only the last
+            // response's are kept
+            if line.starts_with("HTTP/") {
+                state.headers.clear();
+            } else if let Some(colon) = line.find(':') {
+                let (name, value) = line.split_at(colon);
+                state
+                    .headers
+                    .push((name.trim().to_owned(), value[1..].trim().to_owned()));
+            }
+        }
+    })
+}
+
+extern "C" fn body_handler(
+    _transaction: http_transaction_h,
+    body: *mut c_char,
+    size: usize,
+    count: usize,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let state = state(user_data);
+
+        if !body.is_null() {
+            let body = unsafe { std::slice::from_raw_parts(body as *const u8, size * count) };
+            (state.body_fn)(body);
+        }
+    })
+}
+
+extern "C" fn completed_handler(
+    transaction: http_transaction_h,
+    _body: *mut c_char,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let state = state(user_data);
+
+        if let Some(done_fn) = state.done_fn.take() {
+            let mut status = 0;
+            let result =
+                check(unsafe { http_transaction_get_status_code(transaction, &mut status) }).map(
+                    |_| Response {
+                        status: status as u16,
+                        headers: std::mem::take(&mut state.headers),
+                    },
+                );
+
+            done_fn(result);
+        }
+    })
+}
+
+extern "C" fn aborted_handler(
+    _transaction: http_transaction_h,
+    reason: c_int,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let state = state(user_data);
+
+        if let Some(done_fn) = state.done_fn.take() {
+            done_fn(Err(check(reason).err().unwrap_or(Error::Canceled)));
+        }
+    })
+}
@@ -0,0 +1,293 @@
+use super::{read_attributes, Attributes, RawRepresentation, ResponseResult};
+use crate::error::{check, Result};
+use crate::rutin::{abort_on_panic, borrow_str, cstring, pin_callback};
+use rutin_tizen_sys::{
+    iotcon_connectivity_type_e_IOTCON_CONNECTIVITY_ALL, iotcon_find_resource,
+    iotcon_observe_policy_e_IOTCON_OBSERVE_IGNORE_OUT_OF_ORDER, iotcon_query_create,
+    iotcon_query_destroy, iotcon_query_h, iotcon_query_set_resource_type,
+    iotcon_remote_resource_clone, iotcon_remote_resource_destroy, iotcon_remote_resource_get,
+    iotcon_remote_resource_get_device_id, iotcon_remote_resource_get_host_address,
+    iotcon_remote_resource_get_uri_path, iotcon_remote_resource_h,
+    iotcon_remote_resource_observe_deregister, iotcon_remote_resource_observe_register,
+    iotcon_remote_resource_put, iotcon_request_type_e, iotcon_response_get_representation,
+    iotcon_response_get_result, iotcon_response_h, iotcon_response_result_e_IOTCON_RESPONSE_OK,
+};
+use std::os::raw::{c_char, c_int, c_void};
+use std::pin::Pin;
+use std::ptr::{null, null_mut};
+
+/// The answer of a remote resource to a request.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RemoteResponse {
+    pub result: ResponseResult,
+    pub attributes: Attributes,
+}
+
+impl RemoteResponse {
+    unsafe fn from_raw(response: iotcon_response_h) -> Result<RemoteResponse> {
+        let mut result = iotcon_response_result_e_IOTCON_RESPONSE_OK;
+        check(iotcon_response_get_result(response, &mut result))?;
+
+        let mut representation = null_mut();
+        let attributes = match check(iotcon_response_get_representation(
+            response,
+            &mut representation,
+        )) {
+            Ok(()) => read_attributes(representation)?,
+            Err(_) => Attributes::new(),
+        };
+
+        Ok(Self {
+            result: result.into(),
+            attributes,
+        })
+    }
+}
+
+type FindFn = Box<dyn FnMut(Result<RemoteResource>) -> bool>;
+
+/// Looks for resources, on `host_address` or by multicast when `None`, optionally
+/// filtered by resource type.
+///
+/// `callback_fn` is called with each resource found until it returns `false`,
+/// or with an error once the search times out.
+pub fn find_resource<F>(
+    host_address: Option<&str>,
+    resource_type: Option<&str>,
+    callback_fn: F,
+) -> Result<()>
+where
+    F: FnMut(Result<RemoteResource>) -> bool,
+    F: 'static,
+{
+    let host_address = host_address.map(cstring).transpose()?;
+    let resource_type = resource_type.map(cstring).transpose()?;
+
+    let mut query: iotcon_query_h = null_mut();
+
+    if let Some(resource_type) = &resource_type {
+        check(unsafe { iotcon_query_create(&mut query) })?;
+
+        if let Err(e) =
+            check(unsafe { iotcon_query_set_resource_type(query, resource_type.as_ptr()) })
+        {
+            unsafe { iotcon_query_destroy(query) };
+            return Err(e);
+        }
+    }
+
+    let callback_fn: FindFn = Box::new(callback_fn);
+    let data = Box::into_raw(Box::new(callback_fn));
+
+    let ret = unsafe {
+        iotcon_find_resource(
+            host_address.as_ref().map_or(null(), |h| h.as_ptr()),
+            iotcon_connectivity_type_e_IOTCON_CONNECTIVITY_ALL as c_int,
+            query,
+            Some(found_handler),
+            data as *mut c_void,
+        )
+    };
+
+    if !query.is_null() {
+        unsafe { iotcon_query_destroy(query) };
+    }
+
+    if let Err(e) = check(ret) {
+        drop(unsafe { Box::from_raw(data) });
+        return Err(e);
+    }
+
+    Ok(())
+}
+
+type ResponseFn = dyn FnOnce(Result<RemoteResponse>);
+type ObserveFn = Box<dyn FnMut(Result<RemoteResponse>)>;
+
+/// A resource hosted by another device.
+pub struct RemoteResource {
+    handle: iotcon_remote_resource_h,
+    observe_fn: Option<Pin<Box<ObserveFn>>>,
+}
+
+impl RemoteResource {
+    fn get_str(
+        &self,
+        get: unsafe extern "C" fn(iotcon_remote_resource_h, *mut *mut c_char) -> c_int,
+    ) -> Result<String> {
+        // points inside the resource, it must not be freed
+        let mut value = null_mut();
+        check(unsafe { get(self.handle, &mut value) })?;
+
+        Ok(unsafe { borrow_str(value) }
+            .unwrap_or_default()
+            .into_owned())
+    }
+
+    pub fn uri_path(&self) -> Result<String> {
+        self.get_str(iotcon_remote_resource_get_uri_path)
+    }
+
+    pub fn host_address(&self) -> Result<String> {
+        self.get_str(iotcon_remote_resource_get_host_address)
+    }
+
+    pub fn device_id(&self) -> Result<String> {
+        self.get_str(iotcon_remote_resource_get_device_id)
+    }
+
+    /// Reads the state of the resource, `callback_fn` is called with the response.
+    pub fn get<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnOnce(Result<RemoteResponse>),
+        F: 'static,
+    {
+        let callback_fn: Box<ResponseFn> = Box::new(callback_fn);
+        let data = Box::into_raw(Box::new(callback_fn));
+
+        let ret = unsafe {
+            iotcon_remote_resource_get(
+                self.handle,
+                null_mut(),
+                Some(response_handler),
+                data as *mut c_void,
+            )
+        };
+
+        if let Err(e) = check(ret) {
+            drop(unsafe { Box::from_raw(data) });
+            return Err(e);
+        }
+
+        Ok(())
+    }
+
+    /// Replaces the state of the resource, `callback_fn` is called with the response.
+    pub fn put<F>(&mut self, attributes: &Attributes, callback_fn: F) -> Result<()>
+    where
+        F: FnOnce(Result<RemoteResponse>),
+        F: 'static,
+    {
+        let representation = RawRepresentation::new(attributes)?;
+
+        let callback_fn: Box<ResponseFn> = Box::new(callback_fn);
+        let data = Box::into_raw(Box::new(callback_fn));
+
+        let ret = unsafe {
+            iotcon_remote_resource_put(
+                self.handle,
+                representation.handle,
+                null_mut(),
+                Some(response_handler),
+                data as *mut c_void,
+            )
+        };
+
+        if let Err(e) = check(ret) {
+            drop(unsafe { Box::from_raw(data) });
+            return Err(e);
+        }
+
+        Ok(())
+    }
+
+    /// Calls `callback_fn` each time the resource notifies a change, until
+    /// [`RemoteResource::stop_observing`] is called or this is dropped.
+    pub fn observe<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(Result<RemoteResponse>),
+        F: 'static,
+    {
+        let (callback_fn, data) =
+            pin_callback::<dyn FnMut(Result<RemoteResponse>)>(Box::new(callback_fn));
+
+        check(unsafe {
+            iotcon_remote_resource_observe_register(
+                self.handle,
+                iotcon_observe_policy_e_IOTCON_OBSERVE_IGNORE_OUT_OF_ORDER,
+                null_mut(),
+                Some(observe_handler),
+                data,
+            )
+        })?;
+        self.observe_fn = Some(callback_fn);
+
+        Ok(())
+    }
+
+    pub fn stop_observing(&mut self) -> Result<()> {
+        check(unsafe { iotcon_remote_resource_observe_deregister(self.handle) })?;
+        self.observe_fn = None;
+
+        Ok(())
+    }
+}
+
+impl Drop for RemoteResource {
+    fn drop(&mut self) {
+        unsafe {
+            if self.observe_fn.is_some() {
+                iotcon_remote_resource_observe_deregister(self.handle);
+            }
+            iotcon_remote_resource_destroy(self.handle);
+        }
+    }
+}
+
+extern "C" fn found_handler(
+    resource: iotcon_remote_resource_h,
+    result: c_int,
+    user_data: *mut c_void,
+) -> bool {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut FindFn) };
+
+        // the found resource is only valid during the callback
+        let found = check(result).and_then(|_| {
+            let mut handle = null_mut();
+            check(unsafe { iotcon_remote_resource_clone(resource, &mut handle) })?;
+
+            Ok(RemoteResource {
+                handle,
+                observe_fn: None,
+            })
+        });
+
+        let finished = found.is_err();
+        let more = closure(found);
+
+        if finished || !more {
+            drop(unsafe { Box::from_raw(user_data as *mut FindFn) });
+            false
+        } else {
+            true
+        }
+    })
+}
+
+extern "C" fn response_handler(
+    _resource: iotcon_remote_resource_h,
+    result: c_int,
+    _request_type: iotcon_request_type_e,
+    response: iotcon_response_h,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let closure = unsafe { Box::from_raw(user_data as *mut Box<ResponseFn>) };
+        closure(check(result).and_then(|_| unsafe { RemoteResponse::from_raw(response) }));
+    })
+}
+
+extern "C" fn observe_handler(
+    _resource: iotcon_remote_resource_h,
+    result: c_int,
+    _sequence_number: c_int,
+    response: iotcon_response_h,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut ObserveFn) };
+        closure(check(result).and_then(|_| unsafe { RemoteResponse::from_raw(response) }));
+    })
+}
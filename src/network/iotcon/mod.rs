@@ -0,0 +1,227 @@
+//! IoT Connectivity, exposing and using OCF resources on the local network.
+//!
+//! Requires the `http://tizen.org/privilege/internet` and
+//! `http://tizen.org/privilege/network.get` privileges.
+
+mod client;
+mod server;
+
+pub use client::{find_resource, RemoteResource, RemoteResponse};
+pub use server::{policy, Request, RequestType, Resource, INTERFACE_BASELINE};
+
+use crate::error::{check, Error, Result};
+use crate::rutin::{abort_on_panic, borrow_str, cstring, path_cstring};
+use rutin_tizen_sys::{
+    iotcon_attributes_add_bool, iotcon_attributes_add_double, iotcon_attributes_add_int,
+    iotcon_attributes_add_str, iotcon_attributes_create, iotcon_attributes_destroy,
+    iotcon_attributes_foreach, iotcon_attributes_get_bool, iotcon_attributes_get_double,
+    iotcon_attributes_get_int, iotcon_attributes_get_str, iotcon_attributes_get_type,
+    iotcon_attributes_h, iotcon_deinitialize, iotcon_initialize, iotcon_representation_create,
+    iotcon_representation_destroy, iotcon_representation_get_attributes, iotcon_representation_h,
+    iotcon_representation_set_attributes, iotcon_response_result_e,
+    iotcon_response_result_e_IOTCON_RESPONSE_ERROR,
+    iotcon_response_result_e_IOTCON_RESPONSE_FORBIDDEN,
+    iotcon_response_result_e_IOTCON_RESPONSE_OK,
+    iotcon_response_result_e_IOTCON_RESPONSE_RESOURCE_CHANGED,
+    iotcon_response_result_e_IOTCON_RESPONSE_RESOURCE_CREATED,
+    iotcon_response_result_e_IOTCON_RESPONSE_RESOURCE_DELETED,
+    iotcon_response_result_e_IOTCON_RESPONSE_SLOW, iotcon_type_e_IOTCON_TYPE_BOOL,
+    iotcon_type_e_IOTCON_TYPE_DOUBLE, iotcon_type_e_IOTCON_TYPE_INT,
+    iotcon_type_e_IOTCON_TYPE_NONE, iotcon_type_e_IOTCON_TYPE_STR,
+};
+use std::collections::BTreeMap;
+use std::os::raw::{c_char, c_void};
+use std::path::Path;
+use std::ptr::null_mut;
+
+/// Initializes IoTCon, `svr_db_path` being the security resources database,
+/// created on first use if missing.
+pub fn initialize(svr_db_path: &Path) -> Result<()> {
+    let path = path_cstring(svr_db_path)?;
+
+    check(unsafe { iotcon_initialize(path.as_ptr()) })
+}
+
+pub fn deinitialize() {
+    unsafe { iotcon_deinitialize() }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Value {
+    Int(i32),
+    Bool(bool),
+    Double(f64),
+    Str(String),
+}
+
+/// The state of a resource, nested attributes and lists are not supported.
+pub type Attributes = BTreeMap<String, Value>;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ResponseResult {
+    Ok,
+    Error,
+    ResourceCreated,
+    ResourceDeleted,
+    ResourceChanged,
+    Slow,
+    Forbidden,
+}
+
+impl From<ResponseResult> for iotcon_response_result_e {
+    fn from(result: ResponseResult) -> iotcon_response_result_e {
+        match result {
+            ResponseResult::Ok => iotcon_response_result_e_IOTCON_RESPONSE_OK,
+            ResponseResult::Error => iotcon_response_result_e_IOTCON_RESPONSE_ERROR,
+            ResponseResult::ResourceCreated => {
+                iotcon_response_result_e_IOTCON_RESPONSE_RESOURCE_CREATED
+            }
+            ResponseResult::ResourceDeleted => {
+                iotcon_response_result_e_IOTCON_RESPONSE_RESOURCE_DELETED
+            }
+            ResponseResult::ResourceChanged => {
+                iotcon_response_result_e_IOTCON_RESPONSE_RESOURCE_CHANGED
+            }
+            ResponseResult::Slow => iotcon_response_result_e_IOTCON_RESPONSE_SLOW,
+            ResponseResult::Forbidden => iotcon_response_result_e_IOTCON_RESPONSE_FORBIDDEN,
+        }
+    }
+}
+
+impl From<iotcon_response_result_e> for ResponseResult {
+    fn from(result: iotcon_response_result_e) -> ResponseResult {
+        match result {
+            iotcon_response_result_e_IOTCON_RESPONSE_OK => ResponseResult::Ok,
+            iotcon_response_result_e_IOTCON_RESPONSE_RESOURCE_CREATED => {
+                ResponseResult::ResourceCreated
+            }
+            iotcon_response_result_e_IOTCON_RESPONSE_RESOURCE_DELETED => {
+                ResponseResult::ResourceDeleted
+            }
+            iotcon_response_result_e_IOTCON_RESPONSE_RESOURCE_CHANGED => {
+                ResponseResult::ResourceChanged
+            }
+            iotcon_response_result_e_IOTCON_RESPONSE_SLOW => ResponseResult::Slow,
+            iotcon_response_result_e_IOTCON_RESPONSE_FORBIDDEN => ResponseResult::Forbidden,
+            _ => ResponseResult::Error,
+        }
+    }
+}
+
+/// A representation built from [`Attributes`], destroyed with this value.
+struct RawRepresentation {
+    handle: iotcon_representation_h,
+}
+
+impl RawRepresentation {
+    fn new(attributes: &Attributes) -> Result<RawRepresentation> {
+        let mut handle = null_mut();
+        check(unsafe { iotcon_representation_create(&mut handle) })?;
+        let representation = Self { handle };
+
+        let mut raw = null_mut();
+        check(unsafe { iotcon_attributes_create(&mut raw) })?;
+
+        let result = add_attributes(raw, attributes)
+            .and_then(|_| check(unsafe { iotcon_representation_set_attributes(handle, raw) }));
+        unsafe { iotcon_attributes_destroy(raw) };
+
+        result.map(|_| representation)
+    }
+}
+
+impl Drop for RawRepresentation {
+    fn drop(&mut self) {
+        unsafe {
+            iotcon_representation_destroy(self.handle);
+        }
+    }
+}
+
+fn add_attributes(raw: iotcon_attributes_h, attributes: &Attributes) -> Result<()> {
+    for (key, value) in attributes {
+        let key = cstring(key.as_str())?;
+
+        check(unsafe {
+            match value {
+                Value::Int(value) => iotcon_attributes_add_int(raw, key.as_ptr(), *value),
+                Value::Bool(value) => iotcon_attributes_add_bool(raw, key.as_ptr(), *value),
+                Value::Double(value) => iotcon_attributes_add_double(raw, key.as_ptr(), *value),
+                Value::Str(value) => {
+                    let value = cstring(value.as_str())?;
+                    iotcon_attributes_add_str(raw, key.as_ptr(), value.as_ptr() as *mut c_char)
+                }
+            }
+        })?;
+    }
+
+    Ok(())
+}
+
+/// Copies the attributes of a representation owned by the platform.
+unsafe fn read_attributes(representation: iotcon_representation_h) -> Result<Attributes> {
+    let mut raw = null_mut();
+    check(iotcon_representation_get_attributes(
+        representation,
+        &mut raw,
+    ))?;
+
+    let mut attributes = Attributes::new();
+    check(iotcon_attributes_foreach(
+        raw,
+        Some(attribute_handler),
+        &mut attributes as *mut Attributes as *mut c_void,
+    ))?;
+
+    Ok(attributes)
+}
+
+unsafe fn read_value(raw: iotcon_attributes_h, key: *const c_char) -> Option<Value> {
+    let mut value_type = iotcon_type_e_IOTCON_TYPE_NONE;
+    check(iotcon_attributes_get_type(raw, key, &mut value_type)).ok()?;
+
+    match value_type {
+        iotcon_type_e_IOTCON_TYPE_INT => {
+            let mut value = 0;
+            check(iotcon_attributes_get_int(raw, key, &mut value)).ok()?;
+            Some(Value::Int(value))
+        }
+        iotcon_type_e_IOTCON_TYPE_BOOL => {
+            let mut value = false;
+            check(iotcon_attributes_get_bool(raw, key, &mut value)).ok()?;
+            Some(Value::Bool(value))
+        }
+        iotcon_type_e_IOTCON_TYPE_DOUBLE => {
+            let mut value = 0.0;
+            check(iotcon_attributes_get_double(raw, key, &mut value)).ok()?;
+            Some(Value::Double(value))
+        }
+        iotcon_type_e_IOTCON_TYPE_STR => {
+            // points inside the attributes, it must not be freed
+            let mut value = null_mut();
+            check(iotcon_attributes_get_str(raw, key, &mut value)).ok()?;
+            Some(Value::Str(
+                borrow_str(value).unwrap_or_default().into_owned(),
+            ))
+        }
+        _ => None,
+    }
+}
+
+extern "C" fn attribute_handler(
+    raw: iotcon_attributes_h,
+    key: *const c_char,
+    user_data: *mut c_void,
+) -> bool {
+    abort_on_panic(|| {
+        let attributes = unsafe { &mut *(user_data as *mut Attributes) };
+
+        if let (Some(name), Some(value)) = unsafe { (borrow_str(key), read_value(raw, key)) } {
+            attributes.insert(name.into_owned(), value);
+        }
+
+        true
+    })
+}
@@ -0,0 +1,229 @@
+use super::{read_attributes, Attributes, RawRepresentation, ResponseResult};
+use crate::error::{check, Error, Result};
+use crate::rutin::{abort_on_panic, cstring, pin_callback};
+use rutin_tizen_sys::{
+    iotcon_observe_type_e_IOTCON_OBSERVE_NO_TYPE, iotcon_observe_type_e_IOTCON_OBSERVE_REGISTER,
+    iotcon_qos_e_IOTCON_QOS_HIGH, iotcon_request_get_observe_type,
+    iotcon_request_get_representation, iotcon_request_get_request_type, iotcon_request_h,
+    iotcon_request_type_e, iotcon_request_type_e_IOTCON_REQUEST_DELETE,
+    iotcon_request_type_e_IOTCON_REQUEST_GET, iotcon_request_type_e_IOTCON_REQUEST_POST,
+    iotcon_request_type_e_IOTCON_REQUEST_PUT, iotcon_resource_create, iotcon_resource_destroy,
+    iotcon_resource_h, iotcon_resource_interfaces_add, iotcon_resource_interfaces_create,
+    iotcon_resource_interfaces_destroy, iotcon_resource_interfaces_h, iotcon_resource_notify,
+    iotcon_resource_types_add, iotcon_resource_types_create, iotcon_resource_types_destroy,
+    iotcon_resource_types_h, iotcon_response_create, iotcon_response_destroy, iotcon_response_h,
+    iotcon_response_send, iotcon_response_set_representation, iotcon_response_set_result,
+};
+use std::convert::TryFrom;
+use std::os::raw::c_void;
+use std::pin::Pin;
+use std::ptr::null_mut;
+
+/// The interface every resource should implement.
+pub const INTERFACE_BASELINE: &str = "oic.if.baseline";
+
+/// Flags for [`Resource::new`].
+pub mod policy {
+    pub const DISCOVERABLE: u8 = 1 << 0;
+    pub const OBSERVABLE: u8 = 1 << 1;
+    pub const SECURE: u8 = 1 << 4;
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RequestType {
+    Get,
+    Put,
+    Post,
+    Delete,
+}
+
+impl TryFrom<iotcon_request_type_e> for RequestType {
+    type Error = Error;
+
+    fn try_from(request_type: iotcon_request_type_e) -> Result<RequestType> {
+        match request_type {
+            iotcon_request_type_e_IOTCON_REQUEST_GET => Ok(RequestType::Get),
+            iotcon_request_type_e_IOTCON_REQUEST_PUT => Ok(RequestType::Put),
+            iotcon_request_type_e_IOTCON_REQUEST_POST => Ok(RequestType::Post),
+            iotcon_request_type_e_IOTCON_REQUEST_DELETE => Ok(RequestType::Delete),
+            _ => Err(Error::Unknown),
+        }
+    }
+}
+
+/// A request received by a [`Resource`], only valid during its handler.
+pub struct Request {
+    handle: iotcon_request_h,
+}
+
+impl Request {
+    pub fn request_type(&self) -> Result<RequestType> {
+        let mut request_type = iotcon_request_type_e_IOTCON_REQUEST_GET;
+        check(unsafe { iotcon_request_get_request_type(self.handle, &mut request_type) })?;
+
+        RequestType::try_from(request_type)
+    }
+
+    /// The attributes sent with a PUT or POST request.
+    pub fn attributes(&self) -> Result<Attributes> {
+        let mut representation = null_mut();
+        check(unsafe { iotcon_request_get_representation(self.handle, &mut representation) })?;
+
+        unsafe { read_attributes(representation) }
+    }
+
+    /// Whether the client asks to observe the resource, its later changes being
+    /// sent with [`Resource::notify`].
+    pub fn is_observe_register(&self) -> bool {
+        let mut observe_type = iotcon_observe_type_e_IOTCON_OBSERVE_NO_TYPE;
+        let ret = unsafe { iotcon_request_get_observe_type(self.handle, &mut observe_type) };
+
+        ret == 0 && observe_type == iotcon_observe_type_e_IOTCON_OBSERVE_REGISTER
+    }
+
+    /// Answers the request, with the state of the resource when there is one.
+    pub fn respond(&self, result: ResponseResult, attributes: Option<&Attributes>) -> Result<()> {
+        let representation = attributes.map(RawRepresentation::new).transpose()?;
+
+        let mut response = null_mut();
+        check(unsafe { iotcon_response_create(self.handle, &mut response) })?;
+
+        let ret = unsafe { send_response(response, result, representation.as_ref()) };
+        unsafe { iotcon_response_destroy(response) };
+
+        ret
+    }
+}
+
+unsafe fn send_response(
+    response: iotcon_response_h,
+    result: ResponseResult,
+    representation: Option<&RawRepresentation>,
+) -> Result<()> {
+    check(iotcon_response_set_result(response, result.into()))?;
+
+    if let Some(representation) = representation {
+        check(iotcon_response_set_representation(
+            response,
+            representation.handle,
+        ))?;
+    }
+
+    check(iotcon_response_send(response))
+}
+
+type RequestFn = Box<dyn FnMut(&Request)>;
+
+struct Types(iotcon_resource_types_h);
+
+impl Drop for Types {
+    fn drop(&mut self) {
+        unsafe {
+            iotcon_resource_types_destroy(self.0);
+        }
+    }
+}
+
+struct Interfaces(iotcon_resource_interfaces_h);
+
+impl Drop for Interfaces {
+    fn drop(&mut self) {
+        unsafe {
+            iotcon_resource_interfaces_destroy(self.0);
+        }
+    }
+}
+
+/// A resource hosted by the application, unregistered when dropped.
+pub struct Resource {
+    handle: iotcon_resource_h,
+    _request_fn: Pin<Box<RequestFn>>,
+}
+
+impl Resource {
+    /// Registers a resource at `uri_path`, e.g. "/light/1", with types such as
+    /// "core.light", calling `request_fn` with each request it receives.
+    pub fn new<F>(
+        uri_path: &str,
+        resource_types: &[&str],
+        interfaces: &[&str],
+        policies: u8,
+        request_fn: F,
+    ) -> Result<Resource>
+    where
+        F: FnMut(&Request),
+        F: 'static,
+    {
+        let uri_path = cstring(uri_path)?;
+
+        let mut types = Types(null_mut());
+        check(unsafe { iotcon_resource_types_create(&mut types.0) })?;
+
+        for resource_type in resource_types {
+            let resource_type = cstring(*resource_type)?;
+            check(unsafe { iotcon_resource_types_add(types.0, resource_type.as_ptr()) })?;
+        }
+
+        let mut ifaces = Interfaces(null_mut());
+        check(unsafe { iotcon_resource_interfaces_create(&mut ifaces.0) })?;
+
+        for interface in interfaces {
+            let interface = cstring(*interface)?;
+            check(unsafe { iotcon_resource_interfaces_add(ifaces.0, interface.as_ptr()) })?;
+        }
+
+        let (request_fn, data) = pin_callback::<dyn FnMut(&Request)>(Box::new(request_fn));
+
+        let mut handle = null_mut();
+        check(unsafe {
+            iotcon_resource_create(
+                uri_path.as_ptr(),
+                types.0,
+                ifaces.0,
+                policies,
+                Some(request_handler),
+                data,
+                &mut handle,
+            )
+        })?;
+
+        Ok(Self {
+            handle,
+            _request_fn: request_fn,
+        })
+    }
+
+    /// Sends the new state of the resource to every observer.
+    pub fn notify(&mut self, attributes: &Attributes) -> Result<()> {
+        let representation = RawRepresentation::new(attributes)?;
+
+        check(unsafe {
+            iotcon_resource_notify(
+                self.handle,
+                representation.handle,
+                null_mut(),
+                iotcon_qos_e_IOTCON_QOS_HIGH,
+            )
+        })
+    }
+}
+
+impl Drop for Resource {
+    fn drop(&mut self) {
+        unsafe {
+            iotcon_resource_destroy(self.handle);
+        }
+    }
+}
+
+extern "C" fn request_handler(
+    _resource: iotcon_resource_h,
+    request: iotcon_request_h,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut RequestFn) };
+        closure(&Request { handle: request });
+    })
+}
@@ -0,0 +1,25 @@
+#[cfg(feature = "native")]
+pub mod bluetooth;
+#[cfg(feature = "native")]
+pub mod cion;
+pub mod connection;
+#[cfg(feature = "native")]
+pub mod http;
+#[cfg(feature = "native")]
+pub mod iotcon;
+#[cfg(feature = "native")]
+pub mod mtp;
+#[cfg(feature = "native")]
+pub mod nfc;
+#[cfg(feature = "sap")]
+pub mod sap;
+#[cfg(feature = "native")]
+pub mod smartcard;
+#[cfg(feature = "native")]
+pub mod stc;
+#[cfg(feature = "native")]
+pub mod tethering;
+#[cfg(feature = "native")]
+pub mod vpn;
+#[cfg(feature = "efl")]
+pub mod websocket;
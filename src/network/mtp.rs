@@ -0,0 +1,354 @@
+//! MTP initiator, browsing the content of cameras and phones attached over USB.
+
+use crate::error::{check, Error, Result};
+use crate::rutin::{abort_on_panic, path_cstring, pin_callback, take_string};
+use rutin_tizen_sys::{
+    mtp_add_mtp_event_cb, mtp_deinitialize, mtp_delete_object, mtp_deviceinfo_get_device_version,
+    mtp_deviceinfo_get_manufacturer_name, mtp_deviceinfo_get_model_name,
+    mtp_deviceinfo_get_serial_number, mtp_event_e, mtp_event_e_MTP_EVENT_DEVICE_ADDED,
+    mtp_event_e_MTP_EVENT_DEVICE_REMOVED, mtp_event_e_MTP_EVENT_OBJECT_ADDED,
+    mtp_event_e_MTP_EVENT_OBJECT_REMOVED, mtp_event_e_MTP_EVENT_STORAGE_ADDED,
+    mtp_event_e_MTP_EVENT_STORAGE_REMOVED, mtp_event_e_MTP_EVENT_TURNED_OFF, mtp_filetype_e,
+    mtp_filetype_e_MTP_FILETYPE_ALL, mtp_filetype_e_MTP_FILETYPE_ALL_IMAGE,
+    mtp_filetype_e_MTP_FILETYPE_BMP, mtp_filetype_e_MTP_FILETYPE_FOLDER,
+    mtp_filetype_e_MTP_FILETYPE_GIF, mtp_filetype_e_MTP_FILETYPE_JPEG,
+    mtp_filetype_e_MTP_FILETYPE_MP3, mtp_filetype_e_MTP_FILETYPE_MP4,
+    mtp_filetype_e_MTP_FILETYPE_PNG, mtp_filetype_e_MTP_FILETYPE_WAV, mtp_get_devices,
+    mtp_get_object, mtp_get_object_handles, mtp_get_storages, mtp_get_thumbnail, mtp_initialize,
+    mtp_objectinfo_get_date_modified, mtp_objectinfo_get_file_name, mtp_objectinfo_get_file_type,
+    mtp_objectinfo_get_size, mtp_remove_mtp_event_cb, mtp_storage_get_description,
+    mtp_storage_get_free_space, mtp_storage_get_max_capacity,
+};
+use std::convert::TryFrom;
+use std::marker::PhantomData;
+use std::os::raw::{c_char, c_int, c_void};
+use std::path::Path;
+use std::pin::Pin;
+use std::ptr::null_mut;
+
+/// Copies an array of handles allocated by the API, then frees it.
+unsafe fn take_handles(ptr: *mut c_int, len: c_int) -> Vec<c_int> {
+    if ptr.is_null() {
+        return Vec::new();
+    }
+
+    let handles = std::slice::from_raw_parts(ptr, len as usize).to_vec();
+    libc::free(ptr as *mut c_void);
+
+    handles
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MtpEvent {
+    StorageAdded,
+    StorageRemoved,
+    ObjectAdded,
+    ObjectRemoved,
+    DeviceAdded,
+    DeviceRemoved,
+    TurnedOff,
+}
+
+impl TryFrom<mtp_event_e> for MtpEvent {
+    type Error = Error;
+
+    fn try_from(event: mtp_event_e) -> Result<MtpEvent> {
+        match event {
+            mtp_event_e_MTP_EVENT_STORAGE_ADDED => Ok(MtpEvent::StorageAdded),
+            mtp_event_e_MTP_EVENT_STORAGE_REMOVED => Ok(MtpEvent::StorageRemoved),
+            mtp_event_e_MTP_EVENT_OBJECT_ADDED => Ok(MtpEvent::ObjectAdded),
+            mtp_event_e_MTP_EVENT_OBJECT_REMOVED => Ok(MtpEvent::ObjectRemoved),
+            mtp_event_e_MTP_EVENT_DEVICE_ADDED => Ok(MtpEvent::DeviceAdded),
+            mtp_event_e_MTP_EVENT_DEVICE_REMOVED => Ok(MtpEvent::DeviceRemoved),
+            mtp_event_e_MTP_EVENT_TURNED_OFF => Ok(MtpEvent::TurnedOff),
+            _ => Err(Error::Unknown),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FileType {
+    Folder,
+    Jpeg,
+    Png,
+    Gif,
+    Bmp,
+    Mp3,
+    Wav,
+    Mp4,
+    Other,
+}
+
+impl From<mtp_filetype_e> for FileType {
+    fn from(file_type: mtp_filetype_e) -> FileType {
+        match file_type {
+            mtp_filetype_e_MTP_FILETYPE_FOLDER => FileType::Folder,
+            mtp_filetype_e_MTP_FILETYPE_JPEG => FileType::Jpeg,
+            mtp_filetype_e_MTP_FILETYPE_PNG => FileType::Png,
+            mtp_filetype_e_MTP_FILETYPE_GIF => FileType::Gif,
+            mtp_filetype_e_MTP_FILETYPE_BMP => FileType::Bmp,
+            mtp_filetype_e_MTP_FILETYPE_MP3 => FileType::Mp3,
+            mtp_filetype_e_MTP_FILETYPE_WAV => FileType::Wav,
+            mtp_filetype_e_MTP_FILETYPE_MP4 => FileType::Mp4,
+            _ => FileType::Other,
+        }
+    }
+}
+
+type EventFn = Box<dyn FnMut(MtpEvent, i32)>;
+
+/// The MTP service, deinitialized when this is dropped.
+pub struct Mtp {
+    event_fn: Option<Pin<Box<EventFn>>>,
+}
+
+impl Mtp {
+    pub fn new() -> Result<Mtp> {
+        check(unsafe { mtp_initialize() })?;
+
+        Ok(Self { event_fn: None })
+    }
+
+    pub fn devices(&self) -> Result<Vec<MtpDevice<'_>>> {
+        let mut devices = null_mut();
+        let mut len = 0;
+        check(unsafe { mtp_get_devices(&mut devices, &mut len) })?;
+
+        Ok(unsafe { take_handles(devices, len) }
+            .into_iter()
+            .map(|handle| MtpDevice {
+                handle,
+                _mtp: PhantomData,
+            })
+            .collect())
+    }
+
+    /// Calls `callback_fn` when devices, storages or objects come and go, along with
+    /// the handle they concern. Replaces the previous callback.
+    pub fn set_event_cb<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(MtpEvent, i32),
+        F: 'static,
+    {
+        self.unset_event_cb();
+
+        let (callback_fn, data) = pin_callback::<dyn FnMut(MtpEvent, i32)>(Box::new(callback_fn));
+
+        check(unsafe { mtp_add_mtp_event_cb(Some(event_handler), data) })?;
+        self.event_fn = Some(callback_fn);
+
+        Ok(())
+    }
+
+    pub fn unset_event_cb(&mut self) {
+        if self.event_fn.take().is_some() {
+            unsafe { mtp_remove_mtp_event_cb(Some(event_handler)) };
+        }
+    }
+}
+
+impl Drop for Mtp {
+    fn drop(&mut self) {
+        self.unset_event_cb();
+
+        unsafe {
+            mtp_deinitialize();
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct MtpDevice<'m> {
+    handle: c_int,
+    _mtp: PhantomData<&'m Mtp>,
+}
+
+impl<'m> MtpDevice<'m> {
+    fn get_str(
+        &self,
+        get: unsafe extern "C" fn(c_int, *mut *mut c_char) -> c_int,
+    ) -> Result<String> {
+        let mut value = null_mut();
+        check(unsafe { get(self.handle, &mut value) })?;
+
+        Ok(unsafe { take_string(value) }.unwrap_or_default())
+    }
+
+    pub fn manufacturer_name(&self) -> Result<String> {
+        self.get_str(mtp_deviceinfo_get_manufacturer_name)
+    }
+
+    pub fn model_name(&self) -> Result<String> {
+        self.get_str(mtp_deviceinfo_get_model_name)
+    }
+
+    pub fn serial_number(&self) -> Result<String> {
+        self.get_str(mtp_deviceinfo_get_serial_number)
+    }
+
+    pub fn device_version(&self) -> Result<String> {
+        self.get_str(mtp_deviceinfo_get_device_version)
+    }
+
+    pub fn storages(&self) -> Result<Vec<MtpStorage<'m>>> {
+        let mut storages = null_mut();
+        let mut len = 0;
+        check(unsafe { mtp_get_storages(self.handle, &mut storages, &mut len) })?;
+
+        Ok(unsafe { take_handles(storages, len) }
+            .into_iter()
+            .map(|handle| MtpStorage {
+                device: *self,
+                handle,
+            })
+            .collect())
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct MtpStorage<'m> {
+    device: MtpDevice<'m>,
+    handle: c_int,
+}
+
+impl<'m> MtpStorage<'m> {
+    pub fn description(&self) -> Result<String> {
+        let mut description = null_mut();
+        check(unsafe {
+            mtp_storage_get_description(self.device.handle, self.handle, &mut description)
+        })?;
+
+        Ok(unsafe { take_string(description) }.unwrap_or_default())
+    }
+
+    /// The free space, in bytes.
+    pub fn free_space(&self) -> Result<u64> {
+        let mut free_space = 0;
+        check(unsafe {
+            mtp_storage_get_free_space(self.device.handle, self.handle, &mut free_space)
+        })?;
+
+        Ok(free_space)
+    }
+
+    /// The total capacity, in bytes.
+    pub fn max_capacity(&self) -> Result<u64> {
+        let mut capacity = 0;
+        check(unsafe {
+            mtp_storage_get_max_capacity(self.device.handle, self.handle, &mut capacity)
+        })?;
+
+        Ok(capacity)
+    }
+
+    /// Lists the objects in `parent`, or at the root of the storage when `None`.
+    pub fn objects(&self, parent: Option<&MtpObject<'m>>) -> Result<Vec<MtpObject<'m>>> {
+        self.object_handles(parent, mtp_filetype_e_MTP_FILETYPE_ALL)
+    }
+
+    /// Lists the images in `parent`, or at the root of the storage when `None`.
+    pub fn images(&self, parent: Option<&MtpObject<'m>>) -> Result<Vec<MtpObject<'m>>> {
+        self.object_handles(parent, mtp_filetype_e_MTP_FILETYPE_ALL_IMAGE)
+    }
+
+    fn object_handles(
+        &self,
+        parent: Option<&MtpObject<'m>>,
+        file_type: mtp_filetype_e,
+    ) -> Result<Vec<MtpObject<'m>>> {
+        let mut objects = null_mut();
+        let mut len = 0;
+        check(unsafe {
+            mtp_get_object_handles(
+                self.device.handle,
+                self.handle,
+                file_type,
+                parent.map_or(0, |p| p.handle),
+                &mut objects,
+                &mut len,
+            )
+        })?;
+
+        Ok(unsafe { take_handles(objects, len) }
+            .into_iter()
+            .map(|handle| MtpObject {
+                device: self.device,
+                handle,
+            })
+            .collect())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ObjectInfo {
+    pub file_name: String,
+    pub file_type: FileType,
+    /// The size in bytes.
+    pub size: i32,
+    /// Last modification time, in seconds since the epoch.
+    pub date_modified: i32,
+}
+
+/// A file or folder on a device.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct MtpObject<'m> {
+    device: MtpDevice<'m>,
+    handle: c_int,
+}
+
+impl<'m> MtpObject<'m> {
+    pub fn info(&self) -> Result<ObjectInfo> {
+        let (device, object) = (self.device.handle, self.handle);
+
+        let mut file_name = null_mut();
+        check(unsafe { mtp_objectinfo_get_file_name(device, object, &mut file_name) })?;
+        let file_name = unsafe { take_string(file_name) }.unwrap_or_default();
+
+        let mut file_type = mtp_filetype_e_MTP_FILETYPE_ALL;
+        check(unsafe { mtp_objectinfo_get_file_type(device, object, &mut file_type) })?;
+
+        let mut size = 0;
+        check(unsafe { mtp_objectinfo_get_size(device, object, &mut size) })?;
+
+        let mut date_modified = 0;
+        check(unsafe { mtp_objectinfo_get_date_modified(device, object, &mut date_modified) })?;
+
+        Ok(ObjectInfo {
+            file_name,
+            file_type: file_type.into(),
+            size,
+            date_modified,
+        })
+    }
+
+    /// Copies the file content to `dest_path`.
+    pub fn download(&self, dest_path: &Path) -> Result<()> {
+        let dest_path = path_cstring(dest_path)?;
+
+        check(unsafe { mtp_get_object(self.device.handle, self.handle, dest_path.as_ptr()) })
+    }
+
+    /// Copies the thumbnail of an image to `dest_path`.
+    pub fn download_thumbnail(&self, dest_path: &Path) -> Result<()> {
+        let dest_path = path_cstring(dest_path)?;
+
+        check(unsafe { mtp_get_thumbnail(self.device.handle, self.handle, dest_path.as_ptr()) })
+    }
+
+    pub fn delete(self) -> Result<()> {
+        check(unsafe { mtp_delete_object(self.device.handle, self.handle) })
+    }
+}
+
+extern "C" fn event_handler(event: mtp_event_e, event_parameter: c_int, user_data: *mut c_void) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut EventFn) };
+
+        if let Ok(event) = MtpEvent::try_from(event) {
+            closure(event, event_parameter);
+        }
+    })
+}
@@ -0,0 +1,91 @@
+use super::NfcManager;
+use crate::error::{check, Result};
+use crate::rutin::{abort_on_panic, pin_callback};
+use rutin_tizen_sys::{
+    nfc_hce_event_type_e, nfc_hce_event_type_e_NFC_HCE_EVENT_ACTIVATED,
+    nfc_hce_event_type_e_NFC_HCE_EVENT_APDU_RECEIVED,
+    nfc_hce_event_type_e_NFC_HCE_EVENT_DEACTIVATED, nfc_hce_send_apdu_response,
+    nfc_manager_set_hce_event_cb, nfc_manager_unset_hce_event_cb, nfc_se_h,
+};
+use std::os::raw::{c_uint, c_void};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum HceEvent<'a> {
+    /// A reader selected this application.
+    Activated,
+    Deactivated,
+    /// A command APDU sent by the reader, to be answered with [`HceSession::respond`].
+    ApduReceived(&'a [u8]),
+}
+
+/// The secure element session of an HCE event.
+#[derive(Debug)]
+pub struct HceSession {
+    handle: nfc_se_h,
+}
+
+impl HceSession {
+    /// Sends a response APDU to the reader.
+    pub fn respond(&self, response: &[u8]) -> Result<()> {
+        check(unsafe {
+            nfc_hce_send_apdu_response(
+                self.handle,
+                response.as_ptr() as *mut u8,
+                response.len() as c_uint,
+            )
+        })
+    }
+}
+
+pub(super) type HceFn = Box<dyn FnMut(&HceSession, HceEvent<'_>)>;
+
+impl NfcManager {
+    /// Calls `callback_fn` with each host card emulation event, replacing the previous callback.
+    ///
+    /// Requires the `http://tizen.org/privilege/nfc.cardemulation` privilege, and the
+    /// application's AIDs to be registered in its manifest.
+    pub fn set_hce_event_cb<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(&HceSession, HceEvent<'_>),
+        F: 'static,
+    {
+        let (callback_fn, data) =
+            pin_callback::<dyn FnMut(&HceSession, HceEvent<'_>)>(Box::new(callback_fn));
+
+        check(unsafe { nfc_manager_set_hce_event_cb(Some(hce_handler), data) })?;
+        self.hce_fn = Some(callback_fn);
+
+        Ok(())
+    }
+
+    pub fn unset_hce_event_cb(&mut self) {
+        unsafe { nfc_manager_unset_hce_event_cb() };
+        self.hce_fn = None;
+    }
+}
+
+extern "C" fn hce_handler(
+    handle: nfc_se_h,
+    event: nfc_hce_event_type_e,
+    apdu: *mut u8,
+    apdu_len: c_uint,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut HceFn) };
+        let session = HceSession { handle };
+
+        let event = match event {
+            nfc_hce_event_type_e_NFC_HCE_EVENT_ACTIVATED => HceEvent::Activated,
+            nfc_hce_event_type_e_NFC_HCE_EVENT_DEACTIVATED => HceEvent::Deactivated,
+            nfc_hce_event_type_e_NFC_HCE_EVENT_APDU_RECEIVED if !apdu.is_null() => {
+                HceEvent::ApduReceived(unsafe {
+                    std::slice::from_raw_parts(apdu, apdu_len as usize)
+                })
+            }
+            _ => return,
+        };
+
+        closure(&session, event);
+    })
+}
@@ -0,0 +1,387 @@
+//! NFC tags, peer-to-peer NDEF exchange and host card emulation.
+//!
+//! Requires the `http://tizen.org/privilege/nfc` privilege.
+
+mod hce;
+mod ndef;
+mod p2p;
+
+pub use hce::{HceEvent, HceSession};
+pub use ndef::{NdefMessage, NdefRecord, Tnf};
+pub use p2p::{P2pEvent, P2pTarget};
+
+use self::hce::HceFn;
+use self::ndef::RawNdefMessage;
+use self::p2p::P2pFn;
+use crate::error::{check, Result};
+use crate::rutin::{abort_on_panic, pin_callback};
+use rutin_tizen_sys::{
+    nfc_discovered_type_e, nfc_discovered_type_e_NFC_DISCOVERED_TYPE_ATTACHED,
+    nfc_manager_deinitialize, nfc_manager_initialize, nfc_manager_is_activated,
+    nfc_manager_is_supported, nfc_manager_set_activation, nfc_manager_set_activation_changed_cb,
+    nfc_manager_set_ndef_discovered_cb, nfc_manager_set_tag_discovered_cb,
+    nfc_manager_unset_activation_changed_cb, nfc_manager_unset_hce_event_cb,
+    nfc_manager_unset_ndef_discovered_cb, nfc_manager_unset_p2p_target_discovered_cb,
+    nfc_manager_unset_tag_discovered_cb, nfc_ndef_message_h, nfc_tag_get_maximum_ndef_size,
+    nfc_tag_get_type, nfc_tag_h, nfc_tag_is_support_ndef, nfc_tag_read_ndef, nfc_tag_type_e,
+    nfc_tag_type_e_NFC_FELICA_PICC, nfc_tag_type_e_NFC_GENERIC_PICC,
+    nfc_tag_type_e_NFC_ISO14443_3A_PICC, nfc_tag_type_e_NFC_ISO14443_4A_PICC,
+    nfc_tag_type_e_NFC_ISO14443_4B_PICC, nfc_tag_type_e_NFC_ISO14443_A_PICC,
+    nfc_tag_type_e_NFC_ISO14443_BPRIME_PICC, nfc_tag_type_e_NFC_ISO14443_B_PICC,
+    nfc_tag_type_e_NFC_ISO15693_PICC, nfc_tag_type_e_NFC_JEWEL_PICC,
+    nfc_tag_type_e_NFC_MIFARE_1K_PICC, nfc_tag_type_e_NFC_MIFARE_4K_PICC,
+    nfc_tag_type_e_NFC_MIFARE_DESFIRE_PICC, nfc_tag_type_e_NFC_MIFARE_MINI_PICC,
+    nfc_tag_type_e_NFC_MIFARE_ULTRA_PICC, nfc_tag_type_e_NFC_NFCIP1_INITIATOR,
+    nfc_tag_type_e_NFC_NFCIP1_TARGET, nfc_tag_type_e_NFC_UNKNOWN_TARGET, nfc_tag_write_ndef,
+};
+use std::os::raw::{c_int, c_uint, c_void};
+use std::pin::Pin;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TagType {
+    Generic,
+    Iso14443A,
+    Iso14443_4A,
+    Iso14443_3A,
+    MifareMini,
+    Mifare1K,
+    Mifare4K,
+    MifareUltralight,
+    MifareDesfire,
+    Iso14443B,
+    Iso14443_4B,
+    Iso14443BPrime,
+    Felica,
+    Jewel,
+    Iso15693,
+    Nfcip1Target,
+    Nfcip1Initiator,
+    Unknown,
+}
+
+impl From<nfc_tag_type_e> for TagType {
+    fn from(tag_type: nfc_tag_type_e) -> TagType {
+        match tag_type {
+            nfc_tag_type_e_NFC_GENERIC_PICC => TagType::Generic,
+            nfc_tag_type_e_NFC_ISO14443_A_PICC => TagType::Iso14443A,
+            nfc_tag_type_e_NFC_ISO14443_4A_PICC => TagType::Iso14443_4A,
+            nfc_tag_type_e_NFC_ISO14443_3A_PICC => TagType::Iso14443_3A,
+            nfc_tag_type_e_NFC_MIFARE_MINI_PICC => TagType::MifareMini,
+            nfc_tag_type_e_NFC_MIFARE_1K_PICC => TagType::Mifare1K,
+            nfc_tag_type_e_NFC_MIFARE_4K_PICC => TagType::Mifare4K,
+            nfc_tag_type_e_NFC_MIFARE_ULTRA_PICC => TagType::MifareUltralight,
+            nfc_tag_type_e_NFC_MIFARE_DESFIRE_PICC => TagType::MifareDesfire,
+            nfc_tag_type_e_NFC_ISO14443_B_PICC => TagType::Iso14443B,
+            nfc_tag_type_e_NFC_ISO14443_4B_PICC => TagType::Iso14443_4B,
+            nfc_tag_type_e_NFC_ISO14443_BPRIME_PICC => TagType::Iso14443BPrime,
+            nfc_tag_type_e_NFC_FELICA_PICC => TagType::Felica,
+            nfc_tag_type_e_NFC_JEWEL_PICC => TagType::Jewel,
+            nfc_tag_type_e_NFC_ISO15693_PICC => TagType::Iso15693,
+            nfc_tag_type_e_NFC_NFCIP1_TARGET => TagType::Nfcip1Target,
+            nfc_tag_type_e_NFC_NFCIP1_INITIATOR => TagType::Nfcip1Initiator,
+            _ => TagType::Unknown,
+        }
+    }
+}
+
+/// A tag in range of the device.
+///
+/// The tag can only be used until the matching [`TagEvent::Detached`] is received.
+#[derive(Debug)]
+pub struct Tag {
+    handle: nfc_tag_h,
+}
+
+impl Tag {
+    pub fn tag_type(&self) -> Result<TagType> {
+        let mut tag_type = nfc_tag_type_e_NFC_UNKNOWN_TARGET;
+        check(unsafe { nfc_tag_get_type(self.handle, &mut tag_type) })?;
+
+        Ok(tag_type.into())
+    }
+
+    pub fn supports_ndef(&self) -> Result<bool> {
+        let mut supported = false;
+        check(unsafe { nfc_tag_is_support_ndef(self.handle, &mut supported) })?;
+
+        Ok(supported)
+    }
+
+    /// The largest NDEF message the tag can hold, in bytes.
+    pub fn max_ndef_size(&self) -> Result<u32> {
+        let mut size: c_uint = 0;
+        check(unsafe { nfc_tag_get_maximum_ndef_size(self.handle, &mut size) })?;
+
+        Ok(size)
+    }
+
+    /// Reads the NDEF message stored on the tag, `callback_fn` is called with the result.
+    pub fn read_ndef<F>(&self, callback_fn: F) -> Result<()>
+    where
+        F: FnOnce(Result<NdefMessage>),
+        F: 'static,
+    {
+        let callback_fn: Box<ReadFn> = Box::new(callback_fn);
+        let data = Box::into_raw(Box::new(callback_fn));
+
+        let ret = unsafe {
+            nfc_tag_read_ndef(
+                self.handle,
+                Some(read_completed_handler),
+                data as *mut c_void,
+            )
+        };
+
+        if let Err(e) = check(ret) {
+            drop(unsafe { Box::from_raw(data) });
+            return Err(e);
+        }
+
+        Ok(())
+    }
+
+    /// Writes `message` to the tag, `callback_fn` is called with the result.
+    pub fn write_ndef<F>(&mut self, message: &NdefMessage, callback_fn: F) -> Result<()>
+    where
+        F: FnOnce(Result<()>),
+        F: 'static,
+    {
+        let message = message.to_raw()?;
+        let handle = message.handle;
+        let data = Box::into_raw(Box::new(WriteRequest {
+            _message: message,
+            callback_fn: Box::new(callback_fn),
+        }));
+
+        let ret = unsafe {
+            nfc_tag_write_ndef(
+                self.handle,
+                handle,
+                Some(write_completed_handler),
+                data as *mut c_void,
+            )
+        };
+
+        if let Err(e) = check(ret) {
+            drop(unsafe { Box::from_raw(data) });
+            return Err(e);
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub enum TagEvent {
+    Attached(Tag),
+    Detached,
+}
+
+type ReadFn = dyn FnOnce(Result<NdefMessage>);
+
+/// Keeps the message alive until the write completes.
+struct WriteRequest {
+    _message: RawNdefMessage,
+    callback_fn: Box<dyn FnOnce(Result<()>)>,
+}
+
+type ActivationFn = Box<dyn FnMut(bool)>;
+type TagFn = Box<dyn FnMut(TagEvent)>;
+type NdefFn = Box<dyn FnMut(NdefMessage)>;
+
+/// The NFC manager, deinitialized when this is dropped.
+pub struct NfcManager {
+    activation_fn: Option<Pin<Box<ActivationFn>>>,
+    tag_fn: Option<Pin<Box<TagFn>>>,
+    ndef_fn: Option<Pin<Box<NdefFn>>>,
+    p2p_fn: Option<Pin<Box<P2pFn>>>,
+    hce_fn: Option<Pin<Box<HceFn>>>,
+}
+
+impl NfcManager {
+    pub fn new() -> Result<NfcManager> {
+        check(unsafe { nfc_manager_initialize() })?;
+
+        Ok(Self {
+            activation_fn: None,
+            tag_fn: None,
+            ndef_fn: None,
+            p2p_fn: None,
+            hce_fn: None,
+        })
+    }
+
+    pub fn is_supported() -> bool {
+        unsafe { nfc_manager_is_supported() }
+    }
+
+    pub fn is_activated(&self) -> bool {
+        unsafe { nfc_manager_is_activated() }
+    }
+
+    /// Turns NFC on or off, `callback_fn` is called with the result.
+    ///
+    /// Requires the `http://tizen.org/privilege/nfc.admin` privilege.
+    pub fn set_activation<F>(&mut self, activated: bool, callback_fn: F) -> Result<()>
+    where
+        F: FnOnce(Result<()>),
+        F: 'static,
+    {
+        let callback_fn: Box<dyn FnOnce(Result<()>)> = Box::new(callback_fn);
+        let data = Box::into_raw(Box::new(callback_fn));
+
+        let ret = unsafe {
+            nfc_manager_set_activation(
+                activated,
+                Some(activation_completed_handler),
+                data as *mut c_void,
+            )
+        };
+
+        if let Err(e) = check(ret) {
+            drop(unsafe { Box::from_raw(data) });
+            return Err(e);
+        }
+
+        Ok(())
+    }
+
+    /// Calls `callback_fn` each time NFC is turned on or off, replacing the previous callback.
+    pub fn set_activation_changed_cb<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(bool),
+        F: 'static,
+    {
+        let (callback_fn, data) = pin_callback::<dyn FnMut(bool)>(Box::new(callback_fn));
+
+        check(unsafe { nfc_manager_set_activation_changed_cb(Some(activation_handler), data) })?;
+        self.activation_fn = Some(callback_fn);
+
+        Ok(())
+    }
+
+    pub fn unset_activation_changed_cb(&mut self) {
+        unsafe { nfc_manager_unset_activation_changed_cb() };
+        self.activation_fn = None;
+    }
+
+    /// Calls `callback_fn` each time a tag comes in or goes out of range,
+    /// replacing the previous callback.
+    pub fn set_tag_discovered_cb<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(TagEvent),
+        F: 'static,
+    {
+        let (callback_fn, data) = pin_callback::<dyn FnMut(TagEvent)>(Box::new(callback_fn));
+
+        check(unsafe { nfc_manager_set_tag_discovered_cb(Some(tag_handler), data) })?;
+        self.tag_fn = Some(callback_fn);
+
+        Ok(())
+    }
+
+    pub fn unset_tag_discovered_cb(&mut self) {
+        unsafe { nfc_manager_unset_tag_discovered_cb() };
+        self.tag_fn = None;
+    }
+
+    /// Calls `callback_fn` with the NDEF message of each discovered tag,
+    /// replacing the previous callback.
+    pub fn set_ndef_discovered_cb<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(NdefMessage),
+        F: 'static,
+    {
+        let (callback_fn, data) = pin_callback::<dyn FnMut(NdefMessage)>(Box::new(callback_fn));
+
+        check(unsafe { nfc_manager_set_ndef_discovered_cb(Some(ndef_handler), data) })?;
+        self.ndef_fn = Some(callback_fn);
+
+        Ok(())
+    }
+
+    pub fn unset_ndef_discovered_cb(&mut self) {
+        unsafe { nfc_manager_unset_ndef_discovered_cb() };
+        self.ndef_fn = None;
+    }
+}
+
+impl Drop for NfcManager {
+    fn drop(&mut self) {
+        unsafe {
+            if self.activation_fn.is_some() {
+                nfc_manager_unset_activation_changed_cb();
+            }
+            if self.tag_fn.is_some() {
+                nfc_manager_unset_tag_discovered_cb();
+            }
+            if self.ndef_fn.is_some() {
+                nfc_manager_unset_ndef_discovered_cb();
+            }
+            if self.p2p_fn.is_some() {
+                nfc_manager_unset_p2p_target_discovered_cb();
+            }
+            if self.hce_fn.is_some() {
+                nfc_manager_unset_hce_event_cb();
+            }
+            nfc_manager_deinitialize();
+        }
+    }
+}
+
+extern "C" fn activation_completed_handler(result: c_int, user_data: *mut c_void) {
+    abort_on_panic(|| {
+        let closure = unsafe { Box::from_raw(user_data as *mut Box<dyn FnOnce(Result<()>)>) };
+        closure(check(result));
+    })
+}
+
+extern "C" fn activation_handler(activated: bool, user_data: *mut c_void) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut ActivationFn) };
+        closure(activated);
+    })
+}
+
+extern "C" fn tag_handler(kind: nfc_discovered_type_e, tag: nfc_tag_h, user_data: *mut c_void) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut TagFn) };
+
+        if kind == nfc_discovered_type_e_NFC_DISCOVERED_TYPE_ATTACHED {
+            closure(TagEvent::Attached(Tag { handle: tag }));
+        } else {
+            closure(TagEvent::Detached);
+        }
+    })
+}
+
+extern "C" fn ndef_handler(message: nfc_ndef_message_h, user_data: *mut c_void) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut NdefFn) };
+
+        if let Ok(message) = unsafe { NdefMessage::from_raw(message) } {
+            closure(message);
+        }
+    })
+}
+
+extern "C" fn read_completed_handler(
+    result: c_int,
+    message: nfc_ndef_message_h,
+    user_data: *mut c_void,
+) -> bool {
+    abort_on_panic(|| {
+        let closure = unsafe { Box::from_raw(user_data as *mut Box<ReadFn>) };
+        closure(check(result).and_then(|_| unsafe { NdefMessage::from_raw(message) }));
+
+        false
+    })
+}
+
+extern "C" fn write_completed_handler(result: c_int, user_data: *mut c_void) {
+    abort_on_panic(|| {
+        let request = unsafe { Box::from_raw(user_data as *mut WriteRequest) };
+        (request.callback_fn)(check(result));
+    })
+}
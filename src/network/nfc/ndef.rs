@@ -0,0 +1,459 @@
+use crate::error::{check, Result};
+use rutin_tizen_sys::{
+    nfc_ndef_message_append_record, nfc_ndef_message_create, nfc_ndef_message_destroy,
+    nfc_ndef_message_get_record, nfc_ndef_message_get_record_count, nfc_ndef_message_h,
+    nfc_ndef_record_create, nfc_ndef_record_destroy, nfc_ndef_record_get_id,
+    nfc_ndef_record_get_payload, nfc_ndef_record_get_tnf, nfc_ndef_record_get_type,
+    nfc_ndef_record_h, nfc_record_tnf_e, nfc_record_tnf_e_NFC_RECORD_TNF_EMPTY,
+    nfc_record_tnf_e_NFC_RECORD_TNF_EXTERNAL_RTD, nfc_record_tnf_e_NFC_RECORD_TNF_MIME_MEDIA,
+    nfc_record_tnf_e_NFC_RECORD_TNF_UNCHANGED, nfc_record_tnf_e_NFC_RECORD_TNF_UNKNOWN,
+    nfc_record_tnf_e_NFC_RECORD_TNF_URI, nfc_record_tnf_e_NFC_RECORD_TNF_WELL_KNOWN,
+};
+use std::os::raw::{c_int, c_uint};
+use std::ptr::null_mut;
+
+/// Type name format of a record, telling how to interpret its type.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Tnf {
+    Empty,
+    WellKnown,
+    MimeMedia,
+    Uri,
+    External,
+    Unknown,
+    Unchanged,
+}
+
+impl From<Tnf> for nfc_record_tnf_e {
+    fn from(tnf: Tnf) -> nfc_record_tnf_e {
+        match tnf {
+            Tnf::Empty => nfc_record_tnf_e_NFC_RECORD_TNF_EMPTY,
+            Tnf::WellKnown => nfc_record_tnf_e_NFC_RECORD_TNF_WELL_KNOWN,
+            Tnf::MimeMedia => nfc_record_tnf_e_NFC_RECORD_TNF_MIME_MEDIA,
+            Tnf::Uri => nfc_record_tnf_e_NFC_RECORD_TNF_URI,
+            Tnf::External => nfc_record_tnf_e_NFC_RECORD_TNF_EXTERNAL_RTD,
+            Tnf::Unknown => nfc_record_tnf_e_NFC_RECORD_TNF_UNKNOWN,
+            Tnf::Unchanged => nfc_record_tnf_e_NFC_RECORD_TNF_UNCHANGED,
+        }
+    }
+}
+
+impl From<nfc_record_tnf_e> for Tnf {
+    fn from(tnf: nfc_record_tnf_e) -> Tnf {
+        match tnf {
+            nfc_record_tnf_e_NFC_RECORD_TNF_EMPTY => Tnf::Empty,
+            nfc_record_tnf_e_NFC_RECORD_TNF_WELL_KNOWN => Tnf::WellKnown,
+            nfc_record_tnf_e_NFC_RECORD_TNF_MIME_MEDIA => Tnf::MimeMedia,
+            nfc_record_tnf_e_NFC_RECORD_TNF_URI => Tnf::Uri,
+            nfc_record_tnf_e_NFC_RECORD_TNF_EXTERNAL_RTD => Tnf::External,
+            nfc_record_tnf_e_NFC_RECORD_TNF_UNCHANGED => Tnf::Unchanged,
+            _ => Tnf::Unknown,
+        }
+    }
+}
+
+/// Abbreviations of the URI record prefix codes, from the NFC Forum URI RTD.
+const URI_PREFIXES: &[&str] = &[
+    "",
+    "http://www.",
+    "https://www.",
+    "http://",
+    "https://",
+    "tel:",
+    "mailto:",
+    "ftp://anonymous:anonymous@",
+    "ftp://ftp.",
+    "ftps://",
+    "sftp://",
+    "smb://",
+    "nfs://",
+    "ftp://",
+    "dav://",
+    "news:",
+    "telnet://",
+    "imap:",
+    "rtsp://",
+    "urn:",
+    "pop:",
+    "sip:",
+    "sips:",
+    "tftp:",
+    "btspp://",
+    "btl2cap://",
+    "btgoep://",
+    "tcpobex://",
+    "irdaobex://",
+    "file://",
+    "urn:epc:id:",
+    "urn:epc:tag:",
+    "urn:epc:pat:",
+    "urn:epc:raw:",
+    "urn:epc:",
+    "urn:nfc:",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NdefRecord {
+    pub tnf: Tnf,
+    pub record_type: Vec<u8>,
+    pub id: Vec<u8>,
+    pub payload: Vec<u8>,
+}
+
+impl NdefRecord {
+    pub fn new(tnf: Tnf, record_type: &[u8], id: &[u8], payload: &[u8]) -> NdefRecord {
+        Self {
+            tnf,
+            record_type: record_type.to_vec(),
+            id: id.to_vec(),
+            payload: payload.to_vec(),
+        }
+    }
+
+    /// A well known text record, `lang` being an IANA language code such as "en-US".
+    pub fn text(text: &str, lang: &str) -> NdefRecord {
+        let lang = &lang.as_bytes()[..lang.len().min(0x3f)];
+        let mut payload = Vec::with_capacity(1 + lang.len() + text.len());
+
+        // status byte: UTF-8 and the length of the language code
+        payload.push(lang.len() as u8);
+        payload.extend_from_slice(lang);
+        payload.extend_from_slice(text.as_bytes());
+
+        Self::new(Tnf::WellKnown, b"T", &[], &payload)
+    }
+
+    /// A well known URI record, abbreviating the scheme when possible.
+    pub fn uri(uri: &str) -> NdefRecord {
+        let (code, prefix) = URI_PREFIXES
+            .iter()
+            .enumerate()
+            .skip(1)
+            .filter(|(_, prefix)| uri.starts_with(*prefix))
+            .max_by_key(|(_, prefix)| prefix.len())
+            .unwrap_or((0, &""));
+
+        let mut payload = vec![code as u8];
+        payload.extend_from_slice(&uri.as_bytes()[prefix.len()..]);
+
+        Self::new(Tnf::WellKnown, b"U", &[], &payload)
+    }
+
+    pub fn mime(mime_type: &str, data: &[u8]) -> NdefRecord {
+        Self::new(Tnf::MimeMedia, mime_type.as_bytes(), &[], data)
+    }
+
+    /// Returns the text and language code of a text record.
+    pub fn as_text(&self) -> Option<(String, String)> {
+        if self.tnf != Tnf::WellKnown || self.record_type != b"T" {
+            return None;
+        }
+
+        let (&status, rest) = self.payload.split_first()?;
+        let lang_len = (status & 0x3f) as usize;
+
+        if rest.len() < lang_len {
+            return None;
+        }
+
+        let (lang, text) = rest.split_at(lang_len);
+        let lang = String::from_utf8_lossy(lang).into_owned();
+
+        let text = if status & 0x80 != 0 {
+            // big endian, unless a byte order mark says otherwise
+            let (text, from_bytes): (_, fn([u8; 2]) -> u16) = match text {
+                [0xfe, 0xff, rest @ ..] => (rest, u16::from_be_bytes),
+                [0xff, 0xfe, rest @ ..] => (rest, u16::from_le_bytes),
+                _ => (text, u16::from_be_bytes),
+            };
+
+            let units = text
+                .chunks_exact(2)
+                .map(|c| from_bytes([c[0], c[1]]))
+                .collect::<Vec<_>>();
+            String::from_utf16_lossy(&units)
+        } else {
+            String::from_utf8_lossy(text).into_owned()
+        };
+
+        Some((text, lang))
+    }
+
+    /// Returns the expanded URI of a URI record.
+    pub fn as_uri(&self) -> Option<String> {
+        if self.tnf == Tnf::Uri {
+            return Some(String::from_utf8_lossy(&self.record_type).into_owned());
+        }
+
+        if self.tnf != Tnf::WellKnown || self.record_type != b"U" {
+            return None;
+        }
+
+        let (&code, rest) = self.payload.split_first()?;
+        let prefix = URI_PREFIXES.get(code as usize).unwrap_or(&"");
+
+        Some(format!("{}{}", prefix, String::from_utf8_lossy(rest)))
+    }
+
+    /// Returns the mime type of a mime media record.
+    pub fn mime_type(&self) -> Option<String> {
+        if self.tnf == Tnf::MimeMedia {
+            Some(String::from_utf8_lossy(&self.record_type).into_owned())
+        } else {
+            None
+        }
+    }
+
+    unsafe fn from_raw(record: nfc_ndef_record_h) -> Result<NdefRecord> {
+        let mut tnf = nfc_record_tnf_e_NFC_RECORD_TNF_EMPTY;
+        check(nfc_ndef_record_get_tnf(record, &mut tnf))?;
+
+        // these point inside the record, they must not be freed
+        let mut record_type = null_mut();
+        let mut record_type_len: c_int = 0;
+        check(nfc_ndef_record_get_type(
+            record,
+            &mut record_type,
+            &mut record_type_len,
+        ))?;
+
+        let mut id = null_mut();
+        let mut id_len: c_int = 0;
+        check(nfc_ndef_record_get_id(record, &mut id, &mut id_len))?;
+
+        let mut payload = null_mut();
+        let mut payload_len: c_uint = 0;
+        check(nfc_ndef_record_get_payload(
+            record,
+            &mut payload,
+            &mut payload_len,
+        ))?;
+
+        Ok(Self {
+            tnf: tnf.into(),
+            record_type: bytes(record_type, record_type_len as usize),
+            id: bytes(id, id_len as usize),
+            payload: bytes(payload, payload_len as usize),
+        })
+    }
+}
+
+unsafe fn bytes(ptr: *const u8, len: usize) -> Vec<u8> {
+    if ptr.is_null() {
+        Vec::new()
+    } else {
+        std::slice::from_raw_parts(ptr, len).to_vec()
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NdefMessage {
+    pub records: Vec<NdefRecord>,
+}
+
+impl NdefMessage {
+    pub fn new(records: Vec<NdefRecord>) -> NdefMessage {
+        Self { records }
+    }
+
+    /// Copies a message owned by the platform.
+    pub(crate) unsafe fn from_raw(message: nfc_ndef_message_h) -> Result<NdefMessage> {
+        let mut count = 0;
+        check(nfc_ndef_message_get_record_count(message, &mut count))?;
+
+        let mut records = Vec::with_capacity(count as usize);
+
+        for index in 0..count {
+            let mut record = null_mut();
+            check(nfc_ndef_message_get_record(message, index, &mut record))?;
+            records.push(NdefRecord::from_raw(record)?);
+        }
+
+        Ok(Self { records })
+    }
+
+    /// Builds a platform message, destroyed with the returned value.
+    pub(crate) fn to_raw(&self) -> Result<RawNdefMessage> {
+        let mut message = null_mut();
+        check(unsafe { nfc_ndef_message_create(&mut message) })?;
+        let raw = RawNdefMessage { handle: message };
+
+        for record in &self.records {
+            let mut handle = null_mut();
+
+            check(unsafe {
+                nfc_ndef_record_create(
+                    &mut handle,
+                    record.tnf.into(),
+                    record.record_type.as_ptr(),
+                    record.record_type.len() as c_int,
+                    record.id.as_ptr(),
+                    record.id.len() as c_int,
+                    record.payload.as_ptr(),
+                    record.payload.len() as c_uint,
+                )
+            })?;
+
+            // the message owns the record once appended
+            if let Err(e) = check(unsafe { nfc_ndef_message_append_record(raw.handle, handle) }) {
+                unsafe { nfc_ndef_record_destroy(handle) };
+                return Err(e);
+            }
+        }
+
+        Ok(raw)
+    }
+}
+
+pub(crate) struct RawNdefMessage {
+    pub(crate) handle: nfc_ndef_message_h,
+}
+
+impl Drop for RawNdefMessage {
+    fn drop(&mut self) {
+        unsafe {
+            nfc_ndef_message_destroy(self.handle);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn text_round_trip() {
+        let record = NdefRecord::text("Hello, wörld", "en-US");
+
+        assert_eq!(record.tnf, Tnf::WellKnown);
+        assert_eq!(record.record_type, b"T");
+        assert_eq!(record.payload[..6], [5, b'e', b'n', b'-', b'U', b'S']);
+        assert_eq!(
+            record.as_text(),
+            Some(("Hello, wörld".to_string(), "en-US".to_string()))
+        );
+
+        assert_eq!(
+            NdefRecord::text("", "").as_text(),
+            Some((String::new(), String::new()))
+        );
+    }
+
+    #[test]
+    fn text_language_code_limit() {
+        // the status byte has 6 bits for the length of the language code
+        let lang = "x".repeat(100);
+        let (_, parsed) = NdefRecord::text("text", &lang).as_text().unwrap();
+
+        assert_eq!(parsed, "x".repeat(0x3f));
+    }
+
+    #[test]
+    fn utf16_text() {
+        let utf16 = |units: &[u16], to_bytes: fn(u16) -> [u8; 2]| {
+            units
+                .iter()
+                .flat_map(|&unit| to_bytes(unit))
+                .collect::<Vec<_>>()
+        };
+        let units = "h€😀".encode_utf16().collect::<Vec<_>>();
+
+        let mut payload = vec![0x80 | 2, b'f', b'r'];
+        payload.extend(utf16(&units, u16::to_be_bytes));
+        let record = NdefRecord::new(Tnf::WellKnown, b"T", &[], &payload);
+        assert_eq!(
+            record.as_text(),
+            Some(("h€😀".to_string(), "fr".to_string()))
+        );
+
+        let mut payload = vec![0x80, 0xff, 0xfe];
+        payload.extend(utf16(&units, u16::to_le_bytes));
+        let record = NdefRecord::new(Tnf::WellKnown, b"T", &[], &payload);
+        assert_eq!(record.as_text(), Some(("h€😀".to_string(), String::new())));
+
+        let mut payload = vec![0x80, 0xfe, 0xff];
+        payload.extend(utf16(&units, u16::to_be_bytes));
+        let record = NdefRecord::new(Tnf::WellKnown, b"T", &[], &payload);
+        assert_eq!(record.as_text(), Some(("h€😀".to_string(), String::new())));
+    }
+
+    #[test]
+    fn malformed_text() {
+        // no status byte
+        assert_eq!(
+            NdefRecord::new(Tnf::WellKnown, b"T", &[], &[]).as_text(),
+            None
+        );
+        // a language code longer than the payload
+        assert_eq!(
+            NdefRecord::new(Tnf::WellKnown, b"T", &[], &[5, b'e', b'n']).as_text(),
+            None
+        );
+        // invalid UTF-8 and an unpaired surrogate are replaced
+        assert_eq!(
+            NdefRecord::new(Tnf::WellKnown, b"T", &[], &[0, b'a', 0xff]).as_text(),
+            Some(("a\u{fffd}".to_string(), String::new()))
+        );
+        assert_eq!(
+            NdefRecord::new(Tnf::WellKnown, b"T", &[], &[0x80, 0xd8, 0x00]).as_text(),
+            Some(("\u{fffd}".to_string(), String::new()))
+        );
+        // not a text record
+        assert_eq!(NdefRecord::uri("http://example.com").as_text(), None);
+        assert_eq!(
+            NdefRecord::new(Tnf::MimeMedia, b"T", &[], &[0]).as_text(),
+            None
+        );
+    }
+
+    #[test]
+    fn uri_prefixes() {
+        for (code, prefix) in URI_PREFIXES.iter().enumerate() {
+            let uri = format!("{}example", prefix);
+            let record = NdefRecord::uri(&uri);
+
+            assert_eq!(record.payload[0] as usize, code, "{}", uri);
+            assert_eq!(&record.payload[1..], b"example");
+            assert_eq!(record.as_uri(), Some(uri));
+        }
+    }
+
+    #[test]
+    fn uri_longest_prefix() {
+        let record = NdefRecord::uri("https://www.example.com/");
+        assert_eq!(record.payload[0], 2);
+        assert_eq!(&record.payload[1..], b"example.com/");
+
+        // "urn:" is a prefix of "urn:epc:id:"
+        let record = NdefRecord::uri("urn:epc:id:sgtin:1");
+        assert_eq!(record.payload[0], 30);
+        assert_eq!(&record.payload[1..], b"sgtin:1");
+
+        let record = NdefRecord::uri("geo:0,0");
+        assert_eq!(record.payload, b"\0geo:0,0");
+        assert_eq!(record.as_uri().as_deref(), Some("geo:0,0"));
+    }
+
+    #[test]
+    fn malformed_uri() {
+        assert_eq!(
+            NdefRecord::new(Tnf::WellKnown, b"U", &[], &[]).as_uri(),
+            None
+        );
+        // codes past the table are reserved, and expand to nothing
+        assert_eq!(
+            NdefRecord::new(Tnf::WellKnown, b"U", &[], b"\xffexample").as_uri(),
+            Some("example".to_string())
+        );
+        assert_eq!(NdefRecord::text("text", "en").as_uri(), None);
+
+        // absolute URI records keep the URI in their type
+        assert_eq!(
+            NdefRecord::new(Tnf::Uri, b"http://example.com", &[], &[]).as_uri(),
+            Some("http://example.com".to_string())
+        );
+    }
+}
@@ -0,0 +1,147 @@
+use super::{write_completed_handler, NdefMessage, NfcManager, WriteRequest};
+use crate::error::{check, Result};
+use crate::rutin::{abort_on_panic, pin_callback};
+use rutin_tizen_sys::{
+    nfc_discovered_type_e, nfc_discovered_type_e_NFC_DISCOVERED_TYPE_ATTACHED,
+    nfc_manager_set_p2p_target_discovered_cb, nfc_manager_unset_p2p_target_discovered_cb,
+    nfc_ndef_message_h, nfc_p2p_send, nfc_p2p_set_data_received_cb, nfc_p2p_target_h,
+    nfc_p2p_unset_data_received_cb,
+};
+use std::os::raw::c_void;
+use std::pin::Pin;
+
+type DataFn = Box<dyn FnMut(NdefMessage)>;
+pub(super) type P2pFn = Box<dyn FnMut(P2pEvent)>;
+
+/// A peer device in range, exchanging NDEF messages.
+///
+/// The target can only be used until the matching [`P2pEvent::Detached`] is received.
+pub struct P2pTarget {
+    handle: nfc_p2p_target_h,
+    data_fn: Option<Pin<Box<DataFn>>>,
+}
+
+impl P2pTarget {
+    /// Sends `message` to the peer, `callback_fn` is called with the result.
+    pub fn send<F>(&mut self, message: &NdefMessage, callback_fn: F) -> Result<()>
+    where
+        F: FnOnce(Result<()>),
+        F: 'static,
+    {
+        let message = message.to_raw()?;
+        let handle = message.handle;
+        let data = Box::into_raw(Box::new(WriteRequest {
+            _message: message,
+            callback_fn: Box::new(callback_fn),
+        }));
+
+        let ret = unsafe {
+            nfc_p2p_send(
+                self.handle,
+                handle,
+                Some(write_completed_handler),
+                data as *mut c_void,
+            )
+        };
+
+        if let Err(e) = check(ret) {
+            drop(unsafe { Box::from_raw(data) });
+            return Err(e);
+        }
+
+        Ok(())
+    }
+
+    /// Calls `callback_fn` with each message sent by the peer, replacing the previous callback.
+    pub fn set_data_received_cb<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(NdefMessage),
+        F: 'static,
+    {
+        let (callback_fn, data) = pin_callback::<dyn FnMut(NdefMessage)>(Box::new(callback_fn));
+
+        check(unsafe {
+            nfc_p2p_set_data_received_cb(self.handle, Some(data_received_handler), data)
+        })?;
+        self.data_fn = Some(callback_fn);
+
+        Ok(())
+    }
+
+    pub fn unset_data_received_cb(&mut self) -> Result<()> {
+        check(unsafe { nfc_p2p_unset_data_received_cb(self.handle) })?;
+        self.data_fn = None;
+
+        Ok(())
+    }
+}
+
+impl Drop for P2pTarget {
+    fn drop(&mut self) {
+        if self.data_fn.is_some() {
+            unsafe {
+                nfc_p2p_unset_data_received_cb(self.handle);
+            }
+        }
+    }
+}
+
+pub enum P2pEvent {
+    Attached(P2pTarget),
+    Detached,
+}
+
+impl NfcManager {
+    /// Calls `callback_fn` each time a peer device comes in or goes out of range,
+    /// replacing the previous callback.
+    pub fn set_p2p_target_discovered_cb<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(P2pEvent),
+        F: 'static,
+    {
+        let (callback_fn, data) = pin_callback::<dyn FnMut(P2pEvent)>(Box::new(callback_fn));
+
+        check(unsafe { nfc_manager_set_p2p_target_discovered_cb(Some(target_handler), data) })?;
+        self.p2p_fn = Some(callback_fn);
+
+        Ok(())
+    }
+
+    pub fn unset_p2p_target_discovered_cb(&mut self) {
+        unsafe { nfc_manager_unset_p2p_target_discovered_cb() };
+        self.p2p_fn = None;
+    }
+}
+
+extern "C" fn target_handler(
+    kind: nfc_discovered_type_e,
+    target: nfc_p2p_target_h,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut P2pFn) };
+
+        if kind == nfc_discovered_type_e_NFC_DISCOVERED_TYPE_ATTACHED {
+            closure(P2pEvent::Attached(P2pTarget {
+                handle: target,
+                data_fn: None,
+            }));
+        } else {
+            closure(P2pEvent::Detached);
+        }
+    })
+}
+
+extern "C" fn data_received_handler(
+    _target: nfc_p2p_target_h,
+    message: nfc_ndef_message_h,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut DataFn) };
+
+        if let Ok(message) = unsafe { NdefMessage::from_raw(message) } {
+            closure(message);
+        }
+    })
+}
@@ -0,0 +1,190 @@
+//! Declarations from the Accessory SDK's `sap.h`, which isn't part of the Tizen
+//! rootstrap and so isn't covered by `rutin-tizen-sys`.
+
+#![allow(non_camel_case_types)]
+
+use std::os::raw::{c_char, c_int, c_uint, c_ushort, c_void};
+
+pub type sap_agent_h = *mut c_void;
+pub type sap_peer_agent_h = *mut c_void;
+pub type sap_socket_h = *mut c_void;
+pub type sap_file_transaction_h = *mut c_void;
+
+pub type sap_agent_role_e = c_int;
+pub const SAP_AGENT_ROLE_PROVIDER: sap_agent_role_e = 0;
+pub const SAP_AGENT_ROLE_CONSUMER: sap_agent_role_e = 1;
+
+pub type sap_agent_initialized_result_e = c_int;
+pub const SAP_AGENT_INITIALIZED_RESULT_SUCCESS: sap_agent_initialized_result_e = 0;
+
+pub type sap_peer_agent_status_e = c_int;
+pub const SAP_PEER_AGENT_STATUS_AVAILABLE: sap_peer_agent_status_e = 0;
+
+pub type sap_peer_agent_found_result_e = c_int;
+pub const SAP_PEER_AGENT_FOUND_RESULT_DEVICE_NOT_CONNECTED: sap_peer_agent_found_result_e = 0;
+pub const SAP_PEER_AGENT_FOUND_RESULT_FOUND: sap_peer_agent_found_result_e = 1;
+pub const SAP_PEER_AGENT_FOUND_RESULT_SERVICE_NOT_FOUND: sap_peer_agent_found_result_e = 2;
+pub const SAP_PEER_AGENT_FOUND_RESULT_TIMEDOUT: sap_peer_agent_found_result_e = 3;
+
+pub type sap_service_connection_result_e = c_int;
+pub const SAP_CONNECTION_SUCCESS: sap_service_connection_result_e = 0;
+pub const SAP_CONNECTION_ALREADY_EXIST: sap_service_connection_result_e = 1;
+pub const SAP_CONNECTION_FAILURE_DEVICE_UNREACHABLE: sap_service_connection_result_e = 2;
+pub const SAP_CONNECTION_FAILURE_INVALID_PEERAGENT: sap_service_connection_result_e = 3;
+pub const SAP_CONNECTION_FAILURE_NETWORK: sap_service_connection_result_e = 4;
+pub const SAP_CONNECTION_FAILURE_PEERAGENT_NO_RESPONSE: sap_service_connection_result_e = 5;
+pub const SAP_CONNECTION_FAILURE_PEERAGENT_REJECTED: sap_service_connection_result_e = 6;
+
+pub type sap_service_connection_terminated_reason_e = c_int;
+
+pub type sap_ft_transfer_e = c_int;
+pub const SAP_FT_TRANSFER_SUCCESS: sap_ft_transfer_e = 0;
+
+pub type sap_agent_initialized_cb = Option<
+    extern "C" fn(
+        agent: sap_agent_h,
+        result: sap_agent_initialized_result_e,
+        user_data: *mut c_void,
+    ),
+>;
+pub type sap_peer_agent_updated_cb = Option<
+    extern "C" fn(
+        peer_agent: sap_peer_agent_h,
+        status: sap_peer_agent_status_e,
+        result: sap_peer_agent_found_result_e,
+        user_data: *mut c_void,
+    ),
+>;
+pub type sap_service_connection_cb = Option<
+    extern "C" fn(
+        peer_agent: sap_peer_agent_h,
+        socket: sap_socket_h,
+        result: sap_service_connection_result_e,
+        user_data: *mut c_void,
+    ),
+>;
+pub type sap_service_connection_terminated_cb = Option<
+    extern "C" fn(
+        peer_agent: sap_peer_agent_h,
+        socket: sap_socket_h,
+        reason: sap_service_connection_terminated_reason_e,
+        user_data: *mut c_void,
+    ),
+>;
+pub type sap_socket_data_received_cb = Option<
+    extern "C" fn(
+        socket: sap_socket_h,
+        channel_id: c_ushort,
+        payload_length: c_uint,
+        buffer: *mut c_void,
+        user_data: *mut c_void,
+    ),
+>;
+pub type sap_file_transfer_incoming_file_cb = Option<
+    extern "C" fn(
+        peer_agent: sap_peer_agent_h,
+        transaction: sap_file_transaction_h,
+        file_path: *const c_char,
+        user_data: *mut c_void,
+    ),
+>;
+pub type sap_file_transfer_progress_cb = Option<
+    extern "C" fn(
+        peer_agent: sap_peer_agent_h,
+        transaction: sap_file_transaction_h,
+        percentage_progress: c_ushort,
+        user_data: *mut c_void,
+    ),
+>;
+pub type sap_file_transfer_done_cb = Option<
+    extern "C" fn(
+        peer_agent: sap_peer_agent_h,
+        transaction: sap_file_transaction_h,
+        result: sap_ft_transfer_e,
+        file_path: *const c_char,
+        user_data: *mut c_void,
+    ),
+>;
+
+#[link(name = "sap-client-stub-api")]
+extern "C" {
+    pub fn sap_agent_create(agent: *mut sap_agent_h) -> c_int;
+    pub fn sap_agent_destroy(agent: sap_agent_h) -> c_int;
+    pub fn sap_agent_initialize(
+        agent: sap_agent_h,
+        profile_id: *const c_char,
+        role: sap_agent_role_e,
+        callback: sap_agent_initialized_cb,
+        user_data: *mut c_void,
+    ) -> c_int;
+    pub fn sap_agent_deinitialize(
+        agent: sap_agent_h,
+        callback: Option<extern "C" fn(sap_agent_h, c_int, *mut c_void)>,
+        user_data: *mut c_void,
+    ) -> c_int;
+    pub fn sap_agent_find_peer(
+        agent: sap_agent_h,
+        callback: sap_peer_agent_updated_cb,
+        user_data: *mut c_void,
+    ) -> c_int;
+    pub fn sap_agent_request_service_connection(
+        agent: sap_agent_h,
+        peer_agent: sap_peer_agent_h,
+        callback: sap_service_connection_cb,
+        user_data: *mut c_void,
+    ) -> c_int;
+    pub fn sap_agent_set_service_connection_requested_cb(
+        agent: sap_agent_h,
+        callback: sap_service_connection_cb,
+        user_data: *mut c_void,
+    ) -> c_int;
+    pub fn sap_agent_unset_service_connection_requested_cb(agent: sap_agent_h) -> c_int;
+
+    pub fn sap_peer_agent_accept_service_connection(peer_agent: sap_peer_agent_h) -> c_int;
+    pub fn sap_peer_agent_reject_service_connection(peer_agent: sap_peer_agent_h) -> c_int;
+    pub fn sap_peer_agent_terminate_service_connection(peer_agent: sap_peer_agent_h) -> c_int;
+    pub fn sap_peer_agent_set_service_connection_terminated_cb(
+        peer_agent: sap_peer_agent_h,
+        callback: sap_service_connection_terminated_cb,
+        user_data: *mut c_void,
+    ) -> c_int;
+
+    pub fn sap_socket_send_data(
+        socket: sap_socket_h,
+        channel_id: c_ushort,
+        payload_length: c_uint,
+        buffer: *mut c_void,
+    ) -> c_int;
+    pub fn sap_socket_set_data_received_cb(
+        socket: sap_socket_h,
+        callback: sap_socket_data_received_cb,
+        user_data: *mut c_void,
+    ) -> c_int;
+
+    pub fn sap_file_transfer_set_incoming_file_cb(
+        agent: sap_agent_h,
+        callback: sap_file_transfer_incoming_file_cb,
+        user_data: *mut c_void,
+    ) -> c_int;
+    pub fn sap_file_transfer_send(
+        peer_agent: sap_peer_agent_h,
+        file_path: *const c_char,
+        transaction: *mut sap_file_transaction_h,
+    ) -> c_int;
+    pub fn sap_file_transfer_receive(
+        transaction: sap_file_transaction_h,
+        file_path: *const c_char,
+    ) -> c_int;
+    pub fn sap_file_transfer_reject(transaction: sap_file_transaction_h) -> c_int;
+    pub fn sap_file_transfer_cancel(transaction: sap_file_transaction_h) -> c_int;
+    pub fn sap_file_transfer_set_progress_cb(
+        transaction: sap_file_transaction_h,
+        callback: sap_file_transfer_progress_cb,
+        user_data: *mut c_void,
+    ) -> c_int;
+    pub fn sap_file_transfer_set_done_cb(
+        transaction: sap_file_transaction_h,
+        callback: sap_file_transfer_done_cb,
+        user_data: *mut c_void,
+    ) -> c_int;
+}
@@ -0,0 +1,153 @@
+use super::ffi::*;
+use super::PeerAgent;
+use crate::error::{check, Error, Result};
+use crate::rutin::{abort_on_panic, borrow_str, cstring};
+use std::os::raw::{c_char, c_ushort, c_void};
+use std::pin::Pin;
+
+struct TransferState {
+    progress_fn: Box<dyn FnMut(u16)>,
+    done_fn: Option<Box<dyn FnOnce(Result<()>)>>,
+}
+
+/// A file being sent or received, cancelled if dropped before it is done.
+pub struct FileTransfer {
+    handle: sap_file_transaction_h,
+    state: Pin<Box<TransferState>>,
+}
+
+impl FileTransfer {
+    pub(super) fn new<P, D>(
+        handle: sap_file_transaction_h,
+        progress_fn: P,
+        done_fn: D,
+    ) -> Result<FileTransfer>
+    where
+        P: FnMut(u16),
+        P: 'static,
+        D: FnOnce(Result<()>),
+        D: 'static,
+    {
+        let transfer = Self {
+            handle,
+            state: Box::pin(TransferState {
+                progress_fn: Box::new(progress_fn),
+                done_fn: Some(Box::new(done_fn)),
+            }),
+        };
+        let data = &*transfer.state as *const TransferState as *mut c_void;
+
+        unsafe {
+            check(sap_file_transfer_set_progress_cb(
+                handle,
+                Some(progress_handler),
+                data,
+            ))?;
+            check(sap_file_transfer_set_done_cb(
+                handle,
+                Some(done_handler),
+                data,
+            ))?;
+        }
+
+        Ok(transfer)
+    }
+
+    pub fn cancel(&mut self) -> Result<()> {
+        check(unsafe { sap_file_transfer_cancel(self.handle) })
+    }
+}
+
+impl Drop for FileTransfer {
+    fn drop(&mut self) {
+        if self.state.done_fn.is_some() {
+            unsafe {
+                sap_file_transfer_cancel(self.handle);
+                sap_file_transfer_set_progress_cb(self.handle, None, std::ptr::null_mut());
+                sap_file_transfer_set_done_cb(self.handle, None, std::ptr::null_mut());
+            }
+        }
+    }
+}
+
+/// A file offered by a peer, to be received or rejected.
+pub struct IncomingFile {
+    handle: sap_file_transaction_h,
+    pub peer: PeerAgent,
+    /// The name of the file on the sender side.
+    pub path: String,
+}
+
+impl IncomingFile {
+    /// Accepts the file and stores it at `path`, which must be writable by the app.
+    pub fn receive<P, D>(self, path: &str, progress_fn: P, done_fn: D) -> Result<FileTransfer>
+    where
+        P: FnMut(u16),
+        P: 'static,
+        D: FnOnce(Result<()>),
+        D: 'static,
+    {
+        let path = cstring(path)?;
+
+        // the callbacks must be in place before the transfer starts
+        let transfer = FileTransfer::new(self.handle, progress_fn, done_fn)?;
+        check(unsafe { sap_file_transfer_receive(self.handle, path.as_ptr()) })?;
+
+        Ok(transfer)
+    }
+
+    pub fn reject(self) -> Result<()> {
+        check(unsafe { sap_file_transfer_reject(self.handle) })
+    }
+}
+
+pub(super) extern "C" fn incoming_file_handler(
+    peer: sap_peer_agent_h,
+    transaction: sap_file_transaction_h,
+    file_path: *const c_char,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut super::IncomingFileFn) };
+
+        closure(IncomingFile {
+            handle: transaction,
+            peer: PeerAgent { handle: peer },
+            path: unsafe { borrow_str(file_path) }
+                .unwrap_or_default()
+                .into_owned(),
+        });
+    })
+}
+
+extern "C" fn progress_handler(
+    _peer: sap_peer_agent_h,
+    _transaction: sap_file_transaction_h,
+    percentage_progress: c_ushort,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let state = unsafe { &mut *(user_data as *mut TransferState) };
+        (state.progress_fn)(percentage_progress);
+    })
+}
+
+extern "C" fn done_handler(
+    _peer: sap_peer_agent_h,
+    _transaction: sap_file_transaction_h,
+    result: sap_ft_transfer_e,
+    _file_path: *const c_char,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let state = unsafe { &mut *(user_data as *mut TransferState) };
+
+        if let Some(done_fn) = state.done_fn.take() {
+            if result == SAP_FT_TRANSFER_SUCCESS {
+                done_fn(Ok(()));
+            } else {
+                done_fn(Err(Error::Other(result)));
+            }
+        }
+    })
+}
@@ -0,0 +1,466 @@
+//! Samsung Accessory Protocol, the channel between Galaxy Watch apps and their
+//! companion app on the phone.
+//!
+//! Requires the `sap` feature, linking `libsap-client-stub-api` from the Accessory SDK,
+//! and the `http://developer.samsung.com/tizen/privilege/accessoryprotocol` privilege.
+//! The service profile must be declared in the application's accessory services XML.
+
+mod ffi;
+mod file;
+
+pub use file::{FileTransfer, IncomingFile};
+
+use self::ffi::*;
+use crate::error::{check, Error, Result};
+use crate::rutin::{abort_on_panic, cstring, pin_callback};
+use std::os::raw::{c_uint, c_ushort, c_void};
+use std::pin::Pin;
+use std::ptr::null_mut;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AgentRole {
+    Provider,
+    Consumer,
+}
+
+impl From<AgentRole> for sap_agent_role_e {
+    fn from(role: AgentRole) -> sap_agent_role_e {
+        match role {
+            AgentRole::Provider => SAP_AGENT_ROLE_PROVIDER,
+            AgentRole::Consumer => SAP_AGENT_ROLE_CONSUMER,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FindResult {
+    Found,
+    DeviceNotConnected,
+    ServiceNotFound,
+    TimedOut,
+    InternalError,
+}
+
+impl From<sap_peer_agent_found_result_e> for FindResult {
+    fn from(result: sap_peer_agent_found_result_e) -> FindResult {
+        match result {
+            SAP_PEER_AGENT_FOUND_RESULT_FOUND => FindResult::Found,
+            SAP_PEER_AGENT_FOUND_RESULT_DEVICE_NOT_CONNECTED => FindResult::DeviceNotConnected,
+            SAP_PEER_AGENT_FOUND_RESULT_SERVICE_NOT_FOUND => FindResult::ServiceNotFound,
+            SAP_PEER_AGENT_FOUND_RESULT_TIMEDOUT => FindResult::TimedOut,
+            _ => FindResult::InternalError,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ConnectionError {
+    AlreadyExists,
+    DeviceUnreachable,
+    InvalidPeer,
+    Network,
+    NoResponse,
+    Rejected,
+    Unknown,
+}
+
+fn connection_result(
+    result: sap_service_connection_result_e,
+) -> std::result::Result<(), ConnectionError> {
+    match result {
+        SAP_CONNECTION_SUCCESS => Ok(()),
+        SAP_CONNECTION_ALREADY_EXIST => Err(ConnectionError::AlreadyExists),
+        SAP_CONNECTION_FAILURE_DEVICE_UNREACHABLE => Err(ConnectionError::DeviceUnreachable),
+        SAP_CONNECTION_FAILURE_INVALID_PEERAGENT => Err(ConnectionError::InvalidPeer),
+        SAP_CONNECTION_FAILURE_NETWORK => Err(ConnectionError::Network),
+        SAP_CONNECTION_FAILURE_PEERAGENT_NO_RESPONSE => Err(ConnectionError::NoResponse),
+        SAP_CONNECTION_FAILURE_PEERAGENT_REJECTED => Err(ConnectionError::Rejected),
+        _ => Err(ConnectionError::Unknown),
+    }
+}
+
+/// The remote side of a service, owned by the accessory framework.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct PeerAgent {
+    handle: sap_peer_agent_h,
+}
+
+impl PeerAgent {
+    /// Accepts a connection received through [`Agent::set_connection_requested_cb`].
+    pub fn accept_connection(&self) -> Result<()> {
+        check(unsafe { sap_peer_agent_accept_service_connection(self.handle) })
+    }
+
+    pub fn reject_connection(&self) -> Result<()> {
+        check(unsafe { sap_peer_agent_reject_service_connection(self.handle) })
+    }
+
+    pub fn terminate_connection(&self) -> Result<()> {
+        check(unsafe { sap_peer_agent_terminate_service_connection(self.handle) })
+    }
+
+    /// Sends a file to the peer, reporting the progress in percent then the result.
+    pub fn send_file<P, D>(&self, path: &str, progress_fn: P, done_fn: D) -> Result<FileTransfer>
+    where
+        P: FnMut(u16),
+        P: 'static,
+        D: FnOnce(Result<()>),
+        D: 'static,
+    {
+        let path = cstring(path)?;
+        let mut handle = null_mut();
+        check(unsafe { sap_file_transfer_send(self.handle, path.as_ptr(), &mut handle) })?;
+
+        FileTransfer::new(handle, progress_fn, done_fn)
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct PeerUpdate {
+    pub peer: PeerAgent,
+    pub available: bool,
+    pub result: FindResult,
+}
+
+type DataFn = Box<dyn FnMut(u16, &[u8])>;
+type TerminatedFn = Box<dyn FnMut()>;
+
+/// A service connection with a peer, carrying data over numbered channels.
+pub struct Socket {
+    handle: sap_socket_h,
+    peer: PeerAgent,
+    data_fn: Option<Pin<Box<DataFn>>>,
+    terminated_fn: Option<Pin<Box<TerminatedFn>>>,
+}
+
+impl Socket {
+    fn new(handle: sap_socket_h, peer: sap_peer_agent_h) -> Socket {
+        Self {
+            handle,
+            peer: PeerAgent { handle: peer },
+            data_fn: None,
+            terminated_fn: None,
+        }
+    }
+
+    pub fn peer(&self) -> PeerAgent {
+        self.peer
+    }
+
+    /// Sends `data` on one of the channels declared in the service profile.
+    pub fn send(&mut self, channel_id: u16, data: &[u8]) -> Result<()> {
+        check(unsafe {
+            sap_socket_send_data(
+                self.handle,
+                channel_id,
+                data.len() as c_uint,
+                data.as_ptr() as *mut c_void,
+            )
+        })
+    }
+
+    /// Calls `callback_fn` with the channel and payload of each received message,
+    /// replacing the previous callback.
+    pub fn set_data_received_cb<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(u16, &[u8]),
+        F: 'static,
+    {
+        let (callback_fn, data) = pin_callback::<dyn FnMut(u16, &[u8])>(Box::new(callback_fn));
+
+        check(unsafe { sap_socket_set_data_received_cb(self.handle, Some(data_handler), data) })?;
+        self.data_fn = Some(callback_fn);
+
+        Ok(())
+    }
+
+    /// Calls `callback_fn` when the connection is closed by either side or lost.
+    pub fn set_terminated_cb<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(),
+        F: 'static,
+    {
+        let (callback_fn, data) = pin_callback::<dyn FnMut()>(Box::new(callback_fn));
+
+        check(unsafe {
+            sap_peer_agent_set_service_connection_terminated_cb(
+                self.peer.handle,
+                Some(terminated_handler),
+                data,
+            )
+        })?;
+        self.terminated_fn = Some(callback_fn);
+
+        Ok(())
+    }
+}
+
+impl Drop for Socket {
+    fn drop(&mut self) {
+        unsafe {
+            if self.data_fn.is_some() {
+                sap_socket_set_data_received_cb(self.handle, None, null_mut());
+            }
+            if self.terminated_fn.is_some() {
+                sap_peer_agent_set_service_connection_terminated_cb(
+                    self.peer.handle,
+                    None,
+                    null_mut(),
+                );
+            }
+        }
+    }
+}
+
+// taken out by the handler when called, freed with the agent otherwise
+type InitializedFn = Option<Box<dyn FnOnce(Result<()>)>>;
+type PeerFn = Box<dyn FnMut(PeerUpdate)>;
+type ConnectionFn = Option<Box<dyn FnOnce(std::result::Result<Socket, ConnectionError>)>>;
+type RequestedFn = Box<dyn FnMut(Socket)>;
+type IncomingFileFn = Box<dyn FnMut(IncomingFile)>;
+
+/// The local side of a service, as declared by its profile id.
+pub struct Agent {
+    handle: sap_agent_h,
+    initialized_fn: Pin<Box<InitializedFn>>,
+    connection_fns: Vec<Pin<Box<ConnectionFn>>>,
+    peer_fn: Option<Pin<Box<PeerFn>>>,
+    requested_fn: Option<Pin<Box<RequestedFn>>>,
+    incoming_file_fn: Option<Pin<Box<IncomingFileFn>>>,
+}
+
+impl Agent {
+    /// Registers the agent for `profile_id`, `callback_fn` is called once it can be used.
+    pub fn new<F>(profile_id: &str, role: AgentRole, callback_fn: F) -> Result<Agent>
+    where
+        F: FnOnce(Result<()>),
+        F: 'static,
+    {
+        let profile_id = cstring(profile_id)?;
+
+        let mut handle = null_mut();
+        check(unsafe { sap_agent_create(&mut handle) })?;
+
+        let agent = Self {
+            handle,
+            initialized_fn: Box::pin(Some(Box::new(callback_fn))),
+            connection_fns: Vec::new(),
+            peer_fn: None,
+            requested_fn: None,
+            incoming_file_fn: None,
+        };
+        let data = &*agent.initialized_fn as *const InitializedFn as *mut c_void;
+
+        check(unsafe {
+            sap_agent_initialize(
+                agent.handle,
+                profile_id.as_ptr(),
+                role.into(),
+                Some(initialized_handler),
+                data,
+            )
+        })?;
+
+        Ok(agent)
+    }
+
+    /// Looks for peers of the service, `callback_fn` is called each time one is
+    /// found or becomes unavailable.
+    pub fn find_peer<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(PeerUpdate),
+        F: 'static,
+    {
+        let (callback_fn, data) = pin_callback::<dyn FnMut(PeerUpdate)>(Box::new(callback_fn));
+
+        check(unsafe { sap_agent_find_peer(self.handle, Some(peer_handler), data) })?;
+        self.peer_fn = Some(callback_fn);
+
+        Ok(())
+    }
+
+    /// Opens a service connection to `peer`, `callback_fn` is called with the socket.
+    pub fn request_connection<F>(&mut self, peer: &PeerAgent, callback_fn: F) -> Result<()>
+    where
+        F: FnOnce(std::result::Result<Socket, ConnectionError>),
+        F: 'static,
+    {
+        // the slots of the requests already answered aren't needed anymore
+        self.connection_fns
+            .retain(|callback_fn| callback_fn.is_some());
+
+        let callback_fn: Pin<Box<ConnectionFn>> = Box::pin(Some(Box::new(callback_fn)));
+        let data = &*callback_fn as *const ConnectionFn as *mut c_void;
+
+        check(unsafe {
+            sap_agent_request_service_connection(
+                self.handle,
+                peer.handle,
+                Some(connection_handler),
+                data,
+            )
+        })?;
+        self.connection_fns.push(callback_fn);
+
+        Ok(())
+    }
+
+    /// Calls `callback_fn` with each connection requested by a peer, which must then
+    /// be accepted or rejected through [`Socket::peer`].
+    pub fn set_connection_requested_cb<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(Socket),
+        F: 'static,
+    {
+        let (callback_fn, data) = pin_callback::<dyn FnMut(Socket)>(Box::new(callback_fn));
+
+        check(unsafe {
+            sap_agent_set_service_connection_requested_cb(
+                self.handle,
+                Some(requested_handler),
+                data,
+            )
+        })?;
+        self.requested_fn = Some(callback_fn);
+
+        Ok(())
+    }
+
+    pub fn unset_connection_requested_cb(&mut self) -> Result<()> {
+        check(unsafe { sap_agent_unset_service_connection_requested_cb(self.handle) })?;
+        self.requested_fn = None;
+
+        Ok(())
+    }
+
+    /// Calls `callback_fn` with each file a peer offers to send.
+    pub fn set_incoming_file_cb<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(IncomingFile),
+        F: 'static,
+    {
+        let (callback_fn, data) = pin_callback::<dyn FnMut(IncomingFile)>(Box::new(callback_fn));
+
+        check(unsafe {
+            sap_file_transfer_set_incoming_file_cb(
+                self.handle,
+                Some(file::incoming_file_handler),
+                data,
+            )
+        })?;
+        self.incoming_file_fn = Some(callback_fn);
+
+        Ok(())
+    }
+}
+
+impl Drop for Agent {
+    fn drop(&mut self) {
+        unsafe {
+            if self.requested_fn.is_some() {
+                sap_agent_unset_service_connection_requested_cb(self.handle);
+            }
+            if self.incoming_file_fn.is_some() {
+                sap_file_transfer_set_incoming_file_cb(self.handle, None, null_mut());
+            }
+            sap_agent_deinitialize(self.handle, None, null_mut());
+            sap_agent_destroy(self.handle);
+        }
+    }
+}
+
+extern "C" fn initialized_handler(
+    _agent: sap_agent_h,
+    result: sap_agent_initialized_result_e,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let slot = unsafe { &mut *(user_data as *mut InitializedFn) };
+
+        if let Some(closure) = slot.take() {
+            if result == SAP_AGENT_INITIALIZED_RESULT_SUCCESS {
+                closure(Ok(()));
+            } else {
+                closure(Err(Error::Other(result)));
+            }
+        }
+    })
+}
+
+extern "C" fn peer_handler(
+    peer: sap_peer_agent_h,
+    status: sap_peer_agent_status_e,
+    result: sap_peer_agent_found_result_e,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut PeerFn) };
+
+        closure(PeerUpdate {
+            peer: PeerAgent { handle: peer },
+            available: status == SAP_PEER_AGENT_STATUS_AVAILABLE,
+            result: result.into(),
+        });
+    })
+}
+
+extern "C" fn connection_handler(
+    peer: sap_peer_agent_h,
+    socket: sap_socket_h,
+    result: sap_service_connection_result_e,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let slot = unsafe { &mut *(user_data as *mut ConnectionFn) };
+
+        if let Some(closure) = slot.take() {
+            closure(connection_result(result).map(|_| Socket::new(socket, peer)));
+        }
+    })
+}
+
+extern "C" fn requested_handler(
+    peer: sap_peer_agent_h,
+    socket: sap_socket_h,
+    _result: sap_service_connection_result_e,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut RequestedFn) };
+        closure(Socket::new(socket, peer));
+    })
+}
+
+extern "C" fn data_handler(
+    _socket: sap_socket_h,
+    channel_id: c_ushort,
+    payload_length: c_uint,
+    buffer: *mut c_void,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut DataFn) };
+        let payload = if buffer.is_null() {
+            &[][..]
+        } else {
+            unsafe { std::slice::from_raw_parts(buffer as *const u8, payload_length as usize) }
+        };
+
+        closure(channel_id, payload);
+    })
+}
+
+extern "C" fn terminated_handler(
+    _peer: sap_peer_agent_h,
+    _socket: sap_socket_h,
+    _reason: sap_service_connection_terminated_reason_e,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut TerminatedFn) };
+        closure();
+    })
+}
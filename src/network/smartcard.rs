@@ -0,0 +1,244 @@
+//! Readers, sessions and channels of the secure elements.
+//!
+//! Requires the `http://tizen.org/privilege/secureelement` privilege.
+
+use crate::error::{check, Result};
+use crate::rutin::take_string;
+use rutin_tizen_sys::{
+    smartcard_channel_close, smartcard_channel_get_select_response,
+    smartcard_channel_is_basic_channel, smartcard_channel_is_closed, smartcard_channel_select_next,
+    smartcard_channel_transmit, smartcard_deinitialize, smartcard_get_readers,
+    smartcard_initialize, smartcard_reader_close_sessions, smartcard_reader_get_name,
+    smartcard_reader_is_secure_element_present, smartcard_reader_open_session,
+    smartcard_session_close, smartcard_session_close_channels, smartcard_session_get_atr,
+    smartcard_session_is_closed, smartcard_session_open_basic_channel,
+    smartcard_session_open_logical_channel,
+};
+use std::marker::PhantomData;
+use std::os::raw::{c_int, c_void};
+use std::ptr::null_mut;
+
+/// Copies an array allocated by the API, then frees it.
+unsafe fn take_bytes<T: Copy>(ptr: *mut T, len: c_int) -> Vec<T> {
+    if ptr.is_null() {
+        return Vec::new();
+    }
+
+    let bytes = std::slice::from_raw_parts(ptr, len as usize).to_vec();
+    libc::free(ptr as *mut c_void);
+
+    bytes
+}
+
+/// The smartcard service, deinitialized when this is dropped.
+pub struct SmartcardService {
+    _private: (),
+}
+
+impl SmartcardService {
+    pub fn new() -> Result<SmartcardService> {
+        check(unsafe { smartcard_initialize() })?;
+
+        Ok(Self { _private: () })
+    }
+
+    pub fn readers(&self) -> Result<Vec<Reader<'_>>> {
+        let mut readers = null_mut();
+        let mut len = 0;
+        check(unsafe { smartcard_get_readers(&mut readers, &mut len) })?;
+
+        Ok(unsafe { take_bytes(readers, len) }
+            .into_iter()
+            .map(|handle| Reader {
+                handle,
+                _service: PhantomData,
+            })
+            .collect())
+    }
+}
+
+impl Drop for SmartcardService {
+    fn drop(&mut self) {
+        unsafe {
+            smartcard_deinitialize();
+        }
+    }
+}
+
+/// A slot that may hold a secure element, e.g. the eSE or the SIM.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Reader<'s> {
+    handle: c_int,
+    _service: PhantomData<&'s SmartcardService>,
+}
+
+impl<'s> Reader<'s> {
+    pub fn name(&self) -> Result<String> {
+        let mut name = null_mut();
+        check(unsafe { smartcard_reader_get_name(self.handle, &mut name) })?;
+
+        Ok(unsafe { take_string(name) }.unwrap_or_default())
+    }
+
+    pub fn is_secure_element_present(&self) -> Result<bool> {
+        let mut present = false;
+        check(unsafe { smartcard_reader_is_secure_element_present(self.handle, &mut present) })?;
+
+        Ok(present)
+    }
+
+    pub fn open_session(&self) -> Result<Session<'s>> {
+        let mut handle = 0;
+        check(unsafe { smartcard_reader_open_session(self.handle, &mut handle) })?;
+
+        Ok(Session {
+            handle,
+            _service: PhantomData,
+        })
+    }
+
+    /// Closes every session opened on this reader.
+    pub fn close_sessions(&self) -> Result<()> {
+        check(unsafe { smartcard_reader_close_sessions(self.handle) })
+    }
+}
+
+/// A connection to a secure element, closed when dropped.
+pub struct Session<'s> {
+    handle: c_int,
+    _service: PhantomData<&'s SmartcardService>,
+}
+
+impl<'s> Session<'s> {
+    /// The answer to reset of the secure element.
+    pub fn atr(&self) -> Result<Vec<u8>> {
+        let mut atr = null_mut();
+        let mut len = 0;
+        check(unsafe { smartcard_session_get_atr(self.handle, &mut atr, &mut len) })?;
+
+        Ok(unsafe { take_bytes(atr, len) })
+    }
+
+    pub fn is_closed(&self) -> Result<bool> {
+        let mut closed = false;
+        check(unsafe { smartcard_session_is_closed(self.handle, &mut closed) })?;
+
+        Ok(closed)
+    }
+
+    /// Opens the basic channel, selecting the applet `aid` (the default one when empty).
+    pub fn open_basic_channel(&self, aid: &[u8], p2: u8) -> Result<Channel<'_>> {
+        let mut handle = 0;
+        check(unsafe {
+            smartcard_session_open_basic_channel(
+                self.handle,
+                aid.as_ptr() as *mut u8,
+                aid.len() as c_int,
+                p2,
+                &mut handle,
+            )
+        })?;
+
+        Ok(Channel {
+            handle,
+            _session: PhantomData,
+        })
+    }
+
+    /// Opens a logical channel, selecting the applet `aid`.
+    pub fn open_logical_channel(&self, aid: &[u8], p2: u8) -> Result<Channel<'_>> {
+        let mut handle = 0;
+        check(unsafe {
+            smartcard_session_open_logical_channel(
+                self.handle,
+                aid.as_ptr() as *mut u8,
+                aid.len() as c_int,
+                p2,
+                &mut handle,
+            )
+        })?;
+
+        Ok(Channel {
+            handle,
+            _session: PhantomData,
+        })
+    }
+
+    pub fn close_channels(&mut self) -> Result<()> {
+        check(unsafe { smartcard_session_close_channels(self.handle) })
+    }
+}
+
+impl<'s> Drop for Session<'s> {
+    fn drop(&mut self) {
+        unsafe {
+            smartcard_session_close(self.handle);
+        }
+    }
+}
+
+/// A channel to an applet, closed when dropped.
+pub struct Channel<'a> {
+    handle: c_int,
+    _session: PhantomData<&'a Session<'a>>,
+}
+
+impl<'a> Channel<'a> {
+    /// Sends a command APDU, returning the response APDU including its status word.
+    pub fn transmit(&mut self, command: &[u8]) -> Result<Vec<u8>> {
+        let mut response = null_mut();
+        let mut len = 0;
+        check(unsafe {
+            smartcard_channel_transmit(
+                self.handle,
+                command.as_ptr() as *mut u8,
+                command.len() as c_int,
+                &mut response,
+                &mut len,
+            )
+        })?;
+
+        Ok(unsafe { take_bytes(response, len) })
+    }
+
+    /// The response to the SELECT command sent when the channel was opened.
+    pub fn select_response(&self) -> Result<Vec<u8>> {
+        let mut response = null_mut();
+        let mut len = 0;
+        check(unsafe {
+            smartcard_channel_get_select_response(self.handle, &mut response, &mut len)
+        })?;
+
+        Ok(unsafe { take_bytes(response, len) })
+    }
+
+    /// Selects the next applet matching the partial AID, returns whether there was one.
+    pub fn select_next(&mut self) -> Result<bool> {
+        let mut selected = false;
+        check(unsafe { smartcard_channel_select_next(self.handle, &mut selected) })?;
+
+        Ok(selected)
+    }
+
+    pub fn is_basic_channel(&self) -> Result<bool> {
+        let mut basic = false;
+        check(unsafe { smartcard_channel_is_basic_channel(self.handle, &mut basic) })?;
+
+        Ok(basic)
+    }
+
+    pub fn is_closed(&self) -> Result<bool> {
+        let mut closed = false;
+        check(unsafe { smartcard_channel_is_closed(self.handle, &mut closed) })?;
+
+        Ok(closed)
+    }
+}
+
+impl<'a> Drop for Channel<'a> {
+    fn drop(&mut self) {
+        unsafe {
+            smartcard_channel_close(self.handle);
+        }
+    }
+}
@@ -0,0 +1,330 @@
+//! Smart Traffic Control, the network data usage statistics.
+//!
+//! Requires the `http://tizen.org/privilege/network.get` privilege.
+
+use crate::error::{check, Result};
+use crate::rutin::{abort_on_panic, cstring, take_string};
+use rutin_tizen_sys::{
+    stc_all_stats_info_h, stc_callback_ret_e, stc_callback_ret_e_STC_CALLBACK_CANCEL,
+    stc_callback_ret_e_STC_CALLBACK_CONTINUE, stc_deinitialize, stc_foreach_all_stats,
+    stc_get_all_stats, stc_get_stats, stc_get_total_stats, stc_h, stc_iface_type_e,
+    stc_iface_type_e_STC_IFACE_ALL, stc_iface_type_e_STC_IFACE_BLUETOOTH,
+    stc_iface_type_e_STC_IFACE_DATACALL, stc_iface_type_e_STC_IFACE_UNKNOWN,
+    stc_iface_type_e_STC_IFACE_WIFI, stc_iface_type_e_STC_IFACE_WIRED, stc_initialize,
+    stc_stats_info_cb, stc_stats_info_get_app_id, stc_stats_info_get_counter,
+    stc_stats_info_get_iface_name, stc_stats_info_get_iface_type, stc_stats_info_get_time_interval,
+    stc_stats_info_h, stc_stats_rule_create, stc_stats_rule_destroy, stc_stats_rule_h,
+    stc_stats_rule_set_app_id, stc_stats_rule_set_iface_type, stc_stats_rule_set_time_interval,
+};
+use std::os::raw::{c_int, c_void};
+use std::ptr::null_mut;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Interface {
+    Unknown,
+    /// Mobile data.
+    DataCall,
+    Wifi,
+    Wired,
+    Bluetooth,
+    All,
+}
+
+impl From<Interface> for stc_iface_type_e {
+    fn from(interface: Interface) -> stc_iface_type_e {
+        match interface {
+            Interface::Unknown => stc_iface_type_e_STC_IFACE_UNKNOWN,
+            Interface::DataCall => stc_iface_type_e_STC_IFACE_DATACALL,
+            Interface::Wifi => stc_iface_type_e_STC_IFACE_WIFI,
+            Interface::Wired => stc_iface_type_e_STC_IFACE_WIRED,
+            Interface::Bluetooth => stc_iface_type_e_STC_IFACE_BLUETOOTH,
+            Interface::All => stc_iface_type_e_STC_IFACE_ALL,
+        }
+    }
+}
+
+impl From<stc_iface_type_e> for Interface {
+    fn from(interface: stc_iface_type_e) -> Interface {
+        match interface {
+            stc_iface_type_e_STC_IFACE_DATACALL => Interface::DataCall,
+            stc_iface_type_e_STC_IFACE_WIFI => Interface::Wifi,
+            stc_iface_type_e_STC_IFACE_WIRED => Interface::Wired,
+            stc_iface_type_e_STC_IFACE_BLUETOOTH => Interface::Bluetooth,
+            stc_iface_type_e_STC_IFACE_ALL => Interface::All,
+            _ => Interface::Unknown,
+        }
+    }
+}
+
+/// Which traffic to count, times being in seconds since the epoch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StatsRule {
+    /// Only count the traffic of this application, all of them when `None`.
+    pub app_id: Option<String>,
+    pub interface: Interface,
+    pub from: i64,
+    pub to: i64,
+}
+
+impl StatsRule {
+    pub fn new(interface: Interface, from: i64, to: i64) -> StatsRule {
+        Self {
+            app_id: None,
+            interface,
+            from,
+            to,
+        }
+    }
+
+    pub fn app_id(mut self, app_id: &str) -> StatsRule {
+        self.app_id = Some(app_id.to_owned());
+        self
+    }
+}
+
+/// A stats rule handle, destroyed with this value.
+struct RawRule {
+    handle: stc_stats_rule_h,
+}
+
+impl RawRule {
+    fn new(stc: stc_h, rule: &StatsRule) -> Result<RawRule> {
+        let mut handle = null_mut();
+        check(unsafe { stc_stats_rule_create(stc, &mut handle) })?;
+        let raw = Self { handle };
+
+        if let Some(app_id) = &rule.app_id {
+            let app_id = cstring(app_id.as_str())?;
+            check(unsafe { stc_stats_rule_set_app_id(handle, app_id.as_ptr()) })?;
+        }
+
+        check(unsafe { stc_stats_rule_set_iface_type(handle, rule.interface.into()) })?;
+        check(unsafe {
+            stc_stats_rule_set_time_interval(
+                handle,
+                rule.from as libc::time_t,
+                rule.to as libc::time_t,
+            )
+        })?;
+
+        Ok(raw)
+    }
+}
+
+impl Drop for RawRule {
+    fn drop(&mut self) {
+        unsafe {
+            stc_stats_rule_destroy(self.handle);
+        }
+    }
+}
+
+/// The traffic counted over a time interval.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Stats {
+    pub app_id: Option<String>,
+    pub iface_name: Option<String>,
+    pub interface: Interface,
+    pub from: i64,
+    pub to: i64,
+    /// Received bytes.
+    pub incoming: i64,
+    /// Sent bytes.
+    pub outgoing: i64,
+}
+
+impl Stats {
+    unsafe fn from_raw(info: stc_stats_info_h) -> Result<Stats> {
+        let mut app_id = null_mut();
+        stc_stats_info_get_app_id(info, &mut app_id);
+
+        let mut iface_name = null_mut();
+        stc_stats_info_get_iface_name(info, &mut iface_name);
+
+        let mut interface = stc_iface_type_e_STC_IFACE_UNKNOWN;
+        stc_stats_info_get_iface_type(info, &mut interface);
+
+        let mut from: libc::time_t = 0;
+        let mut to: libc::time_t = 0;
+        check(stc_stats_info_get_time_interval(info, &mut from, &mut to))?;
+
+        let mut incoming = 0;
+        let mut outgoing = 0;
+        check(stc_stats_info_get_counter(
+            info,
+            &mut incoming,
+            &mut outgoing,
+        ))?;
+
+        Ok(Self {
+            app_id: take_string(app_id),
+            iface_name: take_string(iface_name),
+            interface: interface.into(),
+            from: i64::from(from),
+            to: i64::from(to),
+            incoming,
+            outgoing,
+        })
+    }
+}
+
+type StatsFn = dyn FnOnce(Result<Stats>);
+type AllStatsFn = dyn FnOnce(Result<Vec<Stats>>);
+
+/// The STC service, deinitialized when this is dropped.
+pub struct Stc {
+    handle: stc_h,
+}
+
+impl Stc {
+    pub fn new() -> Result<Stc> {
+        let mut handle = null_mut();
+        check(unsafe { stc_initialize(&mut handle) })?;
+
+        Ok(Self { handle })
+    }
+
+    /// Counts the traffic matching `rule`, `callback_fn` is called with the result.
+    pub fn get_stats<F>(&self, rule: &StatsRule, callback_fn: F) -> Result<()>
+    where
+        F: FnOnce(Result<Stats>),
+        F: 'static,
+    {
+        self.request_stats(rule, callback_fn, stc_get_stats)
+    }
+
+    /// Counts the traffic of every application together.
+    pub fn get_total_stats<F>(&self, rule: &StatsRule, callback_fn: F) -> Result<()>
+    where
+        F: FnOnce(Result<Stats>),
+        F: 'static,
+    {
+        self.request_stats(rule, callback_fn, stc_get_total_stats)
+    }
+
+    fn request_stats<F>(
+        &self,
+        rule: &StatsRule,
+        callback_fn: F,
+        request: unsafe extern "C" fn(
+            stc_h,
+            stc_stats_rule_h,
+            stc_stats_info_cb,
+            *mut c_void,
+        ) -> c_int,
+    ) -> Result<()>
+    where
+        F: FnOnce(Result<Stats>),
+        F: 'static,
+    {
+        let rule = RawRule::new(self.handle, rule)?;
+
+        let callback_fn: Box<StatsFn> = Box::new(callback_fn);
+        let data = Box::into_raw(Box::new(callback_fn));
+
+        let ret = unsafe {
+            request(
+                self.handle,
+                rule.handle,
+                Some(stats_handler),
+                data as *mut c_void,
+            )
+        };
+
+        if let Err(e) = check(ret) {
+            drop(unsafe { Box::from_raw(data) });
+            return Err(e);
+        }
+
+        Ok(())
+    }
+
+    /// Counts the traffic matching `rule` for each application separately.
+    pub fn get_all_stats<F>(&self, rule: &StatsRule, callback_fn: F) -> Result<()>
+    where
+        F: FnOnce(Result<Vec<Stats>>),
+        F: 'static,
+    {
+        let rule = RawRule::new(self.handle, rule)?;
+
+        let callback_fn: Box<AllStatsFn> = Box::new(callback_fn);
+        let data = Box::into_raw(Box::new(callback_fn));
+
+        let ret = unsafe {
+            stc_get_all_stats(
+                self.handle,
+                rule.handle,
+                Some(all_stats_handler),
+                data as *mut c_void,
+            )
+        };
+
+        if let Err(e) = check(ret) {
+            drop(unsafe { Box::from_raw(data) });
+            return Err(e);
+        }
+
+        Ok(())
+    }
+}
+
+impl Drop for Stc {
+    fn drop(&mut self) {
+        unsafe {
+            stc_deinitialize(self.handle);
+        }
+    }
+}
+
+extern "C" fn stats_handler(
+    result: c_int,
+    info: stc_stats_info_h,
+    user_data: *mut c_void,
+) -> stc_callback_ret_e {
+    abort_on_panic(|| {
+        let closure = unsafe { Box::from_raw(user_data as *mut Box<StatsFn>) };
+        closure(check(result).and_then(|_| unsafe { Stats::from_raw(info) }));
+
+        stc_callback_ret_e_STC_CALLBACK_CANCEL
+    })
+}
+
+extern "C" fn all_stats_handler(result: c_int, info: stc_all_stats_info_h, user_data: *mut c_void) {
+    abort_on_panic(|| {
+        let closure = unsafe { Box::from_raw(user_data as *mut Box<AllStatsFn>) };
+
+        let stats = check(result).and_then(|_| {
+            let mut stats: Vec<Stats> = Vec::new();
+            check(unsafe {
+                stc_foreach_all_stats(
+                    info,
+                    Some(collect_handler),
+                    &mut stats as *mut Vec<Stats> as *mut c_void,
+                )
+            })?;
+
+            Ok(stats)
+        });
+
+        closure(stats);
+    })
+}
+
+extern "C" fn collect_handler(
+    _result: c_int,
+    info: stc_stats_info_h,
+    user_data: *mut c_void,
+) -> stc_callback_ret_e {
+    abort_on_panic(|| {
+        let stats = unsafe { &mut *(user_data as *mut Vec<Stats>) };
+
+        if let Ok(info) = unsafe { Stats::from_raw(info) } {
+            stats.push(info);
+        }
+
+        stc_callback_ret_e_STC_CALLBACK_CONTINUE
+    })
+}
@@ -0,0 +1,347 @@
+//! Sharing the device's connection over Wi-Fi, Bluetooth or USB.
+//!
+//! Requires the `http://tizen.org/privilege/tethering.admin` privilege.
+
+use crate::error::{check, Error, Result};
+use crate::rutin::{abort_on_panic, cstring, pin_callback, take_string};
+use rutin_tizen_sys::{
+    tethering_address_family_e_TETHERING_ADDRESS_FAMILY_IPV4, tethering_client_get_ip_address,
+    tethering_client_get_mac_address, tethering_client_get_name,
+    tethering_client_get_tethering_type, tethering_client_h, tethering_create, tethering_destroy,
+    tethering_disable, tethering_disabled_cause_e, tethering_enable, tethering_h,
+    tethering_is_enabled, tethering_set_connection_state_changed_cb, tethering_set_disabled_cb,
+    tethering_set_enabled_cb, tethering_type_e, tethering_type_e_TETHERING_TYPE_ALL,
+    tethering_type_e_TETHERING_TYPE_BT, tethering_type_e_TETHERING_TYPE_USB,
+    tethering_type_e_TETHERING_TYPE_WIFI, tethering_unset_connection_state_changed_cb,
+    tethering_unset_disabled_cb, tethering_unset_enabled_cb, tethering_wifi_get_passphrase,
+    tethering_wifi_get_ssid, tethering_wifi_security_type_e,
+    tethering_wifi_security_type_e_TETHERING_WIFI_SECURITY_TYPE_NONE,
+    tethering_wifi_security_type_e_TETHERING_WIFI_SECURITY_TYPE_WPA2_PSK,
+    tethering_wifi_set_passphrase, tethering_wifi_set_security_type, tethering_wifi_set_ssid,
+    tethering_wifi_set_ssid_visibility,
+};
+use std::convert::TryFrom;
+use std::os::raw::{c_int, c_void};
+use std::pin::Pin;
+use std::ptr::null_mut;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TetheringType {
+    All,
+    Usb,
+    Wifi,
+    Bluetooth,
+}
+
+impl From<TetheringType> for tethering_type_e {
+    fn from(tethering_type: TetheringType) -> tethering_type_e {
+        match tethering_type {
+            TetheringType::All => tethering_type_e_TETHERING_TYPE_ALL,
+            TetheringType::Usb => tethering_type_e_TETHERING_TYPE_USB,
+            TetheringType::Wifi => tethering_type_e_TETHERING_TYPE_WIFI,
+            TetheringType::Bluetooth => tethering_type_e_TETHERING_TYPE_BT,
+        }
+    }
+}
+
+impl TryFrom<tethering_type_e> for TetheringType {
+    type Error = Error;
+
+    fn try_from(tethering_type: tethering_type_e) -> Result<TetheringType> {
+        match tethering_type {
+            tethering_type_e_TETHERING_TYPE_ALL => Ok(TetheringType::All),
+            tethering_type_e_TETHERING_TYPE_USB => Ok(TetheringType::Usb),
+            tethering_type_e_TETHERING_TYPE_WIFI => Ok(TetheringType::Wifi),
+            tethering_type_e_TETHERING_TYPE_BT => Ok(TetheringType::Bluetooth),
+            _ => Err(Error::Unknown),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum WifiSecurity {
+    None,
+    Wpa2Psk,
+}
+
+impl From<WifiSecurity> for tethering_wifi_security_type_e {
+    fn from(security: WifiSecurity) -> tethering_wifi_security_type_e {
+        match security {
+            WifiSecurity::None => tethering_wifi_security_type_e_TETHERING_WIFI_SECURITY_TYPE_NONE,
+            WifiSecurity::Wpa2Psk => {
+                tethering_wifi_security_type_e_TETHERING_WIFI_SECURITY_TYPE_WPA2_PSK
+            }
+        }
+    }
+}
+
+/// A device using the shared connection.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TetheringClient {
+    pub name: Option<String>,
+    pub ip_address: Option<String>,
+    pub mac_address: Option<String>,
+    pub tethering_type: Option<TetheringType>,
+}
+
+impl TetheringClient {
+    unsafe fn from_raw(client: tethering_client_h) -> TetheringClient {
+        let mut name = null_mut();
+        tethering_client_get_name(client, &mut name);
+
+        let mut ip_address = null_mut();
+        tethering_client_get_ip_address(
+            client,
+            tethering_address_family_e_TETHERING_ADDRESS_FAMILY_IPV4,
+            &mut ip_address,
+        );
+
+        let mut mac_address = null_mut();
+        tethering_client_get_mac_address(client, &mut mac_address);
+
+        let mut tethering_type = tethering_type_e_TETHERING_TYPE_ALL;
+        let tethering_type = check(tethering_client_get_tethering_type(
+            client,
+            &mut tethering_type,
+        ))
+        .and_then(|_| TetheringType::try_from(tethering_type))
+        .ok();
+
+        Self {
+            name: take_string(name),
+            ip_address: take_string(ip_address),
+            mac_address: take_string(mac_address),
+            tethering_type,
+        }
+    }
+}
+
+type StateFn = Box<dyn FnMut(Result<()>, TetheringType)>;
+type ConnectionFn = Box<dyn FnMut(TetheringClient, bool)>;
+
+pub struct Tethering {
+    handle: tethering_h,
+    enabled_fn: Option<Pin<Box<StateFn>>>,
+    disabled_fn: Option<Pin<Box<StateFn>>>,
+    connection_fn: Option<Pin<Box<ConnectionFn>>>,
+}
+
+impl Tethering {
+    pub fn new() -> Result<Tethering> {
+        let mut handle = null_mut();
+        check(unsafe { tethering_create(&mut handle) })?;
+
+        Ok(Self {
+            handle,
+            enabled_fn: None,
+            disabled_fn: None,
+            connection_fn: None,
+        })
+    }
+
+    /// Starts tethering, the result is reported to the enabled callback.
+    pub fn enable(&mut self, tethering_type: TetheringType) -> Result<()> {
+        check(unsafe { tethering_enable(self.handle, tethering_type.into()) })
+    }
+
+    /// Stops tethering, the result is reported to the disabled callback.
+    pub fn disable(&mut self, tethering_type: TetheringType) -> Result<()> {
+        check(unsafe { tethering_disable(self.handle, tethering_type.into()) })
+    }
+
+    pub fn is_enabled(&self, tethering_type: TetheringType) -> bool {
+        unsafe { tethering_is_enabled(self.handle, tethering_type.into()) }
+    }
+
+    /// Calls `callback_fn` each time a tethering type is enabled, replacing the previous callback.
+    pub fn set_enabled_cb<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(Result<()>, TetheringType),
+        F: 'static,
+    {
+        let (callback_fn, data) =
+            pin_callback::<dyn FnMut(Result<()>, TetheringType)>(Box::new(callback_fn));
+
+        check(unsafe {
+            tethering_set_enabled_cb(
+                self.handle,
+                tethering_type_e_TETHERING_TYPE_ALL,
+                Some(enabled_handler),
+                data,
+            )
+        })?;
+        self.enabled_fn = Some(callback_fn);
+
+        Ok(())
+    }
+
+    pub fn unset_enabled_cb(&mut self) -> Result<()> {
+        check(unsafe {
+            tethering_unset_enabled_cb(self.handle, tethering_type_e_TETHERING_TYPE_ALL)
+        })?;
+        self.enabled_fn = None;
+
+        Ok(())
+    }
+
+    /// Calls `callback_fn` each time a tethering type is disabled, replacing the previous callback.
+    pub fn set_disabled_cb<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(Result<()>, TetheringType),
+        F: 'static,
+    {
+        let (callback_fn, data) =
+            pin_callback::<dyn FnMut(Result<()>, TetheringType)>(Box::new(callback_fn));
+
+        check(unsafe {
+            tethering_set_disabled_cb(
+                self.handle,
+                tethering_type_e_TETHERING_TYPE_ALL,
+                Some(disabled_handler),
+                data,
+            )
+        })?;
+        self.disabled_fn = Some(callback_fn);
+
+        Ok(())
+    }
+
+    pub fn unset_disabled_cb(&mut self) -> Result<()> {
+        check(unsafe {
+            tethering_unset_disabled_cb(self.handle, tethering_type_e_TETHERING_TYPE_ALL)
+        })?;
+        self.disabled_fn = None;
+
+        Ok(())
+    }
+
+    /// Calls `callback_fn` each time a client connects (`true`) or disconnects,
+    /// replacing the previous callback.
+    pub fn set_connection_state_changed_cb<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(TetheringClient, bool),
+        F: 'static,
+    {
+        let (callback_fn, data) =
+            pin_callback::<dyn FnMut(TetheringClient, bool)>(Box::new(callback_fn));
+
+        check(unsafe {
+            tethering_set_connection_state_changed_cb(
+                self.handle,
+                tethering_type_e_TETHERING_TYPE_ALL,
+                Some(connection_handler),
+                data,
+            )
+        })?;
+        self.connection_fn = Some(callback_fn);
+
+        Ok(())
+    }
+
+    pub fn unset_connection_state_changed_cb(&mut self) -> Result<()> {
+        check(unsafe {
+            tethering_unset_connection_state_changed_cb(
+                self.handle,
+                tethering_type_e_TETHERING_TYPE_ALL,
+            )
+        })?;
+        self.connection_fn = None;
+
+        Ok(())
+    }
+
+    pub fn set_wifi_ssid(&mut self, ssid: &str) -> Result<()> {
+        let ssid = cstring(ssid)?;
+
+        check(unsafe { tethering_wifi_set_ssid(self.handle, ssid.as_ptr()) })
+    }
+
+    pub fn wifi_ssid(&self) -> Result<String> {
+        let mut ssid = null_mut();
+        check(unsafe { tethering_wifi_get_ssid(self.handle, &mut ssid) })?;
+
+        Ok(unsafe { take_string(ssid) }.unwrap_or_default())
+    }
+
+    /// Sets the passphrase of the SoftAP, 8 to 63 characters.
+    pub fn set_wifi_passphrase(&mut self, passphrase: &str) -> Result<()> {
+        let passphrase = cstring(passphrase)?;
+
+        check(unsafe { tethering_wifi_set_passphrase(self.handle, passphrase.as_ptr()) })
+    }
+
+    pub fn wifi_passphrase(&self) -> Result<String> {
+        let mut passphrase = null_mut();
+        check(unsafe { tethering_wifi_get_passphrase(self.handle, &mut passphrase) })?;
+
+        Ok(unsafe { take_string(passphrase) }.unwrap_or_default())
+    }
+
+    pub fn set_wifi_security(&mut self, security: WifiSecurity) -> Result<()> {
+        check(unsafe { tethering_wifi_set_security_type(self.handle, security.into()) })
+    }
+
+    /// Whether the SSID is broadcast, on by default.
+    pub fn set_wifi_ssid_visibility(&mut self, visible: bool) -> Result<()> {
+        check(unsafe { tethering_wifi_set_ssid_visibility(self.handle, visible) })
+    }
+}
+
+impl Drop for Tethering {
+    fn drop(&mut self) {
+        let all = tethering_type_e_TETHERING_TYPE_ALL;
+
+        unsafe {
+            if self.enabled_fn.is_some() {
+                tethering_unset_enabled_cb(self.handle, all);
+            }
+            if self.disabled_fn.is_some() {
+                tethering_unset_disabled_cb(self.handle, all);
+            }
+            if self.connection_fn.is_some() {
+                tethering_unset_connection_state_changed_cb(self.handle, all);
+            }
+            tethering_destroy(self.handle);
+        }
+    }
+}
+
+extern "C" fn enabled_handler(
+    result: c_int,
+    tethering_type: tethering_type_e,
+    _is_requested: bool,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut StateFn) };
+
+        if let Ok(tethering_type) = TetheringType::try_from(tethering_type) {
+            closure(check(result), tethering_type);
+        }
+    })
+}
+
+extern "C" fn disabled_handler(
+    result: c_int,
+    tethering_type: tethering_type_e,
+    _cause: tethering_disabled_cause_e,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut StateFn) };
+
+        if let Ok(tethering_type) = TetheringType::try_from(tethering_type) {
+            closure(check(result), tethering_type);
+        }
+    })
+}
+
+extern "C" fn connection_handler(client: tethering_client_h, opened: bool, user_data: *mut c_void) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut ConnectionFn) };
+        closure(unsafe { TetheringClient::from_raw(client) }, opened);
+    })
+}
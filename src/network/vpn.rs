@@ -0,0 +1,217 @@
+//! A TUN interface for VPN clients.
+//!
+//! Requires the `http://tizen.org/privilege/vpnservice` privilege, partner level.
+
+use crate::error::{check, check_count, Error, Result};
+use crate::rutin::{cstring, take_string};
+use rutin_tizen_sys::{
+    vpnsvc_add_dns_server, vpnsvc_add_route, vpnsvc_block_networks, vpnsvc_deinit, vpnsvc_down,
+    vpnsvc_get_iface_fd, vpnsvc_get_iface_index, vpnsvc_get_iface_name, vpnsvc_h, vpnsvc_init,
+    vpnsvc_protect, vpnsvc_read, vpnsvc_remove_dns_server, vpnsvc_remove_route,
+    vpnsvc_set_blocking, vpnsvc_set_local_ip_address, vpnsvc_set_mtu, vpnsvc_set_remote_ip_address,
+    vpnsvc_set_session, vpnsvc_unblock_networks, vpnsvc_up, vpnsvc_write,
+};
+use std::ffi::CString;
+use std::io;
+use std::os::raw::{c_char, c_int, c_void};
+use std::os::unix::io::{AsRawFd, RawFd};
+use std::ptr::null_mut;
+
+/// A TUN interface, removed when this is dropped.
+///
+/// Packets are exchanged with [`io::Read`] and [`io::Write`], one IP packet per call.
+pub struct VpnService {
+    handle: vpnsvc_h,
+}
+
+impl VpnService {
+    /// Creates the interface `iface_name`, e.g. "tun0".
+    pub fn new(iface_name: &str) -> Result<VpnService> {
+        let iface_name = cstring(iface_name)?;
+
+        let mut handle = null_mut();
+        check(unsafe { vpnsvc_init(iface_name.as_ptr(), &mut handle) })?;
+
+        Ok(Self { handle })
+    }
+
+    pub fn iface_name(&self) -> Result<String> {
+        let mut name = null_mut();
+        check(unsafe { vpnsvc_get_iface_name(self.handle, &mut name) })?;
+
+        Ok(unsafe { take_string(name) }.unwrap_or_default())
+    }
+
+    pub fn iface_index(&self) -> Result<i32> {
+        let mut index = 0;
+        check(unsafe { vpnsvc_get_iface_index(self.handle, &mut index) })?;
+
+        Ok(index)
+    }
+
+    /// Routes the traffic of `socket` through `iface_name` instead of the tunnel,
+    /// required for the socket carrying the tunnel itself.
+    pub fn protect(&mut self, socket: &impl AsRawFd, iface_name: &str) -> Result<()> {
+        let iface_name = cstring(iface_name)?;
+
+        check(unsafe { vpnsvc_protect(self.handle, socket.as_raw_fd(), iface_name.as_ptr()) })
+    }
+
+    pub fn set_local_ip_address(&mut self, address: &str) -> Result<()> {
+        let address = cstring(address)?;
+
+        check(unsafe { vpnsvc_set_local_ip_address(self.handle, address.as_ptr()) })
+    }
+
+    pub fn set_remote_ip_address(&mut self, address: &str) -> Result<()> {
+        let address = cstring(address)?;
+
+        check(unsafe { vpnsvc_set_remote_ip_address(self.handle, address.as_ptr()) })
+    }
+
+    /// Sends the traffic for `address/prefix` through the tunnel.
+    pub fn add_route(&mut self, address: &str, prefix: u8) -> Result<()> {
+        let address = cstring(address)?;
+
+        check(unsafe { vpnsvc_add_route(self.handle, address.as_ptr(), prefix as c_int) })
+    }
+
+    pub fn remove_route(&mut self, address: &str, prefix: u8) -> Result<()> {
+        let address = cstring(address)?;
+
+        check(unsafe { vpnsvc_remove_route(self.handle, address.as_ptr(), prefix as c_int) })
+    }
+
+    pub fn add_dns_server(&mut self, address: &str) -> Result<()> {
+        let address = cstring(address)?;
+
+        check(unsafe { vpnsvc_add_dns_server(self.handle, address.as_ptr()) })
+    }
+
+    pub fn remove_dns_server(&mut self, address: &str) -> Result<()> {
+        let address = cstring(address)?;
+
+        check(unsafe { vpnsvc_remove_dns_server(self.handle, address.as_ptr()) })
+    }
+
+    pub fn set_mtu(&mut self, mtu: i32) -> Result<()> {
+        check(unsafe { vpnsvc_set_mtu(self.handle, mtu) })
+    }
+
+    /// Whether reads block until a packet is available, on by default.
+    pub fn set_blocking(&mut self, blocking: bool) -> Result<()> {
+        check(unsafe { vpnsvc_set_blocking(self.handle, blocking) })
+    }
+
+    /// A name for the session, shown to the user in the system VPN settings.
+    pub fn set_session(&mut self, session: &str) -> Result<()> {
+        let session = cstring(session)?;
+
+        check(unsafe { vpnsvc_set_session(self.handle, session.as_ptr()) })
+    }
+
+    /// Brings the interface up, applying the addresses, routes and DNS servers set so far.
+    pub fn up(&mut self) -> Result<()> {
+        check(unsafe { vpnsvc_up(self.handle) })
+    }
+
+    pub fn down(&mut self) -> Result<()> {
+        check(unsafe { vpnsvc_down(self.handle) })
+    }
+
+    /// Blocks the traffic outside of the tunnel, except for `allowed_vpn_routes`
+    /// going through the VPN and `allowed_other_routes` going through other interfaces.
+    pub fn block_networks(
+        &mut self,
+        allowed_vpn_routes: &[(&str, u8)],
+        allowed_other_routes: &[(&str, u8)],
+    ) -> Result<()> {
+        let (vpn_addresses, vpn_prefixes) = routes(allowed_vpn_routes)?;
+        let (other_addresses, other_prefixes) = routes(allowed_other_routes)?;
+
+        let mut vpn_ptrs = vpn_addresses
+            .iter()
+            .map(|a| a.as_ptr() as *mut c_char)
+            .collect::<Vec<_>>();
+        let mut other_ptrs = other_addresses
+            .iter()
+            .map(|a| a.as_ptr() as *mut c_char)
+            .collect::<Vec<_>>();
+
+        check(unsafe {
+            vpnsvc_block_networks(
+                self.handle,
+                vpn_ptrs.as_mut_ptr(),
+                vpn_prefixes.as_ptr() as *mut c_int,
+                vpn_ptrs.len(),
+                other_ptrs.as_mut_ptr(),
+                other_prefixes.as_ptr() as *mut c_int,
+                other_ptrs.len(),
+            )
+        })
+    }
+
+    pub fn unblock_networks(&mut self) -> Result<()> {
+        check(unsafe { vpnsvc_unblock_networks(self.handle) })
+    }
+
+    /// Waits up to `timeout_ms` for a packet to be readable.
+    pub fn wait_readable(&self, timeout_ms: i32) -> Result<()> {
+        check(unsafe { vpnsvc_read(self.handle, timeout_ms) })
+    }
+}
+
+fn routes(routes: &[(&str, u8)]) -> Result<(Vec<CString>, Vec<c_int>)> {
+    let mut addresses = Vec::with_capacity(routes.len());
+    let mut prefixes = Vec::with_capacity(routes.len());
+
+    for (address, prefix) in routes {
+        addresses.push(cstring(address)?);
+        prefixes.push(*prefix as c_int);
+    }
+
+    Ok((addresses, prefixes))
+}
+
+impl AsRawFd for VpnService {
+    fn as_raw_fd(&self) -> RawFd {
+        let mut fd = -1;
+        unsafe { vpnsvc_get_iface_fd(self.handle, &mut fd) };
+
+        fd
+    }
+}
+
+impl io::Read for VpnService {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len =
+            unsafe { libc::read(self.as_raw_fd(), buf.as_mut_ptr() as *mut c_void, buf.len()) };
+
+        if len < 0 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(len as usize)
+        }
+    }
+}
+
+impl io::Write for VpnService {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written =
+            unsafe { vpnsvc_write(self.handle, buf.as_ptr() as *const c_char, buf.len()) };
+
+        check_count(written).map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Drop for VpnService {
+    fn drop(&mut self) {
+        unsafe {
+            vpnsvc_deinit(self.handle);
+        }
+    }
+}
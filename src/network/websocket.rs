@@ -0,0 +1,798 @@
+//! A WebSocket client driven by the Ecore main loop.
+//!
+//! Only plain `ws://` URLs are supported, the platform doesn't expose a TLS socket API.
+//! Requires the `http://tizen.org/privilege/internet` privilege.
+
+use crate::efl::ecore::FdHandler;
+use crate::error::{Error, Result};
+use std::cell::Cell;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::io::{ErrorKind, Read, Write};
+use std::net::TcpStream;
+use std::os::unix::io::AsRawFd;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+const OP_CONTINUATION: u8 = 0x0;
+const OP_TEXT: u8 = 0x1;
+const OP_BINARY: u8 = 0x2;
+const OP_CLOSE: u8 = 0x8;
+const OP_PING: u8 = 0x9;
+const OP_PONG: u8 = 0xa;
+
+/// The largest frame or reassembled message accepted from the server.
+const MAX_MESSAGE_SIZE: usize = 16 * 1024 * 1024;
+
+/// The largest handshake response accepted from the server, headers included.
+const MAX_HANDSHAKE_SIZE: usize = 8 * 1024;
+
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+const ACCEPT_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CloseFrame {
+    pub code: u16,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Message {
+    Text(String),
+    Binary(Vec<u8>),
+    /// A ping from the server, the pong is sent automatically.
+    Ping(Vec<u8>),
+    Pong(Vec<u8>),
+    Close(Option<CloseFrame>),
+}
+
+/// An open WebSocket connection, closed when dropped.
+pub struct WebSocket {
+    stream: TcpStream,
+    close_sent: Rc<Cell<bool>>,
+    _handler: FdHandler<'static>,
+}
+
+impl WebSocket {
+    /// Connects to a `ws://host[:port]/path` URL, blocking until the handshake completes.
+    ///
+    /// The handshake fails with [`Error::TimedOut`] if the server doesn't answer within
+    /// 10 seconds, or with [`Error::OutOfMemory`] if its response is over 8 KiB.
+    ///
+    /// IPv6 hosts are written in brackets, as in `ws://[::1]:8080/`.
+    ///
+    /// `message_fn` is then called from the main loop with each received message,
+    /// or with an error once the connection is lost. A frame or message over 16 MiB
+    /// closes the connection and is reported as [`Error::OutOfMemory`].
+    pub fn connect<F>(url: &str, message_fn: F) -> Result<WebSocket>
+    where
+        F: FnMut(Result<Message>),
+        F: 'static,
+    {
+        let (host, port, path) = parse_url(url)?;
+
+        let mut stream = TcpStream::connect((host, port)).map_err(|_| Error::IoError)?;
+        handshake(&mut stream, host, port, path)?;
+
+        let close_sent = Rc::new(Cell::new(false));
+        let mut reader = Reader {
+            stream: stream.try_clone().map_err(|_| Error::IoError)?,
+            buffer: Vec::new(),
+            fragments: None,
+            close_sent: close_sent.clone(),
+            message_fn: Box::new(message_fn),
+        };
+
+        let handler = FdHandler::readable(stream.as_raw_fd(), move || reader.on_readable())
+            .ok_or(Error::Unknown)?;
+
+        Ok(Self {
+            stream,
+            close_sent,
+            _handler: handler,
+        })
+    }
+
+    pub fn send_text(&mut self, text: &str) -> Result<()> {
+        self.send(OP_TEXT, text.as_bytes())
+    }
+
+    pub fn send_binary(&mut self, data: &[u8]) -> Result<()> {
+        self.send(OP_BINARY, data)
+    }
+
+    pub fn ping(&mut self, data: &[u8]) -> Result<()> {
+        self.send(OP_PING, data)
+    }
+
+    /// Starts the closing handshake, the server's reply is delivered as [`Message::Close`].
+    pub fn close(&mut self, code: u16, reason: &str) -> Result<()> {
+        if self.close_sent.replace(true) {
+            return Ok(());
+        }
+
+        let mut payload = code.to_be_bytes().to_vec();
+        payload.extend_from_slice(reason.as_bytes());
+
+        self.send(OP_CLOSE, &payload)
+    }
+
+    fn send(&mut self, opcode: u8, payload: &[u8]) -> Result<()> {
+        (&self.stream)
+            .write_all(&encode_frame(opcode, payload))
+            .map_err(|_| Error::IoError)
+    }
+}
+
+impl Drop for WebSocket {
+    fn drop(&mut self) {
+        // 1001 is "going away"
+        let _ = self.close(1001, "");
+    }
+}
+
+struct Reader {
+    stream: TcpStream,
+    buffer: Vec<u8>,
+    fragments: Option<(u8, Vec<u8>)>,
+    close_sent: Rc<Cell<bool>>,
+    message_fn: Box<dyn FnMut(Result<Message>)>,
+}
+
+impl Reader {
+    /// Reads what is available without blocking, returns whether to keep watching the socket.
+    fn on_readable(&mut self) -> bool {
+        let mut chunk = [0; 4096];
+
+        let len = match self.stream.read(&mut chunk) {
+            Ok(0) | Err(_) => {
+                (self.message_fn)(Err(Error::IoError));
+                return false;
+            }
+            Ok(len) => len,
+        };
+
+        self.buffer.extend_from_slice(&chunk[..len]);
+
+        loop {
+            match decode_frame(&self.buffer) {
+                Ok(Some((fin, opcode, payload, consumed))) => {
+                    self.buffer.drain(..consumed);
+
+                    if !self.on_frame(fin, opcode, payload) {
+                        return false;
+                    }
+                }
+                Ok(None) => return true,
+                Err(e) => {
+                    self.fail(e);
+                    return false;
+                }
+            }
+        }
+    }
+
+    /// Closes the connection with "message too big" and reports the error.
+    fn fail(&mut self, error: Error) {
+        if !self.close_sent.replace(true) {
+            let _ = (&self.stream).write_all(&encode_frame(OP_CLOSE, &1009u16.to_be_bytes()));
+        }
+
+        (self.message_fn)(Err(error));
+    }
+
+    fn on_frame(&mut self, fin: bool, opcode: u8, payload: Vec<u8>) -> bool {
+        let (opcode, payload) = match opcode {
+            OP_CONTINUATION => match self.fragments.as_mut() {
+                Some((_, data)) => {
+                    if data.len() + payload.len() > MAX_MESSAGE_SIZE {
+                        self.fail(Error::OutOfMemory);
+                        return false;
+                    }
+
+                    data.extend_from_slice(&payload);
+
+                    if !fin {
+                        return true;
+                    }
+
+                    self.fragments.take().unwrap()
+                }
+                None => return true,
+            },
+            OP_TEXT | OP_BINARY if !fin => {
+                self.fragments = Some((opcode, payload));
+                return true;
+            }
+            _ => (opcode, payload),
+        };
+
+        let message = match opcode {
+            OP_TEXT => Message::Text(String::from_utf8_lossy(&payload).into_owned()),
+            OP_BINARY => Message::Binary(payload),
+            OP_PING => {
+                let _ = (&self.stream).write_all(&encode_frame(OP_PONG, &payload));
+                Message::Ping(payload)
+            }
+            OP_PONG => Message::Pong(payload),
+            OP_CLOSE => {
+                if !self.close_sent.replace(true) {
+                    let _ = (&self.stream).write_all(&encode_frame(OP_CLOSE, &payload));
+                }
+
+                let frame = if payload.len() >= 2 {
+                    Some(CloseFrame {
+                        code: u16::from_be_bytes([payload[0], payload[1]]),
+                        reason: String::from_utf8_lossy(&payload[2..]).into_owned(),
+                    })
+                } else {
+                    None
+                };
+
+                (self.message_fn)(Ok(Message::Close(frame)));
+                return false;
+            }
+            _ => return true,
+        };
+
+        (self.message_fn)(Ok(message));
+        true
+    }
+}
+
+fn parse_url(url: &str) -> Result<(&str, u16, &str)> {
+    let rest = if let Some(rest) = url.strip_prefix("ws://") {
+        rest
+    } else if url.starts_with("wss://") {
+        return Err(Error::NotSupported);
+    } else {
+        return Err(Error::InvalidParameter);
+    };
+
+    let (authority, path) = match rest.find('/') {
+        Some(slash) => rest.split_at(slash),
+        None => (rest, "/"),
+    };
+
+    // IPv6 literals are bracketed, their colons aren't port separators
+    let (host, port) = match authority.strip_prefix('[') {
+        Some(bracketed) => {
+            let end = bracketed.find(']').ok_or(Error::InvalidParameter)?;
+            (&bracketed[..end], bracketed[end + 1..].strip_prefix(':'))
+        }
+        None => match authority.rfind(':') {
+            Some(colon) => (&authority[..colon], Some(&authority[colon + 1..])),
+            None => (authority, None),
+        },
+    };
+
+    let port = match port {
+        Some(port) => port.parse().map_err(|_| Error::InvalidParameter)?,
+        None => 80,
+    };
+
+    Ok((host, port, path))
+}
+
+fn handshake(stream: &mut TcpStream, host: &str, port: u16, path: &str) -> Result<()> {
+    let key = base64(&random_bytes(16));
+    let host = if host.contains(':') {
+        format!("[{}]", host)
+    } else {
+        host.to_string()
+    };
+    let request = format!(
+        "GET {} HTTP/1.1\r\n\
+         Host: {}:{}\r\n\
+         Upgrade: websocket\r\n\
+         Connection: Upgrade\r\n\
+         Sec-WebSocket-Key: {}\r\n\
+         Sec-WebSocket-Version: 13\r\n\r\n",
+        path, host, port, key
+    );
+
+    stream
+        .write_all(request.as_bytes())
+        .map_err(|_| Error::IoError)?;
+
+    let deadline = Instant::now() + HANDSHAKE_TIMEOUT;
+
+    // peeked and only consumed up to the end of the header, so that the frames sent right
+    // after the response are left for the reader
+    let mut response = Vec::new();
+    let mut chunk = [0; 1024];
+
+    loop {
+        // a zero timeout is rejected, rather than expiring immediately
+        let timeout = deadline
+            .checked_duration_since(Instant::now())
+            .filter(|timeout| !timeout.is_zero())
+            .ok_or(Error::TimedOut)?;
+        stream
+            .set_read_timeout(Some(timeout))
+            .map_err(|_| Error::IoError)?;
+
+        let len = match stream.peek(&mut chunk) {
+            Ok(0) => return Err(Error::IoError),
+            Ok(len) => len,
+            Err(e) if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {
+                return Err(Error::TimedOut)
+            }
+            Err(_) => return Err(Error::IoError),
+        };
+
+        let start = response.len();
+        response.extend_from_slice(&chunk[..len]);
+
+        let end = find_header_end(&response);
+        response.truncate(end.unwrap_or(response.len()));
+        stream
+            .read_exact(&mut chunk[..response.len() - start])
+            .map_err(|_| Error::IoError)?;
+
+        if response.len() > MAX_HANDSHAKE_SIZE {
+            return Err(Error::OutOfMemory);
+        }
+        if end.is_some() {
+            break;
+        }
+    }
+
+    stream.set_read_timeout(None).map_err(|_| Error::IoError)?;
+
+    let response = String::from_utf8_lossy(&response);
+    let mut lines = response.split("\r\n");
+    let status_line = lines.next().unwrap_or_default();
+
+    if !status_line.contains(" 101 ") {
+        return Err(Error::NotPermitted);
+    }
+
+    let accept = lines.find_map(|line| {
+        let (name, value) = line.split_once(':')?;
+        Some(value.trim()).filter(|_| name.trim().eq_ignore_ascii_case("sec-websocket-accept"))
+    });
+    let expected = base64(&sha1(format!("{}{}", key, ACCEPT_GUID).as_bytes()));
+
+    if accept == Some(expected.as_str()) {
+        Ok(())
+    } else {
+        Err(Error::NotPermitted)
+    }
+}
+
+/// Returns the length of the HTTP header at the start of `response`, blank line included.
+fn find_header_end(response: &[u8]) -> Option<usize> {
+    response
+        .windows(4)
+        .position(|window| window == b"\r\n\r\n")
+        .map(|start| start + 4)
+}
+
+/// Encodes a single final frame, masked as required for clients.
+fn encode_frame(opcode: u8, payload: &[u8]) -> Vec<u8> {
+    let mut frame = Vec::with_capacity(payload.len() + 14);
+    frame.push(0x80 | opcode);
+
+    match payload.len() {
+        len if len < 126 => frame.push(0x80 | len as u8),
+        len if len <= u16::MAX as usize => {
+            frame.push(0x80 | 126);
+            frame.extend_from_slice(&(len as u16).to_be_bytes());
+        }
+        len => {
+            frame.push(0x80 | 127);
+            frame.extend_from_slice(&(len as u64).to_be_bytes());
+        }
+    }
+
+    let mask = random_bytes(4);
+    frame.extend_from_slice(&mask);
+    frame.extend(payload.iter().enumerate().map(|(i, b)| b ^ mask[i % 4]));
+
+    frame
+}
+
+/// Whether the frame is final, its opcode, its unmasked payload, and its size in the buffer.
+type Frame = (bool, u8, Vec<u8>, usize);
+
+/// Decodes the frame at the start of `buffer`, if it has been fully received.
+///
+/// Fails as soon as the header announces a payload over [`MAX_MESSAGE_SIZE`], so that
+/// the buffer doesn't grow waiting for it.
+fn decode_frame(buffer: &[u8]) -> Result<Option<Frame>> {
+    if buffer.len() < 2 {
+        return Ok(None);
+    }
+
+    let fin = buffer[0] & 0x80 != 0;
+    let opcode = buffer[0] & 0x0f;
+    let masked = buffer[1] & 0x80 != 0;
+
+    let (len, mut offset) = match buffer[1] & 0x7f {
+        126 if buffer.len() >= 4 => (u16::from_be_bytes([buffer[2], buffer[3]]) as usize, 4),
+        127 if buffer.len() >= 10 => {
+            let mut len = [0; 8];
+            len.copy_from_slice(&buffer[2..10]);
+            (u64::from_be_bytes(len).min(usize::MAX as u64) as usize, 10)
+        }
+        126 | 127 => return Ok(None),
+        len => (len as usize, 2),
+    };
+
+    if len > MAX_MESSAGE_SIZE {
+        return Err(Error::OutOfMemory);
+    }
+
+    let mask = if masked {
+        let mask = match buffer.get(offset..offset + 4) {
+            Some(mask) => mask,
+            None => return Ok(None),
+        };
+        offset += 4;
+        Some([mask[0], mask[1], mask[2], mask[3]])
+    } else {
+        None
+    };
+
+    let payload = match buffer.get(offset..offset + len) {
+        Some(payload) => payload,
+        None => return Ok(None),
+    };
+    let payload = match mask {
+        Some(mask) => payload
+            .iter()
+            .enumerate()
+            .map(|(i, b)| b ^ mask[i % 4])
+            .collect(),
+        None => payload.to_vec(),
+    };
+
+    Ok(Some((fin, opcode, payload, offset + len)))
+}
+
+/// Unpredictable enough for handshake keys and frame masks, not for cryptography.
+fn random_bytes(len: usize) -> Vec<u8> {
+    let state = RandomState::new();
+
+    (0..len)
+        .step_by(8)
+        .flat_map(|i| {
+            let mut hasher = state.build_hasher();
+            hasher.write_usize(i);
+            hasher.finish().to_le_bytes().to_vec()
+        })
+        .take(len)
+        .collect()
+}
+
+fn sha1(data: &[u8]) -> [u8; 20] {
+    let mut h: [u32; 5] = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476, 0xc3d2e1f0];
+
+    let mut message = data.to_vec();
+    message.push(0x80);
+    while message.len() % 64 != 56 {
+        message.push(0);
+    }
+    message.extend_from_slice(&((data.len() as u64) * 8).to_be_bytes());
+
+    for block in message.chunks_exact(64) {
+        let mut w = [0u32; 80];
+
+        for (i, word) in block.chunks_exact(4).enumerate() {
+            w[i] = u32::from_be_bytes([word[0], word[1], word[2], word[3]]);
+        }
+        for i in 16..80 {
+            w[i] = (w[i - 3] ^ w[i - 8] ^ w[i - 14] ^ w[i - 16]).rotate_left(1);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e] = h;
+
+        for (i, &word) in w.iter().enumerate() {
+            let (f, k) = match i {
+                0..=19 => ((b & c) | (!b & d), 0x5a827999),
+                20..=39 => (b ^ c ^ d, 0x6ed9eba1),
+                40..=59 => ((b & c) | (b & d) | (c & d), 0x8f1bbcdc),
+                _ => (b ^ c ^ d, 0xca62c1d6),
+            };
+
+            let temp = a
+                .rotate_left(5)
+                .wrapping_add(f)
+                .wrapping_add(e)
+                .wrapping_add(k)
+                .wrapping_add(word);
+            e = d;
+            d = c;
+            c = b.rotate_left(30);
+            b = a;
+            a = temp;
+        }
+
+        for (h, v) in h.iter_mut().zip([a, b, c, d, e]) {
+            *h = h.wrapping_add(v);
+        }
+    }
+
+    let mut digest = [0; 20];
+    for (chunk, word) in digest.chunks_exact_mut(4).zip(h) {
+        chunk.copy_from_slice(&word.to_be_bytes());
+    }
+
+    digest
+}
+
+fn base64(data: &[u8]) -> String {
+    const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut encoded = String::with_capacity(data.len().div_ceil(3) * 4);
+
+    for chunk in data.chunks(3) {
+        let bytes = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let n = (bytes[0] as u32) << 16 | (bytes[1] as u32) << 8 | bytes[2] as u32;
+
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::net::TcpListener;
+
+    fn hex(data: &[u8]) -> String {
+        data.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    /// A reader on a loopback connection, collecting its messages, and the server's end.
+    fn reader() -> (Reader, Rc<RefCell<Vec<Result<Message>>>>, TcpStream) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let (server, _) = listener.accept().unwrap();
+
+        let messages = Rc::new(RefCell::new(Vec::new()));
+        let received = messages.clone();
+        let reader = Reader {
+            stream,
+            buffer: Vec::new(),
+            fragments: None,
+            close_sent: Rc::new(Cell::new(false)),
+            message_fn: Box::new(move |message| received.borrow_mut().push(message)),
+        };
+
+        (reader, messages, server)
+    }
+
+    #[test]
+    fn sha1_digests() {
+        assert_eq!(hex(&sha1(b"")), "da39a3ee5e6b4b0d3255bfef95601890afd80709");
+        assert_eq!(
+            hex(&sha1(b"abc")),
+            "a9993e364706816aba3e25717850c26c9cd0d89d"
+        );
+        // two blocks once padded
+        assert_eq!(
+            hex(&sha1(
+                b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"
+            )),
+            "84983e441c3bd26ebaae4aa1f95129e5e54670f1"
+        );
+    }
+
+    #[test]
+    fn base64_padding() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(base64(b"foobar"), "Zm9vYmFy");
+        assert_eq!(base64(&[0xfb, 0xff]), "+/8=");
+    }
+
+    #[test]
+    fn accept_key() {
+        // the example of RFC 6455, section 1.3
+        let key = "dGhlIHNhbXBsZSBub25jZQ==";
+
+        assert_eq!(
+            base64(&sha1(format!("{}{}", key, ACCEPT_GUID).as_bytes())),
+            "s3pPLMBiTxaQ9kYGzzhZRbK+xOo="
+        );
+    }
+
+    #[test]
+    fn parse_urls() {
+        assert_eq!(
+            parse_url("ws://example.com/chat?room=1"),
+            Ok(("example.com", 80, "/chat?room=1"))
+        );
+        assert_eq!(
+            parse_url("ws://example.com:8080"),
+            Ok(("example.com", 8080, "/"))
+        );
+        assert_eq!(parse_url("ws://[::1]:9000/"), Ok(("::1", 9000, "/")));
+        assert_eq!(parse_url("ws://[::1]/a"), Ok(("::1", 80, "/a")));
+    }
+
+    #[test]
+    fn parse_malformed_urls() {
+        assert_eq!(parse_url("wss://example.com/"), Err(Error::NotSupported));
+        assert_eq!(
+            parse_url("http://example.com/"),
+            Err(Error::InvalidParameter)
+        );
+        assert_eq!(parse_url("example.com"), Err(Error::InvalidParameter));
+        assert_eq!(
+            parse_url("ws://example.com:/"),
+            Err(Error::InvalidParameter)
+        );
+        assert_eq!(
+            parse_url("ws://example.com:http/"),
+            Err(Error::InvalidParameter)
+        );
+        assert_eq!(
+            parse_url("ws://example.com:65536/"),
+            Err(Error::InvalidParameter)
+        );
+        assert_eq!(parse_url("ws://[::1:80/"), Err(Error::InvalidParameter));
+    }
+
+    #[test]
+    fn masked_frames_round_trip() {
+        for len in [0, 125, 126, u16::MAX as usize, u16::MAX as usize + 1] {
+            let payload: Vec<u8> = (0..len).map(|i| i as u8).collect();
+            let frame = encode_frame(OP_BINARY, &payload);
+
+            assert_eq!(frame[0], 0x80 | OP_BINARY);
+            assert_ne!(frame[1] & 0x80, 0);
+            assert_eq!(
+                decode_frame(&frame),
+                Ok(Some((true, OP_BINARY, payload, frame.len())))
+            );
+        }
+    }
+
+    #[test]
+    fn payload_lengths() {
+        assert_eq!(encode_frame(OP_TEXT, &[0; 125])[1], 0x80 | 125);
+
+        let frame = encode_frame(OP_TEXT, &[0; 126]);
+        assert_eq!(frame[1..4], [0x80 | 126, 0, 126]);
+        assert_eq!(frame.len(), 2 + 2 + 4 + 126);
+
+        let frame = encode_frame(OP_TEXT, &[0; 0x10000]);
+        assert_eq!(frame[1..10], [0x80 | 127, 0, 0, 0, 0, 0, 1, 0, 0]);
+        assert_eq!(frame.len(), 2 + 8 + 4 + 0x10000);
+    }
+
+    #[test]
+    fn unmasked_frames() {
+        assert_eq!(
+            decode_frame(b"\x81\x05Hello"),
+            Ok(Some((true, OP_TEXT, b"Hello".to_vec(), 7)))
+        );
+
+        // the masked example of RFC 6455, section 5.7
+        assert_eq!(
+            decode_frame(b"\x81\x85\x37\xfa\x21\x3d\x7f\x9f\x4d\x51\x58"),
+            Ok(Some((true, OP_TEXT, b"Hello".to_vec(), 11)))
+        );
+
+        let mut frame = vec![0x82, 126, 0x01, 0x00];
+        frame.extend_from_slice(&[7; 256]);
+        frame.extend_from_slice(b"\x89\x00");
+        assert_eq!(
+            decode_frame(&frame),
+            Ok(Some((true, OP_BINARY, vec![7; 256], 260)))
+        );
+        assert_eq!(
+            decode_frame(&frame[260..]),
+            Ok(Some((true, OP_PING, Vec::new(), 2)))
+        );
+    }
+
+    #[test]
+    fn incomplete_frames() {
+        let frame = b"\x81\x05Hello";
+
+        for len in 0..frame.len() {
+            assert_eq!(decode_frame(&frame[..len]), Ok(None));
+        }
+
+        assert_eq!(decode_frame(b"\x82\x7e\x01"), Ok(None));
+        assert_eq!(decode_frame(b"\x82\x7f\x00\x00\x00\x00"), Ok(None));
+        assert_eq!(decode_frame(b"\x81\x85\x37\xfa"), Ok(None));
+    }
+
+    #[test]
+    fn oversized_frames() {
+        let mut frame = vec![0x82, 127];
+        frame.extend_from_slice(&(MAX_MESSAGE_SIZE as u64 + 1).to_be_bytes());
+        assert_eq!(decode_frame(&frame), Err(Error::OutOfMemory));
+
+        let mut frame = vec![0x82, 127];
+        frame.extend_from_slice(&u64::MAX.to_be_bytes());
+        assert_eq!(decode_frame(&frame), Err(Error::OutOfMemory));
+    }
+
+    #[test]
+    fn fragmented_messages() {
+        let (mut reader, messages, _server) = reader();
+
+        assert!(reader.on_frame(false, OP_TEXT, b"Hel".to_vec()));
+        // control frames may be interleaved with the fragments
+        assert!(reader.on_frame(true, OP_PONG, b"!".to_vec()));
+        assert!(reader.on_frame(false, OP_CONTINUATION, b"l".to_vec()));
+        assert!(reader.on_frame(true, OP_CONTINUATION, b"o".to_vec()));
+        // a continuation without a message to continue is ignored
+        assert!(reader.on_frame(true, OP_CONTINUATION, b"?".to_vec()));
+
+        assert_eq!(
+            *messages.borrow(),
+            [
+                Ok(Message::Pong(b"!".to_vec())),
+                Ok(Message::Text("Hello".to_string())),
+            ]
+        );
+    }
+
+    #[test]
+    fn control_frames() {
+        let (mut reader, messages, mut server) = reader();
+
+        assert!(reader.on_frame(true, OP_PING, b"hi".to_vec()));
+
+        let mut pong = [0; 8];
+        server.read_exact(&mut pong).unwrap();
+        assert_eq!(
+            decode_frame(&pong),
+            Ok(Some((true, OP_PONG, b"hi".to_vec(), 8)))
+        );
+
+        let mut close = 1000u16.to_be_bytes().to_vec();
+        close.extend_from_slice(b"bye");
+        assert!(!reader.on_frame(true, OP_CLOSE, close.clone()));
+
+        // echoed, as the client hadn't started closing
+        let mut echo = [0; 11];
+        server.read_exact(&mut echo).unwrap();
+        assert_eq!(decode_frame(&echo), Ok(Some((true, OP_CLOSE, close, 11))));
+
+        assert_eq!(
+            *messages.borrow(),
+            [
+                Ok(Message::Ping(b"hi".to_vec())),
+                Ok(Message::Close(Some(CloseFrame {
+                    code: 1000,
+                    reason: "bye".to_string(),
+                }))),
+            ]
+        );
+    }
+
+    #[test]
+    fn fragmented_messages_over_the_limit() {
+        let (mut reader, messages, _server) = reader();
+
+        assert!(reader.on_frame(false, OP_BINARY, vec![0; MAX_MESSAGE_SIZE]));
+        assert!(!reader.on_frame(true, OP_CONTINUATION, vec![0]));
+
+        assert_eq!(*messages.borrow(), [Err(Error::OutOfMemory)]);
+    }
+}
@@ -0,0 +1,258 @@
+//! The viewer side of the notifications, for apps showing or relaying the notifications
+//! posted by the other apps, such as a custom notification board.
+//!
+//! Requires the `http://tizen.org/privilege/notification` privilege.
+
+use crate::error::{check, Result};
+use crate::rutin::{abort_on_panic, borrow_str, cstring, pin_callback};
+use rutin_tizen_sys::{
+    notification_clear, notification_delete_by_priv_id, notification_free_list,
+    notification_get_id, notification_get_image, notification_get_insert_time,
+    notification_get_list, notification_get_pkgname, notification_get_text, notification_get_time,
+    notification_get_type, notification_h, notification_image_type_e_NOTIFICATION_IMAGE_TYPE_ICON,
+    notification_list_get_data, notification_list_get_head, notification_list_get_next,
+    notification_op, notification_op_type_e_NOTIFICATION_OP_DELETE,
+    notification_op_type_e_NOTIFICATION_OP_DELETE_ALL,
+    notification_op_type_e_NOTIFICATION_OP_INSERT, notification_op_type_e_NOTIFICATION_OP_REFRESH,
+    notification_op_type_e_NOTIFICATION_OP_SERVICE_READY,
+    notification_op_type_e_NOTIFICATION_OP_UPDATE, notification_register_detailed_changed_cb,
+    notification_text_type_e, notification_text_type_e_NOTIFICATION_TEXT_TYPE_CONTENT,
+    notification_text_type_e_NOTIFICATION_TEXT_TYPE_TITLE, notification_type_e,
+    notification_type_e_NOTIFICATION_TYPE_NOTI, notification_type_e_NOTIFICATION_TYPE_ONGOING,
+    notification_unregister_detailed_changed_cb,
+};
+use std::os::raw::{c_char, c_int, c_void};
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::ptr::null_mut;
+use std::slice;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NotificationType {
+    /// Dismissable by the user.
+    Notification,
+    /// Shown as long as the app keeps it, such as the progress of a download.
+    Ongoing,
+}
+
+impl From<NotificationType> for notification_type_e {
+    fn from(notification_type: NotificationType) -> notification_type_e {
+        match notification_type {
+            NotificationType::Notification => notification_type_e_NOTIFICATION_TYPE_NOTI,
+            NotificationType::Ongoing => notification_type_e_NOTIFICATION_TYPE_ONGOING,
+        }
+    }
+}
+
+impl From<notification_type_e> for NotificationType {
+    fn from(notification_type: notification_type_e) -> NotificationType {
+        match notification_type {
+            notification_type_e_NOTIFICATION_TYPE_ONGOING => NotificationType::Ongoing,
+            _ => NotificationType::Notification,
+        }
+    }
+}
+
+/// A notification posted by an app.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Notification {
+    /// The app which posted it.
+    pub app_id: String,
+    /// Identifies the notification among those of the app.
+    pub priv_id: i32,
+    pub group_id: i32,
+    pub notification_type: NotificationType,
+    pub title: Option<String>,
+    pub content: Option<String>,
+    pub icon_path: Option<PathBuf>,
+    /// The time shown by the notification, in seconds since the epoch.
+    pub time: Option<i64>,
+    /// When it was posted, in seconds since the epoch.
+    pub insert_time: Option<i64>,
+}
+
+type TimeGetter = unsafe extern "C" fn(notification_h, *mut libc::time_t) -> c_int;
+
+impl Notification {
+    unsafe fn from_raw(notification: notification_h) -> Result<Notification> {
+        let mut app_id: *mut c_char = null_mut();
+        check(notification_get_pkgname(notification, &mut app_id))?;
+
+        let mut group_id = 0;
+        let mut priv_id = 0;
+        check(notification_get_id(
+            notification,
+            &mut group_id,
+            &mut priv_id,
+        ))?;
+
+        let mut notification_type = notification_type_e_NOTIFICATION_TYPE_NOTI;
+        check(notification_get_type(notification, &mut notification_type))?;
+
+        // the strings belong to the notification, they must not be freed
+        let text = |text_type: notification_text_type_e| {
+            let mut text: *mut c_char = null_mut();
+            check(notification_get_text(notification, text_type, &mut text)).ok()?;
+            borrow_str(text).map(|text| text.into_owned())
+        };
+
+        let mut icon_path: *mut c_char = null_mut();
+        let icon_path = check(notification_get_image(
+            notification,
+            notification_image_type_e_NOTIFICATION_IMAGE_TYPE_ICON,
+            &mut icon_path,
+        ))
+        .ok()
+        .and_then(|_| borrow_str(icon_path))
+        .map(|icon_path| PathBuf::from(icon_path.into_owned()));
+
+        let time = |get: TimeGetter| {
+            let mut time: libc::time_t = 0;
+            check(get(notification, &mut time)).ok()?;
+            Some(i64::from(time)).filter(|&time| time != 0)
+        };
+
+        Ok(Self {
+            app_id: borrow_str(app_id).unwrap_or_default().into_owned(),
+            priv_id,
+            group_id,
+            notification_type: notification_type.into(),
+            title: text(notification_text_type_e_NOTIFICATION_TEXT_TYPE_TITLE),
+            content: text(notification_text_type_e_NOTIFICATION_TEXT_TYPE_CONTENT),
+            icon_path,
+            time: time(notification_get_time),
+            insert_time: time(notification_get_insert_time),
+        })
+    }
+}
+
+/// A change to the notifications of the device.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Event {
+    Inserted(Notification),
+    Updated(Notification),
+    /// The notification with this `priv_id` was dismissed or removed by its app.
+    Deleted(i32),
+    DeletedAll,
+    /// The notifications should be listed again.
+    Refreshed,
+    /// The notification service started, listing and dismissing are possible.
+    ServiceReady,
+}
+
+/// The active notifications of `notification_type`, at most `count` if given.
+pub fn list(notification_type: NotificationType, count: Option<i32>) -> Result<Vec<Notification>> {
+    let mut list = null_mut();
+    check(unsafe {
+        notification_get_list(notification_type.into(), count.unwrap_or(-1), &mut list)
+    })?;
+
+    let mut notifications = Vec::new();
+    let mut result = Ok(());
+    let mut node = unsafe { notification_list_get_head(list) };
+
+    while !node.is_null() {
+        match unsafe { Notification::from_raw(notification_list_get_data(node)) } {
+            Ok(notification) => notifications.push(notification),
+            Err(e) => {
+                result = Err(e);
+                break;
+            }
+        }
+
+        node = unsafe { notification_list_get_next(node) };
+    }
+
+    if !list.is_null() {
+        unsafe { notification_free_list(list) };
+    }
+
+    result.map(|_| notifications)
+}
+
+/// Removes a notification of any app, as if the user dismissed it.
+pub fn dismiss(notification: &Notification) -> Result<()> {
+    let app_id = cstring(notification.app_id.as_str())?;
+
+    check(unsafe {
+        notification_delete_by_priv_id(
+            app_id.as_ptr(),
+            notification.notification_type.into(),
+            notification.priv_id,
+        )
+    })
+}
+
+/// Removes all the notifications of `notification_type`, of every app.
+pub fn dismiss_all(notification_type: NotificationType) -> Result<()> {
+    check(unsafe { notification_clear(notification_type.into()) })
+}
+
+type EventFn = Box<dyn FnMut(Result<Event>)>;
+
+/// Receives the changes to the notifications until dropped.
+pub struct Listener {
+    _callback_fn: Pin<Box<EventFn>>,
+    data: *mut c_void,
+}
+
+impl Listener {
+    pub fn new<F>(callback_fn: F) -> Result<Listener>
+    where
+        F: FnMut(Result<Event>),
+        F: 'static,
+    {
+        let (callback_fn, data) = pin_callback::<dyn FnMut(Result<Event>)>(Box::new(callback_fn));
+
+        check(unsafe { notification_register_detailed_changed_cb(Some(changed_handler), data) })?;
+
+        Ok(Self {
+            _callback_fn: callback_fn,
+            data,
+        })
+    }
+}
+
+impl Drop for Listener {
+    fn drop(&mut self) {
+        unsafe {
+            notification_unregister_detailed_changed_cb(Some(changed_handler), self.data);
+        }
+    }
+}
+
+extern "C" fn changed_handler(
+    user_data: *mut c_void,
+    _notification_type: notification_type_e,
+    op_list: *mut notification_op,
+    num_op: c_int,
+) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut EventFn) };
+
+        if op_list.is_null() {
+            return;
+        }
+
+        for op in unsafe { slice::from_raw_parts(op_list, num_op as usize) } {
+            let event = match op.type_ {
+                notification_op_type_e_NOTIFICATION_OP_INSERT => {
+                    unsafe { Notification::from_raw(op.noti) }.map(Event::Inserted)
+                }
+                notification_op_type_e_NOTIFICATION_OP_UPDATE => {
+                    unsafe { Notification::from_raw(op.noti) }.map(Event::Updated)
+                }
+                notification_op_type_e_NOTIFICATION_OP_DELETE => Ok(Event::Deleted(op.priv_id)),
+                notification_op_type_e_NOTIFICATION_OP_DELETE_ALL => Ok(Event::DeletedAll),
+                notification_op_type_e_NOTIFICATION_OP_REFRESH => Ok(Event::Refreshed),
+                notification_op_type_e_NOTIFICATION_OP_SERVICE_READY => Ok(Event::ServiceReady),
+                _ => continue,
+            };
+
+            closure(event);
+        }
+    })
+}
@@ -0,0 +1,443 @@
+//! OAuth 2.0 authorization, the platform showing the login page of the service and
+//! requesting its tokens.
+//!
+//! Requires the `http://tizen.org/privilege/internet` privilege.
+
+use crate::error::{check, Error, Result};
+use crate::rutin::{abort_on_panic, borrow_str, cstring};
+use rutin_tizen_sys::{
+    oauth2_access_token_cb, oauth2_client_authentication_type_e,
+    oauth2_client_authentication_type_e_OAUTH2_CLIENT_AUTHENTICATION_TYPE_BASIC,
+    oauth2_client_authentication_type_e_OAUTH2_CLIENT_AUTHENTICATION_TYPE_DIGEST,
+    oauth2_client_authentication_type_e_OAUTH2_CLIENT_AUTHENTICATION_TYPE_REQUEST_BODY,
+    oauth2_error_get_code, oauth2_error_get_description, oauth2_error_get_uri, oauth2_error_h,
+    oauth2_grant_type_e, oauth2_grant_type_e_OAUTH2_GRANT_TYPE_AUTH_CODE,
+    oauth2_grant_type_e_OAUTH2_GRANT_TYPE_CLIENT_CREDENTIALS,
+    oauth2_grant_type_e_OAUTH2_GRANT_TYPE_PASSWORD, oauth2_grant_type_e_OAUTH2_GRANT_TYPE_REFRESH,
+    oauth2_manager_clear_cache, oauth2_manager_clear_cookies, oauth2_manager_create,
+    oauth2_manager_destroy, oauth2_manager_h, oauth2_manager_is_request_in_progress,
+    oauth2_manager_refresh_access_token, oauth2_manager_request_access_token,
+    oauth2_manager_request_authorization_grant, oauth2_manager_request_token,
+    oauth2_request_add_custom_data, oauth2_request_create, oauth2_request_destroy,
+    oauth2_request_h, oauth2_request_set_auth_end_point_url, oauth2_request_set_authorization_code,
+    oauth2_request_set_client_authentication_type, oauth2_request_set_client_id,
+    oauth2_request_set_client_secret, oauth2_request_set_grant_type, oauth2_request_set_password,
+    oauth2_request_set_redirection_url, oauth2_request_set_refresh_token,
+    oauth2_request_set_refresh_token_url, oauth2_request_set_response_type,
+    oauth2_request_set_scope, oauth2_request_set_state, oauth2_request_set_token_end_point_url,
+    oauth2_request_set_user_name, oauth2_response_destroy, oauth2_response_get_access_token,
+    oauth2_response_get_authorization_code, oauth2_response_get_error,
+    oauth2_response_get_expires_in, oauth2_response_get_refresh_token, oauth2_response_get_scope,
+    oauth2_response_get_state, oauth2_response_get_token_type, oauth2_response_h,
+    oauth2_response_type_e, oauth2_response_type_e_OAUTH2_RESPONSE_TYPE_CODE,
+    oauth2_response_type_e_OAUTH2_RESPONSE_TYPE_TOKEN,
+};
+use std::os::raw::{c_char, c_int, c_void};
+use std::ptr::null_mut;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ResponseType {
+    /// An authorization code, exchanged for a token with
+    /// [`Manager::request_access_token`].
+    Code,
+    /// An access token, for the implicit grant.
+    Token,
+}
+
+impl From<ResponseType> for oauth2_response_type_e {
+    fn from(response_type: ResponseType) -> oauth2_response_type_e {
+        match response_type {
+            ResponseType::Code => oauth2_response_type_e_OAUTH2_RESPONSE_TYPE_CODE,
+            ResponseType::Token => oauth2_response_type_e_OAUTH2_RESPONSE_TYPE_TOKEN,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GrantType {
+    AuthorizationCode,
+    Password,
+    ClientCredentials,
+    Refresh,
+}
+
+impl From<GrantType> for oauth2_grant_type_e {
+    fn from(grant_type: GrantType) -> oauth2_grant_type_e {
+        match grant_type {
+            GrantType::AuthorizationCode => oauth2_grant_type_e_OAUTH2_GRANT_TYPE_AUTH_CODE,
+            GrantType::Password => oauth2_grant_type_e_OAUTH2_GRANT_TYPE_PASSWORD,
+            GrantType::ClientCredentials => {
+                oauth2_grant_type_e_OAUTH2_GRANT_TYPE_CLIENT_CREDENTIALS
+            }
+            GrantType::Refresh => oauth2_grant_type_e_OAUTH2_GRANT_TYPE_REFRESH,
+        }
+    }
+}
+
+/// How the client credentials are sent to the token end point.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ClientAuthenticationType {
+    Basic,
+    Digest,
+    RequestBody,
+}
+
+impl From<ClientAuthenticationType> for oauth2_client_authentication_type_e {
+    fn from(authentication_type: ClientAuthenticationType) -> oauth2_client_authentication_type_e {
+        match authentication_type {
+            ClientAuthenticationType::Basic => {
+                oauth2_client_authentication_type_e_OAUTH2_CLIENT_AUTHENTICATION_TYPE_BASIC
+            }
+            ClientAuthenticationType::Digest => {
+                oauth2_client_authentication_type_e_OAUTH2_CLIENT_AUTHENTICATION_TYPE_DIGEST
+            }
+            ClientAuthenticationType::RequestBody => {
+                oauth2_client_authentication_type_e_OAUTH2_CLIENT_AUTHENTICATION_TYPE_REQUEST_BODY
+            }
+        }
+    }
+}
+
+/// The parameters of a request, the unset ones being left out.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Request {
+    pub auth_end_point_url: Option<String>,
+    pub token_end_point_url: Option<String>,
+    pub redirection_url: Option<String>,
+    pub refresh_token_url: Option<String>,
+    pub refresh_token: Option<String>,
+    pub response_type: Option<ResponseType>,
+    pub client_id: Option<String>,
+    pub client_secret: Option<String>,
+    pub client_authentication_type: Option<ClientAuthenticationType>,
+    pub scope: Option<String>,
+    pub state: Option<String>,
+    pub grant_type: Option<GrantType>,
+    pub authorization_code: Option<String>,
+    pub user_name: Option<String>,
+    pub password: Option<String>,
+    /// Additional parameters required by the service.
+    pub custom_data: Vec<(String, String)>,
+}
+
+/// What the service answered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Response {
+    pub authorization_code: Option<String>,
+    pub state: Option<String>,
+    pub access_token: Option<String>,
+    pub token_type: Option<String>,
+    /// The lifetime of the access token, in seconds.
+    pub expires_in: Option<i64>,
+    pub refresh_token: Option<String>,
+    pub scope: Option<String>,
+}
+
+/// Why a request failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ResponseError {
+    /// The HTTP status returned by the service, if it was reached.
+    pub server_code: Option<i32>,
+    pub platform_error: Option<Error>,
+    pub description: Option<String>,
+    /// A page describing the error.
+    pub uri: Option<String>,
+}
+
+type ResponseGetter = unsafe extern "C" fn(oauth2_response_h, *mut *mut c_char) -> c_int;
+type ErrorGetter = unsafe extern "C" fn(oauth2_error_h, *mut *mut c_char) -> c_int;
+
+impl Response {
+    unsafe fn from_raw(
+        response: oauth2_response_h,
+    ) -> std::result::Result<Response, ResponseError> {
+        let mut error = null_mut();
+
+        if check(oauth2_response_get_error(response, &mut error)).is_ok() && !error.is_null() {
+            return Err(ResponseError::from_raw(error));
+        }
+
+        // the strings belong to the response, they must not be freed
+        let string = |get: ResponseGetter| {
+            let mut value = null_mut();
+            check(get(response, &mut value)).ok()?;
+            borrow_str(value).map(|value| value.into_owned())
+        };
+
+        let mut expires_in = 0;
+        let expires_in = check(oauth2_response_get_expires_in(response, &mut expires_in))
+            .ok()
+            .map(|_| expires_in)
+            .filter(|&expires_in| expires_in > 0);
+
+        Ok(Self {
+            authorization_code: string(oauth2_response_get_authorization_code),
+            state: string(oauth2_response_get_state),
+            access_token: string(oauth2_response_get_access_token),
+            token_type: string(oauth2_response_get_token_type),
+            expires_in,
+            refresh_token: string(oauth2_response_get_refresh_token),
+            scope: string(oauth2_response_get_scope),
+        })
+    }
+}
+
+impl ResponseError {
+    unsafe fn from_raw(error: oauth2_error_h) -> ResponseError {
+        let string = |get: ErrorGetter| {
+            let mut value = null_mut();
+            check(get(error, &mut value)).ok()?;
+            borrow_str(value).map(|value| value.into_owned())
+        };
+
+        let mut server_code = 0;
+        let mut platform_code = 0;
+        check(oauth2_error_get_code(
+            error,
+            &mut server_code,
+            &mut platform_code,
+        ))
+        .ok();
+
+        Self {
+            server_code: Some(server_code).filter(|&code| code != 0),
+            platform_error: check(platform_code).err(),
+            description: string(oauth2_error_get_description),
+            uri: string(oauth2_error_get_uri),
+        }
+    }
+}
+
+type ResponseFn = Box<dyn FnOnce(std::result::Result<Response, ResponseError>)>;
+type StartFn = unsafe extern "C" fn(
+    oauth2_manager_h,
+    oauth2_request_h,
+    oauth2_access_token_cb,
+    *mut c_void,
+) -> c_int;
+
+/// A request in progress, which must live until its response.
+struct PendingRequest {
+    _request: RawRequest,
+    callback_fn: ResponseFn,
+}
+
+/// Runs the requests, only one at a time.
+pub struct Manager {
+    handle: oauth2_manager_h,
+}
+
+impl Manager {
+    pub fn new() -> Result<Manager> {
+        let mut handle = null_mut();
+        check(unsafe { oauth2_manager_create(&mut handle) })?;
+
+        Ok(Self { handle })
+    }
+
+    /// Runs the whole flow of `request`, showing the login page of the service if needed,
+    /// then calls `callback_fn` with the access token.
+    pub fn request_token<F>(&mut self, request: &Request, callback_fn: F) -> Result<()>
+    where
+        F: FnOnce(std::result::Result<Response, ResponseError>),
+        F: 'static,
+    {
+        self.start(oauth2_manager_request_token, request, Box::new(callback_fn))
+    }
+
+    /// Shows the login page of the service, then calls `callback_fn` with the
+    /// authorization code.
+    pub fn request_authorization_grant<F>(
+        &mut self,
+        request: &Request,
+        callback_fn: F,
+    ) -> Result<()>
+    where
+        F: FnOnce(std::result::Result<Response, ResponseError>),
+        F: 'static,
+    {
+        self.start(
+            oauth2_manager_request_authorization_grant,
+            request,
+            Box::new(callback_fn),
+        )
+    }
+
+    /// Exchanges the authorization code or the credentials of `request` for an access
+    /// token.
+    pub fn request_access_token<F>(&mut self, request: &Request, callback_fn: F) -> Result<()>
+    where
+        F: FnOnce(std::result::Result<Response, ResponseError>),
+        F: 'static,
+    {
+        self.start(
+            oauth2_manager_request_access_token,
+            request,
+            Box::new(callback_fn),
+        )
+    }
+
+    /// Gets a new access token with the refresh token of `request`.
+    pub fn refresh_access_token<F>(&mut self, request: &Request, callback_fn: F) -> Result<()>
+    where
+        F: FnOnce(std::result::Result<Response, ResponseError>),
+        F: 'static,
+    {
+        self.start(
+            oauth2_manager_refresh_access_token,
+            request,
+            Box::new(callback_fn),
+        )
+    }
+
+    pub fn is_request_in_progress(&self) -> bool {
+        unsafe { oauth2_manager_is_request_in_progress(self.handle) }
+    }
+
+    /// Forgets the login session shown by the login page.
+    pub fn clear_cookies(&mut self) -> Result<()> {
+        check(unsafe { oauth2_manager_clear_cookies(self.handle) })
+    }
+
+    pub fn clear_cache(&mut self) -> Result<()> {
+        check(unsafe { oauth2_manager_clear_cache(self.handle) })
+    }
+
+    fn start(&mut self, start: StartFn, request: &Request, callback_fn: ResponseFn) -> Result<()> {
+        let request = RawRequest::new(request)?;
+        let handle = request.handle;
+        let data = Box::into_raw(Box::new(PendingRequest {
+            _request: request,
+            callback_fn,
+        }));
+
+        let ret = unsafe {
+            start(
+                self.handle,
+                handle,
+                Some(response_handler),
+                data as *mut c_void,
+            )
+        };
+
+        if let Err(e) = check(ret) {
+            drop(unsafe { Box::from_raw(data) });
+            return Err(e);
+        }
+
+        Ok(())
+    }
+}
+
+impl Drop for Manager {
+    fn drop(&mut self) {
+        unsafe {
+            oauth2_manager_destroy(self.handle);
+        }
+    }
+}
+
+type StringSetter = unsafe extern "C" fn(oauth2_request_h, *const c_char) -> c_int;
+
+/// A request handle, destroyed with this value.
+struct RawRequest {
+    handle: oauth2_request_h,
+}
+
+impl RawRequest {
+    fn new(request: &Request) -> Result<RawRequest> {
+        let mut handle = null_mut();
+        check(unsafe { oauth2_request_create(&mut handle) })?;
+        let raw = Self { handle };
+
+        let strings: [(StringSetter, &Option<String>); 12] = [
+            (
+                oauth2_request_set_auth_end_point_url,
+                &request.auth_end_point_url,
+            ),
+            (
+                oauth2_request_set_token_end_point_url,
+                &request.token_end_point_url,
+            ),
+            (oauth2_request_set_redirection_url, &request.redirection_url),
+            (
+                oauth2_request_set_refresh_token_url,
+                &request.refresh_token_url,
+            ),
+            (oauth2_request_set_refresh_token, &request.refresh_token),
+            (oauth2_request_set_client_id, &request.client_id),
+            (oauth2_request_set_client_secret, &request.client_secret),
+            (oauth2_request_set_scope, &request.scope),
+            (oauth2_request_set_state, &request.state),
+            (
+                oauth2_request_set_authorization_code,
+                &request.authorization_code,
+            ),
+            (oauth2_request_set_user_name, &request.user_name),
+            (oauth2_request_set_password, &request.password),
+        ];
+
+        for (set, value) in strings.iter() {
+            if let Some(value) = value {
+                let value = cstring(value)?;
+                check(unsafe { set(handle, value.as_ptr()) })?;
+            }
+        }
+
+        if let Some(response_type) = request.response_type {
+            check(unsafe { oauth2_request_set_response_type(handle, response_type.into()) })?;
+        }
+        if let Some(authentication_type) = request.client_authentication_type {
+            check(unsafe {
+                oauth2_request_set_client_authentication_type(handle, authentication_type.into())
+            })?;
+        }
+        if let Some(grant_type) = request.grant_type {
+            check(unsafe { oauth2_request_set_grant_type(handle, grant_type.into()) })?;
+        }
+
+        for (key, value) in &request.custom_data {
+            let key = cstring(key)?;
+            let value = cstring(value)?;
+
+            check(unsafe { oauth2_request_add_custom_data(handle, key.as_ptr(), value.as_ptr()) })?;
+        }
+
+        Ok(raw)
+    }
+}
+
+impl Drop for RawRequest {
+    fn drop(&mut self) {
+        unsafe {
+            oauth2_request_destroy(self.handle);
+        }
+    }
+}
+
+extern "C" fn response_handler(response: oauth2_response_h, user_data: *mut c_void) {
+    abort_on_panic(|| {
+        let pending = unsafe { Box::from_raw(user_data as *mut PendingRequest) };
+
+        let result = if response.is_null() {
+            Err(ResponseError {
+                server_code: None,
+                platform_error: Some(Error::Unknown),
+                description: None,
+                uri: None,
+            })
+        } else {
+            let result = unsafe { Response::from_raw(response) };
+            unsafe { oauth2_response_destroy(response) };
+            result
+        };
+
+        (pending.callback_fn)(result);
+    })
+}
@@ -0,0 +1,295 @@
+//! Package archives and their installation, for provisioning tools.
+//!
+//! Installing and uninstalling require the platform level
+//! `http://tizen.org/privilege/packagemanager.admin` privilege.
+
+use crate::error::{check, Error, Result};
+use crate::rutin::{abort_on_panic, borrow_str, cstring, pin_callback, take_string};
+use rutin_tizen_sys::{
+    package_archive_info_create, package_archive_info_destroy,
+    package_archive_info_get_api_version, package_archive_info_get_author,
+    package_archive_info_get_description, package_archive_info_get_icon,
+    package_archive_info_get_label, package_archive_info_get_package,
+    package_archive_info_get_type, package_archive_info_get_version, package_archive_info_h,
+    package_manager_error_e, package_manager_event_state_e,
+    package_manager_event_state_e_PACKAGE_MANAGER_EVENT_STATE_COMPLETED,
+    package_manager_event_state_e_PACKAGE_MANAGER_EVENT_STATE_FAILED,
+    package_manager_event_state_e_PACKAGE_MANAGER_EVENT_STATE_PROCESSING,
+    package_manager_event_state_e_PACKAGE_MANAGER_EVENT_STATE_STARTED,
+    package_manager_event_type_e, package_manager_event_type_e_PACKAGE_MANAGER_EVENT_TYPE_CLEAR,
+    package_manager_event_type_e_PACKAGE_MANAGER_EVENT_TYPE_INSTALL,
+    package_manager_event_type_e_PACKAGE_MANAGER_EVENT_TYPE_MOVE,
+    package_manager_event_type_e_PACKAGE_MANAGER_EVENT_TYPE_UNINSTALL,
+    package_manager_event_type_e_PACKAGE_MANAGER_EVENT_TYPE_UPDATE, package_manager_request_create,
+    package_manager_request_destroy, package_manager_request_h, package_manager_request_install,
+    package_manager_request_set_event_cb, package_manager_request_uninstall,
+    package_manager_request_unset_event_cb,
+};
+use std::convert::TryFrom;
+use std::os::raw::{c_char, c_int, c_uchar, c_void};
+use std::path::Path;
+use std::pin::Pin;
+use std::ptr::null_mut;
+use std::slice;
+
+/// The metadata of a `.tpk` or `.wgt` package, read from its manifest before installing it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ArchiveInfo {
+    pub package_id: String,
+    /// `tpk` or `wgt`.
+    pub package_type: String,
+    pub version: String,
+    /// The platform version the package requires.
+    pub api_version: Option<String>,
+    pub label: Option<String>,
+    pub description: Option<String>,
+    pub author: Option<String>,
+    /// The content of the icon file.
+    pub icon: Option<Vec<u8>>,
+}
+
+type ArchiveGetter = unsafe extern "C" fn(package_archive_info_h, *mut *mut c_char) -> c_int;
+
+impl ArchiveInfo {
+    pub fn from_file(path: &Path) -> Result<ArchiveInfo> {
+        let path = cstring(&path.to_string_lossy())?;
+
+        let mut handle = null_mut();
+        check(unsafe { package_archive_info_create(path.as_ptr(), &mut handle) })?;
+        let archive = RawArchiveInfo { handle };
+
+        let string = |get: ArchiveGetter| {
+            let mut value = null_mut();
+            check(unsafe { get(archive.handle, &mut value) })?;
+            Ok(unsafe { take_string(value) })
+        };
+
+        let mut icon: *mut c_uchar = null_mut();
+        let mut icon_size = 0;
+        check(unsafe { package_archive_info_get_icon(archive.handle, &mut icon, &mut icon_size) })?;
+
+        let icon = if icon.is_null() {
+            None
+        } else {
+            let bytes = unsafe { slice::from_raw_parts(icon, icon_size as usize) }.to_vec();
+            unsafe { libc::free(icon as *mut c_void) };
+
+            Some(bytes).filter(|bytes| !bytes.is_empty())
+        };
+
+        Ok(Self {
+            package_id: string(package_archive_info_get_package)?.unwrap_or_default(),
+            package_type: string(package_archive_info_get_type)?.unwrap_or_default(),
+            version: string(package_archive_info_get_version)?.unwrap_or_default(),
+            api_version: string(package_archive_info_get_api_version)?,
+            label: string(package_archive_info_get_label)?,
+            description: string(package_archive_info_get_description)?,
+            author: string(package_archive_info_get_author)?,
+            icon,
+        })
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EventType {
+    Install,
+    Uninstall,
+    Update,
+    Move,
+    Clear,
+}
+
+impl TryFrom<package_manager_event_type_e> for EventType {
+    type Error = Error;
+
+    fn try_from(event_type: package_manager_event_type_e) -> Result<EventType> {
+        match event_type {
+            package_manager_event_type_e_PACKAGE_MANAGER_EVENT_TYPE_INSTALL => {
+                Ok(EventType::Install)
+            }
+            package_manager_event_type_e_PACKAGE_MANAGER_EVENT_TYPE_UNINSTALL => {
+                Ok(EventType::Uninstall)
+            }
+            package_manager_event_type_e_PACKAGE_MANAGER_EVENT_TYPE_UPDATE => Ok(EventType::Update),
+            package_manager_event_type_e_PACKAGE_MANAGER_EVENT_TYPE_MOVE => Ok(EventType::Move),
+            package_manager_event_type_e_PACKAGE_MANAGER_EVENT_TYPE_CLEAR => Ok(EventType::Clear),
+            _ => Err(Error::Unknown),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EventState {
+    Started,
+    Processing,
+    Completed,
+    Failed,
+}
+
+impl TryFrom<package_manager_event_state_e> for EventState {
+    type Error = Error;
+
+    fn try_from(state: package_manager_event_state_e) -> Result<EventState> {
+        match state {
+            package_manager_event_state_e_PACKAGE_MANAGER_EVENT_STATE_STARTED => {
+                Ok(EventState::Started)
+            }
+            package_manager_event_state_e_PACKAGE_MANAGER_EVENT_STATE_PROCESSING => {
+                Ok(EventState::Processing)
+            }
+            package_manager_event_state_e_PACKAGE_MANAGER_EVENT_STATE_COMPLETED => {
+                Ok(EventState::Completed)
+            }
+            package_manager_event_state_e_PACKAGE_MANAGER_EVENT_STATE_FAILED => {
+                Ok(EventState::Failed)
+            }
+            _ => Err(Error::Unknown),
+        }
+    }
+}
+
+/// The progress of an installation or uninstallation request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RequestEvent {
+    /// The id returned when making the request.
+    pub id: i32,
+    pub package_type: String,
+    pub package_id: String,
+    pub event_type: EventType,
+    pub state: EventState,
+    /// From 0 to 100.
+    pub progress: i32,
+    /// Why the request failed, with [`EventState::Failed`].
+    pub error: Option<Error>,
+}
+
+type EventFn = Box<dyn FnMut(RequestEvent)>;
+
+/// Makes installation and uninstallation requests, reporting their progress to the event
+/// callback.
+pub struct Request {
+    handle: package_manager_request_h,
+    event_fn: Option<Pin<Box<EventFn>>>,
+}
+
+impl Request {
+    pub fn new() -> Result<Request> {
+        let mut handle = null_mut();
+        check(unsafe { package_manager_request_create(&mut handle) })?;
+
+        Ok(Self {
+            handle,
+            event_fn: None,
+        })
+    }
+
+    /// Calls `callback_fn` with the progress of the requests, replacing the previous
+    /// callback.
+    pub fn set_event_cb<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(RequestEvent),
+        F: 'static,
+    {
+        let (callback_fn, data) = pin_callback::<dyn FnMut(RequestEvent)>(Box::new(callback_fn));
+
+        check(unsafe {
+            package_manager_request_set_event_cb(self.handle, Some(event_handler), data)
+        })?;
+        self.event_fn = Some(callback_fn);
+
+        Ok(())
+    }
+
+    pub fn unset_event_cb(&mut self) -> Result<()> {
+        check(unsafe { package_manager_request_unset_event_cb(self.handle) })?;
+        self.event_fn = None;
+
+        Ok(())
+    }
+
+    /// Installs the package at `path`, returning the id of the request.
+    pub fn install(&self, path: &Path) -> Result<i32> {
+        let path = cstring(&path.to_string_lossy())?;
+
+        let mut id = 0;
+        check(unsafe { package_manager_request_install(self.handle, path.as_ptr(), &mut id) })?;
+
+        Ok(id)
+    }
+
+    /// Uninstalls the package `package_id`, returning the id of the request.
+    pub fn uninstall(&self, package_id: &str) -> Result<i32> {
+        let package_id = cstring(package_id)?;
+
+        let mut id = 0;
+        check(unsafe {
+            package_manager_request_uninstall(self.handle, package_id.as_ptr(), &mut id)
+        })?;
+
+        Ok(id)
+    }
+}
+
+impl Drop for Request {
+    fn drop(&mut self) {
+        unsafe {
+            if self.event_fn.is_some() {
+                package_manager_request_unset_event_cb(self.handle);
+            }
+
+            package_manager_request_destroy(self.handle);
+        }
+    }
+}
+
+struct RawArchiveInfo {
+    handle: package_archive_info_h,
+}
+
+impl Drop for RawArchiveInfo {
+    fn drop(&mut self) {
+        unsafe {
+            package_archive_info_destroy(self.handle);
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+extern "C" fn event_handler(
+    id: c_int,
+    package_type: *const c_char,
+    package_id: *const c_char,
+    event_type: package_manager_event_type_e,
+    state: package_manager_event_state_e,
+    progress: c_int,
+    error: package_manager_error_e,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut EventFn) };
+
+        let (event_type, state) =
+            match (EventType::try_from(event_type), EventState::try_from(state)) {
+                (Ok(event_type), Ok(state)) => (event_type, state),
+                _ => return,
+            };
+
+        closure(RequestEvent {
+            id,
+            package_type: unsafe { borrow_str(package_type) }
+                .unwrap_or_default()
+                .into_owned(),
+            package_id: unsafe { borrow_str(package_id) }
+                .unwrap_or_default()
+                .into_owned(),
+            event_type,
+            state,
+            progress,
+            error: check(error).err(),
+        });
+    })
+}
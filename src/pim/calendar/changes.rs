@@ -0,0 +1,64 @@
+use crate::error::{check, Result};
+use crate::rutin::{abort_on_panic, pin_callback};
+use rutin_tizen_sys::{
+    _calendar_event, _calendar_todo, calendar_db_add_changed_cb, calendar_db_remove_changed_cb,
+};
+use std::os::raw::{c_char, c_void};
+use std::pin::Pin;
+
+type ChangedFn = Box<dyn FnMut()>;
+
+/// Calls a callback whenever records of a view change, including from other apps and sync,
+/// until dropped.
+pub struct ChangeListener {
+    view_uri: *const c_char,
+    callback_fn: Pin<Box<ChangedFn>>,
+}
+
+impl ChangeListener {
+    /// Listens for changes to any event.
+    pub fn events<F>(callback_fn: F) -> Result<ChangeListener>
+    where
+        F: FnMut(),
+        F: 'static,
+    {
+        Self::new(unsafe { _calendar_event._uri }, Box::new(callback_fn))
+    }
+
+    /// Listens for changes to any todo.
+    pub fn todos<F>(callback_fn: F) -> Result<ChangeListener>
+    where
+        F: FnMut(),
+        F: 'static,
+    {
+        Self::new(unsafe { _calendar_todo._uri }, Box::new(callback_fn))
+    }
+
+    fn new(view_uri: *const c_char, callback_fn: ChangedFn) -> Result<ChangeListener> {
+        let (callback_fn, data) = pin_callback::<dyn FnMut()>(callback_fn);
+
+        check(unsafe { calendar_db_add_changed_cb(view_uri, Some(changed_handler), data) })?;
+
+        Ok(Self {
+            view_uri,
+            callback_fn,
+        })
+    }
+}
+
+impl Drop for ChangeListener {
+    fn drop(&mut self) {
+        let data = &*self.callback_fn as *const ChangedFn as *mut c_void;
+
+        unsafe {
+            calendar_db_remove_changed_cb(self.view_uri, Some(changed_handler), data);
+        }
+    }
+}
+
+extern "C" fn changed_handler(_view_uri: *const c_char, user_data: *mut c_void) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut ChangedFn) };
+        closure();
+    })
+}
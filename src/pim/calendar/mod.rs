@@ -0,0 +1,806 @@
+//! The calendar, holding events and todos in calendar books.
+//!
+//! Recurring events are expanded by the service into instances, which are what an agenda
+//! shows. Requires the `http://tizen.org/privilege/calendar.read` privilege, and
+//! `http://tizen.org/privilege/calendar.write` to modify it.
+
+mod changes;
+mod reminder;
+
+pub use changes::ChangeListener;
+pub use reminder::{Reminder, ReminderListener};
+
+use crate::error::{check, Result};
+use crate::rutin::{borrow_str, cstring};
+use rutin_tizen_sys::{
+    _calendar_alarm, _calendar_event, _calendar_instance_localtime_calendar_book,
+    _calendar_instance_utime_calendar_book, _calendar_todo, calendar_connect,
+    calendar_db_delete_record, calendar_db_get_all_records, calendar_db_get_record,
+    calendar_db_get_records_with_query, calendar_db_insert_record, calendar_db_update_record,
+    calendar_disconnect, calendar_filter_add_caltime, calendar_filter_add_operator,
+    calendar_filter_create, calendar_filter_destroy, calendar_filter_h,
+    calendar_filter_operator_e_CALENDAR_FILTER_OPERATOR_AND, calendar_list_destroy,
+    calendar_list_first, calendar_list_get_count, calendar_list_get_current_record_p,
+    calendar_list_h, calendar_list_next, calendar_match_int_flag_e_CALENDAR_MATCH_GREATER_THAN,
+    calendar_match_int_flag_e_CALENDAR_MATCH_LESS_THAN, calendar_query_create,
+    calendar_query_destroy, calendar_query_h, calendar_query_set_filter, calendar_query_set_sort,
+    calendar_range_type_e, calendar_range_type_e_CALENDAR_RANGE_COUNT,
+    calendar_range_type_e_CALENDAR_RANGE_NONE, calendar_range_type_e_CALENDAR_RANGE_UNTIL,
+    calendar_record_add_child_record, calendar_record_create, calendar_record_destroy,
+    calendar_record_get_caltime, calendar_record_get_child_record_at_p,
+    calendar_record_get_child_record_count, calendar_record_get_int, calendar_record_get_str_p,
+    calendar_record_h, calendar_record_remove_child_record, calendar_record_set_caltime,
+    calendar_record_set_int, calendar_record_set_str, calendar_recurrence_frequency_e,
+    calendar_recurrence_frequency_e_CALENDAR_RECURRENCE_DAILY,
+    calendar_recurrence_frequency_e_CALENDAR_RECURRENCE_MONTHLY,
+    calendar_recurrence_frequency_e_CALENDAR_RECURRENCE_NONE,
+    calendar_recurrence_frequency_e_CALENDAR_RECURRENCE_WEEKLY,
+    calendar_recurrence_frequency_e_CALENDAR_RECURRENCE_YEARLY, calendar_time_s,
+    calendar_time_s__bindgen_ty_1, calendar_time_s__bindgen_ty_1__bindgen_ty_1,
+    calendar_time_type_e_CALENDAR_TIME_LOCALTIME, calendar_time_type_e_CALENDAR_TIME_UTIME,
+    calendar_todo_status_e, calendar_todo_status_e_CALENDAR_TODO_STATUS_CANCELED,
+    calendar_todo_status_e_CALENDAR_TODO_STATUS_COMPLETED,
+    calendar_todo_status_e_CALENDAR_TODO_STATUS_IN_PROCESS,
+    calendar_todo_status_e_CALENDAR_TODO_STATUS_NEEDS_ACTION,
+    calendar_todo_status_e_CALENDAR_TODO_STATUS_NONE,
+};
+use std::os::raw::{c_char, c_int, c_uint};
+use std::ptr::{null, null_mut};
+
+/// The book events are inserted in by default.
+pub const DEFAULT_EVENT_BOOK_ID: i32 = 1;
+/// The book todos are inserted in by default.
+pub const DEFAULT_TODO_BOOK_ID: i32 = 2;
+
+/// Connects to the calendar service, needed before any other call of this module.
+pub fn connect() -> Result<()> {
+    check(unsafe { calendar_connect() })
+}
+
+pub fn disconnect() -> Result<()> {
+    check(unsafe { calendar_disconnect() })
+}
+
+/// A date and time in the local time zone, such as for all day events.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LocalTime {
+    pub year: i32,
+    /// From 1 to 12.
+    pub month: i32,
+    pub day: i32,
+    pub hour: i32,
+    pub minute: i32,
+    pub second: i32,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Time {
+    /// Seconds since the epoch, in UTC.
+    Utc(i64),
+    Local(LocalTime),
+}
+
+impl From<Time> for calendar_time_s {
+    fn from(time: Time) -> calendar_time_s {
+        match time {
+            Time::Utc(utime) => calendar_time_s {
+                type_: calendar_time_type_e_CALENDAR_TIME_UTIME,
+                time: calendar_time_s__bindgen_ty_1 { utime },
+            },
+            Time::Local(local) => calendar_time_s {
+                type_: calendar_time_type_e_CALENDAR_TIME_LOCALTIME,
+                time: calendar_time_s__bindgen_ty_1 {
+                    date: calendar_time_s__bindgen_ty_1__bindgen_ty_1 {
+                        year: local.year,
+                        month: local.month,
+                        mday: local.day,
+                        hour: local.hour,
+                        minute: local.minute,
+                        second: local.second,
+                        is_leap_month: false,
+                    },
+                },
+            },
+        }
+    }
+}
+
+impl From<calendar_time_s> for Time {
+    fn from(time: calendar_time_s) -> Time {
+        unsafe {
+            if time.type_ == calendar_time_type_e_CALENDAR_TIME_LOCALTIME {
+                let date = time.time.date;
+
+                Time::Local(LocalTime {
+                    year: date.year,
+                    month: date.month,
+                    day: date.mday,
+                    hour: date.hour,
+                    minute: date.minute,
+                    second: date.second,
+                })
+            } else {
+                Time::Utc(time.time.utime)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+/// When a recurring event stops repeating.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RecurrenceEnd {
+    Never,
+    Until(Time),
+    /// After a number of occurrences.
+    Count(i32),
+}
+
+/// How an event repeats, following the iCalendar RRULE fields.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Recurrence {
+    pub frequency: Frequency,
+    /// Repeats every `interval` days, weeks, months or years.
+    pub interval: i32,
+    pub end: RecurrenceEnd,
+    /// The days of the week, such as "MO,WE,FR" or "1SU" for the first Sunday of the month.
+    pub by_day: Option<String>,
+    /// The days of the month, such as "1,15".
+    pub by_month_day: Option<String>,
+}
+
+impl Recurrence {
+    unsafe fn read(record: calendar_record_h) -> Option<Recurrence> {
+        let event = &_calendar_event;
+        let frequency = match get_int(record, event.freq) as calendar_recurrence_frequency_e {
+            calendar_recurrence_frequency_e_CALENDAR_RECURRENCE_DAILY => Frequency::Daily,
+            calendar_recurrence_frequency_e_CALENDAR_RECURRENCE_WEEKLY => Frequency::Weekly,
+            calendar_recurrence_frequency_e_CALENDAR_RECURRENCE_MONTHLY => Frequency::Monthly,
+            calendar_recurrence_frequency_e_CALENDAR_RECURRENCE_YEARLY => Frequency::Yearly,
+            _ => return None,
+        };
+
+        let end = match get_int(record, event.range_type) as calendar_range_type_e {
+            calendar_range_type_e_CALENDAR_RANGE_UNTIL => {
+                RecurrenceEnd::Until(get_time(record, event.until_time)?)
+            }
+            calendar_range_type_e_CALENDAR_RANGE_COUNT => {
+                RecurrenceEnd::Count(get_int(record, event.count))
+            }
+            _ => RecurrenceEnd::Never,
+        };
+
+        Some(Recurrence {
+            frequency,
+            interval: get_int(record, event.interval),
+            end,
+            by_day: get_str(record, event.byday),
+            by_month_day: get_str(record, event.bymonthday),
+        })
+    }
+
+    unsafe fn write(recurrence: Option<&Recurrence>, record: calendar_record_h) -> Result<()> {
+        let event = &_calendar_event;
+
+        let recurrence = match recurrence {
+            Some(recurrence) => recurrence,
+            None => {
+                return set_int(
+                    record,
+                    event.freq,
+                    calendar_recurrence_frequency_e_CALENDAR_RECURRENCE_NONE as c_int,
+                )
+            }
+        };
+
+        let frequency = match recurrence.frequency {
+            Frequency::Daily => calendar_recurrence_frequency_e_CALENDAR_RECURRENCE_DAILY,
+            Frequency::Weekly => calendar_recurrence_frequency_e_CALENDAR_RECURRENCE_WEEKLY,
+            Frequency::Monthly => calendar_recurrence_frequency_e_CALENDAR_RECURRENCE_MONTHLY,
+            Frequency::Yearly => calendar_recurrence_frequency_e_CALENDAR_RECURRENCE_YEARLY,
+        };
+        set_int(record, event.freq, frequency as c_int)?;
+        set_int(record, event.interval, recurrence.interval)?;
+
+        match recurrence.end {
+            RecurrenceEnd::Never => set_int(
+                record,
+                event.range_type,
+                calendar_range_type_e_CALENDAR_RANGE_NONE as c_int,
+            )?,
+            RecurrenceEnd::Until(until) => {
+                set_int(
+                    record,
+                    event.range_type,
+                    calendar_range_type_e_CALENDAR_RANGE_UNTIL as c_int,
+                )?;
+                check(calendar_record_set_caltime(
+                    record,
+                    event.until_time,
+                    until.into(),
+                ))?;
+            }
+            RecurrenceEnd::Count(count) => {
+                set_int(
+                    record,
+                    event.range_type,
+                    calendar_range_type_e_CALENDAR_RANGE_COUNT as c_int,
+                )?;
+                set_int(record, event.count, count)?;
+            }
+        }
+
+        set_str(record, event.byday, recurrence.by_day.as_deref())?;
+        set_str(record, event.bymonthday, recurrence.by_month_day.as_deref())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Event {
+    /// 0 until inserted.
+    pub id: i32,
+    pub book_id: i32,
+    pub summary: Option<String>,
+    pub description: Option<String>,
+    pub location: Option<String>,
+    /// [`Time::Local`] for all day events, in which case the end is also local.
+    pub start: Time,
+    pub end: Time,
+    pub recurrence: Option<Recurrence>,
+    pub reminders: Vec<Reminder>,
+}
+
+impl Event {
+    /// An event in the default book, which does not repeat.
+    pub fn new(start: Time, end: Time) -> Event {
+        Event {
+            id: 0,
+            book_id: DEFAULT_EVENT_BOOK_ID,
+            summary: None,
+            description: None,
+            location: None,
+            start,
+            end,
+            recurrence: None,
+            reminders: Vec::new(),
+        }
+    }
+
+    unsafe fn read(record: calendar_record_h) -> Event {
+        let event = &_calendar_event;
+
+        Event {
+            id: get_int(record, event.id),
+            book_id: get_int(record, event.book_id),
+            summary: get_str(record, event.summary),
+            description: get_str(record, event.description),
+            location: get_str(record, event.location),
+            start: get_time(record, event.start_time).unwrap_or(Time::Utc(0)),
+            end: get_time(record, event.end_time).unwrap_or(Time::Utc(0)),
+            recurrence: Recurrence::read(record),
+            reminders: read_children(record, event.calendar_alarm, Reminder::read)
+                .into_iter()
+                .flatten()
+                .collect(),
+        }
+    }
+
+    unsafe fn write(&self, record: calendar_record_h) -> Result<()> {
+        let event = &_calendar_event;
+
+        set_int(record, event.book_id, self.book_id)?;
+        set_str(record, event.summary, self.summary.as_deref())?;
+        set_str(record, event.description, self.description.as_deref())?;
+        set_str(record, event.location, self.location.as_deref())?;
+        check(calendar_record_set_caltime(
+            record,
+            event.start_time,
+            self.start.into(),
+        ))?;
+        check(calendar_record_set_caltime(
+            record,
+            event.end_time,
+            self.end.into(),
+        ))?;
+
+        Recurrence::write(self.recurrence.as_ref(), record)?;
+        replace_children(
+            record,
+            event.calendar_alarm,
+            _calendar_alarm._uri,
+            &self.reminders,
+            Reminder::write,
+        )
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TodoStatus {
+    None,
+    NeedsAction,
+    Completed,
+    InProcess,
+    Canceled,
+}
+
+impl From<TodoStatus> for calendar_todo_status_e {
+    fn from(status: TodoStatus) -> calendar_todo_status_e {
+        match status {
+            TodoStatus::None => calendar_todo_status_e_CALENDAR_TODO_STATUS_NONE,
+            TodoStatus::NeedsAction => calendar_todo_status_e_CALENDAR_TODO_STATUS_NEEDS_ACTION,
+            TodoStatus::Completed => calendar_todo_status_e_CALENDAR_TODO_STATUS_COMPLETED,
+            TodoStatus::InProcess => calendar_todo_status_e_CALENDAR_TODO_STATUS_IN_PROCESS,
+            TodoStatus::Canceled => calendar_todo_status_e_CALENDAR_TODO_STATUS_CANCELED,
+        }
+    }
+}
+
+impl From<calendar_todo_status_e> for TodoStatus {
+    fn from(status: calendar_todo_status_e) -> TodoStatus {
+        match status {
+            calendar_todo_status_e_CALENDAR_TODO_STATUS_NEEDS_ACTION => TodoStatus::NeedsAction,
+            calendar_todo_status_e_CALENDAR_TODO_STATUS_COMPLETED => TodoStatus::Completed,
+            calendar_todo_status_e_CALENDAR_TODO_STATUS_IN_PROCESS => TodoStatus::InProcess,
+            calendar_todo_status_e_CALENDAR_TODO_STATUS_CANCELED => TodoStatus::Canceled,
+            _ => TodoStatus::None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Todo {
+    /// 0 until inserted.
+    pub id: i32,
+    pub book_id: i32,
+    pub summary: Option<String>,
+    pub description: Option<String>,
+    pub due: Option<Time>,
+    pub status: TodoStatus,
+}
+
+impl Default for Todo {
+    /// A todo in the default book.
+    fn default() -> Todo {
+        Todo {
+            id: 0,
+            book_id: DEFAULT_TODO_BOOK_ID,
+            summary: None,
+            description: None,
+            due: None,
+            status: TodoStatus::None,
+        }
+    }
+}
+
+impl Todo {
+    unsafe fn read(record: calendar_record_h) -> Todo {
+        let todo = &_calendar_todo;
+
+        Todo {
+            id: get_int(record, todo.id),
+            book_id: get_int(record, todo.book_id),
+            summary: get_str(record, todo.summary),
+            description: get_str(record, todo.description),
+            due: get_time(record, todo.due_time),
+            status: (get_int(record, todo.todo_status) as calendar_todo_status_e).into(),
+        }
+    }
+
+    unsafe fn write(&self, record: calendar_record_h) -> Result<()> {
+        let todo = &_calendar_todo;
+
+        set_int(record, todo.book_id, self.book_id)?;
+        set_str(record, todo.summary, self.summary.as_deref())?;
+        set_str(record, todo.description, self.description.as_deref())?;
+        if let Some(due) = self.due {
+            check(calendar_record_set_caltime(
+                record,
+                todo.due_time,
+                due.into(),
+            ))?;
+        }
+
+        set_int(
+            record,
+            todo.todo_status,
+            calendar_todo_status_e::from(self.status) as c_int,
+        )
+    }
+}
+
+/// One occurrence of an event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Instance {
+    pub event_id: i32,
+    pub book_id: i32,
+    pub summary: Option<String>,
+    pub location: Option<String>,
+    pub start: Time,
+    pub end: Time,
+}
+
+pub fn insert_event(event: &Event) -> Result<i32> {
+    insert(unsafe { _calendar_event._uri }, |record| unsafe {
+        event.write(record)
+    })
+}
+
+pub fn get_event(id: i32) -> Result<Event> {
+    let record = RawRecord::get(unsafe { _calendar_event._uri }, id)?;
+
+    Ok(unsafe { Event::read(record.handle) })
+}
+
+/// Saves the changes to an inserted event, including all its instances.
+pub fn update_event(event: &Event) -> Result<()> {
+    let record = RawRecord::get(unsafe { _calendar_event._uri }, event.id)?;
+    unsafe { event.write(record.handle) }?;
+
+    check(unsafe { calendar_db_update_record(record.handle) })
+}
+
+pub fn delete_event(id: i32) -> Result<()> {
+    check(unsafe { calendar_db_delete_record(_calendar_event._uri, id) })
+}
+
+pub fn insert_todo(todo: &Todo) -> Result<i32> {
+    insert(unsafe { _calendar_todo._uri }, |record| unsafe {
+        todo.write(record)
+    })
+}
+
+pub fn get_todo(id: i32) -> Result<Todo> {
+    let record = RawRecord::get(unsafe { _calendar_todo._uri }, id)?;
+
+    Ok(unsafe { Todo::read(record.handle) })
+}
+
+pub fn update_todo(todo: &Todo) -> Result<()> {
+    let record = RawRecord::get(unsafe { _calendar_todo._uri }, todo.id)?;
+    unsafe { todo.write(record.handle) }?;
+
+    check(unsafe { calendar_db_update_record(record.handle) })
+}
+
+pub fn delete_todo(id: i32) -> Result<()> {
+    check(unsafe { calendar_db_delete_record(_calendar_todo._uri, id) })
+}
+
+/// Up to `limit` todos from `offset`, all of them if `limit` is 0.
+pub fn all_todos(offset: i32, limit: i32) -> Result<Vec<Todo>> {
+    let mut list = null_mut();
+    check(unsafe { calendar_db_get_all_records(_calendar_todo._uri, offset, limit, &mut list) })?;
+
+    unsafe { take_list(list, |record| Todo::read(record)) }
+}
+
+/// The instances of events with a time, overlapping `start..end` in seconds since the epoch,
+/// sorted by start.
+pub fn instances(start: i64, end: i64) -> Result<Vec<Instance>> {
+    unsafe {
+        let view = &_calendar_instance_utime_calendar_book;
+
+        query_instances(
+            view._uri,
+            view.start_time,
+            view.end_time,
+            Time::Utc(start),
+            Time::Utc(end),
+            |record| Instance {
+                event_id: get_int(record, view.event_id),
+                book_id: get_int(record, view.book_id),
+                summary: get_str(record, view.summary),
+                location: get_str(record, view.location),
+                start: get_time(record, view.start_time).unwrap_or(Time::Utc(0)),
+                end: get_time(record, view.end_time).unwrap_or(Time::Utc(0)),
+            },
+        )
+    }
+}
+
+/// The instances of all day events overlapping `start..end`, sorted by start.
+pub fn all_day_instances(start: LocalTime, end: LocalTime) -> Result<Vec<Instance>> {
+    unsafe {
+        let view = &_calendar_instance_localtime_calendar_book;
+
+        query_instances(
+            view._uri,
+            view.start_time,
+            view.end_time,
+            Time::Local(start),
+            Time::Local(end),
+            |record| Instance {
+                event_id: get_int(record, view.event_id),
+                book_id: get_int(record, view.book_id),
+                summary: get_str(record, view.summary),
+                location: get_str(record, view.location),
+                start: get_time(record, view.start_time).unwrap_or(Time::Local(start)),
+                end: get_time(record, view.end_time).unwrap_or(Time::Local(end)),
+            },
+        )
+    }
+}
+
+/// Queries the instances of `view_uri` starting before `end` and ending after `start`.
+unsafe fn query_instances<F>(
+    view_uri: *const c_char,
+    start_property: c_uint,
+    end_property: c_uint,
+    start: Time,
+    end: Time,
+    read: F,
+) -> Result<Vec<Instance>>
+where
+    F: Fn(calendar_record_h) -> Instance,
+{
+    let filter = RawFilter::new(view_uri)?;
+    check(calendar_filter_add_caltime(
+        filter.handle,
+        start_property,
+        calendar_match_int_flag_e_CALENDAR_MATCH_LESS_THAN,
+        end.into(),
+    ))?;
+    check(calendar_filter_add_operator(
+        filter.handle,
+        calendar_filter_operator_e_CALENDAR_FILTER_OPERATOR_AND,
+    ))?;
+    check(calendar_filter_add_caltime(
+        filter.handle,
+        end_property,
+        calendar_match_int_flag_e_CALENDAR_MATCH_GREATER_THAN,
+        start.into(),
+    ))?;
+
+    let query = RawQuery::new(view_uri)?;
+    check(calendar_query_set_filter(query.handle, filter.handle))?;
+    check(calendar_query_set_sort(query.handle, start_property, true))?;
+
+    let mut list = null_mut();
+    check(calendar_db_get_records_with_query(
+        query.handle,
+        0,
+        0,
+        &mut list,
+    ))?;
+
+    take_list(list, read)
+}
+
+fn insert<F>(view_uri: *const c_char, write: F) -> Result<i32>
+where
+    F: FnOnce(calendar_record_h) -> Result<()>,
+{
+    let record = RawRecord::new(view_uri)?;
+    write(record.handle)?;
+
+    let mut id = 0;
+    check(unsafe { calendar_db_insert_record(record.handle, &mut id) })?;
+
+    Ok(id)
+}
+
+/// A record owned by this value, destroyed along with its children.
+struct RawRecord {
+    handle: calendar_record_h,
+}
+
+impl RawRecord {
+    fn new(view_uri: *const c_char) -> Result<RawRecord> {
+        let mut handle = null_mut();
+        check(unsafe { calendar_record_create(view_uri, &mut handle) })?;
+
+        Ok(Self { handle })
+    }
+
+    fn get(view_uri: *const c_char, id: i32) -> Result<RawRecord> {
+        let mut handle = null_mut();
+        check(unsafe { calendar_db_get_record(view_uri, id, &mut handle) })?;
+
+        Ok(Self { handle })
+    }
+
+    fn into_raw(self) -> calendar_record_h {
+        let handle = self.handle;
+        std::mem::forget(self);
+
+        handle
+    }
+}
+
+impl Drop for RawRecord {
+    fn drop(&mut self) {
+        unsafe {
+            calendar_record_destroy(self.handle, true);
+        }
+    }
+}
+
+struct RawFilter {
+    handle: calendar_filter_h,
+}
+
+impl RawFilter {
+    fn new(view_uri: *const c_char) -> Result<RawFilter> {
+        let mut handle = null_mut();
+        check(unsafe { calendar_filter_create(view_uri, &mut handle) })?;
+
+        Ok(Self { handle })
+    }
+}
+
+impl Drop for RawFilter {
+    fn drop(&mut self) {
+        unsafe {
+            calendar_filter_destroy(self.handle);
+        }
+    }
+}
+
+struct RawQuery {
+    handle: calendar_query_h,
+}
+
+impl RawQuery {
+    fn new(view_uri: *const c_char) -> Result<RawQuery> {
+        let mut handle = null_mut();
+        check(unsafe { calendar_query_create(view_uri, &mut handle) })?;
+
+        Ok(Self { handle })
+    }
+}
+
+impl Drop for RawQuery {
+    fn drop(&mut self) {
+        unsafe {
+            calendar_query_destroy(self.handle);
+        }
+    }
+}
+
+unsafe fn get_str(record: calendar_record_h, property: c_uint) -> Option<String> {
+    // points inside the record, it must not be freed
+    let mut value = null_mut();
+    check(calendar_record_get_str_p(record, property, &mut value)).ok()?;
+
+    borrow_str(value).map(|value| value.into_owned())
+}
+
+unsafe fn set_str(record: calendar_record_h, property: c_uint, value: Option<&str>) -> Result<()> {
+    let value = value.map(cstring).transpose()?;
+
+    check(calendar_record_set_str(
+        record,
+        property,
+        value.as_ref().map_or(null(), |value| value.as_ptr()),
+    ))
+}
+
+unsafe fn get_int(record: calendar_record_h, property: c_uint) -> i32 {
+    // left as is if the property is unset
+    let mut value = 0;
+    calendar_record_get_int(record, property, &mut value);
+
+    value
+}
+
+unsafe fn set_int(record: calendar_record_h, property: c_uint, value: i32) -> Result<()> {
+    check(calendar_record_set_int(record, property, value))
+}
+
+unsafe fn get_time(record: calendar_record_h, property: c_uint) -> Option<Time> {
+    let mut time = calendar_time_s::from(Time::Utc(0));
+    check(calendar_record_get_caltime(record, property, &mut time)).ok()?;
+
+    Some(time.into())
+}
+
+unsafe fn read_children<T>(
+    record: calendar_record_h,
+    property: c_uint,
+    read: unsafe fn(calendar_record_h) -> T,
+) -> Vec<T> {
+    let mut count = 0;
+    if check(calendar_record_get_child_record_count(
+        record, property, &mut count,
+    ))
+    .is_err()
+    {
+        return Vec::new();
+    }
+
+    (0..count)
+        .filter_map(|index| {
+            let mut child = null_mut();
+            check(calendar_record_get_child_record_at_p(
+                record, property, index, &mut child,
+            ))
+            .ok()?;
+
+            Some(read(child))
+        })
+        .collect()
+}
+
+/// Removes the children of `record` at `property`, then adds a new `view_uri` record for
+/// each of `children`.
+unsafe fn replace_children<T>(
+    record: calendar_record_h,
+    property: c_uint,
+    view_uri: *const c_char,
+    children: &[T],
+    write: unsafe fn(&T, calendar_record_h) -> Result<()>,
+) -> Result<()> {
+    let mut count = 0;
+    check(calendar_record_get_child_record_count(
+        record, property, &mut count,
+    ))?;
+
+    // removing shifts the next children down
+    for _ in 0..count {
+        let mut child = null_mut();
+        check(calendar_record_get_child_record_at_p(
+            record, property, 0, &mut child,
+        ))?;
+        check(calendar_record_remove_child_record(record, property, child))?;
+        calendar_record_destroy(child, true);
+    }
+
+    for value in children {
+        let child = RawRecord::new(view_uri)?;
+        write(value, child.handle)?;
+
+        // the parent takes ownership of its children
+        check(calendar_record_add_child_record(
+            record,
+            property,
+            child.handle,
+        ))?;
+        child.into_raw();
+    }
+
+    Ok(())
+}
+
+/// Reads then destroys a list of records.
+unsafe fn take_list<T, F>(list: calendar_list_h, read: F) -> Result<Vec<T>>
+where
+    F: Fn(calendar_record_h) -> T,
+{
+    let mut count = 0;
+    let result = check(calendar_list_get_count(list, &mut count));
+    let mut values = Vec::new();
+
+    if result.is_ok() {
+        calendar_list_first(list);
+
+        for _ in 0..count {
+            let mut record = null_mut();
+            if check(calendar_list_get_current_record_p(list, &mut record)).is_err() {
+                break;
+            }
+
+            values.push(read(record));
+            calendar_list_next(list);
+        }
+    }
+
+    calendar_list_destroy(list, true);
+
+    result.map(|_| values)
+}
@@ -0,0 +1,114 @@
+use super::{get_int, get_time, set_int, Time};
+use crate::error::{check, Result};
+use crate::rutin::{abort_on_panic, borrow_str, pin_callback};
+use rutin_tizen_sys::{
+    _calendar_alarm, calendar_alarm_time_unit_type_e,
+    calendar_alarm_time_unit_type_e_CALENDAR_ALARM_TIME_UNIT_MINUTE,
+    calendar_alarm_time_unit_type_e_CALENDAR_ALARM_TIME_UNIT_SPECIFIC, calendar_record_h,
+    calendar_record_set_caltime, calendar_reminder_add_cb, calendar_reminder_remove_cb,
+};
+use std::os::raw::{c_char, c_int, c_void};
+use std::pin::Pin;
+
+/// When the user is reminded of an event.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Reminder {
+    /// A number of minutes before the start.
+    Before(i32),
+    At(Time),
+}
+
+impl Reminder {
+    pub(super) unsafe fn read(record: calendar_record_h) -> Option<Reminder> {
+        let alarm = &_calendar_alarm;
+        let tick = get_int(record, alarm.tick);
+
+        // the unit is a number of seconds, apart from specific times
+        match get_int(record, alarm.tick_unit) as calendar_alarm_time_unit_type_e {
+            calendar_alarm_time_unit_type_e_CALENDAR_ALARM_TIME_UNIT_SPECIFIC => {
+                get_time(record, alarm.alarm_time).map(Reminder::At)
+            }
+            unit if unit > 0 => Some(Reminder::Before(tick * unit / 60)),
+            _ => None,
+        }
+    }
+
+    pub(super) unsafe fn write(&self, record: calendar_record_h) -> Result<()> {
+        let alarm = &_calendar_alarm;
+
+        match *self {
+            Reminder::Before(minutes) => {
+                set_int(
+                    record,
+                    alarm.tick_unit,
+                    calendar_alarm_time_unit_type_e_CALENDAR_ALARM_TIME_UNIT_MINUTE as c_int,
+                )?;
+                set_int(record, alarm.tick, minutes)
+            }
+            Reminder::At(time) => {
+                set_int(
+                    record,
+                    alarm.tick_unit,
+                    calendar_alarm_time_unit_type_e_CALENDAR_ALARM_TIME_UNIT_SPECIFIC as c_int,
+                )?;
+                check(calendar_record_set_caltime(
+                    record,
+                    alarm.alarm_time,
+                    time.into(),
+                ))
+            }
+        }
+    }
+}
+
+type ReminderFn = Box<dyn FnMut(i32)>;
+
+/// Calls a callback with the id of the event when one of its reminders is due, until dropped.
+pub struct ReminderListener {
+    callback_fn: Pin<Box<ReminderFn>>,
+}
+
+impl ReminderListener {
+    pub fn new<F>(callback_fn: F) -> Result<ReminderListener>
+    where
+        F: FnMut(i32),
+        F: 'static,
+    {
+        let (callback_fn, data) = pin_callback::<dyn FnMut(i32)>(Box::new(callback_fn));
+
+        check(unsafe { calendar_reminder_add_cb(Some(reminder_handler), data) })?;
+
+        Ok(Self { callback_fn })
+    }
+}
+
+impl Drop for ReminderListener {
+    fn drop(&mut self) {
+        let data = &*self.callback_fn as *const ReminderFn as *mut c_void;
+
+        unsafe {
+            calendar_reminder_remove_cb(Some(reminder_handler), data);
+        }
+    }
+}
+
+extern "C" fn reminder_handler(param: *const c_char, user_data: *mut c_void) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut ReminderFn) };
+        let param = match unsafe { borrow_str(param) } {
+            Some(param) => param,
+            None => return,
+        };
+
+        // a query string such as "type=0&id=12&time=...&tick=5&unit=60"
+        let id = param
+            .split('&')
+            .filter_map(|pair| pair.strip_prefix("id="))
+            .find_map(|id| id.parse().ok());
+
+        if let Some(id) = id {
+            closure(id);
+        }
+    })
+}
@@ -0,0 +1,147 @@
+use super::take_list;
+use crate::error::{check, Result};
+use crate::rutin::{abort_on_panic, pin_callback};
+use rutin_tizen_sys::{
+    _contacts_contact, _contacts_contact_updated_info, _contacts_person, _contacts_phone_log,
+    contacts_changed_e, contacts_changed_e_CONTACTS_CHANGE_INSERTED,
+    contacts_changed_e_CONTACTS_CHANGE_UPDATED, contacts_db_add_changed_cb,
+    contacts_db_get_changes_by_version, contacts_db_get_current_version,
+    contacts_db_remove_changed_cb, contacts_record_get_int, contacts_record_h,
+};
+use std::os::raw::{c_char, c_void};
+use std::pin::Pin;
+use std::ptr::null_mut;
+
+type ChangedFn = Box<dyn FnMut()>;
+
+/// Calls a callback whenever records of a view change, including from other apps,
+/// until dropped.
+pub struct ChangeListener {
+    view_uri: *const c_char,
+    callback_fn: Pin<Box<ChangedFn>>,
+}
+
+impl ChangeListener {
+    /// Listens for changes to any contact.
+    pub fn contacts<F>(callback_fn: F) -> Result<ChangeListener>
+    where
+        F: FnMut(),
+        F: 'static,
+    {
+        Self::new(unsafe { _contacts_contact._uri }, Box::new(callback_fn))
+    }
+
+    /// Listens for changes to any person, including their linked contacts.
+    pub fn persons<F>(callback_fn: F) -> Result<ChangeListener>
+    where
+        F: FnMut(),
+        F: 'static,
+    {
+        Self::new(unsafe { _contacts_person._uri }, Box::new(callback_fn))
+    }
+
+    /// Listens for calls added to or deleted from the call history.
+    pub fn phone_log<F>(callback_fn: F) -> Result<ChangeListener>
+    where
+        F: FnMut(),
+        F: 'static,
+    {
+        Self::new(unsafe { _contacts_phone_log._uri }, Box::new(callback_fn))
+    }
+
+    fn new(view_uri: *const c_char, callback_fn: ChangedFn) -> Result<ChangeListener> {
+        let (callback_fn, data) = pin_callback::<dyn FnMut()>(callback_fn);
+
+        check(unsafe { contacts_db_add_changed_cb(view_uri, Some(changed_handler), data) })?;
+
+        Ok(Self {
+            view_uri,
+            callback_fn,
+        })
+    }
+}
+
+impl Drop for ChangeListener {
+    fn drop(&mut self) {
+        let data = &*self.callback_fn as *const ChangedFn as *mut c_void;
+
+        unsafe {
+            contacts_db_remove_changed_cb(self.view_uri, Some(changed_handler), data);
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ChangeKind {
+    Inserted,
+    Updated,
+    Deleted,
+}
+
+/// A contact changed after some version of the database.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Change {
+    pub contact_id: i32,
+    pub kind: ChangeKind,
+    /// The version of the database after the change.
+    pub version: i32,
+}
+
+impl Change {
+    unsafe fn read(record: contacts_record_h) -> Change {
+        let info = &_contacts_contact_updated_info;
+        let (mut contact_id, mut kind, mut version) = (0, 0, 0);
+
+        contacts_record_get_int(record, info.contact_id, &mut contact_id);
+        contacts_record_get_int(record, info.type_, &mut kind);
+        contacts_record_get_int(record, info.version, &mut version);
+
+        Change {
+            contact_id,
+            kind: match kind as contacts_changed_e {
+                contacts_changed_e_CONTACTS_CHANGE_INSERTED => ChangeKind::Inserted,
+                contacts_changed_e_CONTACTS_CHANGE_UPDATED => ChangeKind::Updated,
+                _ => ChangeKind::Deleted,
+            },
+            version,
+        }
+    }
+}
+
+/// The version of the database, increased by every change.
+pub fn current_version() -> Result<i32> {
+    let mut version = 0;
+    check(unsafe { contacts_db_get_current_version(&mut version) })?;
+
+    Ok(version)
+}
+
+/// The contacts of an address book changed after `version`, along with the current version
+/// to pass next time. An `address_book_id` of -1 means all of them.
+pub fn changes_since(address_book_id: i32, version: i32) -> Result<(Vec<Change>, i32)> {
+    let mut list = null_mut();
+    let mut current_version = 0;
+
+    check(unsafe {
+        contacts_db_get_changes_by_version(
+            _contacts_contact_updated_info._uri,
+            address_book_id,
+            version,
+            &mut list,
+            &mut current_version,
+        )
+    })?;
+
+    let changes = unsafe { take_list(list, Change::read) }?;
+
+    Ok((changes, current_version))
+}
+
+extern "C" fn changed_handler(_view_uri: *const c_char, user_data: *mut c_void) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut ChangedFn) };
+        closure();
+    })
+}
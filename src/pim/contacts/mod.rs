@@ -0,0 +1,510 @@
+//! The address book, as contacts grouped into persons.
+//!
+//! Each account (phone, SIM, synced services) holds its own contacts, and the contacts
+//! describing the same person are linked together. Requires the
+//! `http://tizen.org/privilege/contact.read` privilege, and
+//! `http://tizen.org/privilege/contact.write` to modify it. The call history is kept
+//! alongside, see [`recent_calls`].
+
+mod changes;
+mod phone_log;
+mod vcard;
+
+pub use changes::{changes_since, current_version, Change, ChangeKind, ChangeListener};
+pub use phone_log::{delete_call, delete_calls_with, recent_calls, Call, CallDirection, CallMedia};
+pub use vcard::{contact_to_vcard, parse_vcard, person_to_vcard};
+
+use crate::error::{check, Result};
+use crate::rutin::{borrow_str, cstring};
+use rutin_tizen_sys::{
+    _contacts_contact, _contacts_email, _contacts_name, _contacts_number, _contacts_person,
+    contacts_connect, contacts_db_delete_record, contacts_db_get_all_records,
+    contacts_db_get_record, contacts_db_insert_record, contacts_db_search_records,
+    contacts_db_update_record, contacts_disconnect, contacts_list_destroy, contacts_list_get_count,
+    contacts_list_get_current_record_p, contacts_list_h, contacts_list_next,
+    contacts_record_add_child_record, contacts_record_create, contacts_record_destroy,
+    contacts_record_get_bool, contacts_record_get_child_record_at_p,
+    contacts_record_get_child_record_count, contacts_record_get_int, contacts_record_get_str_p,
+    contacts_record_h, contacts_record_remove_child_record, contacts_record_set_bool,
+    contacts_record_set_int, contacts_record_set_str,
+};
+use std::os::raw::{c_char, c_uint};
+use std::ptr::{null, null_mut};
+use std::slice;
+
+/// Connects to the contacts service, needed before any other call of this module.
+pub fn connect() -> Result<()> {
+    check(unsafe { contacts_connect() })
+}
+
+pub fn disconnect() -> Result<()> {
+    check(unsafe { contacts_disconnect() })
+}
+
+/// Flags of the kinds of phone numbers.
+pub mod number_type {
+    pub const OTHER: i32 = 0;
+    pub const CUSTOM: i32 = 1 << 0;
+    pub const HOME: i32 = 1 << 1;
+    pub const WORK: i32 = 1 << 2;
+    pub const VOICE: i32 = 1 << 3;
+    pub const FAX: i32 = 1 << 4;
+    pub const MSG: i32 = 1 << 5;
+    pub const CELL: i32 = 1 << 6;
+    pub const PAGER: i32 = 1 << 7;
+    pub const BBS: i32 = 1 << 8;
+    pub const MODEM: i32 = 1 << 9;
+    pub const CAR: i32 = 1 << 10;
+    pub const ISDN: i32 = 1 << 11;
+    pub const VIDEO: i32 = 1 << 12;
+    pub const PCS: i32 = 1 << 13;
+    pub const COMPANY_MAIN: i32 = 1 << 14;
+    pub const RADIO: i32 = 1 << 15;
+    pub const MAIN: i32 = 1 << 29;
+    pub const ASSISTANT: i32 = 1 << 30;
+}
+
+/// Flags of the kinds of email addresses.
+pub mod email_type {
+    pub const OTHER: i32 = 0;
+    pub const CUSTOM: i32 = 1 << 0;
+    pub const HOME: i32 = 1 << 1;
+    pub const WORK: i32 = 1 << 2;
+    pub const MOBILE: i32 = 1 << 3;
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Name {
+    pub first: Option<String>,
+    pub last: Option<String>,
+    /// Middle names.
+    pub addition: Option<String>,
+    pub prefix: Option<String>,
+    pub suffix: Option<String>,
+}
+
+impl Name {
+    unsafe fn read(record: contacts_record_h) -> Name {
+        Name {
+            first: get_str(record, _contacts_name.first),
+            last: get_str(record, _contacts_name.last),
+            addition: get_str(record, _contacts_name.addition),
+            prefix: get_str(record, _contacts_name.prefix),
+            suffix: get_str(record, _contacts_name.suffix),
+        }
+    }
+
+    unsafe fn write(&self, record: contacts_record_h) -> Result<()> {
+        set_str(record, _contacts_name.first, self.first.as_deref())?;
+        set_str(record, _contacts_name.last, self.last.as_deref())?;
+        set_str(record, _contacts_name.addition, self.addition.as_deref())?;
+        set_str(record, _contacts_name.prefix, self.prefix.as_deref())?;
+        set_str(record, _contacts_name.suffix, self.suffix.as_deref())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Number {
+    pub number: String,
+    /// A combination of [`number_type`] flags.
+    pub number_type: i32,
+    /// The name of a [`number_type::CUSTOM`] number.
+    pub label: Option<String>,
+}
+
+impl Number {
+    unsafe fn read(record: contacts_record_h) -> Number {
+        Number {
+            number: get_str(record, _contacts_number.number).unwrap_or_default(),
+            number_type: get_int(record, _contacts_number.type_),
+            label: get_str(record, _contacts_number.label),
+        }
+    }
+
+    unsafe fn write(&self, record: contacts_record_h) -> Result<()> {
+        set_str(record, _contacts_number.number, Some(&self.number))?;
+        check(contacts_record_set_int(
+            record,
+            _contacts_number.type_,
+            self.number_type,
+        ))?;
+        set_str(record, _contacts_number.label, self.label.as_deref())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Email {
+    pub email: String,
+    /// A combination of [`email_type`] flags.
+    pub email_type: i32,
+    /// The name of a [`email_type::CUSTOM`] address.
+    pub label: Option<String>,
+}
+
+impl Email {
+    unsafe fn read(record: contacts_record_h) -> Email {
+        Email {
+            email: get_str(record, _contacts_email.email).unwrap_or_default(),
+            email_type: get_int(record, _contacts_email.type_),
+            label: get_str(record, _contacts_email.label),
+        }
+    }
+
+    unsafe fn write(&self, record: contacts_record_h) -> Result<()> {
+        set_str(record, _contacts_email.email, Some(&self.email))?;
+        check(contacts_record_set_int(
+            record,
+            _contacts_email.type_,
+            self.email_type,
+        ))?;
+        set_str(record, _contacts_email.label, self.label.as_deref())
+    }
+}
+
+/// A contact of an address book account.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Contact {
+    /// 0 until inserted.
+    pub id: i32,
+    /// The person the contact is linked to, 0 until inserted.
+    pub person_id: i32,
+    /// Chosen by the service from the name, numbers and emails, ignored when saving.
+    pub display_name: Option<String>,
+    pub name: Option<Name>,
+    pub numbers: Vec<Number>,
+    pub emails: Vec<Email>,
+    pub is_favorite: bool,
+}
+
+impl Contact {
+    unsafe fn read(record: contacts_record_h) -> Contact {
+        Contact {
+            id: get_int(record, _contacts_contact.id),
+            person_id: get_int(record, _contacts_contact.person_id),
+            display_name: get_str(record, _contacts_contact.display_name),
+            name: read_children(record, _contacts_contact.name, Name::read)
+                .into_iter()
+                .next(),
+            numbers: read_children(record, _contacts_contact.number, Number::read),
+            emails: read_children(record, _contacts_contact.email, Email::read),
+            is_favorite: get_bool(record, _contacts_contact.is_favorite),
+        }
+    }
+
+    /// Writes the fields into `record`, replacing its names, numbers and emails.
+    unsafe fn write(&self, record: contacts_record_h) -> Result<()> {
+        check(contacts_record_set_bool(
+            record,
+            _contacts_contact.is_favorite,
+            self.is_favorite,
+        ))?;
+
+        let names = self.name.as_ref().map_or(&[][..], slice::from_ref);
+        replace_children(
+            record,
+            _contacts_contact.name,
+            _contacts_name._uri,
+            names,
+            Name::write,
+        )?;
+        replace_children(
+            record,
+            _contacts_contact.number,
+            _contacts_number._uri,
+            &self.numbers,
+            Number::write,
+        )?;
+        replace_children(
+            record,
+            _contacts_contact.email,
+            _contacts_email._uri,
+            &self.emails,
+            Email::write,
+        )
+    }
+}
+
+/// The contacts of the same person, linked together.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Person {
+    pub id: i32,
+    pub display_name: Option<String>,
+    /// The contact whose name is displayed.
+    pub display_contact_id: i32,
+    pub image_thumbnail_path: Option<String>,
+    pub has_phone_number: bool,
+    pub has_email: bool,
+    /// The only field saved by [`update_person`].
+    pub is_favorite: bool,
+}
+
+impl Person {
+    unsafe fn read(record: contacts_record_h) -> Person {
+        Person {
+            id: get_int(record, _contacts_person.id),
+            display_name: get_str(record, _contacts_person.display_name),
+            display_contact_id: get_int(record, _contacts_person.display_contact_id),
+            image_thumbnail_path: get_str(record, _contacts_person.image_thumbnail_path),
+            has_phone_number: get_bool(record, _contacts_person.has_phonenumber),
+            has_email: get_bool(record, _contacts_person.has_email),
+            is_favorite: get_bool(record, _contacts_person.is_favorite),
+        }
+    }
+}
+
+/// Inserts a new contact in the phone account, returning its id.
+pub fn insert_contact(contact: &Contact) -> Result<i32> {
+    let record = RawRecord::new(unsafe { _contacts_contact._uri })?;
+    unsafe { contact.write(record.handle) }?;
+
+    let mut id = 0;
+    check(unsafe { contacts_db_insert_record(record.handle, &mut id) })?;
+
+    Ok(id)
+}
+
+pub fn get_contact(id: i32) -> Result<Contact> {
+    let record = RawRecord::get(unsafe { _contacts_contact._uri }, id)?;
+
+    Ok(unsafe { Contact::read(record.handle) })
+}
+
+/// Saves the changes to an inserted contact.
+pub fn update_contact(contact: &Contact) -> Result<()> {
+    let record = RawRecord::get(unsafe { _contacts_contact._uri }, contact.id)?;
+    unsafe { contact.write(record.handle) }?;
+
+    check(unsafe { contacts_db_update_record(record.handle) })
+}
+
+pub fn delete_contact(id: i32) -> Result<()> {
+    check(unsafe { contacts_db_delete_record(_contacts_contact._uri, id) })
+}
+
+/// Up to `limit` contacts from `offset`, all of them if `limit` is 0.
+pub fn all_contacts(offset: i32, limit: i32) -> Result<Vec<Contact>> {
+    let mut list = null_mut();
+    check(unsafe {
+        contacts_db_get_all_records(_contacts_contact._uri, offset, limit, &mut list)
+    })?;
+
+    unsafe { take_list(list, Contact::read) }
+}
+
+pub fn get_person(id: i32) -> Result<Person> {
+    let record = RawRecord::get(unsafe { _contacts_person._uri }, id)?;
+
+    Ok(unsafe { Person::read(record.handle) })
+}
+
+/// Saves whether an existing person is a favorite.
+pub fn update_person(person: &Person) -> Result<()> {
+    let record = RawRecord::get(unsafe { _contacts_person._uri }, person.id)?;
+
+    check(unsafe {
+        contacts_record_set_bool(
+            record.handle,
+            _contacts_person.is_favorite,
+            person.is_favorite,
+        )
+    })?;
+    check(unsafe { contacts_db_update_record(record.handle) })
+}
+
+/// Deletes a person along with all its contacts.
+pub fn delete_person(id: i32) -> Result<()> {
+    check(unsafe { contacts_db_delete_record(_contacts_person._uri, id) })
+}
+
+/// Up to `limit` persons from `offset`, all of them if `limit` is 0.
+pub fn all_persons(offset: i32, limit: i32) -> Result<Vec<Person>> {
+    let mut list = null_mut();
+    check(unsafe { contacts_db_get_all_records(_contacts_person._uri, offset, limit, &mut list) })?;
+
+    unsafe { take_list(list, Person::read) }
+}
+
+/// The persons whose names, numbers, emails or other fields contain `keyword`.
+pub fn search_persons(keyword: &str, offset: i32, limit: i32) -> Result<Vec<Person>> {
+    let keyword = cstring(keyword)?;
+    let mut list = null_mut();
+
+    check(unsafe {
+        contacts_db_search_records(
+            _contacts_person._uri,
+            keyword.as_ptr(),
+            offset,
+            limit,
+            &mut list,
+        )
+    })?;
+
+    unsafe { take_list(list, Person::read) }
+}
+
+/// A record owned by this value, destroyed along with its children.
+struct RawRecord {
+    handle: contacts_record_h,
+}
+
+impl RawRecord {
+    fn new(view_uri: *const c_char) -> Result<RawRecord> {
+        let mut handle = null_mut();
+        check(unsafe { contacts_record_create(view_uri, &mut handle) })?;
+
+        Ok(Self { handle })
+    }
+
+    fn get(view_uri: *const c_char, id: i32) -> Result<RawRecord> {
+        let mut handle = null_mut();
+        check(unsafe { contacts_db_get_record(view_uri, id, &mut handle) })?;
+
+        Ok(Self { handle })
+    }
+
+    fn into_raw(self) -> contacts_record_h {
+        let handle = self.handle;
+        std::mem::forget(self);
+
+        handle
+    }
+}
+
+impl Drop for RawRecord {
+    fn drop(&mut self) {
+        unsafe {
+            contacts_record_destroy(self.handle, true);
+        }
+    }
+}
+
+unsafe fn get_str(record: contacts_record_h, property: c_uint) -> Option<String> {
+    // points inside the record, it must not be freed
+    let mut value = null_mut();
+    check(contacts_record_get_str_p(record, property, &mut value)).ok()?;
+
+    borrow_str(value).map(|value| value.into_owned())
+}
+
+unsafe fn set_str(record: contacts_record_h, property: c_uint, value: Option<&str>) -> Result<()> {
+    let value = value.map(cstring).transpose()?;
+
+    check(contacts_record_set_str(
+        record,
+        property,
+        value.as_ref().map_or(null(), |value| value.as_ptr()),
+    ))
+}
+
+unsafe fn get_int(record: contacts_record_h, property: c_uint) -> i32 {
+    // left as is if the property is unset
+    let mut value = 0;
+    contacts_record_get_int(record, property, &mut value);
+
+    value
+}
+
+unsafe fn get_bool(record: contacts_record_h, property: c_uint) -> bool {
+    let mut value = false;
+    contacts_record_get_bool(record, property, &mut value);
+
+    value
+}
+
+unsafe fn read_children<T>(
+    record: contacts_record_h,
+    property: c_uint,
+    read: unsafe fn(contacts_record_h) -> T,
+) -> Vec<T> {
+    let mut count = 0;
+    if check(contacts_record_get_child_record_count(
+        record, property, &mut count,
+    ))
+    .is_err()
+    {
+        return Vec::new();
+    }
+
+    (0..count)
+        .filter_map(|index| {
+            let mut child = null_mut();
+            check(contacts_record_get_child_record_at_p(
+                record, property, index, &mut child,
+            ))
+            .ok()?;
+
+            Some(read(child))
+        })
+        .collect()
+}
+
+/// Removes the children of `record` at `property`, then adds a new `view_uri` record for
+/// each of `children`.
+unsafe fn replace_children<T>(
+    record: contacts_record_h,
+    property: c_uint,
+    view_uri: *const c_char,
+    children: &[T],
+    write: unsafe fn(&T, contacts_record_h) -> Result<()>,
+) -> Result<()> {
+    let mut count = 0;
+    check(contacts_record_get_child_record_count(
+        record, property, &mut count,
+    ))?;
+
+    // removing shifts the next children down
+    for _ in 0..count {
+        let mut child = null_mut();
+        check(contacts_record_get_child_record_at_p(
+            record, property, 0, &mut child,
+        ))?;
+        check(contacts_record_remove_child_record(record, property, child))?;
+        contacts_record_destroy(child, true);
+    }
+
+    for value in children {
+        let child = RawRecord::new(view_uri)?;
+        write(value, child.handle)?;
+
+        // the parent takes ownership of its children
+        check(contacts_record_add_child_record(
+            record,
+            property,
+            child.handle,
+        ))?;
+        child.into_raw();
+    }
+
+    Ok(())
+}
+
+/// Reads then destroys a list of records.
+unsafe fn take_list<T>(
+    list: contacts_list_h,
+    read: unsafe fn(contacts_record_h) -> T,
+) -> Result<Vec<T>> {
+    let mut count = 0;
+    let result = check(contacts_list_get_count(list, &mut count));
+    let mut values = Vec::new();
+
+    if result.is_ok() {
+        for _ in 0..count {
+            let mut record = null_mut();
+            if check(contacts_list_get_current_record_p(list, &mut record)).is_err() {
+                break;
+            }
+
+            values.push(read(record));
+            contacts_list_next(list);
+        }
+    }
+
+    contacts_list_destroy(list, true);
+
+    result.map(|_| values)
+}
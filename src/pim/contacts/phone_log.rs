@@ -0,0 +1,241 @@
+use super::{get_int, get_str, take_list};
+use crate::error::{check, Result};
+use crate::rutin::cstring;
+use rutin_tizen_sys::{
+    _contacts_phone_log, contacts_db_delete_record, contacts_db_get_records_with_query,
+    contacts_filter_add_int, contacts_filter_add_operator, contacts_filter_add_str,
+    contacts_filter_create, contacts_filter_destroy, contacts_filter_h,
+    contacts_filter_operator_e_CONTACTS_FILTER_OPERATOR_AND,
+    contacts_match_int_flag_e_CONTACTS_MATCH_GREATER_THAN,
+    contacts_match_int_flag_e_CONTACTS_MATCH_LESS_THAN_OR_EQUAL,
+    contacts_match_str_flag_e_CONTACTS_MATCH_EXACTLY, contacts_phone_log_delete,
+    contacts_phone_log_delete_e_CONTACTS_PHONE_LOG_DELETE_BY_ADDRESS, contacts_phone_log_type_e,
+    contacts_phone_log_type_e_CONTACTS_PLOG_TYPE_NONE,
+    contacts_phone_log_type_e_CONTACTS_PLOG_TYPE_VIDEO_BLOCKED,
+    contacts_phone_log_type_e_CONTACTS_PLOG_TYPE_VIDEO_INCOMING,
+    contacts_phone_log_type_e_CONTACTS_PLOG_TYPE_VIDEO_INCOMING_SEEN,
+    contacts_phone_log_type_e_CONTACTS_PLOG_TYPE_VIDEO_INCOMING_UNSEEN,
+    contacts_phone_log_type_e_CONTACTS_PLOG_TYPE_VIDEO_OUTGOING,
+    contacts_phone_log_type_e_CONTACTS_PLOG_TYPE_VIDEO_REJECT,
+    contacts_phone_log_type_e_CONTACTS_PLOG_TYPE_VOICE_BLOCKED,
+    contacts_phone_log_type_e_CONTACTS_PLOG_TYPE_VOICE_INCOMING,
+    contacts_phone_log_type_e_CONTACTS_PLOG_TYPE_VOICE_INCOMING_SEEN,
+    contacts_phone_log_type_e_CONTACTS_PLOG_TYPE_VOICE_INCOMING_UNSEEN,
+    contacts_phone_log_type_e_CONTACTS_PLOG_TYPE_VOICE_OUTGOING,
+    contacts_phone_log_type_e_CONTACTS_PLOG_TYPE_VOICE_REJECT, contacts_query_create,
+    contacts_query_destroy, contacts_query_h, contacts_query_set_filter, contacts_query_set_sort,
+    contacts_record_h,
+};
+use std::os::raw::{c_char, c_int};
+use std::ptr::null_mut;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CallMedia {
+    Voice,
+    Video,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CallDirection {
+    Incoming,
+    Outgoing,
+    /// A missed call, which the user has not seen yet.
+    Missed,
+    MissedSeen,
+    Rejected,
+    Blocked,
+}
+
+/// A call in the history, which requires the `http://tizen.org/privilege/callhistory.read`
+/// privilege, and `http://tizen.org/privilege/callhistory.write` to delete from it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Call {
+    pub id: i32,
+    /// The person matching the number, 0 if none does.
+    pub person_id: i32,
+    /// The number of the other party, unset for hidden numbers.
+    pub address: Option<String>,
+    /// When the call started, in seconds since the epoch.
+    pub time: i64,
+    pub media: CallMedia,
+    pub direction: CallDirection,
+    /// In seconds.
+    pub duration: i32,
+    pub sim_slot: i32,
+}
+
+impl Call {
+    unsafe fn read(record: contacts_record_h) -> Call {
+        let log = &_contacts_phone_log;
+
+        let (media, direction) = match get_int(record, log.log_type) as contacts_phone_log_type_e {
+            contacts_phone_log_type_e_CONTACTS_PLOG_TYPE_VOICE_INCOMING => {
+                (CallMedia::Voice, CallDirection::Incoming)
+            }
+            contacts_phone_log_type_e_CONTACTS_PLOG_TYPE_VOICE_OUTGOING => {
+                (CallMedia::Voice, CallDirection::Outgoing)
+            }
+            contacts_phone_log_type_e_CONTACTS_PLOG_TYPE_VIDEO_INCOMING => {
+                (CallMedia::Video, CallDirection::Incoming)
+            }
+            contacts_phone_log_type_e_CONTACTS_PLOG_TYPE_VIDEO_OUTGOING => {
+                (CallMedia::Video, CallDirection::Outgoing)
+            }
+            contacts_phone_log_type_e_CONTACTS_PLOG_TYPE_VOICE_INCOMING_UNSEEN => {
+                (CallMedia::Voice, CallDirection::Missed)
+            }
+            contacts_phone_log_type_e_CONTACTS_PLOG_TYPE_VIDEO_INCOMING_UNSEEN => {
+                (CallMedia::Video, CallDirection::Missed)
+            }
+            contacts_phone_log_type_e_CONTACTS_PLOG_TYPE_VOICE_INCOMING_SEEN => {
+                (CallMedia::Voice, CallDirection::MissedSeen)
+            }
+            contacts_phone_log_type_e_CONTACTS_PLOG_TYPE_VIDEO_INCOMING_SEEN => {
+                (CallMedia::Video, CallDirection::MissedSeen)
+            }
+            contacts_phone_log_type_e_CONTACTS_PLOG_TYPE_VOICE_REJECT => {
+                (CallMedia::Voice, CallDirection::Rejected)
+            }
+            contacts_phone_log_type_e_CONTACTS_PLOG_TYPE_VIDEO_REJECT => {
+                (CallMedia::Video, CallDirection::Rejected)
+            }
+            contacts_phone_log_type_e_CONTACTS_PLOG_TYPE_VIDEO_BLOCKED => {
+                (CallMedia::Video, CallDirection::Blocked)
+            }
+            contacts_phone_log_type_e_CONTACTS_PLOG_TYPE_VOICE_BLOCKED => {
+                (CallMedia::Voice, CallDirection::Blocked)
+            }
+            // messages, which are filtered out
+            _ => (CallMedia::Voice, CallDirection::Incoming),
+        };
+
+        Call {
+            id: get_int(record, log.id),
+            person_id: get_int(record, log.person_id),
+            address: get_str(record, log.address),
+            time: i64::from(get_int(record, log.log_time)),
+            media,
+            direction,
+            duration: get_int(record, log.extra_data1),
+            sim_slot: get_int(record, log.sim_slot_no),
+        }
+    }
+}
+
+/// Up to `limit` calls from `offset`, the most recent first, all of them if `limit` is 0.
+/// Only the calls with `address` if set.
+pub fn recent_calls(address: Option<&str>, offset: i32, limit: i32) -> Result<Vec<Call>> {
+    let log = unsafe { &_contacts_phone_log };
+    let address = address.map(cstring).transpose()?;
+
+    // the log also holds messages, after the call types
+    let filter = RawFilter::new(log._uri)?;
+    check(unsafe {
+        contacts_filter_add_int(
+            filter.handle,
+            log.log_type,
+            contacts_match_int_flag_e_CONTACTS_MATCH_GREATER_THAN,
+            contacts_phone_log_type_e_CONTACTS_PLOG_TYPE_NONE as c_int,
+        )
+    })?;
+    filter.and()?;
+    check(unsafe {
+        contacts_filter_add_int(
+            filter.handle,
+            log.log_type,
+            contacts_match_int_flag_e_CONTACTS_MATCH_LESS_THAN_OR_EQUAL,
+            contacts_phone_log_type_e_CONTACTS_PLOG_TYPE_VIDEO_BLOCKED as c_int,
+        )
+    })?;
+
+    if let Some(address) = &address {
+        filter.and()?;
+        check(unsafe {
+            contacts_filter_add_str(
+                filter.handle,
+                log.address,
+                contacts_match_str_flag_e_CONTACTS_MATCH_EXACTLY,
+                address.as_ptr(),
+            )
+        })?;
+    }
+
+    let query = RawQuery::new(log._uri)?;
+    check(unsafe { contacts_query_set_filter(query.handle, filter.handle) })?;
+    check(unsafe { contacts_query_set_sort(query.handle, log.log_time, false) })?;
+
+    let mut list = null_mut();
+    check(unsafe { contacts_db_get_records_with_query(query.handle, offset, limit, &mut list) })?;
+
+    unsafe { take_list(list, Call::read) }
+}
+
+pub fn delete_call(id: i32) -> Result<()> {
+    check(unsafe { contacts_db_delete_record(_contacts_phone_log._uri, id) })
+}
+
+/// Deletes all the calls and messages logged with `address`.
+pub fn delete_calls_with(address: &str) -> Result<()> {
+    let address = cstring(address)?;
+
+    check(unsafe {
+        contacts_phone_log_delete(
+            contacts_phone_log_delete_e_CONTACTS_PHONE_LOG_DELETE_BY_ADDRESS,
+            address.as_ptr(),
+        )
+    })
+}
+
+struct RawFilter {
+    handle: contacts_filter_h,
+}
+
+impl RawFilter {
+    fn new(view_uri: *const c_char) -> Result<RawFilter> {
+        let mut handle = null_mut();
+        check(unsafe { contacts_filter_create(view_uri, &mut handle) })?;
+
+        Ok(Self { handle })
+    }
+
+    fn and(&self) -> Result<()> {
+        check(unsafe {
+            contacts_filter_add_operator(
+                self.handle,
+                contacts_filter_operator_e_CONTACTS_FILTER_OPERATOR_AND,
+            )
+        })
+    }
+}
+
+impl Drop for RawFilter {
+    fn drop(&mut self) {
+        unsafe {
+            contacts_filter_destroy(self.handle);
+        }
+    }
+}
+
+struct RawQuery {
+    handle: contacts_query_h,
+}
+
+impl RawQuery {
+    fn new(view_uri: *const c_char) -> Result<RawQuery> {
+        let mut handle = null_mut();
+        check(unsafe { contacts_query_create(view_uri, &mut handle) })?;
+
+        Ok(Self { handle })
+    }
+}
+
+impl Drop for RawQuery {
+    fn drop(&mut self) {
+        unsafe {
+            contacts_query_destroy(self.handle);
+        }
+    }
+}
@@ -0,0 +1,44 @@
+use super::{take_list, Contact, RawRecord};
+use crate::error::{check, Result};
+use crate::rutin::{cstring, take_string};
+use rutin_tizen_sys::{
+    _contacts_contact, _contacts_person, contacts_record_h, contacts_vcard_make_from_contact,
+    contacts_vcard_make_from_person, contacts_vcard_parse_to_contacts,
+};
+use std::os::raw::{c_char, c_int};
+use std::ptr::null_mut;
+
+/// Parses the contacts of one or more vCards, which are not inserted.
+pub fn parse_vcard(vcard: &str) -> Result<Vec<Contact>> {
+    let vcard = cstring(vcard)?;
+    let mut list = null_mut();
+
+    check(unsafe { contacts_vcard_parse_to_contacts(vcard.as_ptr(), &mut list) })?;
+
+    unsafe { take_list(list, Contact::read) }
+}
+
+/// Makes a vCard from the fields of `contact`.
+pub fn contact_to_vcard(contact: &Contact) -> Result<String> {
+    let record = RawRecord::new(unsafe { _contacts_contact._uri })?;
+    unsafe { contact.write(record.handle) }?;
+
+    make_vcard(&record, contacts_vcard_make_from_contact)
+}
+
+/// Makes a vCard merging all the contacts of a person.
+pub fn person_to_vcard(person_id: i32) -> Result<String> {
+    let record = RawRecord::get(unsafe { _contacts_person._uri }, person_id)?;
+
+    make_vcard(&record, contacts_vcard_make_from_person)
+}
+
+fn make_vcard(
+    record: &RawRecord,
+    make: unsafe extern "C" fn(contacts_record_h, *mut *mut c_char) -> c_int,
+) -> Result<String> {
+    let mut vcard = null_mut();
+    check(unsafe { make(record.handle, &mut vcard) })?;
+
+    Ok(unsafe { take_string(vcard) }.unwrap_or_default())
+}
@@ -1,6 +1,19 @@
+// the simulator only builds some of the wrappers using these
+#![cfg_attr(not(feature = "native"), allow(dead_code))]
+
+use crate::error::{Error, Result};
 use crate::system::dlog;
 use crate::system::dlog::Priority;
+use std::borrow::Cow;
+use std::ffi::{CStr, CString};
+use std::os::raw::{c_char, c_void};
+#[cfg(feature = "native")]
+use std::os::unix::ffi::OsStrExt;
 use std::panic;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+#[cfg(feature = "native")]
+use std::path::Path;
+use std::pin::Pin;
 
 pub const LOG_TAG: &str = "RUTIN/LIB";
 
@@ -29,3 +42,76 @@ pub fn set_panic_dlog_hook() {
         }
     }));
 }
+
+/// Sends the `tracing` events of the whole app to dlog under [`LOG_TAG`], including the
+/// failed native calls of this crate.
+///
+/// Each failure is a debug event with the error code, its message and the source location
+/// of the wrapper. The wrappers have no spans and the arguments are not recorded.
+#[cfg(feature = "tracing")]
+pub fn set_tracing_dlog_subscriber() -> Result<(), tracing::subscriber::SetGlobalDefaultError> {
+    use tracing_subscriber::layer::SubscriberExt;
+
+    let subscriber = tracing_subscriber::registry().with(dlog::DlogLayer::new(LOG_TAG));
+    tracing::subscriber::set_global_default(subscriber)
+}
+
+/// Runs a Rust callback invoked from C, aborting the process if it panics,
+/// since unwinding across the FFI boundary is undefined behavior.
+pub(crate) fn abort_on_panic<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(r) => r,
+        Err(e) => {
+            match e.downcast_ref::<&'static str>() {
+                Some(s) => rutin_debug(&format!("panic: {:?}", s)),
+                None => rutin_debug(&format!("panic: unknown {:?}", e)),
+            };
+            std::process::abort();
+        }
+    }
+}
+
+/// Copies a string allocated by a native call and frees it.
+pub(crate) unsafe fn take_string(ptr: *mut c_char) -> Option<String> {
+    if ptr.is_null() {
+        return None;
+    }
+
+    let s = CStr::from_ptr(ptr).to_string_lossy().into_owned();
+    libc::free(ptr as *mut c_void);
+
+    Some(s)
+}
+
+/// Converts a string argument of a native call, failing on interior nul bytes.
+pub(crate) fn cstring(value: &str) -> Result<CString> {
+    CString::new(value).map_err(|_| Error::InvalidParameter)
+}
+
+/// Converts a path argument of a native call as its raw bytes, which needn't be UTF-8.
+#[cfg(feature = "native")]
+pub(crate) fn path_cstring(path: &Path) -> Result<CString> {
+    CString::new(path.as_os_str().as_bytes()).map_err(|_| Error::InvalidParameter)
+}
+
+/// Pins a boxed callback, returning it along with the pointer to pass as
+/// `user_data`, which stays valid for as long as the returned box lives.
+pub(crate) fn pin_callback<F: ?Sized>(callback_fn: Box<F>) -> (Pin<Box<Box<F>>>, *mut c_void) {
+    let mut callback_fn = Box::pin(callback_fn);
+    let data = unsafe { Pin::get_unchecked_mut(Pin::as_mut(&mut callback_fn)) } as *mut Box<F>
+        as *mut c_void;
+
+    (callback_fn, data)
+}
+
+/// Borrows a string passed to a native callback, `None` if null or empty.
+pub(crate) unsafe fn borrow_str<'s>(ptr: *const c_char) -> Option<Cow<'s, str>> {
+    if ptr.is_null() {
+        None
+    } else {
+        Some(CStr::from_ptr(ptr).to_string_lossy()).filter(|s| !s.is_empty())
+    }
+}
@@ -0,0 +1,261 @@
+//! The Device Policy Manager, reading the policies an administrator enforces on a managed
+//! device, so the apps can hide the features it restricts.
+
+use crate::error::{check, last_error, Result};
+use crate::rutin::{abort_on_panic, borrow_str, cstring, pin_callback};
+use rutin_tizen_sys::{
+    device_policy_manager_h, dpm_add_policy_changed_cb, dpm_manager_create, dpm_manager_destroy,
+    dpm_password_get_expires, dpm_password_get_history,
+    dpm_password_get_max_inactivity_time_device_lock,
+    dpm_password_get_maximum_character_occurrences,
+    dpm_password_get_maximum_failed_attempts_for_wipe,
+    dpm_password_get_maximum_numeric_sequence_length, dpm_password_get_min_complex_chars,
+    dpm_password_get_minimum_length, dpm_password_get_quality, dpm_password_quality_e,
+    dpm_password_quality_e_DPM_PASSWORD_QUALITY_ALPHABETIC,
+    dpm_password_quality_e_DPM_PASSWORD_QUALITY_ALPHANUMERIC,
+    dpm_password_quality_e_DPM_PASSWORD_QUALITY_NUMERIC,
+    dpm_password_quality_e_DPM_PASSWORD_QUALITY_SIMPLE_PASSWORD,
+    dpm_password_quality_e_DPM_PASSWORD_QUALITY_SOMETHING,
+    dpm_password_quality_e_DPM_PASSWORD_QUALITY_UNSPECIFIED, dpm_remove_policy_changed_cb,
+    dpm_restriction_get_bluetooth_desktop_connectivity_state,
+    dpm_restriction_get_bluetooth_mode_change_state, dpm_restriction_get_bluetooth_pairing_state,
+    dpm_restriction_get_bluetooth_tethering_state, dpm_restriction_get_browser_state,
+    dpm_restriction_get_camera_state, dpm_restriction_get_clipboard_state,
+    dpm_restriction_get_external_storage_state, dpm_restriction_get_location_state,
+    dpm_restriction_get_microphone_state, dpm_restriction_get_popimap_email_state,
+    dpm_restriction_get_usb_debugging_state, dpm_restriction_get_usb_tethering_state,
+    dpm_restriction_get_wifi_hotspot_state, dpm_restriction_get_wifi_state,
+};
+use std::os::raw::{c_char, c_int, c_void};
+use std::pin::Pin;
+
+/// The features an administrator can disallow.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Restriction {
+    Camera,
+    Microphone,
+    Location,
+    ExternalStorage,
+    Clipboard,
+    UsbDebugging,
+    UsbTethering,
+    Wifi,
+    WifiHotspot,
+    /// Turning Bluetooth on or off.
+    BluetoothModeChange,
+    BluetoothTethering,
+    BluetoothPairing,
+    BluetoothDesktopConnectivity,
+    /// The POP and IMAP email accounts.
+    PopImapEmail,
+    Browser,
+}
+
+impl Restriction {
+    /// The name of the policy, to pass to [`DevicePolicyManager::add_policy_changed_cb`].
+    pub fn policy_name(self) -> &'static str {
+        match self {
+            Restriction::Camera => "camera",
+            Restriction::Microphone => "microphone",
+            Restriction::Location => "location",
+            Restriction::ExternalStorage => "external-storage",
+            Restriction::Clipboard => "clipboard",
+            Restriction::UsbDebugging => "usb-debugging",
+            Restriction::UsbTethering => "usb-tethering",
+            Restriction::Wifi => "wifi",
+            Restriction::WifiHotspot => "wifi-hotspot",
+            Restriction::BluetoothModeChange => "bluetooth",
+            Restriction::BluetoothTethering => "bluetooth-tethering",
+            Restriction::BluetoothPairing => "bluetooth-pairing",
+            Restriction::BluetoothDesktopConnectivity => "bluetooth-desktop-connectivity",
+            Restriction::PopImapEmail => "popimap-email",
+            Restriction::Browser => "browser",
+        }
+    }
+
+    fn getter(self) -> IntGetter {
+        match self {
+            Restriction::Camera => dpm_restriction_get_camera_state,
+            Restriction::Microphone => dpm_restriction_get_microphone_state,
+            Restriction::Location => dpm_restriction_get_location_state,
+            Restriction::ExternalStorage => dpm_restriction_get_external_storage_state,
+            Restriction::Clipboard => dpm_restriction_get_clipboard_state,
+            Restriction::UsbDebugging => dpm_restriction_get_usb_debugging_state,
+            Restriction::UsbTethering => dpm_restriction_get_usb_tethering_state,
+            Restriction::Wifi => dpm_restriction_get_wifi_state,
+            Restriction::WifiHotspot => dpm_restriction_get_wifi_hotspot_state,
+            Restriction::BluetoothModeChange => dpm_restriction_get_bluetooth_mode_change_state,
+            Restriction::BluetoothTethering => dpm_restriction_get_bluetooth_tethering_state,
+            Restriction::BluetoothPairing => dpm_restriction_get_bluetooth_pairing_state,
+            Restriction::BluetoothDesktopConnectivity => {
+                dpm_restriction_get_bluetooth_desktop_connectivity_state
+            }
+            Restriction::PopImapEmail => dpm_restriction_get_popimap_email_state,
+            Restriction::Browser => dpm_restriction_get_browser_state,
+        }
+    }
+}
+
+/// The characters a password must contain.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PasswordQuality {
+    Unspecified,
+    /// A simple password, such as a PIN.
+    Simple,
+    /// Any password.
+    Something,
+    Numeric,
+    Alphabetic,
+    Alphanumeric,
+}
+
+impl From<dpm_password_quality_e> for PasswordQuality {
+    fn from(quality: dpm_password_quality_e) -> PasswordQuality {
+        match quality {
+            dpm_password_quality_e_DPM_PASSWORD_QUALITY_SIMPLE_PASSWORD => PasswordQuality::Simple,
+            dpm_password_quality_e_DPM_PASSWORD_QUALITY_SOMETHING => PasswordQuality::Something,
+            dpm_password_quality_e_DPM_PASSWORD_QUALITY_NUMERIC => PasswordQuality::Numeric,
+            dpm_password_quality_e_DPM_PASSWORD_QUALITY_ALPHABETIC => PasswordQuality::Alphabetic,
+            dpm_password_quality_e_DPM_PASSWORD_QUALITY_ALPHANUMERIC => {
+                PasswordQuality::Alphanumeric
+            }
+            _ => PasswordQuality::Unspecified,
+        }
+    }
+}
+
+/// The requirements for the password of the device, 0 means unrestricted.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PasswordPolicy {
+    pub quality: PasswordQuality,
+    pub minimum_length: i32,
+    /// The minimum number of non alphanumeric characters.
+    pub min_complex_chars: i32,
+    /// The failed attempts after which the device is wiped.
+    pub maximum_failed_attempts_for_wipe: i32,
+    /// The number of days after which the password expires.
+    pub expires: i32,
+    /// The number of previous passwords which cannot be reused.
+    pub history: i32,
+    pub maximum_character_occurrences: i32,
+    pub maximum_numeric_sequence_length: i32,
+    /// The inactivity after which the device is locked, in seconds.
+    pub max_inactivity_time_device_lock: i32,
+}
+
+type IntGetter = unsafe extern "C" fn(device_policy_manager_h, *mut c_int) -> c_int;
+
+pub struct DevicePolicyManager {
+    handle: device_policy_manager_h,
+}
+
+impl DevicePolicyManager {
+    pub fn new() -> Result<DevicePolicyManager> {
+        let handle = unsafe { dpm_manager_create() };
+
+        if handle.is_null() {
+            return Err(last_error());
+        }
+
+        Ok(Self { handle })
+    }
+
+    pub fn is_allowed(&self, restriction: Restriction) -> Result<bool> {
+        Ok(self.get_int(restriction.getter())? != 0)
+    }
+
+    pub fn get_password_policy(&self) -> Result<PasswordPolicy> {
+        let mut quality = dpm_password_quality_e_DPM_PASSWORD_QUALITY_UNSPECIFIED;
+        check(unsafe { dpm_password_get_quality(self.handle, &mut quality) })?;
+
+        Ok(PasswordPolicy {
+            quality: quality.into(),
+            minimum_length: self.get_int(dpm_password_get_minimum_length)?,
+            min_complex_chars: self.get_int(dpm_password_get_min_complex_chars)?,
+            maximum_failed_attempts_for_wipe: self
+                .get_int(dpm_password_get_maximum_failed_attempts_for_wipe)?,
+            expires: self.get_int(dpm_password_get_expires)?,
+            history: self.get_int(dpm_password_get_history)?,
+            maximum_character_occurrences: self
+                .get_int(dpm_password_get_maximum_character_occurrences)?,
+            maximum_numeric_sequence_length: self
+                .get_int(dpm_password_get_maximum_numeric_sequence_length)?,
+            max_inactivity_time_device_lock: self
+                .get_int(dpm_password_get_max_inactivity_time_device_lock)?,
+        })
+    }
+
+    /// Calls `callback_fn` with the new state of the policy `name`, such as `allowed` or
+    /// `disallowed`, until the returned listener is dropped.
+    pub fn add_policy_changed_cb<F>(&self, name: &str, callback_fn: F) -> Result<PolicyListener<'_>>
+    where
+        F: FnMut(&str),
+        F: 'static,
+    {
+        let name = cstring(name)?;
+        let (callback_fn, data) = pin_callback::<dyn FnMut(&str)>(Box::new(callback_fn));
+        let mut id = 0;
+
+        check(unsafe {
+            dpm_add_policy_changed_cb(
+                self.handle,
+                name.as_ptr(),
+                Some(policy_changed_handler),
+                data,
+                &mut id,
+            )
+        })?;
+
+        Ok(PolicyListener {
+            manager: self,
+            id,
+            _callback_fn: callback_fn,
+        })
+    }
+
+    fn get_int(&self, get: IntGetter) -> Result<i32> {
+        let mut value = 0;
+        check(unsafe { get(self.handle, &mut value) })?;
+
+        Ok(value)
+    }
+}
+
+impl Drop for DevicePolicyManager {
+    fn drop(&mut self) {
+        unsafe {
+            dpm_manager_destroy(self.handle);
+        }
+    }
+}
+
+type PolicyFn = Box<dyn FnMut(&str)>;
+
+/// Receives the changes to a policy until dropped.
+pub struct PolicyListener<'m> {
+    manager: &'m DevicePolicyManager,
+    id: c_int,
+    _callback_fn: Pin<Box<PolicyFn>>,
+}
+
+impl<'m> Drop for PolicyListener<'m> {
+    fn drop(&mut self) {
+        unsafe {
+            dpm_remove_policy_changed_cb(self.manager.handle, self.id);
+        }
+    }
+}
+
+extern "C" fn policy_changed_handler(
+    _name: *const c_char,
+    state: *const c_char,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut PolicyFn) };
+        closure(&unsafe { borrow_str(state) }.unwrap_or_default())
+    })
+}
@@ -0,0 +1,208 @@
+//! The FIDO UAF client, authenticating the user to a FIDO server with the authenticators of
+//! the device, on devices with the `http://tizen.org/feature/fido.uaf` feature.
+//!
+//! The UAF messages are the JSON documents exchanged with the server, which the app relays.
+
+use crate::error::{check, Result};
+use crate::rutin::{abort_on_panic, borrow_str, cstring, take_string};
+use rutin_tizen_sys::{
+    fido_auth_user_verify_type_e, fido_authenticator_get_aaid, fido_authenticator_get_description,
+    fido_authenticator_get_icon, fido_authenticator_get_is_second_factor_only,
+    fido_authenticator_get_scheme, fido_authenticator_get_title,
+    fido_authenticator_get_verification_method, fido_authenticator_h, fido_error_e,
+    fido_foreach_authenticator, fido_get_client_vendor, fido_get_client_version,
+    fido_uaf_get_response_message, fido_uaf_is_supported, fido_uaf_set_server_result,
+};
+use std::os::raw::{c_char, c_int, c_void};
+use std::ptr::{null, null_mut};
+
+/// Flags for [`Authenticator::verification_methods`].
+pub mod verification {
+    pub const PRESENCE: u32 = 0x01;
+    pub const FINGERPRINT: u32 = 0x02;
+    pub const PASSCODE: u32 = 0x04;
+    pub const VOICEPRINT: u32 = 0x08;
+    pub const FACEPRINT: u32 = 0x10;
+    pub const LOCATION: u32 = 0x20;
+    pub const EYEPRINT: u32 = 0x40;
+    pub const PATTERN: u32 = 0x80;
+    pub const HANDPRINT: u32 = 0x100;
+    pub const NONE: u32 = 0x200;
+    pub const ALL: u32 = 0x400;
+}
+
+/// The status codes of the UAF protocol, for [`set_server_result`].
+pub mod status_code {
+    pub const OK: i32 = 1200;
+    pub const ACCEPTED: i32 = 1202;
+    pub const BAD_REQUEST: i32 = 1400;
+    pub const UNAUTHORIZED: i32 = 1401;
+    pub const FORBIDDEN: i32 = 1403;
+    pub const NOT_FOUND: i32 = 1404;
+    pub const REQUEST_TIMEOUT: i32 = 1408;
+    pub const UNKNOWN_AAID: i32 = 1480;
+    pub const UNKNOWN_KEYID: i32 = 1481;
+    pub const CHANNEL_BINDING_REFUSED: i32 = 1490;
+    pub const REQUEST_INVALID: i32 = 1491;
+    pub const UNACCEPTABLE_AUTHENTICATOR: i32 = 1492;
+    pub const REVOKED_AUTHENTICATOR: i32 = 1493;
+    pub const UNACCEPTABLE_KEY: i32 = 1494;
+    pub const UNACCEPTABLE_ALGORITHM: i32 = 1495;
+    pub const UNACCEPTABLE_ATTESTATION: i32 = 1496;
+    pub const UNACCEPTABLE_CLIENT_CAPABILITIES: i32 = 1497;
+    pub const UNACCEPTABLE_CONTENT: i32 = 1498;
+    pub const INTERNAL_SERVER_ERROR: i32 = 1500;
+}
+
+/// An authenticator available on the device.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Authenticator {
+    pub title: Option<String>,
+    /// The Authenticator Attestation ID, identifying its model.
+    pub aaid: String,
+    pub description: Option<String>,
+    pub scheme: Option<String>,
+    /// A combination of the [`verification`] flags.
+    pub verification_methods: u32,
+    pub is_second_factor_only: bool,
+    /// A data URL of its icon.
+    pub icon: Option<String>,
+}
+
+type StringGetter = unsafe extern "C" fn(fido_authenticator_h, *mut *mut c_char) -> c_int;
+
+impl Authenticator {
+    unsafe fn from_raw(authenticator: fido_authenticator_h) -> Result<Authenticator> {
+        let string = |get: StringGetter| {
+            let mut value = null_mut();
+            check(get(authenticator, &mut value))?;
+            Ok(take_string(value))
+        };
+
+        let mut verification_methods: fido_auth_user_verify_type_e = 0;
+        check(fido_authenticator_get_verification_method(
+            authenticator,
+            &mut verification_methods,
+        ))?;
+
+        Ok(Self {
+            title: string(fido_authenticator_get_title)?,
+            aaid: string(fido_authenticator_get_aaid)?.unwrap_or_default(),
+            description: string(fido_authenticator_get_description)?,
+            scheme: string(fido_authenticator_get_scheme)?,
+            verification_methods,
+            is_second_factor_only: fido_authenticator_get_is_second_factor_only(authenticator),
+            icon: string(fido_authenticator_get_icon)?,
+        })
+    }
+}
+
+pub fn get_authenticators() -> Result<Vec<Authenticator>> {
+    let mut authenticators: Vec<Result<Authenticator>> = Vec::new();
+
+    check(unsafe {
+        fido_foreach_authenticator(
+            Some(authenticator_handler),
+            &mut authenticators as *mut Vec<Result<Authenticator>> as *mut c_void,
+        )
+    })?;
+
+    authenticators.into_iter().collect()
+}
+
+pub fn get_client_vendor() -> Result<String> {
+    let mut vendor = null_mut();
+    check(unsafe { fido_get_client_vendor(&mut vendor) })?;
+
+    Ok(unsafe { take_string(vendor) }.unwrap_or_default())
+}
+
+/// The major and minor version of the UAF client.
+pub fn get_client_version() -> Result<(i32, i32)> {
+    let mut major = 0;
+    let mut minor = 0;
+    check(unsafe { fido_get_client_version(&mut major, &mut minor) })?;
+
+    Ok((major, minor))
+}
+
+/// Whether a request of the server can be processed with the authenticators of the device.
+pub fn is_supported(uaf_request: &str) -> Result<bool> {
+    let uaf_request = cstring(uaf_request)?;
+
+    let mut supported = false;
+    check(unsafe { fido_uaf_is_supported(uaf_request.as_ptr(), &mut supported) })?;
+
+    Ok(supported)
+}
+
+type ResponseFn = dyn FnOnce(Result<String>);
+
+/// Processes a registration, authentication or deregistration request of the server,
+/// asking the user for verification, then calls `callback_fn` with the response to send
+/// back to the server.
+pub fn get_response_message<F>(
+    uaf_request: &str,
+    channel_binding: Option<&str>,
+    callback_fn: F,
+) -> Result<()>
+where
+    F: FnOnce(Result<String>),
+    F: 'static,
+{
+    let uaf_request = cstring(uaf_request)?;
+    let channel_binding = channel_binding.map(cstring).transpose()?;
+
+    let callback_fn: Box<ResponseFn> = Box::new(callback_fn);
+    let data = Box::into_raw(Box::new(callback_fn));
+
+    let ret = unsafe {
+        fido_uaf_get_response_message(
+            uaf_request.as_ptr(),
+            channel_binding
+                .as_ref()
+                .map_or(null(), |channel_binding| channel_binding.as_ptr()),
+            Some(response_handler),
+            data as *mut c_void,
+        )
+    };
+
+    if let Err(e) = check(ret) {
+        drop(unsafe { Box::from_raw(data) });
+        return Err(e);
+    }
+
+    Ok(())
+}
+
+/// Notifies the client of the answer of the server to a response, `response_code` being
+/// one of the [`status_code`] values.
+pub fn set_server_result(response_code: i32, uaf_response: &str) -> Result<()> {
+    let uaf_response = cstring(uaf_response)?;
+
+    check(unsafe { fido_uaf_set_server_result(response_code, uaf_response.as_ptr()) })
+}
+
+extern "C" fn authenticator_handler(authenticator: fido_authenticator_h, user_data: *mut c_void) {
+    abort_on_panic(|| {
+        let authenticators = unsafe { &mut *(user_data as *mut Vec<Result<Authenticator>>) };
+        authenticators.push(unsafe { Authenticator::from_raw(authenticator) });
+    })
+}
+
+extern "C" fn response_handler(
+    error: fido_error_e,
+    uaf_response: *const c_char,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let closure = unsafe { Box::from_raw(user_data as *mut Box<ResponseFn>) };
+
+        closure(check(error).map(|_| {
+            unsafe { borrow_str(uaf_response) }
+                .unwrap_or_default()
+                .into_owned()
+        }));
+    })
+}
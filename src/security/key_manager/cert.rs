@@ -0,0 +1,260 @@
+use super::{alias_cstring, password_cstring, password_ptr, Certificate, Key, Policy};
+use crate::error::{check, Result};
+use crate::rutin::path_cstring;
+use rutin_tizen_sys::{
+    ckmc_cert_free, ckmc_cert_list_all_free, ckmc_cert_list_s, ckmc_cert_s,
+    ckmc_get_cert_chain_with_trustedcert, ckmc_get_pkcs12, ckmc_key_s, ckmc_load_cert_from_file,
+    ckmc_ocsp_check, ckmc_ocsp_status_e, ckmc_ocsp_status_e_CKMC_OCSP_ERROR_INVALID_RESPONSE,
+    ckmc_ocsp_status_e_CKMC_OCSP_ERROR_INVALID_URL, ckmc_ocsp_status_e_CKMC_OCSP_ERROR_NET,
+    ckmc_ocsp_status_e_CKMC_OCSP_ERROR_REMOTE, ckmc_ocsp_status_e_CKMC_OCSP_ERROR_UNSUPPORTED,
+    ckmc_ocsp_status_e_CKMC_OCSP_STATUS_GOOD, ckmc_ocsp_status_e_CKMC_OCSP_STATUS_REVOKED,
+    ckmc_ocsp_status_e_CKMC_OCSP_STATUS_UNKNOWN, ckmc_pkcs12_free, ckmc_pkcs12_load, ckmc_pkcs12_s,
+    ckmc_save_pkcs12,
+};
+use std::iter;
+use std::path::Path;
+use std::ptr::{null, null_mut};
+
+/// A PKCS#12 bundle, as a private key with its certificate and their CA chain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Pkcs12 {
+    pub key: Option<Key>,
+    pub cert: Option<Certificate>,
+    pub ca_chain: Vec<Certificate>,
+}
+
+impl Pkcs12 {
+    unsafe fn from_raw(pkcs12: &ckmc_pkcs12_s) -> Result<Pkcs12> {
+        Ok(Pkcs12 {
+            key: if pkcs12.priv_key.is_null() {
+                None
+            } else {
+                Some(Key::from_raw(&*pkcs12.priv_key)?)
+            },
+            cert: if pkcs12.cert.is_null() {
+                None
+            } else {
+                Some(Certificate::from_raw(&*pkcs12.cert))
+            },
+            ca_chain: read_cert_list(pkcs12.ca_chain),
+        })
+    }
+}
+
+/// Borrowed native certificates, linked as a list.
+struct CertList {
+    _certs: Vec<ckmc_cert_s>,
+    nodes: Vec<ckmc_cert_list_s>,
+}
+
+impl CertList {
+    fn new(certs: &[Certificate]) -> CertList {
+        let mut raw_certs: Vec<ckmc_cert_s> = certs.iter().map(|cert| cert.raw()).collect();
+        let mut nodes: Vec<ckmc_cert_list_s> = raw_certs
+            .iter_mut()
+            .map(|cert| ckmc_cert_list_s {
+                cert,
+                next: null_mut(),
+            })
+            .collect();
+
+        // the nodes don't move once all are created
+        let next_nodes: Vec<*mut ckmc_cert_list_s> = nodes
+            .iter_mut()
+            .skip(1)
+            .map(|node| node as *mut ckmc_cert_list_s)
+            .chain(iter::once(null_mut()))
+            .collect();
+        for (node, next) in nodes.iter_mut().zip(next_nodes) {
+            node.next = next;
+        }
+
+        Self {
+            _certs: raw_certs,
+            nodes,
+        }
+    }
+
+    /// The first node, null for an empty list.
+    fn as_ptr(&self) -> *const ckmc_cert_list_s {
+        self.nodes
+            .first()
+            .map_or(null(), |node| node as *const ckmc_cert_list_s)
+    }
+}
+
+unsafe fn read_cert_list(list: *const ckmc_cert_list_s) -> Vec<Certificate> {
+    let mut certs = Vec::new();
+    let mut node = list;
+
+    while !node.is_null() {
+        if !(*node).cert.is_null() {
+            certs.push(Certificate::from_raw(&*(*node).cert));
+        }
+        node = (*node).next;
+    }
+
+    certs
+}
+
+/// Reads a PKCS#12 file, decrypted with `passphrase` if it has one.
+pub fn load_pkcs12(path: &Path, passphrase: Option<&str>) -> Result<Pkcs12> {
+    let path = path_cstring(path)?;
+    let passphrase = password_cstring(passphrase)?;
+
+    let mut pkcs12 = null_mut();
+    check(unsafe { ckmc_pkcs12_load(path.as_ptr(), password_ptr(&passphrase), &mut pkcs12) })?;
+
+    let bundle = unsafe { Pkcs12::from_raw(&*pkcs12) };
+    unsafe { ckmc_pkcs12_free(pkcs12) };
+
+    bundle
+}
+
+/// Saves the key and certificates of a bundle under the same alias, with a policy for
+/// the key and one for the certificates.
+pub fn save_pkcs12(
+    alias: &str,
+    pkcs12: &Pkcs12,
+    key_policy: &Policy,
+    cert_policy: &Policy,
+) -> Result<()> {
+    let alias = alias_cstring(alias)?;
+    let key_password = password_cstring(key_policy.password.as_deref())?;
+    let cert_password = password_cstring(cert_policy.password.as_deref())?;
+
+    let mut key: Option<ckmc_key_s> = pkcs12.key.as_ref().map(Key::raw);
+    let mut cert: Option<ckmc_cert_s> = pkcs12.cert.as_ref().map(Certificate::raw);
+    let ca_chain = CertList::new(&pkcs12.ca_chain);
+
+    let raw = ckmc_pkcs12_s {
+        priv_key: key
+            .as_mut()
+            .map_or(null_mut(), |key| key as *mut ckmc_key_s),
+        cert: cert
+            .as_mut()
+            .map_or(null_mut(), |cert| cert as *mut ckmc_cert_s),
+        ca_chain: ca_chain.as_ptr() as *mut ckmc_cert_list_s,
+    };
+
+    check(unsafe {
+        ckmc_save_pkcs12(
+            alias.as_ptr(),
+            &raw,
+            key_policy.raw(&key_password),
+            cert_policy.raw(&cert_password),
+        )
+    })
+}
+
+pub fn get_pkcs12(
+    alias: &str,
+    key_password: Option<&str>,
+    cert_password: Option<&str>,
+) -> Result<Pkcs12> {
+    let alias = alias_cstring(alias)?;
+    let key_password = password_cstring(key_password)?;
+    let cert_password = password_cstring(cert_password)?;
+
+    let mut pkcs12 = null_mut();
+    check(unsafe {
+        ckmc_get_pkcs12(
+            alias.as_ptr(),
+            password_ptr(&key_password),
+            password_ptr(&cert_password),
+            &mut pkcs12,
+        )
+    })?;
+
+    let bundle = unsafe { Pkcs12::from_raw(&*pkcs12) };
+    unsafe { ckmc_pkcs12_free(pkcs12) };
+
+    bundle
+}
+
+/// Reads a certificate file, in DER or PEM.
+pub fn load_cert(path: &Path) -> Result<Certificate> {
+    let path = path_cstring(path)?;
+
+    let mut cert = null_mut();
+    check(unsafe { ckmc_load_cert_from_file(path.as_ptr(), &mut cert) })?;
+
+    let certificate = unsafe { Certificate::from_raw(&*cert) };
+    unsafe { ckmc_cert_free(cert) };
+
+    Ok(certificate)
+}
+
+/// Builds and verifies the chain of `cert` up to a trusted root, returning it from `cert`
+/// to the root. `untrusted` holds the intermediate certificates, the roots are taken from
+/// `trusted` and if `use_system_store` from the system trust store.
+pub fn verify_chain(
+    cert: &Certificate,
+    untrusted: &[Certificate],
+    trusted: &[Certificate],
+    use_system_store: bool,
+) -> Result<Vec<Certificate>> {
+    let raw_cert = cert.raw();
+    let untrusted = CertList::new(untrusted);
+    let trusted = CertList::new(trusted);
+
+    let mut chain = null_mut();
+    check(unsafe {
+        ckmc_get_cert_chain_with_trustedcert(
+            &raw_cert,
+            untrusted.as_ptr(),
+            trusted.as_ptr(),
+            use_system_store,
+            &mut chain,
+        )
+    })?;
+
+    let certs = unsafe { read_cert_list(chain) };
+    unsafe { ckmc_cert_list_all_free(chain) };
+
+    Ok(certs)
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum OcspStatus {
+    Good,
+    Revoked,
+    /// The responder does not know the certificate.
+    Unknown,
+    /// The certificate has no OCSP responder.
+    Unsupported,
+    InvalidUrl,
+    InvalidResponse,
+    RemoteError,
+    NetworkError,
+    InternalError,
+}
+
+impl From<ckmc_ocsp_status_e> for OcspStatus {
+    fn from(status: ckmc_ocsp_status_e) -> OcspStatus {
+        match status {
+            ckmc_ocsp_status_e_CKMC_OCSP_STATUS_GOOD => OcspStatus::Good,
+            ckmc_ocsp_status_e_CKMC_OCSP_STATUS_REVOKED => OcspStatus::Revoked,
+            ckmc_ocsp_status_e_CKMC_OCSP_STATUS_UNKNOWN => OcspStatus::Unknown,
+            ckmc_ocsp_status_e_CKMC_OCSP_ERROR_UNSUPPORTED => OcspStatus::Unsupported,
+            ckmc_ocsp_status_e_CKMC_OCSP_ERROR_INVALID_URL => OcspStatus::InvalidUrl,
+            ckmc_ocsp_status_e_CKMC_OCSP_ERROR_INVALID_RESPONSE => OcspStatus::InvalidResponse,
+            ckmc_ocsp_status_e_CKMC_OCSP_ERROR_REMOTE => OcspStatus::RemoteError,
+            ckmc_ocsp_status_e_CKMC_OCSP_ERROR_NET => OcspStatus::NetworkError,
+            _ => OcspStatus::InternalError,
+        }
+    }
+}
+
+/// Asks the OCSP responders of a chain returned by [`verify_chain`] whether its
+/// certificates are revoked, blocking until they answer.
+pub fn ocsp_check(chain: &[Certificate]) -> Result<OcspStatus> {
+    let chain = CertList::new(chain);
+
+    let mut status = ckmc_ocsp_status_e_CKMC_OCSP_STATUS_UNKNOWN;
+    check(unsafe { ckmc_ocsp_check(chain.as_ptr(), &mut status) })?;
+
+    Ok(status.into())
+}
@@ -0,0 +1,339 @@
+use super::{alias_cstring, password_cstring, password_ptr, raw_buffer, take_buffer, Policy};
+use crate::error::{check, Error, Result};
+use rutin_tizen_sys::{
+    ckmc_algo_type_e, ckmc_algo_type_e_CKMC_ALGO_AES_CBC, ckmc_algo_type_e_CKMC_ALGO_AES_CFB,
+    ckmc_algo_type_e_CKMC_ALGO_AES_CTR, ckmc_algo_type_e_CKMC_ALGO_AES_GCM,
+    ckmc_algo_type_e_CKMC_ALGO_RSA_OAEP, ckmc_create_key_aes, ckmc_create_key_pair_ecdsa,
+    ckmc_create_key_pair_rsa, ckmc_create_signature, ckmc_decrypt_data, ckmc_ec_type_e,
+    ckmc_ec_type_e_CKMC_EC_PRIME192V1, ckmc_ec_type_e_CKMC_EC_PRIME256V1,
+    ckmc_ec_type_e_CKMC_EC_SECP384R1, ckmc_encrypt_data,
+    ckmc_error_e_CKMC_ERROR_VERIFICATION_FAILED, ckmc_generate_new_params, ckmc_hash_algo_e,
+    ckmc_hash_algo_e_CKMC_HASH_NONE, ckmc_hash_algo_e_CKMC_HASH_SHA1,
+    ckmc_hash_algo_e_CKMC_HASH_SHA256, ckmc_hash_algo_e_CKMC_HASH_SHA384,
+    ckmc_hash_algo_e_CKMC_HASH_SHA512, ckmc_param_list_free, ckmc_param_list_h,
+    ckmc_param_list_set_buffer, ckmc_param_list_set_integer, ckmc_param_name_e,
+    ckmc_param_name_e_CKMC_PARAM_ED_AAD, ckmc_param_name_e_CKMC_PARAM_ED_CTR_LEN,
+    ckmc_param_name_e_CKMC_PARAM_ED_IV, ckmc_param_name_e_CKMC_PARAM_ED_LABEL,
+    ckmc_param_name_e_CKMC_PARAM_ED_TAG_LEN, ckmc_raw_buffer_s, ckmc_rsa_padding_algo_e,
+    ckmc_rsa_padding_algo_e_CKMC_NONE_PADDING, ckmc_rsa_padding_algo_e_CKMC_PKCS1_PADDING,
+    ckmc_rsa_padding_algo_e_CKMC_X931_PADDING, ckmc_verify_signature,
+};
+use std::os::raw::{c_char, c_int};
+use std::ptr::null_mut;
+
+/// The curve of an ECDSA key.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EcType {
+    Prime192v1,
+    Prime256v1,
+    Secp384r1,
+}
+
+impl From<EcType> for ckmc_ec_type_e {
+    fn from(ec_type: EcType) -> ckmc_ec_type_e {
+        match ec_type {
+            EcType::Prime192v1 => ckmc_ec_type_e_CKMC_EC_PRIME192V1,
+            EcType::Prime256v1 => ckmc_ec_type_e_CKMC_EC_PRIME256V1,
+            EcType::Secp384r1 => ckmc_ec_type_e_CKMC_EC_SECP384R1,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HashAlgorithm {
+    /// Signs the message as is, which must then be short enough for the key.
+    None,
+    Sha1,
+    Sha256,
+    Sha384,
+    Sha512,
+}
+
+impl From<HashAlgorithm> for ckmc_hash_algo_e {
+    fn from(hash: HashAlgorithm) -> ckmc_hash_algo_e {
+        match hash {
+            HashAlgorithm::None => ckmc_hash_algo_e_CKMC_HASH_NONE,
+            HashAlgorithm::Sha1 => ckmc_hash_algo_e_CKMC_HASH_SHA1,
+            HashAlgorithm::Sha256 => ckmc_hash_algo_e_CKMC_HASH_SHA256,
+            HashAlgorithm::Sha384 => ckmc_hash_algo_e_CKMC_HASH_SHA384,
+            HashAlgorithm::Sha512 => ckmc_hash_algo_e_CKMC_HASH_SHA512,
+        }
+    }
+}
+
+/// The padding of RSA signatures, ignored for the other keys.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Padding {
+    None,
+    Pkcs1,
+    X931,
+}
+
+impl From<Padding> for ckmc_rsa_padding_algo_e {
+    fn from(padding: Padding) -> ckmc_rsa_padding_algo_e {
+        match padding {
+            Padding::None => ckmc_rsa_padding_algo_e_CKMC_NONE_PADDING,
+            Padding::Pkcs1 => ckmc_rsa_padding_algo_e_CKMC_PKCS1_PADDING,
+            Padding::X931 => ckmc_rsa_padding_algo_e_CKMC_X931_PADDING,
+        }
+    }
+}
+
+/// The algorithm used to encrypt or decrypt, along with its parameters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Cipher {
+    /// The initialization vector is 16 bytes long.
+    AesCbc {
+        iv: Vec<u8>,
+    },
+    AesCfb {
+        iv: Vec<u8>,
+    },
+    /// `counter_bits` of the 16 bytes initial counter block are incremented.
+    AesCtr {
+        iv: Vec<u8>,
+        counter_bits: u64,
+    },
+    /// The tag is appended to the encrypted data.
+    AesGcm {
+        iv: Vec<u8>,
+        aad: Option<Vec<u8>>,
+        tag_bits: u64,
+    },
+    RsaOaep {
+        label: Option<Vec<u8>>,
+    },
+}
+
+/// The parameters of a cipher, freed when dropped.
+struct ParamList {
+    handle: ckmc_param_list_h,
+}
+
+impl ParamList {
+    fn new(cipher: &Cipher) -> Result<ParamList> {
+        let algo: ckmc_algo_type_e = match cipher {
+            Cipher::AesCbc { .. } => ckmc_algo_type_e_CKMC_ALGO_AES_CBC,
+            Cipher::AesCfb { .. } => ckmc_algo_type_e_CKMC_ALGO_AES_CFB,
+            Cipher::AesCtr { .. } => ckmc_algo_type_e_CKMC_ALGO_AES_CTR,
+            Cipher::AesGcm { .. } => ckmc_algo_type_e_CKMC_ALGO_AES_GCM,
+            Cipher::RsaOaep { .. } => ckmc_algo_type_e_CKMC_ALGO_RSA_OAEP,
+        };
+
+        let mut handle = null_mut();
+        check(unsafe { ckmc_generate_new_params(algo, &mut handle) })?;
+        let params = Self { handle };
+
+        match cipher {
+            Cipher::AesCbc { iv } | Cipher::AesCfb { iv } => {
+                params.set_buffer(ckmc_param_name_e_CKMC_PARAM_ED_IV, iv)?;
+            }
+            Cipher::AesCtr { iv, counter_bits } => {
+                params.set_buffer(ckmc_param_name_e_CKMC_PARAM_ED_IV, iv)?;
+                params.set_integer(ckmc_param_name_e_CKMC_PARAM_ED_CTR_LEN, *counter_bits)?;
+            }
+            Cipher::AesGcm { iv, aad, tag_bits } => {
+                params.set_buffer(ckmc_param_name_e_CKMC_PARAM_ED_IV, iv)?;
+                if let Some(aad) = aad {
+                    params.set_buffer(ckmc_param_name_e_CKMC_PARAM_ED_AAD, aad)?;
+                }
+                params.set_integer(ckmc_param_name_e_CKMC_PARAM_ED_TAG_LEN, *tag_bits)?;
+            }
+            Cipher::RsaOaep { label } => {
+                if let Some(label) = label {
+                    params.set_buffer(ckmc_param_name_e_CKMC_PARAM_ED_LABEL, label)?;
+                }
+            }
+        }
+
+        Ok(params)
+    }
+
+    fn set_buffer(&self, name: ckmc_param_name_e, data: &[u8]) -> Result<()> {
+        // the value is copied into the list
+        let buffer = raw_buffer(data);
+        check(unsafe { ckmc_param_list_set_buffer(self.handle, name, &buffer) })
+    }
+
+    fn set_integer(&self, name: ckmc_param_name_e, value: u64) -> Result<()> {
+        check(unsafe { ckmc_param_list_set_integer(self.handle, name, value) })
+    }
+}
+
+impl Drop for ParamList {
+    fn drop(&mut self) {
+        unsafe {
+            ckmc_param_list_free(self.handle);
+        }
+    }
+}
+
+/// Creates and saves an RSA key pair of `bits` (1024, 2048 or 4096) in the store.
+pub fn create_rsa_key_pair(
+    bits: usize,
+    private_alias: &str,
+    public_alias: &str,
+    private_policy: &Policy,
+    public_policy: &Policy,
+) -> Result<()> {
+    let private_alias = alias_cstring(private_alias)?;
+    let public_alias = alias_cstring(public_alias)?;
+    let private_password = password_cstring(private_policy.password.as_deref())?;
+    let public_password = password_cstring(public_policy.password.as_deref())?;
+
+    check(unsafe {
+        ckmc_create_key_pair_rsa(
+            bits,
+            private_alias.as_ptr(),
+            public_alias.as_ptr(),
+            private_policy.raw(&private_password),
+            public_policy.raw(&public_password),
+        )
+    })
+}
+
+/// Creates and saves an ECDSA key pair in the store.
+pub fn create_ecdsa_key_pair(
+    ec_type: EcType,
+    private_alias: &str,
+    public_alias: &str,
+    private_policy: &Policy,
+    public_policy: &Policy,
+) -> Result<()> {
+    let private_alias = alias_cstring(private_alias)?;
+    let public_alias = alias_cstring(public_alias)?;
+    let private_password = password_cstring(private_policy.password.as_deref())?;
+    let public_password = password_cstring(public_policy.password.as_deref())?;
+
+    check(unsafe {
+        ckmc_create_key_pair_ecdsa(
+            ec_type.into(),
+            private_alias.as_ptr(),
+            public_alias.as_ptr(),
+            private_policy.raw(&private_password),
+            public_policy.raw(&public_password),
+        )
+    })
+}
+
+/// Creates and saves an AES key of `bits` (128, 192 or 256) in the store.
+pub fn create_aes_key(bits: usize, alias: &str, policy: &Policy) -> Result<()> {
+    let alias = alias_cstring(alias)?;
+    let password = password_cstring(policy.password.as_deref())?;
+
+    check(unsafe { ckmc_create_key_aes(bits, alias.as_ptr(), policy.raw(&password)) })
+}
+
+/// Signs `message` with a stored private key.
+pub fn sign(
+    private_alias: &str,
+    password: Option<&str>,
+    message: &[u8],
+    hash: HashAlgorithm,
+    padding: Padding,
+) -> Result<Vec<u8>> {
+    let private_alias = alias_cstring(private_alias)?;
+    let password = password_cstring(password)?;
+
+    let mut signature = null_mut();
+    check(unsafe {
+        ckmc_create_signature(
+            private_alias.as_ptr(),
+            password_ptr(&password),
+            raw_buffer(message),
+            hash.into(),
+            padding.into(),
+            &mut signature,
+        )
+    })?;
+
+    Ok(unsafe { take_buffer(signature) })
+}
+
+/// Whether `signature` of `message` was made with the private key matching a stored
+/// public key.
+pub fn verify(
+    public_alias: &str,
+    password: Option<&str>,
+    message: &[u8],
+    signature: &[u8],
+    hash: HashAlgorithm,
+    padding: Padding,
+) -> Result<bool> {
+    let public_alias = alias_cstring(public_alias)?;
+    let password = password_cstring(password)?;
+
+    let ret = unsafe {
+        ckmc_verify_signature(
+            public_alias.as_ptr(),
+            password_ptr(&password),
+            raw_buffer(message),
+            raw_buffer(signature),
+            hash.into(),
+            padding.into(),
+        )
+    };
+
+    match check(ret) {
+        Ok(()) => Ok(true),
+        Err(Error::Other(code)) if code == ckmc_error_e_CKMC_ERROR_VERIFICATION_FAILED => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+/// Encrypts `data` with a stored AES key, or RSA public key.
+pub fn encrypt(
+    cipher: &Cipher,
+    key_alias: &str,
+    password: Option<&str>,
+    data: &[u8],
+) -> Result<Vec<u8>> {
+    crypt(ckmc_encrypt_data, cipher, key_alias, password, data)
+}
+
+/// Decrypts `data` with a stored AES key, or RSA private key.
+pub fn decrypt(
+    cipher: &Cipher,
+    key_alias: &str,
+    password: Option<&str>,
+    data: &[u8],
+) -> Result<Vec<u8>> {
+    crypt(ckmc_decrypt_data, cipher, key_alias, password, data)
+}
+
+type CryptFn = unsafe extern "C" fn(
+    ckmc_param_list_h,
+    *const c_char,
+    *const c_char,
+    ckmc_raw_buffer_s,
+    *mut *mut ckmc_raw_buffer_s,
+) -> c_int;
+
+fn crypt(
+    crypt: CryptFn,
+    cipher: &Cipher,
+    key_alias: &str,
+    password: Option<&str>,
+    data: &[u8],
+) -> Result<Vec<u8>> {
+    let params = ParamList::new(cipher)?;
+    let key_alias = alias_cstring(key_alias)?;
+    let password = password_cstring(password)?;
+
+    let mut output = null_mut();
+    check(unsafe {
+        crypt(
+            params.handle,
+            key_alias.as_ptr(),
+            password_ptr(&password),
+            raw_buffer(data),
+            &mut output,
+        )
+    })?;
+
+    Ok(unsafe { take_buffer(output) })
+}
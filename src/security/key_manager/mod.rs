@@ -0,0 +1,342 @@
+//! The key manager, a secure store for data, keys and certificates of the app.
+//!
+//! Items are saved under an alias, optionally protected by a password. An item shared by
+//! another app with [`set_permission`] is named `"<owner package id> <alias>"`. Missing
+//! aliases fail with `Error::Other(CKMC_ERROR_DB_ALIAS_UNKNOWN)`.
+//!
+//! Certificate chains can be verified against the system trust store, OCSP checks require
+//! the `http://tizen.org/privilege/internet` privilege.
+
+mod cert;
+mod crypto;
+
+pub use cert::{
+    get_pkcs12, load_cert, load_pkcs12, ocsp_check, save_pkcs12, verify_chain, OcspStatus, Pkcs12,
+};
+pub use crypto::{
+    create_aes_key, create_ecdsa_key_pair, create_rsa_key_pair, decrypt, encrypt, sign, verify,
+    Cipher, EcType, HashAlgorithm, Padding,
+};
+
+use super::password_cstring;
+use crate::error::{check, Error, Result};
+use crate::rutin::{borrow_str, cstring};
+use rutin_tizen_sys::{
+    ckmc_alias_list_all_free, ckmc_alias_list_s, ckmc_buffer_free, ckmc_cert_free, ckmc_cert_s,
+    ckmc_data_format_e, ckmc_data_format_e_CKMC_FORM_DER, ckmc_data_format_e_CKMC_FORM_DER_BASE64,
+    ckmc_data_format_e_CKMC_FORM_PEM, ckmc_error_e_CKMC_ERROR_DB_ALIAS_UNKNOWN, ckmc_get_cert,
+    ckmc_get_cert_alias_list, ckmc_get_data, ckmc_get_data_alias_list, ckmc_get_key,
+    ckmc_get_key_alias_list, ckmc_key_free, ckmc_key_s, ckmc_key_type_e,
+    ckmc_key_type_e_CKMC_KEY_AES, ckmc_key_type_e_CKMC_KEY_DSA_PRIVATE,
+    ckmc_key_type_e_CKMC_KEY_DSA_PUBLIC, ckmc_key_type_e_CKMC_KEY_ECDSA_PRIVATE,
+    ckmc_key_type_e_CKMC_KEY_ECDSA_PUBLIC, ckmc_key_type_e_CKMC_KEY_RSA_PRIVATE,
+    ckmc_key_type_e_CKMC_KEY_RSA_PUBLIC, ckmc_policy_s, ckmc_raw_buffer_s, ckmc_remove_alias,
+    ckmc_save_cert, ckmc_save_data, ckmc_save_key, ckmc_set_permission,
+};
+use std::convert::TryFrom;
+use std::ffi::CString;
+use std::os::raw::{c_char, c_int};
+use std::ptr::{null, null_mut};
+use std::slice;
+
+/// Flags of what another app can do with a shared item.
+pub mod permission {
+    pub const NONE: i32 = 0;
+    pub const READ: i32 = 1 << 0;
+    pub const REMOVE: i32 = 1 << 1;
+}
+
+/// How a saved item is protected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Policy {
+    /// Encrypts the item with a password, needed to read or use it later.
+    pub password: Option<String>,
+    /// Whether the item can be read back, and not only used for crypto operations.
+    pub extractable: bool,
+}
+
+impl Default for Policy {
+    fn default() -> Self {
+        Self {
+            password: None,
+            extractable: true,
+        }
+    }
+}
+
+impl Policy {
+    /// The native policy, borrowing `password` which must outlive it.
+    fn raw(&self, password: &Option<CString>) -> ckmc_policy_s {
+        ckmc_policy_s {
+            password: password
+                .as_ref()
+                .map_or(null_mut(), |password| password.as_ptr() as *mut c_char),
+            extractable: self.extractable,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum KeyType {
+    RsaPublic,
+    RsaPrivate,
+    EcdsaPublic,
+    EcdsaPrivate,
+    DsaPublic,
+    DsaPrivate,
+    Aes,
+}
+
+impl From<KeyType> for ckmc_key_type_e {
+    fn from(key_type: KeyType) -> ckmc_key_type_e {
+        match key_type {
+            KeyType::RsaPublic => ckmc_key_type_e_CKMC_KEY_RSA_PUBLIC,
+            KeyType::RsaPrivate => ckmc_key_type_e_CKMC_KEY_RSA_PRIVATE,
+            KeyType::EcdsaPublic => ckmc_key_type_e_CKMC_KEY_ECDSA_PUBLIC,
+            KeyType::EcdsaPrivate => ckmc_key_type_e_CKMC_KEY_ECDSA_PRIVATE,
+            KeyType::DsaPublic => ckmc_key_type_e_CKMC_KEY_DSA_PUBLIC,
+            KeyType::DsaPrivate => ckmc_key_type_e_CKMC_KEY_DSA_PRIVATE,
+            KeyType::Aes => ckmc_key_type_e_CKMC_KEY_AES,
+        }
+    }
+}
+
+impl TryFrom<ckmc_key_type_e> for KeyType {
+    type Error = Error;
+
+    fn try_from(key_type: ckmc_key_type_e) -> Result<KeyType> {
+        match key_type {
+            ckmc_key_type_e_CKMC_KEY_RSA_PUBLIC => Ok(KeyType::RsaPublic),
+            ckmc_key_type_e_CKMC_KEY_RSA_PRIVATE => Ok(KeyType::RsaPrivate),
+            ckmc_key_type_e_CKMC_KEY_ECDSA_PUBLIC => Ok(KeyType::EcdsaPublic),
+            ckmc_key_type_e_CKMC_KEY_ECDSA_PRIVATE => Ok(KeyType::EcdsaPrivate),
+            ckmc_key_type_e_CKMC_KEY_DSA_PUBLIC => Ok(KeyType::DsaPublic),
+            ckmc_key_type_e_CKMC_KEY_DSA_PRIVATE => Ok(KeyType::DsaPrivate),
+            ckmc_key_type_e_CKMC_KEY_AES => Ok(KeyType::Aes),
+            _ => Err(Error::Unknown),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DataFormat {
+    DerBase64,
+    Der,
+    Pem,
+}
+
+impl From<DataFormat> for ckmc_data_format_e {
+    fn from(format: DataFormat) -> ckmc_data_format_e {
+        match format {
+            DataFormat::DerBase64 => ckmc_data_format_e_CKMC_FORM_DER_BASE64,
+            DataFormat::Der => ckmc_data_format_e_CKMC_FORM_DER,
+            DataFormat::Pem => ckmc_data_format_e_CKMC_FORM_PEM,
+        }
+    }
+}
+
+impl From<ckmc_data_format_e> for DataFormat {
+    fn from(format: ckmc_data_format_e) -> DataFormat {
+        match format {
+            ckmc_data_format_e_CKMC_FORM_DER_BASE64 => DataFormat::DerBase64,
+            ckmc_data_format_e_CKMC_FORM_PEM => DataFormat::Pem,
+            _ => DataFormat::Der,
+        }
+    }
+}
+
+/// A key, DER or PEM encoded for asymmetric keys, the raw bytes for AES.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Key {
+    pub data: Vec<u8>,
+    pub key_type: KeyType,
+}
+
+impl Key {
+    unsafe fn from_raw(key: &ckmc_key_s) -> Result<Key> {
+        Ok(Key {
+            data: slice::from_raw_parts(key.raw_key, key.key_size).to_vec(),
+            key_type: KeyType::try_from(key.key_type)?,
+        })
+    }
+
+    /// The native key, borrowing the data of this one.
+    fn raw(&self) -> ckmc_key_s {
+        ckmc_key_s {
+            raw_key: self.data.as_ptr() as *mut u8,
+            key_size: self.data.len(),
+            key_type: self.key_type.into(),
+            // the key itself is not encrypted
+            password: null_mut(),
+        }
+    }
+}
+
+/// An X.509 certificate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Certificate {
+    pub data: Vec<u8>,
+    pub format: DataFormat,
+}
+
+impl Certificate {
+    unsafe fn from_raw(cert: &ckmc_cert_s) -> Certificate {
+        Certificate {
+            data: slice::from_raw_parts(cert.raw_cert, cert.cert_size).to_vec(),
+            format: cert.data_format.into(),
+        }
+    }
+
+    /// The native certificate, borrowing the data of this one.
+    fn raw(&self) -> ckmc_cert_s {
+        ckmc_cert_s {
+            raw_cert: self.data.as_ptr() as *mut u8,
+            cert_size: self.data.len(),
+            data_format: self.format.into(),
+        }
+    }
+}
+
+/// A native buffer borrowing `data`.
+fn raw_buffer(data: &[u8]) -> ckmc_raw_buffer_s {
+    ckmc_raw_buffer_s {
+        data: data.as_ptr() as *mut u8,
+        size: data.len(),
+    }
+}
+
+/// Copies then frees a buffer returned by the key manager.
+unsafe fn take_buffer(buffer: *mut ckmc_raw_buffer_s) -> Vec<u8> {
+    let data = slice::from_raw_parts((*buffer).data, (*buffer).size).to_vec();
+    ckmc_buffer_free(buffer);
+
+    data
+}
+
+fn alias_cstring(alias: &str) -> Result<CString> {
+    cstring(alias)
+}
+
+/// The password as a native string, null if unset.
+fn password_ptr(password: &Option<CString>) -> *const c_char {
+    password
+        .as_ref()
+        .map_or(null(), |password| password.as_ptr())
+}
+
+pub fn save_data(alias: &str, data: &[u8], policy: &Policy) -> Result<()> {
+    let alias = alias_cstring(alias)?;
+    let password = password_cstring(policy.password.as_deref())?;
+
+    check(unsafe { ckmc_save_data(alias.as_ptr(), raw_buffer(data), policy.raw(&password)) })
+}
+
+/// Reads back saved data, `password` is needed if it was saved with one.
+pub fn get_data(alias: &str, password: Option<&str>) -> Result<Vec<u8>> {
+    let alias = alias_cstring(alias)?;
+    let password = password_cstring(password)?;
+
+    let mut buffer = null_mut();
+    check(unsafe { ckmc_get_data(alias.as_ptr(), password_ptr(&password), &mut buffer) })?;
+
+    Ok(unsafe { take_buffer(buffer) })
+}
+
+pub fn save_key(alias: &str, key: &Key, policy: &Policy) -> Result<()> {
+    let alias = alias_cstring(alias)?;
+    let password = password_cstring(policy.password.as_deref())?;
+
+    check(unsafe { ckmc_save_key(alias.as_ptr(), key.raw(), policy.raw(&password)) })
+}
+
+/// Reads back a saved key, which must be extractable.
+pub fn get_key(alias: &str, password: Option<&str>) -> Result<Key> {
+    let alias = alias_cstring(alias)?;
+    let password = password_cstring(password)?;
+
+    let mut raw_key = null_mut();
+    check(unsafe { ckmc_get_key(alias.as_ptr(), password_ptr(&password), &mut raw_key) })?;
+
+    let key = unsafe { Key::from_raw(&*raw_key) };
+    unsafe { ckmc_key_free(raw_key) };
+
+    key
+}
+
+pub fn save_cert(alias: &str, cert: &Certificate, policy: &Policy) -> Result<()> {
+    let alias = alias_cstring(alias)?;
+    let password = password_cstring(policy.password.as_deref())?;
+
+    check(unsafe { ckmc_save_cert(alias.as_ptr(), cert.raw(), policy.raw(&password)) })
+}
+
+pub fn get_cert(alias: &str, password: Option<&str>) -> Result<Certificate> {
+    let alias = alias_cstring(alias)?;
+    let password = password_cstring(password)?;
+
+    let mut raw_cert = null_mut();
+    check(unsafe { ckmc_get_cert(alias.as_ptr(), password_ptr(&password), &mut raw_cert) })?;
+
+    let cert = unsafe { Certificate::from_raw(&*raw_cert) };
+    unsafe { ckmc_cert_free(raw_cert) };
+
+    Ok(cert)
+}
+
+/// Removes a saved data, key or certificate.
+pub fn remove(alias: &str) -> Result<()> {
+    let alias = alias_cstring(alias)?;
+
+    check(unsafe { ckmc_remove_alias(alias.as_ptr()) })
+}
+
+/// Lets the app `accessor` use an item, with the [`permission`] flags. `permission::NONE`
+/// revokes its access.
+pub fn set_permission(alias: &str, accessor: &str, permissions: i32) -> Result<()> {
+    let alias = alias_cstring(alias)?;
+    let accessor = cstring(accessor)?;
+
+    check(unsafe { ckmc_set_permission(alias.as_ptr(), accessor.as_ptr(), permissions) })
+}
+
+/// The aliases of the data the app can access, including those shared with it.
+pub fn data_aliases() -> Result<Vec<String>> {
+    aliases(ckmc_get_data_alias_list)
+}
+
+pub fn key_aliases() -> Result<Vec<String>> {
+    aliases(ckmc_get_key_alias_list)
+}
+
+pub fn cert_aliases() -> Result<Vec<String>> {
+    aliases(ckmc_get_cert_alias_list)
+}
+
+fn aliases(get: unsafe extern "C" fn(*mut *mut ckmc_alias_list_s) -> c_int) -> Result<Vec<String>> {
+    let mut list = null_mut();
+
+    match check(unsafe { get(&mut list) }) {
+        Err(Error::Other(code)) if code == ckmc_error_e_CKMC_ERROR_DB_ALIAS_UNKNOWN => {
+            return Ok(Vec::new())
+        }
+        result => result?,
+    }
+
+    let mut aliases = Vec::new();
+    let mut node = list;
+
+    while !node.is_null() {
+        let item = unsafe { &*node };
+        aliases.extend(unsafe { borrow_str(item.alias) }.map(|alias| alias.into_owned()));
+        node = item.next;
+    }
+
+    unsafe { ckmc_alias_list_all_free(list) };
+
+    Ok(aliases)
+}
@@ -0,0 +1,15 @@
+pub mod dpm;
+pub mod fido;
+pub mod key_manager;
+pub mod ppm;
+pub mod teec;
+pub mod yaca;
+
+use crate::error::Result;
+use crate::rutin::cstring;
+use std::ffi::CString;
+
+/// Converts an optional password, `None` when unset.
+fn password_cstring(password: Option<&str>) -> Result<Option<CString>> {
+    password.map(cstring).transpose()
+}
@@ -0,0 +1,290 @@
+//! The Privacy Privilege Manager, asking the user at runtime for privacy related privileges.
+//!
+//! Those privileges must still be declared in the manifest, but since Tizen 4.0 the calls
+//! needing them fail with [`Error::PermissionDenied`] until the user allows them. With the
+//! `async` feature, the answers to `request_permissions` can be awaited on the main loop,
+//! e.g. during app startup.
+
+use crate::error::{check, Error, Result};
+use crate::rutin::abort_on_panic;
+#[cfg(feature = "async")]
+use crate::stream::{Reply, ReplySender};
+#[cfg(feature = "async")]
+use rutin_tizen_sys::ppm_request_permissions;
+use rutin_tizen_sys::{
+    ppm_call_cause_e, ppm_call_cause_e_PRIVACY_PRIVILEGE_MANAGER_CALL_CAUSE_ANSWER,
+    ppm_check_permission, ppm_check_result_e,
+    ppm_check_result_e_PRIVACY_PRIVILEGE_MANAGER_CHECK_RESULT_ALLOW,
+    ppm_check_result_e_PRIVACY_PRIVILEGE_MANAGER_CHECK_RESULT_ASK,
+    ppm_check_result_e_PRIVACY_PRIVILEGE_MANAGER_CHECK_RESULT_DENY, ppm_request_permission,
+    ppm_request_result_e,
+    ppm_request_result_e_PRIVACY_PRIVILEGE_MANAGER_REQUEST_RESULT_ALLOW_FOREVER,
+    ppm_request_result_e_PRIVACY_PRIVILEGE_MANAGER_REQUEST_RESULT_DENY_FOREVER,
+};
+use std::convert::TryFrom;
+use std::os::raw::{c_char, c_void};
+#[cfg(feature = "async")]
+use std::slice;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Privilege {
+    AccountRead,
+    AccountWrite,
+    CalendarRead,
+    CalendarWrite,
+    Call,
+    CallHistoryRead,
+    CallHistoryWrite,
+    Camera,
+    ContactRead,
+    ContactWrite,
+    ExternalStorage,
+    HealthInfo,
+    Location,
+    LocationCoarse,
+    MediaStorage,
+    MessageRead,
+    MessageWrite,
+    Recorder,
+}
+
+impl Privilege {
+    pub fn uri(self) -> &'static str {
+        let uri = self.c_uri();
+        &uri[..uri.len() - 1]
+    }
+
+    // NUL terminated, to pass to the native calls as is
+    fn c_uri(self) -> &'static str {
+        match self {
+            Privilege::AccountRead => "http://tizen.org/privilege/account.read\0",
+            Privilege::AccountWrite => "http://tizen.org/privilege/account.write\0",
+            Privilege::CalendarRead => "http://tizen.org/privilege/calendar.read\0",
+            Privilege::CalendarWrite => "http://tizen.org/privilege/calendar.write\0",
+            Privilege::Call => "http://tizen.org/privilege/call\0",
+            Privilege::CallHistoryRead => "http://tizen.org/privilege/callhistory.read\0",
+            Privilege::CallHistoryWrite => "http://tizen.org/privilege/callhistory.write\0",
+            Privilege::Camera => "http://tizen.org/privilege/camera\0",
+            Privilege::ContactRead => "http://tizen.org/privilege/contact.read\0",
+            Privilege::ContactWrite => "http://tizen.org/privilege/contact.write\0",
+            Privilege::ExternalStorage => "http://tizen.org/privilege/externalstorage\0",
+            Privilege::HealthInfo => "http://tizen.org/privilege/healthinfo\0",
+            Privilege::Location => "http://tizen.org/privilege/location\0",
+            Privilege::LocationCoarse => "http://tizen.org/privilege/location.coarse\0",
+            Privilege::MediaStorage => "http://tizen.org/privilege/mediastorage\0",
+            Privilege::MessageRead => "http://tizen.org/privilege/message.read\0",
+            Privilege::MessageWrite => "http://tizen.org/privilege/message.write\0",
+            Privilege::Recorder => "http://tizen.org/privilege/recorder\0",
+        }
+    }
+
+    fn as_ptr(self) -> *const c_char {
+        self.c_uri().as_ptr() as *const c_char
+    }
+}
+
+impl TryFrom<&str> for Privilege {
+    type Error = Error;
+
+    fn try_from(uri: &str) -> Result<Privilege> {
+        ALL_PRIVILEGES
+            .iter()
+            .copied()
+            .find(|privilege| privilege.uri() == uri)
+            .ok_or(Error::InvalidParameter)
+    }
+}
+
+const ALL_PRIVILEGES: [Privilege; 18] = [
+    Privilege::AccountRead,
+    Privilege::AccountWrite,
+    Privilege::CalendarRead,
+    Privilege::CalendarWrite,
+    Privilege::Call,
+    Privilege::CallHistoryRead,
+    Privilege::CallHistoryWrite,
+    Privilege::Camera,
+    Privilege::ContactRead,
+    Privilege::ContactWrite,
+    Privilege::ExternalStorage,
+    Privilege::HealthInfo,
+    Privilege::Location,
+    Privilege::LocationCoarse,
+    Privilege::MediaStorage,
+    Privilege::MessageRead,
+    Privilege::MessageWrite,
+    Privilege::Recorder,
+];
+
+/// Whether the app can currently use a privilege.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Permission {
+    Allowed,
+    Denied,
+    /// Not decided yet, [`request_permission`] should be called.
+    Ask,
+}
+
+impl TryFrom<ppm_check_result_e> for Permission {
+    type Error = Error;
+
+    fn try_from(result: ppm_check_result_e) -> Result<Permission> {
+        match result {
+            ppm_check_result_e_PRIVACY_PRIVILEGE_MANAGER_CHECK_RESULT_ALLOW => {
+                Ok(Permission::Allowed)
+            }
+            ppm_check_result_e_PRIVACY_PRIVILEGE_MANAGER_CHECK_RESULT_DENY => {
+                Ok(Permission::Denied)
+            }
+            ppm_check_result_e_PRIVACY_PRIVILEGE_MANAGER_CHECK_RESULT_ASK => Ok(Permission::Ask),
+            _ => Err(Error::Unknown),
+        }
+    }
+}
+
+/// The answer of the user to a request.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Decision {
+    AllowForever,
+    DenyForever,
+    /// Denied for now, the user will be asked again next time.
+    DenyOnce,
+}
+
+impl From<ppm_request_result_e> for Decision {
+    fn from(result: ppm_request_result_e) -> Decision {
+        match result {
+            ppm_request_result_e_PRIVACY_PRIVILEGE_MANAGER_REQUEST_RESULT_ALLOW_FOREVER => {
+                Decision::AllowForever
+            }
+            ppm_request_result_e_PRIVACY_PRIVILEGE_MANAGER_REQUEST_RESULT_DENY_FOREVER => {
+                Decision::DenyForever
+            }
+            _ => Decision::DenyOnce,
+        }
+    }
+}
+
+pub fn check_permission(privilege: Privilege) -> Result<Permission> {
+    let mut result = ppm_check_result_e_PRIVACY_PRIVILEGE_MANAGER_CHECK_RESULT_DENY;
+    check(unsafe { ppm_check_permission(privilege.as_ptr(), &mut result) })?;
+
+    Permission::try_from(result)
+}
+
+type ResponseFn = dyn FnOnce(Result<Decision>);
+
+/// Asks the user whether the app can use `privilege`, `callback_fn` is called with the
+/// answer. Only one request can be shown at a time.
+pub fn request_permission<F>(privilege: Privilege, callback_fn: F) -> Result<()>
+where
+    F: FnOnce(Result<Decision>),
+    F: 'static,
+{
+    let callback_fn: Box<ResponseFn> = Box::new(callback_fn);
+    let data = Box::into_raw(Box::new(callback_fn));
+
+    let ret = unsafe {
+        ppm_request_permission(
+            privilege.as_ptr(),
+            Some(response_handler),
+            data as *mut c_void,
+        )
+    };
+
+    if let Err(e) = check(ret) {
+        drop(unsafe { Box::from_raw(data) });
+        return Err(e);
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "async")]
+struct MultipleRequest {
+    privileges: Vec<Privilege>,
+    sender: ReplySender<Result<Vec<(Privilege, Decision)>>>,
+}
+
+/// Asks the user about all of `privileges` at once, resolving to their answers in the same
+/// order. The privileges already allowed are answered without asking.
+#[cfg(feature = "async")]
+pub fn request_permissions(privileges: &[Privilege]) -> Reply<Result<Vec<(Privilege, Decision)>>> {
+    let (sender, reply) = Reply::new();
+    let mut uris: Vec<*const c_char> = privileges
+        .iter()
+        .map(|privilege| privilege.as_ptr())
+        .collect();
+
+    let data = Box::into_raw(Box::new(MultipleRequest {
+        privileges: privileges.to_vec(),
+        sender,
+    }));
+
+    let ret = unsafe {
+        ppm_request_permissions(
+            uris.as_mut_ptr(),
+            uris.len(),
+            Some(multiple_response_handler),
+            data as *mut c_void,
+        )
+    };
+
+    if let Err(e) = check(ret) {
+        drop(unsafe { Box::from_raw(data) });
+        return Reply::ready(Err(e));
+    }
+
+    reply
+}
+
+extern "C" fn response_handler(
+    cause: ppm_call_cause_e,
+    result: ppm_request_result_e,
+    _privilege: *const c_char,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let closure = unsafe { Box::from_raw(user_data as *mut Box<ResponseFn>) };
+
+        if cause == ppm_call_cause_e_PRIVACY_PRIVILEGE_MANAGER_CALL_CAUSE_ANSWER {
+            closure(Ok(result.into()))
+        } else {
+            closure(Err(Error::Unknown))
+        }
+    })
+}
+
+#[cfg(feature = "async")]
+extern "C" fn multiple_response_handler(
+    cause: ppm_call_cause_e,
+    results: *const ppm_request_result_e,
+    _privileges: *mut *const c_char,
+    privileges_count: usize,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let request = unsafe { Box::from_raw(user_data as *mut MultipleRequest) };
+
+        if cause != ppm_call_cause_e_PRIVACY_PRIVILEGE_MANAGER_CALL_CAUSE_ANSWER
+            || results.is_null()
+        {
+            request.sender.send(Err(Error::Unknown));
+            return;
+        }
+
+        // the results are in the order of the request
+        let results = unsafe { slice::from_raw_parts(results, privileges_count) };
+        let decisions = request
+            .privileges
+            .iter()
+            .zip(results)
+            .map(|(&privilege, &result)| (privilege, result.into()))
+            .collect();
+
+        request.sender.send(Ok(decisions));
+    })
+}
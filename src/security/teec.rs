@@ -0,0 +1,279 @@
+//! The GlobalPlatform TEE Client API, calling into trusted applications running in the
+//! Trusted Execution Environment (e.g. TrustZone).
+//!
+//! The TEE has its own error codes, returned as [`TeeError`].
+
+use rutin_tizen_sys::{
+    TEEC_CloseSession, TEEC_Context, TEEC_FinalizeContext, TEEC_InitializeContext,
+    TEEC_InvokeCommand, TEEC_OpenSession, TEEC_Operation, TEEC_Session, TEEC_ERROR_BAD_PARAMETERS,
+    TEEC_LOGIN_PUBLIC, TEEC_MEMREF_TEMP_INOUT, TEEC_MEMREF_TEMP_INPUT, TEEC_MEMREF_TEMP_OUTPUT,
+    TEEC_NONE, TEEC_ORIGIN_API, TEEC_ORIGIN_COMMS, TEEC_ORIGIN_TEE, TEEC_ORIGIN_TRUSTED_APP,
+    TEEC_SUCCESS, TEEC_UUID, TEEC_VALUE_INOUT, TEEC_VALUE_INPUT, TEEC_VALUE_OUTPUT,
+};
+use std::cell::UnsafeCell;
+use std::ffi::CString;
+use std::marker::PhantomData;
+use std::os::raw::c_void;
+use std::ptr::null;
+
+/// Where an error was raised.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Origin {
+    /// The client library, e.g. for bad parameters.
+    Api,
+    /// The communication with the TEE.
+    Comms,
+    /// The TEE itself.
+    Tee,
+    TrustedApp,
+}
+
+impl From<u32> for Origin {
+    fn from(origin: u32) -> Origin {
+        match origin {
+            TEEC_ORIGIN_COMMS => Origin::Comms,
+            TEEC_ORIGIN_TEE => Origin::Tee,
+            TEEC_ORIGIN_TRUSTED_APP => Origin::TrustedApp,
+            _ => Origin::Api,
+        }
+    }
+}
+
+/// A `TEEC_ERROR_*` code, or one defined by the trusted application.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TeeError {
+    pub code: u32,
+    pub origin: Origin,
+}
+
+pub type TeeResult<T> = std::result::Result<T, TeeError>;
+
+fn tee_result(code: u32, origin: u32) -> TeeResult<()> {
+    if code == TEEC_SUCCESS {
+        Ok(())
+    } else {
+        Err(TeeError {
+            code,
+            origin: origin.into(),
+        })
+    }
+}
+
+fn bad_parameters() -> TeeError {
+    TeeError {
+        code: TEEC_ERROR_BAD_PARAMETERS,
+        origin: Origin::Api,
+    }
+}
+
+/// Identifies a trusted application.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Uuid {
+    pub time_low: u32,
+    pub time_mid: u16,
+    pub time_hi_and_version: u16,
+    pub clock_seq_and_node: [u8; 8],
+}
+
+impl From<Uuid> for TEEC_UUID {
+    fn from(uuid: Uuid) -> TEEC_UUID {
+        TEEC_UUID {
+            timeLow: uuid.time_low,
+            timeMid: uuid.time_mid,
+            timeHiAndVersion: uuid.time_hi_and_version,
+            clockSeqAndNode: uuid.clock_seq_and_node,
+        }
+    }
+}
+
+/// A parameter of a command, up to 4 per call. The output parameters are updated once
+/// the call returns, even if it fails.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Param<'a> {
+    None,
+    ValueInput {
+        a: u32,
+        b: u32,
+    },
+    ValueOutput {
+        a: u32,
+        b: u32,
+    },
+    ValueInout {
+        a: u32,
+        b: u32,
+    },
+    MemrefInput(&'a [u8]),
+    /// `size` is set to the number of bytes written by the trusted application, or needed
+    /// if `buffer` was too short.
+    MemrefOutput {
+        buffer: &'a mut [u8],
+        size: usize,
+    },
+    MemrefInout {
+        buffer: &'a mut [u8],
+        size: usize,
+    },
+}
+
+/// The parameters of a call, borrowing the buffers of `params`.
+fn operation(params: &mut [Param<'_>]) -> TeeResult<TEEC_Operation> {
+    if params.len() > 4 {
+        return Err(bad_parameters());
+    }
+
+    let mut operation: TEEC_Operation = unsafe { std::mem::zeroed() };
+    operation.started = 1;
+
+    for (i, param) in params.iter_mut().enumerate() {
+        let raw = &mut operation.params[i];
+
+        // writing to the union fields is safe
+        let param_type = match param {
+            Param::None => TEEC_NONE,
+            Param::ValueInput { a, b } => {
+                raw.value.a = *a;
+                raw.value.b = *b;
+                TEEC_VALUE_INPUT
+            }
+            Param::ValueOutput { .. } => TEEC_VALUE_OUTPUT,
+            Param::ValueInout { a, b } => {
+                raw.value.a = *a;
+                raw.value.b = *b;
+                TEEC_VALUE_INOUT
+            }
+            Param::MemrefInput(buffer) => {
+                raw.tmpref.buffer = buffer.as_ptr() as *mut c_void;
+                raw.tmpref.size = buffer.len();
+                TEEC_MEMREF_TEMP_INPUT
+            }
+            Param::MemrefOutput { buffer, .. } => {
+                raw.tmpref.buffer = buffer.as_mut_ptr() as *mut c_void;
+                raw.tmpref.size = buffer.len();
+                TEEC_MEMREF_TEMP_OUTPUT
+            }
+            Param::MemrefInout { buffer, .. } => {
+                raw.tmpref.buffer = buffer.as_mut_ptr() as *mut c_void;
+                raw.tmpref.size = buffer.len();
+                TEEC_MEMREF_TEMP_INOUT
+            }
+        };
+
+        operation.paramTypes |= param_type << (4 * i);
+    }
+
+    Ok(operation)
+}
+
+/// Copies the outputs of a call back into `params`.
+fn read_outputs(operation: &TEEC_Operation, params: &mut [Param<'_>]) {
+    for (raw, param) in operation.params.iter().zip(params) {
+        unsafe {
+            match param {
+                Param::ValueOutput { a, b } | Param::ValueInout { a, b } => {
+                    *a = raw.value.a;
+                    *b = raw.value.b;
+                }
+                Param::MemrefOutput { size, .. } | Param::MemrefInout { size, .. } => {
+                    *size = raw.tmpref.size;
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// A connection to a TEE, finalized when dropped.
+pub struct Context {
+    // the sessions point to it, and the library updates it through shared references
+    context: Box<UnsafeCell<TEEC_Context>>,
+}
+
+impl Context {
+    /// Connects to the TEE `name`, the default one if unset.
+    pub fn new(name: Option<&str>) -> TeeResult<Context> {
+        let name = name
+            .map(|name| CString::new(name).map_err(|_| bad_parameters()))
+            .transpose()?;
+        let context: Box<UnsafeCell<TEEC_Context>> =
+            Box::new(UnsafeCell::new(unsafe { std::mem::zeroed() }));
+
+        let code = unsafe {
+            TEEC_InitializeContext(
+                name.as_ref().map_or(null(), |name| name.as_ptr()),
+                context.get(),
+            )
+        };
+        tee_result(code, TEEC_ORIGIN_API)?;
+
+        Ok(Self { context })
+    }
+
+    /// Opens a session to the trusted application `uuid`, with public login.
+    pub fn open_session(&self, uuid: Uuid, params: &mut [Param<'_>]) -> TeeResult<Session<'_>> {
+        let uuid = TEEC_UUID::from(uuid);
+        let mut operation = operation(params)?;
+        let mut session: Box<TEEC_Session> = Box::new(unsafe { std::mem::zeroed() });
+        let mut origin = TEEC_ORIGIN_API;
+
+        let code = unsafe {
+            TEEC_OpenSession(
+                self.context.get(),
+                &mut *session,
+                &uuid,
+                TEEC_LOGIN_PUBLIC,
+                null(),
+                &mut operation,
+                &mut origin,
+            )
+        };
+        // also on failure, e.g. the size needed for a short buffer
+        read_outputs(&operation, params);
+        tee_result(code, origin)?;
+
+        Ok(Session {
+            session,
+            _context: PhantomData,
+        })
+    }
+}
+
+impl Drop for Context {
+    fn drop(&mut self) {
+        unsafe {
+            TEEC_FinalizeContext(self.context.get());
+        }
+    }
+}
+
+/// A session with a trusted application, closed when dropped.
+pub struct Session<'c> {
+    session: Box<TEEC_Session>,
+    _context: PhantomData<&'c Context>,
+}
+
+impl<'c> Session<'c> {
+    /// Calls the command `command_id` of the trusted application, blocking until it returns.
+    pub fn invoke(&mut self, command_id: u32, params: &mut [Param<'_>]) -> TeeResult<()> {
+        let mut operation = operation(params)?;
+        let mut origin = TEEC_ORIGIN_API;
+
+        let code = unsafe {
+            TEEC_InvokeCommand(&mut *self.session, command_id, &mut operation, &mut origin)
+        };
+        // also on failure, e.g. the size needed for a short buffer
+        read_outputs(&operation, params);
+        tee_result(code, origin)
+    }
+}
+
+impl<'c> Drop for Session<'c> {
+    fn drop(&mut self) {
+        unsafe {
+            TEEC_CloseSession(&mut *self.session);
+        }
+    }
+}
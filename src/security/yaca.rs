@@ -0,0 +1,444 @@
+//! YACA, the platform crypto library: digests, MACs, ciphers, signatures and key derivation.
+//!
+//! [`initialize`] must be called on each thread before using the other functions. The
+//! `Unsafe*` algorithms are only meant to read legacy data.
+
+use super::password_cstring;
+use crate::error::{check, Error, Result};
+use crate::rutin::cstring;
+use rutin_tizen_sys::{
+    yaca_block_cipher_mode_e, yaca_block_cipher_mode_e_YACA_BCM_CBC,
+    yaca_block_cipher_mode_e_YACA_BCM_CFB, yaca_block_cipher_mode_e_YACA_BCM_CFB1,
+    yaca_block_cipher_mode_e_YACA_BCM_CFB8, yaca_block_cipher_mode_e_YACA_BCM_CTR,
+    yaca_block_cipher_mode_e_YACA_BCM_ECB, yaca_block_cipher_mode_e_YACA_BCM_NONE,
+    yaca_block_cipher_mode_e_YACA_BCM_OFB, yaca_cleanup, yaca_digest_algorithm_e,
+    yaca_digest_algorithm_e_YACA_DIGEST_MD5, yaca_digest_algorithm_e_YACA_DIGEST_SHA1,
+    yaca_digest_algorithm_e_YACA_DIGEST_SHA224, yaca_digest_algorithm_e_YACA_DIGEST_SHA256,
+    yaca_digest_algorithm_e_YACA_DIGEST_SHA384, yaca_digest_algorithm_e_YACA_DIGEST_SHA512,
+    yaca_encrypt_algorithm_e, yaca_encrypt_algorithm_e_YACA_ENCRYPT_3DES_3TDEA,
+    yaca_encrypt_algorithm_e_YACA_ENCRYPT_AES, yaca_encrypt_algorithm_e_YACA_ENCRYPT_CAST5,
+    yaca_encrypt_algorithm_e_YACA_ENCRYPT_UNSAFE_3DES_2TDEA,
+    yaca_encrypt_algorithm_e_YACA_ENCRYPT_UNSAFE_DES,
+    yaca_encrypt_algorithm_e_YACA_ENCRYPT_UNSAFE_RC2,
+    yaca_encrypt_algorithm_e_YACA_ENCRYPT_UNSAFE_RC4, yaca_error_e_YACA_ERROR_DATA_MISMATCH,
+    yaca_free, yaca_initialize, yaca_key_derive_pbkdf2, yaca_key_destroy, yaca_key_export,
+    yaca_key_extract_public, yaca_key_file_format_e,
+    yaca_key_file_format_e_YACA_KEY_FILE_FORMAT_BASE64,
+    yaca_key_file_format_e_YACA_KEY_FILE_FORMAT_DER,
+    yaca_key_file_format_e_YACA_KEY_FILE_FORMAT_PEM,
+    yaca_key_file_format_e_YACA_KEY_FILE_FORMAT_RAW, yaca_key_format_e_YACA_KEY_FORMAT_DEFAULT,
+    yaca_key_generate, yaca_key_h, yaca_key_import, yaca_key_type_e,
+    yaca_key_type_e_YACA_KEY_TYPE_DES, yaca_key_type_e_YACA_KEY_TYPE_DSA_PRIV,
+    yaca_key_type_e_YACA_KEY_TYPE_DSA_PUB, yaca_key_type_e_YACA_KEY_TYPE_EC_PRIV,
+    yaca_key_type_e_YACA_KEY_TYPE_EC_PUB, yaca_key_type_e_YACA_KEY_TYPE_IV,
+    yaca_key_type_e_YACA_KEY_TYPE_RSA_PRIV, yaca_key_type_e_YACA_KEY_TYPE_RSA_PUB,
+    yaca_key_type_e_YACA_KEY_TYPE_SYMMETRIC, yaca_randomize_bytes, yaca_simple_calculate_digest,
+    yaca_simple_calculate_hmac, yaca_simple_calculate_signature, yaca_simple_decrypt,
+    yaca_simple_encrypt, yaca_simple_verify_signature,
+};
+use std::os::raw::{c_char, c_int, c_void};
+use std::ptr::{null, null_mut};
+use std::slice;
+
+/// Initializes the library for the calling thread.
+pub fn initialize() -> Result<()> {
+    check(unsafe { yaca_initialize() })
+}
+
+/// Releases the resources of the calling thread.
+pub fn cleanup() {
+    unsafe { yaca_cleanup() }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DigestAlgorithm {
+    Md5,
+    Sha1,
+    Sha224,
+    Sha256,
+    Sha384,
+    Sha512,
+}
+
+impl From<DigestAlgorithm> for yaca_digest_algorithm_e {
+    fn from(algorithm: DigestAlgorithm) -> yaca_digest_algorithm_e {
+        match algorithm {
+            DigestAlgorithm::Md5 => yaca_digest_algorithm_e_YACA_DIGEST_MD5,
+            DigestAlgorithm::Sha1 => yaca_digest_algorithm_e_YACA_DIGEST_SHA1,
+            DigestAlgorithm::Sha224 => yaca_digest_algorithm_e_YACA_DIGEST_SHA224,
+            DigestAlgorithm::Sha256 => yaca_digest_algorithm_e_YACA_DIGEST_SHA256,
+            DigestAlgorithm::Sha384 => yaca_digest_algorithm_e_YACA_DIGEST_SHA384,
+            DigestAlgorithm::Sha512 => yaca_digest_algorithm_e_YACA_DIGEST_SHA512,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum KeyType {
+    /// A key for AES, HMAC, CMAC or PBKDF2.
+    Symmetric,
+    Des,
+    /// An initialization vector.
+    Iv,
+    RsaPublic,
+    RsaPrivate,
+    DsaPublic,
+    DsaPrivate,
+    EcPublic,
+    EcPrivate,
+}
+
+impl From<KeyType> for yaca_key_type_e {
+    fn from(key_type: KeyType) -> yaca_key_type_e {
+        match key_type {
+            KeyType::Symmetric => yaca_key_type_e_YACA_KEY_TYPE_SYMMETRIC,
+            KeyType::Des => yaca_key_type_e_YACA_KEY_TYPE_DES,
+            KeyType::Iv => yaca_key_type_e_YACA_KEY_TYPE_IV,
+            KeyType::RsaPublic => yaca_key_type_e_YACA_KEY_TYPE_RSA_PUB,
+            KeyType::RsaPrivate => yaca_key_type_e_YACA_KEY_TYPE_RSA_PRIV,
+            KeyType::DsaPublic => yaca_key_type_e_YACA_KEY_TYPE_DSA_PUB,
+            KeyType::DsaPrivate => yaca_key_type_e_YACA_KEY_TYPE_DSA_PRIV,
+            KeyType::EcPublic => yaca_key_type_e_YACA_KEY_TYPE_EC_PUB,
+            KeyType::EcPrivate => yaca_key_type_e_YACA_KEY_TYPE_EC_PRIV,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EncryptAlgorithm {
+    Aes,
+    UnsafeDes,
+    Unsafe3Des2Tdea,
+    TripleDes3Tdea,
+    UnsafeRc2,
+    /// A stream cipher, used with [`BlockCipherMode::None`].
+    UnsafeRc4,
+    Cast5,
+}
+
+impl From<EncryptAlgorithm> for yaca_encrypt_algorithm_e {
+    fn from(algorithm: EncryptAlgorithm) -> yaca_encrypt_algorithm_e {
+        match algorithm {
+            EncryptAlgorithm::Aes => yaca_encrypt_algorithm_e_YACA_ENCRYPT_AES,
+            EncryptAlgorithm::UnsafeDes => yaca_encrypt_algorithm_e_YACA_ENCRYPT_UNSAFE_DES,
+            EncryptAlgorithm::Unsafe3Des2Tdea => {
+                yaca_encrypt_algorithm_e_YACA_ENCRYPT_UNSAFE_3DES_2TDEA
+            }
+            EncryptAlgorithm::TripleDes3Tdea => yaca_encrypt_algorithm_e_YACA_ENCRYPT_3DES_3TDEA,
+            EncryptAlgorithm::UnsafeRc2 => yaca_encrypt_algorithm_e_YACA_ENCRYPT_UNSAFE_RC2,
+            EncryptAlgorithm::UnsafeRc4 => yaca_encrypt_algorithm_e_YACA_ENCRYPT_UNSAFE_RC4,
+            EncryptAlgorithm::Cast5 => yaca_encrypt_algorithm_e_YACA_ENCRYPT_CAST5,
+        }
+    }
+}
+
+/// The block cipher modes usable in a single call, authenticated modes are not.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BlockCipherMode {
+    None,
+    Ecb,
+    Ctr,
+    Cbc,
+    Cfb,
+    Cfb1,
+    Cfb8,
+    Ofb,
+}
+
+impl From<BlockCipherMode> for yaca_block_cipher_mode_e {
+    fn from(mode: BlockCipherMode) -> yaca_block_cipher_mode_e {
+        match mode {
+            BlockCipherMode::None => yaca_block_cipher_mode_e_YACA_BCM_NONE,
+            BlockCipherMode::Ecb => yaca_block_cipher_mode_e_YACA_BCM_ECB,
+            BlockCipherMode::Ctr => yaca_block_cipher_mode_e_YACA_BCM_CTR,
+            BlockCipherMode::Cbc => yaca_block_cipher_mode_e_YACA_BCM_CBC,
+            BlockCipherMode::Cfb => yaca_block_cipher_mode_e_YACA_BCM_CFB,
+            BlockCipherMode::Cfb1 => yaca_block_cipher_mode_e_YACA_BCM_CFB1,
+            BlockCipherMode::Cfb8 => yaca_block_cipher_mode_e_YACA_BCM_CFB8,
+            BlockCipherMode::Ofb => yaca_block_cipher_mode_e_YACA_BCM_OFB,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum KeyFileFormat {
+    /// The bytes of a symmetric key or IV.
+    Raw,
+    Base64,
+    Pem,
+    Der,
+}
+
+impl From<KeyFileFormat> for yaca_key_file_format_e {
+    fn from(format: KeyFileFormat) -> yaca_key_file_format_e {
+        match format {
+            KeyFileFormat::Raw => yaca_key_file_format_e_YACA_KEY_FILE_FORMAT_RAW,
+            KeyFileFormat::Base64 => yaca_key_file_format_e_YACA_KEY_FILE_FORMAT_BASE64,
+            KeyFileFormat::Pem => yaca_key_file_format_e_YACA_KEY_FILE_FORMAT_PEM,
+            KeyFileFormat::Der => yaca_key_file_format_e_YACA_KEY_FILE_FORMAT_DER,
+        }
+    }
+}
+
+/// Copies then frees a buffer allocated by the library, which is null for empty outputs.
+unsafe fn take_output(data: *mut c_char, len: usize) -> Vec<u8> {
+    if data.is_null() {
+        return Vec::new();
+    }
+
+    let output = if len > 0 {
+        slice::from_raw_parts(data as *const u8, len).to_vec()
+    } else {
+        Vec::new()
+    };
+    yaca_free(data as *mut c_void);
+
+    output
+}
+
+/// A key, destroyed when dropped.
+pub struct Key {
+    handle: yaca_key_h,
+}
+
+impl Key {
+    /// Generates a random key of `bits`, a multiple of 8 for symmetric keys and IVs.
+    pub fn generate(key_type: KeyType, bits: usize) -> Result<Key> {
+        let mut handle = null_mut();
+        check(unsafe { yaca_key_generate(key_type.into(), bits, &mut handle) })?;
+
+        Ok(Self { handle })
+    }
+
+    /// Imports a key in any format, the password decrypts an encrypted PEM private key.
+    pub fn import(key_type: KeyType, password: Option<&str>, data: &[u8]) -> Result<Key> {
+        let password = password_cstring(password)?;
+
+        let mut handle = null_mut();
+        check(unsafe {
+            yaca_key_import(
+                key_type.into(),
+                password
+                    .as_ref()
+                    .map_or(null(), |password| password.as_ptr()),
+                data.as_ptr() as *const c_char,
+                data.len(),
+                &mut handle,
+            )
+        })?;
+
+        Ok(Self { handle })
+    }
+
+    /// Derives a symmetric key of `bits` from a password with PBKDF2.
+    pub fn derive_pbkdf2(
+        password: &str,
+        salt: &[u8],
+        iterations: usize,
+        digest: DigestAlgorithm,
+        bits: usize,
+    ) -> Result<Key> {
+        let password = cstring(password)?;
+
+        let mut handle = null_mut();
+        check(unsafe {
+            yaca_key_derive_pbkdf2(
+                password.as_ptr(),
+                salt.as_ptr() as *const c_char,
+                salt.len(),
+                iterations,
+                digest.into(),
+                bits,
+                &mut handle,
+            )
+        })?;
+
+        Ok(Self { handle })
+    }
+
+    /// The public key of an RSA, DSA or EC private key.
+    pub fn extract_public(&self) -> Result<Key> {
+        let mut handle = null_mut();
+        check(unsafe { yaca_key_extract_public(self.handle, &mut handle) })?;
+
+        Ok(Self { handle })
+    }
+
+    /// Exports the key, encrypting private keys in PEM with `password` if set.
+    pub fn export(&self, format: KeyFileFormat, password: Option<&str>) -> Result<Vec<u8>> {
+        let password = password_cstring(password)?;
+
+        let mut data = null_mut();
+        let mut len = 0;
+        check(unsafe {
+            yaca_key_export(
+                self.handle,
+                yaca_key_format_e_YACA_KEY_FORMAT_DEFAULT,
+                format.into(),
+                password
+                    .as_ref()
+                    .map_or(null(), |password| password.as_ptr()),
+                &mut data,
+                &mut len,
+            )
+        })?;
+
+        Ok(unsafe { take_output(data, len) })
+    }
+}
+
+impl Drop for Key {
+    fn drop(&mut self) {
+        unsafe {
+            yaca_key_destroy(self.handle);
+        }
+    }
+}
+
+pub fn digest(algorithm: DigestAlgorithm, message: &[u8]) -> Result<Vec<u8>> {
+    let mut digest = null_mut();
+    let mut len = 0;
+    check(unsafe {
+        yaca_simple_calculate_digest(
+            algorithm.into(),
+            message.as_ptr() as *const c_char,
+            message.len(),
+            &mut digest,
+            &mut len,
+        )
+    })?;
+
+    Ok(unsafe { take_output(digest, len) })
+}
+
+/// The HMAC of `message` with a symmetric key.
+pub fn hmac(algorithm: DigestAlgorithm, key: &Key, message: &[u8]) -> Result<Vec<u8>> {
+    let mut mac = null_mut();
+    let mut len = 0;
+    check(unsafe {
+        yaca_simple_calculate_hmac(
+            algorithm.into(),
+            key.handle,
+            message.as_ptr() as *const c_char,
+            message.len(),
+            &mut mac,
+            &mut len,
+        )
+    })?;
+
+    Ok(unsafe { take_output(mac, len) })
+}
+
+/// Encrypts `plaintext`, `iv` is needed by every mode but ECB.
+pub fn encrypt(
+    algorithm: EncryptAlgorithm,
+    mode: BlockCipherMode,
+    key: &Key,
+    iv: Option<&Key>,
+    plaintext: &[u8],
+) -> Result<Vec<u8>> {
+    crypt(yaca_simple_encrypt, algorithm, mode, key, iv, plaintext)
+}
+
+pub fn decrypt(
+    algorithm: EncryptAlgorithm,
+    mode: BlockCipherMode,
+    key: &Key,
+    iv: Option<&Key>,
+    ciphertext: &[u8],
+) -> Result<Vec<u8>> {
+    crypt(yaca_simple_decrypt, algorithm, mode, key, iv, ciphertext)
+}
+
+type CryptFn = unsafe extern "C" fn(
+    yaca_encrypt_algorithm_e,
+    yaca_block_cipher_mode_e,
+    yaca_key_h,
+    yaca_key_h,
+    *const c_char,
+    usize,
+    *mut *mut c_char,
+    *mut usize,
+) -> c_int;
+
+fn crypt(
+    crypt: CryptFn,
+    algorithm: EncryptAlgorithm,
+    mode: BlockCipherMode,
+    key: &Key,
+    iv: Option<&Key>,
+    input: &[u8],
+) -> Result<Vec<u8>> {
+    let mut output = null_mut();
+    let mut len = 0;
+    check(unsafe {
+        crypt(
+            algorithm.into(),
+            mode.into(),
+            key.handle,
+            iv.map_or(null_mut(), |iv| iv.handle),
+            input.as_ptr() as *const c_char,
+            input.len(),
+            &mut output,
+            &mut len,
+        )
+    })?;
+
+    Ok(unsafe { take_output(output, len) })
+}
+
+/// Signs `message` with an RSA, DSA or EC private key, RSA using PKCS#1 padding.
+pub fn sign(algorithm: DigestAlgorithm, private_key: &Key, message: &[u8]) -> Result<Vec<u8>> {
+    let mut signature = null_mut();
+    let mut len = 0;
+    check(unsafe {
+        yaca_simple_calculate_signature(
+            algorithm.into(),
+            private_key.handle,
+            message.as_ptr() as *const c_char,
+            message.len(),
+            &mut signature,
+            &mut len,
+        )
+    })?;
+
+    Ok(unsafe { take_output(signature, len) })
+}
+
+/// Whether `signature` of `message` was made with the private key of `public_key`.
+pub fn verify(
+    algorithm: DigestAlgorithm,
+    public_key: &Key,
+    message: &[u8],
+    signature: &[u8],
+) -> Result<bool> {
+    let ret = unsafe {
+        yaca_simple_verify_signature(
+            algorithm.into(),
+            public_key.handle,
+            message.as_ptr() as *const c_char,
+            message.len(),
+            signature.as_ptr() as *const c_char,
+            signature.len(),
+        )
+    };
+
+    match check(ret) {
+        Ok(()) => Ok(true),
+        Err(Error::Other(code)) if code == yaca_error_e_YACA_ERROR_DATA_MISMATCH => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+/// `len` cryptographically secure random bytes.
+pub fn random_bytes(len: usize) -> Result<Vec<u8>> {
+    let mut data = vec![0; len];
+    check(unsafe { yaca_randomize_bytes(data.as_mut_ptr() as *mut c_char, len) })?;
+
+    Ok(data)
+}
@@ -0,0 +1,315 @@
+//! Motion, environment and health sensors.
+//!
+//! The heart rate monitor requires the `http://tizen.org/privilege/healthinfo` privilege.
+
+use crate::error::{check, Error, Result};
+use crate::rutin::{abort_on_panic, pin_callback, take_string};
+#[cfg(feature = "async")]
+use crate::stream::EventStream;
+use crate::sys::{
+    sensor_create_listener, sensor_data_accuracy_e_SENSOR_DATA_ACCURACY_BAD,
+    sensor_data_accuracy_e_SENSOR_DATA_ACCURACY_GOOD,
+    sensor_data_accuracy_e_SENSOR_DATA_ACCURACY_NORMAL,
+    sensor_data_accuracy_e_SENSOR_DATA_ACCURACY_VERYGOOD, sensor_destroy_listener, sensor_event_s,
+    sensor_get_default_sensor, sensor_get_max_range, sensor_get_min_interval, sensor_get_min_range,
+    sensor_get_name, sensor_get_resolution, sensor_get_vendor, sensor_h, sensor_is_supported,
+    sensor_listener_h, sensor_listener_read_data, sensor_listener_set_event_cb,
+    sensor_listener_set_interval, sensor_listener_set_option, sensor_listener_start,
+    sensor_listener_stop, sensor_listener_unset_event_cb, sensor_option_e_SENSOR_OPTION_ALWAYS_ON,
+    sensor_option_e_SENSOR_OPTION_DEFAULT, sensor_type_e, sensor_type_e_SENSOR_ACCELEROMETER,
+    sensor_type_e_SENSOR_GRAVITY, sensor_type_e_SENSOR_GYROSCOPE,
+    sensor_type_e_SENSOR_GYROSCOPE_ROTATION_VECTOR, sensor_type_e_SENSOR_HRM,
+    sensor_type_e_SENSOR_HUMAN_PEDOMETER, sensor_type_e_SENSOR_LIGHT,
+    sensor_type_e_SENSOR_LINEAR_ACCELERATION, sensor_type_e_SENSOR_MAGNETIC,
+    sensor_type_e_SENSOR_ORIENTATION, sensor_type_e_SENSOR_PRESSURE,
+    sensor_type_e_SENSOR_PROXIMITY, sensor_type_e_SENSOR_ROTATION_VECTOR,
+};
+use std::os::raw::{c_int, c_void};
+use std::pin::Pin;
+use std::ptr::null_mut;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SensorType {
+    /// The acceleration in m/s² on the x, y and z axes, gravity included.
+    Accelerometer,
+    /// The gravity in m/s² on the x, y and z axes.
+    Gravity,
+    /// The acceleration in m/s² on the x, y and z axes, gravity excluded.
+    LinearAcceleration,
+    /// The magnetic field in µT on the x, y and z axes.
+    Magnetic,
+    RotationVector,
+    /// The azimuth, pitch and roll in degrees.
+    Orientation,
+    /// The rotation speed in degrees/s around the x, y and z axes.
+    Gyroscope,
+    GyroscopeRotationVector,
+    /// The illuminance in lux.
+    Light,
+    /// 0 when an object is near, 5 otherwise.
+    Proximity,
+    /// The atmospheric pressure in hPa.
+    Pressure,
+    /// The heart rate in beats per minute.
+    HeartRate,
+    /// The steps and distance walked, with the walking state.
+    Pedometer,
+}
+
+impl From<SensorType> for sensor_type_e {
+    fn from(sensor_type: SensorType) -> sensor_type_e {
+        match sensor_type {
+            SensorType::Accelerometer => sensor_type_e_SENSOR_ACCELEROMETER,
+            SensorType::Gravity => sensor_type_e_SENSOR_GRAVITY,
+            SensorType::LinearAcceleration => sensor_type_e_SENSOR_LINEAR_ACCELERATION,
+            SensorType::Magnetic => sensor_type_e_SENSOR_MAGNETIC,
+            SensorType::RotationVector => sensor_type_e_SENSOR_ROTATION_VECTOR,
+            SensorType::Orientation => sensor_type_e_SENSOR_ORIENTATION,
+            SensorType::Gyroscope => sensor_type_e_SENSOR_GYROSCOPE,
+            SensorType::GyroscopeRotationVector => sensor_type_e_SENSOR_GYROSCOPE_ROTATION_VECTOR,
+            SensorType::Light => sensor_type_e_SENSOR_LIGHT,
+            SensorType::Proximity => sensor_type_e_SENSOR_PROXIMITY,
+            SensorType::Pressure => sensor_type_e_SENSOR_PRESSURE,
+            SensorType::HeartRate => sensor_type_e_SENSOR_HRM,
+            SensorType::Pedometer => sensor_type_e_SENSOR_HUMAN_PEDOMETER,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Accuracy {
+    Undefined,
+    Bad,
+    Normal,
+    Good,
+    VeryGood,
+}
+
+impl From<c_int> for Accuracy {
+    fn from(accuracy: c_int) -> Accuracy {
+        match accuracy {
+            sensor_data_accuracy_e_SENSOR_DATA_ACCURACY_BAD => Accuracy::Bad,
+            sensor_data_accuracy_e_SENSOR_DATA_ACCURACY_NORMAL => Accuracy::Normal,
+            sensor_data_accuracy_e_SENSOR_DATA_ACCURACY_GOOD => Accuracy::Good,
+            sensor_data_accuracy_e_SENSOR_DATA_ACCURACY_VERYGOOD => Accuracy::VeryGood,
+            _ => Accuracy::Undefined,
+        }
+    }
+}
+
+/// A reading, with the values described by its [`SensorType`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SensorEvent {
+    pub accuracy: Accuracy,
+    /// In microseconds since the boot.
+    pub timestamp: u64,
+    pub values: Vec<f32>,
+}
+
+impl From<&sensor_event_s> for SensorEvent {
+    fn from(event: &sensor_event_s) -> SensorEvent {
+        let count = (event.value_count.max(0) as usize).min(event.values.len());
+
+        Self {
+            accuracy: Accuracy::from(event.accuracy),
+            timestamp: event.timestamp,
+            values: event.values[..count].to_vec(),
+        }
+    }
+}
+
+/// The default sensor of a type, owned by the platform.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Sensor {
+    handle: sensor_h,
+}
+
+impl Sensor {
+    pub fn is_supported(sensor_type: SensorType) -> Result<bool> {
+        let mut supported = false;
+        check(unsafe { sensor_is_supported(sensor_type.into(), &mut supported) })?;
+
+        Ok(supported)
+    }
+
+    /// Fails with [`Error::NotSupported`] if the device has no such sensor.
+    pub fn default_for(sensor_type: SensorType) -> Result<Sensor> {
+        let mut handle = null_mut();
+        check(unsafe { sensor_get_default_sensor(sensor_type.into(), &mut handle) })?;
+
+        Ok(Self { handle })
+    }
+
+    pub fn get_name(&self) -> Result<String> {
+        let mut name = null_mut();
+        check(unsafe { sensor_get_name(self.handle, &mut name) })?;
+
+        unsafe { take_string(name) }.ok_or(Error::NoData)
+    }
+
+    pub fn get_vendor(&self) -> Result<String> {
+        let mut vendor = null_mut();
+        check(unsafe { sensor_get_vendor(self.handle, &mut vendor) })?;
+
+        unsafe { take_string(vendor) }.ok_or(Error::NoData)
+    }
+
+    /// The smallest and largest values it can measure.
+    pub fn get_range(&self) -> Result<(f32, f32)> {
+        let (mut min, mut max) = (0.0, 0.0);
+        check(unsafe { sensor_get_min_range(self.handle, &mut min) })?;
+        check(unsafe { sensor_get_max_range(self.handle, &mut max) })?;
+
+        Ok((min, max))
+    }
+
+    pub fn get_resolution(&self) -> Result<f32> {
+        let mut resolution = 0.0;
+        check(unsafe { sensor_get_resolution(self.handle, &mut resolution) })?;
+
+        Ok(resolution)
+    }
+
+    /// The shortest interval between readings, in milliseconds.
+    pub fn get_min_interval(&self) -> Result<i32> {
+        let mut interval = 0;
+        check(unsafe { sensor_get_min_interval(self.handle, &mut interval) })?;
+
+        Ok(interval)
+    }
+}
+
+type EventFn = Box<dyn FnMut(SensorEvent)>;
+
+/// Receives the readings of a sensor, destroyed when dropped.
+pub struct SensorListener {
+    handle: sensor_listener_h,
+    event_fn: Option<Pin<Box<EventFn>>>,
+}
+
+impl SensorListener {
+    pub fn new(sensor: &Sensor) -> Result<SensorListener> {
+        let mut handle = null_mut();
+        check(unsafe { sensor_create_listener(sensor.handle, &mut handle) })?;
+
+        Ok(Self {
+            handle,
+            event_fn: None,
+        })
+    }
+
+    /// Returns a stream of the readings of the default sensor of `sensor_type`, every
+    /// `interval_ms` milliseconds.
+    #[cfg(feature = "async")]
+    pub fn events(sensor_type: SensorType, interval_ms: u32) -> Result<EventStream<SensorEvent>> {
+        EventStream::new(|sender| {
+            let mut listener = SensorListener::new(&Sensor::default_for(sensor_type)?)?;
+            listener.set_event_cb(interval_ms, move |event| sender.send(event))?;
+            check(unsafe { sensor_listener_start(listener.handle) })?;
+
+            Ok(StartedListener(listener))
+        })
+    }
+
+    /// Starts the sensor, the event callback is called until the returned guard is dropped.
+    pub fn start(&self) -> Result<StartedSensor<'_>> {
+        check(unsafe { sensor_listener_start(self.handle) })?;
+
+        Ok(StartedSensor { listener: self })
+    }
+
+    /// Calls `callback_fn` with a reading every `interval_ms` milliseconds, replacing the
+    /// previous callback.
+    pub fn set_event_cb<F>(&mut self, interval_ms: u32, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(SensorEvent),
+        F: 'static,
+    {
+        let (callback_fn, data) = pin_callback::<dyn FnMut(SensorEvent)>(Box::new(callback_fn));
+
+        check(unsafe {
+            sensor_listener_set_event_cb(self.handle, interval_ms, Some(event_handler), data)
+        })?;
+        self.event_fn = Some(callback_fn);
+
+        Ok(())
+    }
+
+    pub fn unset_event_cb(&mut self) -> Result<()> {
+        check(unsafe { sensor_listener_unset_event_cb(self.handle) })?;
+        self.event_fn = None;
+
+        Ok(())
+    }
+
+    pub fn set_interval(&self, interval_ms: u32) -> Result<()> {
+        check(unsafe { sensor_listener_set_interval(self.handle, interval_ms) })
+    }
+
+    /// Whether to keep receiving readings while the display is off, which drains the battery.
+    pub fn set_always_on(&self, always_on: bool) -> Result<()> {
+        let option = if always_on {
+            sensor_option_e_SENSOR_OPTION_ALWAYS_ON
+        } else {
+            sensor_option_e_SENSOR_OPTION_DEFAULT
+        };
+
+        check(unsafe { sensor_listener_set_option(self.handle, option) })
+    }
+
+    /// The latest reading of a started sensor.
+    pub fn read(&self) -> Result<SensorEvent> {
+        let mut event: sensor_event_s = unsafe { std::mem::zeroed() };
+        check(unsafe { sensor_listener_read_data(self.handle, &mut event) })?;
+
+        Ok(SensorEvent::from(&event))
+    }
+}
+
+impl Drop for SensorListener {
+    fn drop(&mut self) {
+        unsafe {
+            // also unsets the callback
+            sensor_destroy_listener(self.handle);
+        }
+    }
+}
+
+/// A started sensor, stopped when dropped.
+pub struct StartedSensor<'l> {
+    listener: &'l SensorListener,
+}
+
+impl<'l> Drop for StartedSensor<'l> {
+    fn drop(&mut self) {
+        unsafe {
+            sensor_listener_stop(self.listener.handle);
+        }
+    }
+}
+
+/// The listener of a stream, stopped when dropped.
+#[cfg(feature = "async")]
+struct StartedListener(SensorListener);
+
+#[cfg(feature = "async")]
+impl Drop for StartedListener {
+    fn drop(&mut self) {
+        unsafe {
+            sensor_listener_stop(self.0.handle);
+        }
+    }
+}
+
+extern "C" fn event_handler(_sensor: sensor_h, event: *mut sensor_event_s, user_data: *mut c_void) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut EventFn) };
+
+        if let Some(event) = unsafe { event.as_ref() } {
+            closure(SensorEvent::from(event));
+        }
+    })
+}
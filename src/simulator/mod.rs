@@ -0,0 +1,117 @@
+//! Simulated platform for running the crate off the device, e.g. in unit tests.
+//!
+//! The `system`, `sensor` and `network` modules behave as on a round watch connected to
+//! Wi-Fi, and the functions below change what they report. Each thread simulates its own
+//! device, so tests running in parallel do not see each other's changes.
+//!
+//! With the `async` feature, `run_until_idle` stands in for the main loop.
+
+pub(crate) mod sys;
+
+#[cfg(feature = "system")]
+use crate::error::Error;
+#[cfg(any(feature = "network", feature = "system"))]
+use crate::error::Result;
+#[cfg(feature = "network")]
+use crate::network::connection::{CellularState, ConnectionType, ProxyType, WifiState};
+#[cfg(any(feature = "network", feature = "system"))]
+use crate::rutin::cstring;
+#[cfg(feature = "sensor")]
+use crate::sensor::SensorType;
+#[cfg(feature = "system")]
+use sys::info::PlatformValue;
+
+/// Sets a boolean key of [`crate::system::info`], e.g. `http://tizen.org/feature/screen.shape.circle`.
+#[cfg(feature = "system")]
+pub fn set_platform_bool(key: &str, value: bool) {
+    sys::info::set_platform(key, PlatformValue::Bool(value));
+}
+
+#[cfg(feature = "system")]
+pub fn set_platform_int(key: &str, value: i32) {
+    sys::info::set_platform(key, PlatformValue::Int(value));
+}
+
+#[cfg(feature = "system")]
+pub fn set_platform_string(key: &str, value: &str) -> Result<()> {
+    sys::info::set_platform(key, PlatformValue::String(cstring(value)?));
+
+    Ok(())
+}
+
+/// Sets the charge of the battery, notifying the battery listener of the new percentage and
+/// level.
+///
+/// Fails with [`Error::InvalidParameter`] if `percent` is not between 0 and 100.
+#[cfg(feature = "system")]
+pub fn set_battery_percent(percent: i32) -> Result<()> {
+    if !(0..=100).contains(&percent) {
+        return Err(Error::InvalidParameter);
+    }
+
+    sys::battery::set_percent(percent);
+
+    Ok(())
+}
+
+#[cfg(feature = "system")]
+pub fn set_battery_charging(charging: bool) {
+    sys::battery::set_charging(charging);
+}
+
+/// All the sensors are supported until set otherwise.
+#[cfg(feature = "sensor")]
+pub fn set_sensor_supported(sensor_type: SensorType, supported: bool) {
+    sys::sensor::set_supported(sensor_type.into(), supported);
+}
+
+/// Delivers a reading to the started listeners of the sensor, and keeps it as the latest one.
+///
+/// Up to 16 values are kept, as on the device.
+#[cfg(feature = "sensor")]
+pub fn emit_sensor_event(sensor_type: SensorType, values: &[f32]) {
+    sys::sensor::emit(sensor_type.into(), values);
+}
+
+/// Sets the network used for data, notifying the connections if it changed.
+#[cfg(feature = "network")]
+pub fn set_connection_type(connection_type: ConnectionType) {
+    sys::connection::set_type(connection_type.into());
+}
+
+#[cfg(feature = "network")]
+pub fn set_wifi_state(state: WifiState) {
+    sys::connection::set_wifi_state(state.into());
+}
+
+#[cfg(feature = "network")]
+pub fn set_cellular_state(state: CellularState) {
+    sys::connection::set_cellular_state(state.into());
+}
+
+/// Sets the addresses of the current connection, notifying the connections.
+#[cfg(feature = "network")]
+pub fn set_ip_addresses(ipv4: Option<&str>, ipv6: Option<&str>) -> Result<()> {
+    sys::connection::set_ip_addresses(
+        ipv4.map(cstring).transpose()?,
+        ipv6.map(cstring).transpose()?,
+    );
+
+    Ok(())
+}
+
+/// Sets the proxy of the current connection, as `host:port` or the PAC script URL for
+/// [`ProxyType::Auto`].
+#[cfg(feature = "network")]
+pub fn set_proxy(proxy_type: ProxyType, address: Option<&str>) -> Result<()> {
+    sys::connection::set_proxy(proxy_type.into(), address.map(cstring).transpose()?);
+
+    Ok(())
+}
+
+/// Runs the calls queued on the main loop of the calling thread, e.g. the wakes of the tasks
+/// it spawned, until none is left. The tasks may be woken from any thread.
+#[cfg(feature = "async")]
+pub fn run_until_idle() {
+    sys::ecore::run_main_loop();
+}
@@ -0,0 +1,168 @@
+use super::{
+    tizen_error_e_TIZEN_ERROR_INVALID_PARAMETER, tizen_error_e_TIZEN_ERROR_NONE, write_out,
+};
+use std::cell::RefCell;
+use std::os::raw::{c_int, c_uint, c_void};
+
+pub type device_battery_level_e = c_uint;
+
+pub const device_battery_level_e_DEVICE_BATTERY_LEVEL_EMPTY: device_battery_level_e = 0;
+pub const device_battery_level_e_DEVICE_BATTERY_LEVEL_CRITICAL: device_battery_level_e = 1;
+pub const device_battery_level_e_DEVICE_BATTERY_LEVEL_LOW: device_battery_level_e = 2;
+pub const device_battery_level_e_DEVICE_BATTERY_LEVEL_HIGH: device_battery_level_e = 3;
+pub const device_battery_level_e_DEVICE_BATTERY_LEVEL_FULL: device_battery_level_e = 4;
+
+pub type device_callback_e = c_uint;
+
+pub const device_callback_e_DEVICE_CALLBACK_BATTERY_CAPACITY: device_callback_e = 0;
+pub const device_callback_e_DEVICE_CALLBACK_BATTERY_LEVEL: device_callback_e = 1;
+pub const device_callback_e_DEVICE_CALLBACK_BATTERY_CHARGING: device_callback_e = 2;
+
+pub type device_changed_cb = Option<
+    unsafe extern "C" fn(callback: device_callback_e, value: *mut c_void, user_data: *mut c_void),
+>;
+
+type Callback = unsafe extern "C" fn(device_callback_e, *mut c_void, *mut c_void);
+
+struct Battery {
+    percent: c_int,
+    charging: bool,
+    callbacks: Vec<(device_callback_e, Callback, *mut c_void)>,
+}
+
+thread_local! {
+    static BATTERY: RefCell<Battery> = RefCell::new(Battery {
+        percent: 80,
+        charging: false,
+        callbacks: Vec::new(),
+    });
+}
+
+fn level(percent: c_int) -> device_battery_level_e {
+    match percent {
+        0 => device_battery_level_e_DEVICE_BATTERY_LEVEL_EMPTY,
+        1..=5 => device_battery_level_e_DEVICE_BATTERY_LEVEL_CRITICAL,
+        6..=15 => device_battery_level_e_DEVICE_BATTERY_LEVEL_LOW,
+        16..=99 => device_battery_level_e_DEVICE_BATTERY_LEVEL_HIGH,
+        _ => device_battery_level_e_DEVICE_BATTERY_LEVEL_FULL,
+    }
+}
+
+/// Calls the callbacks added for `callback`, with the value passed in the pointer itself.
+fn notify(callback: device_callback_e, value: isize) {
+    let registered = BATTERY.with(|battery| battery.borrow().callbacks.clone());
+
+    for entry in registered.into_iter().filter(|(c, _, _)| *c == callback) {
+        // a previous callback may have removed this one
+        let (_, function, user_data) = entry;
+        let is_added = BATTERY.with(|battery| {
+            battery.borrow().callbacks.iter().any(|(c, f, d)| {
+                *c == callback && *f as usize == function as usize && *d == user_data
+            })
+        });
+
+        if is_added {
+            unsafe { function(callback, value as *mut c_void, user_data) };
+        }
+    }
+}
+
+pub(in crate::simulator) fn set_percent(percent: c_int) {
+    let previous =
+        BATTERY.with(|battery| std::mem::replace(&mut battery.borrow_mut().percent, percent));
+
+    if previous != percent {
+        notify(
+            device_callback_e_DEVICE_CALLBACK_BATTERY_CAPACITY,
+            percent as isize,
+        );
+
+        if level(previous) != level(percent) {
+            notify(
+                device_callback_e_DEVICE_CALLBACK_BATTERY_LEVEL,
+                level(percent) as isize,
+            );
+        }
+    }
+}
+
+pub(in crate::simulator) fn set_charging(charging: bool) {
+    let previous =
+        BATTERY.with(|battery| std::mem::replace(&mut battery.borrow_mut().charging, charging));
+
+    if previous != charging {
+        notify(
+            device_callback_e_DEVICE_CALLBACK_BATTERY_CHARGING,
+            charging as isize,
+        );
+    }
+}
+
+pub unsafe extern "C" fn device_battery_get_percent(percent: *mut c_int) -> c_int {
+    write_out(percent, BATTERY.with(|battery| battery.borrow().percent))
+}
+
+pub unsafe extern "C" fn device_battery_is_charging(charging: *mut bool) -> c_int {
+    write_out(charging, BATTERY.with(|battery| battery.borrow().charging))
+}
+
+pub unsafe extern "C" fn device_battery_get_level_status(
+    status: *mut device_battery_level_e,
+) -> c_int {
+    write_out(
+        status,
+        BATTERY.with(|battery| level(battery.borrow().percent)),
+    )
+}
+
+pub unsafe extern "C" fn device_add_callback(
+    callback_type: device_callback_e,
+    callback: device_changed_cb,
+    user_data: *mut c_void,
+) -> c_int {
+    match (callback_type, callback) {
+        (
+            device_callback_e_DEVICE_CALLBACK_BATTERY_CAPACITY
+            | device_callback_e_DEVICE_CALLBACK_BATTERY_LEVEL
+            | device_callback_e_DEVICE_CALLBACK_BATTERY_CHARGING,
+            Some(callback),
+        ) => {
+            BATTERY.with(|battery| {
+                battery
+                    .borrow_mut()
+                    .callbacks
+                    .push((callback_type, callback, user_data))
+            });
+
+            tizen_error_e_TIZEN_ERROR_NONE
+        }
+        _ => tizen_error_e_TIZEN_ERROR_INVALID_PARAMETER,
+    }
+}
+
+pub unsafe extern "C" fn device_remove_callback(
+    callback_type: device_callback_e,
+    callback: device_changed_cb,
+) -> c_int {
+    let callback = match callback {
+        Some(callback) => callback as usize,
+        None => return tizen_error_e_TIZEN_ERROR_INVALID_PARAMETER,
+    };
+
+    // the state is already gone when a listener is dropped during the thread exit
+    let removed = BATTERY
+        .try_with(|battery| {
+            let callbacks = &mut battery.borrow_mut().callbacks;
+            let len = callbacks.len();
+            callbacks.retain(|(c, f, _)| !(*c == callback_type && *f as usize == callback));
+
+            callbacks.len() != len
+        })
+        .unwrap_or(true);
+
+    if removed {
+        tizen_error_e_TIZEN_ERROR_NONE
+    } else {
+        tizen_error_e_TIZEN_ERROR_INVALID_PARAMETER
+    }
+}
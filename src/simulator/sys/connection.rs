@@ -0,0 +1,466 @@
+use super::{
+    strdup, tizen_error_e_TIZEN_ERROR_INVALID_PARAMETER, tizen_error_e_TIZEN_ERROR_NONE,
+    tizen_error_e_TIZEN_ERROR_NO_DATA, write_out,
+};
+use std::cell::RefCell;
+use std::ffi::CString;
+use std::os::raw::{c_char, c_int, c_uint, c_void};
+use std::ptr::{null, null_mut};
+
+pub type connection_type_e = c_uint;
+
+pub const connection_type_e_CONNECTION_TYPE_DISCONNECTED: connection_type_e = 0;
+pub const connection_type_e_CONNECTION_TYPE_WIFI: connection_type_e = 1;
+pub const connection_type_e_CONNECTION_TYPE_CELLULAR: connection_type_e = 2;
+pub const connection_type_e_CONNECTION_TYPE_ETHERNET: connection_type_e = 3;
+pub const connection_type_e_CONNECTION_TYPE_BT: connection_type_e = 4;
+pub const connection_type_e_CONNECTION_TYPE_NET_PROXY: connection_type_e = 5;
+
+pub type connection_cellular_state_e = c_uint;
+
+pub const connection_cellular_state_e_CONNECTION_CELLULAR_STATE_OUT_OF_SERVICE:
+    connection_cellular_state_e = 0;
+pub const connection_cellular_state_e_CONNECTION_CELLULAR_STATE_FLIGHT_MODE:
+    connection_cellular_state_e = 1;
+pub const connection_cellular_state_e_CONNECTION_CELLULAR_STATE_ROAMING_OFF:
+    connection_cellular_state_e = 2;
+pub const connection_cellular_state_e_CONNECTION_CELLULAR_STATE_CALL_ONLY_AVAILABLE:
+    connection_cellular_state_e = 3;
+pub const connection_cellular_state_e_CONNECTION_CELLULAR_STATE_AVAILABLE:
+    connection_cellular_state_e = 4;
+pub const connection_cellular_state_e_CONNECTION_CELLULAR_STATE_CONNECTED:
+    connection_cellular_state_e = 5;
+
+pub type connection_wifi_state_e = c_uint;
+
+pub const connection_wifi_state_e_CONNECTION_WIFI_STATE_DEACTIVATED: connection_wifi_state_e = 0;
+pub const connection_wifi_state_e_CONNECTION_WIFI_STATE_DISCONNECTED: connection_wifi_state_e = 1;
+pub const connection_wifi_state_e_CONNECTION_WIFI_STATE_CONNECTED: connection_wifi_state_e = 2;
+
+pub type connection_address_family_e = c_uint;
+
+pub const connection_address_family_e_CONNECTION_ADDRESS_FAMILY_IPV4: connection_address_family_e =
+    0;
+pub const connection_address_family_e_CONNECTION_ADDRESS_FAMILY_IPV6: connection_address_family_e =
+    1;
+
+pub type connection_proxy_type_e = c_uint;
+
+pub const connection_proxy_type_e_CONNECTION_PROXY_TYPE_DIRECT: connection_proxy_type_e = 0;
+pub const connection_proxy_type_e_CONNECTION_PROXY_TYPE_AUTO: connection_proxy_type_e = 1;
+pub const connection_proxy_type_e_CONNECTION_PROXY_TYPE_MANUAL: connection_proxy_type_e = 2;
+
+pub type connection_type_changed_cb =
+    Option<unsafe extern "C" fn(connection_type: connection_type_e, user_data: *mut c_void)>;
+pub type connection_address_changed_cb = Option<
+    unsafe extern "C" fn(
+        ipv4_address: *const c_char,
+        ipv6_address: *const c_char,
+        user_data: *mut c_void,
+    ),
+>;
+
+pub struct connection_s {
+    type_changed: Option<(
+        unsafe extern "C" fn(connection_type_e, *mut c_void),
+        *mut c_void,
+    )>,
+    address_changed: Option<(
+        unsafe extern "C" fn(*const c_char, *const c_char, *mut c_void),
+        *mut c_void,
+    )>,
+}
+
+pub type connection_h = *mut connection_s;
+
+/// A snapshot of the current network, taken by [`connection_get_current_profile`].
+pub struct connection_profile_s {
+    interface_name: CString,
+    ipv4: Option<CString>,
+    ipv6: Option<CString>,
+    proxy_type: connection_proxy_type_e,
+    proxy: Option<CString>,
+}
+
+pub type connection_profile_h = *mut connection_profile_s;
+
+struct Network {
+    connection_type: connection_type_e,
+    wifi_state: connection_wifi_state_e,
+    cellular_state: connection_cellular_state_e,
+    ipv4: Option<CString>,
+    ipv6: Option<CString>,
+    mac: CString,
+    proxy_type: connection_proxy_type_e,
+    proxy: Option<CString>,
+    connections: Vec<connection_h>,
+}
+
+thread_local! {
+    // a watch connected to Wi-Fi, without a SIM
+    static NETWORK: RefCell<Network> = RefCell::new(Network {
+        connection_type: connection_type_e_CONNECTION_TYPE_WIFI,
+        wifi_state: connection_wifi_state_e_CONNECTION_WIFI_STATE_CONNECTED,
+        cellular_state: connection_cellular_state_e_CONNECTION_CELLULAR_STATE_OUT_OF_SERVICE,
+        ipv4: Some(CString::new("10.0.2.15").unwrap()),
+        ipv6: None,
+        mac: CString::new("02:00:00:00:00:01").unwrap(),
+        proxy_type: connection_proxy_type_e_CONNECTION_PROXY_TYPE_DIRECT,
+        proxy: None,
+        connections: Vec::new(),
+    });
+}
+
+/// Returns the callback of each existing connection, looked up on each step since a callback
+/// may replace the others or destroy their connection.
+fn callbacks<T>(select: impl Fn(&connection_s) -> Option<T>) -> impl Iterator<Item = T> {
+    let connections = NETWORK.with(|network| network.borrow().connections.clone());
+
+    connections.into_iter().filter_map(move |connection| {
+        if is_alive(connection) {
+            select(unsafe { &*connection })
+        } else {
+            None
+        }
+    })
+}
+
+fn is_alive(connection: connection_h) -> bool {
+    // the state is already gone when a connection is dropped during the thread exit
+    NETWORK
+        .try_with(|network| network.borrow().connections.contains(&connection))
+        .unwrap_or(true)
+}
+
+pub(in crate::simulator) fn set_type(connection_type: connection_type_e) {
+    let changed = NETWORK.with(|network| {
+        let mut network = network.borrow_mut();
+        let previous = std::mem::replace(&mut network.connection_type, connection_type);
+
+        previous != connection_type
+    });
+
+    if changed {
+        for (callback, user_data) in callbacks(|c| c.type_changed) {
+            unsafe { callback(connection_type, user_data) };
+        }
+    }
+}
+
+pub(in crate::simulator) fn set_wifi_state(state: connection_wifi_state_e) {
+    NETWORK.with(|network| network.borrow_mut().wifi_state = state);
+}
+
+pub(in crate::simulator) fn set_cellular_state(state: connection_cellular_state_e) {
+    NETWORK.with(|network| network.borrow_mut().cellular_state = state);
+}
+
+pub(in crate::simulator) fn set_ip_addresses(ipv4: Option<CString>, ipv6: Option<CString>) {
+    let (ipv4, ipv6) = NETWORK.with(|network| {
+        let mut network = network.borrow_mut();
+        network.ipv4 = ipv4;
+        network.ipv6 = ipv6;
+
+        (network.ipv4.clone(), network.ipv6.clone())
+    });
+
+    let as_ptr = |address: &Option<CString>| address.as_ref().map_or(null(), |a| a.as_ptr());
+    for (callback, user_data) in callbacks(|c| c.address_changed) {
+        unsafe { callback(as_ptr(&ipv4), as_ptr(&ipv6), user_data) };
+    }
+}
+
+pub(in crate::simulator) fn set_proxy(proxy_type: connection_proxy_type_e, proxy: Option<CString>) {
+    NETWORK.with(|network| {
+        let mut network = network.borrow_mut();
+        network.proxy_type = proxy_type;
+        network.proxy = proxy;
+    });
+}
+
+pub unsafe extern "C" fn connection_create(connection: *mut connection_h) -> c_int {
+    if connection.is_null() {
+        return tizen_error_e_TIZEN_ERROR_INVALID_PARAMETER;
+    }
+
+    let handle = Box::into_raw(Box::new(connection_s {
+        type_changed: None,
+        address_changed: None,
+    }));
+    NETWORK.with(|network| network.borrow_mut().connections.push(handle));
+
+    write_out(connection, handle)
+}
+
+/// Runs `f` on a connection created by [`connection_create`] and not destroyed yet.
+unsafe fn with_connection(
+    connection: connection_h,
+    f: impl FnOnce(&mut connection_s, &Network) -> c_int,
+) -> c_int {
+    if !is_alive(connection) {
+        return tizen_error_e_TIZEN_ERROR_INVALID_PARAMETER;
+    }
+
+    NETWORK.with(|network| f(&mut *connection, &network.borrow()))
+}
+
+pub unsafe extern "C" fn connection_destroy(connection: connection_h) -> c_int {
+    if !is_alive(connection) {
+        return tizen_error_e_TIZEN_ERROR_INVALID_PARAMETER;
+    }
+
+    let _ = NETWORK.try_with(|network| {
+        network
+            .borrow_mut()
+            .connections
+            .retain(|c| *c != connection)
+    });
+    drop(Box::from_raw(connection));
+
+    tizen_error_e_TIZEN_ERROR_NONE
+}
+
+pub unsafe extern "C" fn connection_set_type_changed_cb(
+    connection: connection_h,
+    callback: connection_type_changed_cb,
+    user_data: *mut c_void,
+) -> c_int {
+    match callback {
+        Some(callback) => with_connection(connection, |connection, _| {
+            connection.type_changed = Some((callback, user_data));
+            tizen_error_e_TIZEN_ERROR_NONE
+        }),
+        None => tizen_error_e_TIZEN_ERROR_INVALID_PARAMETER,
+    }
+}
+
+pub unsafe extern "C" fn connection_unset_type_changed_cb(connection: connection_h) -> c_int {
+    with_connection(connection, |connection, _| {
+        connection.type_changed = None;
+        tizen_error_e_TIZEN_ERROR_NONE
+    })
+}
+
+pub unsafe extern "C" fn connection_set_ip_address_changed_cb(
+    connection: connection_h,
+    callback: connection_address_changed_cb,
+    user_data: *mut c_void,
+) -> c_int {
+    match callback {
+        Some(callback) => with_connection(connection, |connection, _| {
+            connection.address_changed = Some((callback, user_data));
+            tizen_error_e_TIZEN_ERROR_NONE
+        }),
+        None => tizen_error_e_TIZEN_ERROR_INVALID_PARAMETER,
+    }
+}
+
+pub unsafe extern "C" fn connection_unset_ip_address_changed_cb(connection: connection_h) -> c_int {
+    with_connection(connection, |connection, _| {
+        connection.address_changed = None;
+        tizen_error_e_TIZEN_ERROR_NONE
+    })
+}
+
+pub unsafe extern "C" fn connection_get_type(
+    connection: connection_h,
+    connection_type: *mut connection_type_e,
+) -> c_int {
+    with_connection(connection, |_, network| {
+        write_out(connection_type, network.connection_type)
+    })
+}
+
+pub unsafe extern "C" fn connection_get_cellular_state(
+    connection: connection_h,
+    state: *mut connection_cellular_state_e,
+) -> c_int {
+    with_connection(connection, |_, network| {
+        write_out(state, network.cellular_state)
+    })
+}
+
+pub unsafe extern "C" fn connection_get_wifi_state(
+    connection: connection_h,
+    state: *mut connection_wifi_state_e,
+) -> c_int {
+    with_connection(connection, |_, network| {
+        write_out(state, network.wifi_state)
+    })
+}
+
+fn by_family(
+    family: connection_address_family_e,
+    ipv4: &Option<CString>,
+    ipv6: &Option<CString>,
+) -> Option<*mut c_char> {
+    match family {
+        connection_address_family_e_CONNECTION_ADDRESS_FAMILY_IPV4 => {
+            Some(unsafe { strdup(ipv4.as_deref()) })
+        }
+        connection_address_family_e_CONNECTION_ADDRESS_FAMILY_IPV6 => {
+            Some(unsafe { strdup(ipv6.as_deref()) })
+        }
+        _ => None,
+    }
+}
+
+pub unsafe extern "C" fn connection_get_ip_address(
+    connection: connection_h,
+    family: connection_address_family_e,
+    ip_address: *mut *mut c_char,
+) -> c_int {
+    with_connection(connection, |_, network| {
+        match by_family(family, &network.ipv4, &network.ipv6) {
+            Some(address) => write_out(ip_address, address),
+            None => tizen_error_e_TIZEN_ERROR_INVALID_PARAMETER,
+        }
+    })
+}
+
+pub unsafe extern "C" fn connection_get_proxy(
+    connection: connection_h,
+    family: connection_address_family_e,
+    proxy: *mut *mut c_char,
+) -> c_int {
+    with_connection(connection, |_, network| {
+        match by_family(family, &network.proxy, &network.proxy) {
+            Some(address) => write_out(proxy, address),
+            None => tizen_error_e_TIZEN_ERROR_INVALID_PARAMETER,
+        }
+    })
+}
+
+pub unsafe extern "C" fn connection_get_mac_address(
+    connection: connection_h,
+    connection_type: connection_type_e,
+    mac_address: *mut *mut c_char,
+) -> c_int {
+    with_connection(connection, |_, network| match connection_type {
+        connection_type_e_CONNECTION_TYPE_WIFI | connection_type_e_CONNECTION_TYPE_ETHERNET => {
+            write_out(mac_address, strdup(Some(&network.mac)))
+        }
+        _ => tizen_error_e_TIZEN_ERROR_INVALID_PARAMETER,
+    })
+}
+
+pub unsafe extern "C" fn connection_get_current_profile(
+    connection: connection_h,
+    profile: *mut connection_profile_h,
+) -> c_int {
+    if profile.is_null() {
+        return tizen_error_e_TIZEN_ERROR_INVALID_PARAMETER;
+    }
+
+    with_connection(connection, |_, network| {
+        let interface_name = match network.connection_type {
+            connection_type_e_CONNECTION_TYPE_WIFI => "wlan0",
+            connection_type_e_CONNECTION_TYPE_CELLULAR => "rmnet0",
+            connection_type_e_CONNECTION_TYPE_ETHERNET => "eth0",
+            connection_type_e_CONNECTION_TYPE_BT => "bnep0",
+            connection_type_e_CONNECTION_TYPE_NET_PROXY => "sap0",
+            _ => return tizen_error_e_TIZEN_ERROR_NO_DATA,
+        };
+
+        let handle = Box::into_raw(Box::new(connection_profile_s {
+            interface_name: CString::new(interface_name).unwrap(),
+            ipv4: network.ipv4.clone(),
+            ipv6: network.ipv6.clone(),
+            proxy_type: network.proxy_type,
+            proxy: network.proxy.clone(),
+        }));
+
+        write_out(profile, handle)
+    })
+}
+
+pub unsafe extern "C" fn connection_profile_destroy(profile: connection_profile_h) -> c_int {
+    if profile.is_null() {
+        return tizen_error_e_TIZEN_ERROR_INVALID_PARAMETER;
+    }
+
+    drop(Box::from_raw(profile));
+    tizen_error_e_TIZEN_ERROR_NONE
+}
+
+pub unsafe extern "C" fn connection_profile_get_network_interface_name(
+    profile: connection_profile_h,
+    interface_name: *mut *mut c_char,
+) -> c_int {
+    match profile.as_ref() {
+        Some(profile) => write_out(interface_name, strdup(Some(&profile.interface_name))),
+        None => tizen_error_e_TIZEN_ERROR_INVALID_PARAMETER,
+    }
+}
+
+pub unsafe extern "C" fn connection_profile_get_ip_address(
+    profile: connection_profile_h,
+    family: connection_address_family_e,
+    ip_address: *mut *mut c_char,
+) -> c_int {
+    match profile
+        .as_ref()
+        .and_then(|profile| by_family(family, &profile.ipv4, &profile.ipv6))
+    {
+        Some(address) => write_out(ip_address, address),
+        None => tizen_error_e_TIZEN_ERROR_INVALID_PARAMETER,
+    }
+}
+
+/// Writes an empty address, for the settings the simulator does not keep.
+unsafe fn no_address(profile: connection_profile_h, address: *mut *mut c_char) -> c_int {
+    if profile.is_null() {
+        return tizen_error_e_TIZEN_ERROR_INVALID_PARAMETER;
+    }
+
+    write_out(address, null_mut())
+}
+
+pub unsafe extern "C" fn connection_profile_get_subnet_mask(
+    profile: connection_profile_h,
+    _family: connection_address_family_e,
+    subnet_mask: *mut *mut c_char,
+) -> c_int {
+    no_address(profile, subnet_mask)
+}
+
+pub unsafe extern "C" fn connection_profile_get_gateway_address(
+    profile: connection_profile_h,
+    _family: connection_address_family_e,
+    gateway_address: *mut *mut c_char,
+) -> c_int {
+    no_address(profile, gateway_address)
+}
+
+pub unsafe extern "C" fn connection_profile_get_dns_address(
+    profile: connection_profile_h,
+    _order: c_int,
+    _family: connection_address_family_e,
+    dns_address: *mut *mut c_char,
+) -> c_int {
+    no_address(profile, dns_address)
+}
+
+pub unsafe extern "C" fn connection_profile_get_proxy_type(
+    profile: connection_profile_h,
+    proxy_type: *mut connection_proxy_type_e,
+) -> c_int {
+    match profile.as_ref() {
+        Some(profile) => write_out(proxy_type, profile.proxy_type),
+        None => tizen_error_e_TIZEN_ERROR_INVALID_PARAMETER,
+    }
+}
+
+pub unsafe extern "C" fn connection_profile_get_proxy_address(
+    profile: connection_profile_h,
+    family: connection_address_family_e,
+    proxy_address: *mut *mut c_char,
+) -> c_int {
+    match profile
+        .as_ref()
+        .and_then(|profile| by_family(family, &profile.proxy, &profile.proxy))
+    {
+        Some(address) => write_out(proxy_address, address),
+        None => tizen_error_e_TIZEN_ERROR_INVALID_PARAMETER,
+    }
+}
@@ -0,0 +1,53 @@
+use std::os::raw::c_void;
+use std::sync::Mutex;
+use std::thread::{self, ThreadId};
+
+struct Call {
+    thread: ThreadId,
+    callback: extern "C" fn(data: *mut c_void),
+    data: usize,
+}
+
+static CALLS: Mutex<Vec<Call>> = Mutex::new(Vec::new());
+
+/// Queues `callback` on the main loop of `thread`, from any thread.
+///
+/// Stands in for `ecore_main_loop_thread_safe_call_async`: each thread simulates its own
+/// device, so the caller names the main loop, which the real call doesn't need.
+pub fn call_async_on(
+    thread: ThreadId,
+    callback: extern "C" fn(data: *mut c_void),
+    data: *mut c_void,
+) {
+    CALLS.lock().unwrap().push(Call {
+        thread,
+        callback,
+        data: data as usize,
+    });
+}
+
+/// Runs the queued calls of the calling thread until none is left.
+pub(in crate::simulator) fn run_main_loop() {
+    let current = thread::current().id();
+
+    loop {
+        let ready = {
+            let mut calls = CALLS.lock().unwrap();
+
+            let (ready, pending): (Vec<Call>, Vec<Call>) =
+                calls.drain(..).partition(|call| call.thread == current);
+            *calls = pending;
+
+            ready
+        };
+
+        if ready.is_empty() {
+            break;
+        }
+
+        // the calls may queue others, run on the next round
+        for call in ready {
+            (call.callback)(call.data as *mut c_void);
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use super::{
+    strdup, tizen_error_e_TIZEN_ERROR_INVALID_PARAMETER, tizen_error_e_TIZEN_ERROR_NONE, write_out,
+};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::ffi::{CStr, CString};
+use std::os::raw::{c_char, c_int};
+
+pub(in crate::simulator) enum PlatformValue {
+    Bool(bool),
+    Int(c_int),
+    String(CString),
+}
+
+thread_local! {
+    // the keys read by the crate, as on a round watch
+    static PLATFORM: RefCell<HashMap<String, PlatformValue>> = RefCell::new(
+        vec![
+            ("http://tizen.org/feature/profile", PlatformValue::String(c("wearable"))),
+            ("http://tizen.org/feature/screen.width", PlatformValue::Int(360)),
+            ("http://tizen.org/feature/screen.height", PlatformValue::Int(360)),
+            ("http://tizen.org/feature/screen.dpi", PlatformValue::Int(302)),
+            ("http://tizen.org/feature/screen.bpp", PlatformValue::Int(32)),
+            ("http://tizen.org/feature/screen.shape.circle", PlatformValue::Bool(true)),
+            ("http://tizen.org/feature/screen.shape.rectangle", PlatformValue::Bool(false)),
+        ]
+        .into_iter()
+        .map(|(key, value)| (key.to_string(), value))
+        .collect()
+    );
+}
+
+fn c(value: &str) -> CString {
+    CString::new(value).unwrap()
+}
+
+pub(in crate::simulator) fn set_platform(key: &str, value: PlatformValue) {
+    PLATFORM.with(|platform| platform.borrow_mut().insert(key.to_string(), value));
+}
+
+/// Reads a key, failing like the native calls if it is missing or of another type.
+unsafe fn get<T>(key: *const c_char, read: impl FnOnce(&PlatformValue) -> Option<T>) -> Option<T> {
+    if key.is_null() {
+        return None;
+    }
+
+    let key = CStr::from_ptr(key).to_string_lossy();
+    PLATFORM.with(|platform| platform.borrow().get(key.as_ref()).and_then(read))
+}
+
+pub unsafe extern "C" fn system_info_get_platform_bool(
+    key: *const c_char,
+    value: *mut bool,
+) -> c_int {
+    match get(key, |platform_value| match platform_value {
+        PlatformValue::Bool(v) => Some(*v),
+        _ => None,
+    }) {
+        Some(v) => write_out(value, v),
+        None => tizen_error_e_TIZEN_ERROR_INVALID_PARAMETER,
+    }
+}
+
+pub unsafe extern "C" fn system_info_get_platform_int(
+    key: *const c_char,
+    value: *mut c_int,
+) -> c_int {
+    match get(key, |platform_value| match platform_value {
+        PlatformValue::Int(v) => Some(*v),
+        _ => None,
+    }) {
+        Some(v) => write_out(value, v),
+        None => tizen_error_e_TIZEN_ERROR_INVALID_PARAMETER,
+    }
+}
+
+pub unsafe extern "C" fn system_info_get_platform_string(
+    key: *const c_char,
+    value: *mut *mut c_char,
+) -> c_int {
+    if value.is_null() {
+        return tizen_error_e_TIZEN_ERROR_INVALID_PARAMETER;
+    }
+
+    match get(key, |platform_value| match platform_value {
+        PlatformValue::String(v) => Some(strdup(Some(v))),
+        _ => None,
+    }) {
+        Some(v) => {
+            *value = v;
+            tizen_error_e_TIZEN_ERROR_NONE
+        }
+        None => tizen_error_e_TIZEN_ERROR_INVALID_PARAMETER,
+    }
+}
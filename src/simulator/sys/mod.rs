@@ -0,0 +1,138 @@
+//! The fake of the native APIs used by the simulated modules, with the same names and
+//! signatures as their `rutin-tizen-sys` bindings.
+
+#![allow(non_camel_case_types, non_upper_case_globals)]
+
+#[cfg(feature = "system")]
+pub(super) mod battery;
+#[cfg(feature = "network")]
+pub(super) mod connection;
+#[cfg(feature = "async")]
+pub(super) mod ecore;
+#[cfg(feature = "system")]
+pub(super) mod info;
+#[cfg(feature = "sensor")]
+pub(super) mod sensor;
+
+#[cfg(feature = "system")]
+pub use battery::*;
+#[cfg(feature = "network")]
+pub use connection::*;
+#[cfg(feature = "async")]
+pub use ecore::*;
+#[cfg(feature = "system")]
+pub use info::*;
+#[cfg(feature = "sensor")]
+pub use sensor::*;
+
+use std::ffi::CStr;
+use std::os::raw::{c_char, c_int, c_uint};
+
+pub type tizen_error_e = c_int;
+
+pub const tizen_error_e_TIZEN_ERROR_NONE: tizen_error_e = 0;
+pub const tizen_error_e_TIZEN_ERROR_NOT_PERMITTED: tizen_error_e = -libc::EPERM;
+pub const tizen_error_e_TIZEN_ERROR_NO_SUCH_FILE: tizen_error_e = -libc::ENOENT;
+pub const tizen_error_e_TIZEN_ERROR_IO_ERROR: tizen_error_e = -libc::EIO;
+pub const tizen_error_e_TIZEN_ERROR_OUT_OF_MEMORY: tizen_error_e = -libc::ENOMEM;
+pub const tizen_error_e_TIZEN_ERROR_PERMISSION_DENIED: tizen_error_e = -libc::EACCES;
+pub const tizen_error_e_TIZEN_ERROR_RESOURCE_BUSY: tizen_error_e = -libc::EBUSY;
+pub const tizen_error_e_TIZEN_ERROR_INVALID_PARAMETER: tizen_error_e = -libc::EINVAL;
+pub const tizen_error_e_TIZEN_ERROR_NO_DATA: tizen_error_e = -libc::ENODATA;
+pub const tizen_error_e_TIZEN_ERROR_ALREADY_IN_PROGRESS: tizen_error_e = -libc::EALREADY;
+pub const tizen_error_e_TIZEN_ERROR_NOW_IN_PROGRESS: tizen_error_e = -libc::EINPROGRESS;
+pub const tizen_error_e_TIZEN_ERROR_INVALID_OPERATION: tizen_error_e = -libc::ENOSYS;
+pub const tizen_error_e_TIZEN_ERROR_CANCELED: tizen_error_e = -libc::ECANCELED;
+pub const tizen_error_e_TIZEN_ERROR_UNKNOWN: tizen_error_e = -0x4000_0000;
+pub const tizen_error_e_TIZEN_ERROR_TIMED_OUT: tizen_error_e =
+    tizen_error_e_TIZEN_ERROR_UNKNOWN + 1;
+pub const tizen_error_e_TIZEN_ERROR_NOT_SUPPORTED: tizen_error_e =
+    tizen_error_e_TIZEN_ERROR_UNKNOWN + 2;
+
+pub unsafe extern "C" fn get_error_message(code: c_int) -> *const c_char {
+    let message: &[u8] = match code {
+        tizen_error_e_TIZEN_ERROR_NONE => b"Successful\0",
+        tizen_error_e_TIZEN_ERROR_NOT_PERMITTED => b"Operation not permitted\0",
+        tizen_error_e_TIZEN_ERROR_NO_SUCH_FILE => b"No such file or directory\0",
+        tizen_error_e_TIZEN_ERROR_IO_ERROR => b"I/O error\0",
+        tizen_error_e_TIZEN_ERROR_OUT_OF_MEMORY => b"Out of memory\0",
+        tizen_error_e_TIZEN_ERROR_PERMISSION_DENIED => b"Permission denied\0",
+        tizen_error_e_TIZEN_ERROR_RESOURCE_BUSY => b"Device or resource busy\0",
+        tizen_error_e_TIZEN_ERROR_INVALID_PARAMETER => b"Invalid parameter\0",
+        tizen_error_e_TIZEN_ERROR_NO_DATA => b"No data available\0",
+        tizen_error_e_TIZEN_ERROR_ALREADY_IN_PROGRESS => b"Operation already in progress\0",
+        tizen_error_e_TIZEN_ERROR_NOW_IN_PROGRESS => b"Operation now in progress\0",
+        tizen_error_e_TIZEN_ERROR_INVALID_OPERATION => b"Function not implemented\0",
+        tizen_error_e_TIZEN_ERROR_CANCELED => b"Operation canceled\0",
+        tizen_error_e_TIZEN_ERROR_UNKNOWN => b"Unknown error\0",
+        tizen_error_e_TIZEN_ERROR_TIMED_OUT => b"Time out\0",
+        tizen_error_e_TIZEN_ERROR_NOT_SUPPORTED => b"Not supported\0",
+        _ => return std::ptr::null(),
+    };
+
+    message.as_ptr() as *const c_char
+}
+
+pub type log_priority = c_uint;
+
+pub const log_priority_DLOG_UNKNOWN: log_priority = 0;
+pub const log_priority_DLOG_DEFAULT: log_priority = 1;
+pub const log_priority_DLOG_VERBOSE: log_priority = 2;
+pub const log_priority_DLOG_DEBUG: log_priority = 3;
+pub const log_priority_DLOG_INFO: log_priority = 4;
+pub const log_priority_DLOG_WARN: log_priority = 5;
+pub const log_priority_DLOG_ERROR: log_priority = 6;
+pub const log_priority_DLOG_FATAL: log_priority = 7;
+pub const log_priority_DLOG_SILENT: log_priority = 8;
+pub const log_priority_DLOG_PRIO_MAX: log_priority = 9;
+
+/// Prints to stderr in the format of `dlogutil`. Unlike the native call, `fmt` is printed
+/// as is rather than used as a format.
+pub unsafe extern "C" fn dlog_print(
+    prio: log_priority,
+    tag: *const c_char,
+    fmt: *const c_char,
+) -> c_int {
+    if tag.is_null() || fmt.is_null() {
+        return tizen_error_e_TIZEN_ERROR_INVALID_PARAMETER;
+    }
+
+    let level = match prio {
+        log_priority_DLOG_VERBOSE => 'V',
+        log_priority_DLOG_DEBUG => 'D',
+        log_priority_DLOG_INFO => 'I',
+        log_priority_DLOG_WARN => 'W',
+        log_priority_DLOG_ERROR => 'E',
+        log_priority_DLOG_FATAL => 'F',
+        log_priority_DLOG_SILENT => return tizen_error_e_TIZEN_ERROR_NONE,
+        _ => '?',
+    };
+    eprintln!(
+        "{}/{}: {}",
+        level,
+        CStr::from_ptr(tag).to_string_lossy(),
+        CStr::from_ptr(fmt).to_string_lossy(),
+    );
+
+    tizen_error_e_TIZEN_ERROR_NONE
+}
+
+/// Writes `value` to an output argument, failing if it is null like the native calls.
+#[cfg(any(feature = "network", feature = "sensor", feature = "system"))]
+unsafe fn write_out<T>(ptr: *mut T, value: T) -> c_int {
+    if ptr.is_null() {
+        return tizen_error_e_TIZEN_ERROR_INVALID_PARAMETER;
+    }
+
+    ptr.write(value);
+    tizen_error_e_TIZEN_ERROR_NONE
+}
+
+/// Returns a copy allocated with `malloc`, which the caller frees as for the native getters.
+#[cfg(any(feature = "network", feature = "sensor", feature = "system"))]
+unsafe fn strdup(value: Option<&CStr>) -> *mut c_char {
+    match value {
+        Some(value) => libc::strdup(value.as_ptr()),
+        None => std::ptr::null_mut(),
+    }
+}
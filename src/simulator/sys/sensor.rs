@@ -0,0 +1,394 @@
+use super::{
+    strdup, tizen_error_e_TIZEN_ERROR_INVALID_OPERATION,
+    tizen_error_e_TIZEN_ERROR_INVALID_PARAMETER, tizen_error_e_TIZEN_ERROR_NONE,
+    tizen_error_e_TIZEN_ERROR_NOT_SUPPORTED, tizen_error_e_TIZEN_ERROR_NO_DATA, write_out,
+};
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::ffi::{CStr, CString};
+use std::os::raw::{c_char, c_int, c_uint, c_ulonglong, c_void};
+use std::ptr::null_mut;
+use std::time::Instant;
+
+pub type sensor_type_e = c_int;
+
+pub const sensor_type_e_SENSOR_ACCELEROMETER: sensor_type_e = 0;
+pub const sensor_type_e_SENSOR_GRAVITY: sensor_type_e = 1;
+pub const sensor_type_e_SENSOR_LINEAR_ACCELERATION: sensor_type_e = 2;
+pub const sensor_type_e_SENSOR_MAGNETIC: sensor_type_e = 3;
+pub const sensor_type_e_SENSOR_ROTATION_VECTOR: sensor_type_e = 4;
+pub const sensor_type_e_SENSOR_ORIENTATION: sensor_type_e = 5;
+pub const sensor_type_e_SENSOR_GYROSCOPE: sensor_type_e = 6;
+pub const sensor_type_e_SENSOR_LIGHT: sensor_type_e = 7;
+pub const sensor_type_e_SENSOR_PROXIMITY: sensor_type_e = 8;
+pub const sensor_type_e_SENSOR_PRESSURE: sensor_type_e = 9;
+pub const sensor_type_e_SENSOR_HRM: sensor_type_e = 13;
+pub const sensor_type_e_SENSOR_GYROSCOPE_ROTATION_VECTOR: sensor_type_e = 22;
+pub const sensor_type_e_SENSOR_HUMAN_PEDOMETER: sensor_type_e = 0x300;
+
+pub type sensor_data_accuracy_e = c_int;
+
+pub const sensor_data_accuracy_e_SENSOR_DATA_ACCURACY_BAD: sensor_data_accuracy_e = 0;
+pub const sensor_data_accuracy_e_SENSOR_DATA_ACCURACY_NORMAL: sensor_data_accuracy_e = 1;
+pub const sensor_data_accuracy_e_SENSOR_DATA_ACCURACY_GOOD: sensor_data_accuracy_e = 2;
+pub const sensor_data_accuracy_e_SENSOR_DATA_ACCURACY_VERYGOOD: sensor_data_accuracy_e = 3;
+
+pub type sensor_option_e = c_uint;
+
+pub const sensor_option_e_SENSOR_OPTION_DEFAULT: sensor_option_e = 0;
+pub const sensor_option_e_SENSOR_OPTION_ALWAYS_ON: sensor_option_e = 3;
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct sensor_event_s {
+    pub accuracy: c_int,
+    pub timestamp: c_ulonglong,
+    pub value_count: c_int,
+    pub values: [f32; 16],
+}
+
+pub struct sensor_s {
+    sensor_type: sensor_type_e,
+}
+
+pub type sensor_h = *mut sensor_s;
+
+pub struct sensor_listener_s {
+    sensor: sensor_h,
+    started: bool,
+    callback: sensor_event_cb,
+    user_data: *mut c_void,
+}
+
+pub type sensor_listener_h = *mut sensor_listener_s;
+
+pub type sensor_event_cb = Option<
+    unsafe extern "C" fn(sensor: sensor_h, event: *mut sensor_event_s, user_data: *mut c_void),
+>;
+
+struct Sensors {
+    unsupported: HashSet<sensor_type_e>,
+    sensors: HashMap<sensor_type_e, Box<sensor_s>>,
+    latest: HashMap<sensor_type_e, sensor_event_s>,
+    listeners: Vec<sensor_listener_h>,
+    boot: Instant,
+}
+
+thread_local! {
+    static SENSORS: RefCell<Sensors> = RefCell::new(Sensors {
+        unsupported: HashSet::new(),
+        sensors: HashMap::new(),
+        latest: HashMap::new(),
+        listeners: Vec::new(),
+        boot: Instant::now(),
+    });
+}
+
+pub(in crate::simulator) fn set_supported(sensor_type: sensor_type_e, supported: bool) {
+    SENSORS.with(|sensors| {
+        let mut sensors = sensors.borrow_mut();
+
+        if supported {
+            sensors.unsupported.remove(&sensor_type);
+        } else {
+            sensors.unsupported.insert(sensor_type);
+        }
+    })
+}
+
+/// Stores a reading of the sensor and delivers it to its started listeners.
+pub(in crate::simulator) fn emit(sensor_type: sensor_type_e, values: &[f32]) {
+    let mut event = sensor_event_s {
+        accuracy: sensor_data_accuracy_e_SENSOR_DATA_ACCURACY_GOOD,
+        timestamp: 0,
+        value_count: values.len().min(16) as c_int,
+        values: [0.0; 16],
+    };
+    event.values[..event.value_count as usize]
+        .copy_from_slice(&values[..event.value_count as usize]);
+
+    let listeners = SENSORS.with(|sensors| {
+        let mut sensors = sensors.borrow_mut();
+        event.timestamp = sensors.boot.elapsed().as_micros() as c_ulonglong;
+        sensors.latest.insert(sensor_type, event);
+
+        sensors.listeners.clone()
+    });
+
+    for listener in listeners {
+        // looked up again, since a callback may destroy the other listeners
+        let callback = SENSORS.with(|sensors| {
+            if !sensors.borrow().listeners.contains(&listener) {
+                return None;
+            }
+
+            let listener = unsafe { &*listener };
+            let sensor_type_matches = unsafe { (*listener.sensor).sensor_type } == sensor_type;
+
+            match listener.callback {
+                Some(callback) if listener.started && sensor_type_matches => {
+                    Some((callback, listener.sensor, listener.user_data))
+                }
+                _ => None,
+            }
+        });
+
+        if let Some((callback, sensor, user_data)) = callback {
+            let mut event = event;
+            unsafe { callback(sensor, &mut event, user_data) };
+        }
+    }
+}
+
+fn sensor_name(sensor_type: sensor_type_e) -> &'static str {
+    match sensor_type {
+        sensor_type_e_SENSOR_ACCELEROMETER => "Accelerometer",
+        sensor_type_e_SENSOR_GRAVITY => "Gravity",
+        sensor_type_e_SENSOR_LINEAR_ACCELERATION => "Linear acceleration",
+        sensor_type_e_SENSOR_MAGNETIC => "Magnetic",
+        sensor_type_e_SENSOR_ROTATION_VECTOR => "Rotation vector",
+        sensor_type_e_SENSOR_ORIENTATION => "Orientation",
+        sensor_type_e_SENSOR_GYROSCOPE => "Gyroscope",
+        sensor_type_e_SENSOR_LIGHT => "Light",
+        sensor_type_e_SENSOR_PROXIMITY => "Proximity",
+        sensor_type_e_SENSOR_PRESSURE => "Pressure",
+        sensor_type_e_SENSOR_HRM => "Heart rate monitor",
+        sensor_type_e_SENSOR_GYROSCOPE_ROTATION_VECTOR => "Gyroscope rotation vector",
+        sensor_type_e_SENSOR_HUMAN_PEDOMETER => "Pedometer",
+        _ => "Sensor",
+    }
+}
+
+/// The smallest and largest values, the resolution and the shortest interval in ms.
+fn specs(sensor_type: sensor_type_e) -> (f32, f32, f32, c_int) {
+    match sensor_type {
+        sensor_type_e_SENSOR_ACCELEROMETER
+        | sensor_type_e_SENSOR_GRAVITY
+        | sensor_type_e_SENSOR_LINEAR_ACCELERATION => (-19.6, 19.6, 0.01, 10),
+        sensor_type_e_SENSOR_MAGNETIC => (-1200.0, 1200.0, 0.1, 10),
+        sensor_type_e_SENSOR_ORIENTATION => (-180.0, 360.0, 0.01, 10),
+        sensor_type_e_SENSOR_GYROSCOPE => (-573.0, 573.0, 0.01, 10),
+        sensor_type_e_SENSOR_LIGHT => (0.0, 65536.0, 1.0, 100),
+        sensor_type_e_SENSOR_PROXIMITY => (0.0, 5.0, 5.0, 100),
+        sensor_type_e_SENSOR_PRESSURE => (260.0, 1260.0, 0.01, 100),
+        sensor_type_e_SENSOR_HRM => (0.0, 240.0, 1.0, 1000),
+        sensor_type_e_SENSOR_HUMAN_PEDOMETER => (0.0, f32::MAX, 1.0, 1000),
+        _ => (-1.0, 1.0, 0.0001, 10),
+    }
+}
+
+pub unsafe extern "C" fn sensor_is_supported(
+    sensor_type: sensor_type_e,
+    supported: *mut bool,
+) -> c_int {
+    let is_supported = SENSORS.with(|sensors| !sensors.borrow().unsupported.contains(&sensor_type));
+
+    write_out(supported, is_supported)
+}
+
+pub unsafe extern "C" fn sensor_get_default_sensor(
+    sensor_type: sensor_type_e,
+    sensor: *mut sensor_h,
+) -> c_int {
+    let handle = SENSORS.with(|sensors| {
+        let mut sensors = sensors.borrow_mut();
+
+        if sensors.unsupported.contains(&sensor_type) {
+            return None;
+        }
+
+        let handle = sensors
+            .sensors
+            .entry(sensor_type)
+            .or_insert_with(|| Box::new(sensor_s { sensor_type }));
+
+        Some(handle.as_mut() as sensor_h)
+    });
+
+    match handle {
+        Some(handle) => write_out(sensor, handle),
+        None => tizen_error_e_TIZEN_ERROR_NOT_SUPPORTED,
+    }
+}
+
+pub unsafe extern "C" fn sensor_get_name(sensor: sensor_h, name: *mut *mut c_char) -> c_int {
+    if sensor.is_null() {
+        return tizen_error_e_TIZEN_ERROR_INVALID_PARAMETER;
+    }
+
+    let value = format!(
+        "Simulated {}",
+        sensor_name((*sensor).sensor_type).to_lowercase()
+    );
+    write_out(name, strdup(Some(&CString::new(value).unwrap())))
+}
+
+pub unsafe extern "C" fn sensor_get_vendor(sensor: sensor_h, vendor: *mut *mut c_char) -> c_int {
+    if sensor.is_null() {
+        return tizen_error_e_TIZEN_ERROR_INVALID_PARAMETER;
+    }
+
+    let value = CStr::from_bytes_with_nul(b"rutin-tizen simulator\0").unwrap();
+    write_out(vendor, strdup(Some(value)))
+}
+
+pub unsafe extern "C" fn sensor_get_min_range(sensor: sensor_h, min_range: *mut f32) -> c_int {
+    if sensor.is_null() {
+        return tizen_error_e_TIZEN_ERROR_INVALID_PARAMETER;
+    }
+
+    write_out(min_range, specs((*sensor).sensor_type).0)
+}
+
+pub unsafe extern "C" fn sensor_get_max_range(sensor: sensor_h, max_range: *mut f32) -> c_int {
+    if sensor.is_null() {
+        return tizen_error_e_TIZEN_ERROR_INVALID_PARAMETER;
+    }
+
+    write_out(max_range, specs((*sensor).sensor_type).1)
+}
+
+pub unsafe extern "C" fn sensor_get_resolution(sensor: sensor_h, resolution: *mut f32) -> c_int {
+    if sensor.is_null() {
+        return tizen_error_e_TIZEN_ERROR_INVALID_PARAMETER;
+    }
+
+    write_out(resolution, specs((*sensor).sensor_type).2)
+}
+
+pub unsafe extern "C" fn sensor_get_min_interval(
+    sensor: sensor_h,
+    min_interval: *mut c_int,
+) -> c_int {
+    if sensor.is_null() {
+        return tizen_error_e_TIZEN_ERROR_INVALID_PARAMETER;
+    }
+
+    write_out(min_interval, specs((*sensor).sensor_type).3)
+}
+
+pub unsafe extern "C" fn sensor_create_listener(
+    sensor: sensor_h,
+    listener: *mut sensor_listener_h,
+) -> c_int {
+    if sensor.is_null() || listener.is_null() {
+        return tizen_error_e_TIZEN_ERROR_INVALID_PARAMETER;
+    }
+
+    let handle = Box::into_raw(Box::new(sensor_listener_s {
+        sensor,
+        started: false,
+        callback: None,
+        user_data: null_mut(),
+    }));
+    SENSORS.with(|sensors| sensors.borrow_mut().listeners.push(handle));
+
+    write_out(listener, handle)
+}
+
+/// Runs `f` on a listener created by [`sensor_create_listener`] and not destroyed yet.
+unsafe fn with_listener(
+    listener: sensor_listener_h,
+    f: impl FnOnce(&mut sensor_listener_s) -> c_int,
+) -> c_int {
+    // the state is already gone when a listener is dropped during the thread exit
+    let exists = SENSORS
+        .try_with(|sensors| sensors.borrow().listeners.contains(&listener))
+        .unwrap_or(true);
+
+    if exists {
+        f(&mut *listener)
+    } else {
+        tizen_error_e_TIZEN_ERROR_INVALID_PARAMETER
+    }
+}
+
+pub unsafe extern "C" fn sensor_destroy_listener(listener: sensor_listener_h) -> c_int {
+    let removed = SENSORS
+        .try_with(|sensors| {
+            let mut sensors = sensors.borrow_mut();
+            let len = sensors.listeners.len();
+            sensors.listeners.retain(|l| *l != listener);
+
+            sensors.listeners.len() != len
+        })
+        .unwrap_or(true);
+
+    if !removed {
+        return tizen_error_e_TIZEN_ERROR_INVALID_PARAMETER;
+    }
+
+    drop(Box::from_raw(listener));
+    tizen_error_e_TIZEN_ERROR_NONE
+}
+
+pub unsafe extern "C" fn sensor_listener_start(listener: sensor_listener_h) -> c_int {
+    with_listener(listener, |listener| {
+        listener.started = true;
+        tizen_error_e_TIZEN_ERROR_NONE
+    })
+}
+
+pub unsafe extern "C" fn sensor_listener_stop(listener: sensor_listener_h) -> c_int {
+    with_listener(listener, |listener| {
+        listener.started = false;
+        tizen_error_e_TIZEN_ERROR_NONE
+    })
+}
+
+pub unsafe extern "C" fn sensor_listener_set_event_cb(
+    listener: sensor_listener_h,
+    _interval_ms: c_uint,
+    callback: sensor_event_cb,
+    user_data: *mut c_void,
+) -> c_int {
+    if callback.is_none() {
+        return tizen_error_e_TIZEN_ERROR_INVALID_PARAMETER;
+    }
+
+    with_listener(listener, |listener| {
+        listener.callback = callback;
+        listener.user_data = user_data;
+        tizen_error_e_TIZEN_ERROR_NONE
+    })
+}
+
+pub unsafe extern "C" fn sensor_listener_unset_event_cb(listener: sensor_listener_h) -> c_int {
+    with_listener(listener, |listener| {
+        listener.callback = None;
+        listener.user_data = null_mut();
+        tizen_error_e_TIZEN_ERROR_NONE
+    })
+}
+
+/// The readings are delivered as they are emitted, so the interval is only checked.
+pub unsafe extern "C" fn sensor_listener_set_interval(
+    listener: sensor_listener_h,
+    _interval_ms: c_uint,
+) -> c_int {
+    with_listener(listener, |_| tizen_error_e_TIZEN_ERROR_NONE)
+}
+
+pub unsafe extern "C" fn sensor_listener_set_option(
+    listener: sensor_listener_h,
+    _option: sensor_option_e,
+) -> c_int {
+    with_listener(listener, |_| tizen_error_e_TIZEN_ERROR_NONE)
+}
+
+pub unsafe extern "C" fn sensor_listener_read_data(
+    listener: sensor_listener_h,
+    event: *mut sensor_event_s,
+) -> c_int {
+    with_listener(listener, |listener| {
+        if !listener.started {
+            return tizen_error_e_TIZEN_ERROR_INVALID_OPERATION;
+        }
+
+        let sensor_type = (*listener.sensor).sensor_type;
+        match SENSORS.with(|sensors| sensors.borrow().latest.get(&sensor_type).copied()) {
+            Some(latest) => write_out(event, latest),
+            None => tizen_error_e_TIZEN_ERROR_NO_DATA,
+        }
+    })
+}
@@ -0,0 +1,436 @@
+//! Sticker packages, registered by provider apps and shown by keyboards and messaging
+//! apps, since Tizen 5.5.
+//!
+//! Consuming the stickers requires the `http://tizen.org/privilege/mediastorage` privilege
+//! when they are local files.
+
+use crate::error::{check, Error, Result};
+use crate::rutin::{abort_on_panic, borrow_str, cstring, take_string};
+use rutin_tizen_sys::{
+    sticker_consumer_create, sticker_consumer_data_foreach_all,
+    sticker_consumer_data_foreach_by_group, sticker_consumer_data_foreach_by_keyword,
+    sticker_consumer_data_foreach_by_type, sticker_consumer_destroy,
+    sticker_consumer_group_list_foreach_all, sticker_consumer_h,
+    sticker_consumer_keyword_list_foreach_all, sticker_data_add_keyword, sticker_data_create,
+    sticker_data_destroy, sticker_data_display_type_e,
+    sticker_data_display_type_e_STICKER_DATA_DISP_EMOJI,
+    sticker_data_display_type_e_STICKER_DATA_DISP_WALLPAPER, sticker_data_foreach_keyword,
+    sticker_data_get_app_id, sticker_data_get_date, sticker_data_get_description,
+    sticker_data_get_display_type, sticker_data_get_group_name, sticker_data_get_thumbnail,
+    sticker_data_get_uri, sticker_data_h, sticker_data_set_description,
+    sticker_data_set_display_type, sticker_data_set_group_name, sticker_data_set_thumbnail,
+    sticker_data_set_uri, sticker_data_uri_type_e,
+    sticker_data_uri_type_e_STICKER_DATA_URI_LOCAL_PATH,
+    sticker_data_uri_type_e_STICKER_DATA_URI_WEB_RESOURCE, sticker_error_e,
+    sticker_provider_create, sticker_provider_delete_data_by_uri, sticker_provider_destroy,
+    sticker_provider_foreach_data, sticker_provider_get_sticker_count, sticker_provider_h,
+    sticker_provider_insert_data, sticker_provider_insert_data_by_json_file,
+};
+use std::os::raw::{c_char, c_int, c_void};
+use std::path::{Path, PathBuf};
+use std::ptr::null_mut;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum UriType {
+    LocalPath,
+    WebResource,
+}
+
+impl From<UriType> for sticker_data_uri_type_e {
+    fn from(uri_type: UriType) -> sticker_data_uri_type_e {
+        match uri_type {
+            UriType::LocalPath => sticker_data_uri_type_e_STICKER_DATA_URI_LOCAL_PATH,
+            UriType::WebResource => sticker_data_uri_type_e_STICKER_DATA_URI_WEB_RESOURCE,
+        }
+    }
+}
+
+impl From<sticker_data_uri_type_e> for UriType {
+    fn from(uri_type: sticker_data_uri_type_e) -> UriType {
+        match uri_type {
+            sticker_data_uri_type_e_STICKER_DATA_URI_WEB_RESOURCE => UriType::WebResource,
+            _ => UriType::LocalPath,
+        }
+    }
+}
+
+/// Where the sticker is meant to be used.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DisplayType {
+    Emoji,
+    Wallpaper,
+}
+
+impl From<DisplayType> for sticker_data_display_type_e {
+    fn from(display_type: DisplayType) -> sticker_data_display_type_e {
+        match display_type {
+            DisplayType::Emoji => sticker_data_display_type_e_STICKER_DATA_DISP_EMOJI,
+            DisplayType::Wallpaper => sticker_data_display_type_e_STICKER_DATA_DISP_WALLPAPER,
+        }
+    }
+}
+
+impl DisplayType {
+    fn try_from_raw(display_type: sticker_data_display_type_e) -> Option<DisplayType> {
+        match display_type {
+            sticker_data_display_type_e_STICKER_DATA_DISP_EMOJI => Some(DisplayType::Emoji),
+            sticker_data_display_type_e_STICKER_DATA_DISP_WALLPAPER => Some(DisplayType::Wallpaper),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Sticker {
+    /// The app which registered the sticker, set by the platform.
+    pub app_id: Option<String>,
+    pub uri_type: UriType,
+    /// A file path or a URL, unique among the stickers of the provider.
+    pub uri: String,
+    pub keywords: Vec<String>,
+    pub group: String,
+    pub thumbnail: Option<PathBuf>,
+    pub description: Option<String>,
+    /// When the sticker was last updated, as `YYYY-MM-DD HH:MM:SS`.
+    pub date: Option<String>,
+    pub display_type: Option<DisplayType>,
+}
+
+impl Sticker {
+    pub fn new(uri_type: UriType, uri: &str, group: &str) -> Sticker {
+        Self {
+            app_id: None,
+            uri_type,
+            uri: uri.to_string(),
+            keywords: Vec::new(),
+            group: group.to_string(),
+            thumbnail: None,
+            description: None,
+            date: None,
+            display_type: None,
+        }
+    }
+
+    unsafe fn from_raw(data: sticker_data_h) -> Result<Sticker> {
+        let mut uri_type = sticker_data_uri_type_e_STICKER_DATA_URI_LOCAL_PATH;
+        let mut uri = null_mut();
+        check(sticker_data_get_uri(data, &mut uri_type, &mut uri))?;
+        let uri = take_string(uri).unwrap_or_default();
+
+        let mut keywords: Vec<String> = Vec::new();
+        check(sticker_data_foreach_keyword(
+            data,
+            Some(string_handler),
+            &mut keywords as *mut Vec<String> as *mut c_void,
+        ))?;
+
+        let string = |get: StringGetter| {
+            let mut value = null_mut();
+            check(get(data, &mut value)).ok()?;
+            take_string(value)
+        };
+
+        let mut display_type = sticker_data_display_type_e_STICKER_DATA_DISP_EMOJI;
+        let display_type = check(sticker_data_get_display_type(data, &mut display_type))
+            .ok()
+            .and_then(|_| DisplayType::try_from_raw(display_type));
+
+        Ok(Self {
+            app_id: string(sticker_data_get_app_id),
+            uri_type: uri_type.into(),
+            uri,
+            keywords,
+            group: string(sticker_data_get_group_name).unwrap_or_default(),
+            thumbnail: string(sticker_data_get_thumbnail).map(PathBuf::from),
+            description: string(sticker_data_get_description),
+            date: string(sticker_data_get_date),
+            display_type,
+        })
+    }
+
+    fn to_raw(&self) -> Result<RawData> {
+        let mut handle = null_mut();
+        check(unsafe { sticker_data_create(&mut handle) })?;
+        let data = RawData { handle };
+
+        let uri = cstring(&self.uri)?;
+        check(unsafe { sticker_data_set_uri(data.handle, self.uri_type.into(), uri.as_ptr()) })?;
+
+        for keyword in &self.keywords {
+            let keyword = cstring(keyword)?;
+            check(unsafe { sticker_data_add_keyword(data.handle, keyword.as_ptr()) })?;
+        }
+
+        let group = cstring(&self.group)?;
+        check(unsafe { sticker_data_set_group_name(data.handle, group.as_ptr()) })?;
+
+        if let Some(thumbnail) = &self.thumbnail {
+            let thumbnail = cstring(&thumbnail.to_string_lossy())?;
+            check(unsafe { sticker_data_set_thumbnail(data.handle, thumbnail.as_ptr()) })?;
+        }
+
+        if let Some(description) = &self.description {
+            let description = cstring(description)?;
+            check(unsafe { sticker_data_set_description(data.handle, description.as_ptr()) })?;
+        }
+
+        if let Some(display_type) = self.display_type {
+            check(unsafe { sticker_data_set_display_type(data.handle, display_type.into()) })?;
+        }
+
+        Ok(data)
+    }
+}
+
+type StringGetter = unsafe extern "C" fn(sticker_data_h, *mut *mut c_char) -> c_int;
+
+/// Registers the stickers of this app.
+pub struct StickerProvider {
+    handle: sticker_provider_h,
+}
+
+impl StickerProvider {
+    pub fn new() -> Result<StickerProvider> {
+        let mut handle = null_mut();
+        check(unsafe { sticker_provider_create(&mut handle) })?;
+
+        Ok(Self { handle })
+    }
+
+    /// Fails with a `STICKER_ERROR_FILE_EXISTS` error if a sticker has the same URI.
+    pub fn insert(&self, sticker: &Sticker) -> Result<()> {
+        let data = sticker.to_raw()?;
+
+        check(unsafe { sticker_provider_insert_data(self.handle, data.handle) })
+    }
+
+    /// Inserts the stickers described in a JSON file, `callback_fn` is called once they
+    /// are all inserted.
+    pub fn insert_from_json<F>(&self, path: &Path, callback_fn: F) -> Result<()>
+    where
+        F: FnOnce(Result<()>),
+        F: 'static,
+    {
+        let path = cstring(&path.to_string_lossy())?;
+        let callback_fn: Box<dyn FnOnce(Result<()>)> = Box::new(callback_fn);
+        let data = Box::into_raw(Box::new(callback_fn));
+
+        let ret = unsafe {
+            sticker_provider_insert_data_by_json_file(
+                self.handle,
+                path.as_ptr(),
+                Some(inserted_handler),
+                data as *mut c_void,
+            )
+        };
+
+        if let Err(e) = check(ret) {
+            drop(unsafe { Box::from_raw(data) });
+            return Err(e);
+        }
+
+        Ok(())
+    }
+
+    pub fn delete_by_uri(&self, uri: &str) -> Result<()> {
+        let uri = cstring(uri)?;
+
+        check(unsafe { sticker_provider_delete_data_by_uri(self.handle, uri.as_ptr()) })
+    }
+
+    pub fn count(&self) -> Result<i32> {
+        let mut count = 0;
+        check(unsafe { sticker_provider_get_sticker_count(self.handle, &mut count) })?;
+
+        Ok(count)
+    }
+
+    /// The stickers of this app, `count` from `offset`.
+    pub fn stickers(&self, offset: i32, count: i32) -> Result<Vec<Sticker>> {
+        collect_stickers(|result, data| unsafe {
+            sticker_provider_foreach_data(
+                self.handle,
+                offset,
+                count,
+                result,
+                Some(sticker_handler),
+                data,
+            )
+        })
+    }
+}
+
+impl Drop for StickerProvider {
+    fn drop(&mut self) {
+        unsafe {
+            sticker_provider_destroy(self.handle);
+        }
+    }
+}
+
+/// Queries the stickers of all the providers.
+pub struct StickerConsumer {
+    handle: sticker_consumer_h,
+}
+
+impl StickerConsumer {
+    pub fn new() -> Result<StickerConsumer> {
+        let mut handle = null_mut();
+        check(unsafe { sticker_consumer_create(&mut handle) })?;
+
+        Ok(Self { handle })
+    }
+
+    /// All the stickers, `count` from `offset`.
+    pub fn all(&self, offset: i32, count: i32) -> Result<Vec<Sticker>> {
+        collect_stickers(|result, data| unsafe {
+            sticker_consumer_data_foreach_all(
+                self.handle,
+                offset,
+                count,
+                result,
+                Some(sticker_handler),
+                data,
+            )
+        })
+    }
+
+    pub fn by_keyword(&self, keyword: &str, offset: i32, count: i32) -> Result<Vec<Sticker>> {
+        let keyword = cstring(keyword)?;
+
+        collect_stickers(|result, data| unsafe {
+            sticker_consumer_data_foreach_by_keyword(
+                self.handle,
+                offset,
+                count,
+                result,
+                keyword.as_ptr(),
+                Some(sticker_handler),
+                data,
+            )
+        })
+    }
+
+    pub fn by_group(&self, group: &str, offset: i32, count: i32) -> Result<Vec<Sticker>> {
+        let group = cstring(group)?;
+
+        collect_stickers(|result, data| unsafe {
+            sticker_consumer_data_foreach_by_group(
+                self.handle,
+                offset,
+                count,
+                result,
+                group.as_ptr(),
+                Some(sticker_handler),
+                data,
+            )
+        })
+    }
+
+    pub fn by_type(&self, uri_type: UriType, offset: i32, count: i32) -> Result<Vec<Sticker>> {
+        collect_stickers(|result, data| unsafe {
+            sticker_consumer_data_foreach_by_type(
+                self.handle,
+                offset,
+                count,
+                result,
+                uri_type.into(),
+                Some(sticker_handler),
+                data,
+            )
+        })
+    }
+
+    pub fn groups(&self) -> Result<Vec<String>> {
+        let mut groups: Vec<String> = Vec::new();
+        check(unsafe {
+            sticker_consumer_group_list_foreach_all(
+                self.handle,
+                Some(string_handler),
+                &mut groups as *mut Vec<String> as *mut c_void,
+            )
+        })?;
+
+        Ok(groups)
+    }
+
+    pub fn keywords(&self) -> Result<Vec<String>> {
+        let mut keywords: Vec<String> = Vec::new();
+        check(unsafe {
+            sticker_consumer_keyword_list_foreach_all(
+                self.handle,
+                Some(string_handler),
+                &mut keywords as *mut Vec<String> as *mut c_void,
+            )
+        })?;
+
+        Ok(keywords)
+    }
+}
+
+impl Drop for StickerConsumer {
+    fn drop(&mut self) {
+        unsafe {
+            sticker_consumer_destroy(self.handle);
+        }
+    }
+}
+
+/// Runs a foreach call with the result count pointer and the user data of
+/// [`sticker_handler`].
+fn collect_stickers<F>(foreach: F) -> Result<Vec<Sticker>>
+where
+    F: FnOnce(*mut c_int, *mut c_void) -> c_int,
+{
+    let mut stickers: Vec<Result<Sticker>> = Vec::new();
+    let mut result = 0;
+
+    match check(foreach(
+        &mut result,
+        &mut stickers as *mut Vec<Result<Sticker>> as *mut c_void,
+    )) {
+        Err(Error::NoData) => return Ok(Vec::new()),
+        result => result?,
+    }
+
+    stickers.into_iter().collect()
+}
+
+struct RawData {
+    handle: sticker_data_h,
+}
+
+impl Drop for RawData {
+    fn drop(&mut self) {
+        unsafe {
+            sticker_data_destroy(self.handle);
+        }
+    }
+}
+
+extern "C" fn sticker_handler(data: sticker_data_h, user_data: *mut c_void) {
+    abort_on_panic(|| {
+        let stickers = unsafe { &mut *(user_data as *mut Vec<Result<Sticker>>) };
+        stickers.push(unsafe { Sticker::from_raw(data) });
+    })
+}
+
+extern "C" fn string_handler(value: *const c_char, user_data: *mut c_void) {
+    abort_on_panic(|| {
+        let values = unsafe { &mut *(user_data as *mut Vec<String>) };
+
+        if let Some(value) = unsafe { borrow_str(value) } {
+            values.push(value.into_owned());
+        }
+    })
+}
+
+extern "C" fn inserted_handler(error: sticker_error_e, user_data: *mut c_void) {
+    abort_on_panic(|| {
+        let callback_fn = unsafe { Box::from_raw(user_data as *mut Box<dyn FnOnce(Result<()>)>) };
+        callback_fn(check(error));
+    })
+}
@@ -0,0 +1,160 @@
+// the simulator only builds some of the wrappers using these
+#![cfg_attr(not(feature = "native"), allow(dead_code))]
+
+use futures_core::Stream;
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll, Waker};
+
+struct Queue<T> {
+    events: VecDeque<T>,
+    waker: Option<Waker>,
+}
+
+/// The sending half of an [`EventStream`], fed from native callbacks.
+pub(crate) struct EventSender<T> {
+    queue: Rc<RefCell<Queue<T>>>,
+}
+
+impl<T> Clone for EventSender<T> {
+    fn clone(&self) -> Self {
+        Self {
+            queue: self.queue.clone(),
+        }
+    }
+}
+
+impl<T> EventSender<T> {
+    pub(crate) fn send(&self, event: T) {
+        let mut queue = self.queue.borrow_mut();
+        queue.events.push_back(event);
+
+        if let Some(waker) = queue.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+/// A stream of events delivered by the platform on the main loop.
+///
+/// The stream owns the handles its callbacks are registered on, dropping it
+/// unregisters them. It never ends on its own.
+pub struct EventStream<T> {
+    queue: Rc<RefCell<Queue<T>>>,
+    _source: Box<dyn Any>,
+}
+
+impl<T> EventStream<T> {
+    /// Creates a stream and its sender, `make_source` registers the callbacks
+    /// using the sender and returns whatever must be kept alive for them.
+    pub(crate) fn new<S, F>(make_source: F) -> crate::Result<EventStream<T>>
+    where
+        S: Any,
+        F: FnOnce(EventSender<T>) -> crate::Result<S>,
+    {
+        let queue = Rc::new(RefCell::new(Queue {
+            events: VecDeque::new(),
+            waker: None,
+        }));
+
+        let source = make_source(EventSender {
+            queue: queue.clone(),
+        })?;
+
+        Ok(Self {
+            queue,
+            _source: Box::new(source),
+        })
+    }
+
+    /// Takes the next event if one is already queued, without waiting.
+    pub fn try_next(&mut self) -> Option<T> {
+        self.queue.borrow_mut().events.pop_front()
+    }
+}
+
+impl<T> Stream for EventStream<T> {
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<T>> {
+        let mut queue = self.queue.borrow_mut();
+
+        match queue.events.pop_front() {
+            Some(event) => Poll::Ready(Some(event)),
+            None => {
+                queue.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl<T> Unpin for EventStream<T> {}
+
+struct Slot<T> {
+    value: Option<T>,
+    waker: Option<Waker>,
+}
+
+/// The sending half of a [`Reply`], completed from a native callback.
+pub(crate) struct ReplySender<T> {
+    slot: Rc<RefCell<Slot<T>>>,
+}
+
+impl<T> ReplySender<T> {
+    pub(crate) fn send(self, value: T) {
+        let mut slot = self.slot.borrow_mut();
+        slot.value = Some(value);
+
+        if let Some(waker) = slot.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+/// A value delivered once by the platform on the main loop, such as the answer to a
+/// request.
+pub struct Reply<T> {
+    slot: Rc<RefCell<Slot<T>>>,
+}
+
+impl<T> Reply<T> {
+    pub(crate) fn new() -> (ReplySender<T>, Reply<T>) {
+        let slot = Rc::new(RefCell::new(Slot {
+            value: None,
+            waker: None,
+        }));
+
+        (ReplySender { slot: slot.clone() }, Reply { slot })
+    }
+
+    /// A reply which is already known, such as an error before the request was sent.
+    pub(crate) fn ready(value: T) -> Reply<T> {
+        let (sender, reply) = Self::new();
+        sender.send(value);
+
+        reply
+    }
+}
+
+impl<T> Future for Reply<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<T> {
+        let mut slot = self.slot.borrow_mut();
+
+        match slot.value.take() {
+            Some(value) => Poll::Ready(value),
+            None => {
+                slot.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl<T> Unpin for Reply<T> {}
@@ -0,0 +1,275 @@
+//! Sync jobs scheduled by the platform, batched with those of other apps to save power.
+//!
+//! A UI app registers the jobs, and its service app declared as sync adapter in the manifest
+//! runs them from the [`SyncAdapter`] callbacks. Periodic jobs require the
+//! `http://tizen.org/privilege/alarm.set` privilege, and data change jobs the privilege to
+//! read the data, such as `http://tizen.org/privilege/calendar.read`.
+
+use crate::error::{check, Error, Result};
+use crate::rutin::{abort_on_panic, borrow_str, cstring};
+use rutin_tizen_sys::{
+    account_h, bundle, sync_adapter_set_callbacks, sync_adapter_unset_callbacks,
+    sync_manager_add_data_change_sync_job, sync_manager_add_periodic_sync_job,
+    sync_manager_foreach_sync_job, sync_manager_on_demand_sync_job, sync_manager_remove_sync_job,
+    sync_option_e, sync_period_e, sync_period_e_SYNC_PERIOD_INTERVAL_12H,
+    sync_period_e_SYNC_PERIOD_INTERVAL_1DAY, sync_period_e_SYNC_PERIOD_INTERVAL_1H,
+    sync_period_e_SYNC_PERIOD_INTERVAL_2H, sync_period_e_SYNC_PERIOD_INTERVAL_30MIN,
+    sync_period_e_SYNC_PERIOD_INTERVAL_3H, sync_period_e_SYNC_PERIOD_INTERVAL_6H,
+};
+use std::cell::RefCell;
+use std::os::raw::{c_char, c_int, c_void};
+use std::ptr::null_mut;
+
+/// Flags changing how a job is run.
+pub mod option {
+    /// Runs the job right away, ahead of the others.
+    pub const EXPEDITED: u32 = 1 << 0;
+    /// Does not retry the job after a failure.
+    pub const NO_RETRY: u32 = 1 << 1;
+}
+
+/// The data whose changes can trigger a job.
+pub mod capability {
+    pub const CALENDAR: &str = "http://tizen.org/sync/capability/calendar";
+    pub const CONTACT: &str = "http://tizen.org/sync/capability/contact";
+    pub const IMAGE: &str = "http://tizen.org/sync/capability/image";
+    pub const VIDEO: &str = "http://tizen.org/sync/capability/video";
+    pub const SOUND: &str = "http://tizen.org/sync/capability/sound";
+    pub const MUSIC: &str = "http://tizen.org/sync/capability/music";
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Period {
+    Minutes30,
+    Hours1,
+    Hours2,
+    Hours3,
+    Hours6,
+    Hours12,
+    Day1,
+}
+
+impl From<Period> for sync_period_e {
+    fn from(period: Period) -> sync_period_e {
+        match period {
+            Period::Minutes30 => sync_period_e_SYNC_PERIOD_INTERVAL_30MIN,
+            Period::Hours1 => sync_period_e_SYNC_PERIOD_INTERVAL_1H,
+            Period::Hours2 => sync_period_e_SYNC_PERIOD_INTERVAL_2H,
+            Period::Hours3 => sync_period_e_SYNC_PERIOD_INTERVAL_3H,
+            Period::Hours6 => sync_period_e_SYNC_PERIOD_INTERVAL_6H,
+            Period::Hours12 => sync_period_e_SYNC_PERIOD_INTERVAL_12H,
+            Period::Day1 => sync_period_e_SYNC_PERIOD_INTERVAL_1DAY,
+        }
+    }
+}
+
+/// A job to run, either named or triggered by a data change.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SyncJob {
+    /// Only for on demand and periodic jobs.
+    pub name: Option<String>,
+    /// Only for data change jobs, such as [`capability::CONTACT`].
+    pub capability: Option<String>,
+}
+
+/// A job registered by this app.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RegisteredJob {
+    pub id: i32,
+    pub job: SyncJob,
+}
+
+/// Requests the job `name` to run once as soon as possible, returning its id.
+pub fn on_demand_sync(name: &str, options: u32) -> Result<i32> {
+    let name = cstring(name)?;
+    let mut id = 0;
+
+    check(unsafe {
+        sync_manager_on_demand_sync_job(
+            null_mut(),
+            name.as_ptr(),
+            options as sync_option_e,
+            null_mut(),
+            &mut id,
+        )
+    })?;
+
+    Ok(id)
+}
+
+/// Runs the job `name` about every `period`, returning its id. Adding a job with the
+/// same name replaces it.
+pub fn add_periodic_sync(name: &str, period: Period, options: u32) -> Result<i32> {
+    let name = cstring(name)?;
+    let mut id = 0;
+
+    check(unsafe {
+        sync_manager_add_periodic_sync_job(
+            null_mut(),
+            name.as_ptr(),
+            period.into(),
+            options as sync_option_e,
+            null_mut(),
+            &mut id,
+        )
+    })?;
+
+    Ok(id)
+}
+
+/// Runs a job whenever the data of `capability` changes, returning its id.
+pub fn add_data_change_sync(capability: &str, options: u32) -> Result<i32> {
+    let capability = cstring(capability)?;
+    let mut id = 0;
+
+    check(unsafe {
+        sync_manager_add_data_change_sync_job(
+            null_mut(),
+            capability.as_ptr(),
+            options as sync_option_e,
+            null_mut(),
+            &mut id,
+        )
+    })?;
+
+    Ok(id)
+}
+
+pub fn remove_sync(id: i32) -> Result<()> {
+    check(unsafe { sync_manager_remove_sync_job(id) })
+}
+
+pub fn registered_jobs() -> Result<Vec<RegisteredJob>> {
+    let mut jobs = Vec::<RegisteredJob>::new();
+
+    check(unsafe {
+        sync_manager_foreach_sync_job(
+            Some(job_handler),
+            &mut jobs as *mut Vec<RegisteredJob> as *mut c_void,
+        )
+    })?;
+
+    Ok(jobs)
+}
+
+struct AdapterCallbacks {
+    start_fn: Box<dyn FnMut(&SyncJob) -> bool>,
+    cancel_fn: Box<dyn FnMut(&SyncJob)>,
+}
+
+thread_local! {
+    // the adapter callbacks have no user data
+    static ADAPTER: RefCell<Option<AdapterCallbacks>> = const { RefCell::new(None) };
+}
+
+/// Runs the jobs of the app from its service app, until dropped. There can only be one
+/// at a time.
+pub struct SyncAdapter {
+    _private: (),
+}
+
+impl SyncAdapter {
+    /// `start_fn` runs a job and returns whether it succeeded, `cancel_fn` is called when
+    /// a job is removed while running.
+    pub fn new<F, G>(start_fn: F, cancel_fn: G) -> Result<SyncAdapter>
+    where
+        F: FnMut(&SyncJob) -> bool,
+        F: 'static,
+        G: FnMut(&SyncJob),
+        G: 'static,
+    {
+        if ADAPTER.with(|adapter| adapter.borrow().is_some()) {
+            return Err(Error::InvalidOperation);
+        }
+
+        check(unsafe {
+            sync_adapter_set_callbacks(Some(start_sync_handler), Some(cancel_sync_handler))
+        })?;
+
+        ADAPTER.with(|adapter| {
+            *adapter.borrow_mut() = Some(AdapterCallbacks {
+                start_fn: Box::new(start_fn),
+                cancel_fn: Box::new(cancel_fn),
+            })
+        });
+
+        Ok(Self { _private: () })
+    }
+}
+
+impl Drop for SyncAdapter {
+    fn drop(&mut self) {
+        unsafe {
+            sync_adapter_unset_callbacks();
+        }
+
+        ADAPTER.with(|adapter| adapter.borrow_mut().take());
+    }
+}
+
+unsafe fn read_job(name: *const c_char, capability: *const c_char) -> SyncJob {
+    SyncJob {
+        name: borrow_str(name).map(|name| name.into_owned()),
+        capability: borrow_str(capability).map(|capability| capability.into_owned()),
+    }
+}
+
+extern "C" fn start_sync_handler(
+    _account: account_h,
+    sync_job_name: *const c_char,
+    sync_capability: *const c_char,
+    _sync_job_user_data: *mut bundle,
+) -> bool {
+    abort_on_panic(|| {
+        let job = unsafe { read_job(sync_job_name, sync_capability) };
+
+        ADAPTER.with(|adapter| match adapter.try_borrow_mut() {
+            Ok(mut adapter) => adapter
+                .as_mut()
+                .map_or(false, |callbacks| (callbacks.start_fn)(&job)),
+            Err(_) => false,
+        })
+    })
+}
+
+extern "C" fn cancel_sync_handler(
+    _account: account_h,
+    sync_job_name: *const c_char,
+    sync_capability: *const c_char,
+    _sync_job_user_data: *mut bundle,
+) {
+    abort_on_panic(|| {
+        let job = unsafe { read_job(sync_job_name, sync_capability) };
+
+        ADAPTER.with(|adapter| {
+            if let Ok(mut adapter) = adapter.try_borrow_mut() {
+                if let Some(callbacks) = adapter.as_mut() {
+                    (callbacks.cancel_fn)(&job);
+                }
+            }
+        })
+    })
+}
+
+extern "C" fn job_handler(
+    _account: account_h,
+    sync_job_name: *const c_char,
+    sync_capability: *const c_char,
+    sync_job_id: c_int,
+    _sync_job_user_data: *mut bundle,
+    user_data: *mut c_void,
+) -> bool {
+    abort_on_panic(|| {
+        let jobs = unsafe { &mut *(user_data as *mut Vec<RegisteredJob>) };
+
+        jobs.push(RegisteredJob {
+            id: sync_job_id,
+            job: unsafe { read_job(sync_job_name, sync_capability) },
+        });
+
+        true
+    })
+}
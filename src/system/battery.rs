@@ -0,0 +1,173 @@
+//! The charge of the battery and whether it is charging.
+
+use crate::error::{check, Error, Result};
+use crate::rutin::{abort_on_panic, pin_callback};
+#[cfg(feature = "async")]
+use crate::stream::EventStream;
+use crate::sys::{
+    device_add_callback, device_battery_get_level_status, device_battery_get_percent,
+    device_battery_is_charging, device_battery_level_e,
+    device_battery_level_e_DEVICE_BATTERY_LEVEL_CRITICAL,
+    device_battery_level_e_DEVICE_BATTERY_LEVEL_EMPTY,
+    device_battery_level_e_DEVICE_BATTERY_LEVEL_FULL,
+    device_battery_level_e_DEVICE_BATTERY_LEVEL_HIGH,
+    device_battery_level_e_DEVICE_BATTERY_LEVEL_LOW, device_callback_e,
+    device_callback_e_DEVICE_CALLBACK_BATTERY_CAPACITY,
+    device_callback_e_DEVICE_CALLBACK_BATTERY_CHARGING,
+    device_callback_e_DEVICE_CALLBACK_BATTERY_LEVEL, device_remove_callback,
+};
+use std::cell::Cell;
+use std::convert::TryFrom;
+use std::os::raw::c_void;
+use std::pin::Pin;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BatteryLevel {
+    Empty,
+    /// At most 5%.
+    Critical,
+    /// At most 15%.
+    Low,
+    High,
+    Full,
+}
+
+impl TryFrom<device_battery_level_e> for BatteryLevel {
+    type Error = Error;
+
+    fn try_from(level: device_battery_level_e) -> Result<BatteryLevel> {
+        match level {
+            device_battery_level_e_DEVICE_BATTERY_LEVEL_EMPTY => Ok(BatteryLevel::Empty),
+            device_battery_level_e_DEVICE_BATTERY_LEVEL_CRITICAL => Ok(BatteryLevel::Critical),
+            device_battery_level_e_DEVICE_BATTERY_LEVEL_LOW => Ok(BatteryLevel::Low),
+            device_battery_level_e_DEVICE_BATTERY_LEVEL_HIGH => Ok(BatteryLevel::High),
+            device_battery_level_e_DEVICE_BATTERY_LEVEL_FULL => Ok(BatteryLevel::Full),
+            _ => Err(Error::Unknown),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BatteryEvent {
+    PercentChanged(i32),
+    ChargingChanged(bool),
+    LevelChanged(BatteryLevel),
+}
+
+pub fn get_percent() -> Result<i32> {
+    let mut percent = 0;
+    check(unsafe { device_battery_get_percent(&mut percent) })?;
+
+    Ok(percent)
+}
+
+pub fn is_charging() -> Result<bool> {
+    let mut charging = false;
+    check(unsafe { device_battery_is_charging(&mut charging) })?;
+
+    Ok(charging)
+}
+
+pub fn get_level() -> Result<BatteryLevel> {
+    let mut level = device_battery_level_e_DEVICE_BATTERY_LEVEL_EMPTY;
+    check(unsafe { device_battery_get_level_status(&mut level) })?;
+
+    BatteryLevel::try_from(level)
+}
+
+const CALLBACKS: [device_callback_e; 3] = [
+    device_callback_e_DEVICE_CALLBACK_BATTERY_CAPACITY,
+    device_callback_e_DEVICE_CALLBACK_BATTERY_CHARGING,
+    device_callback_e_DEVICE_CALLBACK_BATTERY_LEVEL,
+];
+
+thread_local! {
+    // the callbacks are removed by handler rather than by user data, so only one listener
+    // can exist at a time
+    static LISTENING: Cell<bool> = const { Cell::new(false) };
+}
+
+type EventFn = Box<dyn FnMut(BatteryEvent)>;
+
+/// Receives the battery changes until dropped. There can only be one at a time.
+pub struct BatteryListener {
+    _event_fn: Pin<Box<EventFn>>,
+}
+
+impl BatteryListener {
+    /// Fails with [`Error::InvalidOperation`] if a listener already exists.
+    pub fn new<F>(callback_fn: F) -> Result<BatteryListener>
+    where
+        F: FnMut(BatteryEvent),
+        F: 'static,
+    {
+        if LISTENING.with(|listening| listening.replace(true)) {
+            return Err(Error::InvalidOperation);
+        }
+
+        let (event_fn, data) = pin_callback::<dyn FnMut(BatteryEvent)>(Box::new(callback_fn));
+
+        for (i, callback) in CALLBACKS.iter().enumerate() {
+            if let Err(e) = check(unsafe { device_add_callback(*callback, Some(handler), data) }) {
+                for callback in &CALLBACKS[..i] {
+                    unsafe { device_remove_callback(*callback, Some(handler)) };
+                }
+                LISTENING.with(|listening| listening.set(false));
+
+                return Err(e);
+            }
+        }
+
+        Ok(Self {
+            _event_fn: event_fn,
+        })
+    }
+
+    /// Returns a stream of the battery changes.
+    ///
+    /// Fails with [`Error::InvalidOperation`] if a listener already exists.
+    #[cfg(feature = "async")]
+    pub fn events() -> Result<EventStream<BatteryEvent>> {
+        EventStream::new(|sender| BatteryListener::new(move |event| sender.send(event)))
+    }
+}
+
+impl Drop for BatteryListener {
+    fn drop(&mut self) {
+        for callback in &CALLBACKS {
+            unsafe {
+                device_remove_callback(*callback, Some(handler));
+            }
+        }
+
+        LISTENING.with(|listening| listening.set(false));
+    }
+}
+
+extern "C" fn handler(callback: device_callback_e, value: *mut c_void, user_data: *mut c_void) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut EventFn) };
+
+        // the value is passed in the pointer itself
+        let value = value as isize;
+        let event = match callback {
+            device_callback_e_DEVICE_CALLBACK_BATTERY_CAPACITY => {
+                BatteryEvent::PercentChanged(value as i32)
+            }
+            device_callback_e_DEVICE_CALLBACK_BATTERY_CHARGING => {
+                BatteryEvent::ChargingChanged(value != 0)
+            }
+            device_callback_e_DEVICE_CALLBACK_BATTERY_LEVEL => {
+                match BatteryLevel::try_from(value as device_battery_level_e) {
+                    Ok(level) => BatteryEvent::LevelChanged(level),
+                    Err(_) => return,
+                }
+            }
+            _ => return,
+        };
+
+        closure(event);
+    })
+}
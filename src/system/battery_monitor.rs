@@ -0,0 +1,146 @@
+//! The battery consumed by the apps and the hardware resources, times being in seconds since
+//! the epoch and usages in mAh.
+//!
+//! Requires the `http://tizen.org/privilege/systemmonitor` privilege.
+
+use crate::error::{check, Result};
+use crate::rutin::cstring;
+use rutin_tizen_sys::{
+    battery_monitor_get_power_usage_by_app,
+    battery_monitor_get_power_usage_by_app_for_all_resources,
+    battery_monitor_get_power_usage_by_resource, battery_monitor_resource_id_e,
+    battery_monitor_resource_id_e_BATTERY_MONITOR_RESOURCE_ID_BLE,
+    battery_monitor_resource_id_e_BATTERY_MONITOR_RESOURCE_ID_CPU,
+    battery_monitor_resource_id_e_BATTERY_MONITOR_RESOURCE_ID_DEVICE_NETWORK,
+    battery_monitor_resource_id_e_BATTERY_MONITOR_RESOURCE_ID_DISPLAY,
+    battery_monitor_resource_id_e_BATTERY_MONITOR_RESOURCE_ID_GPS_SENSOR,
+    battery_monitor_resource_id_e_BATTERY_MONITOR_RESOURCE_ID_HRM_SENSOR,
+    battery_monitor_resource_id_e_BATTERY_MONITOR_RESOURCE_ID_WIFI, battery_usage_data_destroy,
+    battery_usage_data_get_power_usage_per_resource, battery_usage_data_h,
+};
+use std::ptr::null_mut;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Resource {
+    Ble,
+    Wifi,
+    Cpu,
+    Display,
+    /// The cellular network.
+    DeviceNetwork,
+    GpsSensor,
+    /// The heart rate monitor.
+    HrmSensor,
+}
+
+impl From<Resource> for battery_monitor_resource_id_e {
+    fn from(resource: Resource) -> battery_monitor_resource_id_e {
+        match resource {
+            Resource::Ble => battery_monitor_resource_id_e_BATTERY_MONITOR_RESOURCE_ID_BLE,
+            Resource::Wifi => battery_monitor_resource_id_e_BATTERY_MONITOR_RESOURCE_ID_WIFI,
+            Resource::Cpu => battery_monitor_resource_id_e_BATTERY_MONITOR_RESOURCE_ID_CPU,
+            Resource::Display => battery_monitor_resource_id_e_BATTERY_MONITOR_RESOURCE_ID_DISPLAY,
+            Resource::DeviceNetwork => {
+                battery_monitor_resource_id_e_BATTERY_MONITOR_RESOURCE_ID_DEVICE_NETWORK
+            }
+            Resource::GpsSensor => {
+                battery_monitor_resource_id_e_BATTERY_MONITOR_RESOURCE_ID_GPS_SENSOR
+            }
+            Resource::HrmSensor => {
+                battery_monitor_resource_id_e_BATTERY_MONITOR_RESOURCE_ID_HRM_SENSOR
+            }
+        }
+    }
+}
+
+const ALL_RESOURCES: [Resource; 7] = [
+    Resource::Ble,
+    Resource::Wifi,
+    Resource::Cpu,
+    Resource::Display,
+    Resource::DeviceNetwork,
+    Resource::GpsSensor,
+    Resource::HrmSensor,
+];
+
+/// The battery consumed by `app_id` through `resource` between `from` and `to`.
+pub fn get_app_usage(app_id: &str, resource: Resource, from: i64, to: i64) -> Result<f64> {
+    let app_id = cstring(app_id)?;
+
+    let mut usage = 0.0;
+    check(unsafe {
+        battery_monitor_get_power_usage_by_app(
+            app_id.as_ptr(),
+            resource.into(),
+            from as libc::time_t,
+            to as libc::time_t,
+            &mut usage,
+        )
+    })?;
+
+    Ok(usage)
+}
+
+/// The battery consumed by `app_id` between `from` and `to`, for each resource.
+pub fn get_app_usage_per_resource(
+    app_id: &str,
+    from: i64,
+    to: i64,
+) -> Result<Vec<(Resource, f64)>> {
+    let app_id = cstring(app_id)?;
+
+    let mut handle = null_mut();
+    check(unsafe {
+        battery_monitor_get_power_usage_by_app_for_all_resources(
+            app_id.as_ptr(),
+            from as libc::time_t,
+            to as libc::time_t,
+            &mut handle,
+        )
+    })?;
+    let data = RawUsageData { handle };
+
+    ALL_RESOURCES
+        .iter()
+        .map(|&resource| {
+            let mut usage = 0.0;
+            check(unsafe {
+                battery_usage_data_get_power_usage_per_resource(
+                    data.handle,
+                    resource.into(),
+                    &mut usage,
+                )
+            })?;
+
+            Ok((resource, usage))
+        })
+        .collect()
+}
+
+/// The battery consumed through `resource` by all the apps between `from` and `to`.
+pub fn get_resource_usage(resource: Resource, from: i64, to: i64) -> Result<f64> {
+    let mut usage = 0.0;
+    check(unsafe {
+        battery_monitor_get_power_usage_by_resource(
+            resource.into(),
+            from as libc::time_t,
+            to as libc::time_t,
+            &mut usage,
+        )
+    })?;
+
+    Ok(usage)
+}
+
+struct RawUsageData {
+    handle: battery_usage_data_h,
+}
+
+impl Drop for RawUsageData {
+    fn drop(&mut self) {
+        unsafe {
+            battery_usage_data_destroy(self.handle);
+        }
+    }
+}
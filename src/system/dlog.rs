@@ -1,4 +1,4 @@
-use rutin_tizen_sys::{
+use crate::sys::{
     dlog_print, log_priority, log_priority_DLOG_DEBUG, log_priority_DLOG_DEFAULT,
     log_priority_DLOG_ERROR, log_priority_DLOG_FATAL, log_priority_DLOG_INFO,
     log_priority_DLOG_PRIO_MAX, log_priority_DLOG_SILENT, log_priority_DLOG_UNKNOWN,
@@ -44,3 +44,66 @@ pub fn print(priority: Priority, tag: &str, message: &str) {
         dlog_print(priority.into(), tag.as_ptr(), message.as_ptr());
     }
 }
+
+/// A `tracing` layer printing the events to dlog under `tag`, prefixed with the names of
+/// their spans.
+#[cfg(feature = "tracing")]
+pub struct DlogLayer {
+    tag: String,
+}
+
+#[cfg(feature = "tracing")]
+impl DlogLayer {
+    pub fn new(tag: &str) -> DlogLayer {
+        Self {
+            tag: tag.to_string(),
+        }
+    }
+}
+
+#[cfg(feature = "tracing")]
+impl<S> tracing_subscriber::Layer<S> for DlogLayer
+where
+    S: tracing::Subscriber,
+    S: for<'a> tracing_subscriber::registry::LookupSpan<'a>,
+{
+    fn on_event(&self, event: &tracing::Event<'_>, ctx: tracing_subscriber::layer::Context<'_, S>) {
+        let mut message = String::new();
+
+        if let Some(scope) = ctx.event_scope(event) {
+            for span in scope.from_root() {
+                message.push_str(span.name());
+                message.push_str(": ");
+            }
+        }
+
+        event.record(&mut MessageVisitor(&mut message));
+
+        let priority = match *event.metadata().level() {
+            tracing::Level::ERROR => Priority::Error,
+            tracing::Level::WARN => Priority::Warn,
+            tracing::Level::INFO => Priority::Info,
+            tracing::Level::DEBUG => Priority::Debug,
+            tracing::Level::TRACE => Priority::Verbose,
+        };
+
+        // interior NULs would make the message rejected
+        print(priority, &self.tag, &message.replace('\0', ""));
+    }
+}
+
+#[cfg(feature = "tracing")]
+struct MessageVisitor<'a>(&'a mut String);
+
+#[cfg(feature = "tracing")]
+impl<'a> tracing::field::Visit for MessageVisitor<'a> {
+    fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+        use std::fmt::Write;
+
+        if field.name() == "message" {
+            let _ = write!(self.0, "{:?}", value);
+        } else {
+            let _ = write!(self.0, " {}={:?}", field.name(), value);
+        }
+    }
+}
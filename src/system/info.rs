@@ -0,0 +1,178 @@
+//! The platform keys describing the hardware and software of the device, such as
+//! `http://tizen.org/feature/screen.width`.
+
+use crate::error::{check, Result};
+use crate::rutin::{cstring, take_string};
+use crate::sys::{
+    system_info_get_platform_bool, system_info_get_platform_int, system_info_get_platform_string,
+};
+use std::cell::Cell;
+use std::ptr::null_mut;
+
+const SCREEN_WIDTH: &str = "http://tizen.org/feature/screen.width";
+const SCREEN_HEIGHT: &str = "http://tizen.org/feature/screen.height";
+const SCREEN_DPI: &str = "http://tizen.org/feature/screen.dpi";
+const SCREEN_BPP: &str = "http://tizen.org/feature/screen.bpp";
+const SCREEN_SHAPE_CIRCLE: &str = "http://tizen.org/feature/screen.shape.circle";
+const PROFILE: &str = "http://tizen.org/feature/profile";
+
+thread_local! {
+    static CURRENT_PROFILE: Cell<Option<Profile>> = const { Cell::new(None) };
+}
+
+pub fn get_platform_bool(key: &str) -> Result<bool> {
+    let key = cstring(key)?;
+
+    let mut value = false;
+    check(unsafe { system_info_get_platform_bool(key.as_ptr(), &mut value) })?;
+
+    Ok(value)
+}
+
+pub fn get_platform_int(key: &str) -> Result<i32> {
+    let key = cstring(key)?;
+
+    let mut value = 0;
+    check(unsafe { system_info_get_platform_int(key.as_ptr(), &mut value) })?;
+
+    Ok(value)
+}
+
+pub fn get_platform_string(key: &str) -> Result<String> {
+    let key = cstring(key)?;
+
+    let mut value = null_mut();
+    check(unsafe { system_info_get_platform_string(key.as_ptr(), &mut value) })?;
+
+    Ok(unsafe { take_string(value) }.unwrap_or_default())
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ScreenShape {
+    Circle,
+    Rectangle,
+}
+
+/// The characteristics of the main screen.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DisplayMetrics {
+    /// In pixels.
+    pub width: i32,
+    /// In pixels.
+    pub height: i32,
+    pub dpi: i32,
+    pub shape: ScreenShape,
+    /// The bits per pixel.
+    pub bpp: i32,
+}
+
+impl DisplayMetrics {
+    pub fn query() -> Result<DisplayMetrics> {
+        let shape = if get_platform_bool(SCREEN_SHAPE_CIRCLE)? {
+            ScreenShape::Circle
+        } else {
+            ScreenShape::Rectangle
+        };
+
+        Ok(Self {
+            width: get_platform_int(SCREEN_WIDTH)?,
+            height: get_platform_int(SCREEN_HEIGHT)?,
+            dpi: get_platform_int(SCREEN_DPI)?,
+            shape,
+            bpp: get_platform_int(SCREEN_BPP)?,
+        })
+    }
+
+    pub fn is_round(&self) -> bool {
+        self.shape == ScreenShape::Circle
+    }
+
+    pub fn width_mm(&self) -> f64 {
+        self.pixels_to_mm(self.width)
+    }
+
+    pub fn height_mm(&self) -> f64 {
+        self.pixels_to_mm(self.height)
+    }
+
+    /// The length of the diagonal, or the diameter of a round screen.
+    pub fn diagonal_mm(&self) -> f64 {
+        if self.is_round() {
+            self.width_mm()
+        } else {
+            self.width_mm().hypot(self.height_mm())
+        }
+    }
+
+    fn pixels_to_mm(&self, pixels: i32) -> f64 {
+        if self.dpi <= 0 {
+            return 0.0;
+        }
+
+        f64::from(pixels) * 25.4 / f64::from(self.dpi)
+    }
+}
+
+/// The device family the platform is built for.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Profile {
+    Mobile,
+    Wearable,
+    Tv,
+    /// In-vehicle infotainment.
+    Ivi,
+    /// The IoT devices and the other devices without a specific profile.
+    Common,
+    Unknown,
+}
+
+impl Profile {
+    /// The profile of the device, `Unknown` if it can't be read.
+    ///
+    /// It is read once per thread, then cached. The simulator gives each thread its own
+    /// device, which may have another profile.
+    pub fn current() -> Profile {
+        CURRENT_PROFILE.with(|current| {
+            current.get().unwrap_or_else(|| {
+                let profile = get_platform_string(PROFILE)
+                    .map(|name| Profile::from(name.as_str()))
+                    .unwrap_or(Profile::Unknown);
+
+                current.set(Some(profile));
+                profile
+            })
+        })
+    }
+
+    pub fn is_current(self) -> bool {
+        Profile::current() == self
+    }
+
+    /// Runs `f` only on this profile, the runtime counterpart of a `cfg` attribute.
+    pub fn when<T, F>(self, f: F) -> Option<T>
+    where
+        F: FnOnce() -> T,
+    {
+        if self.is_current() {
+            Some(f())
+        } else {
+            None
+        }
+    }
+}
+
+impl From<&str> for Profile {
+    fn from(name: &str) -> Profile {
+        match name {
+            "mobile" => Profile::Mobile,
+            "wearable" => Profile::Wearable,
+            "tv" => Profile::Tv,
+            "ivi" => Profile::Ivi,
+            "common" => Profile::Common,
+            _ => Profile::Unknown,
+        }
+    }
+}
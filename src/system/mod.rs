@@ -1 +1,9 @@
+#[cfg(feature = "system")]
+pub mod battery;
+#[cfg(all(feature = "system", feature = "native"))]
+pub mod battery_monitor;
 pub mod dlog;
+#[cfg(feature = "system")]
+pub mod info;
+#[cfg(all(feature = "system", feature = "native"))]
+pub mod usb_host;
@@ -0,0 +1,432 @@
+//! Driving USB peripherals, on devices with the `http://tizen.org/feature/usb.host` feature.
+
+use crate::error::{check, Error, Result};
+use rutin_tizen_sys::{
+    usb_host_claim_interface, usb_host_config_get_interface, usb_host_config_get_num_interfaces,
+    usb_host_config_h, usb_host_context_h, usb_host_control_transfer, usb_host_create,
+    usb_host_destroy, usb_host_destroy_config, usb_host_device_close, usb_host_device_get_address,
+    usb_host_device_get_bus_number, usb_host_device_get_class, usb_host_device_get_id_product,
+    usb_host_device_get_id_vendor, usb_host_device_get_manufacturer_str,
+    usb_host_device_get_product_str, usb_host_device_get_serial_number_str, usb_host_device_h,
+    usb_host_device_open, usb_host_device_open_with_vid_pid,
+    usb_host_endpoint_direction_e_USB_HOST_DIRECTION_IN, usb_host_endpoint_get_direction,
+    usb_host_endpoint_get_max_packet_size, usb_host_endpoint_get_number,
+    usb_host_endpoint_get_transfer_type, usb_host_endpoint_h, usb_host_free_device_list,
+    usb_host_get_active_config, usb_host_get_device_list, usb_host_interface_get_endpoint,
+    usb_host_interface_get_num_endpoints, usb_host_interface_get_number, usb_host_interface_h,
+    usb_host_interface_set_altsetting, usb_host_release_interface, usb_host_transfer,
+    usb_host_transfer_type_e, usb_host_transfer_type_e_USB_HOST_TRANSFER_TYPE_BULK,
+    usb_host_transfer_type_e_USB_HOST_TRANSFER_TYPE_CONTROL,
+    usb_host_transfer_type_e_USB_HOST_TRANSFER_TYPE_INTERRUPT,
+    usb_host_transfer_type_e_USB_HOST_TRANSFER_TYPE_ISOCHRONOUS, usb_host_unref_device,
+};
+use std::convert::TryFrom;
+use std::marker::PhantomData;
+use std::os::raw::c_int;
+use std::ptr::null_mut;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Direction {
+    /// From the device to the host.
+    In,
+    Out,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TransferType {
+    Control,
+    Isochronous,
+    Bulk,
+    Interrupt,
+}
+
+impl TryFrom<usb_host_transfer_type_e> for TransferType {
+    type Error = Error;
+
+    fn try_from(transfer_type: usb_host_transfer_type_e) -> Result<TransferType> {
+        match transfer_type {
+            usb_host_transfer_type_e_USB_HOST_TRANSFER_TYPE_CONTROL => Ok(TransferType::Control),
+            usb_host_transfer_type_e_USB_HOST_TRANSFER_TYPE_ISOCHRONOUS => {
+                Ok(TransferType::Isochronous)
+            }
+            usb_host_transfer_type_e_USB_HOST_TRANSFER_TYPE_BULK => Ok(TransferType::Bulk),
+            usb_host_transfer_type_e_USB_HOST_TRANSFER_TYPE_INTERRUPT => {
+                Ok(TransferType::Interrupt)
+            }
+            _ => Err(Error::Unknown),
+        }
+    }
+}
+
+/// The USB host context, destroyed when dropped.
+pub struct UsbHost {
+    context: usb_host_context_h,
+}
+
+impl UsbHost {
+    pub fn new() -> Result<UsbHost> {
+        let mut context = null_mut();
+        check(unsafe { usb_host_create(&mut context) })?;
+
+        Ok(Self { context })
+    }
+
+    /// Lists the attached devices, none of them opened yet.
+    pub fn devices(&self) -> Result<Vec<UsbDevice<'_>>> {
+        let mut list = null_mut();
+        let mut len = 0;
+        check(unsafe { usb_host_get_device_list(self.context, &mut list, &mut len) })?;
+
+        if list.is_null() {
+            return Ok(Vec::new());
+        }
+
+        let devices = if len > 0 {
+            unsafe { std::slice::from_raw_parts(list, len as usize) }
+                .iter()
+                .map(|&handle| UsbDevice {
+                    handle,
+                    opened: false,
+                    _host: PhantomData,
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        // the references held by the list are now owned by the devices
+        unsafe { usb_host_free_device_list(list, false) };
+
+        Ok(devices)
+    }
+
+    /// Opens the first device with the given vendor and product ids.
+    pub fn open_with_ids(&self, vendor_id: u16, product_id: u16) -> Result<UsbDevice<'_>> {
+        let mut handle = null_mut();
+        check(unsafe {
+            usb_host_device_open_with_vid_pid(
+                self.context,
+                vendor_id as c_int,
+                product_id as c_int,
+                &mut handle,
+            )
+        })?;
+
+        Ok(UsbDevice {
+            handle,
+            opened: true,
+            _host: PhantomData,
+        })
+    }
+}
+
+impl Drop for UsbHost {
+    fn drop(&mut self) {
+        unsafe {
+            usb_host_destroy(self.context);
+        }
+    }
+}
+
+/// An attached device, closed when dropped.
+pub struct UsbDevice<'h> {
+    handle: usb_host_device_h,
+    opened: bool,
+    _host: PhantomData<&'h UsbHost>,
+}
+
+impl<'h> UsbDevice<'h> {
+    fn get_int(
+        &self,
+        get: unsafe extern "C" fn(usb_host_device_h, *mut c_int) -> c_int,
+    ) -> Result<i32> {
+        let mut value = 0;
+        check(unsafe { get(self.handle, &mut value) })?;
+
+        Ok(value)
+    }
+
+    fn get_str(
+        &self,
+        get: unsafe extern "C" fn(usb_host_device_h, *mut c_int, *mut u8) -> c_int,
+    ) -> Result<String> {
+        let mut data = [0u8; 256];
+        let mut len = data.len() as c_int;
+        check(unsafe { get(self.handle, &mut len, data.as_mut_ptr()) })?;
+
+        let data = &data[..(len as usize).min(data.len())];
+        let data = data.split(|&b| b == 0).next().unwrap_or_default();
+
+        Ok(String::from_utf8_lossy(data).into_owned())
+    }
+
+    pub fn bus_number(&self) -> Result<i32> {
+        self.get_int(usb_host_device_get_bus_number)
+    }
+
+    pub fn address(&self) -> Result<i32> {
+        self.get_int(usb_host_device_get_address)
+    }
+
+    pub fn vendor_id(&self) -> Result<u16> {
+        self.get_int(usb_host_device_get_id_vendor)
+            .map(|id| id as u16)
+    }
+
+    pub fn product_id(&self) -> Result<u16> {
+        self.get_int(usb_host_device_get_id_product)
+            .map(|id| id as u16)
+    }
+
+    pub fn class(&self) -> Result<u8> {
+        self.get_int(usb_host_device_get_class)
+            .map(|class| class as u8)
+    }
+
+    /// The manufacturer string descriptor, the device must be opened.
+    pub fn manufacturer(&self) -> Result<String> {
+        self.get_str(usb_host_device_get_manufacturer_str)
+    }
+
+    pub fn product(&self) -> Result<String> {
+        self.get_str(usb_host_device_get_product_str)
+    }
+
+    pub fn serial_number(&self) -> Result<String> {
+        self.get_str(usb_host_device_get_serial_number_str)
+    }
+
+    /// Opens the device, may ask the user for permission first.
+    pub fn open(&mut self) -> Result<()> {
+        if !self.opened {
+            check(unsafe { usb_host_device_open(self.handle) })?;
+            self.opened = true;
+        }
+
+        Ok(())
+    }
+
+    pub fn close(&mut self) -> Result<()> {
+        if self.opened {
+            check(unsafe { usb_host_device_close(self.handle) })?;
+            self.opened = false;
+        }
+
+        Ok(())
+    }
+
+    pub fn active_config(&self) -> Result<UsbConfig<'_>> {
+        let mut handle = null_mut();
+        check(unsafe { usb_host_get_active_config(self.handle, &mut handle) })?;
+
+        Ok(UsbConfig {
+            handle,
+            _device: PhantomData,
+        })
+    }
+
+    /// Performs a control transfer on endpoint 0, `data` being sent or filled
+    /// depending on the direction bit of `request_type`. Returns the transferred length.
+    pub fn control_transfer(
+        &mut self,
+        request_type: u8,
+        request: u8,
+        value: u16,
+        index: u16,
+        data: &mut [u8],
+        timeout_ms: u32,
+    ) -> Result<usize> {
+        let len = u16::try_from(data.len()).map_err(|_| Error::InvalidParameter)?;
+        let mut transferred = 0;
+
+        check(unsafe {
+            usb_host_control_transfer(
+                self.handle,
+                request_type,
+                request,
+                value,
+                index,
+                data.as_mut_ptr(),
+                len,
+                timeout_ms,
+                &mut transferred,
+            )
+        })?;
+
+        Ok(transferred as usize)
+    }
+}
+
+impl<'h> Drop for UsbDevice<'h> {
+    fn drop(&mut self) {
+        unsafe {
+            if self.opened {
+                usb_host_device_close(self.handle);
+            }
+            usb_host_unref_device(self.handle);
+        }
+    }
+}
+
+pub struct UsbConfig<'d> {
+    handle: usb_host_config_h,
+    _device: PhantomData<&'d ()>,
+}
+
+impl<'d> UsbConfig<'d> {
+    pub fn num_interfaces(&self) -> Result<i32> {
+        let mut count = 0;
+        check(unsafe { usb_host_config_get_num_interfaces(self.handle, &mut count) })?;
+
+        Ok(count)
+    }
+
+    pub fn interface(&self, index: i32) -> Result<UsbInterface<'_>> {
+        let mut handle = null_mut();
+        check(unsafe { usb_host_config_get_interface(self.handle, index, &mut handle) })?;
+
+        Ok(UsbInterface {
+            handle,
+            claimed: false,
+            _config: PhantomData,
+        })
+    }
+}
+
+impl<'d> Drop for UsbConfig<'d> {
+    fn drop(&mut self) {
+        unsafe {
+            usb_host_destroy_config(self.handle);
+        }
+    }
+}
+
+/// An interface of a configuration, released when dropped if it was claimed.
+pub struct UsbInterface<'c> {
+    handle: usb_host_interface_h,
+    claimed: bool,
+    _config: PhantomData<&'c ()>,
+}
+
+impl<'c> UsbInterface<'c> {
+    pub fn number(&self) -> Result<i32> {
+        let mut number = 0;
+        check(unsafe { usb_host_interface_get_number(self.handle, &mut number) })?;
+
+        Ok(number)
+    }
+
+    pub fn endpoints(&self) -> Result<Vec<UsbEndpoint<'c>>> {
+        let mut count = 0;
+        check(unsafe { usb_host_interface_get_num_endpoints(self.handle, &mut count) })?;
+
+        (0..count)
+            .map(|index| {
+                let mut handle = null_mut();
+                check(unsafe { usb_host_interface_get_endpoint(self.handle, index, &mut handle) })?;
+
+                Ok(UsbEndpoint {
+                    handle,
+                    _config: PhantomData,
+                })
+            })
+            .collect()
+    }
+
+    /// Claims the interface before transfers, `force` detaching the kernel driver.
+    pub fn claim(&mut self, force: bool) -> Result<()> {
+        check(unsafe { usb_host_claim_interface(self.handle, force) })?;
+        self.claimed = true;
+
+        Ok(())
+    }
+
+    pub fn release(&mut self) -> Result<()> {
+        check(unsafe { usb_host_release_interface(self.handle) })?;
+        self.claimed = false;
+
+        Ok(())
+    }
+
+    pub fn set_altsetting(&mut self, altsetting: i32) -> Result<()> {
+        check(unsafe { usb_host_interface_set_altsetting(self.handle, altsetting) })
+    }
+}
+
+impl<'c> Drop for UsbInterface<'c> {
+    fn drop(&mut self) {
+        if self.claimed {
+            unsafe {
+                usb_host_release_interface(self.handle);
+            }
+        }
+    }
+}
+
+pub struct UsbEndpoint<'c> {
+    handle: usb_host_endpoint_h,
+    _config: PhantomData<&'c ()>,
+}
+
+impl<'c> UsbEndpoint<'c> {
+    pub fn number(&self) -> Result<i32> {
+        let mut number = 0;
+        check(unsafe { usb_host_endpoint_get_number(self.handle, &mut number) })?;
+
+        Ok(number)
+    }
+
+    pub fn direction(&self) -> Result<Direction> {
+        let mut direction = usb_host_endpoint_direction_e_USB_HOST_DIRECTION_IN;
+        check(unsafe { usb_host_endpoint_get_direction(self.handle, &mut direction) })?;
+
+        if direction == usb_host_endpoint_direction_e_USB_HOST_DIRECTION_IN {
+            Ok(Direction::In)
+        } else {
+            Ok(Direction::Out)
+        }
+    }
+
+    pub fn transfer_type(&self) -> Result<TransferType> {
+        let mut transfer_type = usb_host_transfer_type_e_USB_HOST_TRANSFER_TYPE_CONTROL;
+        check(unsafe { usb_host_endpoint_get_transfer_type(self.handle, &mut transfer_type) })?;
+
+        TransferType::try_from(transfer_type)
+    }
+
+    pub fn max_packet_size(&self) -> Result<i32> {
+        let mut size = 0;
+        check(unsafe { usb_host_endpoint_get_max_packet_size(self.handle, &mut size) })?;
+
+        Ok(size)
+    }
+
+    /// Reads from a bulk or interrupt IN endpoint, returns the received length.
+    pub fn read(&mut self, data: &mut [u8], timeout_ms: u32) -> Result<usize> {
+        self.transfer(data.as_mut_ptr(), data.len(), timeout_ms)
+    }
+
+    /// Writes to a bulk or interrupt OUT endpoint, returns the sent length.
+    ///
+    /// Fails with [`Error::InvalidParameter`] on an IN endpoint.
+    pub fn write(&mut self, data: &[u8], timeout_ms: u32) -> Result<usize> {
+        // an IN transfer would write into the shared buffer
+        if self.direction()? != Direction::Out {
+            return Err(Error::InvalidParameter);
+        }
+
+        // the data is only read by OUT transfers
+        self.transfer(data.as_ptr() as *mut u8, data.len(), timeout_ms)
+    }
+
+    fn transfer(&mut self, data: *mut u8, len: usize, timeout_ms: u32) -> Result<usize> {
+        let len = c_int::try_from(len).map_err(|_| Error::InvalidParameter)?;
+        let mut transferred = 0;
+
+        check(unsafe { usb_host_transfer(self.handle, data, len, &mut transferred, timeout_ms) })?;
+
+        Ok(transferred as usize)
+    }
+}
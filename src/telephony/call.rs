@@ -0,0 +1,211 @@
+use super::Telephony;
+use crate::error::{check, Error, Result};
+use crate::rutin::{abort_on_panic, pin_callback, take_string};
+use rutin_tizen_sys::{
+    telephony_call_direction_e_TELEPHONY_CALL_DIRECTION_MO,
+    telephony_call_direction_e_TELEPHONY_CALL_DIRECTION_MT, telephony_call_get_call_list,
+    telephony_call_get_conference_status, telephony_call_get_direction,
+    telephony_call_get_handle_id, telephony_call_get_number, telephony_call_get_status,
+    telephony_call_get_type, telephony_call_h, telephony_call_release_call_list,
+    telephony_call_status_e, telephony_call_status_e_TELEPHONY_CALL_STATUS_ACTIVE,
+    telephony_call_status_e_TELEPHONY_CALL_STATUS_ALERT,
+    telephony_call_status_e_TELEPHONY_CALL_STATUS_DIALING,
+    telephony_call_status_e_TELEPHONY_CALL_STATUS_HELD,
+    telephony_call_status_e_TELEPHONY_CALL_STATUS_IDLE,
+    telephony_call_status_e_TELEPHONY_CALL_STATUS_INCOMING, telephony_call_type_e,
+    telephony_call_type_e_TELEPHONY_CALL_TYPE_E911,
+    telephony_call_type_e_TELEPHONY_CALL_TYPE_VIDEO,
+    telephony_call_type_e_TELEPHONY_CALL_TYPE_VOICE, telephony_h, telephony_noti_e,
+    telephony_noti_e_TELEPHONY_NOTI_VOICE_CALL_STATUS_ACTIVE,
+    telephony_noti_e_TELEPHONY_NOTI_VOICE_CALL_STATUS_ALERT,
+    telephony_noti_e_TELEPHONY_NOTI_VOICE_CALL_STATUS_DIALING,
+    telephony_noti_e_TELEPHONY_NOTI_VOICE_CALL_STATUS_HELD,
+    telephony_noti_e_TELEPHONY_NOTI_VOICE_CALL_STATUS_IDLE,
+    telephony_noti_e_TELEPHONY_NOTI_VOICE_CALL_STATUS_INCOMING,
+};
+use std::os::raw::{c_uint, c_void};
+use std::ptr::null_mut;
+use std::slice;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CallType {
+    Voice,
+    Video,
+    /// An emergency call.
+    E911,
+}
+
+impl From<telephony_call_type_e> for CallType {
+    fn from(call_type: telephony_call_type_e) -> CallType {
+        match call_type {
+            telephony_call_type_e_TELEPHONY_CALL_TYPE_VIDEO => CallType::Video,
+            telephony_call_type_e_TELEPHONY_CALL_TYPE_E911 => CallType::E911,
+            _ => CallType::Voice,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CallStatus {
+    /// The call ended.
+    Idle,
+    Active,
+    /// Put on hold, usually for another call.
+    Held,
+    /// An outgoing call being dialed.
+    Dialing,
+    /// An outgoing call ringing on the other side.
+    Alert,
+    /// An incoming call ringing.
+    Incoming,
+}
+
+impl From<telephony_call_status_e> for CallStatus {
+    fn from(status: telephony_call_status_e) -> CallStatus {
+        match status {
+            telephony_call_status_e_TELEPHONY_CALL_STATUS_ACTIVE => CallStatus::Active,
+            telephony_call_status_e_TELEPHONY_CALL_STATUS_HELD => CallStatus::Held,
+            telephony_call_status_e_TELEPHONY_CALL_STATUS_DIALING => CallStatus::Dialing,
+            telephony_call_status_e_TELEPHONY_CALL_STATUS_ALERT => CallStatus::Alert,
+            telephony_call_status_e_TELEPHONY_CALL_STATUS_INCOMING => CallStatus::Incoming,
+            _ => CallStatus::Idle,
+        }
+    }
+}
+
+/// A call in progress.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Call {
+    /// Identifies the call in status changes.
+    pub id: u32,
+    /// The number of the other party, unset for hidden numbers.
+    pub number: Option<String>,
+    pub call_type: CallType,
+    pub status: CallStatus,
+    pub incoming: bool,
+    pub in_conference: bool,
+}
+
+impl Call {
+    unsafe fn read(call: telephony_call_h) -> Call {
+        let mut id = 0;
+        telephony_call_get_handle_id(call, &mut id);
+
+        let mut number = null_mut();
+        let number = if check(telephony_call_get_number(call, &mut number)).is_ok() {
+            take_string(number)
+        } else {
+            None
+        };
+
+        let mut call_type = telephony_call_type_e_TELEPHONY_CALL_TYPE_VOICE;
+        telephony_call_get_type(call, &mut call_type);
+
+        let mut status = telephony_call_status_e_TELEPHONY_CALL_STATUS_IDLE;
+        telephony_call_get_status(call, &mut status);
+
+        let mut direction = telephony_call_direction_e_TELEPHONY_CALL_DIRECTION_MO;
+        telephony_call_get_direction(call, &mut direction);
+
+        let mut in_conference = false;
+        telephony_call_get_conference_status(call, &mut in_conference);
+
+        Call {
+            id,
+            number,
+            call_type: call_type.into(),
+            status: status.into(),
+            incoming: direction == telephony_call_direction_e_TELEPHONY_CALL_DIRECTION_MT,
+            in_conference,
+        }
+    }
+}
+
+const VOICE_CALL_STATUS_NOTIS: [telephony_noti_e; 6] = [
+    telephony_noti_e_TELEPHONY_NOTI_VOICE_CALL_STATUS_IDLE,
+    telephony_noti_e_TELEPHONY_NOTI_VOICE_CALL_STATUS_ACTIVE,
+    telephony_noti_e_TELEPHONY_NOTI_VOICE_CALL_STATUS_HELD,
+    telephony_noti_e_TELEPHONY_NOTI_VOICE_CALL_STATUS_DIALING,
+    telephony_noti_e_TELEPHONY_NOTI_VOICE_CALL_STATUS_ALERT,
+    telephony_noti_e_TELEPHONY_NOTI_VOICE_CALL_STATUS_INCOMING,
+];
+
+pub(super) type CallStatusFn = Box<dyn FnMut(u32, CallStatus)>;
+
+impl Telephony {
+    /// The calls currently in progress, empty if there are none.
+    pub fn calls(&self) -> Result<Vec<Call>> {
+        let mut count = 0;
+        let mut list = null_mut();
+
+        match check(unsafe { telephony_call_get_call_list(self.handle, &mut count, &mut list) }) {
+            Err(Error::NoData) => return Ok(Vec::new()),
+            result => result?,
+        }
+
+        let calls = if list.is_null() {
+            Vec::new()
+        } else {
+            unsafe { slice::from_raw_parts(list, count as usize) }
+                .iter()
+                .map(|&call| unsafe { Call::read(call) })
+                .collect()
+        };
+
+        unsafe { telephony_call_release_call_list(count, &mut list) };
+
+        Ok(calls)
+    }
+
+    /// Calls `callback_fn` with the id and new status of a voice call whenever it changes,
+    /// replacing the previous callback.
+    pub fn set_call_status_cb<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(u32, CallStatus),
+        F: 'static,
+    {
+        self.unset_call_status_cb()?;
+
+        let (callback_fn, data) = pin_callback::<dyn FnMut(u32, CallStatus)>(Box::new(callback_fn));
+
+        self.set_noti_cbs(&VOICE_CALL_STATUS_NOTIS, call_status_handler, data)?;
+        self.call_status_fn = Some(callback_fn);
+
+        Ok(())
+    }
+
+    pub fn unset_call_status_cb(&mut self) -> Result<()> {
+        if self.call_status_fn.is_some() {
+            self.unset_noti_cbs(&VOICE_CALL_STATUS_NOTIS)?;
+            self.call_status_fn = None;
+        }
+
+        Ok(())
+    }
+}
+
+extern "C" fn call_status_handler(
+    _handle: telephony_h,
+    noti_id: telephony_noti_e,
+    data: *mut c_void,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut CallStatusFn) };
+        let id = unsafe { *(data as *const c_uint) };
+
+        let status = match noti_id {
+            telephony_noti_e_TELEPHONY_NOTI_VOICE_CALL_STATUS_ACTIVE => CallStatus::Active,
+            telephony_noti_e_TELEPHONY_NOTI_VOICE_CALL_STATUS_HELD => CallStatus::Held,
+            telephony_noti_e_TELEPHONY_NOTI_VOICE_CALL_STATUS_DIALING => CallStatus::Dialing,
+            telephony_noti_e_TELEPHONY_NOTI_VOICE_CALL_STATUS_ALERT => CallStatus::Alert,
+            telephony_noti_e_TELEPHONY_NOTI_VOICE_CALL_STATUS_INCOMING => CallStatus::Incoming,
+            _ => CallStatus::Idle,
+        };
+
+        closure(id, status);
+    })
+}
@@ -0,0 +1,397 @@
+//! The cellular network, SIM card, modem and calls, on devices with telephony.
+//!
+//! Requires the `http://tizen.org/privilege/telephony` privilege, calls fail with
+//! [`Error::PermissionDenied`] without it and with [`Error::NotSupported`] on devices
+//! without telephony. The cell location also requires
+//! `http://tizen.org/privilege/location.coarse`.
+//!
+//! On multi-SIM devices each slot has its own [`Telephony`], with its own callbacks.
+
+mod call;
+mod network;
+
+pub use call::{Call, CallStatus, CallType};
+pub use network::TelephonyEvent;
+
+use crate::error::{check, Error, Result};
+use crate::rutin::take_string;
+use call::CallStatusFn;
+use network::{NetworkTypeFn, RoamingFn, ServiceStateFn, SignalStrengthFn};
+use rutin_tizen_sys::{
+    telephony_deinit, telephony_h, telephony_handle_list_s, telephony_init,
+    telephony_modem_get_imei,
+    telephony_network_default_data_subs_e_TELEPHONY_NETWORK_DEFAULT_DATA_SUBS_SIM1,
+    telephony_network_default_data_subs_e_TELEPHONY_NETWORK_DEFAULT_DATA_SUBS_SIM2,
+    telephony_network_default_data_subs_e_TELEPHONY_NETWORK_DEFAULT_DATA_SUBS_UNKNOWN,
+    telephony_network_default_subs_e_TELEPHONY_NETWORK_DEFAULT_SUBS_SIM1,
+    telephony_network_default_subs_e_TELEPHONY_NETWORK_DEFAULT_SUBS_SIM2,
+    telephony_network_default_subs_e_TELEPHONY_NETWORK_DEFAULT_SUBS_UNKNOWN,
+    telephony_network_get_cell_id, telephony_network_get_default_data_subscription,
+    telephony_network_get_default_subscription, telephony_network_get_lac,
+    telephony_network_get_mcc, telephony_network_get_mnc, telephony_network_get_network_name,
+    telephony_network_get_roaming_status, telephony_network_get_rssi,
+    telephony_network_get_service_state, telephony_network_get_type, telephony_network_rssi_e,
+    telephony_network_rssi_e_TELEPHONY_NETWORK_RSSI_0, telephony_network_service_state_e,
+    telephony_network_service_state_e_TELEPHONY_NETWORK_SERVICE_STATE_EMERGENCY_ONLY,
+    telephony_network_service_state_e_TELEPHONY_NETWORK_SERVICE_STATE_IN_SERVICE,
+    telephony_network_service_state_e_TELEPHONY_NETWORK_SERVICE_STATE_OUT_OF_SERVICE,
+    telephony_network_type_e, telephony_network_type_e_TELEPHONY_NETWORK_TYPE_EDGE,
+    telephony_network_type_e_TELEPHONY_NETWORK_TYPE_GPRS,
+    telephony_network_type_e_TELEPHONY_NETWORK_TYPE_GSM,
+    telephony_network_type_e_TELEPHONY_NETWORK_TYPE_HSDPA,
+    telephony_network_type_e_TELEPHONY_NETWORK_TYPE_LTE,
+    telephony_network_type_e_TELEPHONY_NETWORK_TYPE_UMTS,
+    telephony_network_type_e_TELEPHONY_NETWORK_TYPE_UNKNOWN, telephony_noti_e,
+    telephony_set_noti_cb, telephony_sim_get_icc_id, telephony_sim_get_operator,
+    telephony_sim_get_spn, telephony_sim_get_state, telephony_sim_get_subscriber_number,
+    telephony_sim_state_e, telephony_sim_state_e_TELEPHONY_SIM_STATE_AVAILABLE,
+    telephony_sim_state_e_TELEPHONY_SIM_STATE_LOCKED,
+    telephony_sim_state_e_TELEPHONY_SIM_STATE_UNAVAILABLE,
+    telephony_sim_state_e_TELEPHONY_SIM_STATE_UNKNOWN, telephony_unset_noti_cb,
+};
+use std::os::raw::{c_char, c_int, c_void};
+use std::pin::Pin;
+use std::ptr::null_mut;
+use std::rc::Rc;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NetworkType {
+    Unknown,
+    Gsm,
+    Gprs,
+    Edge,
+    Umts,
+    Hsdpa,
+    Lte,
+}
+
+impl From<telephony_network_type_e> for NetworkType {
+    fn from(network_type: telephony_network_type_e) -> NetworkType {
+        match network_type {
+            telephony_network_type_e_TELEPHONY_NETWORK_TYPE_GSM => NetworkType::Gsm,
+            telephony_network_type_e_TELEPHONY_NETWORK_TYPE_GPRS => NetworkType::Gprs,
+            telephony_network_type_e_TELEPHONY_NETWORK_TYPE_EDGE => NetworkType::Edge,
+            telephony_network_type_e_TELEPHONY_NETWORK_TYPE_UMTS => NetworkType::Umts,
+            telephony_network_type_e_TELEPHONY_NETWORK_TYPE_HSDPA => NetworkType::Hsdpa,
+            telephony_network_type_e_TELEPHONY_NETWORK_TYPE_LTE => NetworkType::Lte,
+            _ => NetworkType::Unknown,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ServiceState {
+    InService,
+    OutOfService,
+    /// Only emergency calls can be made.
+    EmergencyOnly,
+}
+
+impl From<telephony_network_service_state_e> for ServiceState {
+    fn from(state: telephony_network_service_state_e) -> ServiceState {
+        match state {
+            telephony_network_service_state_e_TELEPHONY_NETWORK_SERVICE_STATE_IN_SERVICE => {
+                ServiceState::InService
+            }
+            telephony_network_service_state_e_TELEPHONY_NETWORK_SERVICE_STATE_EMERGENCY_ONLY => {
+                ServiceState::EmergencyOnly
+            }
+            _ => ServiceState::OutOfService,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SimState {
+    Unavailable,
+    /// Locked by a PIN, PUK or network lock.
+    Locked,
+    Available,
+    Unknown,
+}
+
+impl From<telephony_sim_state_e> for SimState {
+    fn from(state: telephony_sim_state_e) -> SimState {
+        match state {
+            telephony_sim_state_e_TELEPHONY_SIM_STATE_UNAVAILABLE => SimState::Unavailable,
+            telephony_sim_state_e_TELEPHONY_SIM_STATE_LOCKED => SimState::Locked,
+            telephony_sim_state_e_TELEPHONY_SIM_STATE_AVAILABLE => SimState::Available,
+            _ => SimState::Unknown,
+        }
+    }
+}
+
+type NotiHandler = unsafe extern "C" fn(telephony_h, telephony_noti_e, *mut c_void, *mut c_void);
+type StringGetter = unsafe extern "C" fn(telephony_h, *mut *mut c_char) -> c_int;
+
+/// The handles of all the SIM slots, released once no slot uses them.
+struct HandleList {
+    list: telephony_handle_list_s,
+}
+
+impl HandleList {
+    fn init() -> Result<Rc<HandleList>> {
+        let mut list = telephony_handle_list_s {
+            count: 0,
+            handle: null_mut(),
+        };
+        check(unsafe { telephony_init(&mut list) })?;
+
+        // deinitialized when dropped
+        let list = Rc::new(Self { list });
+
+        if list.len() == 0 {
+            return Err(Error::NotSupported);
+        }
+
+        Ok(list)
+    }
+
+    fn len(&self) -> usize {
+        self.list.count as usize
+    }
+
+    fn handle(&self, slot: usize) -> telephony_h {
+        unsafe { *self.list.handle.add(slot) }
+    }
+}
+
+impl Drop for HandleList {
+    fn drop(&mut self) {
+        unsafe {
+            telephony_deinit(&mut self.list);
+        }
+    }
+}
+
+/// Access to the telephony service through one SIM slot, released when dropped.
+pub struct Telephony {
+    _list: Rc<HandleList>,
+    slot: usize,
+    handle: telephony_h,
+    call_status_fn: Option<Pin<Box<CallStatusFn>>>,
+    signal_strength_fn: Option<Pin<Box<SignalStrengthFn>>>,
+    service_state_fn: Option<Pin<Box<ServiceStateFn>>>,
+    roaming_fn: Option<Pin<Box<RoamingFn>>>,
+    network_type_fn: Option<Pin<Box<NetworkTypeFn>>>,
+}
+
+impl Telephony {
+    /// Uses the first SIM slot, see [`Telephony::all_slots`] on multi-SIM devices.
+    pub fn new() -> Result<Telephony> {
+        Self::for_slot(0)
+    }
+
+    /// Uses the SIM slot at `slot`, from 0.
+    pub fn for_slot(slot: usize) -> Result<Telephony> {
+        let list = HandleList::init()?;
+
+        if slot >= list.len() {
+            return Err(Error::InvalidParameter);
+        }
+
+        Ok(Self::from_list(list, slot))
+    }
+
+    /// One value for each SIM slot of the device, in order.
+    pub fn all_slots() -> Result<Vec<Telephony>> {
+        let list = HandleList::init()?;
+
+        Ok((0..list.len())
+            .map(|slot| Self::from_list(list.clone(), slot))
+            .collect())
+    }
+
+    fn from_list(list: Rc<HandleList>, slot: usize) -> Telephony {
+        let handle = list.handle(slot);
+
+        Self {
+            _list: list,
+            slot,
+            handle,
+            call_status_fn: None,
+            signal_strength_fn: None,
+            service_state_fn: None,
+            roaming_fn: None,
+            network_type_fn: None,
+        }
+    }
+
+    /// The index of the SIM slot used.
+    pub fn slot(&self) -> usize {
+        self.slot
+    }
+
+    /// The slot used by default for voice calls, if the user chose one.
+    pub fn get_default_subscription(&self) -> Result<Option<usize>> {
+        let mut subscription =
+            telephony_network_default_subs_e_TELEPHONY_NETWORK_DEFAULT_SUBS_UNKNOWN;
+        check(unsafe {
+            telephony_network_get_default_subscription(self.handle, &mut subscription)
+        })?;
+
+        Ok(match subscription {
+            telephony_network_default_subs_e_TELEPHONY_NETWORK_DEFAULT_SUBS_SIM1 => Some(0),
+            telephony_network_default_subs_e_TELEPHONY_NETWORK_DEFAULT_SUBS_SIM2 => Some(1),
+            _ => None,
+        })
+    }
+
+    /// The slot used by default for cellular data, if the user chose one.
+    pub fn get_default_data_subscription(&self) -> Result<Option<usize>> {
+        let mut subscription =
+            telephony_network_default_data_subs_e_TELEPHONY_NETWORK_DEFAULT_DATA_SUBS_UNKNOWN;
+        check(unsafe {
+            telephony_network_get_default_data_subscription(self.handle, &mut subscription)
+        })?;
+
+        Ok(match subscription {
+            telephony_network_default_data_subs_e_TELEPHONY_NETWORK_DEFAULT_DATA_SUBS_SIM1 => {
+                Some(0)
+            }
+            telephony_network_default_data_subs_e_TELEPHONY_NETWORK_DEFAULT_DATA_SUBS_SIM2 => {
+                Some(1)
+            }
+            _ => None,
+        })
+    }
+
+    /// Sets `handler` for each of `noti_ids`, none of them if one fails.
+    fn set_noti_cbs(
+        &self,
+        noti_ids: &[telephony_noti_e],
+        handler: NotiHandler,
+        data: *mut c_void,
+    ) -> Result<()> {
+        for (i, &noti_id) in noti_ids.iter().enumerate() {
+            let ret = unsafe { telephony_set_noti_cb(self.handle, noti_id, Some(handler), data) };
+
+            if let Err(e) = check(ret) {
+                self.unset_noti_cbs(&noti_ids[..i])?;
+                return Err(e);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn unset_noti_cbs(&self, noti_ids: &[telephony_noti_e]) -> Result<()> {
+        for &noti_id in noti_ids {
+            check(unsafe { telephony_unset_noti_cb(self.handle, noti_id) })?;
+        }
+
+        Ok(())
+    }
+
+    fn get_string(&self, get: StringGetter) -> Result<String> {
+        let mut value = null_mut();
+        check(unsafe { get(self.handle, &mut value) })?;
+
+        unsafe { take_string(value) }.ok_or(Error::NoData)
+    }
+
+    /// The name of the network the device is registered on.
+    pub fn get_network_name(&self) -> Result<String> {
+        self.get_string(telephony_network_get_network_name)
+    }
+
+    /// The Mobile Country Code of the registered network.
+    pub fn get_mcc(&self) -> Result<String> {
+        self.get_string(telephony_network_get_mcc)
+    }
+
+    /// The Mobile Network Code of the registered network.
+    pub fn get_mnc(&self) -> Result<String> {
+        self.get_string(telephony_network_get_mnc)
+    }
+
+    pub fn get_cell_id(&self) -> Result<i32> {
+        let mut cell_id = 0;
+        check(unsafe { telephony_network_get_cell_id(self.handle, &mut cell_id) })?;
+
+        Ok(cell_id)
+    }
+
+    /// The Location Area Code of the current cell.
+    pub fn get_lac(&self) -> Result<i32> {
+        let mut lac = 0;
+        check(unsafe { telephony_network_get_lac(self.handle, &mut lac) })?;
+
+        Ok(lac)
+    }
+
+    /// The signal strength, as a number of bars from 0 to 6.
+    pub fn get_rssi(&self) -> Result<u8> {
+        let mut rssi: telephony_network_rssi_e = telephony_network_rssi_e_TELEPHONY_NETWORK_RSSI_0;
+        check(unsafe { telephony_network_get_rssi(self.handle, &mut rssi) })?;
+
+        Ok(rssi as u8)
+    }
+
+    pub fn is_roaming(&self) -> Result<bool> {
+        let mut roaming = false;
+        check(unsafe { telephony_network_get_roaming_status(self.handle, &mut roaming) })?;
+
+        Ok(roaming)
+    }
+
+    pub fn get_network_type(&self) -> Result<NetworkType> {
+        let mut network_type = telephony_network_type_e_TELEPHONY_NETWORK_TYPE_UNKNOWN;
+        check(unsafe { telephony_network_get_type(self.handle, &mut network_type) })?;
+
+        Ok(network_type.into())
+    }
+
+    pub fn get_service_state(&self) -> Result<ServiceState> {
+        let mut state =
+            telephony_network_service_state_e_TELEPHONY_NETWORK_SERVICE_STATE_OUT_OF_SERVICE;
+        check(unsafe { telephony_network_get_service_state(self.handle, &mut state) })?;
+
+        Ok(state.into())
+    }
+
+    pub fn get_sim_state(&self) -> Result<SimState> {
+        let mut state = telephony_sim_state_e_TELEPHONY_SIM_STATE_UNKNOWN;
+        check(unsafe { telephony_sim_get_state(self.handle, &mut state) })?;
+
+        Ok(state.into())
+    }
+
+    /// The MCC and MNC of the SIM card operator, which can differ from the registered network.
+    pub fn get_sim_operator(&self) -> Result<String> {
+        self.get_string(telephony_sim_get_operator)
+    }
+
+    /// The Service Provider Name stored on the SIM card.
+    pub fn get_sim_spn(&self) -> Result<String> {
+        self.get_string(telephony_sim_get_spn)
+    }
+
+    /// The serial number of the SIM card.
+    pub fn get_sim_icc_id(&self) -> Result<String> {
+        self.get_string(telephony_sim_get_icc_id)
+    }
+
+    /// The phone number of the SIM card, when the operator stores it there.
+    pub fn get_subscriber_number(&self) -> Result<String> {
+        self.get_string(telephony_sim_get_subscriber_number)
+    }
+
+    /// The IMEI of the modem, [`Error::PermissionDenied`] where the platform restricts it.
+    pub fn get_imei(&self) -> Result<String> {
+        self.get_string(telephony_modem_get_imei)
+    }
+}
+
+impl Drop for Telephony {
+    fn drop(&mut self) {
+        let _ = self.unset_call_status_cb();
+        let _ = self.unset_signal_strength_cb();
+        let _ = self.unset_service_state_cb();
+        let _ = self.unset_roaming_cb();
+        let _ = self.unset_network_type_cb();
+    }
+}
@@ -0,0 +1,231 @@
+use super::{NetworkType, ServiceState, Telephony};
+use crate::error::Result;
+use crate::rutin::{abort_on_panic, pin_callback};
+#[cfg(feature = "async")]
+use crate::stream::EventStream;
+use rutin_tizen_sys::{
+    telephony_h, telephony_network_rssi_e, telephony_network_service_state_e,
+    telephony_network_type_e, telephony_noti_e,
+    telephony_noti_e_TELEPHONY_NOTI_NETWORK_NETWORK_TYPE,
+    telephony_noti_e_TELEPHONY_NOTI_NETWORK_ROAMING_STATUS,
+    telephony_noti_e_TELEPHONY_NOTI_NETWORK_SERVICE_STATE,
+    telephony_noti_e_TELEPHONY_NOTI_NETWORK_SIGNALSTRENGTH_LEVEL,
+};
+use std::os::raw::c_void;
+
+pub(super) type SignalStrengthFn = Box<dyn FnMut(u8)>;
+pub(super) type ServiceStateFn = Box<dyn FnMut(ServiceState)>;
+pub(super) type RoamingFn = Box<dyn FnMut(bool)>;
+pub(super) type NetworkTypeFn = Box<dyn FnMut(NetworkType)>;
+
+const SIGNAL_STRENGTH_NOTI: [telephony_noti_e; 1] =
+    [telephony_noti_e_TELEPHONY_NOTI_NETWORK_SIGNALSTRENGTH_LEVEL];
+const SERVICE_STATE_NOTI: [telephony_noti_e; 1] =
+    [telephony_noti_e_TELEPHONY_NOTI_NETWORK_SERVICE_STATE];
+const ROAMING_NOTI: [telephony_noti_e; 1] =
+    [telephony_noti_e_TELEPHONY_NOTI_NETWORK_ROAMING_STATUS];
+const NETWORK_TYPE_NOTI: [telephony_noti_e; 1] =
+    [telephony_noti_e_TELEPHONY_NOTI_NETWORK_NETWORK_TYPE];
+
+/// A change of the cellular network, as delivered by [`Telephony::events`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TelephonyEvent {
+    /// The signal strength, as a number of bars from 0 to 6.
+    SignalStrengthChanged(u8),
+    ServiceStateChanged(ServiceState),
+    RoamingChanged(bool),
+    /// The network type, which sets the available data speed. Whether data is actually
+    /// connected is reported by `network::connection`.
+    NetworkTypeChanged(NetworkType),
+}
+
+impl Telephony {
+    /// Returns a stream of signal strength, service state, roaming and network type changes
+    /// of the SIM slot at `slot`.
+    ///
+    /// The stream uses its own telephony handle, so it does not replace the callbacks set
+    /// on this one.
+    #[cfg(feature = "async")]
+    pub fn events(slot: usize) -> Result<EventStream<TelephonyEvent>> {
+        EventStream::new(|sender| {
+            let mut telephony = Telephony::for_slot(slot)?;
+
+            let signal_sender = sender.clone();
+            telephony.set_signal_strength_cb(move |bars| {
+                signal_sender.send(TelephonyEvent::SignalStrengthChanged(bars))
+            })?;
+
+            let service_sender = sender.clone();
+            telephony.set_service_state_cb(move |state| {
+                service_sender.send(TelephonyEvent::ServiceStateChanged(state))
+            })?;
+
+            let roaming_sender = sender.clone();
+            telephony.set_roaming_cb(move |roaming| {
+                roaming_sender.send(TelephonyEvent::RoamingChanged(roaming))
+            })?;
+
+            telephony.set_network_type_cb(move |network_type| {
+                sender.send(TelephonyEvent::NetworkTypeChanged(network_type))
+            })?;
+
+            Ok(telephony)
+        })
+    }
+
+    /// Calls `callback_fn` with the number of bars whenever the signal strength changes,
+    /// replacing the previous callback.
+    pub fn set_signal_strength_cb<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(u8),
+        F: 'static,
+    {
+        self.unset_signal_strength_cb()?;
+
+        let (callback_fn, data) = pin_callback::<dyn FnMut(u8)>(Box::new(callback_fn));
+
+        self.set_noti_cbs(&SIGNAL_STRENGTH_NOTI, signal_strength_handler, data)?;
+        self.signal_strength_fn = Some(callback_fn);
+
+        Ok(())
+    }
+
+    pub fn unset_signal_strength_cb(&mut self) -> Result<()> {
+        if self.signal_strength_fn.is_some() {
+            self.unset_noti_cbs(&SIGNAL_STRENGTH_NOTI)?;
+            self.signal_strength_fn = None;
+        }
+
+        Ok(())
+    }
+
+    /// Calls `callback_fn` whenever the service state changes, replacing the previous
+    /// callback.
+    pub fn set_service_state_cb<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(ServiceState),
+        F: 'static,
+    {
+        self.unset_service_state_cb()?;
+
+        let (callback_fn, data) = pin_callback::<dyn FnMut(ServiceState)>(Box::new(callback_fn));
+
+        self.set_noti_cbs(&SERVICE_STATE_NOTI, service_state_handler, data)?;
+        self.service_state_fn = Some(callback_fn);
+
+        Ok(())
+    }
+
+    pub fn unset_service_state_cb(&mut self) -> Result<()> {
+        if self.service_state_fn.is_some() {
+            self.unset_noti_cbs(&SERVICE_STATE_NOTI)?;
+            self.service_state_fn = None;
+        }
+
+        Ok(())
+    }
+
+    /// Calls `callback_fn` whenever the device starts or stops roaming, replacing the
+    /// previous callback.
+    pub fn set_roaming_cb<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(bool),
+        F: 'static,
+    {
+        self.unset_roaming_cb()?;
+
+        let (callback_fn, data) = pin_callback::<dyn FnMut(bool)>(Box::new(callback_fn));
+
+        self.set_noti_cbs(&ROAMING_NOTI, roaming_handler, data)?;
+        self.roaming_fn = Some(callback_fn);
+
+        Ok(())
+    }
+
+    pub fn unset_roaming_cb(&mut self) -> Result<()> {
+        if self.roaming_fn.is_some() {
+            self.unset_noti_cbs(&ROAMING_NOTI)?;
+            self.roaming_fn = None;
+        }
+
+        Ok(())
+    }
+
+    /// Calls `callback_fn` whenever the network type changes, replacing the previous
+    /// callback.
+    pub fn set_network_type_cb<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(NetworkType),
+        F: 'static,
+    {
+        self.unset_network_type_cb()?;
+
+        let (callback_fn, data) = pin_callback::<dyn FnMut(NetworkType)>(Box::new(callback_fn));
+
+        self.set_noti_cbs(&NETWORK_TYPE_NOTI, network_type_handler, data)?;
+        self.network_type_fn = Some(callback_fn);
+
+        Ok(())
+    }
+
+    pub fn unset_network_type_cb(&mut self) -> Result<()> {
+        if self.network_type_fn.is_some() {
+            self.unset_noti_cbs(&NETWORK_TYPE_NOTI)?;
+            self.network_type_fn = None;
+        }
+
+        Ok(())
+    }
+}
+
+extern "C" fn signal_strength_handler(
+    _handle: telephony_h,
+    _noti_id: telephony_noti_e,
+    data: *mut c_void,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut SignalStrengthFn) };
+        let rssi = unsafe { *(data as *const telephony_network_rssi_e) };
+        closure(rssi as u8);
+    })
+}
+
+extern "C" fn service_state_handler(
+    _handle: telephony_h,
+    _noti_id: telephony_noti_e,
+    data: *mut c_void,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut ServiceStateFn) };
+        let state = unsafe { *(data as *const telephony_network_service_state_e) };
+        closure(state.into());
+    })
+}
+
+extern "C" fn roaming_handler(
+    _handle: telephony_h,
+    _noti_id: telephony_noti_e,
+    data: *mut c_void,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut RoamingFn) };
+        closure(unsafe { *(data as *const bool) });
+    })
+}
+
+extern "C" fn network_type_handler(
+    _handle: telephony_h,
+    _noti_id: telephony_noti_e,
+    data: *mut c_void,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut NetworkTypeFn) };
+        let network_type = unsafe { *(data as *const telephony_network_type_e) };
+        closure(network_type.into());
+    })
+}
@@ -0,0 +1,582 @@
+//! Input method editors, the apps providing the on-screen keyboards.
+//!
+//! A keyboard implements [`ImeApp`] and calls [`ImeApp::main`] instead of running an
+//! `app::UIApp`. Its keys are drawn in the [`main_window`] and typed into the focused app
+//! with [`commit_string`] or [`send_key_event`].
+//!
+//! Requires the `http://tizen.org/privilege/ime` privilege.
+
+use crate::efl::elm::Win;
+use crate::error::{check, Result};
+use crate::rutin::{abort_on_panic, borrow_str, cstring, take_string};
+use rutin_tizen_sys::{
+    ime_callback_s, ime_commit_string, ime_context_get_autocapital_type,
+    ime_context_get_cursor_position, ime_context_get_layout, ime_context_get_password_mode,
+    ime_context_get_prediction_mode, ime_context_get_return_key_state,
+    ime_context_get_return_key_type, ime_context_h, ime_delete_surrounding_text, ime_device_info_h,
+    ime_event_set_accessibility_state_changed_cb, ime_event_set_cursor_position_updated_cb,
+    ime_event_set_display_language_changed_cb, ime_event_set_focus_in_cb,
+    ime_event_set_focus_out_cb, ime_event_set_input_context_reset_cb,
+    ime_event_set_process_key_event_cb, ime_event_set_return_key_state_set_cb,
+    ime_event_set_rotation_degree_changed_cb, ime_event_set_surrounding_text_updated_cb,
+    ime_get_main_window, ime_get_selected_text, ime_get_surrounding_text, ime_hide_preedit_string,
+    ime_key_code_e, ime_key_mask_e, ime_request_hide, ime_request_surrounding_text, ime_run,
+    ime_send_key_event, ime_set_selection, ime_set_size, ime_show_preedit_string,
+    ime_update_preedit_string, Ecore_IMF_Autocapital_Type,
+    Ecore_IMF_Autocapital_Type_ECORE_IMF_AUTOCAPITAL_TYPE_ALLCHARACTER,
+    Ecore_IMF_Autocapital_Type_ECORE_IMF_AUTOCAPITAL_TYPE_NONE,
+    Ecore_IMF_Autocapital_Type_ECORE_IMF_AUTOCAPITAL_TYPE_SENTENCE,
+    Ecore_IMF_Autocapital_Type_ECORE_IMF_AUTOCAPITAL_TYPE_WORD, Ecore_IMF_Input_Panel_Layout,
+    Ecore_IMF_Input_Panel_Layout_ECORE_IMF_INPUT_PANEL_LAYOUT_DATETIME,
+    Ecore_IMF_Input_Panel_Layout_ECORE_IMF_INPUT_PANEL_LAYOUT_EMAIL,
+    Ecore_IMF_Input_Panel_Layout_ECORE_IMF_INPUT_PANEL_LAYOUT_EMOTICON,
+    Ecore_IMF_Input_Panel_Layout_ECORE_IMF_INPUT_PANEL_LAYOUT_HEX,
+    Ecore_IMF_Input_Panel_Layout_ECORE_IMF_INPUT_PANEL_LAYOUT_IP,
+    Ecore_IMF_Input_Panel_Layout_ECORE_IMF_INPUT_PANEL_LAYOUT_MONTH,
+    Ecore_IMF_Input_Panel_Layout_ECORE_IMF_INPUT_PANEL_LAYOUT_NORMAL,
+    Ecore_IMF_Input_Panel_Layout_ECORE_IMF_INPUT_PANEL_LAYOUT_NUMBER,
+    Ecore_IMF_Input_Panel_Layout_ECORE_IMF_INPUT_PANEL_LAYOUT_NUMBERONLY,
+    Ecore_IMF_Input_Panel_Layout_ECORE_IMF_INPUT_PANEL_LAYOUT_PASSWORD,
+    Ecore_IMF_Input_Panel_Layout_ECORE_IMF_INPUT_PANEL_LAYOUT_PHONENUMBER,
+    Ecore_IMF_Input_Panel_Layout_ECORE_IMF_INPUT_PANEL_LAYOUT_TERMINAL,
+    Ecore_IMF_Input_Panel_Layout_ECORE_IMF_INPUT_PANEL_LAYOUT_URL,
+    Ecore_IMF_Input_Panel_Layout_ECORE_IMF_INPUT_PANEL_LAYOUT_VOICE,
+    Ecore_IMF_Input_Panel_Return_Key_Type,
+    Ecore_IMF_Input_Panel_Return_Key_Type_ECORE_IMF_INPUT_PANEL_RETURN_KEY_TYPE_DEFAULT,
+    Ecore_IMF_Input_Panel_Return_Key_Type_ECORE_IMF_INPUT_PANEL_RETURN_KEY_TYPE_DONE,
+    Ecore_IMF_Input_Panel_Return_Key_Type_ECORE_IMF_INPUT_PANEL_RETURN_KEY_TYPE_GO,
+    Ecore_IMF_Input_Panel_Return_Key_Type_ECORE_IMF_INPUT_PANEL_RETURN_KEY_TYPE_JOIN,
+    Ecore_IMF_Input_Panel_Return_Key_Type_ECORE_IMF_INPUT_PANEL_RETURN_KEY_TYPE_LOGIN,
+    Ecore_IMF_Input_Panel_Return_Key_Type_ECORE_IMF_INPUT_PANEL_RETURN_KEY_TYPE_NEXT,
+    Ecore_IMF_Input_Panel_Return_Key_Type_ECORE_IMF_INPUT_PANEL_RETURN_KEY_TYPE_SEARCH,
+    Ecore_IMF_Input_Panel_Return_Key_Type_ECORE_IMF_INPUT_PANEL_RETURN_KEY_TYPE_SEND,
+    Ecore_IMF_Input_Panel_Return_Key_Type_ECORE_IMF_INPUT_PANEL_RETURN_KEY_TYPE_SIGNIN,
+};
+use std::os::raw::{c_char, c_int, c_void};
+use std::ptr::null_mut;
+
+/// Key codes, the X11 keysyms of the keys. Printable ASCII characters are their own code.
+pub mod key_code {
+    pub const BACKSPACE: u32 = 0xff08;
+    pub const TAB: u32 = 0xff09;
+    pub const RETURN: u32 = 0xff0d;
+    pub const ESCAPE: u32 = 0xff1b;
+    pub const DELETE: u32 = 0xffff;
+    pub const SPACE: u32 = 0x20;
+    pub const LEFT: u32 = 0xff51;
+    pub const UP: u32 = 0xff52;
+    pub const RIGHT: u32 = 0xff53;
+    pub const DOWN: u32 = 0xff54;
+}
+
+/// Key masks, the state of the key and of the modifiers. A key is pressed unless
+/// `RELEASED` is set.
+pub mod key_mask {
+    pub const SHIFT: u32 = 1 << 0;
+    pub const CAPSLOCK: u32 = 1 << 1;
+    pub const CONTROL: u32 = 1 << 2;
+    pub const ALT: u32 = 1 << 3;
+    pub const META: u32 = 1 << 4;
+    pub const NUMLOCK: u32 = 1 << 7;
+    pub const RELEASED: u32 = 1 << 15;
+}
+
+/// The kind of text expected by the focused entry, which decides the keys to show.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Layout {
+    Normal,
+    Number,
+    Email,
+    Url,
+    PhoneNumber,
+    Ip,
+    Month,
+    NumberOnly,
+    Hex,
+    Terminal,
+    Password,
+    DateTime,
+    Emoticon,
+    Voice,
+}
+
+impl From<Ecore_IMF_Input_Panel_Layout> for Layout {
+    fn from(layout: Ecore_IMF_Input_Panel_Layout) -> Layout {
+        match layout {
+            Ecore_IMF_Input_Panel_Layout_ECORE_IMF_INPUT_PANEL_LAYOUT_NUMBER => Layout::Number,
+            Ecore_IMF_Input_Panel_Layout_ECORE_IMF_INPUT_PANEL_LAYOUT_EMAIL => Layout::Email,
+            Ecore_IMF_Input_Panel_Layout_ECORE_IMF_INPUT_PANEL_LAYOUT_URL => Layout::Url,
+            Ecore_IMF_Input_Panel_Layout_ECORE_IMF_INPUT_PANEL_LAYOUT_PHONENUMBER => {
+                Layout::PhoneNumber
+            }
+            Ecore_IMF_Input_Panel_Layout_ECORE_IMF_INPUT_PANEL_LAYOUT_IP => Layout::Ip,
+            Ecore_IMF_Input_Panel_Layout_ECORE_IMF_INPUT_PANEL_LAYOUT_MONTH => Layout::Month,
+            Ecore_IMF_Input_Panel_Layout_ECORE_IMF_INPUT_PANEL_LAYOUT_NUMBERONLY => {
+                Layout::NumberOnly
+            }
+            Ecore_IMF_Input_Panel_Layout_ECORE_IMF_INPUT_PANEL_LAYOUT_HEX => Layout::Hex,
+            Ecore_IMF_Input_Panel_Layout_ECORE_IMF_INPUT_PANEL_LAYOUT_TERMINAL => Layout::Terminal,
+            Ecore_IMF_Input_Panel_Layout_ECORE_IMF_INPUT_PANEL_LAYOUT_PASSWORD => Layout::Password,
+            Ecore_IMF_Input_Panel_Layout_ECORE_IMF_INPUT_PANEL_LAYOUT_DATETIME => Layout::DateTime,
+            Ecore_IMF_Input_Panel_Layout_ECORE_IMF_INPUT_PANEL_LAYOUT_EMOTICON => Layout::Emoticon,
+            Ecore_IMF_Input_Panel_Layout_ECORE_IMF_INPUT_PANEL_LAYOUT_VOICE => Layout::Voice,
+            _ => Layout::Normal,
+        }
+    }
+}
+
+/// The label of the return key, as requested by the focused entry.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ReturnKeyType {
+    Default,
+    Done,
+    Go,
+    Join,
+    Login,
+    Next,
+    Search,
+    Send,
+    SignIn,
+}
+
+impl From<Ecore_IMF_Input_Panel_Return_Key_Type> for ReturnKeyType {
+    fn from(return_key_type: Ecore_IMF_Input_Panel_Return_Key_Type) -> ReturnKeyType {
+        match return_key_type {
+            Ecore_IMF_Input_Panel_Return_Key_Type_ECORE_IMF_INPUT_PANEL_RETURN_KEY_TYPE_DONE => {
+                ReturnKeyType::Done
+            }
+            Ecore_IMF_Input_Panel_Return_Key_Type_ECORE_IMF_INPUT_PANEL_RETURN_KEY_TYPE_GO => {
+                ReturnKeyType::Go
+            }
+            Ecore_IMF_Input_Panel_Return_Key_Type_ECORE_IMF_INPUT_PANEL_RETURN_KEY_TYPE_JOIN => {
+                ReturnKeyType::Join
+            }
+            Ecore_IMF_Input_Panel_Return_Key_Type_ECORE_IMF_INPUT_PANEL_RETURN_KEY_TYPE_LOGIN => {
+                ReturnKeyType::Login
+            }
+            Ecore_IMF_Input_Panel_Return_Key_Type_ECORE_IMF_INPUT_PANEL_RETURN_KEY_TYPE_NEXT => {
+                ReturnKeyType::Next
+            }
+            Ecore_IMF_Input_Panel_Return_Key_Type_ECORE_IMF_INPUT_PANEL_RETURN_KEY_TYPE_SEARCH => {
+                ReturnKeyType::Search
+            }
+            Ecore_IMF_Input_Panel_Return_Key_Type_ECORE_IMF_INPUT_PANEL_RETURN_KEY_TYPE_SEND => {
+                ReturnKeyType::Send
+            }
+            Ecore_IMF_Input_Panel_Return_Key_Type_ECORE_IMF_INPUT_PANEL_RETURN_KEY_TYPE_SIGNIN => {
+                ReturnKeyType::SignIn
+            }
+            _ => ReturnKeyType::Default,
+        }
+    }
+}
+
+/// Which letters typed in the focused entry should be capitalized.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AutocapitalType {
+    None,
+    Word,
+    Sentence,
+    AllCharacter,
+}
+
+impl From<Ecore_IMF_Autocapital_Type> for AutocapitalType {
+    fn from(autocapital_type: Ecore_IMF_Autocapital_Type) -> AutocapitalType {
+        match autocapital_type {
+            Ecore_IMF_Autocapital_Type_ECORE_IMF_AUTOCAPITAL_TYPE_WORD => AutocapitalType::Word,
+            Ecore_IMF_Autocapital_Type_ECORE_IMF_AUTOCAPITAL_TYPE_SENTENCE => {
+                AutocapitalType::Sentence
+            }
+            Ecore_IMF_Autocapital_Type_ECORE_IMF_AUTOCAPITAL_TYPE_ALLCHARACTER => {
+                AutocapitalType::AllCharacter
+            }
+            _ => AutocapitalType::None,
+        }
+    }
+}
+
+/// The attributes of the entry the keyboard is shown for.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InputContext {
+    pub layout: Layout,
+    pub return_key_type: ReturnKeyType,
+    pub return_key_disabled: bool,
+    pub cursor_position: i32,
+    pub autocapital_type: AutocapitalType,
+    /// Whether word predictions may be shown.
+    pub prediction_allowed: bool,
+    pub password_mode: bool,
+}
+
+impl InputContext {
+    unsafe fn from_raw(context: ime_context_h) -> Result<InputContext> {
+        let mut layout = Ecore_IMF_Input_Panel_Layout_ECORE_IMF_INPUT_PANEL_LAYOUT_NORMAL;
+        check(ime_context_get_layout(context, &mut layout))?;
+
+        let mut return_key_type =
+            Ecore_IMF_Input_Panel_Return_Key_Type_ECORE_IMF_INPUT_PANEL_RETURN_KEY_TYPE_DEFAULT;
+        check(ime_context_get_return_key_type(
+            context,
+            &mut return_key_type,
+        ))?;
+
+        let mut return_key_disabled = false;
+        check(ime_context_get_return_key_state(
+            context,
+            &mut return_key_disabled,
+        ))?;
+
+        let mut cursor_position = 0;
+        check(ime_context_get_cursor_position(
+            context,
+            &mut cursor_position,
+        ))?;
+
+        let mut autocapital_type = Ecore_IMF_Autocapital_Type_ECORE_IMF_AUTOCAPITAL_TYPE_NONE;
+        check(ime_context_get_autocapital_type(
+            context,
+            &mut autocapital_type,
+        ))?;
+
+        let mut prediction_allowed = false;
+        check(ime_context_get_prediction_mode(
+            context,
+            &mut prediction_allowed,
+        ))?;
+
+        let mut password_mode = false;
+        check(ime_context_get_password_mode(context, &mut password_mode))?;
+
+        Ok(Self {
+            layout: layout.into(),
+            return_key_type: return_key_type.into(),
+            return_key_disabled,
+            cursor_position,
+            autocapital_type: autocapital_type.into(),
+            prediction_allowed,
+            password_mode,
+        })
+    }
+}
+
+/// A key event of a hardware keyboard, see [`key_code`] and [`key_mask`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KeyEvent {
+    pub code: u32,
+    pub mask: u32,
+}
+
+/// The events of an input method, only `create`, `terminate`, `show` and `hide` are required.
+///
+/// `context_id` identifies the entry the keyboard is shown for.
+pub trait ImeApp: Sized {
+    /// Called once the main window exists, to fill it with the keys.
+    fn create(&mut self);
+    fn terminate(&mut self);
+    fn show(&mut self, context_id: i32, context: Result<InputContext>);
+    fn hide(&mut self, context_id: i32);
+
+    fn focus_in(&mut self, _context_id: i32) {}
+    fn focus_out(&mut self, _context_id: i32) {}
+
+    /// The text around the cursor, after a [`request_surrounding_text`].
+    fn surrounding_text_updated(&mut self, _context_id: i32, _text: &str, _cursor_position: i32) {}
+
+    /// The entry was cleared or its content was replaced, the preedit string should be dropped.
+    fn input_context_reset(&mut self) {}
+    fn cursor_position_updated(&mut self, _cursor_position: i32) {}
+    fn return_key_state_set(&mut self, _disabled: bool) {}
+    fn rotation_degree_changed(&mut self, _degree: i32) {}
+    fn display_language_changed(&mut self, _language: &str) {}
+    fn accessibility_state_changed(&mut self, _enabled: bool) {}
+
+    /// A key of a hardware keyboard, returns whether the keyboard handled it. Unhandled keys
+    /// are delivered to the focused app.
+    fn process_key_event(&mut self, _event: KeyEvent) -> bool {
+        false
+    }
+
+    /// Runs the main loop of the keyboard until it terminates.
+    fn main(&mut self) -> Result<()> {
+        let data = self as *mut Self as *mut c_void;
+
+        unsafe {
+            check(ime_event_set_focus_in_cb(
+                Some(focus_in_handler::<Self>),
+                data,
+            ))?;
+            check(ime_event_set_focus_out_cb(
+                Some(focus_out_handler::<Self>),
+                data,
+            ))?;
+            check(ime_event_set_surrounding_text_updated_cb(
+                Some(surrounding_text_updated_handler::<Self>),
+                data,
+            ))?;
+            check(ime_event_set_input_context_reset_cb(
+                Some(input_context_reset_handler::<Self>),
+                data,
+            ))?;
+            check(ime_event_set_cursor_position_updated_cb(
+                Some(cursor_position_updated_handler::<Self>),
+                data,
+            ))?;
+            check(ime_event_set_return_key_state_set_cb(
+                Some(return_key_state_set_handler::<Self>),
+                data,
+            ))?;
+            check(ime_event_set_rotation_degree_changed_cb(
+                Some(rotation_degree_changed_handler::<Self>),
+                data,
+            ))?;
+            check(ime_event_set_display_language_changed_cb(
+                Some(display_language_changed_handler::<Self>),
+                data,
+            ))?;
+            check(ime_event_set_accessibility_state_changed_cb(
+                Some(accessibility_state_changed_handler::<Self>),
+                data,
+            ))?;
+            check(ime_event_set_process_key_event_cb(
+                Some(process_key_event_handler::<Self>),
+                data,
+            ))?;
+        }
+
+        let mut basic_callback = ime_callback_s {
+            create: Some(create_handler::<Self>),
+            terminate: Some(terminate_handler::<Self>),
+            show: Some(show_handler::<Self>),
+            hide: Some(hide_handler::<Self>),
+        };
+
+        check(unsafe { ime_run(&mut basic_callback, data) })
+    }
+}
+
+/// The window of the keyboard, created by the framework before [`ImeApp::create`].
+pub fn main_window() -> Option<Win<'static>> {
+    Win::from_eo(unsafe { ime_get_main_window() })
+}
+
+/// Sets the size of the main window in both orientations.
+pub fn set_size(
+    portrait_width: i32,
+    portrait_height: i32,
+    landscape_width: i32,
+    landscape_height: i32,
+) -> Result<()> {
+    check(unsafe {
+        ime_set_size(
+            portrait_width,
+            portrait_height,
+            landscape_width,
+            landscape_height,
+        )
+    })
+}
+
+/// Types `text` into the focused entry.
+pub fn commit_string(text: &str) -> Result<()> {
+    let text = cstring(text)?;
+
+    check(unsafe { ime_commit_string(text.as_ptr()) })
+}
+
+/// Sends a key event to the focused app, `forward` sends it as is instead of as typed by
+/// the keyboard.
+pub fn send_key_event(code: u32, mask: u32, forward: bool) -> Result<()> {
+    check(unsafe { ime_send_key_event(code as ime_key_code_e, mask as ime_key_mask_e, forward) })
+}
+
+/// Shows the text being composed, not yet committed, under the cursor.
+pub fn show_preedit_string() -> Result<()> {
+    check(unsafe { ime_show_preedit_string() })
+}
+
+pub fn hide_preedit_string() -> Result<()> {
+    check(unsafe { ime_hide_preedit_string() })
+}
+
+pub fn update_preedit_string(text: &str) -> Result<()> {
+    let text = cstring(text)?;
+
+    check(unsafe { ime_update_preedit_string(text.as_ptr(), null_mut()) })
+}
+
+/// Asks for the text around the cursor, delivered to
+/// [`ImeApp::surrounding_text_updated`]. Negative lengths ask for all of the text.
+pub fn request_surrounding_text(max_len_before: i32, max_len_after: i32) -> Result<()> {
+    check(unsafe { ime_request_surrounding_text(max_len_before, max_len_after) })
+}
+
+/// Returns the text around the cursor and the position of the cursor in it.
+pub fn get_surrounding_text(max_len_before: i32, max_len_after: i32) -> Result<(String, i32)> {
+    let mut text = null_mut();
+    let mut cursor_position = 0;
+    check(unsafe {
+        ime_get_surrounding_text(
+            max_len_before,
+            max_len_after,
+            &mut text,
+            &mut cursor_position,
+        )
+    })?;
+
+    Ok((
+        unsafe { take_string(text) }.unwrap_or_default(),
+        cursor_position,
+    ))
+}
+
+/// Deletes `len` characters from `offset`, relative to the cursor.
+pub fn delete_surrounding_text(offset: i32, len: i32) -> Result<()> {
+    check(unsafe { ime_delete_surrounding_text(offset, len) })
+}
+
+pub fn set_selection(start: i32, end: i32) -> Result<()> {
+    check(unsafe { ime_set_selection(start, end) })
+}
+
+pub fn get_selected_text() -> Result<Option<String>> {
+    let mut text = null_mut();
+    check(unsafe { ime_get_selected_text(&mut text) })?;
+
+    Ok(unsafe { take_string(text) })
+}
+
+/// Asks the framework to hide the keyboard, [`ImeApp::hide`] is called once hidden.
+pub fn request_hide() -> Result<()> {
+    check(unsafe { ime_request_hide() })
+}
+
+extern "C" fn create_handler<T: ImeApp>(user_data: *mut c_void) {
+    abort_on_panic(|| {
+        let app = unsafe { &mut *(user_data as *mut T) };
+        app.create();
+    })
+}
+
+extern "C" fn terminate_handler<T: ImeApp>(user_data: *mut c_void) {
+    abort_on_panic(|| {
+        let app = unsafe { &mut *(user_data as *mut T) };
+        app.terminate();
+    })
+}
+
+extern "C" fn show_handler<T: ImeApp>(
+    context_id: c_int,
+    context: ime_context_h,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let app = unsafe { &mut *(user_data as *mut T) };
+        app.show(context_id, unsafe { InputContext::from_raw(context) });
+    })
+}
+
+extern "C" fn hide_handler<T: ImeApp>(context_id: c_int, user_data: *mut c_void) {
+    abort_on_panic(|| {
+        let app = unsafe { &mut *(user_data as *mut T) };
+        app.hide(context_id);
+    })
+}
+
+extern "C" fn focus_in_handler<T: ImeApp>(context_id: c_int, user_data: *mut c_void) {
+    abort_on_panic(|| {
+        let app = unsafe { &mut *(user_data as *mut T) };
+        app.focus_in(context_id);
+    })
+}
+
+extern "C" fn focus_out_handler<T: ImeApp>(context_id: c_int, user_data: *mut c_void) {
+    abort_on_panic(|| {
+        let app = unsafe { &mut *(user_data as *mut T) };
+        app.focus_out(context_id);
+    })
+}
+
+extern "C" fn surrounding_text_updated_handler<T: ImeApp>(
+    context_id: c_int,
+    text: *const c_char,
+    cursor_position: c_int,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let app = unsafe { &mut *(user_data as *mut T) };
+        let text = unsafe { borrow_str(text) }.unwrap_or_default();
+        app.surrounding_text_updated(context_id, &text, cursor_position);
+    })
+}
+
+extern "C" fn input_context_reset_handler<T: ImeApp>(user_data: *mut c_void) {
+    abort_on_panic(|| {
+        let app = unsafe { &mut *(user_data as *mut T) };
+        app.input_context_reset();
+    })
+}
+
+extern "C" fn cursor_position_updated_handler<T: ImeApp>(
+    cursor_position: c_int,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let app = unsafe { &mut *(user_data as *mut T) };
+        app.cursor_position_updated(cursor_position);
+    })
+}
+
+extern "C" fn return_key_state_set_handler<T: ImeApp>(disabled: bool, user_data: *mut c_void) {
+    abort_on_panic(|| {
+        let app = unsafe { &mut *(user_data as *mut T) };
+        app.return_key_state_set(disabled);
+    })
+}
+
+extern "C" fn rotation_degree_changed_handler<T: ImeApp>(degree: c_int, user_data: *mut c_void) {
+    abort_on_panic(|| {
+        let app = unsafe { &mut *(user_data as *mut T) };
+        app.rotation_degree_changed(degree);
+    })
+}
+
+extern "C" fn display_language_changed_handler<T: ImeApp>(
+    language: *const c_char,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let app = unsafe { &mut *(user_data as *mut T) };
+        let language = unsafe { borrow_str(language) }.unwrap_or_default();
+        app.display_language_changed(&language);
+    })
+}
+
+extern "C" fn accessibility_state_changed_handler<T: ImeApp>(
+    enabled: bool,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let app = unsafe { &mut *(user_data as *mut T) };
+        app.accessibility_state_changed(enabled);
+    })
+}
+
+extern "C" fn process_key_event_handler<T: ImeApp>(
+    code: ime_key_code_e,
+    mask: ime_key_mask_e,
+    _device_info: ime_device_info_h,
+    user_data: *mut c_void,
+) -> bool {
+    abort_on_panic(|| {
+        let app = unsafe { &mut *(user_data as *mut T) };
+        app.process_key_event(KeyEvent {
+            code: code as u32,
+            mask: mask as u32,
+        })
+    })
+}
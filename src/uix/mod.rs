@@ -0,0 +1,3 @@
+#[cfg(feature = "efl")]
+pub mod ime;
+pub mod tts;
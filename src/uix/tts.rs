@@ -0,0 +1,432 @@
+//! Text-to-speech, reading text aloud with the voices installed on the device.
+//!
+//! Texts can only be added once prepared, which completes asynchronously with a change to
+//! [`TtsState::Ready`].
+
+use crate::error::{check, Error, Result};
+use crate::rutin::{abort_on_panic, borrow_str, cstring, pin_callback, take_string};
+use rutin_tizen_sys::{
+    tts_add_text, tts_create, tts_destroy, tts_foreach_supported_voices, tts_get_default_voice,
+    tts_get_speed_range, tts_get_state, tts_h, tts_mode_e, tts_mode_e_TTS_MODE_DEFAULT,
+    tts_mode_e_TTS_MODE_NOTIFICATION, tts_mode_e_TTS_MODE_SCREEN_READER, tts_pause, tts_play,
+    tts_prepare, tts_set_error_cb, tts_set_mode, tts_set_state_changed_cb,
+    tts_set_utterance_completed_cb, tts_set_utterance_started_cb, tts_state_e,
+    tts_state_e_TTS_STATE_CREATED, tts_state_e_TTS_STATE_PAUSED, tts_state_e_TTS_STATE_PLAYING,
+    tts_state_e_TTS_STATE_READY, tts_stop, tts_unprepare, tts_unset_error_cb,
+    tts_unset_state_changed_cb, tts_unset_utterance_completed_cb, tts_unset_utterance_started_cb,
+    tts_voice_type_e, tts_voice_type_e_TTS_VOICE_TYPE_AUTO, tts_voice_type_e_TTS_VOICE_TYPE_CHILD,
+    tts_voice_type_e_TTS_VOICE_TYPE_FEMALE, tts_voice_type_e_TTS_VOICE_TYPE_MALE, TTS_SPEED_AUTO,
+};
+use std::convert::TryFrom;
+use std::ffi::CString;
+use std::os::raw::{c_char, c_int, c_void};
+use std::pin::Pin;
+use std::ptr::{null, null_mut};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TtsState {
+    Created,
+    Ready,
+    Playing,
+    Paused,
+}
+
+impl TryFrom<tts_state_e> for TtsState {
+    type Error = Error;
+
+    fn try_from(state: tts_state_e) -> Result<TtsState> {
+        match state {
+            tts_state_e_TTS_STATE_CREATED => Ok(TtsState::Created),
+            tts_state_e_TTS_STATE_READY => Ok(TtsState::Ready),
+            tts_state_e_TTS_STATE_PLAYING => Ok(TtsState::Playing),
+            tts_state_e_TTS_STATE_PAUSED => Ok(TtsState::Paused),
+            _ => Err(Error::Unknown),
+        }
+    }
+}
+
+/// How the speech is mixed with the other sounds, set before preparing.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Mode {
+    Default,
+    Notification,
+    /// Only speaks while the screen reader is on.
+    ScreenReader,
+}
+
+impl From<Mode> for tts_mode_e {
+    fn from(mode: Mode) -> tts_mode_e {
+        match mode {
+            Mode::Default => tts_mode_e_TTS_MODE_DEFAULT,
+            Mode::Notification => tts_mode_e_TTS_MODE_NOTIFICATION,
+            Mode::ScreenReader => tts_mode_e_TTS_MODE_SCREEN_READER,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum VoiceType {
+    /// Any voice of the language.
+    Auto,
+    Male,
+    Female,
+    Child,
+}
+
+impl From<VoiceType> for c_int {
+    fn from(voice_type: VoiceType) -> c_int {
+        (match voice_type {
+            VoiceType::Auto => tts_voice_type_e_TTS_VOICE_TYPE_AUTO,
+            VoiceType::Male => tts_voice_type_e_TTS_VOICE_TYPE_MALE,
+            VoiceType::Female => tts_voice_type_e_TTS_VOICE_TYPE_FEMALE,
+            VoiceType::Child => tts_voice_type_e_TTS_VOICE_TYPE_CHILD,
+        }) as c_int
+    }
+}
+
+impl From<c_int> for VoiceType {
+    fn from(voice_type: c_int) -> VoiceType {
+        match voice_type as tts_voice_type_e {
+            tts_voice_type_e_TTS_VOICE_TYPE_MALE => VoiceType::Male,
+            tts_voice_type_e_TTS_VOICE_TYPE_FEMALE => VoiceType::Female,
+            tts_voice_type_e_TTS_VOICE_TYPE_CHILD => VoiceType::Child,
+            _ => VoiceType::Auto,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Voice {
+    /// The language code, such as "en_US".
+    pub language: String,
+    pub voice_type: VoiceType,
+}
+
+/// The speeds supported by the engine.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SpeedRange {
+    pub min: i32,
+    pub normal: i32,
+    pub max: i32,
+}
+
+type StateChangedFn = Box<dyn FnMut(TtsState, TtsState)>;
+type UtteranceFn = Box<dyn FnMut(i32)>;
+type ErrorFn = Box<dyn FnMut(i32, Error)>;
+
+pub struct Tts {
+    handle: tts_h,
+    voice: Option<(CString, c_int)>,
+    speed: Option<i32>,
+    state_changed_fn: Option<Pin<Box<StateChangedFn>>>,
+    utterance_started_fn: Option<Pin<Box<UtteranceFn>>>,
+    utterance_completed_fn: Option<Pin<Box<UtteranceFn>>>,
+    error_fn: Option<Pin<Box<ErrorFn>>>,
+}
+
+impl Tts {
+    pub fn new() -> Result<Tts> {
+        let mut handle = null_mut();
+        check(unsafe { tts_create(&mut handle) })?;
+
+        Ok(Self {
+            handle,
+            voice: None,
+            speed: None,
+            state_changed_fn: None,
+            utterance_started_fn: None,
+            utterance_completed_fn: None,
+            error_fn: None,
+        })
+    }
+
+    pub fn get_state(&self) -> Result<TtsState> {
+        let mut state = tts_state_e_TTS_STATE_CREATED;
+        check(unsafe { tts_get_state(self.handle, &mut state) })?;
+
+        TtsState::try_from(state)
+    }
+
+    pub fn set_mode(&self, mode: Mode) -> Result<()> {
+        check(unsafe { tts_set_mode(self.handle, mode.into()) })
+    }
+
+    /// Connects to the engine in the background.
+    pub fn prepare(&self) -> Result<()> {
+        check(unsafe { tts_prepare(self.handle) })
+    }
+
+    pub fn unprepare(&self) -> Result<()> {
+        check(unsafe { tts_unprepare(self.handle) })
+    }
+
+    /// The voice used by default, chosen in the settings.
+    pub fn get_default_voice(&self) -> Result<Voice> {
+        let mut language = null_mut();
+        let mut voice_type = 0;
+        check(unsafe { tts_get_default_voice(self.handle, &mut language, &mut voice_type) })?;
+
+        Ok(Voice {
+            language: unsafe { take_string(language) }.unwrap_or_default(),
+            voice_type: voice_type.into(),
+        })
+    }
+
+    pub fn supported_voices(&self) -> Result<Vec<Voice>> {
+        let mut voices = Vec::<Voice>::new();
+
+        check(unsafe {
+            tts_foreach_supported_voices(
+                self.handle,
+                Some(voice_handler),
+                &mut voices as *mut Vec<Voice> as *mut c_void,
+            )
+        })?;
+
+        Ok(voices)
+    }
+
+    /// Only available once prepared.
+    pub fn get_speed_range(&self) -> Result<SpeedRange> {
+        let mut range = SpeedRange {
+            min: 0,
+            normal: 0,
+            max: 0,
+        };
+        check(unsafe {
+            tts_get_speed_range(
+                self.handle,
+                &mut range.min,
+                &mut range.normal,
+                &mut range.max,
+            )
+        })?;
+
+        Ok(range)
+    }
+
+    /// The voice of the texts added afterwards, `None` for the default one.
+    pub fn set_voice(&mut self, voice: Option<&Voice>) -> Result<()> {
+        self.voice = match voice {
+            Some(voice) => Some((cstring(voice.language.as_str())?, voice.voice_type.into())),
+            None => None,
+        };
+
+        Ok(())
+    }
+
+    /// The speed of the texts added afterwards, within [`Tts::get_speed_range`],
+    /// `None` for the default one.
+    pub fn set_speed(&mut self, speed: Option<i32>) {
+        self.speed = speed;
+    }
+
+    /// Queues `text` with the current voice and speed, returning the id of the utterance.
+    pub fn add_text(&self, text: &str) -> Result<i32> {
+        let text = cstring(text)?;
+        let (language, voice_type) = match &self.voice {
+            Some((language, voice_type)) => (language.as_ptr(), *voice_type),
+            None => (null(), VoiceType::Auto.into()),
+        };
+        let speed = self.speed.unwrap_or(TTS_SPEED_AUTO as i32);
+
+        let mut id = 0;
+        check(unsafe {
+            tts_add_text(
+                self.handle,
+                text.as_ptr(),
+                language,
+                voice_type,
+                speed,
+                &mut id,
+            )
+        })?;
+
+        Ok(id)
+    }
+
+    /// Starts speaking the queued texts, or resumes if paused.
+    pub fn play(&self) -> Result<()> {
+        check(unsafe { tts_play(self.handle) })
+    }
+
+    pub fn pause(&self) -> Result<()> {
+        check(unsafe { tts_pause(self.handle) })
+    }
+
+    /// Stops speaking and clears the queued texts.
+    pub fn stop(&self) -> Result<()> {
+        check(unsafe { tts_stop(self.handle) })
+    }
+
+    /// Calls `callback_fn` with the previous and current states, replacing the previous callback.
+    pub fn set_state_changed_cb<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(TtsState, TtsState),
+        F: 'static,
+    {
+        let (callback_fn, data) =
+            pin_callback::<dyn FnMut(TtsState, TtsState)>(Box::new(callback_fn));
+
+        check(unsafe { tts_set_state_changed_cb(self.handle, Some(state_changed_handler), data) })?;
+        self.state_changed_fn = Some(callback_fn);
+
+        Ok(())
+    }
+
+    pub fn unset_state_changed_cb(&mut self) -> Result<()> {
+        check(unsafe { tts_unset_state_changed_cb(self.handle) })?;
+        self.state_changed_fn = None;
+
+        Ok(())
+    }
+
+    /// Calls `callback_fn` with the id of each utterance as it starts being spoken,
+    /// replacing the previous callback.
+    pub fn set_utterance_started_cb<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(i32),
+        F: 'static,
+    {
+        let (callback_fn, data) = pin_callback::<dyn FnMut(i32)>(Box::new(callback_fn));
+
+        check(unsafe { tts_set_utterance_started_cb(self.handle, Some(utterance_handler), data) })?;
+        self.utterance_started_fn = Some(callback_fn);
+
+        Ok(())
+    }
+
+    pub fn unset_utterance_started_cb(&mut self) -> Result<()> {
+        check(unsafe { tts_unset_utterance_started_cb(self.handle) })?;
+        self.utterance_started_fn = None;
+
+        Ok(())
+    }
+
+    /// Calls `callback_fn` with the id of each utterance once spoken,
+    /// replacing the previous callback.
+    pub fn set_utterance_completed_cb<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(i32),
+        F: 'static,
+    {
+        let (callback_fn, data) = pin_callback::<dyn FnMut(i32)>(Box::new(callback_fn));
+
+        check(unsafe {
+            tts_set_utterance_completed_cb(self.handle, Some(utterance_handler), data)
+        })?;
+        self.utterance_completed_fn = Some(callback_fn);
+
+        Ok(())
+    }
+
+    pub fn unset_utterance_completed_cb(&mut self) -> Result<()> {
+        check(unsafe { tts_unset_utterance_completed_cb(self.handle) })?;
+        self.utterance_completed_fn = None;
+
+        Ok(())
+    }
+
+    /// Calls `callback_fn` with the id of the failed utterance and the reason,
+    /// replacing the previous callback.
+    pub fn set_error_cb<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(i32, Error),
+        F: 'static,
+    {
+        let (callback_fn, data) = pin_callback::<dyn FnMut(i32, Error)>(Box::new(callback_fn));
+
+        check(unsafe { tts_set_error_cb(self.handle, Some(error_handler), data) })?;
+        self.error_fn = Some(callback_fn);
+
+        Ok(())
+    }
+
+    pub fn unset_error_cb(&mut self) -> Result<()> {
+        check(unsafe { tts_unset_error_cb(self.handle) })?;
+        self.error_fn = None;
+
+        Ok(())
+    }
+}
+
+impl Drop for Tts {
+    fn drop(&mut self) {
+        unsafe {
+            if self.state_changed_fn.is_some() {
+                tts_unset_state_changed_cb(self.handle);
+            }
+
+            if self.utterance_started_fn.is_some() {
+                tts_unset_utterance_started_cb(self.handle);
+            }
+
+            if self.utterance_completed_fn.is_some() {
+                tts_unset_utterance_completed_cb(self.handle);
+            }
+
+            if self.error_fn.is_some() {
+                tts_unset_error_cb(self.handle);
+            }
+
+            // also unprepares
+            tts_destroy(self.handle);
+        }
+    }
+}
+
+extern "C" fn voice_handler(
+    _tts: tts_h,
+    language: *const c_char,
+    voice_type: c_int,
+    user_data: *mut c_void,
+) -> bool {
+    abort_on_panic(|| {
+        let voices = unsafe { &mut *(user_data as *mut Vec<Voice>) };
+
+        if let Some(language) = unsafe { borrow_str(language) } {
+            voices.push(Voice {
+                language: language.into_owned(),
+                voice_type: voice_type.into(),
+            });
+        }
+
+        true
+    })
+}
+
+extern "C" fn state_changed_handler(
+    _tts: tts_h,
+    previous: tts_state_e,
+    current: tts_state_e,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut StateChangedFn) };
+
+        if let (Ok(previous), Ok(current)) =
+            (TtsState::try_from(previous), TtsState::try_from(current))
+        {
+            closure(previous, current);
+        }
+    })
+}
+
+extern "C" fn utterance_handler(_tts: tts_h, utt_id: c_int, user_data: *mut c_void) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut UtteranceFn) };
+        closure(utt_id);
+    })
+}
+
+extern "C" fn error_handler(_tts: tts_h, utt_id: c_int, reason: c_int, user_data: *mut c_void) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut ErrorFn) };
+        closure(utt_id, Error::from(reason));
+    })
+}
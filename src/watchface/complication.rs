@@ -0,0 +1,371 @@
+//! The consumer side, for watch faces, and the editing of their complications.
+
+use super::{ComplicationData, ComplicationType, EventType};
+use crate::error::{check, Result};
+use crate::rutin::{abort_on_panic, borrow_str, cstring, pin_callback, take_string};
+use rutin_tizen_sys::{
+    bundle, watchface_complication_add_updated_cb, watchface_complication_create,
+    watchface_complication_destroy, watchface_complication_error_e,
+    watchface_complication_event_type_e, watchface_complication_get_current_provider_id,
+    watchface_complication_get_current_type, watchface_complication_h,
+    watchface_complication_remove_updated_cb, watchface_complication_send_update_request,
+    watchface_complication_transfer_event, watchface_complication_type_e,
+    watchface_complication_type_e_WATCHFACE_COMPLICATION_TYPE_NO_DATA,
+    watchface_editable_add_complication, watchface_editable_add_edit_ready_cb,
+    watchface_editable_container_h, watchface_editable_edit_state_e,
+    watchface_editable_edit_state_e_WATCHFACE_EDITABLE_EDIT_STATE_CANCEL,
+    watchface_editable_edit_state_e_WATCHFACE_EDITABLE_EDIT_STATE_COMPLETE, watchface_editable_h,
+    watchface_editable_highlight_create, watchface_editable_highlight_destroy,
+    watchface_editable_highlight_h, watchface_editable_highlight_set_geometry,
+    watchface_editable_remove_edit_ready_cb, watchface_editable_request_edit,
+    watchface_editable_shape_type_e,
+    watchface_editable_shape_type_e_WATCHFACE_EDITABLE_SHAPE_TYPE_CIRCLE,
+    watchface_editable_shape_type_e_WATCHFACE_EDITABLE_SHAPE_TYPE_RECT,
+};
+use std::os::raw::{c_char, c_int, c_void};
+use std::pin::Pin;
+use std::ptr::null_mut;
+
+/// New data from the provider of a complication, or the reason it could not be sent.
+pub type Update = Result<(String, ComplicationData)>;
+
+type UpdatedFn = Box<dyn FnMut(Update)>;
+
+/// A slot of the watch face, showing the data of the provider chosen by the user.
+pub struct Complication {
+    handle: watchface_complication_h,
+    updated_fn: Option<Pin<Box<UpdatedFn>>>,
+}
+
+impl Complication {
+    /// `id` identifies the slot in the watch face, `default_provider_id` is used until the user
+    /// picks another provider among those supporting one of `types`.
+    pub fn new(
+        id: i32,
+        default_provider_id: &str,
+        default_type: ComplicationType,
+        types: &[ComplicationType],
+        events: &[EventType],
+    ) -> Result<Complication> {
+        let default_provider_id = cstring(default_provider_id)?;
+        let types = types.iter().fold(0, |types, &complication_type| {
+            types | watchface_complication_type_e::from(complication_type) as c_int
+        });
+        let events = events.iter().fold(0, |events, &event_type| {
+            events | watchface_complication_event_type_e::from(event_type) as c_int
+        });
+
+        let mut handle = null_mut();
+        check(unsafe {
+            watchface_complication_create(
+                id,
+                default_provider_id.as_ptr(),
+                default_type.into(),
+                types,
+                events,
+                &mut handle,
+            )
+        })?;
+
+        Ok(Self {
+            handle,
+            updated_fn: None,
+        })
+    }
+
+    /// Calls `callback_fn` with the id of the provider and its data each time it sends
+    /// some, replacing the previous callback.
+    pub fn set_updated_cb<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(Update),
+        F: 'static,
+    {
+        self.unset_updated_cb()?;
+
+        let (callback_fn, data) = pin_callback::<dyn FnMut(Update)>(Box::new(callback_fn));
+
+        check(unsafe {
+            watchface_complication_add_updated_cb(
+                self.handle,
+                Some(updated_handler),
+                Some(error_handler),
+                data,
+            )
+        })?;
+        self.updated_fn = Some(callback_fn);
+
+        Ok(())
+    }
+
+    pub fn unset_updated_cb(&mut self) -> Result<()> {
+        if self.updated_fn.is_some() {
+            check(unsafe {
+                watchface_complication_remove_updated_cb(self.handle, Some(updated_handler))
+            })?;
+            self.updated_fn = None;
+        }
+
+        Ok(())
+    }
+
+    /// Asks the provider for fresh data, delivered to the updated callback.
+    pub fn request_update(&self) -> Result<()> {
+        check(unsafe { watchface_complication_send_update_request(self.handle) })
+    }
+
+    /// Forwards a user interaction with the complication to its provider.
+    pub fn transfer_event(&self, event: EventType) -> Result<()> {
+        check(unsafe { watchface_complication_transfer_event(self.handle, event.into()) })
+    }
+
+    pub fn get_current_provider_id(&self) -> Result<Option<String>> {
+        let mut provider_id = null_mut();
+        check(unsafe {
+            watchface_complication_get_current_provider_id(self.handle, &mut provider_id)
+        })?;
+
+        Ok(unsafe { take_string(provider_id) })
+    }
+
+    pub fn get_current_type(&self) -> Result<ComplicationType> {
+        let mut complication_type =
+            watchface_complication_type_e_WATCHFACE_COMPLICATION_TYPE_NO_DATA;
+        check(unsafe {
+            watchface_complication_get_current_type(self.handle, &mut complication_type)
+        })?;
+
+        Ok(complication_type.into())
+    }
+}
+
+impl Drop for Complication {
+    fn drop(&mut self) {
+        let _ = self.unset_updated_cb();
+
+        unsafe {
+            watchface_complication_destroy(self.handle);
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Shape {
+    Circle,
+    Rect,
+}
+
+impl From<Shape> for watchface_editable_shape_type_e {
+    fn from(shape: Shape) -> watchface_editable_shape_type_e {
+        match shape {
+            Shape::Circle => watchface_editable_shape_type_e_WATCHFACE_EDITABLE_SHAPE_TYPE_CIRCLE,
+            Shape::Rect => watchface_editable_shape_type_e_WATCHFACE_EDITABLE_SHAPE_TYPE_RECT,
+        }
+    }
+}
+
+/// The outline drawn by the editor around a complication, in screen coordinates.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Highlight {
+    pub shape: Shape,
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EditState {
+    /// The user picked a provider, the edit continues.
+    Ongoing,
+    Complete,
+    Canceled,
+}
+
+impl From<watchface_editable_edit_state_e> for EditState {
+    fn from(state: watchface_editable_edit_state_e) -> EditState {
+        match state {
+            watchface_editable_edit_state_e_WATCHFACE_EDITABLE_EDIT_STATE_COMPLETE => {
+                EditState::Complete
+            }
+            watchface_editable_edit_state_e_WATCHFACE_EDITABLE_EDIT_STATE_CANCEL => {
+                EditState::Canceled
+            }
+            _ => EditState::Ongoing,
+        }
+    }
+}
+
+type EditFn = Box<dyn FnMut(i32, EditState)>;
+
+struct EditReadyState {
+    callback_fn: Box<dyn FnMut(&mut EditContainer, &str)>,
+    edit_fn: Option<Pin<Box<EditFn>>>,
+}
+
+/// The complications offered to the editor, while it gets ready.
+pub struct EditContainer<'s> {
+    handle: watchface_editable_container_h,
+    edit_fn: &'s mut Option<Pin<Box<EditFn>>>,
+}
+
+impl<'s> EditContainer<'s> {
+    /// Lets the user pick the provider of `complication`, which must outlive the edit.
+    pub fn add_complication(
+        &mut self,
+        edit_id: i32,
+        complication: &Complication,
+        highlight: Option<Highlight>,
+    ) -> Result<()> {
+        let mut raw_highlight: watchface_editable_highlight_h = null_mut();
+
+        if let Some(highlight) = highlight {
+            check(unsafe {
+                watchface_editable_highlight_create(&mut raw_highlight, highlight.shape.into())
+            })?;
+
+            let ret = unsafe {
+                watchface_editable_highlight_set_geometry(
+                    raw_highlight,
+                    highlight.x,
+                    highlight.y,
+                    highlight.width,
+                    highlight.height,
+                )
+            };
+
+            if let Err(e) = check(ret) {
+                unsafe { watchface_editable_highlight_destroy(raw_highlight) };
+                return Err(e);
+            }
+        }
+
+        let ret = unsafe {
+            watchface_editable_add_complication(
+                self.handle,
+                edit_id,
+                complication.handle,
+                raw_highlight,
+            )
+        };
+
+        // the container keeps a copy of the highlight
+        if !raw_highlight.is_null() {
+            unsafe { watchface_editable_highlight_destroy(raw_highlight) };
+        }
+
+        check(ret)
+    }
+
+    /// Opens the editor on the added complications, `callback_fn` is called with the index
+    /// of the selected provider as the user edits.
+    pub fn request_edit<F>(&mut self, callback_fn: F) -> Result<()>
+    where
+        F: FnMut(i32, EditState),
+        F: 'static,
+    {
+        let (callback_fn, data) = pin_callback::<dyn FnMut(i32, EditState)>(Box::new(callback_fn));
+
+        check(unsafe { watchface_editable_request_edit(self.handle, Some(edit_handler), data) })?;
+        *self.edit_fn = Some(callback_fn);
+
+        Ok(())
+    }
+}
+
+/// Receives the requests of the editor to edit the watch face, until dropped.
+pub struct EditReadyListener {
+    _state: Pin<Box<EditReadyState>>,
+}
+
+impl EditReadyListener {
+    /// Calls `callback_fn` with the container to fill and the app id of the editor when
+    /// the user starts editing the watch face.
+    pub fn new<F>(callback_fn: F) -> Result<EditReadyListener>
+    where
+        F: FnMut(&mut EditContainer, &str),
+        F: 'static,
+    {
+        let state = Box::pin(EditReadyState {
+            callback_fn: Box::new(callback_fn),
+            edit_fn: None,
+        });
+        let data = &*state as *const EditReadyState as *mut c_void;
+
+        check(unsafe { watchface_editable_add_edit_ready_cb(Some(edit_ready_handler), data) })?;
+
+        Ok(Self { _state: state })
+    }
+}
+
+impl Drop for EditReadyListener {
+    fn drop(&mut self) {
+        unsafe {
+            watchface_editable_remove_edit_ready_cb(Some(edit_ready_handler));
+        }
+    }
+}
+
+extern "C" fn updated_handler(
+    _complication_id: c_int,
+    provider_id: *const c_char,
+    _complication_type: watchface_complication_type_e,
+    data: *const bundle,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut UpdatedFn) };
+        let provider_id = unsafe { borrow_str(provider_id) }
+            .map(|provider_id| provider_id.into_owned())
+            .unwrap_or_default();
+
+        closure(unsafe { ComplicationData::from_raw(data) }.map(|data| (provider_id, data)));
+    })
+}
+
+extern "C" fn error_handler(
+    _complication_id: c_int,
+    _provider_id: *const c_char,
+    _complication_type: watchface_complication_type_e,
+    error: watchface_complication_error_e,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut UpdatedFn) };
+
+        if let Err(e) = check(error) {
+            closure(Err(e));
+        }
+    })
+}
+
+extern "C" fn edit_ready_handler(
+    container: watchface_editable_container_h,
+    editor_app_id: *const c_char,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let state = unsafe { &mut *(user_data as *mut EditReadyState) };
+        let editor_app_id = unsafe { borrow_str(editor_app_id) }.unwrap_or_default();
+
+        let mut container = EditContainer {
+            handle: container,
+            edit_fn: &mut state.edit_fn,
+        };
+        (state.callback_fn)(&mut container, &editor_app_id);
+    })
+}
+
+extern "C" fn edit_handler(
+    _editable: watchface_editable_h,
+    selected_index: c_int,
+    state: watchface_editable_edit_state_e,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut EditFn) };
+        closure(selected_index, state.into());
+    })
+}
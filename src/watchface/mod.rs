@@ -0,0 +1,294 @@
+//! Complications, the small pieces of data (battery, steps, weather) shown by watch faces
+//! and supplied by provider services, since Tizen 5.0.
+//!
+//! Watch faces consume them with [`Complication`], providers supply them with [`Provider`].
+//! Both require the `http://tizen.org/privilege/datasharing` privilege.
+
+pub mod complication;
+pub mod provider;
+
+pub use complication::Complication;
+pub use provider::Provider;
+
+use crate::error::{check, Result};
+use crate::rutin::{cstring, take_string};
+use rutin_tizen_sys::{
+    bundle, complication_provider_data_set_extra_data, complication_provider_data_set_icon_path,
+    complication_provider_data_set_image_path, complication_provider_data_set_long_text,
+    complication_provider_data_set_ranged_value, complication_provider_data_set_screen_reader_text,
+    complication_provider_data_set_short_text, complication_provider_data_set_timestamp,
+    complication_provider_data_set_title, complication_provider_data_set_type,
+    watchface_complication_data_get_extra_data, watchface_complication_data_get_icon_path,
+    watchface_complication_data_get_image_path, watchface_complication_data_get_long_text,
+    watchface_complication_data_get_ranged_value,
+    watchface_complication_data_get_screen_reader_text, watchface_complication_data_get_short_text,
+    watchface_complication_data_get_timestamp, watchface_complication_data_get_title,
+    watchface_complication_data_get_type, watchface_complication_event_type_e,
+    watchface_complication_event_type_e_WATCHFACE_COMPLICATION_EVENT_DOUBLE_TAP,
+    watchface_complication_event_type_e_WATCHFACE_COMPLICATION_EVENT_NONE,
+    watchface_complication_event_type_e_WATCHFACE_COMPLICATION_EVENT_TAP,
+    watchface_complication_type_e, watchface_complication_type_e_WATCHFACE_COMPLICATION_TYPE_ICON,
+    watchface_complication_type_e_WATCHFACE_COMPLICATION_TYPE_IMAGE,
+    watchface_complication_type_e_WATCHFACE_COMPLICATION_TYPE_LONG_TEXT,
+    watchface_complication_type_e_WATCHFACE_COMPLICATION_TYPE_NO_DATA,
+    watchface_complication_type_e_WATCHFACE_COMPLICATION_TYPE_RANGED_VALUE,
+    watchface_complication_type_e_WATCHFACE_COMPLICATION_TYPE_SHORT_TEXT,
+    watchface_complication_type_e_WATCHFACE_COMPLICATION_TYPE_TIME,
+};
+use std::os::raw::{c_char, c_int, c_long};
+use std::path::PathBuf;
+use std::ptr::null_mut;
+
+/// The kind of data of a complication, which decides the fields to show.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ComplicationType {
+    NoData,
+    ShortText,
+    LongText,
+    RangedValue,
+    Time,
+    Icon,
+    Image,
+}
+
+impl From<ComplicationType> for watchface_complication_type_e {
+    fn from(complication_type: ComplicationType) -> watchface_complication_type_e {
+        match complication_type {
+            ComplicationType::NoData => {
+                watchface_complication_type_e_WATCHFACE_COMPLICATION_TYPE_NO_DATA
+            }
+            ComplicationType::ShortText => {
+                watchface_complication_type_e_WATCHFACE_COMPLICATION_TYPE_SHORT_TEXT
+            }
+            ComplicationType::LongText => {
+                watchface_complication_type_e_WATCHFACE_COMPLICATION_TYPE_LONG_TEXT
+            }
+            ComplicationType::RangedValue => {
+                watchface_complication_type_e_WATCHFACE_COMPLICATION_TYPE_RANGED_VALUE
+            }
+            ComplicationType::Time => {
+                watchface_complication_type_e_WATCHFACE_COMPLICATION_TYPE_TIME
+            }
+            ComplicationType::Icon => {
+                watchface_complication_type_e_WATCHFACE_COMPLICATION_TYPE_ICON
+            }
+            ComplicationType::Image => {
+                watchface_complication_type_e_WATCHFACE_COMPLICATION_TYPE_IMAGE
+            }
+        }
+    }
+}
+
+impl From<watchface_complication_type_e> for ComplicationType {
+    fn from(complication_type: watchface_complication_type_e) -> ComplicationType {
+        match complication_type {
+            watchface_complication_type_e_WATCHFACE_COMPLICATION_TYPE_SHORT_TEXT => {
+                ComplicationType::ShortText
+            }
+            watchface_complication_type_e_WATCHFACE_COMPLICATION_TYPE_LONG_TEXT => {
+                ComplicationType::LongText
+            }
+            watchface_complication_type_e_WATCHFACE_COMPLICATION_TYPE_RANGED_VALUE => {
+                ComplicationType::RangedValue
+            }
+            watchface_complication_type_e_WATCHFACE_COMPLICATION_TYPE_TIME => {
+                ComplicationType::Time
+            }
+            watchface_complication_type_e_WATCHFACE_COMPLICATION_TYPE_ICON => {
+                ComplicationType::Icon
+            }
+            watchface_complication_type_e_WATCHFACE_COMPLICATION_TYPE_IMAGE => {
+                ComplicationType::Image
+            }
+            _ => ComplicationType::NoData,
+        }
+    }
+}
+
+/// The user interactions a complication forwards to its provider.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EventType {
+    None,
+    Tap,
+    DoubleTap,
+}
+
+impl From<EventType> for watchface_complication_event_type_e {
+    fn from(event_type: EventType) -> watchface_complication_event_type_e {
+        match event_type {
+            EventType::None => {
+                watchface_complication_event_type_e_WATCHFACE_COMPLICATION_EVENT_NONE
+            }
+            EventType::Tap => watchface_complication_event_type_e_WATCHFACE_COMPLICATION_EVENT_TAP,
+            EventType::DoubleTap => {
+                watchface_complication_event_type_e_WATCHFACE_COMPLICATION_EVENT_DOUBLE_TAP
+            }
+        }
+    }
+}
+
+/// A value between bounds, such as the battery level or the steps toward a goal.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RangedValue {
+    pub current: f64,
+    pub min: f64,
+    pub max: f64,
+}
+
+/// The data of a complication, the fields used depend on its type.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ComplicationData {
+    pub complication_type: ComplicationType,
+    pub short_text: Option<String>,
+    pub long_text: Option<String>,
+    pub title: Option<String>,
+    /// Seconds since the epoch, for [`ComplicationType::Time`].
+    pub timestamp: Option<i64>,
+    pub image_path: Option<PathBuf>,
+    pub ranged_value: Option<RangedValue>,
+    pub icon_path: Option<PathBuf>,
+    /// Free form data for the watch faces which know the provider.
+    pub extra_data: Option<String>,
+    pub screen_reader_text: Option<String>,
+}
+
+type DataGetter = unsafe extern "C" fn(*const bundle, *mut *mut c_char) -> c_int;
+type DataSetter = unsafe extern "C" fn(*mut bundle, *const c_char) -> c_int;
+
+impl ComplicationData {
+    pub fn new(complication_type: ComplicationType) -> ComplicationData {
+        Self {
+            complication_type,
+            short_text: None,
+            long_text: None,
+            title: None,
+            timestamp: None,
+            image_path: None,
+            ranged_value: None,
+            icon_path: None,
+            extra_data: None,
+            screen_reader_text: None,
+        }
+    }
+
+    /// Reads the data sent by a provider, the fields it did not set are `None`.
+    unsafe fn from_raw(data: *const bundle) -> Result<ComplicationData> {
+        let mut complication_type =
+            watchface_complication_type_e_WATCHFACE_COMPLICATION_TYPE_NO_DATA;
+        check(watchface_complication_data_get_type(
+            data,
+            &mut complication_type,
+        ))?;
+
+        let string = |get: DataGetter| {
+            let mut value = null_mut();
+            check(get(data, &mut value)).ok()?;
+            take_string(value)
+        };
+
+        let mut timestamp: c_long = 0;
+        let timestamp = check(watchface_complication_data_get_timestamp(
+            data,
+            &mut timestamp,
+        ))
+        .ok()
+        .map(|_| i64::from(timestamp));
+
+        let mut value = RangedValue {
+            current: 0.0,
+            min: 0.0,
+            max: 0.0,
+        };
+        let ranged_value = check(watchface_complication_data_get_ranged_value(
+            data,
+            &mut value.current,
+            &mut value.min,
+            &mut value.max,
+        ))
+        .ok()
+        .map(|_| value);
+
+        Ok(Self {
+            complication_type: complication_type.into(),
+            short_text: string(watchface_complication_data_get_short_text),
+            long_text: string(watchface_complication_data_get_long_text),
+            title: string(watchface_complication_data_get_title),
+            timestamp,
+            image_path: string(watchface_complication_data_get_image_path).map(PathBuf::from),
+            ranged_value,
+            icon_path: string(watchface_complication_data_get_icon_path).map(PathBuf::from),
+            extra_data: string(watchface_complication_data_get_extra_data),
+            screen_reader_text: string(watchface_complication_data_get_screen_reader_text),
+        })
+    }
+
+    /// Writes the data into the bundle shared with the watch face.
+    unsafe fn write(&self, shared_data: *mut bundle) -> Result<()> {
+        check(complication_provider_data_set_type(
+            shared_data,
+            self.complication_type.into(),
+        ))?;
+
+        let strings: [(DataSetter, Option<String>); 7] = [
+            (
+                complication_provider_data_set_short_text,
+                self.short_text.clone(),
+            ),
+            (
+                complication_provider_data_set_long_text,
+                self.long_text.clone(),
+            ),
+            (complication_provider_data_set_title, self.title.clone()),
+            (
+                complication_provider_data_set_image_path,
+                path_string(&self.image_path),
+            ),
+            (
+                complication_provider_data_set_icon_path,
+                path_string(&self.icon_path),
+            ),
+            (
+                complication_provider_data_set_extra_data,
+                self.extra_data.clone(),
+            ),
+            (
+                complication_provider_data_set_screen_reader_text,
+                self.screen_reader_text.clone(),
+            ),
+        ];
+
+        for (set, value) in strings.iter() {
+            if let Some(value) = value {
+                let value = cstring(value)?;
+                check(set(shared_data, value.as_ptr()))?;
+            }
+        }
+
+        if let Some(timestamp) = self.timestamp {
+            check(complication_provider_data_set_timestamp(
+                shared_data,
+                timestamp as c_long,
+            ))?;
+        }
+
+        if let Some(value) = self.ranged_value {
+            check(complication_provider_data_set_ranged_value(
+                shared_data,
+                value.current,
+                value.min,
+                value.max,
+            ))?;
+        }
+
+        Ok(())
+    }
+}
+
+fn path_string(path: &Option<PathBuf>) -> Option<String> {
+    path.as_ref()
+        .map(|path| path.to_string_lossy().into_owned())
+}
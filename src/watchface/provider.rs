@@ -0,0 +1,95 @@
+//! The provider side, for the services supplying complication data.
+
+use super::{ComplicationData, ComplicationType};
+use crate::error::{check, Result};
+use crate::rutin::{abort_on_panic, borrow_str, cstring, pin_callback};
+use rutin_tizen_sys::{
+    bundle, complication_provider_add_update_requested_cb, complication_provider_notify_update,
+    complication_provider_remove_update_requested_cb, watchface_complication_type_e,
+};
+use std::ffi::CString;
+use std::os::raw::{c_char, c_void};
+use std::pin::Pin;
+
+/// A watch face asking a provider for its data.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UpdateRequest {
+    /// The app id of the watch face.
+    pub app_id: String,
+    pub complication_type: ComplicationType,
+}
+
+type RequestFn = Box<dyn FnMut(&UpdateRequest) -> ComplicationData>;
+
+/// Answers the update requests of the watch faces for one provider id declared in the
+/// manifest, until dropped.
+pub struct Provider {
+    provider_id: CString,
+    _callback_fn: Pin<Box<RequestFn>>,
+}
+
+impl Provider {
+    /// `callback_fn` returns the data to send for each request.
+    pub fn new<F>(provider_id: &str, callback_fn: F) -> Result<Provider>
+    where
+        F: FnMut(&UpdateRequest) -> ComplicationData,
+        F: 'static,
+    {
+        let provider_id = cstring(provider_id)?;
+        let (callback_fn, data) =
+            pin_callback::<dyn FnMut(&UpdateRequest) -> ComplicationData>(Box::new(callback_fn));
+
+        check(unsafe {
+            complication_provider_add_update_requested_cb(
+                provider_id.as_ptr(),
+                Some(update_requested_handler),
+                data,
+            )
+        })?;
+
+        Ok(Self {
+            provider_id,
+            _callback_fn: callback_fn,
+        })
+    }
+
+    /// Tells the watch faces showing this provider that new data is available, they
+    /// then send update requests.
+    pub fn notify_update(&self) -> Result<()> {
+        check(unsafe { complication_provider_notify_update(self.provider_id.as_ptr()) })
+    }
+}
+
+impl Drop for Provider {
+    fn drop(&mut self) {
+        unsafe {
+            complication_provider_remove_update_requested_cb(
+                self.provider_id.as_ptr(),
+                Some(update_requested_handler),
+            );
+        }
+    }
+}
+
+extern "C" fn update_requested_handler(
+    _provider_id: *const c_char,
+    req_app_id: *const c_char,
+    complication_type: watchface_complication_type_e,
+    _context: *const bundle,
+    shared_data: *mut bundle,
+    user_data: *mut c_void,
+) {
+    abort_on_panic(|| {
+        let closure = unsafe { &mut *(user_data as *mut RequestFn) };
+        let request = UpdateRequest {
+            app_id: unsafe { borrow_str(req_app_id) }
+                .map(|app_id| app_id.into_owned())
+                .unwrap_or_default(),
+            complication_type: complication_type.into(),
+        };
+
+        let data = closure(&request);
+        let _ = unsafe { data.write(shared_data) };
+    })
+}
@@ -0,0 +1,328 @@
+//! The home screen widgets installed on the device, for launchers offering a widget picker.
+//!
+//! Requires the `http://tizen.org/privilege/widget.viewer` privilege.
+
+use crate::error::{check, last_error, Error, Result};
+use crate::rutin::{abort_on_panic, borrow_str, cstring, take_string};
+use rutin_tizen_sys::{
+    get_last_result, widget_service_get_icon, widget_service_get_main_app_id,
+    widget_service_get_name, widget_service_get_nodisplay, widget_service_get_package_id,
+    widget_service_get_preview_image_path, widget_service_get_supported_size_types,
+    widget_service_get_supported_sizes, widget_service_get_widget_list,
+    widget_service_get_widget_list_by_pkgid, widget_service_get_widget_max_count,
+    widget_size_type_e, widget_size_type_e_WIDGET_SIZE_TYPE_1x1,
+    widget_size_type_e_WIDGET_SIZE_TYPE_2x1, widget_size_type_e_WIDGET_SIZE_TYPE_2x2,
+    widget_size_type_e_WIDGET_SIZE_TYPE_4x1, widget_size_type_e_WIDGET_SIZE_TYPE_4x2,
+    widget_size_type_e_WIDGET_SIZE_TYPE_4x3, widget_size_type_e_WIDGET_SIZE_TYPE_4x4,
+    widget_size_type_e_WIDGET_SIZE_TYPE_4x5, widget_size_type_e_WIDGET_SIZE_TYPE_4x6,
+    widget_size_type_e_WIDGET_SIZE_TYPE_EASY_1x1, widget_size_type_e_WIDGET_SIZE_TYPE_EASY_3x1,
+    widget_size_type_e_WIDGET_SIZE_TYPE_EASY_3x3, widget_size_type_e_WIDGET_SIZE_TYPE_FULL,
+};
+use std::convert::TryFrom;
+use std::os::raw::{c_char, c_int, c_void};
+use std::path::PathBuf;
+use std::ptr::{null, null_mut};
+use std::slice;
+
+/// The sizes of a widget, in cells of the home screen grid.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SizeType {
+    S1x1,
+    S2x1,
+    S2x2,
+    S4x1,
+    S4x2,
+    S4x3,
+    S4x4,
+    S4x5,
+    S4x6,
+    /// The sizes of the easy mode home screen.
+    Easy1x1,
+    Easy3x1,
+    Easy3x3,
+    /// The whole screen, for wearables.
+    Full,
+}
+
+impl From<SizeType> for widget_size_type_e {
+    fn from(size_type: SizeType) -> widget_size_type_e {
+        match size_type {
+            SizeType::S1x1 => widget_size_type_e_WIDGET_SIZE_TYPE_1x1,
+            SizeType::S2x1 => widget_size_type_e_WIDGET_SIZE_TYPE_2x1,
+            SizeType::S2x2 => widget_size_type_e_WIDGET_SIZE_TYPE_2x2,
+            SizeType::S4x1 => widget_size_type_e_WIDGET_SIZE_TYPE_4x1,
+            SizeType::S4x2 => widget_size_type_e_WIDGET_SIZE_TYPE_4x2,
+            SizeType::S4x3 => widget_size_type_e_WIDGET_SIZE_TYPE_4x3,
+            SizeType::S4x4 => widget_size_type_e_WIDGET_SIZE_TYPE_4x4,
+            SizeType::S4x5 => widget_size_type_e_WIDGET_SIZE_TYPE_4x5,
+            SizeType::S4x6 => widget_size_type_e_WIDGET_SIZE_TYPE_4x6,
+            SizeType::Easy1x1 => widget_size_type_e_WIDGET_SIZE_TYPE_EASY_1x1,
+            SizeType::Easy3x1 => widget_size_type_e_WIDGET_SIZE_TYPE_EASY_3x1,
+            SizeType::Easy3x3 => widget_size_type_e_WIDGET_SIZE_TYPE_EASY_3x3,
+            SizeType::Full => widget_size_type_e_WIDGET_SIZE_TYPE_FULL,
+        }
+    }
+}
+
+impl TryFrom<widget_size_type_e> for SizeType {
+    type Error = Error;
+
+    fn try_from(size_type: widget_size_type_e) -> Result<SizeType> {
+        match size_type {
+            widget_size_type_e_WIDGET_SIZE_TYPE_1x1 => Ok(SizeType::S1x1),
+            widget_size_type_e_WIDGET_SIZE_TYPE_2x1 => Ok(SizeType::S2x1),
+            widget_size_type_e_WIDGET_SIZE_TYPE_2x2 => Ok(SizeType::S2x2),
+            widget_size_type_e_WIDGET_SIZE_TYPE_4x1 => Ok(SizeType::S4x1),
+            widget_size_type_e_WIDGET_SIZE_TYPE_4x2 => Ok(SizeType::S4x2),
+            widget_size_type_e_WIDGET_SIZE_TYPE_4x3 => Ok(SizeType::S4x3),
+            widget_size_type_e_WIDGET_SIZE_TYPE_4x4 => Ok(SizeType::S4x4),
+            widget_size_type_e_WIDGET_SIZE_TYPE_4x5 => Ok(SizeType::S4x5),
+            widget_size_type_e_WIDGET_SIZE_TYPE_4x6 => Ok(SizeType::S4x6),
+            widget_size_type_e_WIDGET_SIZE_TYPE_EASY_1x1 => Ok(SizeType::Easy1x1),
+            widget_size_type_e_WIDGET_SIZE_TYPE_EASY_3x1 => Ok(SizeType::Easy3x1),
+            widget_size_type_e_WIDGET_SIZE_TYPE_EASY_3x3 => Ok(SizeType::Easy3x3),
+            widget_size_type_e_WIDGET_SIZE_TYPE_FULL => Ok(SizeType::Full),
+            _ => Err(Error::Unknown),
+        }
+    }
+}
+
+/// An installed widget.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Widget {
+    pub package_id: String,
+    pub widget_id: String,
+    /// Whether it is the main widget of its package, to list first.
+    pub is_prime: bool,
+}
+
+/// A size supported by a widget, with its dimensions in pixels on this device.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Size {
+    pub size_type: SizeType,
+    pub width: i32,
+    pub height: i32,
+}
+
+/// All the installed widgets.
+pub fn widgets() -> Result<Vec<Widget>> {
+    let mut widgets: Vec<Widget> = Vec::new();
+
+    let ret = unsafe {
+        widget_service_get_widget_list(
+            Some(widget_handler),
+            &mut widgets as *mut Vec<Widget> as *mut c_void,
+        )
+    };
+
+    if ret < 0 {
+        check(ret)?;
+    }
+
+    Ok(widgets)
+}
+
+/// The widgets of the package `package_id`.
+pub fn widgets_by_package(package_id: &str) -> Result<Vec<Widget>> {
+    let c_package_id = cstring(package_id)?;
+    let mut widgets: Vec<Widget> = Vec::new();
+
+    let ret = unsafe {
+        widget_service_get_widget_list_by_pkgid(
+            c_package_id.as_ptr(),
+            Some(package_widget_handler),
+            &mut widgets as *mut Vec<Widget> as *mut c_void,
+        )
+    };
+
+    if ret < 0 {
+        check(ret)?;
+    }
+
+    for widget in &mut widgets {
+        widget.package_id = package_id.to_string();
+    }
+
+    Ok(widgets)
+}
+
+pub fn get_supported_sizes(widget_id: &str) -> Result<Vec<Size>> {
+    let widget_id = cstring(widget_id)?;
+
+    let mut count: c_int = 0;
+    let mut widths: *mut c_int = null_mut();
+    let mut heights: *mut c_int = null_mut();
+    check(unsafe {
+        widget_service_get_supported_sizes(
+            widget_id.as_ptr(),
+            &mut count,
+            &mut widths,
+            &mut heights,
+        )
+    })?;
+    let widths = unsafe { take_ints(widths, count) };
+    let heights = unsafe { take_ints(heights, count) };
+
+    let mut types_count: c_int = 0;
+    let mut types: *mut c_int = null_mut();
+    check(unsafe {
+        widget_service_get_supported_size_types(widget_id.as_ptr(), &mut types_count, &mut types)
+    })?;
+    let types = unsafe { take_ints(types, types_count) };
+
+    types
+        .into_iter()
+        .zip(widths.into_iter().zip(heights))
+        .map(|(size_type, (width, height))| {
+            Ok(Size {
+                size_type: SizeType::try_from(size_type as widget_size_type_e)?,
+                width,
+                height,
+            })
+        })
+        .collect()
+}
+
+pub fn get_preview_image_path(widget_id: &str, size_type: SizeType) -> Result<Option<PathBuf>> {
+    let widget_id = cstring(widget_id)?;
+
+    optional_string(unsafe {
+        widget_service_get_preview_image_path(widget_id.as_ptr(), size_type.into())
+    })
+    .map(|path| path.map(PathBuf::from))
+}
+
+/// The name of the widget in `language` (e.g. `en-us`), or in the current language.
+pub fn get_name(widget_id: &str, language: Option<&str>) -> Result<Option<String>> {
+    let widget_id = cstring(widget_id)?;
+    let language = language.map(cstring).transpose()?;
+
+    optional_string(unsafe {
+        widget_service_get_name(
+            widget_id.as_ptr(),
+            language
+                .as_ref()
+                .map_or(null(), |language| language.as_ptr()),
+        )
+    })
+}
+
+/// The icon of the package `package_id` in `language`, or in the current language.
+pub fn get_icon(package_id: &str, language: Option<&str>) -> Result<Option<PathBuf>> {
+    let package_id = cstring(package_id)?;
+    let language = language.map(cstring).transpose()?;
+
+    optional_string(unsafe {
+        widget_service_get_icon(
+            package_id.as_ptr(),
+            language
+                .as_ref()
+                .map_or(null(), |language| language.as_ptr()),
+        )
+    })
+    .map(|path| path.map(PathBuf::from))
+}
+
+/// The UI app of the package of the widget, launched when the widget is tapped.
+pub fn get_main_app_id(widget_id: &str) -> Result<String> {
+    let widget_id = cstring(widget_id)?;
+
+    unsafe { take_string(widget_service_get_main_app_id(widget_id.as_ptr())) }
+        .ok_or_else(last_error)
+}
+
+pub fn get_package_id(widget_id: &str) -> Result<String> {
+    let widget_id = cstring(widget_id)?;
+
+    unsafe { take_string(widget_service_get_package_id(widget_id.as_ptr())) }.ok_or_else(last_error)
+}
+
+/// Whether the widget should be hidden from the widget picker.
+pub fn is_nodisplay(widget_id: &str) -> Result<bool> {
+    let widget_id = cstring(widget_id)?;
+
+    let nodisplay = unsafe { widget_service_get_nodisplay(widget_id.as_ptr()) };
+    check(unsafe { get_last_result() })?;
+
+    Ok(nodisplay != 0)
+}
+
+/// How many instances of the widget can be added, 0 when unlimited.
+pub fn get_max_count(widget_id: &str) -> Result<i32> {
+    let widget_id = cstring(widget_id)?;
+
+    let ret = unsafe { widget_service_get_widget_max_count(widget_id.as_ptr()) };
+
+    if ret < 0 {
+        check(ret)?;
+    }
+
+    Ok(ret)
+}
+
+/// Takes a string returned by the widget service, a null one is an error only if the last
+/// result says so.
+fn optional_string(value: *mut c_char) -> Result<Option<String>> {
+    match unsafe { take_string(value) } {
+        Some(value) => Ok(Some(value)),
+        None => check(unsafe { get_last_result() }).map(|_| None),
+    }
+}
+
+/// Copies and frees an array allocated by the widget service.
+unsafe fn take_ints(values: *mut c_int, count: c_int) -> Vec<i32> {
+    if values.is_null() {
+        return Vec::new();
+    }
+
+    let vec = slice::from_raw_parts(values, count as usize).to_vec();
+    libc::free(values as *mut c_void);
+
+    vec
+}
+
+extern "C" fn widget_handler(
+    package_id: *const c_char,
+    widget_id: *const c_char,
+    is_prime: c_int,
+    user_data: *mut c_void,
+) -> c_int {
+    abort_on_panic(|| {
+        let widgets = unsafe { &mut *(user_data as *mut Vec<Widget>) };
+
+        widgets.push(Widget {
+            package_id: unsafe { borrow_str(package_id) }
+                .unwrap_or_default()
+                .into_owned(),
+            widget_id: unsafe { borrow_str(widget_id) }
+                .unwrap_or_default()
+                .into_owned(),
+            is_prime: is_prime != 0,
+        });
+
+        0
+    })
+}
+
+extern "C" fn package_widget_handler(
+    widget_id: *const c_char,
+    is_prime: c_int,
+    user_data: *mut c_void,
+) -> c_int {
+    abort_on_panic(|| {
+        let widgets = unsafe { &mut *(user_data as *mut Vec<Widget>) };
+
+        widgets.push(Widget {
+            package_id: String::new(),
+            widget_id: unsafe { borrow_str(widget_id) }
+                .unwrap_or_default()
+                .into_owned(),
+            is_prime: is_prime != 0,
+        });
+
+        0
+    })
+}
@@ -0,0 +1,135 @@
+//! The event streams and the executor on the simulated main loop.
+
+use futures_core::Stream;
+use rutin_tizen::executor;
+use rutin_tizen::network::connection::{Connection, ConnectionEvent, ConnectionType};
+use rutin_tizen::sensor::{SensorListener, SensorType};
+use rutin_tizen::simulator;
+use rutin_tizen::system::battery::{BatteryEvent, BatteryLevel, BatteryListener};
+use std::cell::RefCell;
+use std::future::poll_fn;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+use std::task::{Poll, Waker};
+use std::thread;
+
+/// Spawns a task collecting the events of `stream` into the returned vector.
+fn collect<S>(mut stream: S) -> Rc<RefCell<Vec<S::Item>>>
+where
+    S: Stream + Unpin + 'static,
+{
+    let events = Rc::new(RefCell::new(Vec::new()));
+    let received = events.clone();
+
+    let _reply = executor::spawn(async move {
+        while let Some(event) = poll_fn(|cx| Pin::new(&mut stream).poll_next(cx)).await {
+            received.borrow_mut().push(event);
+        }
+    });
+
+    events
+}
+
+#[test]
+fn spawned_tasks_run_on_the_main_loop() {
+    let ran = Rc::new(RefCell::new(false));
+    let set = ran.clone();
+    let _reply = executor::spawn(async move { *set.borrow_mut() = true });
+
+    assert!(!*ran.borrow());
+
+    simulator::run_until_idle();
+
+    assert!(*ran.borrow());
+}
+
+#[test]
+fn woken_from_another_thread() {
+    // whether another thread has woken the task, and the waker to wake it with
+    let state = Arc::new(Mutex::new((false, None::<Waker>)));
+    let done = Rc::new(RefCell::new(false));
+
+    let polled = state.clone();
+    let set = done.clone();
+    let _reply = executor::spawn(async move {
+        poll_fn(|cx| {
+            let mut state = polled.lock().unwrap();
+            if state.0 {
+                Poll::Ready(())
+            } else {
+                state.1 = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        })
+        .await;
+
+        *set.borrow_mut() = true;
+    });
+
+    simulator::run_until_idle();
+    assert!(!*done.borrow());
+
+    let woken = state.clone();
+    thread::spawn(move || {
+        let mut state = woken.lock().unwrap();
+        state.0 = true;
+        state.1.take().unwrap().wake();
+    })
+    .join()
+    .unwrap();
+
+    // the poll is queued on the main loop of this thread, not on the waking one's
+    simulator::run_until_idle();
+    assert!(*done.borrow());
+}
+
+#[test]
+fn battery_events() {
+    let events = collect(BatteryListener::events().unwrap());
+
+    simulator::set_battery_charging(true);
+    simulator::set_battery_percent(100).unwrap();
+    simulator::run_until_idle();
+
+    assert_eq!(
+        *events.borrow(),
+        [
+            BatteryEvent::ChargingChanged(true),
+            BatteryEvent::PercentChanged(100),
+            BatteryEvent::LevelChanged(BatteryLevel::Full),
+        ]
+    );
+}
+
+#[test]
+fn sensor_events() {
+    let events = collect(SensorListener::events(SensorType::Light, 100).unwrap());
+
+    simulator::emit_sensor_event(SensorType::Light, &[120.0]);
+    simulator::emit_sensor_event(SensorType::Light, &[80.0]);
+    simulator::run_until_idle();
+
+    let values: Vec<_> = events.borrow().iter().map(|e| e.values.clone()).collect();
+    assert_eq!(values, [[120.0], [80.0]]);
+}
+
+#[test]
+fn connection_events() {
+    let events = collect(Connection::events().unwrap());
+
+    simulator::set_connection_type(ConnectionType::Disconnected);
+    simulator::set_ip_addresses(None, None).unwrap();
+    simulator::run_until_idle();
+
+    assert_eq!(
+        *events.borrow(),
+        [
+            ConnectionEvent::TypeChanged(ConnectionType::Disconnected),
+            ConnectionEvent::IpAddressChanged {
+                ipv4: None,
+                ipv6: None,
+            },
+        ]
+    );
+}
@@ -0,0 +1,137 @@
+//! The connection wrappers on the simulated device.
+
+use rutin_tizen::network::connection::{
+    AddressFamily, CellularState, Connection, ConnectionType, ProxyType, WifiState,
+};
+use rutin_tizen::simulator;
+use rutin_tizen::Error;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+#[test]
+fn default_connection() {
+    let connection = Connection::new().unwrap();
+
+    assert_eq!(connection.get_type(), Ok(ConnectionType::Wifi));
+    assert_eq!(connection.is_online(), Ok(true));
+    assert_eq!(connection.get_wifi_state(), Ok(WifiState::Connected));
+    assert_eq!(
+        connection.get_cellular_state(),
+        Ok(CellularState::OutOfService)
+    );
+    assert_eq!(
+        connection.get_ip_address(AddressFamily::Ipv4).unwrap(),
+        "10.0.2.15"
+    );
+    assert_eq!(
+        connection.get_mac_address(ConnectionType::Wifi).unwrap(),
+        "02:00:00:00:00:01"
+    );
+    assert_eq!(connection.get_proxy(AddressFamily::Ipv4), Ok(None));
+}
+
+#[test]
+fn current_profile() {
+    let connection = Connection::new().unwrap();
+    let profile = connection.get_current_profile().unwrap();
+
+    assert_eq!(profile.get_interface_name().unwrap(), "wlan0");
+    assert_eq!(
+        profile.get_ip_address(AddressFamily::Ipv4),
+        Ok(Some("10.0.2.15".to_string()))
+    );
+    assert_eq!(profile.get_gateway_address(AddressFamily::Ipv4), Ok(None));
+    assert_eq!(profile.get_dns_servers(AddressFamily::Ipv4), Ok(Vec::new()));
+    assert_eq!(profile.get_proxy_type(), Ok(ProxyType::Direct));
+}
+
+#[test]
+fn cellular_profile() {
+    simulator::set_cellular_state(CellularState::Connected);
+    simulator::set_connection_type(ConnectionType::Cellular);
+
+    let connection = Connection::new().unwrap();
+
+    assert_eq!(
+        connection.get_cellular_state(),
+        Ok(CellularState::Connected)
+    );
+    assert_eq!(
+        connection
+            .get_current_profile()
+            .unwrap()
+            .get_interface_name()
+            .unwrap(),
+        "rmnet0"
+    );
+}
+
+#[test]
+fn disconnected() {
+    simulator::set_wifi_state(WifiState::Disconnected);
+    simulator::set_connection_type(ConnectionType::Disconnected);
+
+    let connection = Connection::new().unwrap();
+
+    assert_eq!(connection.is_online(), Ok(false));
+    assert_eq!(connection.get_wifi_state(), Ok(WifiState::Disconnected));
+    assert_eq!(connection.get_current_profile().err(), Some(Error::NoData));
+}
+
+#[test]
+fn proxy() {
+    simulator::set_proxy(ProxyType::Manual, Some("proxy.example.com:8080")).unwrap();
+
+    let connection = Connection::new().unwrap();
+
+    assert_eq!(
+        connection.get_proxy(AddressFamily::Ipv4),
+        Ok(Some("proxy.example.com:8080".to_string()))
+    );
+
+    let profile = connection.get_current_profile().unwrap();
+    assert_eq!(profile.get_proxy_type(), Ok(ProxyType::Manual));
+    assert_eq!(
+        profile.get_proxy_address(AddressFamily::Ipv4),
+        Ok(Some("proxy.example.com:8080".to_string()))
+    );
+}
+
+#[test]
+fn connection_callbacks() {
+    let mut connection = Connection::new().unwrap();
+
+    let types = Rc::new(RefCell::new(Vec::new()));
+    let received = types.clone();
+    connection
+        .set_type_changed_cb(move |connection_type| received.borrow_mut().push(connection_type))
+        .unwrap();
+
+    let addresses = Rc::new(RefCell::new(Vec::new()));
+    let received = addresses.clone();
+    connection
+        .set_ip_address_changed_cb(move |ipv4, ipv6| {
+            received
+                .borrow_mut()
+                .push((ipv4.map(str::to_string), ipv6.map(str::to_string)))
+        })
+        .unwrap();
+
+    simulator::set_connection_type(ConnectionType::Ethernet);
+    // unchanged
+    simulator::set_connection_type(ConnectionType::Ethernet);
+    simulator::set_ip_addresses(Some("192.168.1.2"), Some("fe80::1")).unwrap();
+
+    connection.unset_type_changed_cb().unwrap();
+    simulator::set_connection_type(ConnectionType::Wifi);
+
+    assert_eq!(*types.borrow(), [ConnectionType::Ethernet]);
+    assert_eq!(
+        *addresses.borrow(),
+        [(Some("192.168.1.2".to_string()), Some("fe80::1".to_string()))]
+    );
+    assert_eq!(
+        connection.get_ip_address(AddressFamily::Ipv6).unwrap(),
+        "fe80::1"
+    );
+}
@@ -0,0 +1,97 @@
+//! The sensor wrappers on the simulated device.
+
+use rutin_tizen::sensor::{Sensor, SensorListener, SensorType};
+use rutin_tizen::simulator;
+use rutin_tizen::Error;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+#[test]
+fn sensor_info() {
+    let sensor = Sensor::default_for(SensorType::HeartRate).unwrap();
+
+    assert_eq!(sensor.get_name().unwrap(), "Simulated heart rate monitor");
+    assert_eq!(sensor.get_vendor().unwrap(), "rutin-tizen simulator");
+}
+
+#[test]
+fn unsupported_sensor() {
+    assert_eq!(Sensor::is_supported(SensorType::Pressure), Ok(true));
+
+    simulator::set_sensor_supported(SensorType::Pressure, false);
+
+    assert_eq!(Sensor::is_supported(SensorType::Pressure), Ok(false));
+    assert_eq!(
+        Sensor::default_for(SensorType::Pressure).err(),
+        Some(Error::NotSupported)
+    );
+    assert_eq!(Sensor::is_supported(SensorType::Light), Ok(true));
+}
+
+#[test]
+fn read_started_sensor() {
+    let sensor = Sensor::default_for(SensorType::Accelerometer).unwrap();
+    let listener = SensorListener::new(&sensor).unwrap();
+
+    assert_eq!(listener.read().err(), Some(Error::InvalidOperation));
+
+    let started = listener.start().unwrap();
+    assert_eq!(listener.read().err(), Some(Error::NoData));
+
+    simulator::emit_sensor_event(SensorType::Accelerometer, &[0.0, 9.8, 0.5]);
+    assert_eq!(listener.read().unwrap().values, [0.0, 9.8, 0.5]);
+
+    drop(started);
+    assert_eq!(listener.read().err(), Some(Error::InvalidOperation));
+}
+
+#[test]
+fn sensor_events() {
+    let sensor = Sensor::default_for(SensorType::HeartRate).unwrap();
+    let mut listener = SensorListener::new(&sensor).unwrap();
+
+    let values = Rc::new(RefCell::new(Vec::new()));
+    let received = values.clone();
+    listener
+        .set_event_cb(100, move |event| received.borrow_mut().push(event.values))
+        .unwrap();
+
+    // not started yet
+    simulator::emit_sensor_event(SensorType::HeartRate, &[60.0]);
+
+    {
+        let _started = listener.start().unwrap();
+
+        simulator::emit_sensor_event(SensorType::HeartRate, &[72.0]);
+        simulator::emit_sensor_event(SensorType::Light, &[300.0]);
+        // only the first 16 values are kept
+        simulator::emit_sensor_event(SensorType::HeartRate, &[1.0; 20]);
+    }
+
+    simulator::emit_sensor_event(SensorType::HeartRate, &[80.0]);
+
+    assert_eq!(*values.borrow(), [vec![72.0], vec![1.0; 16]]);
+}
+
+#[test]
+fn unset_sensor_events() {
+    let sensor = Sensor::default_for(SensorType::Light).unwrap();
+    let mut listener = SensorListener::new(&sensor).unwrap();
+
+    let count = Rc::new(RefCell::new(0));
+    let received = count.clone();
+    listener
+        .set_event_cb(100, move |_| *received.borrow_mut() += 1)
+        .unwrap();
+
+    let started = listener.start().unwrap();
+    simulator::emit_sensor_event(SensorType::Light, &[1.0]);
+    drop(started);
+
+    listener.unset_event_cb().unwrap();
+    let _started = listener.start().unwrap();
+    simulator::emit_sensor_event(SensorType::Light, &[2.0]);
+
+    assert_eq!(*count.borrow(), 1);
+    assert_eq!(listener.read().unwrap().values, [2.0]);
+}
@@ -0,0 +1,142 @@
+//! The system info and battery wrappers on the simulated device.
+
+use rutin_tizen::simulator;
+use rutin_tizen::system::battery::{self, BatteryEvent, BatteryLevel, BatteryListener};
+use rutin_tizen::system::info::{
+    get_platform_bool, get_platform_int, get_platform_string, DisplayMetrics, Profile, ScreenShape,
+};
+use rutin_tizen::Error;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+#[test]
+fn default_display() {
+    let metrics = DisplayMetrics::query().unwrap();
+
+    assert_eq!(
+        metrics,
+        DisplayMetrics {
+            width: 360,
+            height: 360,
+            dpi: 302,
+            shape: ScreenShape::Circle,
+            bpp: 32,
+        }
+    );
+    assert_eq!(metrics.diagonal_mm(), metrics.width_mm());
+}
+
+#[test]
+fn rectangular_display() {
+    simulator::set_platform_bool("http://tizen.org/feature/screen.shape.circle", false);
+    simulator::set_platform_int("http://tizen.org/feature/screen.width", 300);
+    simulator::set_platform_int("http://tizen.org/feature/screen.height", 400);
+    simulator::set_platform_int("http://tizen.org/feature/screen.dpi", 254);
+
+    let metrics = DisplayMetrics::query().unwrap();
+
+    assert!(!metrics.is_round());
+    assert!((metrics.width_mm() - 30.0).abs() < 1e-9);
+    assert!((metrics.diagonal_mm() - 50.0).abs() < 1e-9);
+}
+
+#[test]
+fn platform_keys() {
+    simulator::set_platform_string("http://example.com/key", "value").unwrap();
+    assert_eq!(
+        get_platform_string("http://example.com/key").unwrap(),
+        "value"
+    );
+
+    assert_eq!(
+        get_platform_bool("http://example.com/missing"),
+        Err(Error::InvalidParameter)
+    );
+    // the key holds a string
+    assert_eq!(
+        get_platform_int("http://example.com/key"),
+        Err(Error::InvalidParameter)
+    );
+    assert_eq!(
+        simulator::set_platform_string("http://example.com/key", "nul\0"),
+        Err(Error::InvalidParameter)
+    );
+}
+
+#[test]
+fn default_profile() {
+    assert_eq!(Profile::current(), Profile::Wearable);
+    assert!(Profile::Wearable.is_current());
+    assert_eq!(Profile::Wearable.when(|| 1), Some(1));
+    assert_eq!(Profile::Tv.when(|| 1), None);
+}
+
+#[test]
+fn other_profile() {
+    simulator::set_platform_string("http://tizen.org/feature/profile", "tv").unwrap();
+
+    assert_eq!(Profile::current(), Profile::Tv);
+}
+
+#[test]
+fn battery_state() {
+    assert_eq!(battery::get_percent(), Ok(80));
+    assert_eq!(battery::is_charging(), Ok(false));
+    assert_eq!(battery::get_level(), Ok(BatteryLevel::High));
+
+    simulator::set_battery_percent(5).unwrap();
+    simulator::set_battery_charging(true);
+
+    assert_eq!(battery::get_percent(), Ok(5));
+    assert_eq!(battery::is_charging(), Ok(true));
+    assert_eq!(battery::get_level(), Ok(BatteryLevel::Critical));
+
+    assert_eq!(
+        simulator::set_battery_percent(101),
+        Err(Error::InvalidParameter)
+    );
+    assert_eq!(
+        simulator::set_battery_percent(-1),
+        Err(Error::InvalidParameter)
+    );
+    assert_eq!(battery::get_percent(), Ok(5));
+}
+
+#[test]
+fn battery_listener() {
+    let events = Rc::new(RefCell::new(Vec::new()));
+    let received = events.clone();
+    let listener = BatteryListener::new(move |event| received.borrow_mut().push(event)).unwrap();
+
+    simulator::set_battery_percent(60).unwrap();
+    simulator::set_battery_percent(10).unwrap();
+    simulator::set_battery_charging(true);
+    // unchanged
+    simulator::set_battery_charging(true);
+
+    drop(listener);
+    simulator::set_battery_percent(100).unwrap();
+
+    assert_eq!(
+        *events.borrow(),
+        [
+            BatteryEvent::PercentChanged(60),
+            BatteryEvent::PercentChanged(10),
+            BatteryEvent::LevelChanged(BatteryLevel::Low),
+            BatteryEvent::ChargingChanged(true),
+        ]
+    );
+}
+
+#[test]
+fn single_battery_listener() {
+    let listener = BatteryListener::new(|_| {}).unwrap();
+
+    assert_eq!(
+        BatteryListener::new(|_| {}).err(),
+        Some(Error::InvalidOperation)
+    );
+
+    drop(listener);
+    assert!(BatteryListener::new(|_| {}).is_ok());
+}